@@ -0,0 +1,50 @@
+use std::time::Instant;
+
+use canzero_config::builder::NetworkBuilder;
+
+/// Times `build()` for a network with many messages.
+///
+/// Usage: `cargo run --release --example build_benchmark -- [messages] [runs]`
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let message_count: u32 = args.next().map(|n| n.parse().unwrap()).unwrap_or(4000);
+    let runs: u32 = args.next().map(|n| n.parse().unwrap()).unwrap_or(5);
+
+    let mut total = 0.0;
+    for _ in 0..runs {
+        let network_builder = create_network(message_count);
+        let start = Instant::now();
+        network_builder.build().unwrap();
+        let elapsed = start.elapsed().as_secs_f64();
+        println!("build() with {message_count} messages took {:.3}s", elapsed);
+        total += elapsed;
+    }
+    println!("mean over {runs} runs: {:.3}s", total / runs as f64);
+}
+
+fn create_network(message_count: u32) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let status = network_builder.define_struct("status");
+    status.add_attribute("state", "state").unwrap();
+    status.add_attribute("temperature", "d10<-40..1.5e2>").unwrap();
+
+    let secu = network_builder.create_node("secu");
+    let master = network_builder.create_node("master");
+    let _ = (&secu, &master);
+    for i in 0..message_count {
+        let message = network_builder.create_message(&format!("message_{i}"), None);
+        message.set_ext_id(0x1000 + i);
+        let format = message.make_type_format();
+        format.add_type("u8", "counter");
+        format.add_type("d16<0..1e3>", "pressure");
+        format.add_type("status", "status");
+        format.add_type("i12", "offset");
+        message.add_transmitter("secu");
+        message.add_receiver("master");
+    }
+    network_builder
+}
@@ -0,0 +1,17 @@
+//! Decodes a candump log from stdin with the network of simple.rs, e.g.
+//! `candump -L can0 | cargo run --example candump_decoder --features tools`.
+use std::io;
+
+use canzero_config::{builder::NetworkBuilder, tools::CandumpDecoder};
+
+fn main() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("X", "u8");
+    secu.create_stream("xyz").add_entry("X");
+    let network = network_builder.build().unwrap();
+
+    let decoder = CandumpDecoder::new(&network);
+    decoder.decode_log(io::stdin().lock(), io::stdout().lock()).unwrap();
+}
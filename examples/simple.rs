@@ -1,10 +1,10 @@
-use can_config_rs::builder::MessagePriority;
+use canzero_config::builder::MessagePriority;
 
 
-extern crate can_config_rs;
+extern crate canzero_config;
 
 fn main() {
-    let network_builder = can_config_rs::builder::NetworkBuilder::new();
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
     network_builder.create_bus("can0", Some(1000000));
     let secu = network_builder.create_node("secu");
     let xyz = secu.create_stream("xyz");
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use crate::config::{latency_path::{LatencyHop, LatencyPathRef}, Network};
+
+use super::{response_time::response_times, MessageResponseTime, ResponseTimeOptions};
+
+/// Contribution of a single hop to the end-to-end latency of a path.
+#[derive(Debug, Clone)]
+pub struct HopLatency {
+    name: String,
+    interval: Duration,
+    response_time: Option<Duration>,
+}
+
+impl HopLatency {
+    /// node::element of the hop.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Time until the value is transmitted, the max interval of streams
+    /// and zero for object entries and commands.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+    /// Worst case response time of the message of the hop.
+    /// None if the bus is overloaded.
+    pub fn response_time(&self) -> Option<Duration> {
+        self.response_time
+    }
+    pub fn worst_case(&self) -> Option<Duration> {
+        self.response_time.map(|r| self.interval + r)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PathLatency {
+    path: LatencyPathRef,
+    hops: Vec<HopLatency>,
+}
+
+impl PathLatency {
+    pub fn path(&self) -> &LatencyPathRef {
+        &self.path
+    }
+    pub fn hops(&self) -> &Vec<HopLatency> {
+        &self.hops
+    }
+    /// Sum of the worst case latencies of all hops.
+    /// None if a message of the path has no bounded response time.
+    pub fn worst_case(&self) -> Option<Duration> {
+        self.hops.iter().map(HopLatency::worst_case).sum()
+    }
+    /// true if the path may exceed its latency budget.
+    pub fn is_violated(&self) -> bool {
+        self.worst_case().is_none_or(|w| w > self.path.budget())
+    }
+}
+
+fn response_time_of(results: &[MessageResponseTime], message_name: &str) -> Option<Duration> {
+    results
+        .iter()
+        .find(|r| r.message().name() == message_name)
+        .and_then(MessageResponseTime::worst_case)
+}
+
+/// End-to-end latencies of the annotated data paths. A stream contributes
+/// its max interval (the value may change right after a transmission) and the
+/// worst case response time of its message, a command the worst case response
+/// time of its call message.
+pub fn path_latencies(network: &Network, options: &ResponseTimeOptions) -> Vec<PathLatency> {
+    let results = response_times(network, options);
+    network
+        .latency_paths()
+        .iter()
+        .map(|path| {
+            let hops = path
+                .hops()
+                .iter()
+                .map(|hop| {
+                    let (interval, response_time) = match hop {
+                        LatencyHop::ObjectEntry { .. } => (Duration::ZERO, Some(Duration::ZERO)),
+                        LatencyHop::Stream { stream, .. } => (
+                            *stream.max_interval(),
+                            response_time_of(&results, stream.message().name()),
+                        ),
+                        LatencyHop::Command { command, .. } => (
+                            Duration::ZERO,
+                            response_time_of(&results, command.tx_message().name()),
+                        ),
+                    };
+                    HopLatency {
+                        name: hop.name(),
+                        interval,
+                        response_time,
+                    }
+                })
+                .collect();
+            PathLatency {
+                path: path.clone(),
+                hops,
+            }
+        })
+        .collect()
+}
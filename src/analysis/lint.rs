@@ -0,0 +1,481 @@
+use std::{collections::HashSet, fmt::Display, time::Duration};
+
+use crate::config::{
+    encoding::TypeSignalEncoding,
+    visitor::{walk_encoding, walk_type, NetworkVisitor},
+    Lifecycle, Network, ObjectEntryAccess, Type, Version,
+};
+
+use super::{od_throughput, OdThroughputOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    /// type that is neither used by an object entry nor by a message.
+    UnusedType,
+    /// message that isn't received by any node.
+    UnreceivedMessage,
+    /// non constant object entry, which isn't mapped to any stream of its node.
+    UnmappedObjectEntry,
+    /// signal, which isn't byte aligned and doesn't fit into a single byte.
+    UnalignedSignal,
+    /// enum, whose values are not contiguous.
+    EnumHoles,
+    /// message, signal or object entry used by a node, whose firmware
+    /// targets a version in which the element wasn't introduced yet.
+    UnavailableElement,
+    /// message, signal or object entry used by a node, whose firmware
+    /// targets a version in which the element is deprecated.
+    DeprecatedElement,
+    /// rx timeout, which doesn't exceed the interval of the message with margin.
+    ShortRxTimeout,
+    /// message received by a node, which uses none of its signals.
+    UnusedRxMessage,
+    /// node, whose object dictionary can't be read within the configured
+    /// snapshot time (see `LintOptions::set_max_od_snapshot_time`).
+    SlowOdSnapshot,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 10] = [
+        LintRule::UnusedType,
+        LintRule::UnreceivedMessage,
+        LintRule::UnmappedObjectEntry,
+        LintRule::UnalignedSignal,
+        LintRule::EnumHoles,
+        LintRule::UnavailableElement,
+        LintRule::DeprecatedElement,
+        LintRule::ShortRxTimeout,
+        LintRule::UnusedRxMessage,
+        LintRule::SlowOdSnapshot,
+    ];
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::UnusedType => "unused_type",
+            LintRule::UnreceivedMessage => "unreceived_message",
+            LintRule::UnmappedObjectEntry => "unmapped_object_entry",
+            LintRule::UnalignedSignal => "unaligned_signal",
+            LintRule::EnumHoles => "enum_holes",
+            LintRule::UnavailableElement => "unavailable_element",
+            LintRule::DeprecatedElement => "deprecated_element",
+            LintRule::ShortRxTimeout => "short_rx_timeout",
+            LintRule::UnusedRxMessage => "unused_rx_message",
+            LintRule::SlowOdSnapshot => "slow_od_snapshot",
+        }
+    }
+}
+
+impl Display for LintRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// the rule isn't checked.
+    Allow,
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Allow => write!(f, "allow"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Severity of every rule, all rules are warnings by default.
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    severities: Vec<(LintRule, Severity)>,
+    /// firmware version of nodes, which don't declare their own.
+    target_version: Option<Version>,
+    /// factor by which rx timeouts have to exceed the expected interval.
+    rx_timeout_margin: f64,
+    /// maximum time to read the object dictionary of a node, unchecked if None.
+    max_od_snapshot_time: Option<Duration>,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            severities: LintRule::ALL
+                .iter()
+                .map(|rule| (*rule, Severity::Warning))
+                .collect(),
+            target_version: None,
+            rx_timeout_margin: 1.5,
+            max_od_snapshot_time: None,
+        }
+    }
+}
+
+impl LintOptions {
+    pub fn severity(&self, rule: LintRule) -> Severity {
+        self.severities
+            .iter()
+            .find(|(r, _)| *r == rule)
+            .map(|(_, severity)| *severity)
+            .unwrap_or(Severity::Warning)
+    }
+    pub fn set_severity(&mut self, rule: LintRule, severity: Severity) -> &mut Self {
+        match self.severities.iter_mut().find(|(r, _)| *r == rule) {
+            Some((_, s)) => *s = severity,
+            None => self.severities.push((rule, severity)),
+        }
+        self
+    }
+    pub fn target_version(&self) -> Option<&Version> {
+        self.target_version.as_ref()
+    }
+    /// Checks the lifecycle of the elements used by nodes without a
+    /// firmware version against `version`.
+    pub fn set_target_version(&mut self, version: Version) -> &mut Self {
+        self.target_version = Some(version);
+        self
+    }
+    pub fn rx_timeout_margin(&self) -> f64 {
+        self.rx_timeout_margin
+    }
+    /// Rx timeouts shorter than `margin` times the expected interval of
+    /// the message are reported (1.5 by default).
+    pub fn set_rx_timeout_margin(&mut self, margin: f64) -> &mut Self {
+        self.rx_timeout_margin = margin;
+        self
+    }
+    pub fn max_od_snapshot_time(&self) -> Option<Duration> {
+        self.max_od_snapshot_time
+    }
+    /// Reports nodes, whose object dictionary takes longer than `limit` to
+    /// read with the default `OdThroughputOptions` (unchecked by default).
+    pub fn set_max_od_snapshot_time(&mut self, limit: Duration) -> &mut Self {
+        self.max_od_snapshot_time = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintDiagnostic {
+    rule: LintRule,
+    severity: Severity,
+    subject: String,
+    message: String,
+}
+
+impl LintDiagnostic {
+    pub fn rule(&self) -> LintRule {
+        self.rule
+    }
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    /// name of the offending element (e.g. `node::object_entry`).
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for LintDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}: {}",
+            self.severity, self.rule, self.subject, self.message
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LintReport {
+    diagnostics: Vec<LintDiagnostic>,
+}
+
+impl LintReport {
+    pub fn diagnostics(&self) -> &Vec<LintDiagnostic> {
+        &self.diagnostics
+    }
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+    pub fn is_clean(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+    /// Report as a json array of {rule, severity, subject, message} objects.
+    pub fn to_json(&self) -> String {
+        fn escape(s: &str) -> String {
+            let mut escaped = String::with_capacity(s.len());
+            for c in s.chars() {
+                match c {
+                    '"' => escaped.push_str("\\\""),
+                    '\\' => escaped.push_str("\\\\"),
+                    '\n' => escaped.push_str("\\n"),
+                    c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => escaped.push(c),
+                }
+            }
+            escaped
+        }
+        let entries: Vec<String> = self
+            .diagnostics
+            .iter()
+            .map(|d| {
+                format!(
+                    "{{\"rule\":\"{}\",\"severity\":\"{}\",\"subject\":\"{}\",\"message\":\"{}\"}}",
+                    d.rule,
+                    d.severity,
+                    escape(&d.subject),
+                    escape(&d.message)
+                )
+            })
+            .collect();
+        format!("[{}]", entries.join(","))
+    }
+}
+
+impl Display for LintReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the names of all types used by object entries and messages.
+#[derive(Default)]
+struct UsedTypes(HashSet<String>);
+
+impl NetworkVisitor for UsedTypes {
+    fn visit_encoding(&mut self, encoding: &TypeSignalEncoding) {
+        self.visit_type(encoding.ty());
+        walk_encoding(self, encoding);
+    }
+    fn visit_type(&mut self, ty: &Type) {
+        self.0.insert(ty.name());
+        walk_type(self, ty);
+    }
+}
+
+/// Names of all types used by object entries and messages, including
+/// the types nested in them.
+pub(crate) fn used_types(network: &Network) -> HashSet<String> {
+    let mut used_types = UsedTypes::default();
+    for node in network.nodes() {
+        used_types.visit_node(node);
+    }
+    for message in network.messages() {
+        used_types.visit_message(message);
+    }
+    used_types.0
+}
+
+pub fn lint(network: &Network, options: &LintOptions) -> LintReport {
+    let mut report = LintReport::default();
+    let mut emit = |rule: LintRule, subject: String, message: String| {
+        let severity = options.severity(rule);
+        if severity != Severity::Allow {
+            report.diagnostics.push(LintDiagnostic {
+                rule,
+                severity,
+                subject,
+                message,
+            });
+        }
+    };
+
+    let used_types = used_types(network);
+    for ty in network.types() {
+        if matches!(ty as &Type, Type::Struct { .. } | Type::Enum { .. })
+            && !used_types.contains(&ty.name())
+        {
+            emit(
+                LintRule::UnusedType,
+                ty.name(),
+                "type is never used".to_owned(),
+            );
+        }
+        if let Type::Enum { name, entries, .. } = ty as &Type {
+            let mut values: Vec<u64> = entries.iter().map(|(_, value)| *value).collect();
+            values.sort();
+            let contiguous = values
+                .iter()
+                .enumerate()
+                .all(|(i, value)| *value == values[0] + i as u64);
+            if !contiguous {
+                emit(
+                    LintRule::EnumHoles,
+                    name.clone(),
+                    format!("enum values {values:?} are not contiguous"),
+                );
+            }
+        }
+    }
+
+    for message in network.messages() {
+        if !network
+            .nodes()
+            .iter()
+            .any(|node| node.rx_messages().iter().any(|m| m.name() == message.name()))
+        {
+            emit(
+                LintRule::UnreceivedMessage,
+                message.name().to_owned(),
+                "message isn't received by any node".to_owned(),
+            );
+        }
+        // reserved regions aren't accessed.
+        for signal in message.signals().iter().filter(|signal| !signal.is_reserved()) {
+            // byte_offset is the offset in bits.
+            let bit_offset = signal.byte_offset();
+            let first_byte = bit_offset / 8;
+            let last_byte = (bit_offset + signal.size() as usize - 1) / 8;
+            let aligned = bit_offset % 8 == 0 && signal.size() % 8 == 0;
+            if !aligned && first_byte != last_byte {
+                emit(
+                    LintRule::UnalignedSignal,
+                    format!("{}::{}", message.name(), signal.name()),
+                    format!(
+                        "signal at bit {bit_offset} with {} bits crosses a byte boundary",
+                        signal.size()
+                    ),
+                );
+            }
+        }
+    }
+
+    for node in network.nodes() {
+        for object_entry in node.object_entries() {
+            if matches!(object_entry.access(), ObjectEntryAccess::Const) {
+                continue;
+            }
+            let mapped = node.tx_streams().iter().any(|stream| {
+                stream
+                    .mapping()
+                    .iter()
+                    .flatten()
+                    .any(|mapping| mapping.name() == object_entry.name())
+            });
+            if !mapped {
+                emit(
+                    LintRule::UnmappedObjectEntry,
+                    format!("{}::{}", node.name(), object_entry.name()),
+                    "object entry isn't mapped to any stream".to_owned(),
+                );
+            }
+        }
+    }
+
+    for node in network.nodes() {
+        for message in node.rx_messages() {
+            if message.signals().is_empty()
+                || message.signals().iter().any(|signal| signal.is_received_by(node.name()))
+            {
+                continue;
+            }
+            emit(
+                LintRule::UnusedRxMessage,
+                format!("{}::{}", node.name(), message.name()),
+                "node receives the message, but uses none of its signals".to_owned(),
+            );
+        }
+    }
+
+    for node in network.nodes() {
+        for (message, timeout) in node.rx_timeouts() {
+            let subject = format!("{}::{}", node.name(), message.name());
+            match message.expected_interval() {
+                None => emit(
+                    LintRule::ShortRxTimeout,
+                    subject,
+                    "message is only transmitted on demand, its timeout can't be supervised"
+                        .to_owned(),
+                ),
+                Some(interval) if *timeout < interval.mul_f64(options.rx_timeout_margin) => emit(
+                    LintRule::ShortRxTimeout,
+                    subject,
+                    format!(
+                        "timeout {timeout:?} doesn't exceed the interval {interval:?} of the message by a factor of {}",
+                        options.rx_timeout_margin
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+
+    if let Some(limit) = options.max_od_snapshot_time {
+        for snapshot in od_throughput::od_snapshot_times(network, &OdThroughputOptions::default()) {
+            if snapshot.duration() > limit {
+                emit(
+                    LintRule::SlowOdSnapshot,
+                    snapshot.node().name().to_owned(),
+                    format!(
+                        "reading the {} object entries takes {:?}, which exceeds {limit:?}",
+                        snapshot.entries().len(),
+                        snapshot.duration()
+                    ),
+                );
+            }
+        }
+    }
+
+    for node in network.nodes() {
+        let Some(version) = node.firmware_version().or(options.target_version()) else {
+            continue;
+        };
+        let mut check = |subject: String, kind: &str, lifecycle: &Lifecycle| {
+            if !lifecycle.is_available_in(version) {
+                emit(
+                    LintRule::UnavailableElement,
+                    subject,
+                    format!(
+                        "{kind} is introduced in {}, but {} targets {version}",
+                        lifecycle.introduced_in().unwrap(),
+                        node.name()
+                    ),
+                );
+            } else if lifecycle.is_deprecated_in(version) {
+                emit(
+                    LintRule::DeprecatedElement,
+                    subject,
+                    format!(
+                        "{kind} is deprecated in {}, but {} targets {version}",
+                        lifecycle.deprecated_in().unwrap(),
+                        node.name()
+                    ),
+                );
+            }
+        };
+        for object_entry in node.object_entries() {
+            check(
+                format!("{}::{}", node.name(), object_entry.name()),
+                "object entry",
+                object_entry.lifecycle(),
+            );
+        }
+        for message in node.tx_messages().iter().chain(node.rx_messages()) {
+            check(
+                format!("{}::{}", node.name(), message.name()),
+                "message",
+                message.lifecycle(),
+            );
+            for signal in message.signals() {
+                check(
+                    format!("{}::{}::{}", node.name(), message.name(), signal.name()),
+                    "signal",
+                    signal.lifecycle(),
+                );
+            }
+        }
+    }
+    report
+}
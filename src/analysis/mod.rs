@@ -0,0 +1,22 @@
+pub use self::latency::HopLatency;
+pub use self::latency::PathLatency;
+pub use self::lint::LintDiagnostic;
+pub use self::lint::LintOptions;
+pub use self::lint::LintReport;
+pub use self::lint::LintRule;
+pub use self::lint::Severity;
+pub use self::od_throughput::OdReadTime;
+pub use self::od_throughput::OdSnapshotTime;
+pub use self::od_throughput::OdThroughputOptions;
+pub use self::response_time::MessageResponseTime;
+pub use self::response_time::ResponseTimeOptions;
+pub use self::stats::BusStats;
+pub use self::stats::Distribution;
+pub use self::stats::IdRangeUtilization;
+pub use self::stats::NetworkStats;
+
+pub mod latency;
+pub mod lint;
+pub mod od_throughput;
+pub mod response_time;
+pub mod stats;
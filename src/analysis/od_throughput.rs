@@ -0,0 +1,138 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::config::{AccessPlan, Message, Network, NodeRef, ObjectEntryRef};
+
+use super::response_time::frame_bits;
+
+/// Assumptions about the object dictionary server of the nodes.
+#[derive(Debug, Clone)]
+pub struct OdThroughputOptions {
+    /// minimum time between two get_resp frames of a server, which limits
+    /// the rate at which the firmware answers requests.
+    pub get_resp_interval: Duration,
+    /// time between receiving a get_req and queuing the first get_resp frame.
+    pub server_delay: Duration,
+}
+
+impl Default for OdThroughputOptions {
+    fn default() -> Self {
+        Self {
+            get_resp_interval: Duration::from_millis(10),
+            server_delay: Duration::ZERO,
+        }
+    }
+}
+
+/// Time to read a single object entry with a get request.
+#[derive(Debug, Clone)]
+pub struct OdReadTime {
+    object_entry: ObjectEntryRef,
+    frames: usize,
+    duration: Duration,
+}
+
+impl OdReadTime {
+    pub fn object_entry(&self) -> &ObjectEntryRef {
+        &self.object_entry
+    }
+    /// number of get_resp frames (fragments) of the response.
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+    /// time between queuing the get_req and receiving the last fragment.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// Time to read every object entry of a node (a snapshot of its dictionary),
+/// assuming the client requests one entry after another.
+#[derive(Debug, Clone)]
+pub struct OdSnapshotTime {
+    node: NodeRef,
+    entries: Vec<OdReadTime>,
+}
+
+impl OdSnapshotTime {
+    pub fn node(&self) -> &NodeRef {
+        &self.node
+    }
+    pub fn entries(&self) -> &Vec<OdReadTime> {
+        &self.entries
+    }
+    pub fn frames(&self) -> usize {
+        self.entries.iter().map(|entry| entry.frames).sum()
+    }
+    pub fn duration(&self) -> Duration {
+        self.entries.iter().map(|entry| entry.duration).sum()
+    }
+    /// bits of object entry values read per second during the snapshot.
+    pub fn throughput(&self) -> f64 {
+        let bits: u64 = self
+            .entries
+            .iter()
+            .map(|entry| entry.object_entry.ty().size() as u64)
+            .sum();
+        match self.duration().as_secs_f64() {
+            secs if secs > 0.0 => bits as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Display for OdSnapshotTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{}: {} object entries, {} frames, {:?} ({:.0} bit/s)",
+            self.node.name(),
+            self.entries.len(),
+            self.frames(),
+            self.duration(),
+            self.throughput()
+        )?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "  {}: {} frames, {:?}",
+                entry.object_entry.name(),
+                entry.frames,
+                entry.duration
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Worst case time a frame of the message occupies its bus.
+fn transmission_time(message: &Message) -> Duration {
+    Duration::from_secs_f64(frame_bits(message) as f64 / message.bus().baudrate() as f64)
+}
+
+/// Time to read every object entry of every node. Each get_resp fragment
+/// occupies the bus for at least the get_resp interval of the server,
+/// arbitration delays caused by other messages are not considered.
+pub fn od_snapshot_times(network: &Network, options: &OdThroughputOptions) -> Vec<OdSnapshotTime> {
+    let get_req = transmission_time(network.get_req_message());
+    let get_resp = transmission_time(network.get_resp_message()).max(options.get_resp_interval);
+    network
+        .nodes()
+        .iter()
+        .map(|node| OdSnapshotTime {
+            node: node.clone(),
+            entries: node
+                .object_entries()
+                .iter()
+                .map(|object_entry| {
+                    // the get_req is followed by the fragments of the response.
+                    let frames = AccessPlan::new(object_entry, node.id()).read().len() - 1;
+                    OdReadTime {
+                        object_entry: object_entry.clone(),
+                        frames,
+                        duration: get_req + options.server_delay + get_resp * frames as u32,
+                    }
+                })
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use crate::config::{message::{MessageUsage, HEARTBEAT_INTERVAL}, Message, MessageId, MessageRef, Network};
+
+/// Assumptions for messages, which are not transmitted periodically.
+#[derive(Debug, Clone)]
+pub struct ResponseTimeOptions {
+    /// minimum inter-arrival time of sporadic messages (get/set requests and
+    /// responses), which do not define an interval.
+    pub sporadic_interval: Duration,
+    /// queuing jitter applied to all messages.
+    pub jitter: Duration,
+}
+
+impl Default for ResponseTimeOptions {
+    fn default() -> Self {
+        Self {
+            sporadic_interval: Duration::from_millis(10),
+            jitter: Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageResponseTime {
+    message: MessageRef,
+    period: Duration,
+    transmission_time: Duration,
+    worst_case: Option<Duration>,
+}
+
+impl MessageResponseTime {
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    /// minimum inter-arrival time assumed for the message.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+    /// worst case time a single frame occupies the bus (including bit stuffing).
+    pub fn transmission_time(&self) -> Duration {
+        self.transmission_time
+    }
+    /// worst case latency between queuing the message and the completed
+    /// transmission. None if the bus is overloaded.
+    pub fn worst_case(&self) -> Option<Duration> {
+        self.worst_case
+    }
+    /// true if the message is always transmitted before it is queued again.
+    pub fn is_schedulable(&self) -> bool {
+        self.worst_case.is_some_and(|r| r <= self.period)
+    }
+}
+
+/// Arbitration key, lower values win the arbitration.
+/// Standard ids win against extended ids with the same base id.
+fn arbitration_key(id: &MessageId) -> u64 {
+    match id {
+        MessageId::StandardId(id) => (*id as u64) << 20,
+        MessageId::ExtendedId(id) => {
+            // base id, recessive SRR and IDE bits, id extension.
+            ((*id as u64 >> 18) << 20) | (0b11 << 18) | (*id as u64 & 0x3FFFF)
+        }
+    }
+}
+
+fn period(message: &Message, options: &ResponseTimeOptions) -> Duration {
+    match message.usage() {
+        // the owners are alive while the network is borrowed, dropped owners
+        // are treated like sporadic messages.
+        MessageUsage::Stream(stream) => stream
+            .upgrade()
+            .map_or(options.sporadic_interval, |stream| *stream.min_interval()),
+        MessageUsage::CommandReq(command) | MessageUsage::CommandResp(command) => command
+            .upgrade()
+            .map_or(options.sporadic_interval, |command| *command.expected_interval()),
+        MessageUsage::External { interval } => *interval,
+        MessageUsage::Heartbeat => HEARTBEAT_INTERVAL,
+        MessageUsage::Debug { interval } => interval.unwrap_or(options.sporadic_interval),
+        // consecutive frames are separated by at least STmin.
+        MessageUsage::TransportData(channel) => {
+            match channel.upgrade().map(|channel| *channel.st_min()) {
+                Some(st_min) if !st_min.is_zero() => st_min,
+                _ => options.sporadic_interval,
+            }
+        }
+        MessageUsage::TransportFlowControl(_)
+        | MessageUsage::Emergency
+        | MessageUsage::Bootloader
+        | MessageUsage::GetResp
+        | MessageUsage::GetReq
+        | MessageUsage::SetResp
+        | MessageUsage::SetReq => options.sporadic_interval,
+    }
+}
+
+/// Worst case number of bits of a frame including stuff bits
+/// (Davis et al. 2007, eq. 1).
+pub(crate) fn frame_bits(message: &Message) -> u64 {
+    let g: u64 = if message.id().ide() { 54 } else { 34 };
+    let data_bits = 8 * message.dlc() as u64;
+    g + data_bits + 13 + (g + data_bits - 1) / 4
+}
+
+struct Task {
+    key: u64,
+    c: f64,
+    t: f64,
+    j: f64,
+}
+
+fn ceil_div(a: f64, b: f64) -> f64 {
+    (a / b).ceil().max(0.0)
+}
+
+/// Worst case response time of task `m` (Davis et al. 2007, section 5).
+fn response_time(m: usize, tasks: &[Task], tau_bit: f64) -> Option<f64> {
+    let task = &tasks[m];
+    let hp = || tasks.iter().filter(|k| k.key < task.key);
+    let hep = || tasks.iter().filter(|k| k.key <= task.key);
+    let utilization: f64 = hep().map(|k| k.c / k.t).sum();
+    if utilization >= 1.0 {
+        return None;
+    }
+    let blocking = tasks
+        .iter()
+        .filter(|k| k.key > task.key)
+        .map(|k| k.c)
+        .fold(task.c, f64::max);
+
+    // length of the priority level-m busy period.
+    let mut busy = task.c;
+    loop {
+        let next = blocking + hep().map(|k| ceil_div(busy + k.j, k.t) * k.c).sum::<f64>();
+        if next <= busy {
+            break;
+        }
+        busy = next;
+    }
+    let instances = ceil_div(busy + task.j, task.t).max(1.0) as u64;
+
+    let mut worst_case: f64 = 0.0;
+    for q in 0..instances {
+        let q = q as f64;
+        let mut w = blocking + q * task.c;
+        loop {
+            let next = blocking
+                + q * task.c
+                + hp()
+                    .map(|k| ceil_div(w + k.j + tau_bit, k.t) * k.c)
+                    .sum::<f64>();
+            if next <= w {
+                break;
+            }
+            w = next;
+        }
+        worst_case = worst_case.max(task.j + w - q * task.t + task.c);
+    }
+    Some(worst_case)
+}
+
+/// Classical CAN schedulability analysis (Davis, Burns, Bril, Lukkien:
+/// "Controller Area Network (CAN) schedulability analysis: Refuted, revisited
+/// and revised", 2007) over the assigned ids, dlcs and intervals of all messages.
+/// Each bus is analyzed independently.
+pub fn response_times(network: &Network, options: &ResponseTimeOptions) -> Vec<MessageResponseTime> {
+    let mut results = vec![];
+    for bus in network.buses() {
+        let tau_bit = 1.0 / bus.baudrate() as f64;
+        let messages: Vec<&MessageRef> = network
+            .messages()
+            .iter()
+            .filter(|m| m.bus().id() == bus.id())
+            .collect();
+        let tasks: Vec<Task> = messages
+            .iter()
+            .map(|m| Task {
+                key: arbitration_key(m.id()),
+                c: frame_bits(m) as f64 * tau_bit,
+                t: period(m, options).as_secs_f64(),
+                j: options.jitter.as_secs_f64(),
+            })
+            .collect();
+        for (i, message) in messages.iter().enumerate() {
+            results.push(MessageResponseTime {
+                message: (*message).clone(),
+                period: Duration::from_secs_f64(tasks[i].t),
+                transmission_time: Duration::from_secs_f64(tasks[i].c),
+                worst_case: response_time(i, &tasks, tau_bit).map(Duration::from_secs_f64),
+            });
+        }
+    }
+    results
+}
@@ -0,0 +1,211 @@
+use crate::{
+    builder::MessagePriority,
+    config::{MessageId, Network},
+};
+
+const STD_ID_COUNT: u32 = 0x800;
+const EXT_ID_COUNT: u32 = 0x2000_0000;
+
+/// Catalogue statistics of a network, see `Network::stats`.
+#[derive(Debug, Clone)]
+pub struct NetworkStats {
+    message_count: usize,
+    signal_count: usize,
+    buses: Vec<BusStats>,
+    dlc_histogram: [usize; 9],
+    signals_per_message: Distribution,
+    object_entries_per_node: Vec<(String, usize)>,
+    id_utilization: Vec<IdRangeUtilization>,
+}
+
+impl NetworkStats {
+    pub fn message_count(&self) -> usize {
+        self.message_count
+    }
+    pub fn signal_count(&self) -> usize {
+        self.signal_count
+    }
+    /// Message and signal counts of every bus in the order of `Network::buses`.
+    pub fn buses(&self) -> &Vec<BusStats> {
+        &self.buses
+    }
+    /// Number of messages for every dlc, indexed by the dlc (0..=8).
+    pub fn dlc_histogram(&self) -> &[usize; 9] {
+        &self.dlc_histogram
+    }
+    pub fn signals_per_message(&self) -> &Distribution {
+        &self.signals_per_message
+    }
+    /// Number of object entries of every node in the order of `Network::nodes`.
+    pub fn object_entries_per_node(&self) -> &Vec<(String, usize)> {
+        &self.object_entries_per_node
+    }
+    /// Used ids of every priority range, standard ranges before extended ranges.
+    pub fn id_utilization(&self) -> &Vec<IdRangeUtilization> {
+        &self.id_utilization
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BusStats {
+    bus: String,
+    messages: usize,
+    signals: usize,
+}
+
+impl BusStats {
+    pub fn bus(&self) -> &str {
+        &self.bus
+    }
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+    pub fn signals(&self) -> usize {
+        self.signals
+    }
+}
+
+/// Summary of a distribution of counts, all zero if there are no samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Distribution {
+    min: usize,
+    max: usize,
+    mean: f64,
+    median: f64,
+}
+
+impl Distribution {
+    fn from_samples(mut samples: Vec<usize>) -> Self {
+        if samples.is_empty() {
+            return Self { min: 0, max: 0, mean: 0.0, median: 0.0 };
+        }
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) as f64 / 2.0
+        } else {
+            samples[n / 2] as f64
+        };
+        Self {
+            min: samples[0],
+            max: samples[n - 1],
+            mean: samples.iter().sum::<usize>() as f64 / n as f64,
+            median,
+        }
+    }
+    pub fn min(&self) -> usize {
+        self.min
+    }
+    pub fn max(&self) -> usize {
+        self.max
+    }
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+    pub fn median(&self) -> f64 {
+        self.median
+    }
+}
+
+/// Ids used within the range of a priority. The id space of each
+/// format is split into `MessagePriority::count()` equally sized ranges,
+/// lower ids have the higher priority.
+#[derive(Debug, Clone)]
+pub struct IdRangeUtilization {
+    priority: MessagePriority,
+    ide: bool,
+    first: u32,
+    last: u32,
+    used: usize,
+}
+
+impl IdRangeUtilization {
+    pub fn priority(&self) -> MessagePriority {
+        self.priority
+    }
+    /// true for the extended id space.
+    pub fn ide(&self) -> bool {
+        self.ide
+    }
+    /// first id of the range (inclusive).
+    pub fn first(&self) -> u32 {
+        self.first
+    }
+    /// last id of the range (inclusive).
+    pub fn last(&self) -> u32 {
+        self.last
+    }
+    /// number of distinct ids within the range, which are used on any bus.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+    /// fraction of the ids within the range, which are used.
+    pub fn utilization(&self) -> f64 {
+        self.used as f64 / (self.last - self.first + 1) as f64
+    }
+}
+
+fn id_ranges(network: &Network, ide: bool) -> Vec<IdRangeUtilization> {
+    let id_count = if ide { EXT_ID_COUNT } else { STD_ID_COUNT };
+    let mut ids: Vec<u32> = network
+        .messages()
+        .iter()
+        .filter_map(|message| match (message.id(), ide) {
+            (MessageId::StandardId(id), false) | (MessageId::ExtendedId(id), true) => Some(*id),
+            _ => None,
+        })
+        .collect();
+    ids.sort_unstable();
+    ids.dedup();
+    let range_size = id_count.div_ceil(MessagePriority::count() as u32);
+    (0..MessagePriority::count() as u32)
+        .map(|prio| {
+            let first = prio * range_size;
+            let last = (first + range_size).min(id_count) - 1;
+            IdRangeUtilization {
+                priority: MessagePriority::from_u32(prio),
+                ide,
+                first,
+                last,
+                used: ids.iter().filter(|id| (first..=last).contains(id)).count(),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn stats(network: &Network) -> NetworkStats {
+    let messages = network.messages();
+    let buses = network
+        .buses()
+        .iter()
+        .map(|bus| {
+            let on_bus = messages.iter().filter(|message| message.bus().id() == bus.id());
+            BusStats {
+                bus: bus.name().to_owned(),
+                messages: on_bus.clone().count(),
+                signals: on_bus.map(|message| message.signals().len()).sum(),
+            }
+        })
+        .collect();
+    let mut dlc_histogram = [0; 9];
+    for message in messages {
+        dlc_histogram[(message.dlc() as usize).min(8)] += 1;
+    }
+    let mut id_utilization = id_ranges(network, false);
+    id_utilization.extend(id_ranges(network, true));
+    NetworkStats {
+        message_count: messages.len(),
+        signal_count: messages.iter().map(|message| message.signals().len()).sum(),
+        buses,
+        dlc_histogram,
+        signals_per_message: Distribution::from_samples(
+            messages.iter().map(|message| message.signals().len()).collect(),
+        ),
+        object_entries_per_node: network
+            .nodes()
+            .iter()
+            .map(|node| (node.name().to_owned(), node.object_entries().len()))
+            .collect(),
+        id_utilization,
+    }
+}
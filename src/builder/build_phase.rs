@@ -0,0 +1,143 @@
+use std::time::Instant;
+
+use crate::errors::{ConfigError, Result};
+
+/// Log target of the phases of `NetworkBuilder::build`.
+pub const BUILD_LOG_TARGET: &str = "canzero_config::build";
+
+/// Phases of `NetworkBuilder::build` in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildPhase {
+    Buses,
+    TypeResolution,
+    IdAssignment,
+    MessageAssembly,
+    NodeAssembly,
+    Validation,
+}
+
+impl BuildPhase {
+    pub fn name(&self) -> &'static str {
+        match &self {
+            BuildPhase::Buses => "buses",
+            BuildPhase::TypeResolution => "type resolution",
+            BuildPhase::IdAssignment => "id assignment",
+            BuildPhase::MessageAssembly => "message assembly",
+            BuildPhase::NodeAssembly => "node assembly",
+            BuildPhase::Validation => "validation",
+        }
+    }
+    // progress in percent at the begin and the end of the phase,
+    // the id assignment usually dominates the build time.
+    fn percent_range(&self) -> (u8, u8) {
+        match &self {
+            BuildPhase::Buses => (0, 5),
+            BuildPhase::TypeResolution => (5, 10),
+            BuildPhase::IdAssignment => (10, 60),
+            BuildPhase::MessageAssembly => (60, 70),
+            BuildPhase::NodeAssembly => (70, 90),
+            BuildPhase::Validation => (90, 100),
+        }
+    }
+}
+
+/// Observes the progress of `NetworkBuilder::build_with_observer`,
+/// e.g. to show a progress bar and to abort long builds.
+pub trait BuildObserver {
+    /// Called when a phase begins.
+    fn on_phase(&mut self, _phase: BuildPhase) {}
+    /// Called with the progress of the whole build in percent, which never
+    /// decreases and is 100 once the network was built.
+    fn on_progress(&mut self, _percent: u8) {}
+    /// Polled between the phases and the steps of a phase, the build fails
+    /// with `ConfigError::BuildCancelled` once it returns true.
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+/// `NetworkBuilder::build` doesn't observe the build.
+impl BuildObserver for () {}
+
+/// Structured span of a phase or a step of the build, which is entered until
+/// it's dropped. Spans are only recorded with the `logging-info` feature,
+/// which reports them through `tracing` (target `BUILD_LOG_TARGET`).
+pub(crate) struct BuildSpan {
+    #[cfg(feature = "logging-info")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl BuildSpan {
+    fn phase(_phase: BuildPhase) -> Self {
+        Self {
+            #[cfg(feature = "logging-info")]
+            _span: tracing::info_span!(target: BUILD_LOG_TARGET, "build_phase", phase = _phase.name())
+                .entered(),
+        }
+    }
+
+    /// Span of a step within a phase, e.g. the J1939 ids of the id assignment.
+    pub(crate) fn step(_step: &'static str) -> Self {
+        Self {
+            #[cfg(feature = "logging-info")]
+            _span: tracing::info_span!(target: BUILD_LOG_TARGET, "build_step", step = _step).entered(),
+        }
+    }
+}
+
+/// Logs the begin of a phase of the build and its duration once it's dropped.
+pub(crate) struct PhaseTimer {
+    phase: BuildPhase,
+    begin: Instant,
+    _span: BuildSpan,
+}
+
+impl PhaseTimer {
+    pub(crate) fn begin(observer: &mut dyn BuildObserver, phase: BuildPhase) -> Result<Self> {
+        check_cancelled(observer, phase)?;
+        log::debug!(target: BUILD_LOG_TARGET, "begin {}", phase.name());
+        observer.on_phase(phase);
+        observer.on_progress(phase.percent_range().0);
+        Ok(Self {
+            phase,
+            begin: Instant::now(),
+            _span: BuildSpan::phase(phase),
+        })
+    }
+
+    /// Reports that `done` of the `total` steps of the phase are done.
+    pub(crate) fn step(
+        &self,
+        observer: &mut dyn BuildObserver,
+        done: usize,
+        total: usize,
+    ) -> Result<()> {
+        check_cancelled(observer, self.phase)?;
+        let (begin, end) = self.phase.percent_range();
+        let percent = begin as usize + (end - begin) as usize * done / total.max(1);
+        observer.on_progress(percent as u8);
+        Ok(())
+    }
+}
+
+fn check_cancelled(observer: &dyn BuildObserver, phase: BuildPhase) -> Result<()> {
+    if observer.is_cancelled() {
+        log::debug!(target: BUILD_LOG_TARGET, "cancelled during {}", phase.name());
+        return Err(ConfigError::BuildCancelled(format!(
+            "build was cancelled during {}",
+            phase.name()
+        )));
+    }
+    Ok(())
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        log::debug!(
+            target: BUILD_LOG_TARGET,
+            "end {} after {:?}",
+            self.phase.name(),
+            self.begin.elapsed()
+        );
+    }
+}
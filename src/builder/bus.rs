@@ -1,5 +1,13 @@
 use super::{BuilderRef, make_builder_ref};
 
+/// baudrate of buses, which don't declare one.
+pub const DEFAULT_BAUDRATE: u32 = 1000000;
+
+/// baudrates recommended by CiA 301.
+pub const STANDARD_BAUDRATES: [u32; 9] = [
+    10000, 20000, 50000, 100000, 125000, 250000, 500000, 800000, 1000000,
+];
+
 
 #[derive(Debug, Clone)]
 pub struct BusBuilder(pub BuilderRef<BusData>);
@@ -7,25 +15,76 @@ pub struct BusBuilder(pub BuilderRef<BusData>);
 #[derive(Debug)]
 pub struct BusData {
     pub name : String,
+    pub description : Option<String>,
+    /// label of the physical connector of the bus (e.g. "X12").
+    pub connector : Option<String>,
     pub id : u32,
-    pub baudrate : u32,
+    /// None if not declared, the bus is then built with DEFAULT_BAUDRATE.
+    pub baudrate : Option<u32>,
+    /// baudrates supported by the transceivers and controllers on the bus,
+    /// STANDARD_BAUDRATES if not declared.
+    pub allowed_baudrates : Option<Vec<u32>>,
+    /// clock of the CAN controllers on the bus in Hz.
+    pub controller_clock : Option<u32>,
     pub expected_utilization : u32,
+    /// sample point in percent of the bit time.
+    pub sample_point : Option<f64>,
+    pub sjw : Option<u8>,
 }
 
 impl BusBuilder {
     pub fn new(name : &str, id : u32, baudrate: Option<u32>) -> Self {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating bus {name} with id {id}");
+        log::trace!("Creating bus {name} with id {id}");
         BusBuilder(make_builder_ref(BusData {
             name : name.to_owned(),
+            description : None,
+            connector : None,
             id,
-            baudrate : baudrate.unwrap_or(1000000),
+            baudrate,
+            allowed_baudrates : None,
+            controller_clock : None,
             expected_utilization : 0,
+            sample_point : None,
+            sjw : None,
         }))
     }
 
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+
+    pub fn add_description(&self, description : &str) {
+        self.0.borrow_mut().description = Some(description.to_owned());
+    }
+    /// Label of the physical connector (e.g. "X12"), which is
+    /// referenced by diagnostics and wiring documentation.
+    pub fn set_connector(&self, connector : &str) {
+        self.0.borrow_mut().connector = Some(connector.to_owned());
+    }
     pub fn baudrate(&self, baudrate : u32) {
-        self.0.borrow_mut().baudrate = baudrate;
+        self.set_baudrate(baudrate);
+    }
+    pub fn set_baudrate(&self, baudrate : u32) {
+        self.0.borrow_mut().baudrate = Some(baudrate);
+    }
+    /// Restricts the baudrate of the bus to the given set
+    /// instead of the standard CAN baudrates.
+    pub fn set_allowed_baudrates(&self, baudrates : &[u32]) {
+        self.0.borrow_mut().allowed_baudrates = Some(baudrates.to_vec());
+    }
+    /// Clock of the CAN controllers on the bus in Hz, the build fails if
+    /// the baudrate can't be derived from it (see config::bit_timing).
+    pub fn set_controller_clock(&self, clock_hz : u32) {
+        self.0.borrow_mut().controller_clock = Some(clock_hz);
+    }
+    /// Sample point in percent of the bit time, which all nodes
+    /// on the bus have to agree on.
+    pub fn set_sample_point(&self, percent : f64) {
+        self.0.borrow_mut().sample_point = Some(percent);
+    }
+    /// Synchronization jump width in time quanta.
+    pub fn set_sjw(&self, tq : u8) {
+        self.0.borrow_mut().sjw = Some(tq);
     }
 }
 
@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
 
 use crate::config::Visibility;
 
@@ -17,12 +17,15 @@ pub struct CommandData {
     pub resp_message: MessageBuilder,
     pub visibility: Visibility,
     pub expected_interval : Duration,
+    pub callee_groups: Vec<String>,
+    /// nodes, which execute the command in addition to the tx_node.
+    pub responders: Vec<NodeBuilder>,
+    pub responder_groups: Vec<String>,
 }
 
 impl CommandBuilder {
     pub fn new(name: &str, tx_node_builder: &NodeBuilder, expected_interval : Option<Duration>) -> CommandBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating command {name}");
+        log::trace!("Creating command {name}");
         let node_data = tx_node_builder.0.borrow();
         let network_builder = &node_data.network_builder;
         let tx_message =
@@ -46,10 +49,13 @@ impl CommandBuilder {
             resp_message: rx_message.clone(),
             tx_node: tx_node_builder.clone(),
             visibility: Visibility::Global,
-            expected_interval : Duration::from_millis(1000),
+            expected_interval : expected_interval.unwrap_or(Duration::from_millis(1000)),
+            callee_groups: vec![],
+            responders: vec![],
+            responder_groups: vec![],
         }));
-        tx_message.__assign_to_command_req(&new);
-        rx_message.__assign_to_command_resp(&new);
+        tx_message.assign_to_command_req(&new);
+        rx_message.assign_to_command_resp(&new);
         new
     }
     pub fn expected_interval(&self, interval : Duration) {
@@ -76,4 +82,40 @@ impl CommandBuilder {
         let callee = network_builder.create_node(name);
         callee.add_extern_command(&self);
     }
+    /// Adds every node of the group, except the node of the command itself,
+    /// as callee. The group is resolved while building.
+    pub fn add_callee_group(&self, group_name: &str) {
+        let mut command_data = self.0.borrow_mut();
+        if !command_data.callee_groups.iter().any(|g| g == group_name) {
+            command_data.callee_groups.push(group_name.to_owned());
+        }
+    }
+    /// Addresses the command to the node {name} as well, every responder
+    /// answers with its own response, which is identified by a generated
+    /// sender_id signal (see `Command::responders`).
+    pub fn add_responder(&self, name: &str) {
+        let command_data = self.0.borrow();
+        let network_builder = command_data.tx_node.0.borrow().network_builder.clone();
+        let responder = network_builder.create_node(name);
+        if Rc::ptr_eq(&responder.0, &command_data.tx_node.0)
+            || command_data.responders.iter().any(|r| Rc::ptr_eq(&r.0, &responder.0))
+        {
+            return;
+        }
+        let mut responder_data = responder.0.borrow_mut();
+        responder_data.rx_messages.push(command_data.call_message.clone());
+        responder_data.tx_messages.push(command_data.resp_message.clone());
+        drop(responder_data);
+        command_data.resp_message.0.borrow_mut().shared = true;
+        drop(command_data);
+        self.0.borrow_mut().responders.push(responder);
+    }
+    /// Adds every node of the group, except the node of the command itself,
+    /// as responder. The group is resolved while building.
+    pub fn add_responder_group(&self, group_name: &str) {
+        let mut command_data = self.0.borrow_mut();
+        if !command_data.responder_groups.iter().any(|g| g == group_name) {
+            command_data.responder_groups.push(group_name.to_owned());
+        }
+    }
 }
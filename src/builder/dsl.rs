@@ -0,0 +1,116 @@
+/// Declares a network in a compact DSL, which expands to the equivalent builder calls.
+/// Evaluates to a `Result<NetworkBuilder>`.
+///
+/// Nodes and object entries are bound to local variables, so referencing a
+/// node (transmitter, receiver, callee or stream source) or an object entry
+/// (stream entry) that was never declared fails to compile.
+/// Types are given either as identifiers (`u16`, `state`) or as string
+/// literals (`"d8<0..100>"`) and are resolved while building.
+///
+/// ```ignore
+/// let network_builder = network! {
+///     enum state { idle, running = 4 }
+///     struct status { state: state, temperature: "d8<0..100>" }
+///     node secu {
+///         object_entry state: state;
+///         stream state { state }
+///         command reset(delay: u16) -> master;
+///     }
+///     node master {
+///         receive secu::state;
+///     }
+///     message dyno_torque: 0x50 { torque: u16 } master -> secu;
+/// }?;
+/// ```
+#[macro_export]
+macro_rules! network {
+    ($($body:tt)*) => {
+        (|| -> $crate::errors::Result<$crate::builder::NetworkBuilder> {
+            let network_builder = $crate::builder::NetworkBuilder::new();
+            $crate::__network!(@nodes network_builder; $($body)*);
+            $crate::__network!(@items network_builder; $($body)*);
+            Ok(network_builder)
+        })()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __network {
+    // first pass: declare all nodes, so items can reference nodes declared after them.
+    (@nodes $nb:ident;) => {};
+    (@nodes $nb:ident; node $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        #[allow(unused_variables)]
+        let $name = $nb.create_node(stringify!($name));
+        $crate::__network!(@nodes $nb; $($rest)*);
+    };
+    (@nodes $nb:ident; enum $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        $crate::__network!(@nodes $nb; $($rest)*);
+    };
+    (@nodes $nb:ident; struct $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        $crate::__network!(@nodes $nb; $($rest)*);
+    };
+    (@nodes $nb:ident; message $name:ident : $id:literal { $($body:tt)* }
+        $($tx:ident),+ -> $($rx:ident),* ; $($rest:tt)*) => {
+        $crate::__network!(@nodes $nb; $($rest)*);
+    };
+
+    // second pass: everything else.
+    (@items $nb:ident;) => {};
+    (@items $nb:ident; enum $name:ident { $($entry:ident $(= $value:literal)?),* $(,)? } $($rest:tt)*) => {
+        let enum_builder = $nb.define_enum(stringify!($name));
+        $(enum_builder.add_entry(stringify!($entry), $crate::__network!(@opt $($value)?))?;)*
+        $crate::__network!(@items $nb; $($rest)*);
+    };
+    (@items $nb:ident; struct $name:ident { $($attrib:ident : $ty:tt),* $(,)? } $($rest:tt)*) => {
+        let struct_builder = $nb.define_struct(stringify!($name));
+        $(struct_builder.add_attribute(stringify!($attrib), $crate::__network!(@ty $ty))?;)*
+        $crate::__network!(@items $nb; $($rest)*);
+    };
+    (@items $nb:ident; node $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        {
+            $crate::__network!(@node $name; $($body)*);
+        }
+        $crate::__network!(@items $nb; $($rest)*);
+    };
+    (@items $nb:ident; message $name:ident : $id:literal { $($signal:ident : $ty:tt),* $(,)? }
+        $($tx:ident),+ -> $($rx:ident),* ; $($rest:tt)*) => {
+        let message_builder = $nb.create_message(stringify!($name), None);
+        message_builder.set_std_id($id);
+        let format = message_builder.make_type_format();
+        $(format.add_type($crate::__network!(@ty $ty), stringify!($signal));)*
+        $(let _ = &$tx; message_builder.add_transmitter(stringify!($tx));)+
+        $(let _ = &$rx; message_builder.add_receiver(stringify!($rx));)*
+        $crate::__network!(@items $nb; $($rest)*);
+    };
+
+    // node body.
+    (@node $node:ident;) => {};
+    (@node $node:ident; object_entry $name:ident : $ty:tt ; $($rest:tt)*) => {
+        #[allow(unused_variables)]
+        let $name = $node.create_object_entry(stringify!($name), $crate::__network!(@ty $ty));
+        $crate::__network!(@node $node; $($rest)*);
+    };
+    (@node $node:ident; stream $name:ident { $($entry:ident),* $(,)? } $($rest:tt)*) => {
+        let stream_builder = $node.create_stream(stringify!($name));
+        $(let _ = &$entry; stream_builder.add_entry(stringify!($entry));)*
+        $crate::__network!(@node $node; $($rest)*);
+    };
+    (@node $node:ident; command $name:ident ( $($arg:ident : $ty:tt),* $(,)? )
+        $(-> $($callee:ident),+)? ; $($rest:tt)*) => {
+        let command_builder = $node.create_command(stringify!($name), None);
+        $(command_builder.add_argument(stringify!($arg), $crate::__network!(@ty $ty));)*
+        $($(let _ = &$callee; command_builder.add_callee(stringify!($callee));)+)?
+        $crate::__network!(@node $node; $($rest)*);
+    };
+    (@node $node:ident; receive $tx:ident :: $stream:ident ; $($rest:tt)*) => {
+        let _ = &$tx;
+        $node.receive_stream(stringify!($tx), stringify!($stream));
+        $crate::__network!(@node $node; $($rest)*);
+    };
+
+    (@ty $ty:ident) => { stringify!($ty) };
+    (@ty $ty:literal) => { $ty };
+    (@opt) => { None };
+    (@opt $value:literal) => { Some($value) };
+}
@@ -0,0 +1,200 @@
+use std::rc::Rc;
+
+use super::{CommandBuilder, MessageBuilder, MessageFormat, NetworkBuilder, TypeBuilder};
+use crate::errors::{ConfigError, Result};
+
+/// Removes all messages matching the predicate from the network and the
+/// nodes, including the streams and commands using them.
+pub fn remove_messages(network_builder: &NetworkBuilder, removed: impl Fn(&MessageBuilder) -> bool) {
+    let network_data = network_builder.0.borrow();
+    network_data.messages.borrow_mut().retain(|m| !removed(m));
+    for node in network_data.nodes.borrow().iter() {
+        let mut node_data = node.0.borrow_mut();
+        node_data.rx_messages.retain(|m| !removed(m));
+        node_data.rx_timeouts.retain(|(m, _)| !removed(m));
+        node_data.tx_messages.retain(|m| !removed(m));
+        node_data
+            .tx_streams
+            .retain(|stream| !removed(&stream.0.borrow().message));
+        node_data
+            .rx_streams
+            .retain(|rx| !removed(&rx.0.borrow().stream_builder.0.borrow().message));
+        let command_removed = |command: &CommandBuilder| {
+            let command_data = command.0.borrow();
+            removed(&command_data.call_message) || removed(&command_data.resp_message)
+        };
+        node_data.commands.retain(|c| !command_removed(c));
+        node_data.extern_commands.retain(|c| !command_removed(c));
+        node_data.transport_channels.retain(|channel| {
+            let channel_data = channel.0.borrow();
+            !removed(&channel_data.data_message) && !removed(&channel_data.flow_control_message)
+        });
+    }
+}
+
+pub fn remove_message(network_builder: &NetworkBuilder, name: &str) -> Result<()> {
+    let message = network_builder
+        .0
+        .borrow()
+        .messages
+        .borrow()
+        .iter()
+        .find(|m| m.0.borrow().name == name)
+        .cloned()
+        .ok_or_else(|| ConfigError::UndefinedMessage(format!("message {name} is not defined")))?;
+    remove_messages(network_builder, |m| Rc::ptr_eq(&m.0, &message.0));
+    Ok(())
+}
+
+pub fn rename_message(network_builder: &NetworkBuilder, name: &str, new_name: &str) -> Result<()> {
+    let network_data = network_builder.0.borrow();
+    let messages = network_data.messages.borrow();
+    if messages.iter().any(|m| m.0.borrow().name == new_name) {
+        return Err(ConfigError::DuplicatedMessage(format!(
+            "can't rename message {name}, {new_name} is already defined"
+        )));
+    }
+    let message = messages
+        .iter()
+        .find(|m| m.0.borrow().name == name)
+        .ok_or_else(|| ConfigError::UndefinedMessage(format!("message {name} is not defined")))?;
+    message.0.borrow_mut().name = new_name.to_owned();
+    Ok(())
+}
+
+/// Renames a node and the messages derived from its name
+/// (streams, commands and transport channels).
+pub fn rename_node(network_builder: &NetworkBuilder, name: &str, new_name: &str) -> Result<()> {
+    let network_data = network_builder.0.borrow();
+    let nodes = network_data.nodes.borrow();
+    if nodes.iter().any(|n| n.0.borrow().name == new_name) {
+        return Err(ConfigError::DuplicatedNode(format!(
+            "can't rename node {name}, {new_name} is already defined"
+        )));
+    }
+    let node = nodes
+        .iter()
+        .find(|n| n.0.borrow().name == name)
+        .ok_or_else(|| ConfigError::UndefinedNode(format!("node {name} is not defined")))?;
+    let mut derived_messages = vec![];
+    {
+        let node_data = node.0.borrow();
+        for stream in &node_data.tx_streams {
+            derived_messages.push(stream.0.borrow().message.clone());
+        }
+        for command in &node_data.commands {
+            let command_data = command.0.borrow();
+            derived_messages.push(command_data.call_message.clone());
+            derived_messages.push(command_data.resp_message.clone());
+        }
+        for channel in &node_data.transport_channels {
+            let channel_data = channel.0.borrow();
+            if Rc::ptr_eq(&channel_data.tx_node.0, &node.0) {
+                derived_messages.push(channel_data.data_message.clone());
+                derived_messages.push(channel_data.flow_control_message.clone());
+            }
+        }
+    }
+    let prefix = format!("{name}_");
+    for message in derived_messages {
+        let mut message_data = message.0.borrow_mut();
+        if let Some(suffix) = message_data.name.strip_prefix(&prefix) {
+            message_data.name = format!("{new_name}_{suffix}");
+        }
+    }
+    node.0.borrow_mut().name = new_name.to_owned();
+    if network_data.uds_tester.as_deref() == Some(name) {
+        drop(nodes);
+        drop(network_data);
+        network_builder.0.borrow_mut().uds_tester = Some(new_name.to_owned());
+    }
+    Ok(())
+}
+
+/// Replaces all references to the type {name} with {replacement}.
+fn replace_type_references(network_builder: &NetworkBuilder, name: &str, replacement: &str) {
+    let network_data = network_builder.0.borrow();
+    for ty in network_data.types.borrow().iter() {
+        match ty {
+            TypeBuilder::Struct(struct_builder) => {
+                for (_, attrib_ty) in struct_builder.0.borrow_mut().attributes.iter_mut() {
+                    if attrib_ty == name {
+                        *attrib_ty = replacement.to_owned();
+                    }
+                }
+            }
+            TypeBuilder::Union(union_builder) => {
+                let mut union_data = union_builder.0.borrow_mut();
+                if union_data.tag.as_deref() == Some(name) {
+                    union_data.tag = Some(replacement.to_owned());
+                }
+                for (_, variant_ty) in union_data.variants.iter_mut() {
+                    if variant_ty == name {
+                        *variant_ty = replacement.to_owned();
+                    }
+                }
+            }
+            TypeBuilder::Enum(_) => (),
+        }
+    }
+    for message in network_data.messages.borrow().iter() {
+        if let MessageFormat::Types(format) = &message.0.borrow().format {
+            for (ty, _) in format.0.borrow_mut().0.iter_mut() {
+                if ty == name {
+                    *ty = replacement.to_owned();
+                }
+            }
+        }
+    }
+    for node in network_data.nodes.borrow().iter() {
+        for object_entry in &node.0.borrow().object_entries {
+            let mut object_entry_data = object_entry.0.borrow_mut();
+            if object_entry_data.ty == name {
+                object_entry_data.ty = replacement.to_owned();
+            }
+        }
+    }
+}
+
+pub fn rename_type(network_builder: &NetworkBuilder, name: &str, new_name: &str) -> Result<()> {
+    let ty = {
+        let network_data = network_builder.0.borrow();
+        let types = network_data.types.borrow();
+        if types.iter().any(|t| t.name() == new_name) {
+            return Err(ConfigError::DuplicatedType(format!(
+                "can't rename type {name}, {new_name} is already defined"
+            )));
+        }
+        types
+            .iter()
+            .find(|t| t.name() == name)
+            .cloned()
+            .ok_or_else(|| ConfigError::UndefinedType(format!("type {name} is not defined")))?
+    };
+    match &ty {
+        TypeBuilder::Enum(enum_builder) => enum_builder.0.borrow_mut().name = new_name.to_owned(),
+        TypeBuilder::Struct(struct_builder) => {
+            struct_builder.0.borrow_mut().name = new_name.to_owned()
+        }
+        TypeBuilder::Union(union_builder) => {
+            union_builder.0.borrow_mut().name = new_name.to_owned()
+        }
+    }
+    replace_type_references(network_builder, name, new_name);
+    Ok(())
+}
+
+/// Removes the type {name} and replaces all references to it with {replacement}.
+pub fn replace_type(network_builder: &NetworkBuilder, name: &str, replacement: &str) -> Result<()> {
+    {
+        let network_data = network_builder.0.borrow();
+        let mut types = network_data.types.borrow_mut();
+        let position = types
+            .iter()
+            .position(|t| t.name() == name)
+            .ok_or_else(|| ConfigError::UndefinedType(format!("type {name} is not defined")))?;
+        types.remove(position);
+    }
+    replace_type_references(network_builder, name, replacement);
+    Ok(())
+}
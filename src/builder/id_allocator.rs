@@ -0,0 +1,307 @@
+use std::fmt::Debug;
+
+use crate::{
+    config::{MessageId, RxFilter},
+    errors::{ConfigError, Result},
+};
+
+use super::{
+    message_builder::MessageIdTemplate, message_resolution, MessageBuilder, MessagePriority,
+    NodeBuilder,
+};
+
+const MAX_STD_ID: u32 = 0x7FF;
+const MAX_EXT_ID: u32 = 0x1FFF_FFFF;
+
+/// Message, which requires an id from the `IdAllocator`.
+#[derive(Debug, Clone)]
+pub struct IdRequest {
+    name: String,
+    priority: MessagePriority,
+    ide: bool,
+    receivers: Vec<String>,
+    bus: Option<String>,
+}
+
+impl IdRequest {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn priority(&self) -> MessagePriority {
+        self.priority
+    }
+    /// true if the message requires an extended id.
+    pub fn ide(&self) -> bool {
+        self.ide
+    }
+    /// Names of the receiving nodes in alphabetical order.
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+    /// Bus the message was assigned to, the remaining messages are
+    /// distributed over the buses after the ids are allocated.
+    pub fn bus(&self) -> Option<&str> {
+        self.bus.as_deref()
+    }
+}
+
+/// All messages of a build, which require an id, ordered by name.
+pub struct IdRequests {
+    requests: Vec<IdRequest>,
+    fixed_ids: Vec<(String, MessageId)>,
+    // bus of every fixed id, None if the message is distributed later.
+    fixed_buses: Vec<Option<String>>,
+    node_names: Vec<String>,
+    // builders of the requests, used by the built-in priority buckets.
+    pub(crate) messages: Vec<MessageBuilder>,
+    pub(crate) nodes: Vec<NodeBuilder>,
+}
+
+impl IdRequests {
+    pub(crate) fn new(messages: &[MessageBuilder], nodes: &[NodeBuilder]) -> Self {
+        let mut requests = vec![];
+        let mut fixed_ids = vec![];
+        let mut fixed_buses = vec![];
+        for message in messages {
+            let message_data = message.0.borrow();
+            let bus = message_data.bus.as_ref().map(|bus| bus.0.borrow().name.clone());
+            let (priority, ide) = match &message_data.id {
+                MessageIdTemplate::StdId(id) => {
+                    fixed_ids.push((message_data.name.clone(), MessageId::StandardId(*id)));
+                    fixed_buses.push(bus);
+                    continue;
+                }
+                MessageIdTemplate::ExtId(id) => {
+                    fixed_ids.push((message_data.name.clone(), MessageId::ExtendedId(*id)));
+                    fixed_buses.push(bus);
+                    continue;
+                }
+                MessageIdTemplate::AnyStd(priority) | MessageIdTemplate::AnyAny(priority) => {
+                    (*priority, false)
+                }
+                MessageIdTemplate::AnyExt(priority) => (*priority, true),
+            };
+            let mut receivers: Vec<String> = message_data
+                .receivers
+                .iter()
+                .map(|node| node.0.borrow().name.clone())
+                .collect();
+            receivers.sort();
+            requests.push(IdRequest {
+                name: message_data.name.clone(),
+                priority,
+                ide,
+                receivers,
+                bus,
+            });
+        }
+        Self {
+            requests,
+            fixed_ids,
+            fixed_buses,
+            node_names: nodes.iter().map(|node| node.0.borrow().name.clone()).collect(),
+            messages: messages.to_vec(),
+            nodes: nodes.to_vec(),
+        }
+    }
+
+    pub fn requests(&self) -> &Vec<IdRequest> {
+        &self.requests
+    }
+    /// Ids set with `MessageBuilder::set_std_id` or `set_ext_id`,
+    /// which must not be allocated again.
+    pub fn fixed_ids(&self) -> &Vec<(String, MessageId)> {
+        &self.fixed_ids
+    }
+    pub fn node_names(&self) -> &Vec<String> {
+        &self.node_names
+    }
+}
+
+/// Result of an `IdAllocator`.
+#[derive(Debug, Clone, Default)]
+pub struct IdAllocation {
+    ids: Vec<MessageId>,
+    filters: Vec<(String, Vec<RxFilter>)>,
+}
+
+impl IdAllocation {
+    /// Ids of the requests in the order of `IdRequests::requests`.
+    pub fn new(ids: Vec<MessageId>) -> Self {
+        Self {
+            ids,
+            filters: vec![],
+        }
+    }
+    /// Acceptance filters of a node. Received messages, which aren't
+    /// accepted by any filter of the node, get a filter of their exact id.
+    pub fn with_filters(mut self, node_name: &str, filters: Vec<RxFilter>) -> Self {
+        self.filters.push((node_name.to_owned(), filters));
+        self
+    }
+    pub fn ids(&self) -> &Vec<MessageId> {
+        &self.ids
+    }
+    pub fn filters(&self, node_name: &str) -> &[RxFilter] {
+        self.filters
+            .iter()
+            .find(|(name, _)| name == node_name)
+            .map(|(_, filters)| filters.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Checks that every request got a unique id of the requested kind,
+    /// which isn't the fixed id of another message. Fixed ids may only be
+    /// reused if both messages are assigned to different buses.
+    pub(crate) fn validate(&self, requests: &IdRequests) -> Result<()> {
+        if self.ids.len() != requests.requests().len() {
+            return Err(ConfigError::UnresolvedId(format!(
+                "id allocator returned {} ids for {} messages",
+                self.ids.len(),
+                requests.requests().len()
+            )));
+        }
+        let mut used: Vec<MessageId> = vec![];
+        for (request, id) in std::iter::zip(requests.requests(), &self.ids) {
+            let ide = matches!(id, MessageId::ExtendedId(_));
+            if ide != request.ide() {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "id allocator assigned the id {id:?} of the wrong kind to message {}",
+                    request.name()
+                )));
+            }
+            if used.contains(id) {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "id allocator assigned the id {id:?} of message {} twice",
+                    request.name()
+                )));
+            }
+            let collision = std::iter::zip(requests.fixed_ids(), &requests.fixed_buses).find(
+                |((_, fixed_id), fixed_bus)| {
+                    fixed_id == id
+                        && match (request.bus(), fixed_bus) {
+                            (Some(bus), Some(fixed_bus)) => bus == fixed_bus,
+                            _ => true,
+                        }
+                },
+            );
+            if let Some(((fixed_name, _), _)) = collision {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "id allocator assigned the fixed id {id:?} of message {fixed_name} to message {}",
+                    request.name()
+                )));
+            }
+            used.push(*id);
+        }
+        Ok(())
+    }
+}
+
+/// Strategy, which assigns the ids of all messages without a fixed id,
+/// see `NetworkBuilder::set_id_allocator`.
+pub trait IdAllocator: Debug {
+    fn allocate(&self, requests: &IdRequests) -> Result<IdAllocation>;
+    /// Like `allocate`, but reports its progress, allocators, which take
+    /// long, should report it regularly to allow cancelling the build.
+    fn allocate_with_progress(
+        &self,
+        requests: &IdRequests,
+        progress: &mut AllocationProgress,
+    ) -> Result<IdAllocation> {
+        progress(0, 1)?;
+        self.allocate(requests)
+    }
+}
+
+/// Called with the `done` and `total` steps of an allocation, fails with
+/// `ConfigError::BuildCancelled` once the build was cancelled.
+pub type AllocationProgress<'a> = dyn FnMut(usize, usize) -> Result<()> + 'a;
+
+/// Groups messages with the same receivers into sets, which share a
+/// setcode in the lowest bits of the id, so every node requires at most
+/// 8 acceptance filters. The priority is encoded in the remaining bits.
+/// This is the default allocator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PriorityBucketAllocator;
+
+impl IdAllocator for PriorityBucketAllocator {
+    fn allocate(&self, requests: &IdRequests) -> Result<IdAllocation> {
+        message_resolution::allocate_priority_buckets(requests, &mut |_, _| Ok(()))
+    }
+    fn allocate_with_progress(
+        &self,
+        requests: &IdRequests,
+        progress: &mut AllocationProgress,
+    ) -> Result<IdAllocation> {
+        message_resolution::allocate_priority_buckets(requests, progress)
+    }
+}
+
+/// Assigns the smallest free ids in the order of the priorities,
+/// messages of the same priority are ordered by name.
+/// Nodes get a filter for every id they receive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactAllocator;
+
+impl IdAllocator for CompactAllocator {
+    fn allocate(&self, requests: &IdRequests) -> Result<IdAllocation> {
+        let mut order: Vec<usize> = (0..requests.requests().len()).collect();
+        order.sort_by_key(|i| requests.requests()[*i].priority().to_u32());
+        let mut used: Vec<MessageId> = requests.fixed_ids().iter().map(|(_, id)| *id).collect();
+        let mut ids = vec![MessageId::StandardId(0); order.len()];
+        for i in order {
+            let request = &requests.requests()[i];
+            let (make_id, max): (fn(u32) -> MessageId, u32) = if request.ide() {
+                (MessageId::ExtendedId, MAX_EXT_ID)
+            } else {
+                (MessageId::StandardId, MAX_STD_ID)
+            };
+            let Some(id) = (0..=max).map(make_id).find(|id| !used.contains(id)) else {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "no free id left for message {}",
+                    request.name()
+                )));
+            };
+            used.push(id);
+            ids[i] = id;
+        }
+        Ok(IdAllocation::new(ids))
+    }
+}
+
+/// Derives the ids from a hash of the message name, so adding or removing
+/// a message never changes the id of any other message (unless the hashes
+/// collide). The id space is divided into one range per priority.
+/// Nodes get a filter for every id they receive.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashStableAllocator;
+
+impl IdAllocator for HashStableAllocator {
+    fn allocate(&self, requests: &IdRequests) -> Result<IdAllocation> {
+        let mut used: Vec<MessageId> = requests.fixed_ids().iter().map(|(_, id)| *id).collect();
+        let mut ids = vec![];
+        for request in requests.requests() {
+            let (make_id, max): (fn(u32) -> MessageId, u32) = if request.ide() {
+                (MessageId::ExtendedId, MAX_EXT_ID)
+            } else {
+                (MessageId::StandardId, MAX_STD_ID)
+            };
+            let range = (max + 1) / MessagePriority::count() as u32;
+            let begin = request.priority().to_u32() * range;
+            let start = (seahash::hash(request.name().as_bytes()) % range as u64) as u32;
+            let Some(id) = (0..range)
+                .map(|offset| make_id(begin + (start + offset) % range))
+                .find(|id| !used.contains(id))
+            else {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "no free id left for message {} with priority {:?}",
+                    request.name(),
+                    request.priority()
+                )));
+            };
+            used.push(id);
+            ids.push(id);
+        }
+        Ok(IdAllocation::new(ids))
+    }
+}
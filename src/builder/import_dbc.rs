@@ -1,11 +1,14 @@
 use std::fs::File;
 use std::io::prelude::*;
 
-use super::NetworkBuilder;
+use super::{import_kcd::INTRINSIC_MESSAGES, NetworkBuilder};
 use crate::config::signal::Signal;
 use crate::config::SignalType;
 use crate::errors::Result;
 
+/// placeholder for a missing receiver.
+const NO_NODE: &str = "Vector__XXX";
+
 pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -> Result<()> {
     let mut dbc_file = File::open(dbc_path)?;
     let mut buffer = Vec::new();
@@ -15,6 +18,10 @@ pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -
 
     for message in dbc.messages() {
         let name = message.message_name();
+        // the get/set and heartbeat messages are generated by the builder.
+        if INTRINSIC_MESSAGES.contains(&name.as_str()) {
+            continue;
+        }
         let message_builder = network_builder.create_message(name, None);
         message_builder.assign_bus(bus);
         match dbc.message_comment(message.message_id().clone()){
@@ -29,6 +36,7 @@ pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -
             message_builder.set_std_id(message.message_id().0);
         }
         let signal_format = message_builder.make_signal_format();
+        signal_format.use_manual_offsets();
         
         let mut receives = vec![];
         for dbc_signal in message.signals() {
@@ -44,16 +52,37 @@ pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -
                 SignalType::Decimal { size, offset: *dbc_signal.offset(), scale: *dbc_signal.factor() }
             };
 
-            signal_format.add_signal(Signal::new(&name, None, ty, start_bit))?;
+            let description = dbc.signal_comment(*message.message_id(), name);
+            signal_format.add_signal(Signal::new(name, description, ty, start_bit))?;
 
             for rx in dbc_signal.receivers() {
-                receives.push(rx.clone());
+                if rx != NO_NODE && !receives.contains(rx) {
+                    receives.push(rx.clone());
+                }
+            }
+        }
+        // signals are only annotated, if they aren't used by every receiver.
+        for dbc_signal in message.signals() {
+            let used_by_all = receives.iter().all(|rx| dbc_signal.receivers().contains(rx));
+            if !used_by_all {
+                for rx in dbc_signal.receivers().iter().filter(|rx| *rx != NO_NODE) {
+                    signal_format.add_signal_receiver(dbc_signal.name(), rx)?;
+                }
             }
         }
         for rx in receives {
             message_builder.add_receiver(&rx);
         }
 
+        for group in dbc
+            .signal_groups()
+            .iter()
+            .filter(|group| group.message_id() == message.message_id())
+        {
+            let signals: Vec<&str> = group.signal_names().iter().map(|s| s.as_str()).collect();
+            message_builder.add_signal_group(group.signal_group_name(), &signals);
+        }
+
         for msg_transmitter in dbc.message_transmitters().iter().filter(|transmitter| transmitter.message_id() == message.message_id()) {
             let transmitters = msg_transmitter.transmitter();
             for tx in transmitters {
@@ -65,5 +94,19 @@ pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -
         }
     }
 
+    for comment in dbc.comments() {
+        match comment {
+            can_dbc::Comment::Node { node_name, comment } => {
+                if let Some(node) = network_builder.find_node(node_name) {
+                    node.add_description(comment);
+                }
+            }
+            can_dbc::Comment::Plain { comment } if network_builder.0.borrow().description.is_none() => {
+                network_builder.set_description(comment);
+            }
+            _ => (),
+        }
+    }
+
     Ok(())
 }
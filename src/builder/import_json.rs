@@ -0,0 +1,205 @@
+use serde_json::Value;
+
+use super::NetworkBuilder;
+use crate::{
+    errors::{ConfigError, Result},
+    export::canonical::CANONICAL_SCHEMA_VERSION,
+};
+
+/// Value of a network definition, errors are reported with the
+/// variant of the format the definition was written in.
+#[derive(Clone, Copy)]
+struct Item<'a> {
+    value: &'a Value,
+    error: fn(String) -> ConfigError,
+}
+
+impl<'a> Item<'a> {
+    fn item(&self, value: &'a Value) -> Item<'a> {
+        Item {
+            value,
+            error: self.error,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&'a Value> {
+        self.value.get(key).filter(|value| !value.is_null())
+    }
+
+    fn str(&self, key: &str) -> Result<&'a str> {
+        match self.get(key) {
+            Some(Value::String(value)) => Ok(value),
+            Some(_) => Err((self.error)(format!("{key} has to be a string"))),
+            None => Err((self.error)(format!("missing {key}"))),
+        }
+    }
+
+    fn opt_str(&self, key: &str) -> Result<Option<&'a str>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(_) => self.str(key).map(Some),
+        }
+    }
+
+    fn opt_u32(&self, key: &str) -> Result<Option<u32>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(value) => match value.as_u64().and_then(|n| u32::try_from(n).ok()) {
+                Some(n) => Ok(Some(n)),
+                None => Err((self.error)(format!("{key} has to be an unsigned integer"))),
+            },
+        }
+    }
+
+    fn opt_bool(&self, key: &str) -> Result<Option<bool>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(Value::Bool(value)) => Ok(Some(*value)),
+            Some(_) => Err((self.error)(format!("{key} has to be a bool"))),
+        }
+    }
+
+    fn array(&self, key: &str) -> Result<Vec<Item<'a>>> {
+        match self.get(key) {
+            None => Ok(vec![]),
+            Some(Value::Array(elements)) => Ok(elements.iter().map(|e| self.item(e)).collect()),
+            Some(_) => Err((self.error)(format!("{key} has to be an array"))),
+        }
+    }
+
+    fn str_array(&self, key: &str) -> Result<Vec<&'a str>> {
+        self.array(key)?
+            .iter()
+            .map(|element| match element.value {
+                Value::String(value) => Ok(value.as_str()),
+                _ => Err((self.error)(format!("{key} has to be an array of strings"))),
+            })
+            .collect()
+    }
+}
+
+/// Imports a JSON network definition, see `NetworkBuilder::from_json`.
+pub fn import_json(network_builder: &NetworkBuilder, json: &str) -> Result<()> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|error| ConfigError::Json(error.to_string()))?;
+    import_definition(network_builder, Item { value: &root, error: ConfigError::Json })
+}
+
+/// Imports a YAML network definition, see `NetworkBuilder::from_yaml`.
+pub fn import_yaml(network_builder: &NetworkBuilder, yaml: &str) -> Result<()> {
+    let root: Value =
+        serde_yaml::from_str(yaml).map_err(|error| ConfigError::Yaml(error.to_string()))?;
+    import_definition(network_builder, Item { value: &root, error: ConfigError::Yaml })
+}
+
+fn import_definition(network_builder: &NetworkBuilder, root: Item) -> Result<()> {
+    if !root.value.is_object() {
+        return Err((root.error)("the network has to be an object".to_owned()));
+    }
+    // definitions without a version are read with the current schema.
+    if let Some(version) = root.opt_u32("schema_version")? {
+        if version != CANONICAL_SCHEMA_VERSION {
+            return Err((root.error)(format!(
+                "schema_version {version} is not supported, expected {CANONICAL_SCHEMA_VERSION}"
+            )));
+        }
+    }
+    for bus in root.array("buses")? {
+        let bus_builder = network_builder.create_bus(bus.str("name")?, bus.opt_u32("baudrate")?);
+        if let Some(description) = bus.opt_str("description")? {
+            bus_builder.add_description(description);
+        }
+    }
+    for enum_def in root.array("enums")? {
+        let enum_builder = network_builder.define_enum(enum_def.str("name")?);
+        if let Some(description) = enum_def.opt_str("description")? {
+            enum_builder.add_description(description);
+        }
+        for entry in enum_def.array("entries")? {
+            match entry.value {
+                Value::String(name) => enum_builder.add_entry(name, None)?,
+                _ => enum_builder
+                    .add_entry(entry.str("name")?, entry.opt_u32("value")?.map(|v| v as u64))?,
+            }
+        }
+    }
+    for struct_def in root.array("structs")? {
+        let struct_builder = network_builder.define_struct(struct_def.str("name")?);
+        if let Some(description) = struct_def.opt_str("description")? {
+            struct_builder.add_description(description);
+        }
+        for attribute in struct_def.array("attributes")? {
+            struct_builder.add_attribute(attribute.str("name")?, attribute.str("type")?)?;
+        }
+    }
+    for node in root.array("nodes")? {
+        let node_builder = network_builder.create_node(node.str("name")?);
+        if let Some(description) = node.opt_str("description")? {
+            node_builder.add_description(description);
+        }
+        for object_entry in node.array("object_entries")? {
+            let object_entry_builder =
+                node_builder.create_object_entry(object_entry.str("name")?, object_entry.str("type")?);
+            if let Some(description) = object_entry.opt_str("description")? {
+                object_entry_builder.add_description(description);
+            }
+            if let Some(unit) = object_entry.opt_str("unit")? {
+                object_entry_builder.add_unit(unit);
+            }
+        }
+        for stream in node.array("streams")? {
+            let stream_builder = node_builder.create_stream(stream.str("name")?);
+            if let Some(description) = stream.opt_str("description")? {
+                stream_builder.add_description(description);
+            }
+            for entry in stream.str_array("entries")? {
+                stream_builder.add_entry(entry);
+            }
+            match stream.opt_u32("keyframe_interval")? {
+                Some(0) => return Err((stream.error)("keyframe_interval has to be at least 1".to_owned())),
+                Some(keyframe_interval) => stream_builder.set_delta_encoding(keyframe_interval),
+                None => (),
+            }
+        }
+        for command in node.array("commands")? {
+            let command_builder = node_builder.create_command(command.str("name")?, None);
+            if let Some(description) = command.opt_str("description")? {
+                command_builder.add_description(description);
+            }
+            for argument in command.array("arguments")? {
+                command_builder.add_argument(argument.str("name")?, argument.str("type")?);
+            }
+            for callee in command.str_array("callees")? {
+                command_builder.add_callee(callee);
+            }
+            for responder in command.str_array("responders")? {
+                command_builder.add_responder(responder);
+            }
+        }
+        for rx_stream in node.array("receive")? {
+            node_builder.receive_stream(rx_stream.str("node")?, rx_stream.str("stream")?);
+        }
+    }
+    for message in root.array("messages")? {
+        let message_builder = network_builder.create_message(message.str("name")?, None);
+        if let Some(description) = message.opt_str("description")? {
+            message_builder.add_description(description);
+        }
+        match (message.opt_u32("id")?, message.opt_bool("extended")?) {
+            (Some(id), Some(true)) => message_builder.set_ext_id(id),
+            (Some(id), _) => message_builder.set_std_id(id),
+            (None, _) => (),
+        }
+        let format = message_builder.make_type_format();
+        for signal in message.array("signals")? {
+            format.add_type(signal.str("type")?, signal.str("name")?);
+        }
+        for transmitter in message.str_array("transmitters")? {
+            message_builder.add_transmitter(transmitter);
+        }
+        for receiver in message.str_array("receivers")? {
+            message_builder.add_receiver(receiver);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use super::{MessageBuilder, MessageSignalFormatBuilder, NetworkBuilder};
+use crate::config::signal::Signal;
+use crate::config::SignalType;
+use crate::errors::{ConfigError, Result};
+
+/// messages which are created implicitly by every network.
+pub(crate) const INTRINSIC_MESSAGES: [&str; 5] = ["get_req", "get_resp", "set_req", "set_resp", "heartbeat"];
+
+fn attributes(element: &BytesStart) -> Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(|e| ConfigError::Kcd(e.to_string()))?;
+        let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+        let value = attribute
+            .unescape_value()
+            .map_err(|e| ConfigError::Kcd(e.to_string()))?
+            .into_owned();
+        attributes.insert(key, value);
+    }
+    Ok(attributes)
+}
+
+fn required<'a>(attributes: &'a HashMap<String, String>, element: &str, key: &str) -> Result<&'a str> {
+    attributes
+        .get(key)
+        .map(|value| value.as_str())
+        .ok_or_else(|| ConfigError::Kcd(format!("<{element}> is missing the attribute {key}")))
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| ConfigError::Kcd(format!("{value} is not a valid number")))
+}
+
+fn parse_id(value: &str) -> Result<u32> {
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16)
+            .map_err(|_| ConfigError::Kcd(format!("{value} is not a valid message id"))),
+        None => parse_number(value),
+    }
+}
+
+struct SignalDefinition {
+    name: String,
+    description: Option<String>,
+    offset: usize,
+    size: u8,
+    signed: bool,
+    slope: f64,
+    intercept: f64,
+    consumers: Vec<String>,
+}
+
+impl SignalDefinition {
+    /// Decimals are encoded as unsigned raw values, signed raw values
+    /// with a slope or intercept would change the encoding on the wire.
+    fn signal_type(&self) -> Result<SignalType> {
+        let size = self.size;
+        let scaled = self.slope != 1.0 || self.intercept != 0.0;
+        match (self.signed, scaled) {
+            (true, true) => Err(ConfigError::Kcd(format!(
+                "signed signal {} with slope {} and intercept {} is not supported",
+                self.name, self.slope, self.intercept
+            ))),
+            (true, false) => Ok(SignalType::SignedInt { size }),
+            (false, true) => Ok(SignalType::Decimal {
+                size,
+                offset: self.intercept,
+                scale: self.slope,
+            }),
+            (false, false) => Ok(SignalType::UnsignedInt { size }),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+enum NodeRefContext {
+    None,
+    Producer,
+    Consumer,
+}
+
+pub fn import_kcd(network_builder: &NetworkBuilder, kcd: &str) -> Result<()> {
+    let mut reader = Reader::from_str(kcd);
+    reader.config_mut().trim_text(true);
+
+    let mut node_names: HashMap<String, String> = HashMap::new();
+    let mut bus: Option<String> = None;
+    let mut message: Option<(MessageBuilder, MessageSignalFormatBuilder)> = None;
+    let mut skip_message = false;
+    let mut signal: Option<SignalDefinition> = None;
+    // (signal name, consumers) of the signals of the current message.
+    let mut signal_consumers: Vec<(String, Vec<String>)> = vec![];
+    let mut node_ref_context = NodeRefContext::None;
+    let mut in_notes = false;
+    let mut in_document = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| ConfigError::Kcd(e.to_string()))?;
+        let (element, is_empty) = match &event {
+            Event::Start(element) => (Some(element), false),
+            Event::Empty(element) => (Some(element), true),
+            _ => (None, false),
+        };
+        if let Some(element) = element {
+            let tag = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+            let attribs = attributes(element)?;
+            match tag.as_str() {
+                "Node" => {
+                    let id = required(&attribs, "Node", "id")?.to_owned();
+                    let name = required(&attribs, "Node", "name")?.to_owned();
+                    network_builder.create_node(&name);
+                    node_names.insert(id, name);
+                }
+                "Bus" => {
+                    let name = required(&attribs, "Bus", "name")?;
+                    let baudrate = match attribs.get("baudrate") {
+                        Some(baudrate) => Some(parse_number(baudrate)?),
+                        None => None,
+                    };
+                    let exists = network_builder
+                        .0
+                        .borrow()
+                        .buses
+                        .borrow()
+                        .iter()
+                        .any(|b| b.0.borrow().name == name);
+                    if !exists {
+                        network_builder.create_bus(name, baudrate);
+                    }
+                    bus = Some(name.to_owned());
+                }
+                "Message" => {
+                    let name = required(&attribs, "Message", "name")?;
+                    skip_message = INTRINSIC_MESSAGES.contains(&name);
+                    if !skip_message {
+                        let id = parse_id(required(&attribs, "Message", "id")?)?;
+                        let message_builder = network_builder.create_message(name, None);
+                        if let Some(bus) = &bus {
+                            message_builder.assign_bus(bus);
+                        }
+                        match attribs.get("format").map(|f| f.as_str()) {
+                            Some("extended") => message_builder.set_ext_id(id),
+                            _ => message_builder.set_std_id(id),
+                        }
+                        let format = message_builder.make_signal_format();
+                        format.use_manual_offsets();
+                        message = Some((message_builder, format));
+                    }
+                }
+                "Signal" if !skip_message => {
+                    let endianess = attribs.get("endianess").map(|e| e.as_str());
+                    if endianess == Some("big") {
+                        return Err(ConfigError::Kcd(format!(
+                            "big endian signal {} is not supported",
+                            required(&attribs, "Signal", "name")?
+                        )));
+                    }
+                    signal = Some(SignalDefinition {
+                        name: required(&attribs, "Signal", "name")?.to_owned(),
+                        description: None,
+                        offset: parse_number(required(&attribs, "Signal", "offset")?)?,
+                        size: match attribs.get("length") {
+                            Some(length) => parse_number(length)?,
+                            None => 1,
+                        },
+                        signed: false,
+                        slope: 1.0,
+                        intercept: 0.0,
+                        consumers: vec![],
+                    });
+                }
+                "Value" => {
+                    if let Some(signal) = &mut signal {
+                        match attribs.get("type").map(|t| t.as_str()) {
+                            Some("signed") => signal.signed = true,
+                            Some("unsigned") | None => signal.signed = false,
+                            Some(other) => {
+                                return Err(ConfigError::Kcd(format!(
+                                    "value type {other} of signal {} is not supported",
+                                    signal.name
+                                )))
+                            }
+                        }
+                        if let Some(slope) = attribs.get("slope") {
+                            signal.slope = parse_number(slope)?;
+                        }
+                        if let Some(intercept) = attribs.get("intercept") {
+                            signal.intercept = parse_number(intercept)?;
+                        }
+                    }
+                }
+                "Producer" => node_ref_context = NodeRefContext::Producer,
+                "Consumer" => node_ref_context = NodeRefContext::Consumer,
+                "NodeRef" if !skip_message => {
+                    let id = required(&attribs, "NodeRef", "id")?;
+                    let node_name = node_names
+                        .get(id)
+                        .ok_or_else(|| ConfigError::Kcd(format!("node {id} is not defined")))?;
+                    match node_ref_context {
+                        NodeRefContext::Producer => {
+                            if let Some((message_builder, _)) = &message {
+                                message_builder.add_transmitter(node_name);
+                            }
+                        }
+                        NodeRefContext::Consumer => {
+                            if let Some(signal) = &mut signal {
+                                signal.consumers.push(node_name.clone());
+                            }
+                        }
+                        NodeRefContext::None => (),
+                    }
+                }
+                "Notes" => in_notes = !is_empty,
+                "Document" => in_document = !is_empty,
+                _ => (),
+            }
+            if !is_empty {
+                continue;
+            }
+        }
+        match event {
+            Event::Text(text) if in_notes => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| ConfigError::Kcd(e.to_string()))?
+                    .into_owned();
+                match (&mut signal, &message) {
+                    (Some(signal), _) => signal.description = Some(text),
+                    (None, Some((message_builder, _))) => message_builder.add_description(&text),
+                    (None, None) => (),
+                }
+            }
+            Event::Text(text) if in_document => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| ConfigError::Kcd(e.to_string()))?
+                    .into_owned();
+                if network_builder.0.borrow().description.is_none() {
+                    network_builder.set_description(&text);
+                }
+            }
+            Event::End(element) => match element.local_name().as_ref() {
+                b"Notes" => in_notes = false,
+                b"Document" => in_document = false,
+                b"Producer" | b"Consumer" => node_ref_context = NodeRefContext::None,
+                b"Signal" => {
+                    if let (Some(definition), Some((message_builder, format))) =
+                        (signal.take(), &message)
+                    {
+                        format.add_signal(Signal::new(
+                            &definition.name,
+                            definition.description.as_deref(),
+                            definition.signal_type()?,
+                            definition.offset,
+                        ))?;
+                        for consumer in &definition.consumers {
+                            let receives = message_builder
+                                .0
+                                .borrow()
+                                .receivers
+                                .iter()
+                                .any(|n| &n.0.borrow().name == consumer);
+                            if !receives {
+                                message_builder.add_receiver(consumer);
+                            }
+                        }
+                        signal_consumers.push((definition.name, definition.consumers));
+                    }
+                }
+                b"Message" => {
+                    // signals are only annotated, if they aren't used by every receiver.
+                    if let Some((message_builder, format)) = message.take() {
+                        let receivers: Vec<String> = message_builder
+                            .0
+                            .borrow()
+                            .receivers
+                            .iter()
+                            .map(|n| n.0.borrow().name.clone())
+                            .collect();
+                        for (signal_name, consumers) in signal_consumers.drain(..) {
+                            if receivers.iter().all(|rx| consumers.contains(rx)) {
+                                continue;
+                            }
+                            for consumer in &consumers {
+                                format.add_signal_receiver(&signal_name, consumer)?;
+                            }
+                        }
+                    }
+                    signal_consumers.clear();
+                    skip_message = false;
+                }
+                b"Bus" => bus = None,
+                _ => (),
+            },
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    Ok(())
+}
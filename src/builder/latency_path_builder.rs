@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use super::{make_builder_ref, BuilderRef};
+
+#[derive(Debug, Clone)]
+pub struct LatencyPathBuilder(pub BuilderRef<LatencyPathData>);
+
+/// Step of a data path, referenced by the name of the node and the element,
+/// which are resolved while building.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LatencyHopBuilder {
+    ObjectEntry { node: String, name: String },
+    Stream { node: String, name: String },
+    Command { node: String, name: String },
+}
+
+#[derive(Debug)]
+pub struct LatencyPathData {
+    pub name: String,
+    pub description: Option<String>,
+    pub budget: Duration,
+    pub hops: Vec<LatencyHopBuilder>,
+}
+
+impl LatencyPathBuilder {
+    pub fn new(name: &str) -> LatencyPathBuilder {
+        log::trace!("Creating latency path {name}");
+        LatencyPathBuilder(make_builder_ref(LatencyPathData {
+            name: name.to_owned(),
+            description: None,
+            budget: Duration::MAX,
+            hops: vec![],
+        }))
+    }
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn add_description(&self, description: &str) {
+        let mut data = self.0.borrow_mut();
+        data.description = Some(description.to_owned());
+    }
+    /// Maximum end-to-end latency of the path.
+    pub fn set_budget(&self, budget: Duration) {
+        let mut data = self.0.borrow_mut();
+        data.budget = budget;
+    }
+    /// Object entry of the node, where the path starts (e.g. a sensor value).
+    pub fn add_object_entry(&self, node_name: &str, object_entry_name: &str) {
+        self.0.borrow_mut().hops.push(LatencyHopBuilder::ObjectEntry {
+            node: node_name.to_owned(),
+            name: object_entry_name.to_owned(),
+        });
+    }
+    /// Stream transmitted by the node.
+    pub fn add_stream(&self, node_name: &str, stream_name: &str) {
+        self.0.borrow_mut().hops.push(LatencyHopBuilder::Stream {
+            node: node_name.to_owned(),
+            name: stream_name.to_owned(),
+        });
+    }
+    /// Command of the node, which is called by the previous node of the path.
+    pub fn add_command(&self, node_name: &str, command_name: &str) {
+        self.0.borrow_mut().hops.push(LatencyHopBuilder::Command {
+            node: node_name.to_owned(),
+            name: command_name.to_owned(),
+        });
+    }
+}
@@ -0,0 +1,573 @@
+use std::{rc::Rc, time::Duration};
+
+use super::{
+    bus::{BusBuilder, BusData}, object_entry_builder::ObjectEntryData, MessageBuilder, NetworkBuilder,
+    NodeBuilder, TypeBuilder,
+};
+use crate::errors::{ConfigError, Result};
+
+fn same_type_definition(a: &TypeBuilder, b: &TypeBuilder) -> bool {
+    match (a, b) {
+        (TypeBuilder::Enum(a), TypeBuilder::Enum(b)) => {
+            let a = a.0.borrow();
+            let b = b.0.borrow();
+            a.name == b.name
+                && a.entries == b.entries
+                && a.entry_descriptions == b.entry_descriptions
+                && a.deprecated_entries == b.deprecated_entries
+                && a.description == b.description
+                && a.visibility == b.visibility
+        }
+        (TypeBuilder::Union(a), TypeBuilder::Union(b)) => {
+            let a = a.0.borrow();
+            let b = b.0.borrow();
+            a.name == b.name
+                && a.tag == b.tag
+                && a.variants == b.variants
+                && a.description == b.description
+                && a.visibility == b.visibility
+        }
+        (TypeBuilder::Struct(a), TypeBuilder::Struct(b)) => {
+            let a = a.0.borrow();
+            let b = b.0.borrow();
+            a.name == b.name
+                && a.attributes == b.attributes
+                && a.attribute_descriptions == b.attribute_descriptions
+                && a.attribute_padding == b.attribute_padding
+                && a.description == b.description
+                && a.visibility == b.visibility
+        }
+        _ => false,
+    }
+}
+
+/// first property, which is declared by both buses with different values.
+fn conflicting_bus_property(a: &BusData, b: &BusData) -> Option<&'static str> {
+    fn differ<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+        matches!((a, b), (Some(a), Some(b)) if a != b)
+    }
+    if differ(&a.baudrate, &b.baudrate) {
+        Some("baudrate")
+    } else if differ(&a.allowed_baudrates, &b.allowed_baudrates) {
+        Some("allowed baudrates")
+    } else if differ(&a.sample_point, &b.sample_point) {
+        Some("sample point")
+    } else if differ(&a.sjw, &b.sjw) {
+        Some("sjw")
+    } else if differ(&a.controller_clock, &b.controller_clock) {
+        Some("controller clock")
+    } else if differ(&a.connector, &b.connector) {
+        Some("connector")
+    } else if differ(&a.description, &b.description) {
+        Some("description")
+    } else {
+        None
+    }
+}
+
+/// first property, which differs between two object entries with the same name.
+fn conflicting_object_entry_property(a: &ObjectEntryData, b: &ObjectEntryData) -> Option<&'static str> {
+    if a.ty != b.ty {
+        Some("type")
+    } else if a.access != b.access {
+        Some("access")
+    } else if a.default != b.default {
+        Some("default value")
+    } else if a.persistent != b.persistent {
+        Some("persistence")
+    } else if a.index != b.index {
+        Some("index")
+    } else {
+        None
+    }
+}
+
+fn contains_message(messages: &[MessageBuilder], message: &MessageBuilder) -> bool {
+    messages.iter().any(|m| Rc::ptr_eq(&m.0, &message.0))
+}
+
+pub fn merge_network(network_builder: &NetworkBuilder, other: NetworkBuilder) -> Result<()> {
+    if Rc::ptr_eq(&network_builder.0, &other.0) {
+        return Ok(());
+    }
+    // the protocol messages of the other network are replaced by our own.
+    let protocol_messages = [
+        (other.get_req_message(), network_builder.get_req_message()),
+        (other.get_resp_message(), network_builder.get_resp_message()),
+        (other.set_req_message(), network_builder.set_req_message()),
+        (other.set_resp_message(), network_builder.set_resp_message()),
+    ];
+    let is_protocol_message = |message: &MessageBuilder| {
+        protocol_messages
+            .iter()
+            .any(|(o, _)| Rc::ptr_eq(&o.0, &message.0))
+    };
+    let resolve_message = |message: &MessageBuilder| -> MessageBuilder {
+        protocol_messages
+            .iter()
+            .find(|(o, _)| Rc::ptr_eq(&o.0, &message.0))
+            .map(|(_, s)| s.clone())
+            .unwrap_or_else(|| message.clone())
+    };
+
+    let other_data = other.0.borrow();
+    let network_data = network_builder.0.borrow();
+
+    // Check for collisions before modifying anything.
+    let mut merged_types = vec![];
+    for other_type in other_data.types.borrow().iter() {
+        let name = other_type.name();
+        match network_data
+            .types
+            .borrow()
+            .iter()
+            .find(|ty| ty.name() == name)
+        {
+            Some(existing) if same_type_definition(existing, other_type) => (),
+            Some(_) => {
+                return Err(ConfigError::DuplicatedType(format!(
+                    "type {name} is defined differently in both networks"
+                )))
+            }
+            None => merged_types.push(other_type.clone()),
+        }
+    }
+
+    let merged_messages: Vec<MessageBuilder> = other_data
+        .messages
+        .borrow()
+        .iter()
+        .filter(|m| !is_protocol_message(m))
+        .cloned()
+        .collect();
+    for message in &merged_messages {
+        let name = message.0.borrow().name.clone();
+        if network_data
+            .messages
+            .borrow()
+            .iter()
+            .any(|m| m.0.borrow().name == name)
+        {
+            return Err(ConfigError::DuplicatedMessage(format!(
+                "message {name} is defined in both networks"
+            )));
+        }
+    }
+
+    let mut node_pairs: Vec<(NodeBuilder, Option<NodeBuilder>)> = vec![];
+    for other_node in other_data.nodes.borrow().iter() {
+        let other_node_data = other_node.0.borrow();
+        let existing = network_data
+            .nodes
+            .borrow()
+            .iter()
+            .find(|n| n.0.borrow().name == other_node_data.name)
+            .cloned();
+        if let Some(existing) = &existing {
+            let existing_data = existing.0.borrow();
+            for oe in &other_node_data.object_entries {
+                let oe_data = oe.0.borrow();
+                if let Some(existing_oe) = existing_data
+                    .object_entries
+                    .iter()
+                    .find(|e| e.0.borrow().name == oe_data.name)
+                {
+                    if let Some(property) =
+                        conflicting_object_entry_property(&existing_oe.0.borrow(), &oe_data)
+                    {
+                        return Err(ConfigError::DuplicatedObjectEntry(format!(
+                            "object entry {}::{} has a different {property} in both networks",
+                            other_node_data.name, oe_data.name
+                        )));
+                    }
+                }
+            }
+            for stream in &other_node_data.tx_streams {
+                let stream_name = &stream.0.borrow().name;
+                if existing_data
+                    .tx_streams
+                    .iter()
+                    .any(|s| &s.0.borrow().name == stream_name)
+                {
+                    return Err(ConfigError::DuplicatedStream(format!(
+                        "stream {}::{stream_name} is defined in both networks",
+                        other_node_data.name
+                    )));
+                }
+            }
+            for command in &other_node_data.commands {
+                let command_name = &command.0.borrow().name;
+                if existing_data
+                    .commands
+                    .iter()
+                    .any(|c| &c.0.borrow().name == command_name)
+                {
+                    return Err(ConfigError::DuplicatedCommand(format!(
+                        "command {}::{command_name} is defined in both networks",
+                        other_node_data.name
+                    )));
+                }
+            }
+        }
+        drop(other_node_data);
+        node_pairs.push((other_node.clone(), existing));
+    }
+    // the rank of an access level is its position, so levels with the same
+    // name have to be at the same position and must not be described differently.
+    for (rank, (name, description)) in other_data.access_levels.iter().enumerate() {
+        let Some(existing_rank) = network_data.access_levels.iter().position(|(n, _)| n == name) else {
+            continue;
+        };
+        if existing_rank != rank {
+            return Err(ConfigError::DuplicatedAccessLevel(format!(
+                "access level {name} has the rank {existing_rank} in one network and {rank} in the other"
+            )));
+        }
+        if let (Some(existing_description), Some(description)) =
+            (&network_data.access_levels[existing_rank].1, description)
+        {
+            if existing_description != description {
+                return Err(ConfigError::DuplicatedAccessLevel(format!(
+                    "access level {name} is described differently in both networks"
+                )));
+            }
+        }
+    }
+    // buses with the same name are merged, properties declared by
+    // only one of them are kept.
+    for other_bus in other_data.buses.borrow().iter() {
+        let other_bus_data = other_bus.0.borrow();
+        if let Some(existing) = network_data
+            .buses
+            .borrow()
+            .iter()
+            .find(|b| b.0.borrow().name == other_bus_data.name)
+        {
+            if let Some(property) = conflicting_bus_property(&existing.0.borrow(), &other_bus_data) {
+                return Err(ConfigError::DuplicatedBus(format!(
+                    "bus {} has a different {property} in both networks",
+                    other_bus_data.name
+                )));
+            }
+        }
+    }
+    // buses of the merged messages and nodes have to be registered in the other network.
+    let is_registered_bus = |bus: &BusBuilder| other_data.buses.borrow().iter().any(|b| Rc::ptr_eq(&b.0, &bus.0));
+    let unregistered_bus = merged_messages
+        .iter()
+        .filter_map(|m| m.0.borrow().bus.clone())
+        .chain(other_data.nodes.borrow().iter().flat_map(|n| n.0.borrow().buses.clone()))
+        .find(|bus| !is_registered_bus(bus));
+    if let Some(bus) = unregistered_bus {
+        return Err(ConfigError::BusNotFound(format!(
+            "bus {} of the merged network was not registered",
+            bus.0.borrow().name
+        )));
+    }
+    let other_buses = other_data.buses.borrow().clone();
+    let other_access_levels = other_data.access_levels.clone();
+    let other_uds_tester = other_data.uds_tester.clone();
+    let other_variants = other_data.variants.clone();
+    let other_node_groups: Vec<(String, Option<String>, Vec<String>)> = other_data
+        .node_groups
+        .iter()
+        .map(|group| {
+            let group_data = group.0.borrow();
+            let nodes = group_data.nodes.iter().map(|n| n.0.borrow().name.clone()).collect();
+            (group_data.name.clone(), group_data.description.clone(), nodes)
+        })
+        .collect();
+    // paths with the same name have to describe the same path.
+    let mut other_latency_paths = vec![];
+    for path in &other_data.latency_paths {
+        let path_data = path.0.borrow();
+        match network_data
+            .latency_paths
+            .iter()
+            .find(|p| p.0.borrow().name == path_data.name)
+        {
+            Some(existing) => {
+                let existing_data = existing.0.borrow();
+                if existing_data.hops != path_data.hops || existing_data.budget != path_data.budget {
+                    return Err(ConfigError::InvalidLatencyPath(format!(
+                        "latency path {} is defined differently in both networks",
+                        path_data.name
+                    )));
+                }
+            }
+            None => other_latency_paths.push(path.clone()),
+        }
+    }
+    drop(network_data);
+    drop(other_data);
+
+    // Merge
+    let resolve_node = |node: &NodeBuilder| -> NodeBuilder {
+        node_pairs
+            .iter()
+            .find(|(o, _)| Rc::ptr_eq(&o.0, &node.0))
+            .and_then(|(_, existing)| existing.clone())
+            .unwrap_or_else(|| node.clone())
+    };
+    let resolve_nodes = |nodes: &[NodeBuilder]| -> Vec<NodeBuilder> {
+        let mut resolved: Vec<NodeBuilder> = vec![];
+        for node in nodes.iter().map(resolve_node) {
+            if !resolved.iter().any(|n| Rc::ptr_eq(&n.0, &node.0)) {
+                resolved.push(node);
+            }
+        }
+        resolved
+    };
+
+    let mut bus_mapping = vec![];
+    for other_bus in &other_buses {
+        let bus_data = other_bus.0.borrow();
+        let existing = network_builder
+            .0
+            .borrow()
+            .buses
+            .borrow()
+            .iter()
+            .find(|b| b.0.borrow().name == bus_data.name)
+            .cloned();
+        let bus = match existing {
+            Some(bus) => {
+                let mut existing_data = bus.0.borrow_mut();
+                existing_data.baudrate = existing_data.baudrate.or(bus_data.baudrate);
+                existing_data.allowed_baudrates = existing_data
+                    .allowed_baudrates
+                    .take()
+                    .or_else(|| bus_data.allowed_baudrates.clone());
+                existing_data.sample_point = existing_data.sample_point.or(bus_data.sample_point);
+                existing_data.sjw = existing_data.sjw.or(bus_data.sjw);
+                existing_data.controller_clock =
+                    existing_data.controller_clock.or(bus_data.controller_clock);
+                existing_data.connector = existing_data.connector.take().or_else(|| bus_data.connector.clone());
+                existing_data.description =
+                    existing_data.description.take().or_else(|| bus_data.description.clone());
+                drop(existing_data);
+                bus
+            }
+            None => {
+                let bus = network_builder.create_bus(&bus_data.name, bus_data.baudrate);
+                if let Some(sample_point) = bus_data.sample_point {
+                    bus.set_sample_point(sample_point);
+                }
+                if let Some(sjw) = bus_data.sjw {
+                    bus.set_sjw(sjw);
+                }
+                if let Some(baudrates) = &bus_data.allowed_baudrates {
+                    bus.set_allowed_baudrates(baudrates);
+                }
+                if let Some(clock_hz) = bus_data.controller_clock {
+                    bus.set_controller_clock(clock_hz);
+                }
+                if let Some(description) = &bus_data.description {
+                    bus.add_description(description);
+                }
+                if let Some(connector) = &bus_data.connector {
+                    bus.set_connector(connector);
+                }
+                bus
+            }
+        };
+        bus_mapping.push((other_bus.clone(), bus));
+    }
+    let resolve_bus = |bus: &BusBuilder| -> Result<BusBuilder> {
+        bus_mapping
+            .iter()
+            .find(|(o, _)| Rc::ptr_eq(&o.0, &bus.0))
+            .map(|(_, b)| b.clone())
+            .ok_or_else(|| {
+                ConfigError::BusNotFound(format!(
+                    "bus {} of the merged network was not registered",
+                    bus.0.borrow().name
+                ))
+            })
+    };
+
+    network_builder
+        .0
+        .borrow()
+        .types
+        .borrow_mut()
+        .extend(merged_types);
+
+    {
+        let mut network_data = network_builder.0.borrow_mut();
+        for variant in other_variants {
+            if !network_data.variants.contains(&variant) {
+                network_data.variants.push(variant);
+            }
+        }
+        if network_data.uds_tester.is_none() {
+            network_data.uds_tester = other_uds_tester;
+        }
+        for level in other_access_levels {
+            match network_data.access_levels.iter_mut().find(|l| l.0 == level.0) {
+                Some(existing) => {
+                    if existing.1.is_none() {
+                        existing.1 = level.1;
+                    }
+                }
+                None => network_data.access_levels.push(level),
+            }
+        }
+    }
+
+    for message in &merged_messages {
+        let mut message_data = message.0.borrow_mut();
+        message_data.network_builder = network_builder.clone();
+        message_data.bus = message_data.bus.as_ref().map(resolve_bus).transpose()?;
+        message_data.receivers = resolve_nodes(&message_data.receivers);
+        message_data.transmitters = resolve_nodes(&message_data.transmitters);
+    }
+    network_builder
+        .0
+        .borrow()
+        .messages
+        .borrow_mut()
+        .extend(merged_messages.iter().cloned());
+
+    for (other_node, existing) in &node_pairs {
+        let mut other_node_data = other_node.0.borrow_mut();
+        let target = existing.clone().unwrap_or_else(|| other_node.clone());
+        for stream in &other_node_data.tx_streams {
+            stream.0.borrow_mut().tx_node = target.clone();
+        }
+        for rx_stream in &other_node_data.rx_streams {
+            rx_stream.0.borrow_mut().rx_node = target.clone();
+        }
+        for command in &other_node_data.commands {
+            command.0.borrow_mut().tx_node = target.clone();
+        }
+        for group in &other_node_data.object_entry_groups {
+            group.0.borrow_mut().node = target.clone();
+        }
+        for channel in &other_node_data.transport_channels {
+            let mut channel_data = channel.0.borrow_mut();
+            if Rc::ptr_eq(&channel_data.tx_node.0, &other_node.0) {
+                channel_data.tx_node = target.clone();
+            }
+            if Rc::ptr_eq(&channel_data.rx_node.0, &other_node.0) {
+                channel_data.rx_node = target.clone();
+            }
+        }
+        let rx_messages: Vec<MessageBuilder> =
+            other_node_data.rx_messages.iter().map(resolve_message).collect();
+        let tx_messages: Vec<MessageBuilder> =
+            other_node_data.tx_messages.iter().map(resolve_message).collect();
+        let rx_timeouts: Vec<(MessageBuilder, Duration)> = other_node_data
+            .rx_timeouts
+            .iter()
+            .map(|(message, timeout)| (resolve_message(message), *timeout))
+            .collect();
+        let buses: Vec<_> = other_node_data.buses.iter().map(resolve_bus).collect::<Result<_>>()?;
+        match existing {
+            Some(existing) => {
+                let mut node_data = existing.0.borrow_mut();
+                if node_data.description.is_none() {
+                    node_data.description = other_node_data.description.take();
+                }
+                for message in rx_messages {
+                    if !contains_message(&node_data.rx_messages, &message) {
+                        node_data.rx_messages.push(message);
+                    }
+                }
+                for message in tx_messages {
+                    if !contains_message(&node_data.tx_messages, &message) {
+                        node_data.tx_messages.push(message);
+                    }
+                }
+                for (message, timeout) in rx_timeouts {
+                    if !node_data.rx_timeouts.iter().any(|(m, _)| Rc::ptr_eq(&m.0, &message.0)) {
+                        node_data.rx_timeouts.push((message, timeout));
+                    }
+                }
+                node_data.listen_only |= other_node_data.listen_only;
+                for supervision in other_node_data.supervisions.drain(..) {
+                    if !node_data.supervisions.contains(&supervision) {
+                        node_data.supervisions.push(supervision);
+                    }
+                }
+                for oe in other_node_data.object_entries.drain(..) {
+                    let name = oe.0.borrow().name.clone();
+                    if !node_data
+                        .object_entries
+                        .iter()
+                        .any(|e| e.0.borrow().name == name)
+                    {
+                        node_data.object_entries.push(oe);
+                    }
+                }
+                node_data.tx_streams.append(&mut other_node_data.tx_streams);
+                node_data.rx_streams.append(&mut other_node_data.rx_streams);
+                node_data.commands.append(&mut other_node_data.commands);
+                node_data
+                    .object_entry_groups
+                    .append(&mut other_node_data.object_entry_groups);
+                node_data
+                    .extern_commands
+                    .append(&mut other_node_data.extern_commands);
+                node_data
+                    .transport_channels
+                    .append(&mut other_node_data.transport_channels);
+                if let Some(services) = other_node_data.uds_services.take() {
+                    let uds_services = node_data.uds_services.get_or_insert_with(Vec::new);
+                    for service in services {
+                        if !uds_services.contains(&service) {
+                            uds_services.push(service);
+                        }
+                    }
+                }
+                for bus in buses {
+                    if !node_data.buses.iter().any(|b| Rc::ptr_eq(&b.0, &bus.0)) {
+                        node_data.buses.push(bus);
+                    }
+                }
+            }
+            None => {
+                other_node_data.network_builder = network_builder.clone();
+                other_node_data.rx_messages = rx_messages;
+                other_node_data.tx_messages = tx_messages;
+                other_node_data.rx_timeouts = rx_timeouts;
+                other_node_data.buses = buses;
+                drop(other_node_data);
+                for (_, protocol_message) in &protocol_messages {
+                    let mut message_data = protocol_message.0.borrow_mut();
+                    let node = other_node.clone();
+                    if contains_message(&node.0.borrow().rx_messages, protocol_message) {
+                        message_data.receivers.push(node.clone());
+                    }
+                    if contains_message(&node.0.borrow().tx_messages, protocol_message) {
+                        message_data.transmitters.push(node);
+                    }
+                }
+                network_builder
+                    .0
+                    .borrow()
+                    .nodes
+                    .borrow_mut()
+                    .push(other_node.clone());
+            }
+        }
+    }
+    // groups with the same name are joined, all of their nodes are merged by now.
+    for (name, description, nodes) in other_node_groups {
+        let group = network_builder.create_group(&name);
+        if let Some(description) = description {
+            if group.0.borrow().description.is_none() {
+                group.add_description(&description);
+            }
+        }
+        for node in nodes {
+            group.add_node(&node);
+        }
+    }
+    network_builder
+        .0
+        .borrow_mut()
+        .latency_paths
+        .extend(other_latency_paths);
+    Ok(())
+}
@@ -0,0 +1,447 @@
+use crate::{
+    config::{
+        bus::BusRef,
+        encoding::{CompositeSignalEncoding, MultiplexedSignalEncoding, PrimitiveSignalEncoding},
+        make_config_ref,
+        message::MessageUsage,
+        signal::Signal,
+        stream::keyframe_signal_name,
+        E2EProfile, E2EProtection, Lifecycle, Message, MessageEncoding, MessageParams, MessageId, OverflowPolicy,
+        SignalGroup, SignalRef, SignalType, Type, TypeRef, TypeSignalEncoding, Visibility,
+    },
+    errors::{self, Result},
+};
+
+/// Everything required to construct a message, extracted from its builder.
+/// Drafts don't reference any builders, so they can be assembled
+/// independently of each other (and in parallel with the parallel feature).
+pub(crate) struct MessageDraft {
+    pub name: String,
+    pub description: Option<String>,
+    pub id: MessageId,
+    pub format: DraftFormat,
+    pub visibility: Visibility,
+    pub bus: BusRef,
+    pub e2e: Option<E2EProfile>,
+    pub signal_groups: Vec<(String, Vec<String>)>,
+    pub lifecycle: Lifecycle,
+    /// usages, which don't reference streams, commands or transport channels.
+    pub usage: Option<MessageUsage>,
+    /// dlc set with `MessageBuilder::set_dlc`.
+    pub dlc: Option<u8>,
+    pub fill: u8,
+    /// size of the sender id signal of a shared message.
+    pub sender_id_size: Option<u8>,
+    /// true for messages of delta encoded streams, which mark their keyframes.
+    pub keyframe: bool,
+}
+
+pub(crate) enum DraftFormat {
+    /// signals and whether their offset is pinned.
+    Signals(Vec<(Signal, bool)>),
+    /// (type, value name, overflow policy) of every value.
+    Types(Vec<(TypeRef, String, OverflowPolicy)>),
+    Empty,
+}
+
+fn build_attribute(
+    ty: &TypeRef,
+    name: &str,
+    offset: &mut usize,
+    prefix: &str,
+    description: Option<&str>,
+    overflow: OverflowPolicy,
+    signals: &mut Vec<SignalRef>,
+) -> TypeSignalEncoding {
+    match ty as &Type {
+        Type::Primitive(signal_type) => {
+            let signal = make_config_ref(Signal::new(
+                &format!("{prefix}_{name}"),
+                description,
+                signal_type.clone(),
+                *offset,
+            ).with_overflow(overflow));
+            signals.push(signal.clone());
+            *offset += signal.size() as usize;
+            TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
+                name.to_owned(),
+                ty.clone(),
+                signal,
+            ))
+        }
+        Type::Struct {
+            name: struct_name,
+            description: _,
+            attribs,
+            attrib_info,
+            visibility: _,
+        } => {
+            let mut attributes = vec![];
+            let start = *offset;
+            for ((attrib_name, attrib_type), info) in attribs.iter().zip(attrib_info) {
+                // padding is relative to the start of the struct.
+                let padding = info.padding().bits(*offset - start);
+                *offset += padding;
+                attributes.push(build_attribute(
+                    attrib_type,
+                    attrib_name,
+                    offset,
+                    &format!("{prefix}_{struct_name}"),
+                    info.description(),
+                    overflow,
+                    signals,
+                ).with_padding(padding));
+            }
+            TypeSignalEncoding::Composite(CompositeSignalEncoding::new(
+                name.to_owned(),
+                attributes,
+                ty.clone(),
+            ))
+        }
+        Type::Enum {
+            name: enum_name,
+            description: _,
+            size,
+            entries: _,
+            entry_info: _,
+            visibility: _,
+        } => {
+            let size = *size;
+            let signal = make_config_ref(Signal::new(
+                &format!("{prefix}_{enum_name}"),
+                description,
+                SignalType::UnsignedInt { size },
+                *offset,
+            ));
+            signals.push(signal.clone());
+            *offset += signal.size() as usize;
+            TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
+                name.to_owned(),
+                ty.clone(),
+                signal,
+            ))
+        }
+        Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
+        Type::Union {
+            name: union_name,
+            tag,
+            variants,
+            ..
+        } => {
+            let union_prefix = format!("{prefix}_{union_name}");
+            let TypeSignalEncoding::Primitive(tag_encoding) = build_attribute(
+                tag,
+                "tag",
+                offset,
+                &union_prefix,
+                None,
+                OverflowPolicy::default(),
+                signals,
+            ) else {
+                unreachable!("tags are enums")
+            };
+            let Type::Enum { entries, .. } = tag as &Type else {
+                unreachable!("tags are enums")
+            };
+            // all variants start right after the tag.
+            let start = *offset;
+            let mut end = start;
+            let mut variant_encodings = vec![];
+            for (entry, variant_ty) in variants {
+                let mut variant_offset = start;
+                let variant_prefix = match variant_ty as &Type {
+                    Type::Primitive(_) => union_prefix.clone(),
+                    _ => format!("{union_prefix}_{entry}"),
+                };
+                let encoding = build_attribute(
+                    variant_ty,
+                    entry,
+                    &mut variant_offset,
+                    &variant_prefix,
+                    None,
+                    overflow,
+                    signals,
+                );
+                end = end.max(variant_offset);
+                let value = entries
+                    .iter()
+                    .find(|(name, _)| name == entry)
+                    .map(|(_, value)| *value)
+                    .expect("variants are checked while building the union");
+                variant_encodings.push((entry.clone(), value, encoding));
+            }
+            *offset = end;
+            TypeSignalEncoding::Multiplexed(MultiplexedSignalEncoding::new(
+                name.to_owned(),
+                tag_encoding,
+                variant_encodings,
+                ty.clone(),
+            ))
+        }
+    }
+}
+
+/// Lowest offset, at which {size} bits don't overlap any of the signals,
+/// which are sorted by their offset.
+fn first_free_offset(sorted_signals: &[&SignalRef], size: usize) -> usize {
+    let mut offset = 0;
+    for signal in sorted_signals {
+        if signal.byte_offset() >= offset + size {
+            break;
+        }
+        offset = offset.max(signal.byte_offset() + signal.size() as usize);
+    }
+    offset
+}
+
+fn assemble_message(draft: MessageDraft) -> Result<Message> {
+    let (mut signals, encoding) = match draft.format {
+        DraftFormat::Signals(signal_data) => {
+            let mut offset: usize = 0;
+            let mut signals = vec![];
+            for (signal_data, pinned) in signal_data {
+                if pinned {
+                    offset = signal_data.byte_offset();
+                }
+                let size = signal_data.size() as usize;
+                signals.push(make_config_ref(Signal {
+                    name: format!("{}_{}", draft.name, signal_data.name),
+                    offset,
+                    ..signal_data
+                }));
+                offset += size;
+            }
+            (signals, None)
+        }
+        DraftFormat::Types(values) => {
+            let mut attributes: Vec<TypeSignalEncoding> = vec![];
+            let mut signals = vec![];
+            let mut offset: usize = 0;
+            for (type_ref, var_name, overflow) in &values {
+                attributes.push(build_attribute(
+                    type_ref,
+                    var_name,
+                    &mut offset,
+                    "value_name",
+                    None,
+                    *overflow,
+                    &mut signals,
+                ));
+            }
+            (signals, Some(MessageEncoding::new(attributes)))
+        }
+        DraftFormat::Empty => (vec![], None),
+    };
+    // the keyframe flag directly follows the values of the stream.
+    if draft.keyframe {
+        let offset = signals
+            .iter()
+            .map(|s| s.byte_offset() + s.size() as usize)
+            .max()
+            .unwrap_or(0);
+        signals.push(make_config_ref(Signal::new(
+            &keyframe_signal_name(&draft.name),
+            Some("set for keyframes of a delta encoded stream"),
+            SignalType::UnsignedInt { size: 1 },
+            offset,
+        )));
+    }
+
+    let mut max_bit = 0;
+    for signal in &signals {
+        let signal_max_bit = signal.byte_offset() + signal.size() as usize;
+        max_bit = max_bit.max(signal_max_bit);
+    }
+    if max_bit > 64 {
+        let signal = signals
+            .iter()
+            .find(|s| s.byte_offset() + s.size() as usize > 64)
+            .unwrap();
+        return Err(errors::ConfigError::MessageTooLong(format!(
+            "message {} requires {max_bit} bits, but a frame has at most 64 bits (signal {} occupies bits {}..{})",
+            draft.name,
+            signal.name(),
+            signal.byte_offset(),
+            signal.byte_offset() + signal.size() as usize
+        )));
+    }
+    let mut sorted_signals: Vec<&SignalRef> = signals.iter().collect();
+    sorted_signals.sort_by_key(|s| s.byte_offset());
+    // signals of different variants of a union share their bits.
+    let exclusive = |a: &SignalRef, b: &SignalRef| {
+        let multiplexor = |s: &SignalRef| {
+            encoding
+                .as_ref()
+                .and_then(|encoding| encoding.multiplexor_of(s.name()))
+                .map(|(tag, value)| (tag.name().to_owned(), value))
+        };
+        match (multiplexor(a), multiplexor(b)) {
+            (Some((a_tag, a_value)), Some((b_tag, b_value))) => a_tag == b_tag && a_value != b_value,
+            _ => false,
+        }
+    };
+    for (i, a) in sorted_signals.iter().enumerate() {
+        let a_end = a.byte_offset() + a.size() as usize;
+        let overlapping = sorted_signals[i + 1..]
+            .iter()
+            .find(|b| b.byte_offset() < a_end && !exclusive(a, b));
+        if let Some(b) = overlapping {
+            return Err(errors::ConfigError::OverlappingSignals(format!(
+                "signals {} (bits {}..{a_end}) and {} (bits {}..{}) of message {} overlap",
+                a.name(),
+                a.byte_offset(),
+                b.name(),
+                b.byte_offset(),
+                b.byte_offset() + b.size() as usize,
+                draft.name
+            )));
+        }
+    }
+    // the sender id takes the first free bits, which are left by
+    // the (possibly pinned) signals of the message.
+    let sender_id = match draft.sender_id_size {
+        Some(size) => {
+            let offset = first_free_offset(&sorted_signals, size as usize);
+            let sender_id = make_config_ref(Signal::new(
+                &format!("{}_sender_id", draft.name),
+                Some("node id of the transmitter"),
+                SignalType::UnsignedInt { size },
+                offset,
+            ));
+            max_bit = max_bit.max(offset + size as usize);
+            if max_bit > 64 {
+                return Err(errors::ConfigError::MessageTooLong(format!(
+                    "message {} has no space left for its {size} bit sender id",
+                    draft.name
+                )));
+            }
+            Some(sender_id)
+        }
+        None => None,
+    };
+    if let Some(sender_id) = &sender_id {
+        signals.push(sender_id.clone());
+    }
+    let e2e = match draft.e2e {
+        Some(profile) => {
+            let counter = make_config_ref(
+                Signal::new(
+                    &format!("{}_e2e_counter", draft.name),
+                    Some("alive counter"),
+                    SignalType::UnsignedInt { size: 4 },
+                    max_bit,
+                )
+                .with_overflow(OverflowPolicy::Wrap),
+            );
+            // the crc always occupies a whole byte.
+            let crc_offset = (max_bit + 4).div_ceil(8) * 8;
+            let crc = make_config_ref(Signal::new(
+                &format!("{}_e2e_crc", draft.name),
+                Some("CRC-8 of the payload"),
+                SignalType::UnsignedInt { size: 8 },
+                crc_offset,
+            ));
+            max_bit = crc_offset + 8;
+            if max_bit > 64 {
+                return Err(errors::ConfigError::InvalidRange(format!(
+                    "message {} exceeds 8 bytes with e2e protection",
+                    draft.name
+                )));
+            }
+            signals.push(counter.clone());
+            signals.push(crc.clone());
+            Some(E2EProtection::new(profile, crc, counter))
+        }
+        None => None,
+    };
+    let required_dlc = max_bit.div_ceil(8) as u8;
+    let dlc = match draft.dlc {
+        Some(dlc) if dlc > 8 => {
+            return Err(errors::ConfigError::InvalidRange(format!(
+                "dlc {dlc} of message {} exceeds 8 bytes",
+                draft.name
+            )));
+        }
+        Some(dlc) if dlc < required_dlc => {
+            return Err(errors::ConfigError::MessageTooLong(format!(
+                "message {} requires {required_dlc} bytes, but its dlc is set to {dlc}",
+                draft.name
+            )));
+        }
+        Some(dlc) => dlc,
+        None => required_dlc,
+    };
+
+    let mut signal_groups: Vec<SignalGroup> = vec![];
+    for (group_name, signal_names) in &draft.signal_groups {
+        if signal_groups.iter().any(|group| group.name() == group_name) {
+            return Err(errors::ConfigError::InvalidSignalGroup(format!(
+                "signal group {group_name} of message {} is defined twice",
+                draft.name
+            )));
+        }
+        if signal_names.is_empty() {
+            return Err(errors::ConfigError::InvalidSignalGroup(format!(
+                "signal group {group_name} of message {} is empty",
+                draft.name
+            )));
+        }
+        let group_signals = signal_names
+            .iter()
+            .map(|signal_name| {
+                signals
+                    .iter()
+                    .find(|s| {
+                        // signals of signal formats are prefixed with the message name.
+                        s.name() == signal_name
+                            || s.name() == format!("{}_{signal_name}", draft.name)
+                    })
+                    .cloned()
+                    .ok_or_else(|| {
+                        errors::ConfigError::InvalidSignalGroup(format!(
+                            "signal group {group_name} of message {} references the undefined signal {signal_name}",
+                            draft.name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<SignalRef>>>()?;
+        signal_groups.push(SignalGroup::new(group_name.clone(), group_signals));
+    }
+
+    let message = Message::new(MessageParams {
+        name: draft.name,
+        description: draft.description,
+        id: draft.id,
+        encoding,
+        signals,
+        visibility: draft.visibility,
+        dlc,
+        bus: draft.bus,
+        e2e,
+        lifecycle: draft.lifecycle,
+        signal_groups,
+        fill: draft.fill,
+        sender_id,
+    });
+    Ok(match draft.usage {
+        Some(usage) => message.with_usage(usage),
+        None => message,
+    })
+}
+
+/// Assembles the messages in the order of the drafts,
+/// the first failing draft determines the error.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn assemble_messages(drafts: Vec<MessageDraft>) -> Result<Vec<Message>> {
+    drafts.into_iter().map(assemble_message).collect()
+}
+
+/// Assembles the messages in the order of the drafts on all available cores,
+/// the first failing draft determines the error.
+#[cfg(feature = "parallel")]
+pub(crate) fn assemble_messages(drafts: Vec<MessageDraft>) -> Result<Vec<Message>> {
+    use rayon::prelude::*;
+    // rayon returns any error, collecting all results keeps the first one.
+    let messages: Vec<Result<Message>> = drafts.into_par_iter().map(assemble_message).collect();
+    messages.into_iter().collect()
+}
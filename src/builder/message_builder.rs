@@ -1,11 +1,11 @@
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
 
 use crate::{
-    config::{signal::Signal, Visibility},
+    config::{signal::Signal, E2EProfile, SignalType, Visibility},
     errors,
 };
 
-use super::{bus::BusBuilder, make_builder_ref, BuilderRef, NetworkBuilder, NodeBuilder, stream_builder::StreamBuilder, CommandBuilder};
+use super::{bus::BusBuilder, make_builder_ref, BuilderRef, DirtyFlag, NetworkBuilder, NodeBuilder, stream_builder::StreamBuilder, CommandBuilder, TransportChannelBuilder};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum MessagePriority {
@@ -48,8 +48,12 @@ pub enum MessageBuilderUsage {
     Stream(StreamBuilder),
     CommandReq(CommandBuilder),
     CommandResp(CommandBuilder),
+    TransportChannel(TransportChannelBuilder),
     Configuration,
     Heartbeat,
+    Emergency,
+    Debug{interval : Option<Duration>},
+    Bootloader,
     External{interval : Option<Duration>},
 }
 
@@ -77,6 +81,22 @@ pub struct MessageData {
     pub visibility: Visibility,
     pub bus: Option<BusBuilder>,
     pub usage : MessageBuilderUsage,
+    pub e2e: Option<E2EProfile>,
+    /// (pgn, priority), the id is derived while building.
+    pub j1939: Option<(u32, u8)>,
+    /// variants containing the message, None if it's part of all variants.
+    pub variants: Option<Vec<String>>,
+    pub introduced_in: Option<String>,
+    pub deprecated_in: Option<String>,
+    /// (group name, signal names)
+    pub signal_groups: Vec<(String, Vec<String>)>,
+    /// dlc, which overrides the length required by the signals.
+    pub dlc: Option<u8>,
+    /// fill pattern of the bits, which aren't occupied by signals.
+    pub fill: u8,
+    /// transmitted by several nodes, which identify themselves
+    /// with a generated sender_id signal.
+    pub shared: bool,
 }
 
 #[derive(Debug)]
@@ -89,7 +109,13 @@ pub enum MessageFormat {
 #[derive(Clone, Debug)]
 pub struct MessageSignalFormatBuilder(pub BuilderRef<MessageSignalFormatData>);
 #[derive(Debug)]
-pub struct MessageSignalFormatData(pub Vec<Signal>);
+pub struct MessageSignalFormatData {
+    pub signals: Vec<Signal>,
+    /// signals keep their offsets instead of being packed in order.
+    pub pinned: bool,
+    /// names of the signals, which are pinned at their offset (see `add_signal_at`).
+    pub pinned_signals: Vec<String>,
+}
 #[derive(Clone, Debug)]
 pub struct MessageTypeFormatBuilder(pub BuilderRef<MessageTypeFormatData>);
 #[derive(Debug)]
@@ -108,9 +134,21 @@ impl MessagePriority {
 }
 
 impl MessageBuilder {
+    pub(crate) fn dirty_flags(&self) -> Vec<Rc<dyn DirtyFlag>> {
+        let mut flags: Vec<Rc<dyn DirtyFlag>> = vec![self.0.clone()];
+        match &self.0.borrow().format {
+            MessageFormat::Signals(format) => flags.push(format.0.clone()),
+            MessageFormat::Types(format) => flags.push(format.0.clone()),
+            MessageFormat::Empty => (),
+        }
+        flags
+    }
+    /// Modified since the last build of the network.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_flags().iter().any(|flag| flag.is_dirty())
+    }
     pub fn new(name: &str, network_builder: &NetworkBuilder, expected_interval : Option<Duration>) -> MessageBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating message {name}");
+        log::trace!("Creating message {name}");
         MessageBuilder(make_builder_ref(MessageData {
             name: name.to_owned(),
             description: None,
@@ -122,17 +160,26 @@ impl MessageBuilder {
             receivers : vec![],
             transmitters : vec![],
             usage : MessageBuilderUsage::External { interval: expected_interval },
+            e2e: None,
+            j1939: None,
+            variants: None,
+            introduced_in: None,
+            deprecated_in: None,
+            signal_groups: vec![],
+            dlc: None,
+            fill: 0x00,
+            shared: false,
             // usage,
         }))
     }
     pub fn assign_bus(&self, bus_name: &str) -> BusBuilder {
         let mut message_data = self.0.borrow_mut();
         if message_data.bus.is_some() {
-            #[cfg(feature = "logging_info")]
-            println!("WARNING: reassiged bus of message : {}
-                     but messages can only be assigned to one bus,
-                     if splitting is required it is done automatically by 
-                     the id, filter and load balancing code!", message_data.name);
+            log::warn!(
+                "reassigned the bus of message {}, messages can only be assigned to one bus, \
+                 splitting is done automatically by the id, filter and load balancing",
+                message_data.name
+            );
         }
         let network_data = message_data.network_builder.0.borrow_mut();
         let bus = network_data
@@ -154,21 +201,60 @@ impl MessageBuilder {
             }
         }
     }
-    pub fn __assign_to_stream(&self, stream : &StreamBuilder) {
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn transmitters(&self) -> Vec<NodeBuilder> {
+        self.0.borrow().transmitters.clone()
+    }
+    pub fn receivers(&self) -> Vec<NodeBuilder> {
+        self.0.borrow().receivers.clone()
+    }
+    pub(crate) fn assign_to_stream(&self, stream : &StreamBuilder) {
         self.0.borrow_mut().usage = MessageBuilderUsage::Stream(stream.clone());
     }
-    pub fn __assign_to_command_resp(&self, command : &CommandBuilder) {
+    pub(crate) fn assign_to_command_resp(&self, command : &CommandBuilder) {
         self.0.borrow_mut().usage = MessageBuilderUsage::CommandResp(command.clone());
     }
-    pub fn __assign_to_command_req(&self, command : &CommandBuilder) {
+    pub(crate) fn assign_to_command_req(&self, command : &CommandBuilder) {
         self.0.borrow_mut().usage = MessageBuilderUsage::CommandReq(command.clone());
     }
-    pub fn __assign_to_configuration(&self) {
+    pub(crate) fn assign_to_transport_channel(&self, channel : &TransportChannelBuilder) {
+        self.0.borrow_mut().usage = MessageBuilderUsage::TransportChannel(channel.clone());
+    }
+    pub(crate) fn assign_to_configuration(&self) {
         self.0.borrow_mut().usage = MessageBuilderUsage::Configuration;
     }
-    pub fn __assign_to_heartbeat(&self) {
+    pub(crate) fn assign_to_heartbeat(&self) {
         self.0.borrow_mut().usage = MessageBuilderUsage::Heartbeat;
     }
+    /// Marks the message as an emergency (e.g. a fault or a safety shutdown),
+    /// which wins the arbitration against every other message without a fixed id.
+    /// Overwrites the priority of previously set id templates.
+    pub fn set_emergency(&self) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.usage = MessageBuilderUsage::Emergency;
+        message_data.id = match message_data.id {
+            MessageIdTemplate::AnyStd(_) => MessageIdTemplate::AnyStd(MessagePriority::Realtime),
+            MessageIdTemplate::AnyExt(_) => MessageIdTemplate::AnyExt(MessagePriority::Realtime),
+            MessageIdTemplate::AnyAny(_) => MessageIdTemplate::AnyAny(MessagePriority::Realtime),
+            ref id => id.clone(),
+        };
+    }
+    /// Marks the message as debug output, which isn't required during operation.
+    /// The expected interval of the message is kept.
+    pub fn set_debug(&self) {
+        let mut message_data = self.0.borrow_mut();
+        let interval = match message_data.usage {
+            MessageBuilderUsage::External { interval } | MessageBuilderUsage::Debug { interval } => interval,
+            _ => None,
+        };
+        message_data.usage = MessageBuilderUsage::Debug { interval };
+    }
+    /// Marks the message as part of a bootloader (firmware update) protocol.
+    pub fn set_bootloader(&self) {
+        self.0.borrow_mut().usage = MessageBuilderUsage::Bootloader;
+    }
     pub fn hide(&self) {
         let mut message_data = self.0.borrow_mut();
         message_data.visibility = Visibility::Static;
@@ -181,6 +267,27 @@ impl MessageBuilder {
         let mut message_data = self.0.borrow_mut();
         message_data.id = MessageIdTemplate::ExtId(id);
     }
+    /// Restricts the message to the given variants (see NetworkBuilder::build_variant).
+    pub fn only_in(&self, variants: &[&str]) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.variants = Some(variants.iter().map(|v| v.to_string()).collect());
+    }
+    /// Version of the configuration in which the message was added (e.g. "v2.3"),
+    /// firmware targeting an older version can't transmit or receive it.
+    pub fn introduced_in(&self, version: &str) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.introduced_in = Some(version.to_owned());
+    }
+    pub fn deprecated_in(&self, version: &str) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.deprecated_in = Some(version.to_owned());
+    }
+    /// Derives the extended id from a J1939 parameter group number,
+    /// the priority and the id of the transmitting node as source address.
+    pub fn set_j1939_id(&self, pgn: u32, priority: u8) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.j1939 = Some((pgn, priority));
+    }
     pub fn set_any_std_id(&self, priority: MessagePriority) {
         let mut message_data = self.0.borrow_mut();
         message_data.id = MessageIdTemplate::AnyStd(priority);
@@ -205,6 +312,43 @@ impl MessageBuilder {
         let mut message_data = self.0.borrow_mut();
         message_data.description = Some(name.to_owned());
     }
+    /// Groups signals of the message, which have to be updated atomically.
+    /// The signals are referenced by the names they were added with to the
+    /// signal format (or by their full names) and are resolved while building.
+    pub fn add_signal_group(&self, name: &str, signals: &[&str]) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.signal_groups.push((
+            name.to_owned(),
+            signals.iter().map(|s| s.to_string()).collect(),
+        ));
+    }
+    /// Appends a 4 bit alive counter and a CRC-8 signal to the
+    /// layout of the message, which are checked by the receivers.
+    pub fn enable_e2e_protection(&self, profile: E2EProfile) {
+        let mut message_data = self.0.borrow_mut();
+        message_data.e2e = Some(profile);
+    }
+    /// Forces the dlc of the message (e.g. 8 for receivers, which require
+    /// fixed length frames), it must not be smaller than the signals require.
+    pub fn set_dlc(&self, dlc: u8) {
+        self.0.borrow_mut().dlc = Some(dlc);
+    }
+    /// Byte pattern of the bits, which aren't occupied by signals (0x00 by default).
+    pub fn set_fill(&self, fill: u8) {
+        self.0.borrow_mut().fill = fill;
+    }
+    /// Every node transmits the message under the same id (e.g. a common
+    /// node_status frame), a sender_id signal containing the node id of
+    /// the transmitter occupies the first bits, which are left free by
+    /// the (possibly pinned) signals of the message.
+    pub fn shared_by_nodes(&self, node_names: &[&str]) {
+        for node_name in node_names {
+            if !self.transmitters().iter().any(|node| node.0.borrow().name == *node_name) {
+                self.add_transmitter(node_name);
+            }
+        }
+        self.0.borrow_mut().shared = true;
+    }
     pub fn add_transmitter(&self, node_name: &str) {
         // check if node with {name} exists.
         let message_data = self.0.borrow();
@@ -257,19 +401,87 @@ impl MessageBuilder {
 
 impl MessageSignalFormatBuilder {
     pub fn new() -> MessageSignalFormatBuilder {
-        MessageSignalFormatBuilder(make_builder_ref(MessageSignalFormatData(vec![])))
+        MessageSignalFormatBuilder(make_builder_ref(MessageSignalFormatData {
+            signals: vec![],
+            pinned: false,
+            pinned_signals: vec![],
+        }))
     }
     pub fn add_signal(&self, signal: Signal) -> errors::Result<()> {
         let mut builder_data = self.0.borrow_mut();
-        if builder_data.0.iter().any(|s| s.name() == signal.name()) {
+        if builder_data.signals.iter().any(|s| s.name() == signal.name()) {
             return Err(errors::ConfigError::DuplicatedSignal(format!(
                 "Dupplicated signal name in message: {}",
                 signal.name()
             )));
         }
-        builder_data.0.push(signal);
+        builder_data.signals.push(signal);
         Ok(())
     }
+    /// Adds the signal at the fixed `start_bit` (position of the least
+    /// significant bit) instead of packing it after the previous signal.
+    /// Signals added afterwards are packed behind it. Overlapping signals
+    /// are rejected while building the network.
+    pub fn add_signal_at(&self, signal: Signal, start_bit: usize) -> errors::Result<()> {
+        let name = signal.name().to_owned();
+        self.add_signal(Signal {
+            offset: start_bit,
+            ..signal
+        })?;
+        self.0.borrow_mut().pinned_signals.push(name);
+        Ok(())
+    }
+    /// Adds a reserved region of `bits` bits named `reserved_<n>`, which
+    /// occupies space in the layout and is exported (e.g. to dbc files),
+    /// but doesn't carry a value. Returns the name of the region.
+    pub fn add_reserved(&self, bits: u8) -> errors::Result<String> {
+        if !(1..=64).contains(&bits) {
+            return Err(errors::ConfigError::InvalidRange(format!(
+                "reserved regions have between 1 and 64 bits, got {bits}"
+            )));
+        }
+        let name = (0..)
+            .map(|index| format!("reserved_{index}"))
+            .find(|name| !self.0.borrow().signals.iter().any(|s| s.name() == name))
+            .unwrap();
+        let signal = Signal {
+            reserved: true,
+            ..Signal::create(&name, Some("reserved"), SignalType::UnsignedInt { size: bits })
+        };
+        self.add_signal(signal)?;
+        Ok(name)
+    }
+    /// Attaches a description to an added signal.
+    pub fn add_signal_description(&self, signal_name: &str, description: &str) -> errors::Result<()> {
+        let mut builder_data = self.0.borrow_mut();
+        let Some(signal) = builder_data.signals.iter_mut().find(|s| s.name() == signal_name) else {
+            return Err(errors::ConfigError::UndefinedSignal(format!(
+                "signal {signal_name} is not part of the message"
+            )));
+        };
+        signal.description = Some(description.to_owned());
+        Ok(())
+    }
+    /// Annotates that the node uses the signal. Signals without annotations
+    /// are used by every receiver of the message, annotated receivers have
+    /// to receive the message.
+    pub fn add_signal_receiver(&self, signal_name: &str, node_name: &str) -> errors::Result<()> {
+        let mut builder_data = self.0.borrow_mut();
+        let Some(signal) = builder_data.signals.iter_mut().find(|s| s.name() == signal_name) else {
+            return Err(errors::ConfigError::UndefinedSignal(format!(
+                "signal {signal_name} is not part of the message"
+            )));
+        };
+        if !signal.receivers.iter().any(|rx| rx == node_name) {
+            signal.receivers.push(node_name.to_owned());
+        }
+        Ok(())
+    }
+    /// Uses the offsets of the added signals (e.g. from imported
+    /// dbc or kcd files) instead of packing them in order.
+    pub fn use_manual_offsets(&self) {
+        self.0.borrow_mut().pinned = true;
+    }
 }
 impl MessageTypeFormatBuilder {
     pub fn new() -> MessageTypeFormatBuilder {
@@ -1,10 +1,16 @@
-use crate::builder::{MessageBuilder, MessagePriority, NodeBuilder};
+use crate::{
+    builder::{MessageBuilder, MessagePriority, NodeBuilder},
+    errors::{self, ConfigError},
+};
 
 use super::{
     fixed_messages::FixedMessage,
     set_minimization::{MinimizedNetwork, MinimizedSet},
 };
 
+const MAX_STD_ID: u32 = 0x7FF;
+const MAX_EXT_ID: u32 = 0x1FFF_FFFF;
+
 #[derive(Clone)]
 struct AssignedSet {
     receivers: Vec<NodeBuilder>,
@@ -73,7 +79,7 @@ pub fn assign_messages_ids(
     fixed_messages: &Vec<FixedMessage>,
     minimized_network: MinimizedNetwork,
     nodes: &Vec<NodeBuilder>,
-) -> Vec<NodeFilterInfo> {
+) -> errors::Result<Vec<NodeFilterInfo>> {
     let setcode_len = (minimized_network.sets().len() as f64).log2().ceil() as u32;
     let mut setcode_allocator = SetCodeAllocator::new(setcode_len);
     let mut fixed_messages = fixed_messages.clone();
@@ -164,6 +170,16 @@ pub fn assign_messages_ids(
         let setcode = assigned_set.setcode;
         let mut reserved_ids: Vec<u32> =
             Vec::from_iter(assigned_set.fixed_ids.clone().into_iter());
+        // fixed messages, which didn't fit into any set, may still share
+        // their id with a message of this set.
+        reserved_ids.extend(
+            fixed_messages
+                .iter()
+                .filter(|fixed| fixed.ide() == assigned_set.ide)
+                .map(|fixed| fixed.id()),
+        );
+        let max_id = if assigned_set.ide { MAX_EXT_ID } else { MAX_STD_ID };
+        let max_priority = max_id.overflowing_shr(setcode_len).0;
 
         let bucket_layout = minimized_network.bucket_layout();
 
@@ -175,17 +191,29 @@ pub fn assign_messages_ids(
                     let id = (priority << setcode_len) | setcode;
                     !reserved_ids.contains(&id)
                 });
-                let prio_offset: i32 = match prio_offset {
-                    Some(offset) => offset as i32,
-                    None => (-1..-(bucket_offset as i32) - 1)
-                        .find(|prio_offset| {
-                            let priority = (bucket_offset as i32 + *prio_offset) as u32;
+                let prio_offset: Option<i32> = match prio_offset {
+                    Some(offset) => Some(offset as i32),
+                    None => (-1..-(bucket_offset as i32) - 1).find(|prio_offset| {
+                        let priority = (bucket_offset as i32 + *prio_offset) as u32;
+                        let id = (priority << setcode_len) | setcode;
+                        !reserved_ids.contains(&id)
+                    }),
+                };
+                // the set is full of reserved ids, fall back to the
+                // priorities behind the set.
+                let prio_offset = prio_offset.or_else(|| {
+                    (max_messages_per_set as u32..=max_priority)
+                        .find(|priority| {
                             let id = (priority << setcode_len) | setcode;
                             !reserved_ids.contains(&id)
                         })
-                        .expect(
-                            "fixed message was probably inserted in a set where there wasn't space for it",
-                        ),
+                        .map(|priority| priority as i32 - bucket_offset as i32)
+                });
+                let Some(prio_offset) = prio_offset else {
+                    return Err(ConfigError::UnresolvedId(format!(
+                        "no free id left for message {} next to the fixed ids",
+                        msg.0.borrow().name
+                    )));
                 };
                 let priority = (bucket_offset as i32 + prio_offset) as u32;
                 let id = (priority << setcode_len) | setcode;
@@ -204,7 +232,7 @@ pub fn assign_messages_ids(
         }
     }
 
-    nodes
+    Ok(nodes
         .iter()
         .map(|node| {
             let node_name = node.0.borrow().name.clone();
@@ -220,8 +248,26 @@ pub fn assign_messages_ids(
                         setcode_len,
                         ide: x.ide,
                     })
+                    // fixed messages, which didn't fit into any set.
+                    .chain(
+                        fixed_messages
+                            .iter()
+                            .filter(|fixed| {
+                                fixed
+                                    .message()
+                                    .0
+                                    .borrow()
+                                    .receivers
+                                    .iter()
+                                    .any(|n| n.0.borrow().name == node_name)
+                            })
+                            .map(|fixed| FilterInfo::Single {
+                                id: fixed.id(),
+                                ide: fixed.ide(),
+                            }),
+                    )
                     .collect(),
             }
         })
-        .collect()
+        .collect())
 }
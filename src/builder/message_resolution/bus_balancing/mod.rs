@@ -1,8 +1,8 @@
 use std::{time::Duration, cmp::Ordering};
 
 use crate::{
-    builder::{bus::BusBuilder, MessageBuilder, NetworkBuilder},
-    config::{TypeRef, Type},
+    builder::{bus::BusBuilder, type_parser::TypeResolver, MessageBuilder},
+    config::Type,
 };
 
 struct AssignedMessage {
@@ -119,7 +119,7 @@ struct MessageWithLoad {
 }
 
 impl MessageWithLoad {
-    pub fn from(types : &Vec<TypeRef>, msg: &MessageBuilder) -> Self {
+    pub fn from(types : &TypeResolver, msg: &MessageBuilder) -> Self {
         let ide = match msg.0.borrow().id {
             crate::builder::message_builder::MessageIdTemplate::StdId(_) => false,
             crate::builder::message_builder::MessageIdTemplate::ExtId(_) => true,
@@ -133,34 +133,32 @@ impl MessageWithLoad {
             crate::builder::MessageFormat::Signals(signal_format) => signal_format
                 .0
                 .borrow()
-                .0
+                .signals
                 .iter()
                 .map(|s| s.byte_offset() + s.size() as usize)
                 .max()
-                .unwrap(),
+                .unwrap_or(0),
             crate::builder::MessageFormat::Types(type_format) => {
                 fn acc_dlc(ty: &Type) -> usize {
                     match ty {
                         crate::config::Type::Primitive(signal_type) => signal_type.size() as usize,
-                        crate::config::Type::Struct {
-                            name: _,
-                            description: _,
-                            attribs,
-                            visibility: _,
-                        } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
+                        crate::config::Type::Struct { .. } | crate::config::Type::Union { .. } => {
+                            ty.size() as usize
+                        }
                         crate::config::Type::Enum {
                             name: _,
                             description: _,
                             size,
                             entries: _,
+                            entry_info: _,
                             visibility: _,
                         } => *size as usize,
-                        crate::config::Type::Array { len: _, ty: _ } => todo!(),
+                        crate::config::Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
                     }
                 }
                 let mut dlc = 0usize;
                 for (attr_ty, _) in &type_format.0.borrow().0 {
-                    let ty = NetworkBuilder::resolve_type(types, attr_ty)
+                    let ty = types.resolve(attr_ty)
                         .expect("failed to resolve type");
                     dlc += acc_dlc(&ty);
                 }
@@ -183,9 +181,19 @@ impl MessageWithLoad {
             | crate::builder::message_builder::MessageBuilderUsage::CommandReq(command_builder) => {
                 command_builder.0.borrow().expected_interval
             }
+            crate::builder::message_builder::MessageBuilderUsage::TransportChannel(channel_builder) => {
+                channel_builder.0.borrow().expected_interval
+            }
             crate::builder::message_builder::MessageBuilderUsage::Configuration => {
                 Duration::from_millis(100)
             }
+            crate::builder::message_builder::MessageBuilderUsage::Emergency
+            | crate::builder::message_builder::MessageBuilderUsage::Bootloader => {
+                Duration::from_millis(100)
+            }
+            crate::builder::message_builder::MessageBuilderUsage::Debug { interval } => {
+                interval.unwrap_or(Duration::from_millis(50))
+            }
             crate::builder::message_builder::MessageBuilderUsage::Heartbeat => {
                 Duration::from_millis(100)
             }
@@ -241,7 +249,7 @@ impl Ord for MessageWithLoad {
 
 pub fn balance_buses(
     messages: &Vec<MessageBuilder>,
-    types: &Vec<TypeRef>,
+    types: &TypeResolver,
     buses: &Vec<BusBuilder>,
 ) {
     let mut buses = Buses::from(buses);
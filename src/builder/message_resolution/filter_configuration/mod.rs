@@ -1,52 +1,54 @@
-use crate::builder::NodeBuilder;
+use crate::{
+    builder::NodeBuilder,
+    config::{MessageId, RxFilter},
+};
 
-use super::assign_messages::NodeFilterInfo;
+use super::assign_messages::{FilterInfo, NodeFilterInfo};
 
 pub struct NodeFilterBank {
-    filters: Vec<Filter>,
+    filters: Vec<RxFilter>,
     node: NodeBuilder,
 }
 
 impl NodeFilterBank {
+    pub fn new(node: NodeBuilder, filters: Vec<RxFilter>) -> Self {
+        Self { filters, node }
+    }
     pub fn node(&self) -> &NodeBuilder {
         &self.node
     }
-    pub fn filters(&self) -> &Vec<Filter> {
+    pub fn filters(&self) -> &Vec<RxFilter> {
         &self.filters
     }
 }
 
-pub struct Filter {
-    mask: u32,
-    id: u32,
-}
-impl Filter {
-    pub fn mask(&self) -> u32 {
-        self.mask
-    }
-    pub fn id(&self) -> u32 {
-        self.id
-    }
-}
-
 pub fn find_filter_configuration(filter_infos: Vec<NodeFilterInfo>) -> Vec<NodeFilterBank> {
     filter_infos
         .iter()
         .map(|node_filter_info| NodeFilterBank {
-            filters: node_filter_info.filter_infos().iter().map(|filter| -> Filter{
-                match filter {
-                    super::assign_messages::FilterInfo::Setcode { setcode, setcode_len, ide : _ } => Filter {
-                        mask : 0xFFFFFFFFu32.overflowing_shr(32 - *setcode_len).0,
-                        id : *setcode
-                    },
-                    super::assign_messages::FilterInfo::Single { id, ide : _ } => {
-                        Filter {
-                            mask : 0xFFFFFFFFu32,
-                            id : *id,
-                        }
+            filters: node_filter_info
+                .filter_infos()
+                .iter()
+                .map(|filter| match filter {
+                    // the setcode occupies the lowest bits of the id,
+                    // without setcode bits all ids are accepted.
+                    FilterInfo::Setcode {
+                        setcode,
+                        setcode_len,
+                        ide,
+                    } => RxFilter::new(
+                        *setcode,
+                        u32::MAX.checked_shr(32 - *setcode_len).unwrap_or(0),
+                        *ide,
+                    ),
+                    FilterInfo::Single { id, ide: false } => {
+                        RxFilter::exact(&MessageId::StandardId(*id))
+                    }
+                    FilterInfo::Single { id, ide: true } => {
+                        RxFilter::exact(&MessageId::ExtendedId(*id))
                     }
-                }
-            }).collect(),
+                })
+                .collect(),
             node: node_filter_info.node().clone(),
         })
         .collect()
@@ -67,13 +67,13 @@ impl MessageSplit {
                         ide: true,
                     });
                 }
+                // messages without an id template get a standard id,
+                // like in the IdRequests.
                 crate::builder::message_builder::MessageIdTemplate::AnyStd(_)
-                | crate::builder::message_builder::MessageIdTemplate::AnyExt(_) => {
+                | crate::builder::message_builder::MessageIdTemplate::AnyExt(_)
+                | crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => {
                     prio_messages.push(message.clone())
                 }
-                crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => {
-                    panic!("ANY ANY Id Template is deprecated and not supported")
-                }
             }
         }
         MessageSplit {
@@ -1,8 +1,8 @@
 use std::time::Duration;
 
 use crate::{
-    builder::{MessageBuilder, NetworkBuilder},
-    config::{Type, TypeRef},
+    builder::{type_parser::TypeResolver, MessageBuilder},
+    config::Type,
 };
 
 
@@ -15,11 +15,10 @@ struct MessageInfo {
 
 pub struct LogInfo {
     messages: Vec<MessageInfo>,
-    types: Vec<TypeRef>,
+    types: TypeResolver,
 }
 
-#[allow(unused)]
-pub fn cache_logging_info(types: &Vec<TypeRef>, messages: &Vec<MessageBuilder>) -> LogInfo {
+pub fn cache_logging_info(types: &TypeResolver, messages: &[MessageBuilder]) -> LogInfo {
     LogInfo {
         types: types.clone(),
         messages: messages
@@ -33,13 +32,13 @@ pub fn cache_logging_info(types: &Vec<TypeRef>, messages: &Vec<MessageBuilder>)
                     crate::builder::message_builder::MessageIdTemplate::ExtId(id) => {
                         format!("0x{id:X}x")
                     }
-                    crate::builder::message_builder::MessageIdTemplate::AnyStd(prio) => {
+                    crate::builder::message_builder::MessageIdTemplate::AnyStd(prio)
+                    | crate::builder::message_builder::MessageIdTemplate::AnyAny(prio) => {
                         format!("{prio:?}")
                     }
                     crate::builder::message_builder::MessageIdTemplate::AnyExt(prio) => {
                         format!("{prio:?}x")
                     }
-                    crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => panic!(),
                 };
                 let bus = match &data.bus {
                     Some(bus) => bus.0.borrow().name.clone(),
@@ -55,7 +54,7 @@ pub fn cache_logging_info(types: &Vec<TypeRef>, messages: &Vec<MessageBuilder>)
     }
 }
 
-fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
+fn load_estimation(types: &TypeResolver, msg: &MessageBuilder) -> f64 {
     let ide = match msg.0.borrow().id {
         crate::builder::message_builder::MessageIdTemplate::StdId(_) => false,
         crate::builder::message_builder::MessageIdTemplate::ExtId(_) => true,
@@ -67,7 +66,7 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
         crate::builder::MessageFormat::Signals(signal_format) => signal_format
             .0
             .borrow()
-            .0
+            .signals
             .iter()
             .map(|s| s.byte_offset() + s.size() as usize)
             .max()
@@ -76,26 +75,23 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
             fn acc_dlc(ty: &Type) -> usize {
                 match ty {
                     crate::config::Type::Primitive(signal_type) => signal_type.size() as usize,
-                    crate::config::Type::Struct {
-                        name: _,
-                        description: _,
-                        attribs,
-                        visibility: _,
-                    } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
+                    crate::config::Type::Struct { .. } | crate::config::Type::Union { .. } => {
+                        ty.size() as usize
+                    }
                     crate::config::Type::Enum {
                         name: _,
                         description: _,
                         size,
                         entries: _,
+                        entry_info: _,
                         visibility: _,
                     } => *size as usize,
-                    crate::config::Type::Array { len: _, ty: _ } => todo!(),
+                    crate::config::Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
                 }
             }
             let mut dlc = 0usize;
             for (attr_ty, _) in &type_format.0.borrow().0 {
-                let ty =
-                    NetworkBuilder::resolve_type(types, attr_ty).expect("failed to resolve type");
+                let ty = types.resolve(attr_ty).expect("failed to resolve type");
                 dlc += acc_dlc(&ty);
             }
             dlc
@@ -117,9 +113,19 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
         | crate::builder::message_builder::MessageBuilderUsage::CommandReq(command_builder) => {
             command_builder.0.borrow().expected_interval
         }
+        crate::builder::message_builder::MessageBuilderUsage::TransportChannel(channel_builder) => {
+            channel_builder.0.borrow().expected_interval
+        }
         crate::builder::message_builder::MessageBuilderUsage::Configuration => {
             Duration::from_millis(100)
         }
+        crate::builder::message_builder::MessageBuilderUsage::Emergency
+        | crate::builder::message_builder::MessageBuilderUsage::Bootloader => {
+            Duration::from_millis(100)
+        }
+        crate::builder::message_builder::MessageBuilderUsage::Debug { interval } => {
+            interval.unwrap_or(Duration::from_millis(50))
+        }
         crate::builder::message_builder::MessageBuilderUsage::Heartbeat => {
             Duration::from_millis(100)
         }
@@ -130,7 +136,6 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
     (max_bitlen as f64 / interval.as_millis() as f64) * 1e3f64
 }
 
-#[allow(unused)]
 pub fn log_info(log_info: LogInfo) {
     let mut messages = log_info.messages;
     messages.sort_by_key(|msg_info| match msg_info.message.0.borrow().id {
@@ -160,7 +165,7 @@ pub fn log_info(log_info: LogInfo) {
         }
     }
     for (bus_name, messages) in &mut bus_messages {
-        println!("BUS : {bus_name}");
+    log::debug!("BUS : {bus_name}");
         // sort messages by id!
         messages.sort_by_key(|m| match m.message.0.borrow().id {
             crate::builder::message_builder::MessageIdTemplate::StdId(id) => id,
@@ -191,8 +196,8 @@ pub fn log_info(log_info: LogInfo) {
                     panic!("unresolved id")
                 }
             };
-            println!("{msg_name} : [{prev_prio}] -> {id}");
+      log::debug!("{msg_name} : [{prev_prio}] -> {id}");
         }
-        println!("BusLoad = {bus_load}");
+    log::debug!("BusLoad = {bus_load}");
     }
 }
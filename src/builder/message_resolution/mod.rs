@@ -1,8 +1,17 @@
-use crate::{config::TypeRef, errors};
+use crate::{
+    config::MessageId,
+    errors::{self, ConfigError},
+};
 
 use self::{filter_configuration::NodeFilterBank, fixed_messages::MessageSplit};
 
-use super::{bus::BusBuilder, MessageBuilder, NodeBuilder};
+use super::{
+    bus::BusBuilder,
+    id_allocator::{AllocationProgress, IdAllocation, IdAllocator, IdRequests},
+    message_builder::MessageIdTemplate,
+    type_parser::TypeResolver,
+    MessageBuilder, NodeBuilder,
+};
 
 mod assign_messages;
 mod bus_balancing;
@@ -16,38 +25,106 @@ pub fn resolve_ids_filters_and_buses(
     buses: &Vec<BusBuilder>,
     messages: &Vec<MessageBuilder>,
     nodes: &Vec<NodeBuilder>,
-    types: &Vec<TypeRef>,
+    types: &TypeResolver,
+    id_allocator: &dyn IdAllocator,
+    progress: &mut AllocationProgress,
 ) -> errors::Result<Vec<NodeFilterBank>> {
     let mut messages = messages.clone();
     let mut nodes = nodes.clone();
     let mut buses = buses.clone();
-    let mut types = types.clone();
     // NOTE hack deterministic behaviour
     messages.sort_by_key(|k| k.0.borrow().name.clone());
     nodes.sort_by_key(|k| k.0.borrow().name.clone());
     buses.sort_by_key(|k| k.0.borrow().name.clone());
-    types.sort_by_key(|t| t.name());
 
 
-    #[cfg(feature = "logging_idrp")]
-    let logging_info = logging::cache_logging_info(&types, &messages);
+    // the priorities before the assignment are only cached if they are logged.
+    let logging_info =
+        log::log_enabled!(log::Level::Debug).then(|| logging::cache_logging_info(types, &messages));
 
-    let message_split = MessageSplit::from(&messages);
-    let network_info =
-        receive_set::generate_receive_sets_from_messages(&nodes, message_split.prio_messages());
-    let minimized_network = set_minimization::minimize_sets(network_info);
+    let requests = IdRequests::new(&messages, &nodes);
+    // the builtin allocator assigns the ids of the builders itself.
+    let requested: Vec<&MessageBuilder> = messages
+        .iter()
+        .filter(|message| {
+            !matches!(
+                message.0.borrow().id,
+                MessageIdTemplate::StdId(_) | MessageIdTemplate::ExtId(_)
+            )
+        })
+        .collect();
+    // the allocation takes most of the time, the bus balancing the rest.
+    let allocation = id_allocator
+        .allocate_with_progress(&requests, &mut |done, total| progress(9 * done, 10 * total))?;
+    allocation.validate(&requests)?;
+    for (message, id) in std::iter::zip(requested, allocation.ids()) {
+        match id {
+            MessageId::StandardId(id) => message.set_std_id(*id),
+            MessageId::ExtendedId(id) => message.set_ext_id(*id),
+        }
+    }
+    progress(9, 10)?;
+    bus_balancing::balance_buses(&messages, types, &buses);
+    progress(10, 10)?;
+    let filter_banks = nodes
+        .iter()
+        .map(|node| {
+            let filters = allocation.filters(&node.0.borrow().name).to_vec();
+            NodeFilterBank::new(node.clone(), filters)
+        })
+        .collect();
+
+    if let Some(logging_info) = logging_info {
+        logging::log_info(logging_info);
+    }
+
+    Ok(filter_banks)
+}
+
+/// Allocation of the `PriorityBucketAllocator`.
+/// The minimization of the receive sets reports every iteration.
+pub fn allocate_priority_buckets(
+    requests: &IdRequests,
+    progress: &mut AllocationProgress,
+) -> errors::Result<IdAllocation> {
+    progress(0, 4)?;
+    let message_split = MessageSplit::from(&requests.messages);
+    let network_info = receive_set::generate_receive_sets_from_messages(
+        &requests.nodes,
+        message_split.prio_messages(),
+    );
+    progress(1, 4)?;
+    let minimized_network = set_minimization::minimize_sets(network_info, &mut || progress(1, 4))?;
+    progress(2, 4)?;
     let filter_infos = assign_messages::assign_messages_ids(
         message_split.fixed_messages(),
         minimized_network,
-        &nodes,
-    );
-    bus_balancing::balance_buses(&messages, &types, &buses);
+        &requests.nodes,
+    )?;
+    progress(3, 4)?;
     let filter_banks = filter_configuration::find_filter_configuration(filter_infos);
+    progress(4, 4)?;
 
-    #[cfg(feature = "logging_idrp")]
-    logging::log_info(logging_info);
-
-    Ok(filter_banks)
+    let mut ids = vec![];
+    for message in message_split.prio_messages() {
+        let message_data = message.0.borrow();
+        ids.push(match message_data.id {
+            MessageIdTemplate::StdId(id) => MessageId::StandardId(id),
+            MessageIdTemplate::ExtId(id) => MessageId::ExtendedId(id),
+            _ => {
+                return Err(ConfigError::UnresolvedId(format!(
+                    "message {} has no id after resolving all ids",
+                    message_data.name
+                )))
+            }
+        });
+    }
+    Ok(filter_banks
+        .into_iter()
+        .fold(IdAllocation::new(ids), |allocation, bank| {
+            let node_name = bank.node().0.borrow().name.clone();
+            allocation.with_filters(&node_name, bank.filters().clone())
+        }))
 }
 
 //
@@ -133,7 +133,7 @@ impl ReceiverSet {
                     crate::builder::MessageFormat::Signals(signal_format) => signal_format
                         .0
                         .borrow()
-                        .0
+                        .signals
                         .iter()
                         .map(|s| s.byte_offset() + s.size() as usize)
                         .max()
@@ -144,20 +144,18 @@ impl ReceiverSet {
                                 crate::config::Type::Primitive(signal_type) => {
                                     signal_type.size() as usize
                                 }
-                                crate::config::Type::Struct {
-                                    name: _,
-                                    description: _,
-                                    attribs,
-                                    visibility: _,
-                                } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
+                                crate::config::Type::Struct { .. } | crate::config::Type::Union { .. } => {
+                                    ty.size() as usize
+                                }
                                 crate::config::Type::Enum {
                                     name: _,
                                     description: _,
                                     size,
                                     entries: _,
+                                    entry_info: _,
                                     visibility: _,
                                 } => *size as usize,
-                                crate::config::Type::Array { len: _, ty: _ } => todo!(),
+                                crate::config::Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
                             }
                         }
                         let mut dlc = 0usize;
@@ -186,9 +184,19 @@ impl ReceiverSet {
                     | crate::builder::message_builder::MessageBuilderUsage::CommandReq(
                         command_builder,
                     ) => command_builder.0.borrow().expected_interval,
+                    crate::builder::message_builder::MessageBuilderUsage::TransportChannel(
+                        channel_builder,
+                    ) => channel_builder.0.borrow().expected_interval,
                     crate::builder::message_builder::MessageBuilderUsage::Configuration => {
                         Duration::from_millis(100)
                     }
+                    crate::builder::message_builder::MessageBuilderUsage::Emergency
+                    | crate::builder::message_builder::MessageBuilderUsage::Bootloader => {
+                        Duration::from_millis(100)
+                    }
+                    crate::builder::message_builder::MessageBuilderUsage::Debug { interval } => {
+                        interval.unwrap_or(Duration::from_millis(50))
+                    }
                     crate::builder::message_builder::MessageBuilderUsage::Heartbeat => {
                         Duration::from_millis(100)
                     }
@@ -213,9 +221,9 @@ pub fn generate_receive_sets_from_messages(nodes : &Vec<NodeBuilder>, messages:
             crate::builder::message_builder::MessageIdTemplate::ExtId(_) => {
                 panic!("receive sets do not support fixed ids")
             }
-            crate::builder::message_builder::MessageIdTemplate::AnyStd(_) => false,
+            crate::builder::message_builder::MessageIdTemplate::AnyStd(_)
+            | crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => false,
             crate::builder::message_builder::MessageIdTemplate::AnyExt(_) => true,
-            crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => panic!("receive sets do not support AnyAny id templates"),
         };
         let set_identifier = SetIdentifier::new(&message.0.borrow().receivers, bus, ide);
         for rx in &message.0.borrow().receivers {
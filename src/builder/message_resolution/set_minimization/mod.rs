@@ -1,5 +1,6 @@
 use self::set_identifier::SetIdentifier;
 use crate::builder::MessagePriority;
+use crate::errors;
 use crate::builder::{
     message_resolution::set_minimization::bucket_layout::BucketLayout, MessageBuilder,
 };
@@ -13,8 +14,6 @@ pub mod set_identifier;
 const MAX_FILTERS_PER_NODE: usize = 8;
 const STD_ID_LENGTH: u32 = 11;
 
-const LOGGING: bool = false;
-
 pub struct MinimizedSet {
     messages: [Vec<MessageBuilder>; MessagePriority::count()],
     id: SetIdentifier,
@@ -65,7 +64,11 @@ impl MinimizedNetwork {
  * messages is not allowed to contain messages with fixed id assignments!
  */
 
-pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
+/// `poll` is called every iteration and aborts the minimization if it fails.
+pub fn minimize_sets(
+    network_info: NetworkInfo,
+    poll: &mut dyn FnMut() -> errors::Result<()>,
+) -> errors::Result<MinimizedNetwork> {
     if network_info.node_receive_sets().is_empty() {
         assert_eq!(network_info.receive_sets().len(), 1, "It is assumed that if no receiver exists in 
                    the network that all messages are defined by the config itself are are std and non fixed");
@@ -73,6 +76,7 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         let mut bucket_layout = BucketLayout::new();
 
         loop {
+            poll()?;
             let Some(commit) = set.min_commit_to_merge(&bucket_layout) else {
                 break;
             };
@@ -83,31 +87,32 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
             "Failed to find message id configuration"
         );
         let minimized_sets = set.to_minimized_sets(&bucket_layout);
-        return MinimizedNetwork {
+        return Ok(MinimizedNetwork {
             sets: minimized_sets,
             bucket_layout,
-        };
+        });
     }
-    if LOGGING {
-        println!("receive set count: {}", network_info.receive_sets().len());
+    if log::log_enabled!(log::Level::Trace) {
+    log::trace!("receive set count: {}", network_info.receive_sets().len());
     }
 
     let mut bucket_layout = BucketLayout::new();
 
     let mut it = 0;
     loop {
-        if LOGGING {
-            println!("\nBegin Iteration {it}");
+        poll()?;
+        if log::log_enabled!(log::Level::Trace) {
+      log::trace!("Begin Iteration {it}");
         }
         it += 1;
 
-        if LOGGING {
-            println!("Bucket Stats:");
-            println!("-realtime    : {}", bucket_layout.bucket_size(0));
-            println!("-high        : {}", bucket_layout.bucket_size(1));
-            println!("-normal      : {}", bucket_layout.bucket_size(2));
-            println!("-low         : {}", bucket_layout.bucket_size(3));
-            println!("-super-low   : {}", bucket_layout.bucket_size(4));
+        if log::log_enabled!(log::Level::Trace) {
+      log::trace!("Bucket Stats:");
+      log::trace!("-realtime    : {}", bucket_layout.bucket_size(0));
+      log::trace!("-high        : {}", bucket_layout.bucket_size(1));
+      log::trace!("-normal      : {}", bucket_layout.bucket_size(2));
+      log::trace!("-low         : {}", bucket_layout.bucket_size(3));
+      log::trace!("-super-low   : {}", bucket_layout.bucket_size(4));
         }
 
         let reducable_node = network_info
@@ -123,73 +128,73 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
             .sum();
 
         for rx_set in network_info.receive_sets() {
-            if LOGGING {
-                println!("RxSet {:?}:", rx_set.identifier());
-                println!("-set-count : {}", rx_set.set_count(&bucket_layout));
-                println!(
+            if log::log_enabled!(log::Level::Trace) {
+        log::trace!("RxSet {:?}:", rx_set.identifier());
+        log::trace!("-set-count : {}", rx_set.set_count(&bucket_layout));
+        log::trace!(
                     "-realtime  : {} -> {}",
                     rx_set.priorioty_bucket(0).message_count(),
                     rx_set
                         .priorioty_bucket(0)
                         .required_sets(bucket_layout.bucket_size(0))
                 );
-                println!(
+        log::trace!(
                     "--required-inc = {}",
                     rx_set
                         .priorioty_bucket(0)
                         .required_inc_for_merge(bucket_layout.bucket_size(0))
                         .unwrap_or_default()
                 );
-                println!(
+        log::trace!(
                     "-high      : {} -> {}",
                     rx_set.priorioty_bucket(1).message_count(),
                     rx_set
                         .priorioty_bucket(1)
                         .required_sets(bucket_layout.bucket_size(1))
                 );
-                println!(
+        log::trace!(
                     "--required-inc = {}",
                     rx_set
                         .priorioty_bucket(1)
                         .required_inc_for_merge(bucket_layout.bucket_size(1))
                         .unwrap_or_default()
                 );
-                println!(
+        log::trace!(
                     "-normal    : {} -> {}",
                     rx_set.priorioty_bucket(2).message_count(),
                     rx_set
                         .priorioty_bucket(2)
                         .required_sets(bucket_layout.bucket_size(2))
                 );
-                println!(
+        log::trace!(
                     "--required-inc = {}",
                     rx_set
                         .priorioty_bucket(2)
                         .required_inc_for_merge(bucket_layout.bucket_size(2))
                         .unwrap_or_default()
                 );
-                println!(
+        log::trace!(
                     "-low       : {} -> {}",
                     rx_set.priorioty_bucket(3).message_count(),
                     rx_set
                         .priorioty_bucket(3)
                         .required_sets(bucket_layout.bucket_size(3))
                 );
-                println!(
+        log::trace!(
                     "--required-inc = {}",
                     rx_set
                         .priorioty_bucket(3)
                         .required_inc_for_merge(bucket_layout.bucket_size(3))
                         .unwrap_or_default()
                 );
-                println!(
+        log::trace!(
                     "-superlow  : {} -> {}",
                     rx_set.priorioty_bucket(4).message_count(),
                     rx_set
                         .priorioty_bucket(4)
                         .required_sets(bucket_layout.bucket_size(4))
                 );
-                println!(
+        log::trace!(
                     "--required-inc = {}",
                     rx_set
                         .priorioty_bucket(4)
@@ -205,13 +210,13 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         let id_len = setcode_len + prio_len;
         let max_filters = reducable_node.receive_set_count(&bucket_layout);
 
-        if LOGGING {
-            println!("Result:");
-            println!("-set-count   : {set_count}");
-            println!("-setcode_len : {setcode_len}");
-            println!("-prio_len    : {prio_len}");
-            println!("-unused-bits : {}", 11 as i32 - id_len as i32);
-            println!("-max_filters : {max_filters}");
+        if log::log_enabled!(log::Level::Trace) {
+      log::trace!("Result:");
+      log::trace!("-set-count   : {set_count}");
+      log::trace!("-setcode_len : {setcode_len}");
+      log::trace!("-prio_len    : {prio_len}");
+      log::trace!("-unused-bits : {}", 11_i32 - id_len as i32);
+      log::trace!("-max_filters : {max_filters}");
         }
 
         if id_len <= STD_ID_LENGTH && max_filters <= MAX_FILTERS_PER_NODE {
@@ -226,18 +231,18 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
             .min_by_key(|commit| commit.count());
         match best_commit {
             Some(best_commit) => {
-                if LOGGING {
-                    println!("APPLY_COMMIT:");
-                    println!("-realtime-inc : {}", best_commit.inc()[0]);
-                    println!("-high-inc     : {}", best_commit.inc()[1]);
-                    println!("-normal-inc   : {}", best_commit.inc()[2]);
-                    println!("-low-inc      : {}", best_commit.inc()[3]);
-                    println!("-superlow-inc : {}", best_commit.inc()[4]);
+                if log::log_enabled!(log::Level::Trace) {
+          log::trace!("APPLY_COMMIT:");
+          log::trace!("-realtime-inc : {}", best_commit.inc()[0]);
+          log::trace!("-high-inc     : {}", best_commit.inc()[1]);
+          log::trace!("-normal-inc   : {}", best_commit.inc()[2]);
+          log::trace!("-low-inc      : {}", best_commit.inc()[3]);
+          log::trace!("-superlow-inc : {}", best_commit.inc()[4]);
                 }
                 bucket_layout.apply_commit(best_commit)
             }
             None => {
-                println!("WARNING : exit without finding valid id assignment");
+                log::warn!("exit without finding valid id assignment");
                 break;
             }
         }
@@ -249,14 +254,13 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         .map(|rx_set| rx_set.set_count(&bucket_layout))
         .sum();
     let setcode_len = (total_set_count as f64).log2().ceil() as u32;
-    if LOGGING {
-        println!("");
-        println!("Bucket Stats:");
-        println!("-realtime    : {}", bucket_layout.bucket_size(0));
-        println!("-high        : {}", bucket_layout.bucket_size(1));
-        println!("-normal      : {}", bucket_layout.bucket_size(2));
-        println!("-low         : {}", bucket_layout.bucket_size(3));
-        println!("-super-low   : {}", bucket_layout.bucket_size(4));
+    if log::log_enabled!(log::Level::Trace) {
+    log::trace!("Bucket Stats:");
+    log::trace!("-realtime    : {}", bucket_layout.bucket_size(0));
+    log::trace!("-high        : {}", bucket_layout.bucket_size(1));
+    log::trace!("-normal      : {}", bucket_layout.bucket_size(2));
+    log::trace!("-low         : {}", bucket_layout.bucket_size(3));
+    log::trace!("-super-low   : {}", bucket_layout.bucket_size(4));
     }
 
     let total_priority_count = bucket_layout.total_bucket_size();
@@ -267,13 +271,12 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         .max_by_key(|node_rx_set| node_rx_set.receive_set_count(&bucket_layout))
         .expect("It was asserted that there exist at least one node receiver set");
     let max_filters = reducable_node.receive_set_count(&bucket_layout);
-    if LOGGING {
-        println!("Combined bucket count : {total_priority_count}");
-        println!("Priority bit count    : {total_priority_bits}");
-        println!("Total setcount : {total_set_count}");
-        println!("setcode-len    : {setcode_len}");
-        println!("Max filters: {max_filters}");
-        println!("");
+    if log::log_enabled!(log::Level::Trace) {
+    log::trace!("Combined bucket count : {total_priority_count}");
+    log::trace!("Priority bit count    : {total_priority_bits}");
+    log::trace!("Total setcount : {total_set_count}");
+    log::trace!("setcode-len    : {setcode_len}");
+    log::trace!("Max filters: {max_filters}");
     }
 
     let minimized_sets: Vec<MinimizedSet> = network_info
@@ -283,5 +286,5 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         .flatten()
         .collect();
 
-    MinimizedNetwork::new(minimized_sets, bucket_layout)
+    Ok(MinimizedNetwork::new(minimized_sets, bucket_layout))
 }
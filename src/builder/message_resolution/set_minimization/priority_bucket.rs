@@ -1,7 +1,5 @@
 use crate::builder::MessageBuilder;
 
-use super::bucket_layout::BucketLayout;
-
 pub struct PriorityBucket {
     messages: Vec<MessageBuilder>,
 }
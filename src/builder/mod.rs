@@ -1,31 +1,122 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    fmt::Debug,
+    rc::Rc,
+};
 
 pub use self::command_builder::CommandBuilder;
+pub use self::id_allocator::AllocationProgress;
+pub use self::id_allocator::CompactAllocator;
+pub use self::id_allocator::HashStableAllocator;
+pub use self::id_allocator::IdAllocation;
+pub use self::id_allocator::IdAllocator;
+pub use self::id_allocator::IdRequest;
+pub use self::id_allocator::IdRequests;
+pub use self::id_allocator::PriorityBucketAllocator;
+pub use self::latency_path_builder::LatencyPathBuilder;
 pub use self::message_builder::MessageBuilder;
 pub use self::message_builder::MessageFormat;
 pub use self::message_builder::MessagePriority;
 pub use self::message_builder::MessageTypeFormatBuilder;
 pub use self::message_builder::MessageSignalFormatBuilder;
 pub use self::network_builder::NetworkBuilder;
+pub use self::network_builder::ObjectEntryIndexAssignment;
+pub use self::build_phase::BUILD_LOG_TARGET;
+pub use self::build_phase::BuildObserver;
+pub use self::build_phase::BuildPhase;
 pub use self::node::NodeBuilder;
+pub use self::node_group_builder::NodeGroupBuilder;
+pub use self::node_template::NodeTemplate;
 pub use self::object_entry_builder::ObjectEntryBuilder;
+pub use self::od_error_codes_builder::OdErrorCodesBuilder;
+pub use self::od_error_codes_builder::STANDARD_OD_ERROR_CODES;
+pub use self::object_entry_group_builder::ObjectEntryGroupBuilder;
+pub use self::transport_channel_builder::TransportChannelBuilder;
 pub use self::type_builder::TypeBuilder;
+pub use self::type_parser::parse_signal_type;
 pub use self::type_builder::EnumBuilder;
 pub use self::type_builder::StructBuilder;
+pub use self::type_builder::UnionBuilder;
+pub use self::workspace_builder::TypeLibrary;
+pub use self::workspace_builder::WorkspaceBuilder;
 
 pub mod command_builder;
+pub mod id_allocator;
+pub mod latency_path_builder;
 pub mod message_builder;
 pub mod network_builder;
 pub mod node;
+pub mod node_group_builder;
+pub mod node_template;
 pub mod object_entry_builder;
+pub mod object_entry_group_builder;
+pub mod od_error_codes_builder;
 pub mod stream_builder;
+pub mod transport_channel_builder;
 pub mod type_builder;
+pub mod type_parser;
+pub mod workspace_builder;
 pub mod bus;
 mod message_resolution;
+mod message_assembly;
+mod type_arena;
+mod build_phase;
 mod import_dbc;
+mod import_kcd;
+#[cfg(feature = "cli")]
+mod import_json;
+mod merge;
+mod edit;
+mod dsl;
 
-type BuilderRef<T> = Rc<RefCell<T>>;
+/// Shared state of a builder, which remembers whether it was modified
+/// since the last build of the network.
+pub struct BuilderCell<T> {
+    value: RefCell<T>,
+    dirty: Cell<bool>,
+}
+
+impl<T> BuilderCell<T> {
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+    /// Mutable access, which marks the builder as dirty.
+    pub fn borrow_mut(&self) -> RefMut<'_, T> {
+        self.dirty.set(true);
+        self.value.borrow_mut()
+    }
+    /// Modified since the last build (or never built).
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+}
+
+impl<T: Debug> Debug for BuilderCell<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+/// Dirty flag of a builder of any type.
+pub(crate) trait DirtyFlag {
+    fn is_dirty(&self) -> bool;
+    fn clear_dirty(&self);
+}
+
+impl<T> DirtyFlag for BuilderCell<T> {
+    fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+    fn clear_dirty(&self) {
+        self.dirty.set(false);
+    }
+}
+
+type BuilderRef<T> = Rc<BuilderCell<T>>;
 
 fn make_builder_ref<T>(value: T) -> BuilderRef<T> {
-    Rc::new(RefCell::new(value))
+    Rc::new(BuilderCell {
+        value: RefCell::new(value),
+        dirty: Cell::new(true),
+    })
 }
@@ -1,33 +1,93 @@
 use std::{
-    cell::{OnceCell, RefCell},
+    cell::OnceCell,
     cmp::Ordering,
+    collections::HashMap,
+    rc::Rc,
+    str::FromStr,
     time::Duration,
 };
 
+use once_cell::sync::Lazy;
 use regex::Regex;
 
 use crate::{
+    builder::message_assembly::{assemble_messages, DraftFormat, MessageDraft},
     builder::message_resolution::resolve_ids_filters_and_buses,
+    builder::id_allocator::{IdAllocator, PriorityBucketAllocator},
+    builder::od_error_codes_builder::{OdErrorCodesBuilder, STANDARD_OD_ERROR_CODES},
     config::{
         self,
         bus::BusRef,
-        encoding::{CompositeSignalEncoding, PrimitiveSignalEncoding},
-        make_config_ref,
-        message::MessageUsage,
-        signal::Signal,
-        stream::Stream,
-        Command, ConfigRef, Message, MessageEncoding, MessageId, Network, NetworkRef, Node,
-        ObjectEntry, SignalRef, SignalType, Type, TypeRef, TypeSignalEncoding,
+        bit_timing::{compute_bit_timing, BitTimingOptions},
+        make_config_ref, make_cyclic_config_refs,
+        message::{MessageUsage, HEARTBEAT_INTERVAL},
+        AccessLevel, AccessLevelRef, ObjectEntryAccess, OverflowPolicy,
+        stream::{Stream, StreamEncoding, StreamMapping, StreamRef},
+        node_group::NodeGroup,
+        latency_path::{LatencyHop, LatencyPath},
+        Command, CommandRef, ConfigRef, J1939Id, Message, MessageId, MessageRef, Network, NetworkParams, NetworkRef, Node, NodeParams,
+        ObjectEntry, ObjectEntryGroup, ObjectEntryRef, ObjectEntryGroupRef, SignalType, TransportChannel,
+        TransportChannelRef, Type, Uds, TypeRef, EnumEntryInfo, StructAttributeInfo, TypeSignalEncoding, Lifecycle, Version,
+        RxFilter, Supervision,
     },
     errors::Result,
     errors::{self},
+    graph::topological_sort,
 };
 
 use super::{
-    bus::BusBuilder, import_dbc::import_dbc, make_builder_ref, message_builder::MessageIdTemplate,
-    BuilderRef, EnumBuilder, MessageBuilder, MessageFormat, MessagePriority, NodeBuilder,
-    StructBuilder, TypeBuilder,
+    bus::{BusBuilder, DEFAULT_BAUDRATE, STANDARD_BAUDRATES}, edit::{self, remove_messages}, import_dbc::import_dbc, import_kcd::import_kcd, make_builder_ref, merge::merge_network, message_builder::{MessageBuilderUsage, MessageIdTemplate},
+    latency_path_builder::LatencyHopBuilder,
+    BuilderRef, DirtyFlag, EnumBuilder, LatencyPathBuilder, MessageBuilder, MessageFormat, MessagePriority, NodeBuilder, NodeGroupBuilder,
+    ObjectEntryBuilder, TransportChannelBuilder,
+    StructBuilder, TypeBuilder, UnionBuilder, type_parser::{self, resolve_builtin_type, TypeResolver},
+    type_arena::{TypeArena, TypeSet},
+    build_phase::{BuildObserver, BuildPhase, BuildSpan, PhaseTimer},
 };
+#[cfg(feature = "cli")]
+use super::import_json;
+
+/// names have to be valid c/c++ identifiers, which aren't keywords.
+static VALID_C_VAR: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-zA-Z_]+[a-zA-Z0-9_]*$").unwrap());
+static C_KEYWORD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(restrict|alignas|alignof|and|and_eq|asm|atomic_cancel|atomic_commit|auto|bitand|bitor|bool|break|case|catch|char|char8_t|char16_t|char32_t|class|compl|concept|const|consteval|constexpr|constinit|const_cast|continue|co_await|co_return|co_yield|decltype|default|delete|do|double|dynamic_cast|else|enum|explicit|export|extern|false|float|for|friend|goto|if|inline|int|long|mutable|namespace|new|noexpect|not|not_eq|nullptr|operator|or|or_eq|private|protected|public|reflexpr|register|reinterpret_cast|require|return|short|signed|sizeof|static|static_assert|static_cast|struct|switch|synchronized|template|this|thread_local|throw|true|try|typedef|typeid|typename|union|unsigned|using|virtual|void|volatile|wchar_t|while|xor|xor_eq)$").unwrap()
+});
+
+/// default size of the od_index field in the get/set protocol headers in bits.
+const OD_INDEX_SIZE: u8 = 13;
+/// the get_resp and set_req frames carry a 32 bit value next to their
+/// 3 bit fragmentation flags and both ids, which leaves 13 bits for the
+/// od_index with 8 bit ids and 2 more bits for every bit the ids are smaller.
+const MAX_OD_INDEX_SIZE: u8 = 29;
+/// EDS exports map object entries to 0x2000 + od_index, the manufacturer
+/// specific area ends at 0x5FFF, higher indices are standardized profiles.
+const MAX_EDS_OD_INDEX_SIZE: u8 = 14;
+/// default size of the client_id and server_id fields in the get/set
+/// protocol headers in bits, which is also the maximum.
+const NODE_ID_SIZE: u8 = 8;
+/// headers, which contain the od_index, client_id and server_id fields.
+const OD_INDEX_HEADERS: [&str; 4] = [
+    "get_req_header",
+    "get_resp_header",
+    "set_req_header",
+    "set_resp_header",
+];
+
+/// Defines how object entries without a pinned index are assigned
+/// their index in the object dictionary of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ObjectEntryIndexAssignment {
+    /// indices are assigned in creation order, skipping pinned indices.
+    #[default]
+    Sequential,
+    /// indices are derived from a hash of the object entry name, so inserting
+    /// or removing an entry usually doesn't change the index of any other entry.
+    /// Colliding hashes are resolved by probing the next free index in creation
+    /// order, which means an entry can move if an entry it collided with is
+    /// removed or an entry created before it takes its index. Entries, which
+    /// have to keep their index, should pin it.
+    HashBased,
+}
 
 #[derive(Debug, Clone)]
 pub struct NetworkBuilder(pub BuilderRef<NetworkData>);
@@ -42,12 +102,27 @@ pub struct NetworkData {
     pub set_req_message: OnceCell<MessageBuilder>,
     pub set_resp_message: OnceCell<MessageBuilder>,
     pub buses: BuilderRef<Vec<BusBuilder>>,
+    pub build_time: Option<chrono::DateTime<chrono::Local>>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub access_levels: Vec<(String, Option<String>)>,
+    pub object_entry_index_assignment: ObjectEntryIndexAssignment,
+    pub od_index_width: u8,
+    pub node_id_width: u8,
+    pub id_allocator: Rc<dyn IdAllocator>,
+    pub node_groups: Vec<NodeGroupBuilder>,
+    pub latency_paths: Vec<LatencyPathBuilder>,
+    pub uds_tester: Option<String>,
+    pub variants: Vec<String>,
+    /// result of the last successful build, see `rebuild`.
+    pub last_build: Option<NetworkRef>,
 }
 
 impl NetworkBuilder {
     pub fn new() -> NetworkBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Create Network");
+        log::trace!("Create Network");
         let network_builder = NetworkBuilder(make_builder_ref(NetworkData {
             messages: make_builder_ref(vec![]),
             types: make_builder_ref(vec![]),
@@ -57,6 +132,21 @@ impl NetworkBuilder {
             set_req_message: OnceCell::new(),
             set_resp_message: OnceCell::new(),
             buses: make_builder_ref(vec![]),
+            build_time: None,
+            name: None,
+            description: None,
+            version: None,
+            author: None,
+            access_levels: vec![],
+            object_entry_index_assignment: ObjectEntryIndexAssignment::default(),
+            od_index_width: OD_INDEX_SIZE,
+            node_id_width: NODE_ID_SIZE,
+            id_allocator: Rc::new(PriorityBucketAllocator),
+            node_groups: vec![],
+            latency_paths: vec![],
+            uds_tester: None,
+            variants: vec![],
+            last_build: None,
         }));
 
         let client_id_name = "client_id";
@@ -66,22 +156,22 @@ impl NetworkBuilder {
         let eof_name = "eof";
         let toggle_name = "toggle";
 
-        let get_resp_erno = network_builder.define_enum("get_resp_erno");
-        get_resp_erno.add_entry("Success", Some(0)).unwrap();
-        get_resp_erno.add_entry("Error", Some(1)).unwrap();
-
-        let set_resp_erno = network_builder.define_enum("set_resp_erno");
-        set_resp_erno.add_entry("Success", Some(0)).unwrap();
-        set_resp_erno.add_entry("Error", Some(1)).unwrap();
+        network_builder.define_enum("get_resp_erno");
+        network_builder.define_enum("set_resp_erno");
+        let od_error_codes = network_builder.od_error_codes().unwrap();
+        for (value, (code, description)) in STANDARD_OD_ERROR_CODES.iter().enumerate() {
+            od_error_codes.add_code(code, Some(value as u64)).unwrap();
+            od_error_codes.add_code_description(code, description).unwrap();
+        }
 
         let get_req_message = network_builder.create_message("get_req", None);
         get_req_message.set_any_std_id(MessagePriority::Low);
-        get_req_message.__assign_to_configuration();
+        get_req_message.assign_to_configuration();
         let get_req_format = get_req_message.make_type_format();
         let get_req_header = network_builder.define_struct("get_req_header");
-        get_req_header.add_attribute(oe_index_name, "u13").unwrap();
-        get_req_header.add_attribute(client_id_name, "u8").unwrap();
-        get_req_header.add_attribute(server_id_name, "u8").unwrap();
+        get_req_header.add_attribute(oe_index_name, &format!("u{OD_INDEX_SIZE}")).unwrap();
+        get_req_header.add_attribute(client_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
+        get_req_header.add_attribute(server_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
         get_req_format.add_type("get_req_header", "header");
         network_builder
             .0
@@ -92,15 +182,15 @@ impl NetworkBuilder {
 
         let get_resp_message = network_builder.create_message("get_resp", None);
         get_resp_message.set_any_std_id(MessagePriority::Low);
-        get_resp_message.__assign_to_configuration();
+        get_resp_message.assign_to_configuration();
         let get_resp_format = get_resp_message.make_type_format();
         let get_resp_header = network_builder.define_struct("get_resp_header");
         get_resp_header.add_attribute(sof_name, "u1").unwrap();
         get_resp_header.add_attribute(eof_name, "u1").unwrap();
         get_resp_header.add_attribute(toggle_name, "u1").unwrap();
-        get_resp_header.add_attribute(oe_index_name, "u13").unwrap();
-        get_resp_header.add_attribute(client_id_name, "u8").unwrap();
-        get_resp_header.add_attribute(server_id_name, "u8").unwrap();
+        get_resp_header.add_attribute(oe_index_name, &format!("u{OD_INDEX_SIZE}")).unwrap();
+        get_resp_header.add_attribute(client_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
+        get_resp_header.add_attribute(server_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
         get_resp_format.add_type("get_resp_header", "header");
         get_resp_format.add_type("u32", "data");
         network_builder
@@ -112,15 +202,15 @@ impl NetworkBuilder {
 
         let set_req_message = network_builder.create_message("set_req", None);
         set_req_message.set_any_std_id(MessagePriority::Low);
-        set_req_message.__assign_to_configuration();
+        set_req_message.assign_to_configuration();
         let set_req_format = set_req_message.make_type_format();
         let set_req_header = network_builder.define_struct("set_req_header");
         set_req_header.add_attribute(sof_name, "u1").unwrap();
         set_req_header.add_attribute(eof_name, "u1").unwrap();
         set_req_header.add_attribute(toggle_name, "u1").unwrap();
-        set_req_header.add_attribute(oe_index_name, "u13").unwrap();
-        set_req_header.add_attribute(client_id_name, "u8").unwrap();
-        set_req_header.add_attribute(server_id_name, "u8").unwrap();
+        set_req_header.add_attribute(oe_index_name, &format!("u{OD_INDEX_SIZE}")).unwrap();
+        set_req_header.add_attribute(client_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
+        set_req_header.add_attribute(server_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
         set_req_format.add_type("set_req_header", "header");
         set_req_format.add_type("u32", "data");
         network_builder
@@ -132,12 +222,12 @@ impl NetworkBuilder {
 
         let set_resp_message = network_builder.create_message("set_resp", None);
         set_resp_message.set_any_std_id(MessagePriority::Low);
-        set_resp_message.__assign_to_configuration();
+        set_resp_message.assign_to_configuration();
         let set_resp_format = set_resp_message.make_type_format();
         let set_resp_header = network_builder.define_struct("set_resp_header");
-        set_resp_header.add_attribute(oe_index_name, "u13").unwrap();
-        set_resp_header.add_attribute(client_id_name, "u8").unwrap();
-        set_resp_header.add_attribute(server_id_name, "u8").unwrap();
+        set_resp_header.add_attribute(oe_index_name, &format!("u{OD_INDEX_SIZE}")).unwrap();
+        set_resp_header.add_attribute(client_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
+        set_resp_header.add_attribute(server_id_name, &format!("u{NODE_ID_SIZE}")).unwrap();
         set_resp_header
             .add_attribute("erno", "set_resp_erno")
             .unwrap();
@@ -166,10 +256,337 @@ impl NetworkBuilder {
         network_builder
     }
 
+    /// Imports the messages of a dbc file as external messages of the bus,
+    /// the get/set and heartbeat messages are skipped.
     pub fn include_dbc(&self, bus: &str, dbc_path: &str) -> Result<()> {
         import_dbc(self, bus, dbc_path)
     }
 
+    /// Creates a network from a Kayak CAN definition (KCD).
+    /// Messages are imported as external messages with fixed ids,
+    /// the get/set and heartbeat messages are skipped.
+    pub fn from_kcd(kcd: &str) -> Result<NetworkBuilder> {
+        let network_builder = NetworkBuilder::new();
+        import_kcd(&network_builder, kcd)?;
+        Ok(network_builder)
+    }
+
+    /// Creates a network from a JSON network definition, which mirrors the `network!` DSL:
+    ///
+    /// ```json
+    /// {
+    ///   "schema_version": 1,
+    ///   "buses": [{ "name": "can0", "baudrate": 1000000 }],
+    ///   "enums": [{ "name": "state", "entries": ["idle", { "name": "running", "value": 4 }] }],
+    ///   "structs": [{ "name": "status", "attributes": [{ "name": "state", "type": "state" }] }],
+    ///   "nodes": [{
+    ///     "name": "secu",
+    ///     "object_entries": [{ "name": "state", "type": "state" }],
+    ///     "streams": [{ "name": "state", "entries": ["state"] }],
+    ///     "commands": [{ "name": "reset", "arguments": [{ "name": "delay", "type": "u16" }], "callees": ["master"] }],
+    ///     "receive": [{ "node": "master", "stream": "status" }]
+    ///   }],
+    ///   "messages": [{ "name": "dyno_torque", "id": 80, "extended": false,
+    ///     "signals": [{ "name": "torque", "type": "u16" }],
+    ///     "transmitters": ["master"], "receivers": ["secu"] }]
+    /// }
+    /// ```
+    ///
+    /// All sections are optional and every item accepts an optional description.
+    /// A schema_version other than `CANONICAL_SCHEMA_VERSION` is rejected.
+    #[cfg(feature = "cli")]
+    pub fn from_json(json: &str) -> Result<NetworkBuilder> {
+        let network_builder = NetworkBuilder::new();
+        import_json::import_json(&network_builder, json)?;
+        Ok(network_builder)
+    }
+
+    /// Creates a network from a YAML network definition with the same
+    /// structure as the JSON definition of `from_json`.
+    #[cfg(feature = "cli")]
+    pub fn from_yaml(yaml: &str) -> Result<NetworkBuilder> {
+        let network_builder = NetworkBuilder::new();
+        import_json::import_yaml(&network_builder, yaml)?;
+        Ok(network_builder)
+    }
+
+    /// Imports all nodes, messages, types and buses of another network builder.
+    /// Nodes and buses with the same name are merged, identical type definitions
+    /// are deduplicated and conflicting definitions are reported as errors.
+    pub fn merge(&self, other: NetworkBuilder) -> Result<()> {
+        merge_network(self, other)
+    }
+
+    /// Removes a message including the streams and commands using it.
+    pub fn remove_message(&self, name: &str) -> Result<()> {
+        edit::remove_message(self, name)
+    }
+
+    pub fn rename_message(&self, name: &str, new_name: &str) -> Result<()> {
+        edit::rename_message(self, name, new_name)
+    }
+
+    /// Renames a node including the messages named after it.
+    pub fn rename_node(&self, name: &str, new_name: &str) -> Result<()> {
+        edit::rename_node(self, name, new_name)
+    }
+
+    /// Renames a type and updates all references to it.
+    pub fn rename_type(&self, name: &str, new_name: &str) -> Result<()> {
+        edit::rename_type(self, name, new_name)
+    }
+
+    /// Drops the type {name} and uses {replacement} wherever it was referenced.
+    pub fn replace_type(&self, name: &str, replacement: &str) -> Result<()> {
+        edit::replace_type(self, name, replacement)
+    }
+
+    /// Overwrites the build time of the network, which otherwise is the
+    /// time build() was called. Required for reproducible builds.
+    pub fn set_build_time(&self, build_time: chrono::DateTime<chrono::Local>) {
+        self.0.borrow_mut().build_time = Some(build_time);
+    }
+
+    /// Name of the network (e.g. the vehicle), which is emitted by the
+    /// exporters to identify the generated artifacts.
+    pub fn set_name(&self, name: &str) {
+        self.0.borrow_mut().name = Some(name.to_owned());
+    }
+    pub fn set_description(&self, description: &str) {
+        self.0.borrow_mut().description = Some(description.to_owned());
+    }
+    /// Version of the configuration (e.g. "v2.3.1"), validated while building.
+    pub fn set_version(&self, version: &str) {
+        self.0.borrow_mut().version = Some(version.to_owned());
+    }
+    pub fn set_author(&self, author: &str) {
+        self.0.borrow_mut().author = Some(author.to_owned());
+    }
+
+    pub fn set_object_entry_index_assignment(&self, assignment: ObjectEntryIndexAssignment) {
+        self.0.borrow_mut().object_entry_index_assignment = assignment;
+    }
+
+    /// Sets the size of the od_index field in the get/set protocol headers in bits
+    /// (13 by default), which limits the number of object entries per node.
+    /// Smaller nodes can use fewer bits, the width is validated while building.
+    /// Wider indices (at most 14 bits) require smaller node_ids.
+    pub fn set_od_index_width(&self, bits: u8) {
+        self.0.borrow_mut().od_index_width = bits;
+    }
+
+    /// Sets the size of the client_id and server_id fields in the get/set
+    /// protocol headers in bits (8 by default). The largest id is the
+    /// broadcast id, group ids are assigned below it.
+    pub fn set_node_id_width(&self, bits: u8) {
+        self.0.borrow_mut().node_id_width = bits;
+    }
+
+    /// Replaces the strategy, which assigns the ids of messages without a
+    /// fixed id (`PriorityBucketAllocator` by default).
+    pub fn set_id_allocator(&self, id_allocator: impl IdAllocator + 'static) {
+        self.0.borrow_mut().id_allocator = Rc::new(id_allocator);
+    }
+
+    /// Adds the nodes of the callee (and responder) groups of all commands as
+    /// callees (and responders), groups are resolved while building, because
+    /// nodes may join them later.
+    fn resolve_callee_groups(&self) -> errors::Result<()> {
+        for node in self.nodes() {
+            let commands = node.0.borrow().commands.clone();
+            for command in commands {
+                let callee_groups = command.0.borrow().callee_groups.clone();
+                for group_name in callee_groups {
+                    let Some(group) = self.find_group(&group_name) else {
+                        return Err(errors::ConfigError::UndefinedGroup(format!(
+                            "group {group_name} called by command {}::{} is not defined",
+                            node.0.borrow().name,
+                            command.0.borrow().name
+                        )));
+                    };
+                    let members = group.0.borrow().nodes.clone();
+                    for member in members {
+                        if Rc::ptr_eq(&member.0, &node.0) {
+                            continue;
+                        }
+                        let already_callee = member
+                            .0
+                            .borrow()
+                            .extern_commands
+                            .iter()
+                            .any(|c| Rc::ptr_eq(&c.0, &command.0));
+                        if !already_callee {
+                            member.add_extern_command(&command);
+                        }
+                    }
+                }
+                let responder_groups = command.0.borrow().responder_groups.clone();
+                for group_name in responder_groups {
+                    let Some(group) = self.find_group(&group_name) else {
+                        return Err(errors::ConfigError::UndefinedGroup(format!(
+                            "group {group_name} responding to command {}::{} is not defined",
+                            node.0.borrow().name,
+                            command.0.borrow().name
+                        )));
+                    };
+                    let members = group.0.borrow().nodes.clone();
+                    for member in members {
+                        let member_name = member.0.borrow().name.clone();
+                        command.add_responder(&member_name);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the od_index width and applies it to the protocol headers.
+    fn apply_od_index_width(&self) -> errors::Result<()> {
+        let width = self.0.borrow().od_index_width;
+        let max_width = (MAX_OD_INDEX_SIZE - 2 * self.0.borrow().node_id_width).min(MAX_EDS_OD_INDEX_SIZE);
+        if !(1..=max_width).contains(&width) {
+            return Err(errors::ConfigError::InvalidRange(format!(
+                "od_index width of {width} bits is not within 1..={max_width} bits"
+            )));
+        }
+        for header in OD_INDEX_HEADERS {
+            let Some(TypeBuilder::Struct(struct_builder)) = self.find_type(header) else {
+                continue;
+            };
+            for (name, ty) in struct_builder.0.borrow_mut().attributes.iter_mut() {
+                if name == "od_index" {
+                    *ty = format!("u{width}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the node_id width and applies it to the client_id and
+    /// server_id fields of the protocol headers.
+    fn apply_node_id_width(&self) -> errors::Result<()> {
+        let width = self.0.borrow().node_id_width;
+        if !(1..=NODE_ID_SIZE).contains(&width) {
+            return Err(errors::ConfigError::InvalidRange(format!(
+                "node_id width of {width} bits is not within 1..={NODE_ID_SIZE} bits"
+            )));
+        }
+        for header in OD_INDEX_HEADERS {
+            let Some(TypeBuilder::Struct(struct_builder)) = self.find_type(header) else {
+                continue;
+            };
+            for (name, ty) in struct_builder.0.borrow_mut().attributes.iter_mut() {
+                if name == "client_id" || name == "server_id" {
+                    *ty = format!("u{width}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// server_id of get/set requests, which are addressed to all nodes,
+    /// it's the largest id, which fits into the node_id width.
+    fn broadcast_server_id(&self) -> u8 {
+        u8::MAX >> (NODE_ID_SIZE - self.0.borrow().node_id_width)
+    }
+
+    /// Ids of the nodes in creation order. Pinned ids are reserved first,
+    /// the remaining nodes get the smallest free id. Ids count upwards
+    /// from 0, group ids downwards from below the broadcast id.
+    fn assign_node_ids(&self) -> errors::Result<Vec<u8>> {
+        let broadcast_server_id = self.broadcast_server_id();
+        let node_width = self.0.borrow().node_id_width;
+        let group_count = self.0.borrow().node_groups.len();
+        let nodes = self.nodes();
+        let Some(id_count) = (broadcast_server_id as usize).checked_sub(group_count) else {
+            return Err(errors::ConfigError::InvalidBroadcast(format!(
+                "{group_count} node groups exceed the {broadcast_server_id} server ids available with a {node_width} bit node_id"
+            )));
+        };
+        let mut ids: Vec<Option<u8>> = vec![None; nodes.len()];
+        for (i, node) in nodes.iter().enumerate() {
+            let Some(id) = node.node_id() else {
+                continue;
+            };
+            if id as usize >= id_count {
+                return Err(errors::ConfigError::InvalidNodeId(format!(
+                    "node id {id} of node {} does not fit below the {group_count} group ids and the broadcast id {broadcast_server_id} of the {node_width} bit node_id",
+                    node.name()
+                )));
+            }
+            if let Some(other) = ids.iter().position(|x| *x == Some(id)) {
+                return Err(errors::ConfigError::DuplicatedNodeId(format!(
+                    "nodes {} and {} are both pinned to node id {id}",
+                    nodes[other].name(),
+                    node.name()
+                )));
+            }
+            ids[i] = Some(id);
+        }
+        let mut reserved: Vec<u8> = ids.iter().flatten().copied().collect();
+        for node_id in ids.iter_mut().filter(|id| id.is_none()) {
+            let Some(id) = (0..id_count as u8).find(|id| !reserved.contains(id)) else {
+                return Err(errors::ConfigError::InvalidBroadcast(format!(
+                    "{} nodes and {group_count} node groups exceed the {broadcast_server_id} server ids available with a {node_width} bit node_id",
+                    nodes.len()
+                )));
+            };
+            *node_id = Some(id);
+            reserved.push(id);
+        }
+        Ok(ids.into_iter().flatten().collect())
+    }
+
+    /// Defines an access level that can be used to protect object entries
+    /// with `ObjectEntryAccess::Protected`. Levels defined later rank higher.
+    pub fn define_access_level(&self, name: &str, description: Option<&str>) {
+        self.0
+            .borrow_mut()
+            .access_levels
+            .push((name.to_owned(), description.map(str::to_owned)));
+    }
+
+    /// Creates a group of nodes (e.g. a subsystem like propulsion or telemetry),
+    /// which can be addressed together by the get/set protocol and by commands.
+    /// Returns the existing group if it's already defined.
+    pub fn create_group(&self, name: &str) -> NodeGroupBuilder {
+        let existing = self
+            .0
+            .borrow()
+            .node_groups
+            .iter()
+            .find(|group| group.0.borrow().name == name)
+            .cloned();
+        if let Some(group) = existing {
+            return group;
+        }
+        let group = NodeGroupBuilder::new(name, self);
+        self.0.borrow_mut().node_groups.push(group.clone());
+        group
+    }
+
+    /// Creates a data path (e.g. sensor object entry -> stream -> command),
+    /// whose end-to-end latency is checked against a budget by
+    /// Network::path_latencies. Returns the existing path if it's already defined.
+    pub fn create_latency_path(&self, name: &str) -> LatencyPathBuilder {
+        let existing = self
+            .0
+            .borrow()
+            .latency_paths
+            .iter()
+            .find(|path| path.0.borrow().name == name)
+            .cloned();
+        if let Some(path) = existing {
+            return path;
+        }
+        let path = LatencyPathBuilder::new(name);
+        self.0.borrow_mut().latency_paths.push(path.clone());
+        path
+    }
+    pub fn latency_paths(&self) -> Vec<LatencyPathBuilder> {
+        self.0.borrow().latency_paths.clone()
+    }
+
     pub fn create_bus(&self, name: &str, baudrate: Option<u32>) -> BusBuilder {
         let network_data = self.0.borrow_mut();
         let id = network_data.buses.borrow().len();
@@ -209,10 +626,20 @@ impl NetworkBuilder {
             .push(TypeBuilder::Struct(type_builder.clone()));
         type_builder
     }
+    /// Defines a tagged union, where the value of a tag enum selects which
+    /// payload type occupies the following bits.
+    pub fn define_union(&self, name: &str) -> UnionBuilder {
+        let network_data = self.0.borrow();
+        let type_builder = UnionBuilder::new(name);
+        network_data
+            .types
+            .borrow_mut()
+            .push(TypeBuilder::Union(type_builder.clone()));
+        type_builder
+    }
     pub fn create_node(&self, name: &str) -> NodeBuilder {
         let network_data = self.0.borrow();
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Require node {name}");
+        log::trace!("Require node {name}");
         // check if node already exists.
         let existing_node = network_data
             .nodes
@@ -228,19 +655,78 @@ impl NetworkBuilder {
         node
     }
 
-    pub fn _get_req_message(&self) -> MessageBuilder {
+    /// Creates a node for a third-party device (e.g. a purchased BMS), we
+    /// don't generate code for. Its messages are only received by our nodes,
+    /// so they have to be defined with fixed ids (see set_std_id/set_ext_id
+    /// or include_dbc after creating the node).
+    pub fn create_extern_node(&self, name: &str) -> NodeBuilder {
+        let network_data = self.0.borrow();
+        let existing_node = network_data
+            .nodes
+            .borrow()
+            .iter()
+            .find(|n| n.0.borrow().name == name)
+            .map(NodeBuilder::to_owned);
+        let Some(node) = existing_node else {
+            let node_builder = NodeBuilder::new_extern(name, self);
+            network_data.nodes.borrow_mut().push(node_builder.clone());
+            return node_builder;
+        };
+        node
+    }
+
+    pub fn messages(&self) -> Vec<MessageBuilder> {
+        self.0.borrow().messages.borrow().clone()
+    }
+    pub fn nodes(&self) -> Vec<NodeBuilder> {
+        self.0.borrow().nodes.borrow().clone()
+    }
+    pub fn types(&self) -> Vec<TypeBuilder> {
+        self.0.borrow().types.borrow().clone()
+    }
+    pub fn buses(&self) -> Vec<BusBuilder> {
+        self.0.borrow().buses.borrow().clone()
+    }
+    pub fn find_message(&self, name: &str) -> Option<MessageBuilder> {
+        self.messages().into_iter().find(|m| m.name() == name)
+    }
+    pub fn find_node(&self, name: &str) -> Option<NodeBuilder> {
+        self.nodes().into_iter().find(|n| n.name() == name)
+    }
+    pub fn find_type(&self, name: &str) -> Option<TypeBuilder> {
+        self.types().into_iter().find(|t| t.name() == name)
+    }
+    pub fn node_groups(&self) -> Vec<NodeGroupBuilder> {
+        self.0.borrow().node_groups.clone()
+    }
+    pub fn find_group(&self, name: &str) -> Option<NodeGroupBuilder> {
+        self.node_groups().into_iter().find(|g| g.0.borrow().name == name)
+    }
+    pub fn find_bus(&self, name: &str) -> Option<BusBuilder> {
+        self.buses().into_iter().find(|b| b.name() == name)
+    }
+
+    /// Error codes of the get/set responses, which start with the
+    /// `STANDARD_OD_ERROR_CODES` and can be extended per network.
+    /// Fails if get_resp_erno or set_resp_erno isn't defined as an enum anymore.
+    pub fn od_error_codes(&self) -> Result<OdErrorCodesBuilder> {
+        OdErrorCodesBuilder::new(self)
+    }
+
+    /// Messages of the object dictionary protocol, shared by all nodes.
+    pub fn get_req_message(&self) -> MessageBuilder {
         self.0.borrow().get_req_message.get().unwrap().clone()
     }
 
-    pub fn _get_resp_message(&self) -> MessageBuilder {
+    pub fn get_resp_message(&self) -> MessageBuilder {
         self.0.borrow().get_resp_message.get().unwrap().clone()
     }
 
-    pub fn _set_req_message(&self) -> MessageBuilder {
+    pub fn set_req_message(&self) -> MessageBuilder {
         self.0.borrow().set_req_message.get().unwrap().clone()
     }
 
-    pub fn _set_resp_message(&self) -> MessageBuilder {
+    pub fn set_resp_message(&self) -> MessageBuilder {
         self.0.borrow().set_resp_message.get().unwrap().clone()
     }
 }
@@ -250,73 +736,10 @@ impl NetworkBuilder {
         defined_types: &Vec<TypeRef>,
         type_name: &str,
     ) -> errors::Result<ConfigRef<Type>> {
-        let int_regex = regex::Regex::new(r#"^i(?<size>[0-9]{1,2})$"#).unwrap();
-        match int_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                if size > 0 && size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::SignedInt {
-                        size,
-                    })));
-                }
-            }
-            None => (),
-        }
-        let uint_regex = regex::Regex::new(r#"^u(?<size>[0-9]{1,2})$"#).unwrap();
-        match uint_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                if size > 0 && size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::UnsignedInt {
-                        size,
-                    })));
-                }
-            }
-            None => (),
-        }
-        let dec_regex = regex::Regex::new(r"^d(?<size>[0-9]{1,2})<(?<min>[+-]?([0-9]*[.])?[0-9]+)\.\.(?<max>[+-]?([0-9]*[.])?[0-9]+)>$").unwrap();
-        match dec_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                let min = &cap["min"];
-                let min = min.parse::<f64>().unwrap();
-                let max = &cap["max"];
-                let max = max.parse::<f64>().unwrap();
-                if min >= max {
-                    return Err(errors::ConfigError::InvalidRange(
-                        "invalid decimal range min has to be less than max".to_owned(),
-                    ));
-                }
-                let range = max - min;
-                let scale = range / ((0xFFFFFFFFFFFFFFFF as u64 >> (64 - size)) as f64);
-                let offset = min;
-                if size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::Decimal {
-                        size,
-                        offset,
-                        scale,
-                    })));
-                }
-            }
-            None => (),
-        }
-        let array_regex =
-                regex::Regex::new(r#"^(?<type>[a-zA-Z][a-zA-Z0-9]*(<[+-]?([0-9]*[.])?[0-9]+\.\.[+-]?([0-9]*[.])?[0-9]+>)?)\[(?<len>[0-9]+)\]$"#).unwrap();
-        match array_regex.captures(type_name) {
-            Some(cap) => {
-                let len = &cap["len"];
-                let len = len.parse::<usize>().unwrap();
-                let ty = &cap["type"];
-                let inner_type = Self::resolve_type(defined_types, ty)?;
-                return Ok(make_config_ref(Type::Array {
-                    len,
-                    ty: inner_type,
-                }));
-            }
-            None => (),
+        if let Some(ty) =
+            resolve_builtin_type(type_name, |element| Self::resolve_type(defined_types, element))
+        {
+            return ty;
         }
         for ty in defined_types {
             match ty as &Type {
@@ -324,6 +747,7 @@ impl NetworkBuilder {
                     name,
                     description: _,
                     attribs: _,
+                    attrib_info: _,
                     visibility: _,
                 } if name == type_name => return Ok(ty.clone()),
                 Type::Enum {
@@ -331,8 +755,10 @@ impl NetworkBuilder {
                     description: _,
                     size: _,
                     entries: _,
+                    entry_info: _,
                     visibility: _,
                 } if name == type_name => return Ok(ty.clone()),
+                Type::Union { name, .. } if name == type_name => return Ok(ty.clone()),
                 _ => (),
             }
         }
@@ -341,193 +767,1164 @@ impl NetworkBuilder {
         )));
     }
 
-    fn topo_sort_types(types: &Vec<TypeRef>) -> Vec<TypeRef> {
-        let n = types.len();
-        struct Node {
-            // index: usize,
-            adj_list: Vec<usize>,
+    pub(crate) fn topo_sort_types(types: &[TypeRef]) -> Vec<TypeRef> {
+        let mut arena = TypeArena::default();
+        let mut indices = HashMap::new();
+        for (index, ty) in types.iter().enumerate() {
+            let (id, _) = arena.intern(ty.clone());
+            indices.entry(id).or_insert(index);
+        }
+        let dependencies: Vec<Vec<usize>> = types
+            .iter()
+            .map(|ty| {
+                let dependencies: Vec<&TypeRef> = match ty as &Type {
+                    Type::Struct { attribs, .. } => attribs.iter().map(|(_, ty)| ty).collect(),
+                    Type::Array { ty, .. } => vec![ty],
+                    Type::Union { tag, variants, .. } => std::iter::once(tag)
+                        .chain(variants.iter().map(|(_, ty)| ty))
+                        .collect(),
+                    _ => vec![],
+                };
+                dependencies
+                    .into_iter()
+                    .filter_map(|dep| indices.get(&arena.intern(dep.clone()).0).copied())
+                    .collect()
+            })
+            .collect();
+        // built types only reference types that were built before them.
+        let order = topological_sort(&dependencies).expect("built types can't be cyclic");
+        order.iter().map(|index| types[*index].clone()).collect()
+    }
+
+    /// Sorts the types of every node in topological order.
+    #[cfg(not(feature = "parallel"))]
+    fn topo_sort_node_types(node_types: Vec<Vec<TypeRef>>) -> Vec<Vec<TypeRef>> {
+        node_types.iter().map(|types| Self::topo_sort_types(types)).collect()
+    }
+
+    /// Sorts the types of every node in topological order on all available cores.
+    #[cfg(feature = "parallel")]
+    fn topo_sort_node_types(node_types: Vec<Vec<TypeRef>>) -> Vec<Vec<TypeRef>> {
+        use rayon::prelude::*;
+        node_types.par_iter().map(|types| Self::topo_sort_types(types)).collect()
+    }
+
+    /// Filters of the node on each of its buses, which accept at least one
+    /// message received on the bus. Received messages, which the id assignment
+    /// didn't place into a set of the node (e.g. messages of commands, which
+    /// don't declare receivers), get a filter of their exact id.
+    fn rx_filters_per_bus(
+        filters: &[RxFilter],
+        buses: &[BusRef],
+        rx_messages: &[MessageRef],
+    ) -> Vec<(BusRef, Vec<RxFilter>)> {
+        // nodes without assigned buses still receive messages on the buses
+        // of their rx messages.
+        let mut rx_buses: Vec<&BusRef> = buses.iter().collect();
+        for message in rx_messages {
+            if !rx_buses.iter().any(|bus| bus.id() == message.bus().id()) {
+                rx_buses.push(message.bus());
+            }
         }
-        let mut nodes: Vec<Node> = vec![];
-        for i in 0..n {
-            let ty = &types[i];
+        rx_buses
+            .into_iter()
+            .map(|bus| {
+                let bus_messages: Vec<&MessageRef> = rx_messages
+                    .iter()
+                    .filter(|message| message.bus().id() == bus.id())
+                    .collect();
+                let mut bus_filters: Vec<RxFilter> = filters
+                    .iter()
+                    .filter(|filter| {
+                        bus_messages
+                            .iter()
+                            .any(|message| filter.accepts(message.id()))
+                    })
+                    .copied()
+                    .collect();
+                for message in bus_messages {
+                    if bus_filters
+                        .iter()
+                        .any(|filter| filter.accepts(message.id()))
+                    {
+                        continue;
+                    }
+                    bus_filters.push(RxFilter::exact(message.id()));
+                }
+                (bus.clone(), bus_filters)
+            })
+            .collect()
+    }
+
+    fn topo_sort_type_builders(
+        type_builders: &Vec<TypeBuilder>,
+    ) -> errors::Result<Vec<TypeBuilder>> {
+        let mut dependencies = vec![];
+        for type_builder in type_builders {
             let mut adj_list = vec![];
-            match ty as &Type {
-                Type::Struct {
-                    name: _,
-                    description: _,
-                    attribs,
-                    visibility: _,
-                } => {
-                    for (_, attrib_type) in attribs {
-                        match types.iter().position(|t| t == attrib_type) {
-                            Some(adj) => adj_list.push(adj),
-                            None => (),
-                        }
+            for attrib_type_name in &type_builder.referenced_types() {
+                //check if type is a inplace definition (u?, i?, d?)
+                let is_inplace = Self::resolve_type(&vec![], attrib_type_name).is_ok();
+                if is_inplace {
+                    continue;
+                }
+                let opt = type_builders
+                    .iter()
+                    .position(|builder| &builder.name() == attrib_type_name);
+                match opt {
+                    Some(adj_index) => {
+                        adj_list.push(adj_index);
+                    }
+                    None => {
+                        return Err(errors::ConfigError::UndefinedType(format!(
+                            "{attrib_type_name}"
+                        )))
                     }
                 }
-                Type::Array { len: _, ty } => match types.iter().position(|t| t == ty) {
-                    Some(adj) => adj_list.push(adj),
-                    None => (),
-                },
-                _ => (),
             }
-            nodes.push(Node {
-                // index: i,
-                adj_list,
-            })
+            dependencies.push(adj_list);
         }
-        let mut stack: Vec<usize> = vec![];
-        let mut visited = vec![false; nodes.len()];
-        fn topo_sort_rec(
-            nodes: &Vec<Node>,
-            visited: &mut Vec<bool>,
-            current: usize,
-            stack: &mut Vec<usize>,
-        ) {
-            visited[current] = true;
-            for adj_index in &nodes[current].adj_list {
-                if !visited[*adj_index] {
-                    topo_sort_rec(nodes, visited, *adj_index, stack);
+
+        match topological_sort(&dependencies) {
+            Ok(order) => Ok(order
+                .iter()
+                .map(|index| type_builders[*index].clone())
+                .collect()),
+            Err(cycle) => {
+                let mut names: Vec<String> =
+                    cycle.iter().map(|index| type_builders[*index].name()).collect();
+                names.push(names[0].clone());
+                Err(errors::ConfigError::CyclicType(format!(
+                    "types {} depend on each other",
+                    names.join(" -> ")
+                )))
+            }
+        }
+    }
+
+    fn check_default_value(ty: &Type, value: &str) -> std::result::Result<(), String> {
+        match ty {
+            Type::Primitive(signal_type) => {
+                let value = value
+                    .parse::<f64>()
+                    .map_err(|_| "not a number".to_owned())?;
+                let (min, max) = signal_type.physical_range();
+                if value < min || value > max {
+                    return Err(format!("out of range [{min}, {max}]"));
                 }
+                if !matches!(signal_type, SignalType::Decimal { .. }) && value.fract() != 0.0 {
+                    return Err("integer types require an integer value".to_owned());
+                }
+                Ok(())
+            }
+            Type::Enum { entries, .. } => {
+                if entries.iter().any(|(entry, _)| entry == value) {
+                    Ok(())
+                } else {
+                    Err(format!("not an entry of enum {}", ty.name()))
+                }
+            }
+            Type::Struct { .. } | Type::Array { .. } | Type::Union { .. } => {
+                Err("default values are only supported for primitive and enum types".to_owned())
+            }
+        }
+    }
+
+    fn assign_object_entry_indices(
+        node_name: &str,
+        object_entries: &[ObjectEntryBuilder],
+        assignment: ObjectEntryIndexAssignment,
+        od_index_width: u8,
+    ) -> errors::Result<Vec<u32>> {
+        let max_index = 1u32 << od_index_width;
+        let mut indices: Vec<Option<u32>> = vec![None; object_entries.len()];
+        let mut reserved: Vec<u32> = vec![];
+        for (i, object_entry_builder) in object_entries.iter().enumerate() {
+            let object_entry_data = object_entry_builder.0.borrow();
+            let Some(index) = object_entry_data.index else {
+                continue;
+            };
+            let index = index as u32;
+            if index >= max_index {
+                return Err(errors::ConfigError::InvalidObjectEntryIndex(format!(
+                    "index {index} of object entry {node_name}::{} does not fit into the {od_index_width} bit od_index",
+                    object_entry_data.name
+                )));
             }
-            stack.push(current);
+            if let Some(other) = indices.iter().position(|x| *x == Some(index)) {
+                return Err(errors::ConfigError::DuplicatedObjectEntryIndex(format!(
+                    "object entries {node_name}::{} and {node_name}::{} are both pinned to index {index}",
+                    object_entries[other].0.borrow().name,
+                    object_entry_data.name
+                )));
+            }
+            indices[i] = Some(index);
+            reserved.push(index);
         }
-        for i in 0..n {
-            if !visited[i] {
-                topo_sort_rec(&nodes, &mut visited, i, &mut stack);
+        let mut next_index = 0;
+        for (i, object_entry_builder) in object_entries.iter().enumerate() {
+            if indices[i].is_some() {
+                continue;
             }
+            let start = match assignment {
+                ObjectEntryIndexAssignment::Sequential => next_index,
+                ObjectEntryIndexAssignment::HashBased => {
+                    (seahash::hash(object_entry_builder.0.borrow().name.as_bytes())
+                        % max_index as u64) as u32
+                }
+            };
+            let Some(index) = (0..max_index)
+                .map(|offset| (start + offset) % max_index)
+                .find(|index| !reserved.contains(index))
+            else {
+                return Err(errors::ConfigError::InvalidObjectEntryIndex(format!(
+                    "node {node_name} has {} object entries, but the {od_index_width} bit od_index can only address {max_index}",
+                    object_entries.len()
+                )));
+            };
+            indices[i] = Some(index);
+            reserved.push(index);
+            next_index = index + 1;
         }
+        Ok(indices.into_iter().flatten().collect())
+    }
 
-        stack.iter().map(|index| types[*index].clone()).collect()
+    /// Defines a variant of the network (e.g. a test bench), messages can
+    /// be restricted to with MessageBuilder::only_in.
+    pub fn define_variant(&self, name: &str) {
+        let mut network_data = self.0.borrow_mut();
+        if !network_data.variants.iter().any(|v| v == name) {
+            network_data.variants.push(name.to_owned());
+        }
     }
 
-    fn topo_sort_type_builders(
-        type_builders: &Vec<TypeBuilder>,
-    ) -> errors::Result<Vec<TypeBuilder>> {
-        // TODO check for cycles in the graph
-        // number of nodes
-        let n = type_builders.len();
+    fn check_variants(&self) -> errors::Result<()> {
+        let network_data = self.0.borrow();
+        for message in network_data.messages.borrow().iter() {
+            let message_data = message.0.borrow();
+            for variant in message_data.variants.iter().flatten() {
+                if !network_data.variants.contains(variant) {
+                    return Err(errors::ConfigError::UndefinedVariant(format!(
+                        "message {} is restricted to the undefined variant {variant}",
+                        message_data.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Array types are parsed, but have no signal layout, so they can't be
+    /// used in messages and object entries (directly or within a struct or union).
+    fn check_array_types(&self) -> errors::Result<()> {
+        let network_data = self.0.borrow();
+        // names of all types, which contain an array.
+        let mut array_types: Vec<String> = vec![];
+        let contains_array = |array_types: &Vec<String>, type_name: &str| {
+            type_parser::is_array_type(type_name) || array_types.iter().any(|name| name == type_name)
+        };
+        loop {
+            let mut changed = false;
+            for type_builder in network_data.types.borrow().iter() {
+                let (name, members) = match type_builder {
+                    TypeBuilder::Enum(_) => continue,
+                    TypeBuilder::Struct(struct_builder) => {
+                        let struct_data = struct_builder.0.borrow();
+                        (struct_data.name.clone(), struct_data.attributes.clone())
+                    }
+                    TypeBuilder::Union(union_builder) => {
+                        let union_data = union_builder.0.borrow();
+                        (union_data.name.clone(), union_data.variants.clone())
+                    }
+                };
+                if !array_types.contains(&name)
+                    && members.iter().any(|(_, ty)| contains_array(&array_types, ty))
+                {
+                    array_types.push(name);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        for message in network_data.messages.borrow().iter() {
+            let message_data = message.0.borrow();
+            let MessageFormat::Types(type_format) = &message_data.format else {
+                continue;
+            };
+            for (type_name, value_name) in &type_format.0.borrow().0 {
+                if contains_array(&array_types, type_name) {
+                    return Err(errors::ConfigError::InvalidType(format!(
+                        "{value_name} of message {} has the type {type_name}, arrays aren't supported in messages",
+                        message_data.name
+                    )));
+                }
+            }
+        }
+        for node in network_data.nodes.borrow().iter() {
+            let node_data = node.0.borrow();
+            for object_entry in &node_data.object_entries {
+                let object_entry_data = object_entry.0.borrow();
+                if contains_array(&array_types, &object_entry_data.ty) {
+                    return Err(errors::ConfigError::InvalidType(format!(
+                        "object entry {} of node {} has the type {}, arrays aren't supported in object entries",
+                        object_entry_data.name, node_data.name, object_entry_data.ty
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 
-        #[derive(Debug)]
-        struct Node {
-            // index: usize,
-            adj_list: Vec<usize>,
+    /// Builds the network without the messages (and the streams and commands
+    /// using them), which are restricted to other variants.
+    pub fn build_variant(self, variant: &str) -> errors::Result<NetworkRef> {
+        self.check_variants()?;
+        if !self.0.borrow().variants.iter().any(|v| v == variant) {
+            return Err(errors::ConfigError::UndefinedVariant(format!(
+                "variant {variant} is not defined"
+            )));
         }
+        remove_messages(&self, |message| {
+            message
+                .0
+                .borrow()
+                .variants
+                .as_ref()
+                .is_some_and(|variants| !variants.iter().any(|v| v == variant))
+        });
+        self.build()
+    }
 
-        let mut nodes: Vec<Node> = vec![];
-        for node_index in 0..n {
-            let adj_list = match &type_builders[node_index] {
-                TypeBuilder::Enum(_) => vec![],
-                TypeBuilder::Struct(struct_builder) => {
-                    let struct_data = struct_builder.0.borrow();
-                    let mut dependencies = vec![];
-                    for (_, attrib_type_name) in &struct_data.attributes {
-                        //check if type is a inplace definition (u?, i?, d?)
-                        let is_inplace = Self::resolve_type(&vec![], attrib_type_name).is_ok();
-                        if is_inplace {
-                            continue;
-                        }
-                        let opt = type_builders
-                            .iter()
-                            .position(|builder| &builder.name() == attrib_type_name);
-                        match opt {
-                            Some(adj_index) => {
-                                dependencies.push(adj_index);
-                            }
-                            None => {
-                                return Err(errors::ConfigError::UndefinedType(format!(
-                                    "{attrib_type_name}"
-                                )))
-                            }
-                        }
+    /// Defines the node sending diagnostic requests to nodes with enabled UDS.
+    pub fn set_uds_tester(&self, node_name: &str) {
+        self.0.borrow_mut().uds_tester = Some(node_name.to_owned());
+    }
+
+    fn generate_uds_channels(&self) -> errors::Result<()> {
+        let uds_nodes: Vec<NodeBuilder> = self
+            .0
+            .borrow()
+            .nodes
+            .borrow()
+            .iter()
+            .filter(|node| {
+                let node_data = node.0.borrow();
+                // channels of a previous build are kept.
+                node_data.uds_services.is_some() && node_data.uds_channels.is_none()
+            })
+            .cloned()
+            .collect();
+        if uds_nodes.is_empty() {
+            return Ok(());
+        }
+        let Some(tester_name) = self.0.borrow().uds_tester.clone() else {
+            return Err(errors::ConfigError::InvalidUdsConfiguration(
+                "nodes with enabled UDS require a tester (set_uds_tester)".to_owned(),
+            ));
+        };
+        let tester = self.create_node(&tester_name);
+        for node in uds_nodes {
+            let node_name = node.0.borrow().name.clone();
+            if node_name == tester_name {
+                return Err(errors::ConfigError::InvalidUdsConfiguration(format!(
+                    "the tester {tester_name} can't answer to diagnostic requests itself"
+                )));
+            }
+            let request_channel =
+                tester.create_transport_channel(&format!("uds_{node_name}"), &node_name);
+            let response_channel = node.create_transport_channel("uds", &tester_name);
+            node.0.borrow_mut().uds_channels = Some((request_channel, response_channel));
+        }
+        Ok(())
+    }
+
+    /// Checks that every message, bus and object entry referenced by the nodes
+    /// was added to the network, so that assembling the nodes can't fail.
+    fn check_references(&self, messages: &[Message], buses: &[BusRef]) -> errors::Result<()> {
+        let builder = self.0.borrow();
+        let check_message = |message_builder: &MessageBuilder, context: &str| {
+            let name = &message_builder.0.borrow().name;
+            if messages.iter().any(|m| m.name() == name) {
+                Ok(())
+            } else {
+                Err(errors::ConfigError::MessageNotRegistered(format!(
+                    "message {name} ({context}) was not added to the network"
+                )))
+            }
+        };
+        for (protocol, cell) in [
+            ("get_resp", &builder.get_resp_message),
+            ("get_req", &builder.get_req_message),
+            ("set_resp", &builder.set_resp_message),
+            ("set_req", &builder.set_req_message),
+        ] {
+            match cell.get() {
+                Some(message_builder) => {
+                    check_message(message_builder, &format!("{protocol} protocol message"))?
+                }
+                None => {
+                    return Err(errors::ConfigError::MessageNotRegistered(format!(
+                        "{protocol} protocol message was not defined"
+                    )))
+                }
+            }
+        }
+        let nodes = builder.nodes.borrow();
+        for node_builder in nodes.iter() {
+            let node_data = node_builder.0.borrow();
+            let node_name = &node_data.name;
+            for message_builder in node_data.rx_messages.iter() {
+                check_message(message_builder, &format!("received by {node_name}"))?;
+            }
+            for message_builder in node_data.tx_messages.iter() {
+                check_message(message_builder, &format!("transmitted by {node_name}"))?;
+            }
+            for command_builder in &node_data.commands {
+                let command_data = command_builder.0.borrow();
+                let context = format!("of command {node_name}::{}", command_data.name);
+                check_message(&command_data.call_message, &context)?;
+                check_message(&command_data.resp_message, &context)?;
+            }
+            for channel_builder in &node_data.transport_channels {
+                let channel_data = channel_builder.0.borrow();
+                let context = format!("of transport channel {}", channel_data.name);
+                check_message(&channel_data.data_message, &context)?;
+                check_message(&channel_data.flow_control_message, &context)?;
+            }
+            let has_object_entry = |name: &str| {
+                node_data
+                    .object_entries
+                    .iter()
+                    .any(|oe| oe.0.borrow().name == name)
+            };
+            for stream_builder in &node_data.tx_streams {
+                let stream_data = stream_builder.0.borrow();
+                check_message(
+                    &stream_data.message,
+                    &format!("of stream {node_name}::{}", stream_data.name),
+                )?;
+                for oe_builder in stream_data.object_entries.iter().flatten() {
+                    let oe_name = &oe_builder.0.borrow().name;
+                    if !has_object_entry(oe_name) {
+                        return Err(errors::ConfigError::StreamEntryMissing(format!(
+                            "object entry {oe_name} of stream {node_name}::{} isn't defined by node {node_name}",
+                            stream_data.name
+                        )));
                     }
-                    dependencies
                 }
+                drop(stream_data);
+                stream_builder.conversions()?;
+            }
+            for rx_stream_builder in &node_data.rx_streams {
+                let rx_stream_data = rx_stream_builder.0.borrow();
+                let tx_stream_data = rx_stream_data.stream_builder.0.borrow();
+                let tx_node_name = tx_stream_data.tx_node.0.borrow().name.clone();
+                let tx_node = nodes
+                    .iter()
+                    .find(|n| n.0.borrow().name == tx_node_name)
+                    .ok_or_else(|| {
+                        errors::ConfigError::UndefinedNode(format!(
+                            "node {tx_node_name} transmitting stream {} received by {node_name} was not added to the network",
+                            tx_stream_data.name
+                        ))
+                    })?;
+                if !tx_node
+                    .0
+                    .borrow()
+                    .tx_streams
+                    .iter()
+                    .any(|s| Rc::ptr_eq(&s.0, &rx_stream_data.stream_builder.0))
+                {
+                    return Err(errors::ConfigError::StreamEntryMissing(format!(
+                        "stream {tx_node_name}::{} received by {node_name} isn't transmitted by {tx_node_name}",
+                        tx_stream_data.name
+                    )));
+                }
+                for (_, oe_builder) in &rx_stream_data.object_entries {
+                    let oe_name = &oe_builder.0.borrow().name;
+                    if !has_object_entry(oe_name) {
+                        return Err(errors::ConfigError::StreamEntryMissing(format!(
+                            "object entry {oe_name} mapped from stream {tx_node_name}::{} isn't defined by node {node_name}",
+                            tx_stream_data.name
+                        )));
+                    }
+                }
+            }
+            for bus_builder in &node_data.buses {
+                let bus_data = bus_builder.0.borrow();
+                if !buses.iter().any(|bus| bus.id() == bus_data.id) {
+                    return Err(errors::ConfigError::BusNotFound(format!(
+                        "bus {} of node {node_name} was not added to the network",
+                        bus_data.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_extern_nodes(&self) -> errors::Result<()> {
+        for node in self.nodes().iter().filter(|node| node.is_extern()) {
+            let node_data = node.0.borrow();
+            if !node_data.object_entries.is_empty()
+                || !node_data.commands.is_empty()
+                || !node_data.tx_streams.is_empty()
+                || !node_data.rx_streams.is_empty()
+            {
+                return Err(errors::ConfigError::InvalidExternNode(format!(
+                    "extern node {} can't have object entries, commands or streams",
+                    node_data.name
+                )));
+            }
+            for message in &node_data.tx_messages {
+                let message_data = message.0.borrow();
+                let fixed = matches!(
+                    message_data.id,
+                    MessageIdTemplate::StdId(_) | MessageIdTemplate::ExtId(_)
+                ) || message_data.j1939.is_some();
+                if !fixed {
+                    return Err(errors::ConfigError::InvalidExternNode(format!(
+                        "message {} transmitted by extern node {} requires a fixed id",
+                        message_data.name, node_data.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_listen_only_nodes(&self) -> errors::Result<()> {
+        for node in self.nodes() {
+            let node_data = node.0.borrow();
+            if !node_data.listen_only {
+                continue;
+            }
+            let invalid = |what: String| {
+                Err(errors::ConfigError::InvalidListenOnlyNode(format!(
+                    "listen-only node {} can't transmit {what}",
+                    node_data.name
+                )))
             };
-            nodes.push(Node {
-                // index: node_index,
-                adj_list,
-            });
+            if let Some(stream) = node_data.tx_streams.first() {
+                return invalid(format!("stream {}", stream.0.borrow().name));
+            }
+            if let Some(command) = node_data.commands.first() {
+                return invalid(format!("the responses of command {}", command.0.borrow().name));
+            }
+            if let Some(command) = node_data.extern_commands.first() {
+                return invalid(format!("the calls of command {}", command.0.borrow().name));
+            }
+            if let Some(message) = node_data.tx_messages.first() {
+                return invalid(format!("message {}", message.0.borrow().name));
+            }
         }
+        Ok(())
+    }
 
-        let mut stack: Vec<usize> = vec![];
-        let mut visited = vec![false; nodes.len()];
-        fn topo_sort_rec(
-            nodes: &Vec<Node>,
-            visited: &mut Vec<bool>,
-            current: usize,
-            stack: &mut Vec<usize>,
-        ) {
-            visited[current] = true;
-            for adj_index in &nodes[current].adj_list {
-                if !visited[*adj_index] {
-                    topo_sort_rec(nodes, visited, *adj_index, stack);
+    /// Nodes without the object dictionary protocol can't receive get/set
+    /// requests, which are required to write object entries and address groups.
+    fn check_od_protocol(&self) -> errors::Result<()> {
+        let protocol_messages = [
+            self.get_req_message(),
+            self.get_resp_message(),
+            self.set_req_message(),
+            self.set_resp_message(),
+        ];
+        for node in self.nodes().iter().filter(|node| !node.0.borrow().od_protocol) {
+            if node.is_extern() {
+                continue;
+            }
+            let node_data = node.0.borrow();
+            if let Some(message) = node_data
+                .rx_messages
+                .iter()
+                .chain(&node_data.tx_messages)
+                .find(|m| protocol_messages.iter().any(|p| Rc::ptr_eq(&p.0, &m.0)))
+            {
+                return Err(errors::ConfigError::InvalidOdProtocol(format!(
+                    "node {} disabled the od protocol, but uses the message {}",
+                    node_data.name,
+                    message.0.borrow().name
+                )));
+            }
+            // entries are globally writable by default, only explicit
+            // protection and broadcasts are rejected.
+            if let Some(oe) = node_data.object_entries.iter().find(|oe| {
+                let oe_data = oe.0.borrow();
+                oe_data.broadcast || matches!(oe_data.access, ObjectEntryAccess::Protected { .. })
+            }) {
+                return Err(errors::ConfigError::InvalidOdProtocol(format!(
+                    "object entry {}::{} is protected or accepts broadcasts, but the node disabled the od protocol",
+                    node_data.name,
+                    oe.0.borrow().name
+                )));
+            }
+            if let Some(group) = self
+                .node_groups()
+                .iter()
+                .find(|group| group.0.borrow().nodes.iter().any(|n| Rc::ptr_eq(&n.0, &node.0)))
+            {
+                return Err(errors::ConfigError::InvalidOdProtocol(format!(
+                    "node {} disabled the od protocol, but is part of the group {}",
+                    node_data.name,
+                    group.name()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_latency_paths(&self) -> errors::Result<()> {
+        for path in self.latency_paths() {
+            let path_data = path.0.borrow();
+            if path_data.hops.is_empty() {
+                return Err(errors::ConfigError::InvalidLatencyPath(format!(
+                    "latency path {} has no hops",
+                    path_data.name
+                )));
+            }
+            for hop in &path_data.hops {
+                let (LatencyHopBuilder::ObjectEntry { node, name }
+                | LatencyHopBuilder::Stream { node, name }
+                | LatencyHopBuilder::Command { node, name }) = hop;
+                let Some(node_builder) = self.find_node(node) else {
+                    return Err(errors::ConfigError::InvalidLatencyPath(format!(
+                        "node {node} of latency path {} is not defined",
+                        path_data.name
+                    )));
+                };
+                let node_data = node_builder.0.borrow();
+                let (kind, exists) = match hop {
+                    LatencyHopBuilder::ObjectEntry { .. } => (
+                        "object entry",
+                        node_data.object_entries.iter().any(|oe| &oe.0.borrow().name == name),
+                    ),
+                    LatencyHopBuilder::Stream { .. } => (
+                        "stream",
+                        node_data.tx_streams.iter().any(|s| &s.0.borrow().name == name),
+                    ),
+                    LatencyHopBuilder::Command { .. } => (
+                        "command",
+                        node_data.commands.iter().any(|c| &c.0.borrow().name == name),
+                    ),
+                };
+                if !exists {
+                    return Err(errors::ConfigError::InvalidLatencyPath(format!(
+                        "{kind} {node}::{name} of latency path {} is not defined",
+                        path_data.name
+                    )));
                 }
             }
-            stack.push(current);
         }
-        for i in 0..n {
-            if !visited[i] {
-                topo_sort_rec(&nodes, &mut visited, i, &mut stack);
+        Ok(())
+    }
+
+    /// Checks that every supervisor receives the message,
+    /// which is transmitted by the supervised node.
+    fn check_supervisions(&self) -> errors::Result<()> {
+        for node in self.nodes() {
+            let node_data = node.0.borrow();
+            for (supervised, message_name, timeout) in &node_data.supervisions {
+                let context = format!(
+                    "supervision of {supervised} by {} via {message_name}",
+                    node_data.name
+                );
+                let Some(supervised_node) = self.find_node(supervised) else {
+                    return Err(errors::ConfigError::InvalidSupervision(format!(
+                        "node {supervised} of the {context} is not defined"
+                    )));
+                };
+                let Some(message) = self.find_message(message_name) else {
+                    return Err(errors::ConfigError::InvalidSupervision(format!(
+                        "message {message_name} of the {context} is not defined"
+                    )));
+                };
+                if timeout.is_zero() {
+                    return Err(errors::ConfigError::InvalidSupervision(format!(
+                        "the timeout of the {context} has to be positive"
+                    )));
+                }
+                if !node_data.rx_messages.iter().any(|m| Rc::ptr_eq(&m.0, &message.0)) {
+                    return Err(errors::ConfigError::InvalidSupervision(format!(
+                        "{} doesn't receive {message_name} ({context})",
+                        node_data.name
+                    )));
+                }
+                let transmits = supervised_node
+                    .0
+                    .borrow()
+                    .tx_messages
+                    .iter()
+                    .any(|m| Rc::ptr_eq(&m.0, &message.0));
+                if !transmits {
+                    return Err(errors::ConfigError::InvalidSupervision(format!(
+                        "{supervised} doesn't transmit {message_name} ({context})"
+                    )));
+                }
             }
         }
-        Ok(stack
+        Ok(())
+    }
+
+    /// Checks that messages, types, nodes, buses and the commands, streams
+    /// and object entries of every node have unique names, because the
+    /// build resolves references by name.
+    fn check_names(&self) -> errors::Result<()> {
+        // (name, definition) of every element of a category.
+        fn check_unique(category: &str, definitions: Vec<(String, String)>) -> errors::Result<()> {
+            for (i, (name, definition)) in definitions.iter().enumerate() {
+                if let Some((_, first)) = definitions[..i].iter().find(|(other, _)| other == name) {
+                    return Err(errors::ConfigError::DuplicateName(format!(
+                        "{category} {name} is defined twice, as {first} and as {definition}"
+                    )));
+                }
+            }
+            Ok(())
+        }
+        let types = self
+            .types()
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| {
+                let kind = match ty {
+                    TypeBuilder::Enum(_) => "enum",
+                    TypeBuilder::Struct(_) => "struct",
+                    TypeBuilder::Union(_) => "union",
+                };
+                (ty.name(), format!("{kind} #{i}"))
+            })
+            .collect();
+        check_unique("type", types)?;
+        let nodes = self
+            .nodes()
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.0.borrow().name.clone(), format!("node #{i}")))
+            .collect();
+        check_unique("node", nodes)?;
+        let buses = self
+            .buses()
+            .iter()
+            .map(|bus| {
+                let bus_data = bus.0.borrow();
+                (bus_data.name.clone(), format!("bus with id {}", bus_data.id))
+            })
+            .collect();
+        check_unique("bus", buses)?;
+        for node in self.nodes() {
+            let node_data = node.0.borrow();
+            let qualified = |name: &str| format!("{}::{name}", node_data.name);
+            let commands = node_data
+                .commands
+                .iter()
+                .enumerate()
+                .map(|(i, command)| (qualified(&command.0.borrow().name), format!("command #{i}")))
+                .collect();
+            check_unique("command", commands)?;
+            let streams = node_data
+                .tx_streams
+                .iter()
+                .enumerate()
+                .map(|(i, stream)| (qualified(&stream.0.borrow().name), format!("stream #{i}")))
+                .collect();
+            check_unique("stream", streams)?;
+            let object_entries = node_data
+                .object_entries
+                .iter()
+                .map(|oe| {
+                    let oe_data = oe.0.borrow();
+                    (qualified(&oe_data.name), format!("object entry of type {}", oe_data.ty))
+                })
+                .collect();
+            check_unique("object entry", object_entries)?;
+        }
+        // the messages of streams and commands are named after them.
+        let messages = self
+            .messages()
             .iter()
-            .map(|index| type_builders[*index].clone())
-            .collect())
+            .enumerate()
+            .map(|(i, message)| {
+                let message_data = message.0.borrow();
+                let definition = match &message_data.usage {
+                    MessageBuilderUsage::Stream(stream) => {
+                        let stream_data = stream.0.borrow();
+                        format!("message of stream {}::{}", stream_data.tx_node.0.borrow().name, stream_data.name)
+                    }
+                    MessageBuilderUsage::CommandReq(command) => {
+                        let command_data = command.0.borrow();
+                        format!("call message of command {}::{}", command_data.tx_node.0.borrow().name, command_data.name)
+                    }
+                    MessageBuilderUsage::CommandResp(command) => {
+                        let command_data = command.0.borrow();
+                        format!("response message of command {}::{}", command_data.tx_node.0.borrow().name, command_data.name)
+                    }
+                    MessageBuilderUsage::TransportChannel(channel) => {
+                        format!("message of transport channel {}", channel.0.borrow().name)
+                    }
+                    MessageBuilderUsage::Configuration => "object dictionary message".to_owned(),
+                    MessageBuilderUsage::Heartbeat => "heartbeat message".to_owned(),
+                    MessageBuilderUsage::Emergency => format!("emergency message #{i}"),
+                    MessageBuilderUsage::Debug { .. } => format!("debug message #{i}"),
+                    MessageBuilderUsage::Bootloader => format!("bootloader message #{i}"),
+                    MessageBuilderUsage::External { .. } if message_data.transmitters.is_empty() => {
+                        format!("message #{i}")
+                    }
+                    MessageBuilderUsage::External { .. } => {
+                        let transmitters: Vec<String> =
+                            message_data.transmitters.iter().map(|node| node.0.borrow().name.clone()).collect();
+                        format!("message #{i} transmitted by {}", transmitters.join(", "))
+                    }
+                };
+                (message_data.name.clone(), definition)
+            })
+            .collect();
+        check_unique("message", messages)?;
+        Ok(())
+    }
+
+    fn lifecycle(
+        introduced_in: &Option<String>,
+        deprecated_in: &Option<String>,
+    ) -> errors::Result<Lifecycle> {
+        let parse = |version: &Option<String>| version.as_deref().map(Version::from_str).transpose();
+        Ok(Lifecycle::new(parse(introduced_in)?, parse(deprecated_in)?))
+    }
+
+    /// Checks that the annotated receivers of signals receive their message.
+    fn check_signal_receivers(&self) -> errors::Result<()> {
+        for message in self.messages() {
+            let message_data = message.0.borrow();
+            let MessageFormat::Signals(signal_format) = &message_data.format else {
+                continue;
+            };
+            for signal in &signal_format.0.borrow().signals {
+                for receiver in signal.receivers() {
+                    let receives = message_data
+                        .receivers
+                        .iter()
+                        .any(|node| &node.0.borrow().name == receiver);
+                    if !receives {
+                        return Err(errors::ConfigError::InvalidSignalReceiver(format!(
+                            "node {receiver} uses signal {}::{}, but doesn't receive the message",
+                            message_data.name,
+                            signal.name()
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_versions(&self) -> errors::Result<()> {
+        let check = |subject: String, introduced_in: &Option<String>, deprecated_in: &Option<String>| {
+            let lifecycle = Self::lifecycle(introduced_in, deprecated_in).map_err(|err| match err {
+                errors::ConfigError::InvalidVersion(reason) => {
+                    errors::ConfigError::InvalidVersion(format!("{subject} : {reason}"))
+                }
+                err => err,
+            })?;
+            if let (Some(introduced), Some(deprecated)) =
+                (lifecycle.introduced_in(), lifecycle.deprecated_in())
+            {
+                if deprecated < introduced {
+                    return Err(errors::ConfigError::InvalidVersion(format!(
+                        "{subject} is deprecated in {deprecated} before it's introduced in {introduced}"
+                    )));
+                }
+            }
+            Ok(())
+        };
+        for message in self.0.borrow().messages.borrow().iter() {
+            let message_data = message.0.borrow();
+            check(
+                format!("message {}", message_data.name),
+                &message_data.introduced_in,
+                &message_data.deprecated_in,
+            )?;
+            if let MessageFormat::Signals(signal_format) = &message_data.format {
+                for signal in &signal_format.0.borrow().signals {
+                    let lifecycle = signal.lifecycle();
+                    if let (Some(introduced), Some(deprecated)) =
+                        (lifecycle.introduced_in(), lifecycle.deprecated_in())
+                    {
+                        if deprecated < introduced {
+                            return Err(errors::ConfigError::InvalidVersion(format!(
+                                "signal {}::{} is deprecated in {deprecated} before it's introduced in {introduced}",
+                                message_data.name, signal.name()
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        check("network version".to_owned(), &self.0.borrow().version, &None)?;
+        for node in self.nodes() {
+            let node_data = node.0.borrow();
+            check(
+                format!("firmware version of node {}", node_data.name),
+                &node_data.firmware_version,
+                &None,
+            )?;
+            for object_entry in &node_data.object_entries {
+                let object_entry_data = object_entry.0.borrow();
+                check(
+                    format!("object entry {}::{}", node_data.name, object_entry_data.name),
+                    &object_entry_data.introduced_in,
+                    &object_entry_data.deprecated_in,
+                )?;
+            }
+        }
+        Ok(())
     }
 
+    pub(crate) fn dirty_flags(&self) -> Vec<Rc<dyn DirtyFlag>> {
+        let network_data = self.0.borrow();
+        let mut flags: Vec<Rc<dyn DirtyFlag>> = vec![
+            self.0.clone(),
+            network_data.messages.clone(),
+            network_data.types.clone(),
+            network_data.nodes.clone(),
+            network_data.buses.clone(),
+        ];
+        for message in network_data.messages.borrow().iter() {
+            flags.extend(message.dirty_flags());
+        }
+        for node in network_data.nodes.borrow().iter() {
+            flags.extend(node.dirty_flags());
+        }
+        for type_builder in network_data.types.borrow().iter() {
+            flags.push(type_builder.dirty_flag());
+        }
+        flags.extend(network_data.buses.borrow().iter().map(|bus| bus.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(network_data.node_groups.iter().map(|group| group.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(network_data.latency_paths.iter().map(|path| path.0.clone() as Rc<dyn DirtyFlag>));
+        flags
+    }
+
+    /// Any builder of the network was modified since the last build.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_flags().iter().any(|flag| flag.is_dirty())
+    }
+
+    /// Builds the network again after it was edited, e.g. by an interactive tool.
+    ///
+    /// Returns the previously built network if no builder was modified since
+    /// the last build. Otherwise the network is rebuilt, reusing the types of
+    /// the previous build, which didn't change. The ids resolved by the previous
+    /// build are kept, so only new messages and messages whose id was changed are
+    /// resolved again. Messages and nodes are always reassembled, because they
+    /// reference each other.
+    pub fn rebuild(&self) -> errors::Result<NetworkRef> {
+        let last_build = self.0.borrow().last_build.clone();
+        match last_build {
+            Some(network) if !self.is_dirty() => Ok(network),
+            _ => self.clone().build(),
+        }
+    }
+
+    /// Validates the builders and builds the network.
+    ///
+    /// Progress is reported through the `log` crate. The phases of the build
+    /// (buses, type resolution, id assignment, message assembly, node assembly
+    /// and validation) are logged at debug level with their duration under the
+    /// target `BUILD_LOG_TARGET`, warnings about the network at warn level.
     pub fn build(self) -> errors::Result<NetworkRef> {
+        self.build_with_observer(&mut ())
+    }
+
+    /// Builds the network like `build` and reports the progress to the observer,
+    /// which can cancel the build between its steps.
+    pub fn build_with_observer(
+        self,
+        observer: &mut dyn BuildObserver,
+    ) -> errors::Result<NetworkRef> {
+        // the heartbeat message and the node_id enum were generated by a previous build.
+        let is_heartbeat =
+            |message: &MessageBuilder| matches!(message.0.borrow().usage, MessageBuilderUsage::Heartbeat);
+        if self.messages().iter().any(is_heartbeat) {
+            remove_messages(&self, is_heartbeat);
+            self.0
+                .borrow()
+                .types
+                .borrow_mut()
+                .retain(|ty| !matches!(ty, TypeBuilder::Enum(_)) || ty.name() != "node_id");
+        }
+        self.check_names()?;
+        self.check_variants()?;
+        self.check_array_types()?;
+        self.apply_node_id_width()?;
+        self.apply_od_index_width()?;
+        self.resolve_callee_groups()?;
+        self.check_extern_nodes()?;
+        self.check_od_protocol()?;
+        self.check_latency_paths()?;
+        self.check_supervisions()?;
+        self.check_versions()?;
+        self.check_signal_receivers()?;
+        self.generate_uds_channels()?;
+        self.check_listen_only_nodes()?;
+        let node_ids = self.assign_node_ids()?;
+        let broadcast_server_id = self.broadcast_server_id();
         // Generate Heartbeat messages!
         let enum_node_id = self.define_enum("node_id");
-        let mut node_id = 0;
-        for node_builder in self.0.borrow().nodes.borrow().iter() {
+        for (node_builder, node_id) in std::iter::zip(self.nodes(), &node_ids) {
             let node_name = node_builder.0.borrow().name.clone();
-            enum_node_id.add_entry(&node_name, Some(node_id))?;
-            node_id += 1;
+            enum_node_id.add_entry(&node_name, Some(*node_id as u64))?;
         }
-        let heartbeat_message = self.create_message("heartbeat", Some(Duration::from_millis(100)));
-        heartbeat_message.__assign_to_heartbeat();
+        let heartbeat_message = self.create_message("heartbeat", Some(HEARTBEAT_INTERVAL));
+        heartbeat_message.assign_to_heartbeat();
         heartbeat_message.set_any_std_id(MessagePriority::SuperLow);
         let heartbeat_message_format = heartbeat_message.make_type_format();
         heartbeat_message_format.add_type("node_id", "node_id");
         for node_builder in self.0.borrow().nodes.borrow().iter() {
-            node_builder.add_tx_message(&heartbeat_message);
+            if node_builder.is_extern() {
+                continue;
+            }
+            // listen-only nodes observe the heartbeats of the other nodes.
+            if !node_builder.0.borrow().listen_only {
+                node_builder.add_tx_message(&heartbeat_message);
+            }
             node_builder.add_rx_message(&heartbeat_message);
         }
 
         if self.0.borrow().buses.borrow().is_empty() {
             // ensure that there is always at least one bus defined!
             self.create_bus("can0", None);
-            #[cfg(feature = "logging_info")]
-            println!("[CANZERO-CONFIG::build] Implicitly added can0 as the only bus");
+            log::debug!("Implicitly added can0 as the only bus");
         }
         let builder = self.0.borrow();
 
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Building buses");
-        let buses: Vec<BusRef> = builder
-            .buses
-            .borrow()
-            .iter()
-            .map(|bus_builder| {
-                let bus_data = bus_builder.0.borrow();
-                make_config_ref(config::bus::Bus::new(
-                    &bus_data.name,
-                    bus_data.id,
-                    bus_data.baudrate,
+        let mut access_levels: Vec<AccessLevelRef> = vec![];
+        for (rank, (name, description)) in builder.access_levels.iter().enumerate() {
+            if access_levels.iter().any(|level| level.name() == name) {
+                return Err(errors::ConfigError::DuplicatedAccessLevel(format!(
+                    "access level {name} is defined more than once"
+                )));
+            }
+            let rank = u8::try_from(rank).map_err(|_| {
+                errors::ConfigError::InvalidRange(format!(
+                    "access level {name} has the rank {rank}, but at most {} access levels are supported",
+                    u8::MAX as usize + 1
                 ))
-            })
-            .collect();
+            })?;
+            access_levels.push(make_config_ref(AccessLevel::new(
+                name,
+                description.as_deref(),
+                rank,
+            )));
+        }
+
+        let phase = PhaseTimer::begin(observer, BuildPhase::Buses)?;
+        let mut buses: Vec<BusRef> = vec![];
+        for bus_builder in builder.buses.borrow().iter() {
+            let bus_data = bus_builder.0.borrow();
+            if let Some(sample_point) = bus_data.sample_point {
+                if !(50.0..100.0).contains(&sample_point) {
+                    return Err(errors::ConfigError::InvalidRange(format!(
+                        "sample point {sample_point}% of bus {} is not within [50%, 100%)",
+                        bus_data.name
+                    )));
+                }
+            }
+            if bus_data.sjw == Some(0) {
+                return Err(errors::ConfigError::InvalidRange(format!(
+                    "sjw of bus {} has to be at least one time quantum",
+                    bus_data.name
+                )));
+            }
+            let baudrate = match bus_data.baudrate {
+                Some(baudrate) => baudrate,
+                None => {
+                    log::warn!(
+                        "bus {} doesn't declare a baudrate, defaulting to {DEFAULT_BAUDRATE} bit/s",
+                        bus_data.name
+                    );
+                    DEFAULT_BAUDRATE
+                }
+            };
+            let allowed_baudrates = bus_data
+                .allowed_baudrates
+                .as_deref()
+                .unwrap_or(&STANDARD_BAUDRATES);
+            if !allowed_baudrates.contains(&baudrate) {
+                return Err(errors::ConfigError::InvalidBaudrate(format!(
+                    "baudrate {baudrate} of bus {} is not one of {allowed_baudrates:?}",
+                    bus_data.name
+                )));
+            }
+            if let Some(clock_hz) = bus_data.controller_clock {
+                let options = BitTimingOptions {
+                    sample_point: bus_data
+                        .sample_point
+                        .map_or(BitTimingOptions::default().sample_point, |p| p / 100.0),
+                    sjw: bus_data.sjw,
+                    ..Default::default()
+                };
+                if compute_bit_timing(clock_hz, baudrate, &options).is_none() {
+                    return Err(errors::ConfigError::InvalidBaudrate(format!(
+                        "baudrate {baudrate} of bus {} can't be achieved with a controller clock of {clock_hz} Hz",
+                        bus_data.name
+                    )));
+                }
+            }
+            buses.push(make_config_ref(config::bus::Bus::new(
+                &bus_data.name,
+                bus_data.id,
+                baudrate,
+                bus_data.sample_point,
+                bus_data.sjw,
+                bus_data.description.clone(),
+                bus_data.connector.clone(),
+            )));
+        }
+
+        // the controllers of a node usually share a single clock.
+        for node in builder.nodes.borrow().iter() {
+            let node_data = node.0.borrow();
+            let mut clocks: Vec<(String, u32)> = node_data
+                .buses
+                .iter()
+                .filter_map(|bus| {
+                    let bus_data = bus.0.borrow();
+                    bus_data.controller_clock.map(|clock| (bus_data.name.clone(), clock))
+                })
+                .collect();
+            clocks.sort_by_key(|(_, clock)| *clock);
+            clocks.dedup_by_key(|(_, clock)| *clock);
+            if clocks.len() > 1 {
+                let clocks: Vec<String> = clocks
+                    .iter()
+                    .map(|(bus, clock)| format!("{bus} : {clock} Hz"))
+                    .collect();
+                log::warn!(
+                    "node {} is attached to buses with conflicting controller clocks ({})",
+                    node_data.name,
+                    clocks.join(", ")
+                );
+            }
+        }
 
         // sort types in topological order!
         let type_builders = Self::topo_sort_type_builders(&builder.types.borrow())?;
 
         // define types.
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Building Types");
+        drop(phase);
+
+        let phase = PhaseTimer::begin(observer, BuildPhase::TypeResolution)?;
         let mut types = vec![];
-        for type_builder in type_builders.iter() {
+        let type_resolver = TypeResolver::default();
+        // unchanged types keep the ref of the previous build.
+        if let Some(network) = &builder.last_build {
+            let mut arena = type_resolver.arena();
+            for ty in network.types() {
+                arena.intern(ty.clone());
+            }
+        }
+        for (i, type_builder) in type_builders.iter().enumerate() {
+            phase.step(observer, i, type_builders.len())?;
             let type_ref: TypeRef = match type_builder {
                 TypeBuilder::Enum(enum_builder) => {
                     let enum_data = enum_builder.0.borrow();
@@ -554,14 +1951,79 @@ impl NetworkBuilder {
                     } else {
                         (max_entry as f64).log2().floor() as u8 + 1
                     };
+                    let entry_info = entries
+                        .iter()
+                        .map(|(entry_name, _)| {
+                            EnumEntryInfo::new(
+                                enum_data
+                                    .entry_descriptions
+                                    .iter()
+                                    .find(|(name, _)| name == entry_name)
+                                    .map(|(_, description)| description.clone()),
+                                enum_data.deprecated_entries.contains(entry_name),
+                            )
+                        })
+                        .collect();
                     make_config_ref(Type::Enum {
                         name: enum_data.name.clone(),
                         size,
                         description: enum_data.description.clone(),
                         entries,
+                        entry_info,
                         visibility: enum_data.visibility.clone(),
                     })
                 }
+                TypeBuilder::Union(union_builder) => {
+                    let union_data = union_builder.0.borrow();
+                    let Some(tag_name) = &union_data.tag else {
+                        return Err(errors::ConfigError::InvalidType(format!(
+                            "union {} has no tag",
+                            union_data.name
+                        )));
+                    };
+                    let tag = type_resolver.resolve(tag_name)?;
+                    let Type::Enum { entries, .. } = &tag as &Type else {
+                        return Err(errors::ConfigError::InvalidType(format!(
+                            "the tag {tag_name} of union {} isn't an enum",
+                            union_data.name
+                        )));
+                    };
+                    fn contains_union(ty: &Type) -> bool {
+                        match ty {
+                            Type::Union { .. } => true,
+                            Type::Struct { attribs, .. } => {
+                                attribs.iter().any(|(_, attrib_ty)| contains_union(attrib_ty))
+                            }
+                            Type::Array { ty, .. } => contains_union(ty),
+                            Type::Primitive(_) | Type::Enum { .. } => false,
+                        }
+                    }
+                    let mut variants = vec![];
+                    for (entry, type_name) in &union_data.variants {
+                        if !entries.iter().any(|(name, _)| name == entry) {
+                            return Err(errors::ConfigError::UndefinedEnumEntry(format!(
+                                "variant {entry} of union {} isn't an entry of the tag {tag_name}",
+                                union_data.name
+                            )));
+                        }
+                        let ty = type_resolver.resolve(type_name)?;
+                        // the variants of nested unions couldn't be told apart by a single tag.
+                        if contains_union(&ty) {
+                            return Err(errors::ConfigError::InvalidType(format!(
+                                "variant {entry} of union {} contains another union",
+                                union_data.name
+                            )));
+                        }
+                        variants.push((entry.clone(), ty));
+                    }
+                    make_config_ref(Type::Union {
+                        name: union_data.name.clone(),
+                        description: union_data.description.clone(),
+                        tag: tag.clone(),
+                        variants,
+                        visibility: union_data.visibility.clone(),
+                    })
+                }
                 TypeBuilder::Struct(struct_builder) => {
                     let struct_data = struct_builder.0.borrow();
                     let mut attribs = vec![];
@@ -571,19 +2033,40 @@ impl NetworkBuilder {
                         // This creates the restiction that the types
                         // are not defined recursivly which is probably
                         // a good restriction
-                        let ty = Self::resolve_type(&types, type_name)?;
+                        let ty = type_resolver.resolve(type_name)?;
                         attribs.push((name.clone(), ty));
                     }
+                    let attrib_info = struct_data
+                        .attributes
+                        .iter()
+                        .map(|(attrib_name, _)| {
+                            StructAttributeInfo::new(
+                                struct_data
+                                    .attribute_descriptions
+                                    .iter()
+                                    .find(|(name, _)| name == attrib_name)
+                                    .map(|(_, description)| description.clone()),
+                                struct_data
+                                    .attribute_padding
+                                    .iter()
+                                    .find(|(name, _)| name == attrib_name)
+                                    .map(|(_, padding)| *padding)
+                                    .unwrap_or_default(),
+                            )
+                        })
+                        .collect();
                     make_config_ref(Type::Struct {
                         name: struct_data.name.clone(),
                         description: struct_data.description.clone(),
                         attribs,
+                        attrib_info,
                         visibility: struct_data.visibility.clone(),
                     })
                 }
             };
-            types.push(type_ref);
+            types.push(type_resolver.define(type_ref));
         }
+        drop(phase);
 
         let tmp_buses = builder.buses.borrow().clone();
         let tmp_messages = builder.messages.borrow().clone();
@@ -591,190 +2074,202 @@ impl NetworkBuilder {
         // function might require a mutable reference to self for assigning ids
         // and buses!
         let nodes = builder.nodes.borrow().clone();
+        let id_allocator = builder.id_allocator.clone();
         drop(builder);
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Resolving message ids and bus assignments");
-        resolve_ids_filters_and_buses(&tmp_buses, &tmp_messages, &nodes, &types)?;
+
+        let phase = PhaseTimer::begin(observer, BuildPhase::IdAssignment)?;
+        // J1939 ids depend on the node id of the transmitter.
+        let span = BuildSpan::step("j1939 ids");
+        for message in &tmp_messages {
+            let mut message_data = message.0.borrow_mut();
+            let Some((pgn, priority)) = message_data.j1939 else {
+                continue;
+            };
+            let source_address = message_data
+                .transmitters
+                .first()
+                .and_then(|tx| nodes.iter().position(|node| Rc::ptr_eq(&node.0, &tx.0)))
+                .map(|i| node_ids[i])
+                .ok_or_else(|| {
+                    errors::ConfigError::InvalidJ1939Id(format!(
+                        "message {} requires a transmitter as source address",
+                        message_data.name
+                    ))
+                })?;
+            let id = J1939Id::new(priority, pgn, source_address).map_err(|err| match err {
+                errors::ConfigError::InvalidJ1939Id(reason) => errors::ConfigError::InvalidJ1939Id(
+                    format!("message {} : {reason}", message_data.name),
+                ),
+                err => err,
+            })?;
+            message_data.id = MessageIdTemplate::ExtId(id.to_ext_id());
+        }
+        drop(span);
+        let span = BuildSpan::step("resolve_ids_filters_and_buses");
+        let filter_banks =
+            resolve_ids_filters_and_buses(
+                &tmp_buses,
+                &tmp_messages,
+                &nodes,
+                &type_resolver,
+                id_allocator.as_ref(),
+                &mut |done, total| phase.step(observer, done, total),
+            )?;
+        drop(span);
+        drop(phase);
         let builder = self.0.borrow();
 
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Building messages");
-        let mut messages = vec![];
-        for message_builder in builder.messages.borrow().iter() {
+        let phase = PhaseTimer::begin(observer, BuildPhase::MessageAssembly)?;
+        // messages are assembled in two phases: the drafts resolve everything,
+        // which references builders, and can't be shared between threads.
+        // Assembling the signals and encodings of the drafts is independent.
+        let mut drafts = vec![];
+        let message_count = builder.messages.borrow().len();
+        for (i, message_builder) in builder.messages.borrow().iter().enumerate() {
+            phase.step(observer, i, message_count)?;
             let message_data = message_builder.0.borrow();
             let id = match message_data.id {
                 MessageIdTemplate::StdId(id) => MessageId::StandardId(id),
                 MessageIdTemplate::ExtId(id) => MessageId::ExtendedId(id),
-                MessageIdTemplate::AnyStd(_) => panic!("unresolved id"),
-                MessageIdTemplate::AnyExt(_) => panic!("unresolved id"),
-                MessageIdTemplate::AnyAny(_) => panic!("unresolved id"),
+                MessageIdTemplate::AnyStd(_)
+                | MessageIdTemplate::AnyExt(_)
+                | MessageIdTemplate::AnyAny(_) => {
+                    return Err(errors::ConfigError::UnresolvedId(format!(
+                        "message {} has no id after resolving all ids",
+                        message_data.name
+                    )))
+                }
             };
-            let (signals, encoding) = match &message_data.format {
+            let format = match &message_data.format {
                 MessageFormat::Signals(signal_format_builder) => {
-                    let mut offset: usize = 0;
                     let signal_format_data = signal_format_builder.0.borrow();
-                    let mut signals = vec![];
-                    for signal_data in signal_format_data.0.iter() {
-                        signals.push(make_config_ref(Signal {
-                            name: format!("{}_{}", message_data.name, signal_data.name),
-                            offset,
-                            ..signal_data.clone()
-                        }));
-                        offset += signal_data.size() as usize;
-                    }
-                    (signals, None)
+                    DraftFormat::Signals(
+                        signal_format_data
+                            .signals
+                            .iter()
+                            .map(|signal| {
+                                let pinned = signal_format_data.pinned
+                                    || signal_format_data.pinned_signals.iter().any(|name| name == signal.name());
+                                (signal.clone(), pinned)
+                            })
+                            .collect(),
+                    )
                 }
                 MessageFormat::Types(type_format_builder) => {
                     let type_format_data = type_format_builder.0.borrow();
-                    let mut attributes: Vec<TypeSignalEncoding> = vec![];
-                    let mut signals = vec![];
-                    let mut offset: usize = 0;
-
-                    pub fn build_attribute(
-                        ty: &TypeRef,
-                        name: &str,
-                        offset: &mut usize,
-                        prefix: &str,
-                        signals: &mut Vec<SignalRef>,
-                    ) -> TypeSignalEncoding {
-                        match ty as &Type {
-                            Type::Primitive(signal_type) => {
-                                let signal = make_config_ref(Signal::new(
-                                    &format!("{prefix}_{name}"),
-                                    None,
-                                    signal_type.clone(),
-                                    *offset,
-                                ));
-                                signals.push(signal.clone());
-                                *offset += signal.size() as usize;
-                                TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
-                                    name.to_owned(),
-                                    ty.clone(),
-                                    signal,
-                                ))
-                            }
-                            Type::Struct {
-                                name: struct_name,
-                                description: _,
-                                attribs,
-                                visibility: _,
-                            } => {
-                                let mut attributes = vec![];
-                                for (attrib_name, attrib_type) in attribs {
-                                    attributes.push(build_attribute(
-                                        attrib_type,
-                                        attrib_name,
-                                        offset,
-                                        &format!("{prefix}_{struct_name}"),
-                                        signals,
-                                    ));
-                                }
-                                TypeSignalEncoding::Composite(CompositeSignalEncoding::new(
-                                    name.to_owned(),
-                                    attributes,
-                                    ty.clone(),
-                                ))
-                            }
-                            Type::Enum {
-                                name: enum_name,
-                                description: _,
-                                size,
-                                entries: _,
-                                visibility: _,
-                            } => {
-                                let size = *size;
-                                let signal = make_config_ref(Signal::new(
-                                    &format!("{prefix}_{enum_name}"),
-                                    None,
-                                    SignalType::UnsignedInt { size },
-                                    *offset,
-                                ));
-                                signals.push(signal.clone());
-                                *offset += signal.size() as usize;
-                                TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
-                                    name.to_owned(),
-                                    ty.clone(),
-                                    signal,
-                                ))
-                            }
-                            Type::Array { len: _, ty: _ } => todo!(),
-                        }
-                    }
-
+                    let mut values = vec![];
                     for (type_name, var_name) in &type_format_data.0 {
-                        let type_ref = Self::resolve_type(&types, type_name)?;
-                        attributes.push(build_attribute(
-                            &type_ref,
-                            var_name,
-                            &mut offset,
-                            &format!("value_name"),
-                            &mut signals,
-                        ));
+                        let type_ref = type_resolver.resolve(type_name)?;
+                        // streams encode object entries, which define the overflow policy.
+                        let overflow = match &message_data.usage {
+                            MessageBuilderUsage::Stream(stream_builder) => stream_builder
+                                .0
+                                .borrow()
+                                .object_entries
+                                .iter()
+                                .flatten()
+                                .find(|oe| &oe.0.borrow().name == var_name)
+                                .map(|oe| oe.0.borrow().overflow)
+                                .unwrap_or_default(),
+                            _ => OverflowPolicy::default(),
+                        };
+                        values.push((type_ref, var_name.clone(), overflow));
                     }
-                    let encoding = MessageEncoding::new(attributes);
-
-                    (signals, Some(encoding))
+                    DraftFormat::Types(values)
+                }
+                MessageFormat::Empty => DraftFormat::Empty,
+            };
+            let bus = match &message_data.bus {
+                Some(bus_builder) => buses
+                    .iter()
+                    .find(|bus| bus.id() == bus_builder.0.borrow().id)
+                    .ok_or_else(|| {
+                        errors::ConfigError::BusNotFound(format!(
+                            "bus {} of message {} was not added to the network",
+                            bus_builder.0.borrow().name,
+                            message_data.name
+                        ))
+                    })?
+                    .clone(),
+                None => {
+                    return Err(errors::ConfigError::BusNotFound(format!(
+                        "message {} wasn't assigned to a bus",
+                        message_data.name
+                    )))
                 }
-                MessageFormat::Empty => (vec![], None),
             };
 
-            let mut max_bit = 0;
-            for signal in &signals {
-                let signal_max_bit = signal.byte_offset() + signal.size() as usize;
-                max_bit = max_bit.max(signal_max_bit);
-            }
-            let dlc = ((max_bit + 8 - 1) / 8) as u8;
-
-            let bus = buses
-                .iter()
-                .find(|bus| bus.id() == message_data.bus.clone().unwrap().0.borrow().id)
-                .unwrap()
-                .clone();
-
-            messages.push(make_config_ref(Message::new(
-                message_data.name.clone(),
-                message_data.description.clone(),
+            // usages referencing streams, commands or transport channels
+            // are set while assembling the nodes.
+            let is_message = |cell: &OnceCell<MessageBuilder>| {
+                Rc::ptr_eq(&cell.get().unwrap().0, &message_builder.0)
+            };
+            let usage = match &message_data.usage {
+                MessageBuilderUsage::Configuration if is_message(&builder.get_resp_message) => {
+                    Some(MessageUsage::GetResp)
+                }
+                MessageBuilderUsage::Configuration if is_message(&builder.get_req_message) => {
+                    Some(MessageUsage::GetReq)
+                }
+                MessageBuilderUsage::Configuration if is_message(&builder.set_resp_message) => {
+                    Some(MessageUsage::SetResp)
+                }
+                MessageBuilderUsage::Configuration if is_message(&builder.set_req_message) => {
+                    Some(MessageUsage::SetReq)
+                }
+                MessageBuilderUsage::Heartbeat => Some(MessageUsage::Heartbeat),
+                MessageBuilderUsage::Emergency => Some(MessageUsage::Emergency),
+                MessageBuilderUsage::Debug { interval } => Some(MessageUsage::Debug { interval: *interval }),
+                MessageBuilderUsage::Bootloader => Some(MessageUsage::Bootloader),
+                MessageBuilderUsage::External { interval } => Some(MessageUsage::External {
+                    interval: interval.unwrap_or(Duration::from_secs(60)),
+                }),
+                _ => None,
+            };
+            drafts.push(MessageDraft {
+                name: message_data.name.clone(),
+                description: message_data.description.clone(),
                 id,
-                encoding,
-                signals,
-                message_data.visibility.clone(),
-                dlc,
+                format,
+                visibility: message_data.visibility.clone(),
                 bus,
-            )));
+                e2e: message_data.e2e,
+                signal_groups: message_data.signal_groups.clone(),
+                lifecycle: Self::lifecycle(&message_data.introduced_in, &message_data.deprecated_in)
+                    .expect("versions are checked by check_versions"),
+                usage,
+                dlc: message_data.dlc,
+                fill: message_data.fill,
+                sender_id_size: message_data.shared.then_some(builder.node_id_width),
+                keyframe: matches!(
+                    &message_data.usage,
+                    MessageBuilderUsage::Stream(stream_builder)
+                        if matches!(stream_builder.0.borrow().encoding, StreamEncoding::Delta { .. })
+                ),
+            });
         }
-        let get_resp_message = messages
-            .iter()
-            .find(|m| m.name() == builder.get_resp_message.get().unwrap().0.borrow().name)
-            .unwrap()
-            .clone();
-        get_resp_message.__set_usage(MessageUsage::GetResp);
-        let get_req_message = messages
-            .iter()
-            .find(|m| m.name() == builder.get_req_message.get().unwrap().0.borrow().name)
-            .unwrap()
-            .clone();
-        get_req_message.__set_usage(MessageUsage::GetReq);
-        let set_resp_message = messages
-            .iter()
-            .find(|m| m.name() == builder.set_resp_message.get().unwrap().0.borrow().name)
-            .unwrap()
-            .clone();
-        set_resp_message.__set_usage(MessageUsage::SetResp);
-        let set_req_message = messages
-            .iter()
-            .find(|m| m.name() == builder.set_req_message.get().unwrap().0.borrow().name)
-            .unwrap()
-            .clone();
-        set_req_message.__set_usage(MessageUsage::SetReq);
-
-        pub fn rec_type_acc(node_types: &mut Vec<TypeRef>, encoding: &TypeSignalEncoding) {
+        let span = BuildSpan::step("assemble_messages");
+        let messages = assemble_messages(drafts)?;
+        drop(span);
+        drop(phase);
+
+        pub fn rec_type_acc(
+            node_types: &mut TypeSet,
+            arena: &mut TypeArena,
+            encoding: &TypeSignalEncoding,
+        ) {
             match encoding {
                 TypeSignalEncoding::Composite(composite) => {
-                    if !node_types.contains(composite.ty()) {
-                        node_types.push(composite.ty().clone());
-                    }
+                    node_types.insert(arena, composite.ty());
                     for attribute in composite.attributes() {
-                        rec_type_acc(node_types, attribute);
+                        rec_type_acc(node_types, arena, attribute);
+                    }
+                }
+                TypeSignalEncoding::Multiplexed(mux) => {
+                    node_types.insert(arena, mux.ty());
+                    node_types.insert(arena, mux.tag().ty());
+                    for (_, _, variant) in mux.variants() {
+                        rec_type_acc(node_types, arena, variant);
                     }
                 }
                 TypeSignalEncoding::Primitive(primitive) => match primitive.ty() as &Type {
@@ -783,6 +2278,7 @@ impl NetworkBuilder {
                         name: _,
                         description: _,
                         attribs: _,
+                        attrib_info: _,
                         visibility: _,
                     } => panic!("not a primitive"),
                     Type::Enum {
@@ -790,127 +2286,106 @@ impl NetworkBuilder {
                         description: _,
                         size: _,
                         entries: _,
+                        entry_info: _,
                         visibility: _,
                     } => {
-                        if !node_types.contains(primitive.ty()) {
-                            node_types.push(primitive.ty().clone());
-                        }
+                        node_types.insert(arena, primitive.ty());
                     }
-                    Type::Array { len: _, ty: _ } => todo!(),
+                    Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
+                    Type::Union { .. } => panic!("not a primitive"),
                 },
             }
         }
 
-        // add get and set req,resp to all nodes
-        let n_nodes = builder.nodes.borrow().len();
-
-        let mut nodes = vec![];
-        // first create messages with tx and rx messages.
+        let mut channel_builders: Vec<TransportChannelBuilder> = vec![];
         for node_builder in builder.nodes.borrow().iter() {
-            let node_data = node_builder.0.borrow();
-
-            let mut node_types = vec![];
-
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Collecting all messages received by node {}",
-                &node_data.name
-            );
-            let mut rx_messages = vec![];
-            for rx_message_builder in &node_data.rx_messages {
-                let message_ref = messages
+            for channel_builder in &node_builder.0.borrow().transport_channels {
+                if channel_builders
                     .iter()
-                    .find(|m| m.name() == rx_message_builder.0.borrow().name)
-                    .expect("invalid message_builder was probably not added to the network");
-
-                match &message_ref.encoding() {
-                    Some(encoding) => {
-                        for attribute in encoding.attributes() {
-                            rec_type_acc(&mut node_types, attribute);
-                        }
-                    }
-                    None => (),
+                    .any(|b| Rc::ptr_eq(&b.0, &channel_builder.0))
+                {
+                    continue;
                 }
-                rx_messages.push(message_ref.clone());
-            }
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Collecting all messages transmitted by node {}",
-                &node_data.name
-            );
-            let mut tx_messages = vec![];
-            for tx_message_builder in &node_data.tx_messages {
-                let message_ref = messages
-                    .iter()
-                    .find(|m| m.name() == tx_message_builder.0.borrow().name)
-                    .expect("invalid message_builder was probably not added to the network");
-                match &message_ref.encoding() {
-                    Some(encoding) => {
-                        for attribute in encoding.attributes() {
-                            rec_type_acc(&mut node_types, attribute);
-                        }
-                    }
-                    None => (),
+                let channel_data = channel_builder.0.borrow();
+                // below 1ms STmin is encoded in steps of 100us, above in whole milliseconds.
+                let step = if channel_data.st_min < Duration::from_millis(1) {
+                    Duration::from_micros(100)
+                } else {
+                    Duration::from_millis(1)
+                };
+                if channel_data.st_min > Duration::from_millis(127)
+                    || channel_data.st_min.as_nanos() % step.as_nanos() != 0
+                {
+                    return Err(errors::ConfigError::InvalidRange(format!(
+                        "STmin {:?} of transport channel {} can't be encoded",
+                        channel_data.st_min, channel_data.name
+                    )));
                 }
-                tx_messages.push(message_ref.clone());
+                channel_builders.push(channel_builder.clone());
             }
+        }
+
+        // everything that may fail is done before the nodes are assembled,
+        // because the cyclic references between nodes, messages and their
+        // owners have to be created at once.
+        self.check_references(&messages, &buses)?;
+        let node_count = builder.nodes.borrow().len();
+        let find_message = |messages: &[Message], message_builder: &MessageBuilder| {
+            messages
+                .iter()
+                .position(|m| m.name() == message_builder.0.borrow().name)
+                .expect("messages of nodes are checked by check_references")
+        };
+        let phase = PhaseTimer::begin(observer, BuildPhase::NodeAssembly)?;
+        let mut node_parts = vec![];
+        for (i, node_builder) in builder.nodes.borrow().iter().enumerate() {
+            phase.step(observer, i, node_count)?;
+            let node_data = node_builder.0.borrow();
 
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Building all commands transmitted by node {}",
+            let mut node_types = TypeSet::default();
+
+            log::debug!(
+                "Collecting all types of messages used by node {}",
                 &node_data.name
             );
-            let mut commands: Vec<ConfigRef<Command>> = vec![];
-            for tx_command_builder in &node_builder.0.borrow().commands {
-                let command_data = tx_command_builder.0.borrow();
-                let tx_message = messages
-                    .iter()
-                    .find(|m| m.name() == command_data.call_message.0.borrow().name)
-                    .expect("invalid command builder tx_message wasn't added to the network")
-                    .clone();
-                let rx_message = messages
-                    .iter()
-                    .find(|m| m.name() == command_data.resp_message.0.borrow().name)
-                    .expect("invalid command builder rx_message wasn't added to the network")
-                    .clone();
-                let command_ref = make_config_ref(Command::new(
-                    command_data.name.clone(),
-                    command_data.description.clone(),
-                    tx_message.clone(),
-                    rx_message.clone(),
-                    command_data.visibility.clone(),
-                    command_data.expected_interval.clone(),
-                ));
-                rx_message.__set_usage(MessageUsage::CommandResp(command_ref.clone()));
-                tx_message.__set_usage(MessageUsage::CommandReq(command_ref.clone()));
-
-                commands.push(command_ref);
+            for message_builder in node_data.rx_messages.iter().chain(&node_data.tx_messages) {
+                let message = &messages[find_message(&messages, message_builder)];
+                if let Some(encoding) = message.encoding() {
+                    for attribute in encoding.attributes() {
+                        rec_type_acc(&mut node_types, &mut type_resolver.arena(), attribute);
+                    }
+                }
             }
 
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Building Object Entries of node {}",
+            log::debug!(
+                "Building Object Entries of node {}",
                 &node_data.name
             );
             let mut object_entries = vec![];
-            let mut id_acc = 0;
-            for object_entry_builder in &node_builder.0.borrow().object_entries {
+            let object_entry_indices = Self::assign_object_entry_indices(
+                &node_data.name,
+                &node_data.object_entries,
+                builder.object_entry_index_assignment,
+                builder.od_index_width,
+            )?;
+            for (object_entry_builder, id) in
+                std::iter::zip(&node_data.object_entries, object_entry_indices)
+            {
                 let object_entry_data = object_entry_builder.0.borrow();
-                let ty = Self::resolve_type(&mut types, &object_entry_data.ty)?;
-                fn rec_add_type(node_types: &mut Vec<TypeRef>, ty: &TypeRef) {
+                let ty = type_resolver.resolve(&object_entry_data.ty)?;
+                fn rec_add_type(node_types: &mut TypeSet, arena: &mut TypeArena, ty: &TypeRef) {
                     match ty as &Type {
                         Type::Primitive(_) => (),
                         Type::Struct {
                             name: _,
                             description: _,
                             attribs,
+                            attrib_info: _,
                             visibility: _,
                         } => {
-                            if !node_types.contains(ty) {
-                                node_types.push(ty.clone());
-                            }
+                            node_types.insert(arena, ty);
                             for (_, attrib_ty) in attribs {
-                                rec_add_type(node_types, attrib_ty);
+                                rec_add_type(node_types, arena, attrib_ty);
                             }
                         }
                         Type::Enum {
@@ -918,19 +2393,61 @@ impl NetworkBuilder {
                             description: _,
                             size: _,
                             entries: _,
+                            entry_info: _,
                             visibility: _,
                         } => {
-                            if !node_types.contains(ty) {
-                                node_types.push(ty.clone());
+                            node_types.insert(arena, ty);
+                        }
+                        Type::Array { len: _, ty: _ } => unreachable!("arrays are rejected by check_array_types"),
+                        Type::Union { tag, variants, .. } => {
+                            node_types.insert(arena, ty);
+                            rec_add_type(node_types, arena, tag);
+                            for (_, variant_ty) in variants {
+                                rec_add_type(node_types, arena, variant_ty);
                             }
                         }
-                        Type::Array { len: _, ty: _ } => todo!(),
                     };
                 }
-                rec_add_type(&mut node_types, &ty);
-                let id = id_acc;
-                id_acc += 1;
-                object_entries.push(make_config_ref(ObjectEntry::new(
+                rec_add_type(&mut node_types, &mut type_resolver.arena(), &ty);
+                if let ObjectEntryAccess::Protected { level } = &object_entry_data.access {
+                    if !access_levels.iter().any(|l| l.name() == level) {
+                        return Err(errors::ConfigError::UndefinedAccessLevel(format!(
+                            "object entry {}::{} is protected by the undefined access level {level}",
+                            node_data.name, object_entry_data.name
+                        )));
+                    }
+                }
+                if let Some(default) = &object_entry_data.default {
+                    Self::check_default_value(&ty, default).map_err(|reason| {
+                        errors::ConfigError::InvalidDefaultValue(format!(
+                            "default value {default:?} of object entry {}::{} is invalid : {reason}",
+                            node_data.name, object_entry_data.name
+                        ))
+                    })?;
+                }
+                if object_entry_data.broadcast
+                    && matches!(
+                        object_entry_data.access,
+                        ObjectEntryAccess::Const | ObjectEntryAccess::Local
+                    )
+                {
+                    return Err(errors::ConfigError::InvalidBroadcast(format!(
+                        "object entry {}::{} accepts broadcast writes, but isn't writable by other nodes",
+                        node_data.name, object_entry_data.name
+                    )));
+                }
+                if let (Some((min, max)), Type::Primitive(signal_type)) =
+                    (object_entry_data.range, &ty as &Type)
+                {
+                    let (type_min, type_max) = signal_type.physical_range();
+                    if min < type_min || max > type_max {
+                        log::warn!(
+                            "declared range [{min}, {max}] of object entry {}::{} can't be represented by {signal_type:?} (range [{type_min}, {type_max}])",
+                            node_data.name, object_entry_data.name
+                        );
+                    }
+                }
+                object_entries.push(ObjectEntry::new(
                     object_entry_data.name.clone(),
                     object_entry_data.description.clone(),
                     object_entry_data.unit.clone(),
@@ -938,259 +2455,556 @@ impl NetworkBuilder {
                     ty,
                     object_entry_data.access.clone(),
                     object_entry_data.visibility.clone(),
-                )));
+                    object_entry_data.default.clone(),
+                    object_entry_data.persistent,
+                    object_entry_data.overflow,
+                    object_entry_data.range,
+                    object_entry_data.broadcast,
+                ).with_lifecycle(
+                    Self::lifecycle(&object_entry_data.introduced_in, &object_entry_data.deprecated_in)
+                        .expect("versions are checked by check_versions"),
+                ));
             }
 
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Building streams transmitted by node {}",
-                node_data.name
-            );
-            let mut tx_streams = vec![];
-            for tx_stream in &node_builder.0.borrow().tx_streams {
-                let stream_data = tx_stream.0.borrow();
-
-                //resolve message
-                let message = messages
-                    .iter()
-                    .find(|m| m.name() == stream_data.message.0.borrow().name)
-                    .expect("stream message was not added to the network")
-                    .clone();
-                let mut mappings = vec![];
-                for oe_builder in &stream_data.object_entries {
-                    let oe_data = oe_builder.0.borrow();
-                    let oe = object_entries
-                        .iter()
-                        .find(|oe| oe.name() == oe_data.name)
-                        .expect("stream object entry wasn't added to the node")
-                        .clone();
-                    mappings.push(Some(oe));
+            let mut grouped: Vec<(String, String)> = vec![];
+            for group in &node_data.object_entry_groups {
+                let group_data = group.0.borrow();
+                for oe in &group_data.object_entries {
+                    let oe_name = oe.0.borrow().name.clone();
+                    if let Some((other, _)) = grouped.iter().find(|(_, name)| name == &oe_name) {
+                        return Err(errors::ConfigError::DuplicatedObjectEntry(format!(
+                            "object entry {}::{oe_name} is part of the groups {} and {}",
+                            node_data.name, other, group_data.name
+                        )));
+                    }
+                    grouped.push((group_data.name.clone(), oe_name));
                 }
-
-                let stream_ref = make_config_ref(Stream::new(
-                    stream_data.name.clone(),
-                    stream_data.description.clone(),
-                    mappings,
-                    message.clone(),
-                    stream_data.visbility.clone(),
-                    stream_data.interval,
-                ));
-                message.__set_usage(MessageUsage::Stream(stream_ref.clone()));
-                tx_streams.push(stream_ref);
             }
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Collected all types used by node {}",
-                &node_data.name
-            );
 
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Sorting all types of {} in topological order",
-                &node_data.name
-            );
-            let node_types = Self::topo_sort_types(&node_types);
+            node_parts.push((object_entries, node_types.into_vec()));
+        }
 
-            let buses = node_data
-                .buses
+        // the types of the nodes are sorted independently of each other
+        // (and in parallel with the parallel feature).
+        log::debug!("Sorting the types of all nodes in topological order");
+        let (object_entries, node_types): (Vec<_>, Vec<_>) = node_parts.into_iter().unzip();
+        let mut node_parts: Vec<_> = std::iter::zip(
+            object_entries,
+            Self::topo_sort_node_types(node_types),
+        )
+        .collect();
+
+        log::debug!("Assembling nodes");
+        let n_messages = messages.len();
+        let mut pending: Vec<Option<Message>> = messages.into_iter().map(Some).collect();
+        let mut shared: Vec<Option<MessageRef>> = vec![None; n_messages];
+        fn share_message(
+            pending: &mut [Option<Message>],
+            shared: &mut [Option<MessageRef>],
+            message_builder: &MessageBuilder,
+            usage: MessageUsage,
+        ) -> MessageRef {
+            let index = pending
                 .iter()
-                .map(|bus_builder| {
-                    buses
-                        .iter()
-                        .find(|bus| bus.id() == bus_builder.0.borrow().id)
-                        .unwrap()
-                        .clone()
+                .position(|m| {
+                    m.as_ref()
+                        .is_some_and(|m| m.name() == message_builder.0.borrow().name)
+                })
+                .expect("message is owned by more than one stream, command or transport channel");
+            let message_ref = make_config_ref(pending[index].take().unwrap().with_usage(usage));
+            shared[index] = Some(message_ref.clone());
+            message_ref
+        }
+
+        log::debug!("Building transport channels");
+        let transport_channels: Vec<(TransportChannelBuilder, TransportChannelRef)> =
+            channel_builders
+                .into_iter()
+                .map(|channel_builder| {
+                    let channel_data = channel_builder.0.borrow();
+                    let channel_ref = ConfigRef::new_cyclic(|channel| {
+                        TransportChannel::new(
+                            channel_data.name.clone(),
+                            channel_data.description.clone(),
+                            channel_data.tx_node.0.borrow().name.clone(),
+                            channel_data.rx_node.0.borrow().name.clone(),
+                            share_message(
+                                &mut pending,
+                                &mut shared,
+                                &channel_data.data_message,
+                                MessageUsage::TransportData(channel.clone()),
+                            ),
+                            share_message(
+                                &mut pending,
+                                &mut shared,
+                                &channel_data.flow_control_message,
+                                MessageUsage::TransportFlowControl(channel.clone()),
+                            ),
+                            channel_data.block_size,
+                            channel_data.st_min,
+                            channel_data.visibility.clone(),
+                        )
+                    });
+                    drop(channel_data);
+                    (channel_builder, channel_ref)
                 })
                 .collect();
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Collecting all buses the node {} is connected to",
-                node_data.name
-            );
 
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Successfully build transmitting part of node {}",
-                node_data.name
-            );
-            nodes.push(RefCell::new(Node::new(
-                node_data.name.clone(),
-                node_data.description.clone(),
-                nodes.len() as u8,
-                node_types,
-                commands,
-                vec![],
-                tx_streams,
-                vec![],
-                rx_messages,
-                tx_messages,
-                object_entries,
-                buses,
-            )));
-        }
+        let node_builders = builder.nodes.borrow();
+        let mut messages = vec![];
+        let nodes = make_cyclic_config_refs(node_builders.len(), |weak_nodes| {
+            // commands, object entries and tx streams of every node.
+            let mut tx_parts = vec![];
+            for ((node_builder, (object_entries, _)), weak_node) in
+                node_builders.iter().zip(&mut node_parts).zip(weak_nodes)
+            {
+                let node_data = node_builder.0.borrow();
+
+                log::debug!(
+                    "Building all commands transmitted by node {}",
+                    &node_data.name
+                );
+                let commands: Vec<CommandRef> = node_data
+                    .commands
+                    .iter()
+                    .map(|tx_command_builder| {
+                        let command_data = tx_command_builder.0.borrow();
+                        ConfigRef::new_cyclic(|command| {
+                            Command::new(
+                                command_data.name.clone(),
+                                command_data.description.clone(),
+                                share_message(
+                                    &mut pending,
+                                    &mut shared,
+                                    &command_data.call_message,
+                                    MessageUsage::CommandReq(command.clone()),
+                                ),
+                                share_message(
+                                    &mut pending,
+                                    &mut shared,
+                                    &command_data.resp_message,
+                                    MessageUsage::CommandResp(command.clone()),
+                                ),
+                                command_data.visibility.clone(),
+                                command_data.expected_interval,
+                            )
+                            .with_responders(
+                                std::iter::once(&node_data.name)
+                                    .cloned()
+                                    .chain(
+                                        command_data
+                                            .responders
+                                            .iter()
+                                            .map(|responder| responder.0.borrow().name.clone()),
+                                    )
+                                    .collect(),
+                            )
+                        })
+                    })
+                    .collect();
 
-        // add extern commands to nodes
-        // requires all nodes to be constructed beforehand.
-        for i in 0..n_nodes {
-            let node_builder = &builder.nodes.borrow()[i];
-            let node_data = node_builder.0.borrow();
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Linking Received command of node {}",
-                node_data.name
-            );
-            for rx_command in &node_data.extern_commands {
-                let rx_command_data = rx_command.0.borrow();
-                'outer: for j in 0..n_nodes {
-                    if i == j {
-                        continue;
-                    }
-                    let other_node = nodes[j].borrow();
-                    for tx_command in other_node.commands() {
-                        if tx_command.tx_message().name()
-                            == rx_command_data.call_message.0.borrow().name
-                        {
-                            nodes[i]
-                                .borrow_mut()
-                                .extern_commands_mut()
-                                .push((other_node.name().to_owned(), tx_command.clone()));
-                            break 'outer;
-                        }
-                    }
+                let object_entries: Vec<ObjectEntryRef> = std::mem::take(object_entries)
+                    .into_iter()
+                    .map(|oe| make_config_ref(oe.with_node(weak_node.clone())))
+                    .collect();
+
+                let object_entry_groups: Vec<ObjectEntryGroupRef> = node_data
+                    .object_entry_groups
+                    .iter()
+                    .map(|group| {
+                        let group_data = group.0.borrow();
+                        let group_entries = group_data
+                            .object_entries
+                            .iter()
+                            .map(|oe| {
+                                object_entries
+                                    .iter()
+                                    .find(|e| e.name() == oe.0.borrow().name)
+                                    .expect("grouped object entries are always part of the node")
+                                    .clone()
+                            })
+                            .collect();
+                        make_config_ref(ObjectEntryGroup::new(
+                            group_data.name.clone(),
+                            group_data.description.clone(),
+                            group_entries,
+                        ))
+                    })
+                    .collect();
+
+                log::debug!(
+                    "Building streams transmitted by node {}",
+                    node_data.name
+                );
+                let tx_streams: Vec<StreamRef> = node_data
+                    .tx_streams
+                    .iter()
+                    .map(|tx_stream| {
+                        let conversions = tx_stream
+                            .conversions()
+                            .expect("stream conversions are checked by check_references");
+                        let stream_data = tx_stream.0.borrow();
+                        let mappings = stream_data
+                            .object_entries
+                            .iter()
+                            .zip(conversions)
+                            .map(|(oe_builder, conversion)| {
+                                // skipped slots aren't mapped.
+                                let oe_builder = oe_builder.as_ref()?;
+                                let oe = object_entries
+                                    .iter()
+                                    .find(|oe| oe.name() == oe_builder.0.borrow().name)
+                                    .expect("stream entries are checked by check_references");
+                                Some(StreamMapping::new(oe.clone(), conversion))
+                            })
+                            .collect();
+                        ConfigRef::new_cyclic(|stream| {
+                            Stream::new(
+                                stream_data.name.clone(),
+                                stream_data.description.clone(),
+                                mappings,
+                                share_message(
+                                    &mut pending,
+                                    &mut shared,
+                                    &stream_data.message,
+                                    MessageUsage::Stream(stream.clone()),
+                                ),
+                                stream_data.visbility.clone(),
+                                stream_data.interval,
+                            )
+                            .with_encoding(stream_data.encoding)
+                        })
+                    })
+                    .collect();
+                tx_parts.push((commands, object_entries, object_entry_groups, tx_streams));
+            }
+
+            // all remaining messages don't have an owner.
+            for (index, message) in pending.iter_mut().enumerate() {
+                if let Some(message) = message.take() {
+                    shared[index] = Some(make_config_ref(message));
                 }
             }
-            #[cfg(feature = "logging_info")]
-            println!(
-                "[CANZERO-CONFIG::build] Linking Received streams to node {}",
-                node_data.name
-            );
-            for rx_stream in &node_data.rx_streams {
-                let rx_stream_data = rx_stream.0.borrow();
-                let tx_stream_builder = rx_stream_data.stream_builder.clone();
-                let tx_stream_data = tx_stream_builder.0.borrow();
-                let tx_node_builder = tx_stream_data.tx_node.clone();
-                let tx_node_data = tx_node_builder.0.borrow();
-                // resolve node.
-                let tx_node = nodes
+            messages = shared
+                .iter()
+                .map(|m| m.clone().expect("message usage was not resolved"))
+                .collect();
+            let find_message = |message_builder: &MessageBuilder| {
+                messages
                     .iter()
-                    .find(|n| n.borrow().name() == tx_node_data.name)
-                    .unwrap()
-                    .borrow();
-                let tx_stream = tx_node
-                    .tx_streams()
+                    .find(|m| m.name() == message_builder.0.borrow().name)
+                    .expect("messages of nodes are checked by check_references")
+                    .clone()
+            };
+            let find_channel = |channel_builder: &TransportChannelBuilder| {
+                transport_channels
                     .iter()
-                    .find(|s| s.name() == tx_stream_data.name)
+                    .find(|(b, _)| Rc::ptr_eq(&b.0, &channel_builder.0))
                     .unwrap()
-                    .clone();
+                    .1
+                    .clone()
+            };
 
-                let mut builder_mapping = rx_stream_data.object_entries.clone();
-                builder_mapping.sort_by(|(i1, _), (i2, _)| {
-                    if i1 < i2 {
-                        Ordering::Less
-                    } else if i1 == i2 {
-                        Ordering::Equal
-                    } else {
-                        Ordering::Greater
-                    }
-                });
-                let oe_count = builder_mapping.len();
-                let mut mappings = vec![];
-                let mut j = 0;
-                let rx_node_data = rx_stream_data.rx_node.0.borrow();
-                let rx_node = nodes
-                    .iter()
-                    .find(|n| n.borrow().name() == rx_node_data.name)
-                    .unwrap()
-                    .borrow();
-                for i in 0..oe_count {
-                    if builder_mapping[j].0 == i {
-                        // search for object entry in rx_node
-                        let oe = rx_node
-                            .object_entries()
-                            .iter()
-                            .find(|oe| oe.name() == builder_mapping[j].1 .0.borrow().name)
-                            .unwrap();
-                        mappings.push(Some(oe.clone()));
-                        j += 1;
-                    } else {
-                        // insert null mapping
-                        mappings.push(None);
+            let mut nodes = vec![];
+            for (i, node_builder) in node_builders.iter().enumerate() {
+                let node_data = node_builder.0.borrow();
+                let (commands, object_entries, object_entry_groups, tx_streams) = &tx_parts[i];
+
+                log::debug!(
+                    "Linking Received command of node {}",
+                    node_data.name
+                );
+                let mut extern_commands = vec![];
+                for rx_command in &node_data.extern_commands {
+                    let rx_command_data = rx_command.0.borrow();
+                    let tx_command = node_builders.iter().zip(&tx_parts).enumerate().find_map(
+                        |(j, (other_builder, (other_commands, _, _, _)))| {
+                            if i == j {
+                                return None;
+                            }
+                            other_commands
+                                .iter()
+                                .find(|tx_command| {
+                                    tx_command.tx_message().name()
+                                        == rx_command_data.call_message.0.borrow().name
+                                })
+                                .map(|tx_command| {
+                                    (other_builder.0.borrow().name.clone(), tx_command.clone())
+                                })
+                        },
+                    );
+                    if let Some(tx_command) = tx_command {
+                        extern_commands.push(tx_command);
                     }
                 }
 
-                drop(tx_node);
-                drop(rx_node);
-                nodes[i]
-                    .borrow_mut()
-                    .rx_streams_mut()
-                    .push(make_config_ref(Stream::new(
+                log::debug!(
+                    "Linking Received streams to node {}",
+                    node_data.name
+                );
+                let mut rx_streams = vec![];
+                for rx_stream in &node_data.rx_streams {
+                    let rx_stream_data = rx_stream.0.borrow();
+                    let tx_stream_data = rx_stream_data.stream_builder.0.borrow();
+                    let tx_node_name = tx_stream_data.tx_node.0.borrow().name.clone();
+                    // resolve node.
+                    let tx_node_index = node_builders
+                        .iter()
+                        .position(|n| n.0.borrow().name == tx_node_name)
+                        .expect("received streams are checked by check_references");
+                    let tx_stream = tx_parts[tx_node_index]
+                        .3
+                        .iter()
+                        .find(|s| s.name() == tx_stream_data.name)
+                        .expect("received streams are checked by check_references")
+                        .clone();
+
+                    let mut builder_mapping = rx_stream_data.object_entries.clone();
+                    builder_mapping.sort_by(|(i1, _), (i2, _)| {
+                        if i1 < i2 {
+                            Ordering::Less
+                        } else if i1 == i2 {
+                            Ordering::Equal
+                        } else {
+                            Ordering::Greater
+                        }
+                    });
+                    // every slot of the tx stream, which isn't received, is a null mapping.
+                    let slot_count = tx_stream.mapping().len();
+                    let mut mappings = vec![];
+                    let mut j = 0;
+                    for i in 0..slot_count {
+                        if j < builder_mapping.len() && builder_mapping[j].0 == i {
+                            // search for object entry in rx_node
+                            let oe = object_entries
+                                .iter()
+                                .find(|oe| oe.name() == builder_mapping[j].1 .0.borrow().name)
+                                .expect("stream entries are checked by check_references");
+                            // the signal is converted like on the transmitting side.
+                            let conversion = tx_stream.mapping()[i]
+                                .as_ref()
+                                .and_then(|mapping| mapping.conversion().cloned());
+                            mappings.push(Some(StreamMapping::new(oe.clone(), conversion)));
+                            j += 1;
+                        } else {
+                            // insert null mapping
+                            mappings.push(None);
+                        }
+                    }
+
+                    rx_streams.push(make_config_ref(Stream::new(
                         tx_stream.name().to_owned(),
                         tx_stream.description().map(|d| d.to_owned()),
                         mappings,
                         tx_stream.message().clone(),
                         rx_stream_data.visibility.clone(),
                         *tx_stream.interval(),
-                    )));
-            }
-        }
-
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Successfully build all nodes");
-        let nodes: Vec<ConfigRef<Node>> = nodes
-            .into_iter()
-            .map(|n| make_config_ref(n.into_inner()))
-            .collect();
-
-        // set node for all object entries!
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Linking Object Entries to nodes");
-        for node in &nodes {
-            for oe in node.object_entries() {
-                oe.__set_node(node.clone());
-            }
-        }
-
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Finalizing usage of all messages");
-        // set usage for all messages!
-        for message in &messages {
-            let once_cell = message.__get_usage();
-            if once_cell.get().is_none() {
-                let expected = builder
-                    .messages
-                    .borrow()
-                    .iter()
-                    .find(|m| &m.0.borrow().name == message.name())
-                    .unwrap()
-                    .0
-                    .borrow()
-                    .usage
-                    .clone();
-                let interval = match expected {
-                    crate::builder::message_builder::MessageBuilderUsage::External { interval } => {
-                        interval
-                    }
-                    crate::builder::message_builder::MessageBuilderUsage::Heartbeat => {
-                        Some(Duration::from_millis(100))
-                    }
-                    _ => panic!(),
+                    )
+                    .with_encoding(*tx_stream.encoding())));
                 }
-                .unwrap_or(Duration::from_secs(60));
 
-                once_cell.set(MessageUsage::External { interval }).unwrap();
+                let node_buses: Vec<BusRef> = node_data
+                    .buses
+                    .iter()
+                    .map(|bus_builder| {
+                        buses
+                            .iter()
+                            .find(|bus| bus.id() == bus_builder.0.borrow().id)
+                            .expect("buses of nodes are checked by check_references")
+                            .clone()
+                    })
+                    .collect();
+                let rx_messages: Vec<MessageRef> =
+                    node_data.rx_messages.iter().map(find_message).collect();
+                let rx_filters = filter_banks
+                    .iter()
+                    .find(|bank| Rc::ptr_eq(&bank.node().0, &node_builder.0))
+                    .map(|bank| Self::rx_filters_per_bus(bank.filters(), &node_buses, &rx_messages))
+                    .unwrap_or_default();
+                let node_transport_channels =
+                    node_data.transport_channels.iter().map(find_channel).collect();
+                let uds = match (&node_data.uds_services, &node_data.uds_channels) {
+                    (Some(services), Some((request_channel, response_channel))) => Some(Uds::new(
+                        services.clone(),
+                        find_channel(request_channel),
+                        find_channel(response_channel),
+                    )),
+                    _ => None,
+                };
+                nodes.push(Node::new(NodeParams {
+                    name: node_data.name.clone(),
+                    description: node_data.description.clone(),
+                    id: node_ids[i],
+                    types: node_parts[i].1.clone(),
+                    commands: commands.clone(),
+                    extern_commands,
+                    tx_streams: tx_streams.clone(),
+                    rx_streams,
+                    rx_messages,
+                    tx_messages: node_data.tx_messages.iter().map(find_message).collect(),
+                    object_entries: object_entries.clone(),
+                    object_entry_groups: object_entry_groups.clone(),
+                    transport_channels: node_transport_channels,
+                    uds,
+                    buses: node_buses,
+                    tx_mailbox_count: node_data.tx_mailbox_count,
+                    extern_node: node_data.extern_node,
+                    firmware_version: node_data.firmware_version.as_deref().map(|version| {
+                        Version::from_str(version).expect("versions are checked by check_versions")
+                    }),
+                    rx_timeouts: node_data
+                        .rx_timeouts
+                        .iter()
+                        .map(|(message, timeout)| (find_message(message), *timeout))
+                        .collect(),
+                    rx_filters,
+                    od_protocol: node_data.od_protocol,
+                    listen_only: node_data.listen_only,
+                }));
             }
-        }
+            nodes
+        });
+        drop(node_builders);
+        drop(phase);
+
+        let find_message = |cell: &OnceCell<MessageBuilder>| {
+            messages
+                .iter()
+                .find(|m| Some(m.name()) == cell.get().map(|m| m.0.borrow().name.clone()).as_deref())
+                .expect("protocol messages are checked by check_references")
+                .clone()
+        };
+        let get_resp_message = find_message(&builder.get_resp_message);
+        let get_req_message = find_message(&builder.get_req_message);
+        let set_resp_message = find_message(&builder.set_resp_message);
+        let set_req_message = find_message(&builder.set_req_message);
 
         let heartbeat_message = messages
             .iter()
             .find(|message| message.name() == "heartbeat")
-            .expect("heartbeat message was not defined")
+            .ok_or_else(|| {
+                errors::ConfigError::MessageNotRegistered(
+                    "heartbeat message was not defined".to_owned(),
+                )
+            })?
             .clone();
 
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Successfully build configuration");
-        let network_ref = make_config_ref(Network::new(
-            chrono::Local::now(),
+        log::debug!("Successfully build configuration");
+        let node_groups = builder
+            .node_groups
+            .iter()
+            .enumerate()
+            .map(|(i, group_builder)| {
+                let group_data = group_builder.0.borrow();
+                let group_nodes = group_data
+                    .nodes
+                    .iter()
+                    .map(|node_builder| {
+                        nodes
+                            .iter()
+                            .find(|node| node.name() == node_builder.0.borrow().name)
+                            .expect("nodes of groups are always added to the network")
+                            .clone()
+                    })
+                    .collect();
+                make_config_ref(NodeGroup::new(
+                    group_data.name.clone(),
+                    group_data.description.clone(),
+                    broadcast_server_id - 1 - i as u8,
+                    group_nodes,
+                ))
+            })
+            .collect();
+        let find_node = |name: &str| {
+            nodes
+                .iter()
+                .find(|node| node.name() == name)
+                .expect("latency paths are checked by check_latency_paths")
+                .clone()
+        };
+        let latency_paths = builder
+            .latency_paths
+            .iter()
+            .map(|path_builder| {
+                let path_data = path_builder.0.borrow();
+                let hops = path_data
+                    .hops
+                    .iter()
+                    .map(|hop| match hop {
+                        LatencyHopBuilder::ObjectEntry { node, name } => {
+                            let node = find_node(node);
+                            let object_entry = node
+                                .object_entries()
+                                .iter()
+                                .find(|oe| oe.name() == name)
+                                .expect("latency paths are checked by check_latency_paths")
+                                .clone();
+                            LatencyHop::ObjectEntry { node, object_entry }
+                        }
+                        LatencyHopBuilder::Stream { node, name } => {
+                            let node = find_node(node);
+                            let stream = node
+                                .tx_streams()
+                                .iter()
+                                .find(|s| s.name() == name)
+                                .expect("latency paths are checked by check_latency_paths")
+                                .clone();
+                            LatencyHop::Stream { node, stream }
+                        }
+                        LatencyHopBuilder::Command { node, name } => {
+                            let node = find_node(node);
+                            let command = node
+                                .commands()
+                                .iter()
+                                .find(|c| c.name() == name)
+                                .expect("latency paths are checked by check_latency_paths")
+                                .clone();
+                            LatencyHop::Command { node, command }
+                        }
+                    })
+                    .collect();
+                make_config_ref(LatencyPath::new(
+                    path_data.name.clone(),
+                    path_data.description.clone(),
+                    path_data.budget,
+                    hops,
+                ))
+            })
+            .collect();
+
+        let supervisions = builder
+            .nodes
+            .borrow()
+            .iter()
+            .flat_map(|node_builder| {
+                let node_data = node_builder.0.borrow();
+                let supervisor = nodes
+                    .iter()
+                    .find(|node| node.name() == node_data.name)
+                    .expect("every node builder is built")
+                    .clone();
+                node_data
+                    .supervisions
+                    .iter()
+                    .map(|(supervised, message, timeout)| {
+                        let supervised = nodes
+                            .iter()
+                            .find(|node| node.name() == supervised)
+                            .expect("supervisions are checked by check_supervisions")
+                            .clone();
+                        let message = messages
+                            .iter()
+                            .find(|m| m.name() == message)
+                            .expect("supervisions are checked by check_supervisions")
+                            .clone();
+                        Supervision::new(supervisor.clone(), supervised, message, *timeout)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let network_ref = make_config_ref(Network::new(NetworkParams {
+            build_time: builder.build_time.unwrap_or_else(chrono::Local::now),
             nodes,
             messages,
             types,
@@ -1200,42 +3014,79 @@ impl NetworkBuilder {
             set_resp_message,
             heartbeat_message,
             buses,
-        ));
+            access_levels,
+            node_groups,
+            latency_paths,
+            node_id_width: builder.node_id_width,
+            od_index_width: builder.od_index_width,
+        }).with_metadata(
+            builder.name.clone(),
+            builder.description.clone(),
+            builder.version.as_deref().map(|version| {
+                Version::from_str(version).expect("versions are checked by check_versions")
+            }),
+            builder.author.clone(),
+        ).with_supervisions(supervisions));
 
         // SEMANTIC CHECKS!
+        let phase = PhaseTimer::begin(observer, BuildPhase::Validation)?;
         
         // check that all names are valid c/c++ variables
-        let valid_c_var = Regex::new(r"^[a-zA-Z_]+[a-zA-Z0-9_]*$").unwrap();
-        let is_c_keyword = Regex::new(r"^(restrict|alignas|alignof|and|and_eq|asm|atomic_cancel|atomic_commit|auto|bitand|bitor|bool|break|case|catch|char|char8_t|char16_t|char32_t|class|compl|concept|const|consteval|constexpr|constinit|const_cast|continue|co_await|co_return|co_yield|decltype|default|delete|do|double|dynamic_cast|else|enum|explicit|export|extern|false|float|for|friend|goto|if|inline|int|long|mutable|namespace|new|noexpect|not|not_eq|nullptr|operator|or|or_eq|private|protected|public|reflexpr|register|reinterpret_cast|require|return|short|signed|sizeof|static|static_assert|static_cast|struct|switch|synchronized|template|this|thread_local|throw|true|try|typedef|typeid|typename|union|unsigned|using|virtual|void|volatile|wchar_t|while|xor|xor_eq)$").unwrap();
         for node in network_ref.nodes() {
             let node_name = node.name();
-            if !valid_c_var.is_match(node_name) || is_c_keyword.is_match(node_name) {
-                panic!("{node_name} is not a valid node name.");
+            if !VALID_C_VAR.is_match(node_name) || C_KEYWORD.is_match(node_name) {
+                return Err(errors::ConfigError::InvalidName(format!(
+                    "{node_name} is not a valid node name"
+                )));
             }
             for stream in node.tx_streams() {
                 let name = stream.name();
-                if !valid_c_var.is_match(name) || is_c_keyword.is_match(name) {
-                    panic!("{name} is not a valid stream name.");
+                if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                    return Err(errors::ConfigError::InvalidName(format!(
+                        "{name} is not a valid stream name (in node {node_name})"
+                    )));
                 }
             }
             for oe in node.object_entries() {
                 let name = oe.name();
-                if !valid_c_var.is_match(name) || is_c_keyword.is_match(name) {
-                    panic!("{name} is not a valid object entry name.");
+                if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                    return Err(errors::ConfigError::InvalidName(format!(
+                        "{name} is not a valid object entry name (in node {node_name})"
+                    )));
                 }
             }
             for cmd in node.commands() {
                 let name = cmd.name();
-                if !valid_c_var.is_match(name) || is_c_keyword.is_match(name) {
-                    panic!("{name} is not a valid command name");
+                if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                    return Err(errors::ConfigError::InvalidName(format!(
+                        "{name} is not a valid command name (in node {node_name})"
+                    )));
                 }
             }
         }
 
+        for node in network_ref.nodes() {
+            if let Some(mailboxes) = node.tx_mailbox_count() {
+                let required = node.required_tx_mailboxes();
+                if required > mailboxes as usize {
+                    log::warn!(
+                        "node {} may have {required} pending transmissions, but only has {mailboxes} tx mailboxes",
+                        node.name()
+                    );
+                }
+            }
+        }
+
+        for warning in network_ref.wiring_warnings() {
+            log::warn!("{warning}");
+        }
+
         for bus in network_ref.buses() {
             let name = bus.name();
-            if !valid_c_var.is_match(name) || is_c_keyword.is_match(name) {
-                panic!("{name} is not a valid bus name");
+            if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                return Err(errors::ConfigError::InvalidName(format!(
+                    "{name} is not a valid bus name"
+                )));
             }
         }
 
@@ -1243,58 +3094,89 @@ impl NetworkBuilder {
             let dlc = message.dlc();
             let msg_name = message.name();
 
-            if !valid_c_var.is_match(msg_name) || is_c_keyword.is_match(msg_name) {
-                panic!("{msg_name} is not a valid name for a message.");
+            if !VALID_C_VAR.is_match(msg_name) || C_KEYWORD.is_match(msg_name) {
+                return Err(errors::ConfigError::InvalidName(format!(
+                    "{msg_name} is not a valid name for a message"
+                )));
             }
             if dlc > 8 {
-                panic!(
-                    "All messages have to have a dlc less than 8. \n{msg_name} has dlc = {dlc}."
-                );
+                return Err(errors::ConfigError::MessageTooLong(format!(
+                    "all messages have to have a dlc of at most 8, {msg_name} has dlc = {dlc}"
+                )));
             }
 
             if dlc == 0 {
-                println!("WARN: message {msg_name} is empty");
+                log::warn!("message {msg_name} is empty");
             }
         }
 
-        fn check_ty(ty: &Type, valid_c_var: &Regex, c_keyword: &Regex) {
+        fn check_ty(ty: &Type) -> errors::Result<()> {
             match &ty as &Type {
-                Type::Primitive(_) => (),
+                Type::Primitive(_) => Ok(()),
                 Type::Struct {
                     name,
                     description : _,
                     attribs,
+                    attrib_info: _,
                     visibility : _,
                 } => {
-                    if !valid_c_var.is_match(name) || c_keyword.is_match(name) {
-                        panic!("{name} is not a valid name for a struct");
+                    if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                        return Err(errors::ConfigError::InvalidName(format!(
+                            "{name} is not a valid name for a struct"
+                        )));
                     }
                     for (attrib_name, attrib_ty) in attribs {
-                        if !valid_c_var.is_match(attrib_name) || c_keyword.is_match(attrib_name) {
-                            panic!("{attrib_name} is not a valid attribute for a struct (in struct {name})");
+                        if !VALID_C_VAR.is_match(attrib_name) || C_KEYWORD.is_match(attrib_name) {
+                            return Err(errors::ConfigError::InvalidName(format!(
+                                "{attrib_name} is not a valid attribute for a struct (in struct {name})"
+                            )));
                         }
-                        check_ty(attrib_ty, valid_c_var, c_keyword)
+                        check_ty(attrib_ty)?;
                     }
+                    Ok(())
                 }
                 Type::Enum {
                     name,
                     description : _,
                     size : _,
                     entries : _,
+                    entry_info : _,
                     visibility : _,
                 } => {
-                    if !valid_c_var.is_match(name) || c_keyword.is_match(name) {
-                        panic!("{name} is not a valid name for a struct");
+                    if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                        return Err(errors::ConfigError::InvalidName(format!(
+                            "{name} is not a valid name for an enum"
+                        )));
+                    }
+                    Ok(())
+                }
+                Type::Array { len : _, ty } => check_ty(ty),
+                Type::Union { name, tag, variants, .. } => {
+                    if !VALID_C_VAR.is_match(name) || C_KEYWORD.is_match(name) {
+                        return Err(errors::ConfigError::InvalidName(format!(
+                            "{name} is not a valid name for a union"
+                        )));
+                    }
+                    check_ty(tag)?;
+                    for (_, variant_ty) in variants {
+                        check_ty(variant_ty)?;
                     }
+                    Ok(())
                 }
-                Type::Array { len : _, ty } => check_ty(ty, valid_c_var, c_keyword),
             }
         }
 
         for ty in network_ref.types() {
-            check_ty(ty, &valid_c_var, &is_c_keyword);
+            check_ty(ty)?;
         }
+        drop(phase);
 
+        drop(builder);
+        self.0.borrow_mut().last_build = Some(network_ref.clone());
+        for flag in self.dirty_flags() {
+            flag.clear_dirty();
+        }
+        observer.on_progress(100);
         Ok(network_ref)
     }
 }
@@ -1,8 +1,8 @@
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
 
-use crate::config::ObjectEntryAccess;
+use crate::config::{ObjectEntryAccess, UdsService};
 
-use super::{stream_builder::{ReceiveStreamBuilder, StreamBuilder}, ObjectEntryBuilder, MessageBuilder, NetworkBuilder, CommandBuilder, BuilderRef, MessagePriority, make_builder_ref, bus::BusBuilder};
+use super::{stream_builder::{ReceiveStreamBuilder, StreamBuilder}, ObjectEntryBuilder, ObjectEntryGroupBuilder, TransportChannelBuilder, MessageBuilder, NetworkBuilder, CommandBuilder, BuilderRef, DirtyFlag, MessagePriority, make_builder_ref, bus::BusBuilder};
 
 
 #[derive(Debug, Clone)]
@@ -15,19 +15,79 @@ pub struct NodeData {
     pub extern_commands: Vec<CommandBuilder>,
     pub network_builder: NetworkBuilder,
     pub rx_messages: Vec<MessageBuilder>,
+    /// rx messages, whose freshness is supervised with a timeout.
+    pub rx_timeouts: Vec<(MessageBuilder, Duration)>,
     pub tx_messages: Vec<MessageBuilder>,
     pub object_entries: Vec<ObjectEntryBuilder>,
+    pub object_entry_groups: Vec<ObjectEntryGroupBuilder>,
     pub tx_streams: Vec<StreamBuilder>,
     pub rx_streams: Vec<ReceiveStreamBuilder>,
+    pub transport_channels: Vec<TransportChannelBuilder>,
+    pub uds_services: Option<Vec<UdsService>>,
+    /// (request, response) channels, generated while building.
+    pub uds_channels: Option<(TransportChannelBuilder, TransportChannelBuilder)>,
     pub buses : Vec<BusBuilder>,
+    pub tx_mailbox_count : Option<u8>,
+    /// Third-party device, for which no code is generated.
+    pub extern_node : bool,
+    /// version of the configuration the firmware of the node is built against.
+    pub firmware_version : Option<String>,
+    /// Receives get/set requests and transmits their responses.
+    pub od_protocol : bool,
+    /// Never transmits, see `set_listen_only`.
+    pub listen_only : bool,
+    /// Pinned node id, see `set_node_id`.
+    pub node_id : Option<u8>,
+    /// (supervised node, message, timeout), see `supervise`.
+    pub supervisions : Vec<(String, String, Duration)>,
 }
 
 
 impl NodeBuilder {
+    /// flags of the node and of the object entries, streams,
+    /// commands and transport channels it owns.
+    pub(crate) fn dirty_flags(&self) -> Vec<Rc<dyn DirtyFlag>> {
+        let node_data = self.0.borrow();
+        let mut flags: Vec<Rc<dyn DirtyFlag>> = vec![self.0.clone()];
+        flags.extend(node_data.object_entries.iter().map(|oe| oe.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(node_data.object_entry_groups.iter().map(|g| g.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(node_data.tx_streams.iter().map(|s| s.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(node_data.rx_streams.iter().map(|s| s.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(node_data.commands.iter().map(|c| c.0.clone() as Rc<dyn DirtyFlag>));
+        flags.extend(node_data.transport_channels.iter().map(|c| c.0.clone() as Rc<dyn DirtyFlag>));
+        flags
+    }
+    /// Modified since the last build of the network.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_flags().iter().any(|flag| flag.is_dirty())
+    }
     pub fn new(name: &str, network_builder: &NetworkBuilder) -> NodeBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating node {name}");
-        let node_builder = NodeBuilder(make_builder_ref(NodeData {
+        log::trace!("Creating node {name}");
+        let node_builder = Self::empty(name, network_builder, false);
+        node_builder.add_rx_message(&network_builder.get_req_message());
+        node_builder.add_tx_message(&network_builder.get_resp_message());
+        node_builder.add_rx_message(&network_builder.set_req_message());
+        node_builder.add_tx_message(&network_builder.set_resp_message());
+
+        let config_hash = node_builder.create_object_entry("config_hash", "u64");
+        config_hash.add_description("Hash of the local network configuration");
+        config_hash.set_access(ObjectEntryAccess::Const);
+
+        let build_time = node_builder.create_object_entry("build_time", "date_time");
+        build_time.add_description("Time the node was last build from source");
+        build_time.set_access(ObjectEntryAccess::Const);
+
+        node_builder
+    }
+    /// Node of a third-party device (e.g. a purchased BMS).
+    /// It does not take part in the get/set protocol and the heartbeat,
+    /// all messages it transmits have to be assigned a fixed id.
+    pub fn new_extern(name: &str, network_builder: &NetworkBuilder) -> NodeBuilder {
+        log::trace!("Creating extern node {name}");
+        Self::empty(name, network_builder, true)
+    }
+    fn empty(name: &str, network_builder: &NetworkBuilder, extern_node: bool) -> NodeBuilder {
+        NodeBuilder(make_builder_ref(NodeData {
             name: name.to_owned(),
             description: None,
             network_builder: network_builder.clone(),
@@ -35,25 +95,63 @@ impl NodeBuilder {
             extern_commands: vec![],
             tx_messages: vec![],
             rx_messages: vec![],
+            rx_timeouts: vec![],
             object_entries: vec![],
+            object_entry_groups: vec![],
             tx_streams: vec![],
             rx_streams: vec![],
+            transport_channels: vec![],
+            uds_services: None,
+            uds_channels: None,
             buses : vec![],
-        }));
-        node_builder.add_rx_message(&network_builder._get_req_message());
-        node_builder.add_tx_message(&network_builder._get_resp_message());
-        node_builder.add_rx_message(&network_builder._set_req_message());
-        node_builder.add_tx_message(&network_builder._set_resp_message());
-
-        let config_hash = node_builder.create_object_entry("config_hash", "u64");
-        config_hash.add_description("Hash of the local network configuration");
-        config_hash.set_access(ObjectEntryAccess::Const);
-
-        let build_time = node_builder.create_object_entry("build_time", "date_time");
-        build_time.add_description("Time the node was last build from source");
-        build_time.set_access(ObjectEntryAccess::Const);
-
-        node_builder
+            tx_mailbox_count : None,
+            extern_node,
+            firmware_version : None,
+            od_protocol : !extern_node,
+            listen_only : false,
+            node_id : None,
+            supervisions : vec![],
+        }))
+    }
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn is_extern(&self) -> bool {
+        self.0.borrow().extern_node
+    }
+    pub fn object_entries(&self) -> Vec<ObjectEntryBuilder> {
+        self.0.borrow().object_entries.clone()
+    }
+    pub fn tx_messages(&self) -> Vec<MessageBuilder> {
+        self.0.borrow().tx_messages.clone()
+    }
+    pub fn rx_messages(&self) -> Vec<MessageBuilder> {
+        self.0.borrow().rx_messages.clone()
+    }
+    pub fn tx_streams(&self) -> Vec<StreamBuilder> {
+        self.0.borrow().tx_streams.clone()
+    }
+    pub fn rx_streams(&self) -> Vec<ReceiveStreamBuilder> {
+        self.0.borrow().rx_streams.clone()
+    }
+    pub fn commands(&self) -> Vec<CommandBuilder> {
+        self.0.borrow().commands.clone()
+    }
+    pub fn find_object_entry(&self, name: &str) -> Option<ObjectEntryBuilder> {
+        self.0
+            .borrow()
+            .object_entries
+            .iter()
+            .find(|oe| oe.0.borrow().name == name)
+            .cloned()
+    }
+    pub fn find_stream(&self, name: &str) -> Option<StreamBuilder> {
+        self.0
+            .borrow()
+            .tx_streams
+            .iter()
+            .find(|s| s.0.borrow().name == name)
+            .cloned()
     }
     pub fn assign_bus(&self, bus_name : &str) -> BusBuilder{
         let mut node_data = self.0.borrow_mut();       
@@ -72,10 +170,96 @@ impl NodeBuilder {
             }
         }
     }
+    /// Number of hardware tx mailboxes of the CAN controllers of the node.
+    /// If set, the build warns if the node may have more pending transmissions.
+    pub fn set_tx_mailbox_count(&self, count : u8) {
+        let mut node_data = self.0.borrow_mut();
+        node_data.tx_mailbox_count = Some(count);
+    }
+    /// Version of the configuration the firmware of the node targets (e.g. "v2.1"),
+    /// lints report elements the node uses, which are not available in that version.
+    pub fn set_firmware_version(&self, version : &str) {
+        let mut node_data = self.0.borrow_mut();
+        node_data.firmware_version = Some(version.to_owned());
+    }
     pub fn add_description(&self, description: &str) {
         let mut node_data = self.0.borrow_mut();
         node_data.description = Some(description.to_owned());
     }
+    /// For nodes with a listen-only transceiver (e.g. loggers or telemetry
+    /// bridges), which must never transmit. Disables the object dictionary
+    /// protocol and the heartbeat of the node, the build fails if the node
+    /// transmits any message, stream or command.
+    pub fn set_listen_only(&self, listen_only: bool) {
+        if listen_only {
+            self.disable_od_protocol();
+        }
+        self.0.borrow_mut().listen_only = listen_only;
+    }
+    /// For minimal nodes (e.g. sensors), which don't implement the object
+    /// dictionary protocol: the node neither receives get/set requests nor
+    /// transmits their responses. The object entries config_hash and build_time
+    /// are removed, unless they are streamed, because they can only be read
+    /// with the protocol. The build fails if the node has protected object
+    /// entries, entries accepting broadcasts or is part of a node group.
+    pub fn disable_od_protocol(&self) {
+        let network_builder = self.0.borrow().network_builder.clone();
+        let protocol_messages = [
+            network_builder.get_req_message(),
+            network_builder.get_resp_message(),
+            network_builder.set_req_message(),
+            network_builder.set_resp_message(),
+        ];
+        let is_protocol_message = |message: &MessageBuilder| {
+            protocol_messages.iter().any(|m| Rc::ptr_eq(&m.0, &message.0))
+        };
+        for message in &protocol_messages {
+            let mut message_data = message.0.borrow_mut();
+            message_data.receivers.retain(|n| !Rc::ptr_eq(&n.0, &self.0));
+            message_data.transmitters.retain(|n| !Rc::ptr_eq(&n.0, &self.0));
+        }
+        let mut node_data = self.0.borrow_mut();
+        node_data.od_protocol = false;
+        node_data.rx_messages.retain(|m| !is_protocol_message(m));
+        node_data.tx_messages.retain(|m| !is_protocol_message(m));
+        let streamed: Vec<ObjectEntryBuilder> = node_data
+            .tx_streams
+            .iter()
+            .flat_map(|stream| stream.0.borrow().object_entries.clone())
+            .flatten()
+            .collect();
+        node_data.object_entries.retain(|oe| {
+            let name = &oe.0.borrow().name;
+            (name != "config_hash" && name != "build_time")
+                || streamed.iter().any(|s| Rc::ptr_eq(&s.0, &oe.0))
+        });
+    }
+    pub fn has_od_protocol(&self) -> bool {
+        self.0.borrow().od_protocol
+    }
+    /// Pins the id of the node, which is used as server_id of the get/set
+    /// protocol and in the heartbeat. Nodes without a pinned id get the
+    /// smallest free id in creation order. Collisions and ids, which don't
+    /// fit into the node_id width, are rejected while building.
+    pub fn set_node_id(&self, node_id : u8) {
+        self.0.borrow_mut().node_id = Some(node_id);
+    }
+    pub fn node_id(&self) -> Option<u8> {
+        self.0.borrow().node_id
+    }
+    /// Declares that this node supervises the node {other} by receiving
+    /// the message {message}, which {other} transmits, at least every
+    /// `timeout`. The relationship is validated while building and
+    /// available as `Network::supervisions`.
+    pub fn supervise(&self, other: &str, message: &str, timeout: Duration) {
+        let mut node_data = self.0.borrow_mut();
+        node_data
+            .supervisions
+            .retain(|(node, m, _)| node != other || m != message);
+        node_data
+            .supervisions
+            .push((other.to_owned(), message.to_owned(), timeout));
+    }
     pub fn add_tx_message(&self, message_builder: &MessageBuilder) {
         let node_name = self.0.borrow().name.clone();
         if !message_builder.0.borrow().transmitters.iter().any(|n| &n.0.borrow().name == &node_name) {
@@ -90,6 +274,16 @@ impl NodeBuilder {
         }
         self.0.borrow_mut().rx_messages.push(message_builder.clone());
     }
+    /// Receives the message and expects it at least every `timeout`,
+    /// otherwise the firmware considers the message stale.
+    pub fn add_rx_message_with_timeout(&self, message_builder: &MessageBuilder, timeout: Duration) {
+        if !self.0.borrow().rx_messages.iter().any(|m| Rc::ptr_eq(&m.0, &message_builder.0)) {
+            self.add_rx_message(message_builder);
+        }
+        let mut node_data = self.0.borrow_mut();
+        node_data.rx_timeouts.retain(|(m, _)| !Rc::ptr_eq(&m.0, &message_builder.0));
+        node_data.rx_timeouts.push((message_builder.clone(), timeout));
+    }
     pub fn create_command(&self, name: &str, expected_interval : Option<Duration>) -> CommandBuilder {
         let command_builder = CommandBuilder::new(name, &self, expected_interval);
         let mut node_data = self.0.borrow_mut();
@@ -112,9 +306,38 @@ impl NodeBuilder {
             .tx_messages
             .push(message_builder.0.borrow().call_message.clone());
     }
+    /// Reserves a pair of messages for ISO 15765-2 segmented transfers
+    /// from this node to the node {rx_node_name}.
+    pub fn create_transport_channel(&self, name: &str, rx_node_name: &str) -> TransportChannelBuilder {
+        let network_builder = self.0.borrow().network_builder.clone();
+        let rx_node = network_builder.create_node(rx_node_name);
+        let channel = TransportChannelBuilder::new(name, self, &rx_node);
+        let (data_message, flow_control_message) = {
+            let channel_data = channel.0.borrow();
+            (channel_data.data_message.clone(), channel_data.flow_control_message.clone())
+        };
+        self.add_tx_message(&data_message);
+        self.add_rx_message(&flow_control_message);
+        rx_node.add_rx_message(&data_message);
+        rx_node.add_tx_message(&flow_control_message);
+        self.0.borrow_mut().transport_channels.push(channel.clone());
+        rx_node.0.borrow_mut().transport_channels.push(channel.clone());
+        channel
+    }
+    /// Lists the diagnostic services the node answers to. The request and
+    /// response transport channels to the tester
+    /// (see NetworkBuilder::set_uds_tester) are generated while building.
+    pub fn enable_uds(&self, services: &[UdsService]) {
+        let mut node_data = self.0.borrow_mut();
+        let uds_services = node_data.uds_services.get_or_insert_with(Vec::new);
+        for service in services {
+            if !uds_services.contains(service) {
+                uds_services.push(*service);
+            }
+        }
+    }
     pub fn create_object_entry(&self, name: &str, ty: &str) -> ObjectEntryBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Require ObjectEntry {}::{name}", self.0.borrow().name);
+        log::trace!("Require ObjectEntry {}::{name}", self.0.borrow().name);
         let existing_oe = self.0.borrow().object_entries.iter().find(|oe| oe.0.borrow().name == name).cloned();
         match existing_oe {
             Some(oe) => {
@@ -128,9 +351,20 @@ impl NodeBuilder {
         node_data.object_entries.push(object_entry_builder.clone());
         object_entry_builder
     }
+    /// Groups related object entries (e.g. controller gains), which are
+    /// rendered and generated together.
+    pub fn create_object_entry_group(&self, name: &str) -> ObjectEntryGroupBuilder {
+        let existing = self.0.borrow().object_entry_groups.iter().find(|group| group.0.borrow().name == name).cloned();
+        if let Some(group) = existing {
+            return group;
+        }
+        let group_builder = ObjectEntryGroupBuilder::new(name, self.clone());
+        let mut node_data = self.0.borrow_mut();
+        node_data.object_entry_groups.push(group_builder.clone());
+        group_builder
+    }
     pub fn create_stream(&self, name: &str) -> StreamBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Require Stream {}::{name}", self.0.borrow().name);
+        log::trace!("Require Stream {}::{name}", self.0.borrow().name);
         match self.0.borrow().tx_streams.iter().find(|stream| stream.0.borrow().name == name).cloned() {
             Some(stream) => return stream,
             None => (),
@@ -143,8 +377,7 @@ impl NodeBuilder {
 
     pub fn receive_stream(&self, tx_node_name: &str, tx_stream_name: &str) -> ReceiveStreamBuilder {
         let node_data = self.0.borrow();
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating dependencies of receive stream {tx_node_name}::{tx_stream_name} -> {}", node_data.name);
+        log::trace!("Creating dependencies of receive stream {tx_node_name}::{tx_stream_name} -> {}", node_data.name);
         if tx_node_name == node_data.name {
             panic!("can't receive local stream");
         }
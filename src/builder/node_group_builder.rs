@@ -0,0 +1,39 @@
+use super::{make_builder_ref, BuilderRef, NetworkBuilder, NodeBuilder};
+
+#[derive(Debug, Clone)]
+pub struct NodeGroupBuilder(pub BuilderRef<NodeGroupData>);
+#[derive(Debug)]
+pub struct NodeGroupData {
+    pub name: String,
+    pub description: Option<String>,
+    pub network_builder: NetworkBuilder,
+    pub nodes: Vec<NodeBuilder>,
+}
+
+impl NodeGroupBuilder {
+    pub fn new(name: &str, network_builder: &NetworkBuilder) -> NodeGroupBuilder {
+        log::trace!("Creating node group {name}");
+        NodeGroupBuilder(make_builder_ref(NodeGroupData {
+            name: name.to_owned(),
+            description: None,
+            network_builder: network_builder.clone(),
+            nodes: vec![],
+        }))
+    }
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn add_description(&self, description: &str) {
+        let mut data = self.0.borrow_mut();
+        data.description = Some(description.to_owned());
+    }
+    /// Adds the node to the group, the node is created if it doesn't exist yet.
+    pub fn add_node(&self, node_name: &str) {
+        let network_builder = self.0.borrow().network_builder.clone();
+        let node = network_builder.create_node(node_name);
+        let mut data = self.0.borrow_mut();
+        if !data.nodes.iter().any(|n| n.0.borrow().name == node_name) {
+            data.nodes.push(node);
+        }
+    }
+}
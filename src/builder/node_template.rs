@@ -0,0 +1,48 @@
+use std::rc::Rc;
+
+use super::{NetworkBuilder, NodeBuilder};
+
+/// Definition of a node type (e.g. a wheel), which is stamped out
+/// multiple times with suffixed names. Every instance gets its own
+/// object entries, streams, commands and node id.
+#[derive(Clone)]
+pub struct NodeTemplate(Rc<dyn Fn(&NodeBuilder)>);
+
+impl std::fmt::Debug for NodeTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NodeTemplate")
+    }
+}
+
+impl NodeTemplate {
+    pub fn new(definition: impl Fn(&NodeBuilder) + 'static) -> NodeTemplate {
+        NodeTemplate(Rc::new(definition))
+    }
+    /// Creates the nodes {name}_0 to {name}_{count - 1}.
+    pub fn instantiate(
+        &self,
+        network_builder: &NetworkBuilder,
+        name: &str,
+        count: usize,
+    ) -> Vec<NodeBuilder> {
+        self.instantiate_with(network_builder, name, count, |_, _| ())
+    }
+    /// Like instantiate, but calls overrides with the index of every
+    /// instance after applying the template to it.
+    pub fn instantiate_with(
+        &self,
+        network_builder: &NetworkBuilder,
+        name: &str,
+        count: usize,
+        overrides: impl Fn(usize, &NodeBuilder),
+    ) -> Vec<NodeBuilder> {
+        (0..count)
+            .map(|index| {
+                let node = network_builder.create_node(&format!("{name}_{index}"));
+                (self.0)(&node);
+                overrides(index, &node);
+                node
+            })
+            .collect()
+    }
+}
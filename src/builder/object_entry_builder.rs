@@ -1,4 +1,4 @@
-use crate::config::{ObjectEntryAccess, Visibility};
+use crate::config::{ObjectEntryAccess, OverflowPolicy, Visibility};
 
 use super::{make_builder_ref, BuilderRef};
 
@@ -13,13 +13,20 @@ pub struct ObjectEntryData {
     pub ty: String,
     pub access: ObjectEntryAccess,
     pub visibility: Visibility,
+    pub index: Option<u16>,
+    pub default: Option<String>,
+    pub persistent: bool,
+    pub overflow: OverflowPolicy,
+    pub range: Option<(f64, f64)>,
+    pub broadcast: bool,
+    pub introduced_in: Option<String>,
+    pub deprecated_in: Option<String>,
 }
 
 
 impl ObjectEntryBuilder {
     pub fn new(name: &str, ty: &str, node_name : &str) -> ObjectEntryBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Create ObjectEntry {node_name}::{name} : {ty}");
+        log::trace!("Create ObjectEntry {node_name}::{name} : {ty}");
         ObjectEntryBuilder(make_builder_ref(ObjectEntryData {
             name: name.to_owned(),
             ty: ty.to_owned(),
@@ -27,8 +34,22 @@ impl ObjectEntryBuilder {
             unit: None,
             access: ObjectEntryAccess::Global,
             visibility: Visibility::Global,
+            index: None,
+            default: None,
+            persistent: false,
+            overflow: OverflowPolicy::Saturate,
+            range: None,
+            broadcast: false,
+            introduced_in: None,
+            deprecated_in: None,
         }))
     }
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn ty(&self) -> String {
+        self.0.borrow().ty.clone()
+    }
     pub fn hide(&self) {
         let mut data = self.0.borrow_mut();
         data.visibility = Visibility::Static;
@@ -45,5 +66,47 @@ impl ObjectEntryBuilder {
         let mut data = self.0.borrow_mut();
         data.unit = Some(unit.to_owned());
     }
+    /// Pins the object entry to a fixed index in the object dictionary,
+    /// which is kept even if other entries are added or removed.
+    pub fn set_index(&self, index: u16) {
+        let mut data = self.0.borrow_mut();
+        data.index = Some(index);
+    }
+    /// Sets the value the object entry is initialized with and reset to.
+    /// Numbers are given as literals ("1.5"), enums by the name of the entry.
+    pub fn set_default(&self, value: &str) {
+        let mut data = self.0.borrow_mut();
+        data.default = Some(value.to_owned());
+    }
+    /// Persistent object entries are stored in non volatile memory.
+    pub fn set_persistent(&self, persistent: bool) {
+        let mut data = self.0.borrow_mut();
+        data.persistent = persistent;
+    }
+    /// Defines how values outside of the representable range of the
+    /// type are encoded into the signals of streams containing the entry.
+    pub fn set_overflow_policy(&self, overflow: OverflowPolicy) {
+        let mut data = self.0.borrow_mut();
+        data.overflow = overflow;
+    }
+    /// Declares the range of values the object entry is expected to take.
+    pub fn set_range(&self, min: f64, max: f64) {
+        let mut data = self.0.borrow_mut();
+        data.range = Some((min, max));
+    }
+    /// Opts the object entry into set requests addressed to a node group
+    /// or to all nodes (e.g. entering a safe state), which requires global write access.
+    pub fn set_broadcast(&self, broadcast: bool) {
+        let mut data = self.0.borrow_mut();
+        data.broadcast = broadcast;
+    }
+    /// Version of the configuration in which the object entry was added (e.g. "v2.3").
+    pub fn introduced_in(&self, version: &str) {
+        let mut data = self.0.borrow_mut();
+        data.introduced_in = Some(version.to_owned());
+    }
+    pub fn deprecated_in(&self, version: &str) {
+        let mut data = self.0.borrow_mut();
+        data.deprecated_in = Some(version.to_owned());
+    }
 }
-
@@ -0,0 +1,68 @@
+use crate::errors;
+
+use super::{make_builder_ref, BuilderRef, NodeBuilder, ObjectEntryBuilder};
+
+#[derive(Debug, Clone)]
+pub struct ObjectEntryGroupBuilder(pub BuilderRef<ObjectEntryGroupData>);
+#[derive(Debug)]
+pub struct ObjectEntryGroupData {
+    pub name: String,
+    pub description: Option<String>,
+    pub node: NodeBuilder,
+    pub object_entries: Vec<ObjectEntryBuilder>,
+}
+
+impl ObjectEntryGroupBuilder {
+    pub fn new(name: &str, node_builder: NodeBuilder) -> ObjectEntryGroupBuilder {
+        log::trace!(
+            "Creating ObjectEntry group {}::{name}",
+            node_builder.0.borrow().name
+        );
+        ObjectEntryGroupBuilder(make_builder_ref(ObjectEntryGroupData {
+            name: name.to_owned(),
+            description: None,
+            node: node_builder,
+            object_entries: vec![],
+        }))
+    }
+    pub fn add_description(&self, description: &str) {
+        let mut data = self.0.borrow_mut();
+        data.description = Some(description.to_owned());
+    }
+    /// Adds an existing object entry of the node to the group.
+    pub fn add_entry(&self, name: &str) -> errors::Result<()> {
+        let node = self.0.borrow().node.clone();
+        let node_data = node.0.borrow();
+        let Some(oe) = node_data
+            .object_entries
+            .iter()
+            .find(|oe| oe.0.borrow().name == name)
+            .cloned()
+        else {
+            return Err(errors::ConfigError::UndefinedObjectEntry(format!(
+                "object entry {}::{name} of group {} is not defined",
+                node_data.name,
+                self.0.borrow().name
+            )));
+        };
+        self.push_entry(oe);
+        Ok(())
+    }
+    /// Creates a object entry on the node and adds it to the group.
+    pub fn create_object_entry(&self, name: &str, ty: &str) -> ObjectEntryBuilder {
+        let node = self.0.borrow().node.clone();
+        let oe = node.create_object_entry(name, ty);
+        self.push_entry(oe.clone());
+        oe
+    }
+    fn push_entry(&self, oe: ObjectEntryBuilder) {
+        let mut group_data = self.0.borrow_mut();
+        if !group_data
+            .object_entries
+            .iter()
+            .any(|entry| std::rc::Rc::ptr_eq(&entry.0, &oe.0))
+        {
+            group_data.object_entries.push(oe);
+        }
+    }
+}
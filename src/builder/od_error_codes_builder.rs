@@ -0,0 +1,74 @@
+use crate::errors;
+
+use super::{EnumBuilder, NetworkBuilder, TypeBuilder};
+
+/// Error codes every network defines with a description, Success and Error
+/// keep the values of the original protocol.
+pub const STANDARD_OD_ERROR_CODES: [(&str, &str); 6] = [
+    ("Success", "the request was executed"),
+    ("Error", "the request failed for an unspecified reason"),
+    ("InvalidIndex", "the server has no object entry with the od_index"),
+    ("AccessDenied", "the client isn't allowed to write the object entry"),
+    ("Timeout", "a fragment of the value wasn't received in time"),
+    ("ValueOutOfRange", "the value is outside of the range of the object entry"),
+];
+
+/// Error codes of the get and set responses, which are shared
+/// by the enums get_resp_erno and set_resp_erno.
+#[derive(Debug, Clone)]
+pub struct OdErrorCodesBuilder {
+    enums: [EnumBuilder; 2],
+}
+
+impl OdErrorCodesBuilder {
+    pub(crate) fn new(network_builder: &NetworkBuilder) -> errors::Result<OdErrorCodesBuilder> {
+        // the enums can be renamed or replaced after NetworkBuilder::new.
+        let find_enum = |name: &str| match network_builder.find_type(name) {
+            Some(TypeBuilder::Enum(enum_builder)) => Ok(enum_builder),
+            _ => Err(errors::ConfigError::UndefinedType(format!(
+                "{name} isn't defined as an enum, the od error codes can't be edited"
+            ))),
+        };
+        Ok(OdErrorCodesBuilder {
+            enums: [find_enum("get_resp_erno")?, find_enum("set_resp_erno")?],
+        })
+    }
+    /// Adds a network specific error code, the value is assigned
+    /// after the standard codes if it's None.
+    pub fn add_code(&self, name: &str, value: Option<u64>) -> errors::Result<()> {
+        // both enums are checked first, so they don't diverge on errors.
+        for enum_builder in &self.enums {
+            if enum_builder.0.borrow().entries.iter().any(|(entry, _)| entry == name) {
+                return Err(errors::ConfigError::DuplicatedEnumEntry(name.to_owned()));
+            }
+        }
+        for enum_builder in &self.enums {
+            enum_builder.add_entry(name, value)?;
+        }
+        Ok(())
+    }
+    pub fn add_code_description(&self, name: &str, description: &str) -> errors::Result<()> {
+        for enum_builder in &self.enums {
+            let enum_data = enum_builder.0.borrow();
+            if !enum_data.entries.iter().any(|(entry, _)| entry == name) {
+                return Err(errors::ConfigError::UndefinedEnumEntry(format!(
+                    "{}::{name}",
+                    enum_data.name
+                )));
+            }
+        }
+        for enum_builder in &self.enums {
+            enum_builder.add_entry_description(name, description)?;
+        }
+        Ok(())
+    }
+    pub fn codes(&self) -> Vec<String> {
+        self.enums[0]
+            .0
+            .borrow()
+            .entries
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
@@ -1,9 +1,12 @@
 use std::time::Duration;
 
-use crate::config::Visibility;
+use crate::{
+    config::{stream::{SignalConversion, StreamEncoding}, Visibility},
+    errors::{self, ConfigError},
+};
 
 use super::{
-    make_builder_ref, BuilderRef, MessageBuilder, MessagePriority, MessageTypeFormatBuilder,
+    make_builder_ref, parse_signal_type, BuilderRef, MessageBuilder, MessagePriority, MessageTypeFormatBuilder,
     NodeBuilder, ObjectEntryBuilder,
 };
 
@@ -16,9 +19,14 @@ pub struct StreamData {
     pub message: MessageBuilder,
     pub format: MessageTypeFormatBuilder,
     pub tx_node: NodeBuilder,
-    pub object_entries: Vec<ObjectEntryBuilder>,
+    /// slots of the stream message, None for skipped (reserved) slots.
+    pub object_entries: Vec<Option<ObjectEntryBuilder>>,
+    /// signal type of every object entry, None if the entry is
+    /// encoded with its own type.
+    pub signal_types: Vec<Option<String>>,
     pub visbility: Visibility,
     pub interval: (Duration, Duration),
+    pub encoding: StreamEncoding,
 }
 
 #[derive(Debug, Clone)]
@@ -33,8 +41,7 @@ pub struct ReceiveStreamData {
 
 impl StreamBuilder {
     pub fn new(name: &str, node_builder: NodeBuilder) -> StreamBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating (tx)-Stream {name} for node {}", node_builder.0.borrow().name);
+        log::trace!("Creating (tx)-Stream {name} for node {}", node_builder.0.borrow().name);
         let node_data = node_builder.0.borrow();
         let message = node_data.network_builder.create_message(
             &format!("{}_stream_{name}", node_builder.0.borrow().name),
@@ -53,18 +60,32 @@ impl StreamBuilder {
             format,
             tx_node: node_builder,
             object_entries: vec![],
+            signal_types: vec![],
             visbility: Visibility::Global,
             interval: (Duration::from_millis(50), Duration::from_millis(500)),
+            encoding: StreamEncoding::Absolute,
         }));
-        message.__assign_to_stream(&new);
+        message.assign_to_stream(&new);
         new
     }
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+    pub fn message(&self) -> MessageBuilder {
+        self.0.borrow().message.clone()
+    }
     // max : max time between two messages
     // min : min time between two messages
     pub fn set_interval(&self, min: Duration, max: Duration) {
         assert!(min.as_micros() <= max.as_micros());
         self.0.borrow_mut().interval = (min, max);
     }
+    /// Transmits only the changes of the entries, every `keyframe_interval`-th
+    /// message contains all values (see `StreamEncoding::Delta`).
+    pub fn set_delta_encoding(&self, keyframe_interval: u32) {
+        assert!(keyframe_interval > 0, "the keyframe interval has to be at least 1");
+        self.0.borrow_mut().encoding = StreamEncoding::Delta { keyframe_interval };
+    }
     pub fn hide(&self) {
         let mut stream_data = self.0.borrow_mut();
         stream_data.visbility = Visibility::Static;
@@ -74,30 +95,132 @@ impl StreamBuilder {
         stream_data.description = Some(description.to_owned());
     }
     pub fn add_entry(&self, name: &str) {
+        self.add_entry_with(name, None);
+    }
+    /// Maps the object entry into the stream encoded as the primitive
+    /// `signal_type` (e.g. a f32 entry as "d16<0..100>"). The signal has to
+    /// cover the range and resolution of the entry, which is checked while
+    /// building, the conversion is available as `StreamMapping::conversion`.
+    pub fn add_entry_as(&self, name: &str, signal_type: &str) {
+        self.add_entry_with(name, Some(signal_type));
+    }
+    fn add_entry_with(&self, name: &str, signal_type: Option<&str>) {
         let mut stream_data = self.0.borrow_mut();
         // CHECK if entry already exists
-        if stream_data.object_entries.iter().any(|oe| &oe.0.borrow().name == name) {
+        if stream_data.position_of(name).is_some() {
             // Skip if the object entry is already mapped!
             return;
         }
-        let node = stream_data.tx_node.clone();
-        let node_data = node.0.borrow();
-        let oe = match node_data
+        let oe = stream_data.find_object_entry(name);
+        stream_data.object_entries.push(Some(oe.clone()));
+        stream_data.signal_types.push(signal_type.map(str::to_owned));
+        let oe_data = oe.0.borrow();
+        stream_data
+            .format
+            .add_type(signal_type.unwrap_or(&oe_data.ty), &oe_data.name);
+    }
+    /// Reserves a slot of type `ty` in the stream message, which isn't
+    /// mapped to any object entry, returns the index of the slot.
+    /// The slot can be filled later with `map_at`.
+    pub fn skip_slot(&self, ty: &str) -> usize {
+        let mut stream_data = self.0.borrow_mut();
+        let index = stream_data.object_entries.len();
+        stream_data.object_entries.push(None);
+        stream_data.signal_types.push(None);
+        stream_data.format.add_type(ty, &format!("reserved_{index}"));
+        index
+    }
+    /// Maps the object entry to the slot at `index`, which has to be
+    /// a skipped slot or the next free slot of the stream. A skipped slot
+    /// keeps its layout, so the entry has to have the type of the slot.
+    pub fn map_at(&self, index: usize, name: &str) -> errors::Result<()> {
+        let mut stream_data = self.0.borrow_mut();
+        match stream_data.position_of(name) {
+            Some(position) if position == index => return Ok(()),
+            Some(position) => {
+                return Err(ConfigError::InvalidStreamMapping(format!(
+                    "object entry {name} is already mapped to slot {position} of stream {}",
+                    stream_data.name
+                )))
+            }
+            None => (),
+        }
+        if !stream_data
+            .tx_node
+            .0
+            .borrow()
             .object_entries
             .iter()
-            .find(|oe| oe.0.borrow().name == name)
-            .cloned()
+            .any(|oe| oe.0.borrow().name == name)
         {
-            Some(oe) => oe,
-            None => {
-                let node_name = &node_data.name;
-                panic!("Failed to create stream entry. Object entry {node_name}:{name} does not exist");
-            }
-        };
-        // .unwrap_or_else(|| node.create_object_entry(name, "u1"));
-        stream_data.object_entries.push(oe.clone());
+            return Err(ConfigError::StreamEntryMissing(format!(
+                "object entry {name} of stream {} does not exist",
+                stream_data.name
+            )));
+        }
+        let oe = stream_data.find_object_entry(name);
+        let slot_count = stream_data.object_entries.len();
+        if index == slot_count {
+            drop(stream_data);
+            self.add_entry(name);
+            return Ok(());
+        }
+        if index > slot_count {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "slot {index} of stream {} doesn't exist, skip the slots in front of it first",
+                stream_data.name
+            )));
+        }
+        if let Some(mapped) = &stream_data.object_entries[index] {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "slot {index} of stream {} is already mapped to {}",
+                stream_data.name,
+                mapped.0.borrow().name
+            )));
+        }
         let oe_data = oe.0.borrow();
-        stream_data.format.add_type(&oe_data.ty, &oe_data.name);
+        let mut format = stream_data.format.0.borrow_mut();
+        let slot_type = &format.0[index].0;
+        if *slot_type != oe_data.ty {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "object entry {name} of type {} can't be mapped to slot {index} of stream {}, which is reserved for {slot_type}",
+                oe_data.ty, stream_data.name
+            )));
+        }
+        format.0[index].1 = oe_data.name.clone();
+        drop(format);
+        stream_data.object_entries[index] = Some(oe.clone());
+        Ok(())
+    }
+    /// Conversions of the object entries, which are encoded
+    /// with a different signal type (see `add_entry_as`).
+    pub(crate) fn conversions(&self) -> errors::Result<Vec<Option<SignalConversion>>> {
+        let stream_data = self.0.borrow();
+        stream_data
+            .object_entries
+            .iter()
+            .zip(&stream_data.signal_types)
+            .map(|(oe, signal_type)| {
+                let (Some(oe), Some(signal_type)) = (oe, signal_type) else {
+                    return Ok(None);
+                };
+                let oe_data = oe.0.borrow();
+                let oe_type = parse_signal_type(&oe_data.ty).map_err(|_| {
+                    ConfigError::InvalidStreamMapping(format!(
+                        "object entry {} of type {} can't be encoded as {signal_type}, only primitive types can be converted",
+                        oe_data.name, oe_data.ty
+                    ))
+                })?;
+                SignalConversion::new(oe_type, parse_signal_type(signal_type)?)
+                    .map(Some)
+                    .map_err(|err| match err {
+                        ConfigError::InvalidStreamMapping(msg) => ConfigError::InvalidStreamMapping(
+                            format!("object entry {} of stream {}: {msg}", oe_data.name, stream_data.name),
+                        ),
+                        err => err,
+                    })
+            })
+            .collect()
     }
     pub fn set_priority(&self, priority: MessagePriority) {
         self.0.borrow().message.set_any_std_id(priority);
@@ -107,10 +230,32 @@ impl StreamBuilder {
     }
 }
 
+impl StreamData {
+    fn position_of(&self, name: &str) -> Option<usize> {
+        self.object_entries
+            .iter()
+            .position(|oe| oe.as_ref().is_some_and(|oe| oe.0.borrow().name == name))
+    }
+    fn find_object_entry(&self, name: &str) -> ObjectEntryBuilder {
+        let node_data = self.tx_node.0.borrow();
+        match node_data
+            .object_entries
+            .iter()
+            .find(|oe| oe.0.borrow().name == name)
+            .cloned()
+        {
+            Some(oe) => oe,
+            None => {
+                let node_name = &node_data.name;
+                panic!("Failed to create stream entry. Object entry {node_name}:{name} does not exist");
+            }
+        }
+    }
+}
+
 impl ReceiveStreamBuilder {
     pub fn new(stream_builder: StreamBuilder, rx_node: NodeBuilder) -> ReceiveStreamBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating (rx)-Stream {}::{}", rx_node.0.borrow().name, stream_builder.0.borrow().name);
+        log::trace!("Creating (rx)-Stream {}::{}", rx_node.0.borrow().name, stream_builder.0.borrow().name);
         let rx_node_name = rx_node.0.borrow().name.clone();
         drop(rx_node_name);
         ReceiveStreamBuilder(make_builder_ref(ReceiveStreamData {
@@ -133,6 +278,7 @@ impl ReceiveStreamBuilder {
             .borrow()
             .object_entries
             .iter()
+            .flatten()
             .find(|oe| oe.0.borrow().name == from)
             .cloned();
         let rx_oe = self
@@ -177,12 +323,7 @@ impl ReceiveStreamBuilder {
             }
         };
         let tx_oe_name = tx_oe.0.borrow().name.clone();
-        let tx_oe_map_position = tx_stream_builder
-            .0
-            .borrow()
-            .object_entries
-            .iter()
-            .position(|oe| &oe.0.borrow().name == &tx_oe_name);
+        let tx_oe_map_position = tx_stream_builder.0.borrow().position_of(&tx_oe_name);
         let tx_oe_map_position = match tx_oe_map_position {
             Some(pos) => pos,
             None => {
@@ -191,9 +332,8 @@ impl ReceiveStreamBuilder {
                 tx_stream_builder
                     .0
                     .borrow()
-                    .object_entries
-                    .iter()
-                    .position(|oe| &oe.0.borrow().name == &tx_oe_name).expect("Mapping should really really exist by now xD.")
+                    .position_of(&tx_oe_name)
+                    .expect("Mapping should really really exist by now xD.")
             }
         };
         self.0
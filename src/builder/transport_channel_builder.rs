@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use crate::config::Visibility;
+
+use super::{make_builder_ref, BuilderRef, MessageBuilder, MessagePriority, NodeBuilder};
+
+#[derive(Debug, Clone)]
+pub struct TransportChannelBuilder(pub BuilderRef<TransportChannelData>);
+#[derive(Debug)]
+pub struct TransportChannelData {
+    pub name: String,
+    pub description: Option<String>,
+    pub tx_node: NodeBuilder,
+    pub rx_node: NodeBuilder,
+    /// segmented frames from tx_node to rx_node.
+    pub data_message: MessageBuilder,
+    /// flow control frames from rx_node to tx_node.
+    pub flow_control_message: MessageBuilder,
+    pub block_size: u8,
+    pub st_min: Duration,
+    pub visibility: Visibility,
+    pub expected_interval: Duration,
+}
+
+impl TransportChannelBuilder {
+    pub fn new(name: &str, tx_node_builder: &NodeBuilder, rx_node_builder: &NodeBuilder) -> TransportChannelBuilder {
+        log::trace!("Creating transport channel {name}");
+        let node_data = tx_node_builder.0.borrow();
+        let network_builder = &node_data.network_builder;
+        let data_message = network_builder.create_message(
+            &format!("{}_{}_transport_data", node_data.name, name),
+            None,
+        );
+        data_message.hide();
+        data_message.set_any_std_id(MessagePriority::Low);
+        data_message.make_type_format().add_type("u64", "frame");
+
+        let flow_control_message = network_builder.create_message(
+            &format!("{}_{}_transport_fc", node_data.name, name),
+            None,
+        );
+        flow_control_message.hide();
+        flow_control_message.set_any_std_id(MessagePriority::Low);
+        flow_control_message.make_type_format().add_type("u64", "frame");
+
+        let new = TransportChannelBuilder(make_builder_ref(TransportChannelData {
+            name: name.to_owned(),
+            description: None,
+            tx_node: tx_node_builder.clone(),
+            rx_node: rx_node_builder.clone(),
+            data_message: data_message.clone(),
+            flow_control_message: flow_control_message.clone(),
+            block_size: 0,
+            st_min: Duration::ZERO,
+            visibility: Visibility::Global,
+            expected_interval: Duration::from_millis(1000),
+        }));
+        data_message.assign_to_transport_channel(&new);
+        flow_control_message.assign_to_transport_channel(&new);
+        new
+    }
+    pub fn add_description(&self, description: &str) {
+        let mut channel_data = self.0.borrow_mut();
+        channel_data.description = Some(description.to_owned());
+    }
+    pub fn hide(&self) {
+        let mut channel_data = self.0.borrow_mut();
+        channel_data.visibility = Visibility::Static;
+    }
+    pub fn set_priority(&self, priority: MessagePriority) {
+        let channel_data = self.0.borrow();
+        channel_data.data_message.set_any_std_id(priority);
+        channel_data.flow_control_message.set_any_std_id(priority);
+    }
+    /// Number of consecutive frames sent before waiting for the next
+    /// flow control frame (0 = no limit).
+    pub fn set_block_size(&self, block_size: u8) {
+        let mut channel_data = self.0.borrow_mut();
+        channel_data.block_size = block_size;
+    }
+    /// Minimum separation time between consecutive frames,
+    /// either 100us - 900us in steps of 100us or 0ms - 127ms in whole
+    /// milliseconds, other values are rejected by the build.
+    pub fn set_st_min(&self, st_min: Duration) {
+        let mut channel_data = self.0.borrow_mut();
+        channel_data.st_min = st_min;
+    }
+    pub fn expected_interval(&self, interval: Duration) {
+        self.0.borrow_mut().expected_interval = interval;
+    }
+}
@@ -0,0 +1,71 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use crate::config::{ConfigRef, TypeRef};
+
+/// Handle of a type interned in a `TypeArena`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct TypeId(u32);
+
+/// Interns the types of a build.
+///
+/// Structurally equal types share the ref, which was interned first, so
+/// equal interned types have the same id and comparing them doesn't require
+/// a recursive comparison. Types are found by their hash and the address of
+/// their ref, only types with the same hash are compared structurally.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TypeArena {
+    types: Vec<TypeRef>,
+    by_hash: HashMap<u64, Vec<TypeId>>,
+    // the arena keeps the interned refs alive, so their addresses are unique.
+    by_address: HashMap<usize, TypeId>,
+}
+
+fn address(ty: &TypeRef) -> usize {
+    ConfigRef::as_ptr(ty) as usize
+}
+
+impl TypeArena {
+    /// Returns the id and the shared ref of the type.
+    pub(crate) fn intern(&mut self, ty: TypeRef) -> (TypeId, TypeRef) {
+        if let Some(id) = self.by_address.get(&address(&ty)) {
+            return (*id, ty);
+        }
+        let mut hasher = DefaultHasher::new();
+        ty.hash(&mut hasher);
+        let bucket = self.by_hash.entry(hasher.finish()).or_default();
+        if let Some(id) = bucket.iter().find(|id| *self.types[id.0 as usize] == *ty) {
+            return (*id, self.types[id.0 as usize].clone());
+        }
+        let id = TypeId(self.types.len() as u32);
+        bucket.push(id);
+        self.by_address.insert(address(&ty), id);
+        self.types.push(ty.clone());
+        (id, ty)
+    }
+}
+
+/// Set of interned types in insertion order.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TypeSet {
+    ids: HashSet<TypeId>,
+    types: Vec<TypeRef>,
+}
+
+impl TypeSet {
+    /// Returns false if an equal type is already in the set.
+    pub(crate) fn insert(&mut self, arena: &mut TypeArena, ty: &TypeRef) -> bool {
+        let (id, ty) = arena.intern(ty.clone());
+        if !self.ids.insert(id) {
+            return false;
+        }
+        self.types.push(ty);
+        true
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<TypeRef> {
+        self.types
+    }
+}
@@ -1,6 +1,8 @@
-use crate::{config::Visibility, errors};
+use std::rc::Rc;
 
-use super::{make_builder_ref, BuilderRef};
+use crate::{config::{AttributePadding, Visibility}, errors};
+
+use super::{make_builder_ref, BuilderRef, DirtyFlag};
 
 
 
@@ -11,6 +13,9 @@ pub struct EnumData {
     pub name: String,
     pub description: Option<String>,
     pub entries: Vec<(String, Option<u64>)>,
+    /// (entry name, description)
+    pub entry_descriptions: Vec<(String, String)>,
+    pub deprecated_entries: Vec<String>,
     pub visibility: Visibility,
 }
 
@@ -21,6 +26,22 @@ pub struct StructData {
     pub name: String,
     pub description: Option<String>,
     pub attributes: Vec<(String, String)>,
+    /// (attribute name, description)
+    pub attribute_descriptions: Vec<(String, String)>,
+    pub attribute_padding: Vec<(String, AttributePadding)>,
+    pub visibility: Visibility,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnionBuilder(pub BuilderRef<UnionData>);
+#[derive(Debug)]
+pub struct UnionData {
+    pub name: String,
+    pub description: Option<String>,
+    /// name of the enum selecting the variant.
+    pub tag: Option<String>,
+    /// (tag entry, payload type name)
+    pub variants: Vec<(String, String)>,
     pub visibility: Visibility,
 }
 
@@ -28,16 +49,18 @@ pub struct StructData {
 pub enum TypeBuilder {
     Enum(EnumBuilder),
     Struct(StructBuilder),
+    Union(UnionBuilder),
 }
 
 impl EnumBuilder {
     pub fn new(name: &str) -> EnumBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating enum {name}");
+        log::trace!("Creating enum {name}");
         EnumBuilder(make_builder_ref(EnumData {
             name: name.to_owned(),
             description: None,
             entries: vec![],
+            entry_descriptions: vec![],
+            deprecated_entries: vec![],
             visibility: Visibility::Global,
         }))
     }
@@ -53,6 +76,36 @@ impl EnumBuilder {
         enum_data.entries.push((name.to_owned(), value));
         Ok(())
     }
+    /// Describes what the state of an entry means, documented
+    /// in the generated code.
+    pub fn add_entry_description(&self, entry: &str, description: &str) -> errors::Result<()> {
+        let mut enum_data = self.0.borrow_mut();
+        if !enum_data.entries.iter().any(|a| a.0 == entry) {
+            return Err(errors::ConfigError::UndefinedEnumEntry(format!(
+                "{}::{entry}",
+                enum_data.name
+            )));
+        }
+        enum_data.entry_descriptions.retain(|(name, _)| name != entry);
+        enum_data
+            .entry_descriptions
+            .push((entry.to_owned(), description.to_owned()));
+        Ok(())
+    }
+    /// Marks an entry as deprecated, it keeps its value for compatibility.
+    pub fn deprecate_entry(&self, entry: &str) -> errors::Result<()> {
+        let mut enum_data = self.0.borrow_mut();
+        if !enum_data.entries.iter().any(|a| a.0 == entry) {
+            return Err(errors::ConfigError::UndefinedEnumEntry(format!(
+                "{}::{entry}",
+                enum_data.name
+            )));
+        }
+        if !enum_data.deprecated_entries.iter().any(|name| name == entry) {
+            enum_data.deprecated_entries.push(entry.to_owned());
+        }
+        Ok(())
+    }
     pub fn hide(&self) {
         let mut enum_data = self.0.borrow_mut();
         enum_data.visibility = Visibility::Static;
@@ -61,12 +114,13 @@ impl EnumBuilder {
 
 impl StructBuilder {
     pub fn new(name: &str) -> StructBuilder {
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating struct {name}");
+        log::trace!("Creating struct {name}");
         StructBuilder(make_builder_ref(StructData {
             name: name.to_owned(),
             description: None,
             attributes: vec![],
+            attribute_descriptions: vec![],
+            attribute_padding: vec![],
             visibility: Visibility::Global,
         }))
     }
@@ -86,17 +140,120 @@ impl StructBuilder {
             .push((name.to_owned(), ty.to_owned()));
         Ok(())
     }
+    /// Adds a documented attribute, the padding inserts unused bits in front
+    /// of it when the struct is encoded into a message, e.g.
+    /// AttributePadding::Align(8) starts the attribute at a byte boundary.
+    pub fn add_attribute_with(
+        &self,
+        name: &str,
+        ty: &str,
+        description: Option<&str>,
+        padding: AttributePadding,
+    ) -> errors::Result<()> {
+        if padding == AttributePadding::Align(0) {
+            return Err(errors::ConfigError::InvalidRange(format!(
+                "attribute {name} of struct {} can't be aligned to 0 bits",
+                self.0.borrow().name
+            )));
+        }
+        self.add_attribute(name, ty)?;
+        let mut struct_data = self.0.borrow_mut();
+        if let Some(description) = description {
+            struct_data
+                .attribute_descriptions
+                .push((name.to_owned(), description.to_owned()));
+        }
+        if padding != AttributePadding::None {
+            struct_data.attribute_padding.push((name.to_owned(), padding));
+        }
+        Ok(())
+    }
     pub fn hide(&self) {
         let mut struct_data = self.0.borrow_mut();
         struct_data.visibility = Visibility::Static;
     }
 }
 
+impl UnionBuilder {
+    pub fn new(name: &str) -> UnionBuilder {
+        log::trace!("Creating union {name}");
+        UnionBuilder(make_builder_ref(UnionData {
+            name: name.to_owned(),
+            description: None,
+            tag: None,
+            variants: vec![],
+            visibility: Visibility::Global,
+        }))
+    }
+    pub fn add_description(&self, description: &str) {
+        let mut union_data = self.0.borrow_mut();
+        union_data.description = Some(description.to_owned());
+    }
+    /// Enum whose value selects the variant, it's encoded in front of the payload.
+    pub fn set_tag(&self, enum_name: &str) {
+        let mut union_data = self.0.borrow_mut();
+        union_data.tag = Some(enum_name.to_owned());
+    }
+    /// Payload of the union if the tag has the value of the entry {tag_entry}.
+    /// Entries of the tag without a variant don't carry a payload.
+    pub fn add_variant(&self, tag_entry: &str, ty: &str) -> errors::Result<()> {
+        let mut union_data = self.0.borrow_mut();
+        if union_data.variants.iter().any(|v| v.0 == tag_entry) {
+            return Err(errors::ConfigError::InvalidType(format!(
+                "union {} defines the variant {tag_entry} twice",
+                union_data.name
+            )));
+        }
+        union_data
+            .variants
+            .push((tag_entry.to_owned(), ty.to_owned()));
+        Ok(())
+    }
+    pub fn hide(&self) {
+        let mut union_data = self.0.borrow_mut();
+        union_data.visibility = Visibility::Static;
+    }
+}
+
 impl TypeBuilder {
     pub fn name(&self) -> String {
         match &self {
             TypeBuilder::Enum(enum_builder) => enum_builder.0.borrow().name.clone(),
             TypeBuilder::Struct(struct_builder) => struct_builder.0.borrow().name.clone(),
+            TypeBuilder::Union(union_builder) => union_builder.0.borrow().name.clone(),
+        }
+    }
+    pub(crate) fn dirty_flag(&self) -> Rc<dyn DirtyFlag> {
+        match &self {
+            TypeBuilder::Enum(enum_builder) => enum_builder.0.clone(),
+            TypeBuilder::Struct(struct_builder) => struct_builder.0.clone(),
+            TypeBuilder::Union(union_builder) => union_builder.0.clone(),
+        }
+    }
+    /// Modified since the last build of the network.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_flag().is_dirty()
+    }
+    /// names of the types the type is composed of.
+    pub(crate) fn referenced_types(&self) -> Vec<String> {
+        match &self {
+            TypeBuilder::Enum(_) => vec![],
+            TypeBuilder::Struct(struct_builder) => struct_builder
+                .0
+                .borrow()
+                .attributes
+                .iter()
+                .map(|(_, ty)| ty.clone())
+                .collect(),
+            TypeBuilder::Union(union_builder) => {
+                let union_data = union_builder.0.borrow();
+                union_data
+                    .tag
+                    .iter()
+                    .cloned()
+                    .chain(union_data.variants.iter().map(|(_, ty)| ty.clone()))
+                    .collect()
+            }
         }
     }
 }
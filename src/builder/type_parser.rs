@@ -0,0 +1,171 @@
+use std::{
+    cell::{RefCell, RefMut},
+    collections::HashMap,
+};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::{
+    config::{make_config_ref, SignalType, Type, TypeRef},
+    errors,
+};
+
+use super::type_arena::TypeArena;
+
+/// Decimal number, optionally in scientific notation (e.g. `-1.5`, `.5`, `1e-3`).
+const NUMBER: &str = r"[+-]?(?:[0-9]+(?:\.[0-9]*)?|\.[0-9]+)(?:[eE][+-]?[0-9]+)?";
+
+static INT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?<sign>[iu])(?<size>[0-9]+)$").unwrap());
+static DEC_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^d(?<size>[0-9]+)<(?<min>{NUMBER})\.\.(?<max>{NUMBER})>$"
+    ))
+    .unwrap()
+});
+static ARRAY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^(?<type>[a-zA-Z][a-zA-Z0-9]*(<{NUMBER}\.\.{NUMBER}>)?)\[(?<len>[0-9]+)\]$"
+    ))
+    .unwrap()
+});
+
+/// Parses a primitive type name (`u8`, `i16`, `d10<-1.5..1e3>`).
+///
+/// Decimals are encoded as unsigned integers of the given size,
+/// which are mapped linearly onto the range `min..max`.
+pub fn parse_signal_type(type_name: &str) -> errors::Result<SignalType> {
+    try_parse_signal_type(type_name).unwrap_or_else(|| {
+        Err(errors::ConfigError::InvalidType(format!(
+            "{type_name:?} is not a primitive type"
+        )))
+    })
+}
+
+/// Returns `None` if the name doesn't look like a primitive type,
+/// so that it can still be resolved as a user defined type.
+pub(crate) fn try_parse_signal_type(type_name: &str) -> Option<errors::Result<SignalType>> {
+    if let Some(cap) = INT_REGEX.captures(type_name) {
+        let size = match parse_size(type_name, &cap["size"]) {
+            Ok(size) => size,
+            Err(err) => return Some(Err(err)),
+        };
+        return Some(Ok(match &cap["sign"] {
+            "i" => SignalType::SignedInt { size },
+            _ => SignalType::UnsignedInt { size },
+        }));
+    }
+    let cap = DEC_REGEX.captures(type_name)?;
+    Some(parse_decimal(type_name, &cap["size"], &cap["min"], &cap["max"]))
+}
+
+fn parse_size(type_name: &str, size: &str) -> errors::Result<u8> {
+    match size.parse::<u8>() {
+        Ok(size) if (1..=64).contains(&size) => Ok(size),
+        _ => Err(errors::ConfigError::InvalidType(format!(
+            "invalid size of {type_name:?}, primitive types have between 1 and 64 bits"
+        ))),
+    }
+}
+
+fn parse_decimal(type_name: &str, size: &str, min: &str, max: &str) -> errors::Result<SignalType> {
+    let size = parse_size(type_name, size)
+        .map_err(|_| errors::ConfigError::InvalidDecimalDefinition(format!(
+            "invalid size of {type_name:?}, decimals have between 1 and 64 bits"
+        )))?;
+    let parse_bound = |bound: &str| match bound.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(errors::ConfigError::InvalidDecimalDefinition(format!(
+            "bound {bound} of {type_name:?} is not a finite number"
+        ))),
+    };
+    let min = parse_bound(min)?;
+    let max = parse_bound(max)?;
+    if min >= max {
+        return Err(errors::ConfigError::InvalidRange(format!(
+            "invalid decimal range of {type_name:?}, min has to be less than max"
+        )));
+    }
+    // size is within 1..=64, so the shift can't overflow.
+    let max_raw = u64::MAX >> (64 - size as u32);
+    let scale = (max - min) / max_raw as f64;
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(errors::ConfigError::InvalidDecimalDefinition(format!(
+            "range of {type_name:?} can't be represented with {size} bits"
+        )));
+    }
+    Ok(SignalType::Decimal {
+        size,
+        offset: min,
+        scale,
+    })
+}
+
+/// Resolves primitive and array type names, returns `None` for any other name.
+/// The element type of arrays is resolved with `resolve_element`.
+pub(crate) fn resolve_builtin_type(
+    type_name: &str,
+    resolve_element: impl FnOnce(&str) -> errors::Result<TypeRef>,
+) -> Option<errors::Result<TypeRef>> {
+    if let Some(signal_type) = try_parse_signal_type(type_name) {
+        return Some(signal_type.map(|signal_type| make_config_ref(Type::Primitive(signal_type))));
+    }
+    let cap = ARRAY_REGEX.captures(type_name)?;
+    let len = match cap["len"].parse::<usize>() {
+        Ok(len) => len,
+        Err(_) => {
+            return Some(Err(errors::ConfigError::InvalidType(format!(
+                "invalid array length of {type_name:?}"
+            ))))
+        }
+    };
+    Some(resolve_element(&cap["type"]).map(|ty| make_config_ref(Type::Array { len, ty })))
+}
+
+/// Checks if the type name is an array type (e.g. `u8[4]`).
+pub(crate) fn is_array_type(type_name: &str) -> bool {
+    ARRAY_REGEX.is_match(type_name)
+}
+
+/// Resolves type names against the defined types while building.
+///
+/// Every resolved name is cached, so the same primitive or array type
+/// is only parsed once and defined types are found in constant time.
+/// All resolved types are interned, equal types resolve to the same ref.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TypeResolver {
+    cache: RefCell<HashMap<String, TypeRef>>,
+    arena: RefCell<TypeArena>,
+}
+
+impl TypeResolver {
+    /// Makes a struct or enum resolvable by its name,
+    /// returns the interned ref of the type.
+    pub(crate) fn define(&self, ty: TypeRef) -> TypeRef {
+        let (_, ty) = self.arena.borrow_mut().intern(ty);
+        self.cache.borrow_mut().insert(ty.name(), ty.clone());
+        ty
+    }
+    pub(crate) fn resolve(&self, type_name: &str) -> errors::Result<TypeRef> {
+        if let Some(ty) = self.cache.borrow().get(type_name) {
+            return Ok(ty.clone());
+        }
+        let ty = resolve_builtin_type(type_name, |element| self.resolve(element)).unwrap_or_else(
+            || {
+                Err(errors::ConfigError::InvalidType(format!(
+                    "failed to resolve type : {type_name:?}"
+                )))
+            },
+        )?;
+        let (_, ty) = self.arena.borrow_mut().intern(ty);
+        self.cache
+            .borrow_mut()
+            .insert(type_name.to_owned(), ty.clone());
+        Ok(ty)
+    }
+    /// The arena of the resolved types.
+    pub(crate) fn arena(&self) -> RefMut<'_, TypeArena> {
+        self.arena.borrow_mut()
+    }
+}
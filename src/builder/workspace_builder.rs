@@ -0,0 +1,150 @@
+use std::rc::Rc;
+
+use crate::{
+    config::{make_config_ref, NetworkRef, Workspace, WorkspaceRef},
+    errors::{ConfigError, Result},
+};
+
+use super::{make_builder_ref, BuilderRef, NetworkBuilder};
+
+/// Types (e.g. units or states), which are defined in every network of a workspace.
+#[derive(Clone)]
+pub struct TypeLibrary(Rc<dyn Fn(&NetworkBuilder)>);
+
+impl std::fmt::Debug for TypeLibrary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TypeLibrary")
+    }
+}
+
+impl TypeLibrary {
+    pub fn new(definition: impl Fn(&NetworkBuilder) + 'static) -> TypeLibrary {
+        TypeLibrary(Rc::new(definition))
+    }
+}
+
+/// Several named networks (e.g. vehicle, test bench and charger CAN),
+/// which share type libraries. Nodes, which are part of multiple networks,
+/// have to define the same object dictionary in all of them.
+#[derive(Debug, Clone)]
+pub struct WorkspaceBuilder(pub BuilderRef<WorkspaceData>);
+#[derive(Debug)]
+pub struct WorkspaceData {
+    pub networks: Vec<(String, NetworkBuilder)>,
+    pub type_libraries: Vec<TypeLibrary>,
+}
+
+impl WorkspaceBuilder {
+    pub fn new() -> WorkspaceBuilder {
+        WorkspaceBuilder(make_builder_ref(WorkspaceData {
+            networks: vec![],
+            type_libraries: vec![],
+        }))
+    }
+    /// Returns the network with the name, creates it if it doesn't exist yet.
+    /// New networks contain the types of all type libraries.
+    pub fn create_network(&self, name: &str) -> NetworkBuilder {
+        if let Some(network_builder) = self.find_network(name) {
+            return network_builder;
+        }
+        let network_builder = NetworkBuilder::new();
+        for library in &self.0.borrow().type_libraries {
+            (library.0)(&network_builder);
+        }
+        self.0
+            .borrow_mut()
+            .networks
+            .push((name.to_owned(), network_builder.clone()));
+        network_builder
+    }
+    pub fn find_network(&self, name: &str) -> Option<NetworkBuilder> {
+        self.0
+            .borrow()
+            .networks
+            .iter()
+            .find(|(network_name, _)| network_name == name)
+            .map(|(_, network_builder)| network_builder.clone())
+    }
+    /// Defines the types of the library in every network of the workspace.
+    pub fn add_type_library(&self, library: TypeLibrary) {
+        for (_, network_builder) in &self.0.borrow().networks {
+            (library.0)(network_builder);
+        }
+        self.0.borrow_mut().type_libraries.push(library);
+    }
+    /// Builds every network and checks the nodes, which are shared by networks.
+    pub fn build(&self) -> Result<WorkspaceRef> {
+        let networks = self
+            .0
+            .borrow()
+            .networks
+            .iter()
+            .map(|(name, network_builder)| Ok((name.clone(), network_builder.clone().build()?)))
+            .collect::<Result<Vec<(String, NetworkRef)>>>()?;
+        check_shared_nodes(&networks)?;
+        Ok(make_config_ref(Workspace::new(networks)))
+    }
+}
+
+impl Default for WorkspaceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Nodes with the same name have to define the same object entries
+/// (name, index, type and access) in every network.
+fn check_shared_nodes(networks: &[(String, NetworkRef)]) -> Result<()> {
+    for (i, (a_name, a)) in networks.iter().enumerate() {
+        for (b_name, b) in &networks[i + 1..] {
+            for a_node in a.nodes() {
+                let Some(b_node) = b.nodes().iter().find(|n| n.name() == a_node.name()) else {
+                    continue;
+                };
+                let inconsistent = |reason: String| {
+                    Err(ConfigError::InvalidWorkspace(format!(
+                        "node {} is part of {a_name} and {b_name}, but {reason}",
+                        a_node.name()
+                    )))
+                };
+                for a_oe in a_node.object_entries() {
+                    let Some(b_oe) = b_node.object_entries().iter().find(|oe| oe.name() == a_oe.name()) else {
+                        return inconsistent(format!("object entry {} is missing in {b_name}", a_oe.name()));
+                    };
+                    if a_oe.id() != b_oe.id() {
+                        return inconsistent(format!(
+                            "object entry {} has the index {} in {a_name} and {} in {b_name}",
+                            a_oe.name(),
+                            a_oe.id(),
+                            b_oe.id()
+                        ));
+                    }
+                    if a_oe.ty() != b_oe.ty() {
+                        return inconsistent(format!(
+                            "object entry {} has the type {} in {a_name} and {} in {b_name}",
+                            a_oe.name(),
+                            a_oe.ty().name(),
+                            b_oe.ty().name()
+                        ));
+                    }
+                    if a_oe.access() != b_oe.access() {
+                        return inconsistent(format!(
+                            "object entry {} has the access {:?} in {a_name} and {:?} in {b_name}",
+                            a_oe.name(),
+                            a_oe.access(),
+                            b_oe.access()
+                        ));
+                    }
+                }
+                if let Some(b_oe) = b_node
+                    .object_entries()
+                    .iter()
+                    .find(|b_oe| !a_node.object_entries().iter().any(|oe| oe.name() == b_oe.name()))
+                {
+                    return inconsistent(format!("object entry {} is missing in {a_name}", b_oe.name()));
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,256 @@
+//! Command line interface, which builds a network, runs the lints
+//! and writes the selected artifacts.
+//!
+//! ```text
+//! canzero-config [<network.json|network.yaml>] [--deny-warnings] [--verbose] [--emit <artifact>[=<path>]]...
+//! ```
+//!
+//! Artifacts are written to stdout if no path is given. Warnings of the build
+//! are logged to stderr, `--verbose` additionally logs the build phases. Projects, which
+//! define their network in Rust, call `main_with` with their build function
+//! instead of loading a JSON or YAML definition.
+
+use std::{fs, process::ExitCode};
+
+use crate::{
+    analysis::lint::LintOptions,
+    builder::NetworkBuilder,
+    codegen::ConstsLanguage,
+    config::Network,
+    errors::Result,
+};
+
+const USAGE: &str = "usage: canzero-config [<network.json|network.yaml>] [--deny-warnings] [--verbose] [--emit <artifact>[=<path>]]...
+artifacts: dbc, kcd, sym, arxml, c, cpp, python, rust-consts, c-consts, dot, docs, canonical, hash";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+    Dbc,
+    Kcd,
+    Sym,
+    Arxml,
+    /// header-only C library, see `codegen::c`.
+    C,
+    Cpp,
+    Python,
+    /// constants of the network, see `codegen::consts`.
+    RustConsts,
+    CConsts,
+    Dot,
+    /// markdown reference of the network, see `export::docs`.
+    Docs,
+    Canonical,
+    /// config hash of the network in hex.
+    Hash,
+}
+
+impl Artifact {
+    pub const ALL: [Artifact; 13] = [
+        Artifact::Dbc,
+        Artifact::Kcd,
+        Artifact::Sym,
+        Artifact::Arxml,
+        Artifact::C,
+        Artifact::Cpp,
+        Artifact::Python,
+        Artifact::RustConsts,
+        Artifact::CConsts,
+        Artifact::Dot,
+        Artifact::Docs,
+        Artifact::Canonical,
+        Artifact::Hash,
+    ];
+    pub fn name(&self) -> &'static str {
+        match &self {
+            Artifact::Dbc => "dbc",
+            Artifact::Kcd => "kcd",
+            Artifact::Sym => "sym",
+            Artifact::Arxml => "arxml",
+            Artifact::C => "c",
+            Artifact::Cpp => "cpp",
+            Artifact::Python => "python",
+            Artifact::RustConsts => "rust-consts",
+            Artifact::CConsts => "c-consts",
+            Artifact::Dot => "dot",
+            Artifact::Docs => "docs",
+            Artifact::Canonical => "canonical",
+            Artifact::Hash => "hash",
+        }
+    }
+    pub fn render(&self, network: &Network) -> String {
+        match &self {
+            Artifact::Dbc => network.to_dbc(),
+            Artifact::Kcd => network.to_kcd(),
+            Artifact::Sym => network.to_sym(),
+            Artifact::Arxml => network.to_arxml(),
+            Artifact::C => network.to_c(),
+            Artifact::Cpp => network.to_cpp(),
+            Artifact::Python => network.to_python(),
+            Artifact::RustConsts => network.to_consts(ConstsLanguage::Rust),
+            Artifact::CConsts => network.to_consts(ConstsLanguage::C),
+            Artifact::Dot => network.to_dot(),
+            Artifact::Docs => network.to_docs(),
+            Artifact::Canonical => network.to_canonical_string(),
+            Artifact::Hash => format!("{:016x}\n", network.config_hash()),
+        }
+    }
+}
+
+/// Parsed command line arguments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliOptions {
+    input: Option<String>,
+    deny_warnings: bool,
+    verbose: bool,
+    emit: Vec<(Artifact, Option<String>)>,
+}
+
+impl CliOptions {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> std::result::Result<Self, String> {
+        let mut options = CliOptions::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--deny-warnings" => options.deny_warnings = true,
+                "--verbose" => options.verbose = true,
+                "--emit" => {
+                    let Some(value) = args.next() else {
+                        return Err("--emit requires an artifact".to_owned());
+                    };
+                    let (name, path) = match value.split_once('=') {
+                        Some((name, path)) => (name, Some(path.to_owned())),
+                        None => (value.as_str(), None),
+                    };
+                    let Some(artifact) = Artifact::ALL.iter().find(|a| a.name() == name) else {
+                        return Err(format!("unknown artifact {name}"));
+                    };
+                    options.emit.push((*artifact, path));
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+                _ if options.input.is_none() => options.input = Some(arg),
+                _ => return Err(format!("unexpected argument {arg}")),
+            }
+        }
+        Ok(options)
+    }
+    /// path of the JSON or YAML network definition.
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+    /// fail on lint warnings, not only on lint errors.
+    pub fn deny_warnings(&self) -> bool {
+        self.deny_warnings
+    }
+    /// log the phases of the build, not only warnings.
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+    /// artifacts with the path they are written to, None for stdout.
+    pub fn emit(&self) -> &Vec<(Artifact, Option<String>)> {
+        &self.emit
+    }
+}
+
+/// Builds and lints the network and writes the artifacts.
+/// Returns false if the checks failed, in which case nothing is written.
+pub fn run(options: &CliOptions, network_builder: NetworkBuilder) -> Result<bool> {
+    let network = network_builder.build()?;
+    let report = network.lint(&LintOptions::default());
+    if !report.is_clean() {
+        eprint!("{report}");
+    }
+    if report.has_errors() || (options.deny_warnings && !report.is_clean()) {
+        return Ok(false);
+    }
+    for (artifact, path) in &options.emit {
+        let output = artifact.render(&network);
+        match path {
+            Some(path) => fs::write(path, output)?,
+            None => print!("{output}"),
+        }
+    }
+    Ok(true)
+}
+
+fn exit_code(options: &CliOptions, network_builder: Result<NetworkBuilder>) -> ExitCode {
+    match network_builder.and_then(|network_builder| run(options, network_builder)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}: {}", record.level().as_str().to_lowercase(), record.args());
+        }
+    }
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+fn parse_args() -> std::result::Result<CliOptions, ExitCode> {
+    let options = CliOptions::parse(std::env::args().skip(1)).map_err(|error| {
+        eprintln!("error: {error}\n{USAGE}");
+        ExitCode::from(2)
+    })?;
+    // fails if the binary already installed a logger, which is kept.
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(if options.verbose {
+            log::LevelFilter::Debug
+        } else {
+            log::LevelFilter::Warn
+        });
+    }
+    Ok(options)
+}
+
+/// Entry point of the `canzero-config` binary, which loads the network from a JSON definition
+/// or from a YAML definition if the file ends with `.yaml` or `.yml`.
+pub fn main() -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+    let Some(input) = options.input() else {
+        eprintln!("error: missing network definition\n{USAGE}");
+        return ExitCode::from(2);
+    };
+    let yaml = input.ends_with(".yaml") || input.ends_with(".yml");
+    let network_builder = fs::read_to_string(input)
+        .map_err(Into::into)
+        .and_then(|definition| match yaml {
+            true => NetworkBuilder::from_yaml(&definition),
+            false => NetworkBuilder::from_json(&definition),
+        });
+    exit_code(&options, network_builder)
+}
+
+/// Entry point for project binaries, which build the network in Rust.
+///
+/// ```ignore
+/// fn main() -> std::process::ExitCode {
+///     canzero_config::cli::main_with(build_network)
+/// }
+/// ```
+pub fn main_with(build: impl FnOnce() -> Result<NetworkBuilder>) -> ExitCode {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(code) => return code,
+    };
+    if options.input().is_some() {
+        eprintln!("error: the network is built by the binary\n{USAGE}");
+        return ExitCode::from(2);
+    }
+    exit_code(&options, build())
+}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use crate::{
+    builder::NetworkBuilder,
+    config::{MessageRef, Network, NodeRef, SignalRef, TypeRef},
+};
+
+/// Output buffer and read access to the network for a `CodegenBackend`.
+/// Backends write into the context with `write!` / `writeln!`.
+pub struct CodegenContext<'a> {
+    network: &'a Network,
+    output: String,
+}
+
+impl<'a> CodegenContext<'a> {
+    pub fn new(network: &'a Network) -> Self {
+        Self {
+            network,
+            output: String::new(),
+        }
+    }
+    pub fn network(&self) -> &'a Network {
+        self.network
+    }
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+    pub fn into_output(self) -> String {
+        self.output
+    }
+}
+
+impl Write for CodegenContext<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.output.write_str(s)
+    }
+}
+
+/// Visitor over a built network, see `generate` for the visiting order.
+/// All callbacks do nothing by default.
+pub trait CodegenBackend {
+    fn begin(&mut self, _ctx: &mut CodegenContext) {}
+    /// called for all types of the network in topological order,
+    /// a type is always visited after all types it depends on.
+    fn visit_type(&mut self, _ctx: &mut CodegenContext, _ty: &TypeRef) {}
+    fn visit_node(&mut self, _ctx: &mut CodegenContext, _node: &NodeRef) {}
+    fn visit_message(&mut self, _ctx: &mut CodegenContext, _message: &MessageRef) {}
+    /// called for all signals of a message after `visit_message`.
+    fn visit_signal(
+        &mut self,
+        _ctx: &mut CodegenContext,
+        _message: &MessageRef,
+        _signal: &SignalRef,
+    ) {
+    }
+    /// called after all signals of a message were visited.
+    fn end_message(&mut self, _ctx: &mut CodegenContext, _message: &MessageRef) {}
+    fn end(&mut self, _ctx: &mut CodegenContext) {}
+}
+
+/// Walks the network with the backend and returns the generated output.
+/// Visiting order: begin, types (topological order), nodes,
+/// messages (each followed by its signals and end_message), end.
+pub fn generate<B: CodegenBackend + ?Sized>(network: &Network, backend: &mut B) -> String {
+    let mut ctx = CodegenContext::new(network);
+    backend.begin(&mut ctx);
+    for ty in NetworkBuilder::topo_sort_types(network.types()) {
+        backend.visit_type(&mut ctx, &ty);
+    }
+    for node in network.nodes() {
+        backend.visit_node(&mut ctx, node);
+    }
+    for message in network.messages() {
+        backend.visit_message(&mut ctx, message);
+        for signal in message.signals() {
+            backend.visit_signal(&mut ctx, message, signal);
+        }
+        backend.end_message(&mut ctx, message);
+    }
+    backend.end(&mut ctx);
+    ctx.into_output()
+}
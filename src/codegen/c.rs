@@ -0,0 +1,311 @@
+use std::fmt::Write;
+
+use crate::config::{EnumEntryInfo, Message, MessageRef, Network, OverflowPolicy, Type, TypeRef};
+
+use super::{
+    backend::{generate, CodegenBackend, CodegenContext},
+    layout::{message_layout, SignalKind, SignalLayout},
+    upper_snake_case,
+};
+
+const PRELUDE: &str = r#"// Generated by canzero-config. Do not edit!
+#pragma once
+
+#include <math.h>
+#include <stdbool.h>
+#include <stdint.h>
+
+static inline uint64_t canzero_pack(uint64_t value, uint8_t offset, uint8_t size) {
+  const uint64_t mask = size == 64 ? ~(uint64_t)0 : ((uint64_t)1 << size) - 1;
+  return (value & mask) << offset;
+}
+
+static inline uint64_t canzero_unpack(uint64_t raw, uint8_t offset, uint8_t size) {
+  const uint64_t mask = size == 64 ? ~(uint64_t)0 : ((uint64_t)1 << size) - 1;
+  return (raw >> offset) & mask;
+}
+
+static inline int64_t canzero_sign_extend(uint64_t value, uint8_t size) {
+  const uint64_t sign = (uint64_t)1 << (size - 1);
+  return (int64_t)((value ^ sign) - sign);
+}
+
+static inline uint64_t canzero_to_fixed(double value, double offset, double scale, uint8_t size) {
+  const double max = size == 64 ? 18446744073709551615.0 : (double)(((uint64_t)1 << size) - 1);
+  const double raw = round((value - offset) / scale);
+  return (uint64_t)(raw < 0.0 ? 0.0 : (raw > max ? max : raw));
+}
+
+static inline uint64_t canzero_to_fixed_wrap(double value, double offset, double scale) {
+  return (uint64_t)(int64_t)round((value - offset) / scale);
+}
+
+static inline uint64_t canzero_saturate_unsigned(uint64_t value, uint64_t max) {
+  return value > max ? max : value;
+}
+
+static inline uint64_t canzero_saturate_signed(int64_t value, int64_t min, int64_t max) {
+  return (uint64_t)(value < min ? min : (value > max ? max : value));
+}
+
+static inline uint8_t canzero_crc8(uint16_t data_id, const uint8_t* data, uint8_t len, uint8_t skip) {
+  uint8_t crc = 0xFF;
+  for (int i = -2; i < len; ++i) {
+    if (i == skip) {
+      continue;
+    }
+    crc ^= i == -2 ? (uint8_t)data_id : (i == -1 ? (uint8_t)(data_id >> 8) : data[i]);
+    for (uint8_t bit = 0; bit < 8; ++bit) {
+      crc = crc & 0x80 ? (uint8_t)((crc << 1) ^ 0x1D) : (uint8_t)(crc << 1);
+    }
+  }
+  return crc ^ 0xFF;
+}
+"#;
+
+fn unsigned_type(size: u8) -> &'static str {
+    match size {
+        0..=8 => "uint8_t",
+        9..=16 => "uint16_t",
+        17..=32 => "uint32_t",
+        _ => "uint64_t",
+    }
+}
+
+fn signed_type(size: u8) -> &'static str {
+    match size {
+        0..=8 => "int8_t",
+        9..=16 => "int16_t",
+        17..=32 => "int32_t",
+        _ => "int64_t",
+    }
+}
+
+/// integer fields have to be range checked if the signal is narrower than the field type.
+fn needs_saturation(signal: &SignalLayout) -> bool {
+    !matches!(signal.size, 1 | 8 | 16 | 32 | 64)
+}
+
+fn int_bounds(signal: &SignalLayout) -> (String, String) {
+    let (min, max) = signal.range;
+    match signal.kind {
+        SignalKind::Signed => (format!("{}LL", min as i64), format!("{}LL", max as i64)),
+        _ => (format!("{}ULL", min as u64), format!("{}ULL", max as u64)),
+    }
+}
+
+/// C has no namespaces, so all types are prefixed with `canzero_`.
+fn type_name(name: &str) -> String {
+    format!("canzero_{name}")
+}
+
+fn field_type(signal: &SignalLayout) -> String {
+    match &signal.kind {
+        SignalKind::Unsigned if signal.size == 1 => "bool".to_owned(),
+        SignalKind::Unsigned => unsigned_type(signal.size).to_owned(),
+        SignalKind::Signed => signed_type(signal.size).to_owned(),
+        SignalKind::Decimal { .. } if signal.size <= 24 => "float".to_owned(),
+        SignalKind::Decimal { .. } => "double".to_owned(),
+        SignalKind::Enum { name, .. } => type_name(name),
+    }
+}
+
+fn write_enum(
+    c: &mut impl Write,
+    name: &str,
+    description: Option<&str>,
+    entries: &[(String, u64)],
+    entry_info: &[EnumEntryInfo],
+) {
+    match description {
+        Some(description) => writeln!(c, "\n/// {}", description.replace('\n', "\n/// ")).unwrap(),
+        None => writeln!(c).unwrap(),
+    }
+    writeln!(c, "typedef enum {{").unwrap();
+    let prefix = upper_snake_case(&type_name(name));
+    for ((entry, value), info) in entries.iter().zip(entry_info) {
+        if let Some(description) = info.description() {
+            writeln!(c, "  /// {}", description.replace('\n', "\n  /// ")).unwrap();
+        }
+        if info.is_deprecated() {
+            writeln!(c, "  /// @deprecated").unwrap();
+        }
+        writeln!(c, "  {prefix}_{} = {value},", upper_snake_case(entry)).unwrap();
+    }
+    writeln!(c, "}} {};", type_name(name)).unwrap();
+}
+
+fn write_message(c: &mut impl Write, message: &Message) {
+    let struct_name = type_name(message.name());
+    let prefix = upper_snake_case(&struct_name);
+    let layout = message_layout(message);
+    if let Some(description) = message.description() {
+        writeln!(c, "\n/// {}", description.replace('\n', "\n/// ")).unwrap();
+    } else {
+        writeln!(c).unwrap();
+    }
+    writeln!(c, "#define {prefix}_ID 0x{:X}U", message.id().as_u32()).unwrap();
+    writeln!(c, "#define {prefix}_IDE {}", message.id().ide() as u8).unwrap();
+    writeln!(c, "#define {prefix}_DLC {}", message.dlc()).unwrap();
+    writeln!(c, "#define {prefix}_BUS_ID {}U", message.bus().id()).unwrap();
+    if let Some(e2e) = message.e2e() {
+        writeln!(c, "#define {prefix}_E2E_DATA_ID 0x{:X}U", e2e.profile().data_id()).unwrap();
+        writeln!(c, "#define {prefix}_E2E_CRC_BYTE {}", e2e.crc().byte_offset() / 8).unwrap();
+    }
+    writeln!(c, "typedef struct {{").unwrap();
+    for signal in &layout {
+        writeln!(c, "  {} {};", field_type(signal), signal.name).unwrap();
+    }
+    writeln!(c, "}} {struct_name};").unwrap();
+
+    let crc_signal = message.e2e().map(|e2e| e2e.crc().name());
+    writeln!(
+        c,
+        "\nstatic inline void {struct_name}_encode(const {struct_name}* message, uint8_t* data) {{"
+    )
+    .unwrap();
+    // bits, which aren't occupied by signals, contain the fill pattern.
+    match message.fill_bits() {
+        0 => writeln!(c, "  uint64_t raw = 0;").unwrap(),
+        fill => writeln!(c, "  uint64_t raw = 0x{fill:X}ULL;").unwrap(),
+    }
+    for signal in layout.iter().filter(|s| Some(s.name) != crc_signal) {
+        let (name, offset, size) = (signal.name, signal.offset, signal.size);
+        let value = match (&signal.kind, signal.overflow) {
+            (
+                SignalKind::Decimal {
+                    offset: value_offset,
+                    scale,
+                },
+                OverflowPolicy::Wrap,
+            ) => format!("canzero_to_fixed_wrap(message->{name}, {value_offset:?}, {scale:?})"),
+            (
+                SignalKind::Decimal {
+                    offset: value_offset,
+                    scale,
+                },
+                _,
+            ) => format!("canzero_to_fixed(message->{name}, {value_offset:?}, {scale:?}, {size})"),
+            (SignalKind::Unsigned, OverflowPolicy::Saturate) if needs_saturation(signal) => {
+                let (_, max) = int_bounds(signal);
+                format!("canzero_saturate_unsigned(message->{name}, {max})")
+            }
+            (SignalKind::Signed, OverflowPolicy::Saturate) if needs_saturation(signal) => {
+                let (min, max) = int_bounds(signal);
+                format!("canzero_saturate_signed(message->{name}, {min}, {max})")
+            }
+            _ => format!("(uint64_t)message->{name}"),
+        };
+        match signal.multiplexor {
+            // only the variant selected by the tag of the union is encoded.
+            Some((tag, tag_value)) => writeln!(
+                c,
+                "  if ((uint64_t)message->{tag} == {tag_value}) raw |= canzero_pack({value}, {offset}, {size});"
+            )
+            .unwrap(),
+            None => writeln!(c, "  raw |= canzero_pack({value}, {offset}, {size});").unwrap(),
+        }
+    }
+    writeln!(c, "  for (uint8_t i = 0; i < {prefix}_DLC; ++i) {{").unwrap();
+    writeln!(c, "    data[i] = (uint8_t)(raw >> (8 * i));").unwrap();
+    writeln!(c, "  }}").unwrap();
+    if message.e2e().is_some() {
+        writeln!(c, "  data[{prefix}_E2E_CRC_BYTE] = canzero_crc8({prefix}_E2E_DATA_ID, data, {prefix}_DLC, {prefix}_E2E_CRC_BYTE);").unwrap();
+    }
+    writeln!(c, "}}").unwrap();
+    if message.e2e().is_some() {
+        writeln!(c, "\nstatic inline bool {struct_name}_check(const uint8_t* data) {{").unwrap();
+        writeln!(c, "  return data[{prefix}_E2E_CRC_BYTE] == canzero_crc8({prefix}_E2E_DATA_ID, data, {prefix}_DLC, {prefix}_E2E_CRC_BYTE);").unwrap();
+        writeln!(c, "}}").unwrap();
+    }
+
+    writeln!(c, "\nstatic inline bool {struct_name}_valid(const {struct_name}* message) {{").unwrap();
+    writeln!(c, "  (void)message;").unwrap();
+    writeln!(c, "  bool valid = true;").unwrap();
+    for signal in layout.iter().filter(|s| s.overflow == OverflowPolicy::Error) {
+        let name = signal.name;
+        match &signal.kind {
+            SignalKind::Decimal { .. } => {
+                let (min, max) = signal.range;
+                writeln!(c, "  valid = valid && message->{name} >= {min:?} && message->{name} <= {max:?};").unwrap();
+            }
+            SignalKind::Unsigned if needs_saturation(signal) => {
+                let (_, max) = int_bounds(signal);
+                writeln!(c, "  valid = valid && message->{name} <= {max};").unwrap();
+            }
+            SignalKind::Signed if needs_saturation(signal) => {
+                let (min, max) = int_bounds(signal);
+                writeln!(c, "  valid = valid && message->{name} >= {min} && message->{name} <= {max};").unwrap();
+            }
+            _ => (),
+        }
+    }
+    writeln!(c, "  return valid;").unwrap();
+    writeln!(c, "}}").unwrap();
+
+    writeln!(c, "\nstatic inline {struct_name} {struct_name}_decode(const uint8_t* data) {{").unwrap();
+    writeln!(c, "  uint64_t raw = 0;").unwrap();
+    writeln!(c, "  for (uint8_t i = 0; i < {prefix}_DLC; ++i) {{").unwrap();
+    writeln!(c, "    raw |= (uint64_t)data[i] << (8 * i);").unwrap();
+    writeln!(c, "  }}").unwrap();
+    writeln!(c, "  {struct_name} message;").unwrap();
+    for signal in &layout {
+        let (name, offset, size) = (signal.name, signal.offset, signal.size);
+        let ty = field_type(signal);
+        let raw = format!("canzero_unpack(raw, {offset}, {size})");
+        let value = match &signal.kind {
+            SignalKind::Unsigned | SignalKind::Enum { .. } => format!("({ty}){raw}"),
+            SignalKind::Signed => format!("({ty})canzero_sign_extend({raw}, {size})"),
+            SignalKind::Decimal {
+                offset: value_offset,
+                scale,
+            } => format!("({ty})({raw} * {scale:?} + {value_offset:?})"),
+        };
+        writeln!(c, "  message.{name} = {value};").unwrap();
+    }
+    writeln!(c, "  return message;").unwrap();
+    writeln!(c, "}}").unwrap();
+}
+
+/// Generates a header-only C99 library with a typedef enum for every enum
+/// and a struct with id / dlc defines and encode / decode functions for every message.
+/// All names are prefixed with `canzero_` (`CANZERO_` for defines and enum entries).
+pub struct CBackend;
+
+impl CodegenBackend for CBackend {
+    fn begin(&mut self, ctx: &mut CodegenContext) {
+        ctx.write_str(PRELUDE).unwrap();
+        let buses = ctx.network().buses().clone();
+        for bus in &buses {
+            let prefix = format!("CANZERO_{}_BUS", upper_snake_case(bus.name()));
+            writeln!(ctx).unwrap();
+            writeln!(ctx, "#define {prefix}_ID {}U", bus.id()).unwrap();
+            writeln!(ctx, "#define {prefix}_BAUDRATE {}U", bus.baudrate()).unwrap();
+            if let Some(sample_point) = bus.sample_point() {
+                writeln!(ctx, "#define {prefix}_SAMPLE_POINT {:?}f", sample_point / 100.0).unwrap();
+            }
+            if let Some(sjw) = bus.sjw() {
+                writeln!(ctx, "#define {prefix}_SJW {sjw}").unwrap();
+            }
+        }
+    }
+    fn visit_type(&mut self, ctx: &mut CodegenContext, ty: &TypeRef) {
+        if let Type::Enum {
+            name,
+            description,
+            entries,
+            entry_info,
+            ..
+        } = ty as &Type
+        {
+            write_enum(ctx, name, description.as_deref(), entries, entry_info);
+        }
+    }
+    fn visit_message(&mut self, ctx: &mut CodegenContext, message: &MessageRef) {
+        write_message(ctx, message);
+    }
+}
+
+pub fn network_to_c(network: &Network) -> String {
+    generate(network, &mut CBackend)
+}
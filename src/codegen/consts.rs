@@ -0,0 +1,139 @@
+use std::fmt::Write;
+
+use crate::config::{Network, Type};
+
+use super::upper_snake_case;
+
+/// Language of the generated constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstsLanguage {
+    /// Rust module with nested modules per category.
+    Rust,
+    /// C header with prefixed defines.
+    C,
+}
+
+/// (name, rust type, value literal) of a constant.
+type Constant = (String, &'static str, String);
+
+/// (category, constants) of the network, categories are nested with `::`.
+struct Constants {
+    groups: Vec<(String, Vec<Constant>)>,
+}
+
+impl Constants {
+    fn group(&mut self, name: &str) -> &mut Vec<Constant> {
+        self.groups.push((name.to_owned(), vec![]));
+        &mut self.groups.last_mut().unwrap().1
+    }
+}
+
+fn collect(network: &Network) -> Constants {
+    let mut constants = Constants { groups: vec![] };
+
+    let node_ids = constants.group("node_id");
+    for node in network.nodes() {
+        node_ids.push((node.name().to_owned(), "u8", node.id().to_string()));
+    }
+
+    let bus_ids = constants.group("bus_id");
+    for bus in network.buses() {
+        bus_ids.push((bus.name().to_owned(), "u32", bus.id().to_string()));
+    }
+
+    let mut messages: Vec<_> = network.messages().iter().collect();
+    messages.sort_by(|a, b| a.name().cmp(b.name()));
+    let message_ids = constants.group("message_id");
+    for message in &messages {
+        message_ids.push((
+            message.name().to_owned(),
+            "u32",
+            format!("0x{:X}", message.id().as_u32()),
+        ));
+    }
+    let message_ides = constants.group("message_ide");
+    for message in &messages {
+        message_ides.push((message.name().to_owned(), "bool", message.id().ide().to_string()));
+    }
+
+    for node in network.nodes() {
+        let od_indices = constants.group(&format!("od_index::{}", node.name()));
+        for oe in node.object_entries() {
+            od_indices.push((oe.name().to_owned(), "u32", oe.id().to_string()));
+        }
+    }
+
+    for ty in network.types() {
+        if let Type::Enum { name, entries, .. } = ty as &Type {
+            let values = constants.group(&format!("enums::{name}"));
+            for (entry, value) in entries {
+                values.push((entry.clone(), "u64", value.to_string()));
+            }
+        }
+    }
+    constants
+}
+
+fn rust_consts(network: &Network, constants: &Constants) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by canzero-config. Do not edit!").unwrap();
+    writeln!(out, "#![allow(dead_code)]\n").unwrap();
+    writeln!(out, "/// config hash of the network.").unwrap();
+    writeln!(out, "pub const NETWORK_HASH: u64 = 0x{:016X};", network.config_hash()).unwrap();
+    let mut open: Vec<&str> = vec![];
+    for (group, values) in &constants.groups {
+        let path: Vec<&str> = group.split("::").collect();
+        // close the modules, which aren't shared with the previous group.
+        let shared = open.iter().zip(&path).take_while(|(a, b)| a == b).count();
+        while open.len() > shared {
+            open.pop();
+            writeln!(out, "{}}}", "    ".repeat(open.len())).unwrap();
+        }
+        for (i, module) in path[shared..].iter().enumerate() {
+            let separator = if i == 0 { "\n" } else { "" };
+            writeln!(out, "{separator}{}pub mod {module} {{", "    ".repeat(open.len())).unwrap();
+            open.push(module);
+        }
+        let indent = "    ".repeat(open.len());
+        for (name, ty, value) in values {
+            writeln!(out, "{indent}pub const {}: {ty} = {value};", upper_snake_case(name)).unwrap();
+        }
+    }
+    while open.pop().is_some() {
+        writeln!(out, "{}}}", "    ".repeat(open.len())).unwrap();
+    }
+    out
+}
+
+fn c_consts(network: &Network, constants: &Constants) -> String {
+    let mut out = String::new();
+    writeln!(out, "// Generated by canzero-config. Do not edit!").unwrap();
+    writeln!(out, "#pragma once\n").unwrap();
+    writeln!(out, "// config hash of the network.").unwrap();
+    writeln!(out, "#define CANZERO_NETWORK_HASH 0x{:016X}ULL", network.config_hash()).unwrap();
+    for (group, values) in &constants.groups {
+        let prefix = format!("CANZERO_{}", group.replace("::", "_").to_uppercase());
+        writeln!(out).unwrap();
+        for (name, ty, value) in values {
+            let value = match *ty {
+                "bool" => if value == "true" { "1" } else { "0" }.to_owned(),
+                "u32" => format!("{value}U"),
+                "u64" => format!("{value}ULL"),
+                _ => value.clone(),
+            };
+            writeln!(out, "#define {prefix}_{} {value}", upper_snake_case(name)).unwrap();
+        }
+    }
+    out
+}
+
+/// Generates a single file with the magic numbers of the network (node ids,
+/// bus ids, message ids, od indices, enum values and the config hash) for
+/// projects, which don't need the encode/decode code of `cpp` or `python`.
+pub fn network_to_consts(network: &Network, language: ConstsLanguage) -> String {
+    let constants = collect(network);
+    match language {
+        ConstsLanguage::Rust => rust_consts(network, &constants),
+        ConstsLanguage::C => c_consts(network, &constants),
+    }
+}
@@ -0,0 +1,330 @@
+use std::fmt::Write;
+
+use crate::config::{EnumEntryInfo, Message, MessageRef, Network, OverflowPolicy, Type, TypeRef};
+
+use super::{
+    backend::{generate, CodegenBackend, CodegenContext},
+    camel_case,
+    layout::{message_layout, SignalKind, SignalLayout},
+};
+
+const PRELUDE: &str = r#"// Generated by canzero-config. Do not edit!
+#pragma once
+
+#include <array>
+#include <cmath>
+#include <cstdint>
+
+namespace canzero {
+
+namespace detail {
+
+constexpr uint64_t pack(uint64_t value, uint8_t offset, uint8_t size) {
+  const uint64_t mask = size == 64 ? ~uint64_t{0} : (uint64_t{1} << size) - 1;
+  return (value & mask) << offset;
+}
+
+constexpr uint64_t unpack(uint64_t raw, uint8_t offset, uint8_t size) {
+  const uint64_t mask = size == 64 ? ~uint64_t{0} : (uint64_t{1} << size) - 1;
+  return (raw >> offset) & mask;
+}
+
+constexpr int64_t sign_extend(uint64_t value, uint8_t size) {
+  const uint64_t sign = uint64_t{1} << (size - 1);
+  return static_cast<int64_t>((value ^ sign) - sign);
+}
+
+inline uint64_t to_fixed(double value, double offset, double scale, uint8_t size) {
+  const double max = size == 64 ? 18446744073709551615.0 : static_cast<double>((uint64_t{1} << size) - 1);
+  const double raw = std::round((value - offset) / scale);
+  return static_cast<uint64_t>(raw < 0.0 ? 0.0 : (raw > max ? max : raw));
+}
+
+inline uint64_t to_fixed_wrap(double value, double offset, double scale) {
+  return static_cast<uint64_t>(static_cast<int64_t>(std::round((value - offset) / scale)));
+}
+
+constexpr uint8_t crc8(uint16_t data_id, const uint8_t* data, uint8_t len, uint8_t skip) {
+  uint8_t crc = 0xFF;
+  for (int i = -2; i < len; ++i) {
+    if (i == skip) {
+      continue;
+    }
+    crc ^= i == -2 ? static_cast<uint8_t>(data_id) : (i == -1 ? static_cast<uint8_t>(data_id >> 8) : data[i]);
+    for (uint8_t bit = 0; bit < 8; ++bit) {
+      crc = crc & 0x80 ? static_cast<uint8_t>((crc << 1) ^ 0x1D) : static_cast<uint8_t>(crc << 1);
+    }
+  }
+  return crc ^ 0xFF;
+}
+
+template <typename T>
+constexpr T saturate(T value, T lo, T hi) {
+  return value < lo ? lo : (value > hi ? hi : value);
+}
+
+} // namespace detail
+"#;
+
+fn unsigned_type(size: u8) -> &'static str {
+    match size {
+        0..=8 => "uint8_t",
+        9..=16 => "uint16_t",
+        17..=32 => "uint32_t",
+        _ => "uint64_t",
+    }
+}
+
+/// integer fields have to be range checked if the signal is narrower than the field type.
+fn needs_saturation(signal: &SignalLayout) -> bool {
+    !matches!(signal.size, 1 | 8 | 16 | 32 | 64)
+}
+
+fn int_bounds(signal: &SignalLayout) -> (String, String) {
+    let (min, max) = signal.range;
+    match signal.kind {
+        SignalKind::Signed => (format!("{}", min as i64), format!("{}", max as i64)),
+        _ => (format!("{}u", min as u64), format!("{}u", max as u64)),
+    }
+}
+
+fn signed_type(size: u8) -> &'static str {
+    match size {
+        0..=8 => "int8_t",
+        9..=16 => "int16_t",
+        17..=32 => "int32_t",
+        _ => "int64_t",
+    }
+}
+
+fn field_type(signal: &SignalLayout) -> String {
+    match &signal.kind {
+        SignalKind::Unsigned if signal.size == 1 => "bool".to_owned(),
+        SignalKind::Unsigned => unsigned_type(signal.size).to_owned(),
+        SignalKind::Signed => signed_type(signal.size).to_owned(),
+        SignalKind::Decimal { .. } if signal.size <= 24 => "float".to_owned(),
+        SignalKind::Decimal { .. } => "double".to_owned(),
+        SignalKind::Enum { name, .. } => camel_case(name),
+    }
+}
+
+fn write_enum(
+    cpp: &mut impl Write,
+    name: &str,
+    description: Option<&str>,
+    size: u8,
+    entries: &[(String, u64)],
+    entry_info: &[EnumEntryInfo],
+) {
+    match description {
+        Some(description) => {
+            writeln!(cpp, "\n/// {}", description.replace('\n', "\n/// ")).unwrap()
+        }
+        None => writeln!(cpp).unwrap(),
+    }
+    writeln!(
+        cpp,
+        "enum class {} : {} {{",
+        camel_case(name),
+        unsigned_type(size)
+    )
+    .unwrap();
+    for ((entry, value), info) in entries.iter().zip(entry_info) {
+        if let Some(description) = info.description() {
+            writeln!(cpp, "  /// {}", description.replace('\n', "\n  /// ")).unwrap();
+        }
+        if info.is_deprecated() {
+            writeln!(cpp, "  /// @deprecated").unwrap();
+        }
+        writeln!(cpp, "  {entry} = {value},").unwrap();
+    }
+    writeln!(cpp, "}};").unwrap();
+}
+
+fn write_message(cpp: &mut impl Write, message: &Message) {
+    let struct_name = camel_case(message.name());
+    let layout = message_layout(message);
+    if let Some(description) = message.description() {
+        writeln!(cpp, "\n/// {}", description.replace('\n', "\n/// ")).unwrap();
+    } else {
+        writeln!(cpp).unwrap();
+    }
+    writeln!(cpp, "struct {struct_name} {{").unwrap();
+    writeln!(
+        cpp,
+        "  static constexpr uint32_t id = 0x{:X};",
+        message.id().as_u32()
+    )
+    .unwrap();
+    writeln!(cpp, "  static constexpr bool ide = {};", message.id().ide()).unwrap();
+    writeln!(cpp, "  static constexpr uint8_t dlc = {};", message.dlc()).unwrap();
+    writeln!(cpp, "  static constexpr uint32_t bus_id = {};", message.bus().id()).unwrap();
+    if let Some(e2e) = message.e2e() {
+        writeln!(cpp, "  static constexpr uint16_t e2e_data_id = 0x{:X};", e2e.profile().data_id()).unwrap();
+        writeln!(cpp, "  static constexpr uint8_t e2e_crc_byte = {};", e2e.crc().byte_offset() / 8).unwrap();
+    }
+    let crc_signal = message.e2e().map(|e2e| e2e.crc().name());
+    for signal in &layout {
+        let default = match &signal.kind {
+            SignalKind::Decimal { offset, .. } => format!("{offset:?}"),
+            SignalKind::Enum { name, entries } => match entries.first() {
+                Some((entry, _)) => format!("{}::{entry}", camel_case(name)),
+                None => format!("{}{{}}", camel_case(name)),
+            },
+            _ => "{}".to_owned(),
+        };
+        writeln!(cpp, "  {} {} = {default};", field_type(signal), signal.name).unwrap();
+    }
+
+    writeln!(cpp, "\n  std::array<uint8_t, dlc> encode() const {{").unwrap();
+    // bits, which aren't occupied by signals, contain the fill pattern.
+    match message.fill_bits() {
+        0 => writeln!(cpp, "    uint64_t raw = 0;").unwrap(),
+        fill => writeln!(cpp, "    uint64_t raw = 0x{fill:X}ull;").unwrap(),
+    }
+    for signal in layout.iter().filter(|s| Some(s.name) != crc_signal) {
+        let (name, offset, size) = (signal.name, signal.offset, signal.size);
+        let value = match (&signal.kind, signal.overflow) {
+            (
+                SignalKind::Decimal {
+                    offset: value_offset,
+                    scale,
+                },
+                OverflowPolicy::Wrap,
+            ) => format!("detail::to_fixed_wrap({name}, {value_offset:?}, {scale:?})"),
+            (
+                SignalKind::Decimal {
+                    offset: value_offset,
+                    scale,
+                },
+                _,
+            ) => format!("detail::to_fixed({name}, {value_offset:?}, {scale:?}, {size})"),
+            (SignalKind::Unsigned | SignalKind::Signed, OverflowPolicy::Saturate)
+                if needs_saturation(signal) =>
+            {
+                let (min, max) = int_bounds(signal);
+                let ty = field_type(signal);
+                format!("static_cast<uint64_t>(detail::saturate<{ty}>({name}, {min}, {max}))")
+            }
+            _ => format!("static_cast<uint64_t>({name})"),
+        };
+        match signal.multiplexor {
+            // only the variant selected by the tag of the union is encoded.
+            Some((tag, tag_value)) => writeln!(
+                cpp,
+                "    if (static_cast<uint64_t>({tag}) == {tag_value}) raw |= detail::pack({value}, {offset}, {size});"
+            )
+            .unwrap(),
+            None => writeln!(cpp, "    raw |= detail::pack({value}, {offset}, {size});").unwrap(),
+        }
+    }
+    writeln!(cpp, "    std::array<uint8_t, dlc> data{{}};").unwrap();
+    writeln!(cpp, "    for (uint8_t i = 0; i < dlc; ++i) {{").unwrap();
+    writeln!(cpp, "      data[i] = static_cast<uint8_t>(raw >> (8 * i));").unwrap();
+    writeln!(cpp, "    }}").unwrap();
+    if message.e2e().is_some() {
+        writeln!(cpp, "    data[e2e_crc_byte] = detail::crc8(e2e_data_id, data.data(), dlc, e2e_crc_byte);").unwrap();
+    }
+    writeln!(cpp, "    return data;").unwrap();
+    writeln!(cpp, "  }}").unwrap();
+    if message.e2e().is_some() {
+        writeln!(cpp, "\n  static bool check(const uint8_t* data) {{").unwrap();
+        writeln!(cpp, "    return data[e2e_crc_byte] == detail::crc8(e2e_data_id, data, dlc, e2e_crc_byte);").unwrap();
+        writeln!(cpp, "  }}").unwrap();
+    }
+
+    writeln!(cpp, "\n  bool valid() const {{").unwrap();
+    writeln!(cpp, "    bool valid = true;").unwrap();
+    for signal in layout.iter().filter(|s| s.overflow == OverflowPolicy::Error) {
+        let name = signal.name;
+        match &signal.kind {
+            SignalKind::Decimal { .. } => {
+                let (min, max) = signal.range;
+                writeln!(cpp, "    valid = valid && {name} >= {min:?} && {name} <= {max:?};").unwrap();
+            }
+            SignalKind::Unsigned if needs_saturation(signal) => {
+                let (_, max) = int_bounds(signal);
+                writeln!(cpp, "    valid = valid && {name} <= {max};").unwrap();
+            }
+            SignalKind::Signed if needs_saturation(signal) => {
+                let (min, max) = int_bounds(signal);
+                writeln!(cpp, "    valid = valid && {name} >= {min} && {name} <= {max};").unwrap();
+            }
+            _ => (),
+        }
+    }
+    writeln!(cpp, "    return valid;").unwrap();
+    writeln!(cpp, "  }}").unwrap();
+
+    writeln!(cpp, "\n  static {struct_name} decode(const uint8_t* data) {{").unwrap();
+    writeln!(cpp, "    uint64_t raw = 0;").unwrap();
+    writeln!(cpp, "    for (uint8_t i = 0; i < dlc; ++i) {{").unwrap();
+    writeln!(cpp, "      raw |= static_cast<uint64_t>(data[i]) << (8 * i);").unwrap();
+    writeln!(cpp, "    }}").unwrap();
+    writeln!(cpp, "    {struct_name} message;").unwrap();
+    for signal in &layout {
+        let (name, offset, size) = (signal.name, signal.offset, signal.size);
+        let ty = field_type(signal);
+        let raw = format!("detail::unpack(raw, {offset}, {size})");
+        let value = match &signal.kind {
+            SignalKind::Unsigned | SignalKind::Enum { .. } => format!("static_cast<{ty}>({raw})"),
+            SignalKind::Signed => {
+                format!("static_cast<{ty}>(detail::sign_extend({raw}, {size}))")
+            }
+            SignalKind::Decimal {
+                offset: value_offset,
+                scale,
+            } => format!("static_cast<{ty}>({raw} * {scale:?} + {value_offset:?})"),
+        };
+        writeln!(cpp, "    message.{name} = {value};").unwrap();
+    }
+    writeln!(cpp, "    return message;").unwrap();
+    writeln!(cpp, "  }}").unwrap();
+    writeln!(cpp, "}};").unwrap();
+}
+
+/// Generates a header-only C++17 library with an enum class for every enum
+/// and a struct with constexpr id / dlc and encode / decode for every message.
+pub struct CppBackend;
+
+impl CodegenBackend for CppBackend {
+    fn begin(&mut self, ctx: &mut CodegenContext) {
+        ctx.write_str(PRELUDE).unwrap();
+        let buses = ctx.network().buses().clone();
+        for bus in &buses {
+            writeln!(ctx, "\nstruct {}Bus {{", camel_case(bus.name())).unwrap();
+            writeln!(ctx, "  static constexpr uint32_t id = {};", bus.id()).unwrap();
+            writeln!(ctx, "  static constexpr uint32_t baudrate = {};", bus.baudrate()).unwrap();
+            if let Some(sample_point) = bus.sample_point() {
+                writeln!(ctx, "  static constexpr float sample_point = {:?}f;", sample_point / 100.0).unwrap();
+            }
+            if let Some(sjw) = bus.sjw() {
+                writeln!(ctx, "  static constexpr uint8_t sjw = {sjw};").unwrap();
+            }
+            writeln!(ctx, "}};").unwrap();
+        }
+    }
+    fn visit_type(&mut self, ctx: &mut CodegenContext, ty: &TypeRef) {
+        if let Type::Enum {
+            name,
+            description,
+            size,
+            entries,
+            entry_info,
+            ..
+        } = ty as &Type
+        {
+            write_enum(ctx, name, description.as_deref(), *size, entries, entry_info);
+        }
+    }
+    fn visit_message(&mut self, ctx: &mut CodegenContext, message: &MessageRef) {
+        write_message(ctx, message);
+    }
+    fn end(&mut self, ctx: &mut CodegenContext) {
+        writeln!(ctx, "\n}} // namespace canzero").unwrap();
+    }
+}
+
+pub fn network_to_cpp(network: &Network) -> String {
+    generate(network, &mut CppBackend)
+}
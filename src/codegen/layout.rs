@@ -0,0 +1,101 @@
+use crate::config::{encoding::TypeSignalEncoding, Message, OverflowPolicy, SignalType, Type};
+
+/// How the raw bits of a signal are interpreted.
+#[derive(Debug, Clone)]
+pub enum SignalKind<'a> {
+    Unsigned,
+    Signed,
+    Decimal { offset: f64, scale: f64 },
+    Enum {
+        name: &'a str,
+        entries: &'a [(String, u64)],
+    },
+}
+
+/// Bit layout of a signal within the little endian payload of a message,
+/// shared by all code generation backends.
+#[derive(Debug, Clone)]
+pub struct SignalLayout<'a> {
+    pub name: &'a str,
+    pub offset: usize,
+    pub size: u8,
+    pub kind: SignalKind<'a>,
+    pub overflow: OverflowPolicy,
+    /// smallest and largest representable physical value.
+    pub range: (f64, f64),
+    /// (tag signal, tag value) the signal is only encoded if the tag
+    /// of its union has the value of its variant.
+    pub multiplexor: Option<(&'a str, u64)>,
+}
+
+impl<'a> SignalLayout<'a> {
+    /// mask of the raw value (without offset).
+    pub fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.size as u32)
+    }
+}
+
+fn find_enum<'a>(
+    encoding: &'a TypeSignalEncoding,
+    signal_name: &str,
+) -> Option<(&'a str, &'a [(String, u64)])> {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => composite
+            .attributes()
+            .iter()
+            .find_map(|attrib| find_enum(attrib, signal_name)),
+        TypeSignalEncoding::Primitive(primitive) => match primitive.ty() as &Type {
+            Type::Enum { name, entries, .. } if primitive.signal().name() == signal_name => {
+                Some((name, entries))
+            }
+            _ => None,
+        },
+        TypeSignalEncoding::Multiplexed(mux) => match mux.tag().ty() as &Type {
+            Type::Enum { name, entries, .. } if mux.tag().signal().name() == signal_name => {
+                Some((name, entries))
+            }
+            _ => mux
+                .variants()
+                .iter()
+                .find_map(|(_, _, variant)| find_enum(variant, signal_name)),
+        },
+    }
+}
+
+pub fn message_layout(message: &Message) -> Vec<SignalLayout<'_>> {
+    message
+        .signals()
+        .iter()
+        // reserved regions don't have values.
+        .filter(|signal| !signal.is_reserved())
+        .map(|signal| {
+            let enum_def = message.encoding().and_then(|encoding| {
+                encoding
+                    .attributes()
+                    .iter()
+                    .find_map(|attrib| find_enum(attrib, signal.name()))
+            });
+            let kind = match (enum_def, signal.ty()) {
+                (Some((name, entries)), _) => SignalKind::Enum { name, entries },
+                (None, SignalType::UnsignedInt { size: _ }) => SignalKind::Unsigned,
+                (None, SignalType::SignedInt { size: _ }) => SignalKind::Signed,
+                (None, SignalType::Decimal { size: _, offset, scale }) => SignalKind::Decimal {
+                    offset: *offset,
+                    scale: *scale,
+                },
+            };
+            SignalLayout {
+                name: signal.name(),
+                offset: signal.byte_offset(),
+                size: signal.size(),
+                kind,
+                overflow: signal.overflow(),
+                range: signal.ty().physical_range(),
+                multiplexor: message
+                    .encoding()
+                    .and_then(|encoding| encoding.multiplexor_of(signal.name()))
+                    .map(|(tag, value)| (tag.name(), value)),
+            }
+        })
+        .collect()
+}
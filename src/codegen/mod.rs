@@ -0,0 +1,42 @@
+pub use self::backend::generate;
+pub use self::backend::CodegenBackend;
+pub use self::backend::CodegenContext;
+pub use self::consts::ConstsLanguage;
+
+pub mod backend;
+pub mod c;
+pub mod consts;
+pub mod cpp;
+pub mod layout;
+pub mod python;
+pub mod sim;
+#[cfg(feature = "template")]
+pub mod template;
+
+/// converts snake_case names into CamelCase type names.
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// converts snake_case and CamelCase names (e.g. enum entries) into UPPER_SNAKE_CASE.
+fn upper_snake_case(name: &str) -> String {
+    let mut upper = String::with_capacity(name.len() + 4);
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && prev_lower {
+            upper.push('_');
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        upper.extend(c.to_uppercase());
+    }
+    upper
+}
@@ -0,0 +1,274 @@
+use std::fmt::Write;
+
+use crate::config::{EnumEntryInfo, Message, MessageRef, Network, OverflowPolicy, Type, TypeRef};
+
+use super::{
+    backend::{generate, CodegenBackend, CodegenContext},
+    camel_case,
+    layout::{message_layout, SignalKind},
+};
+
+const PYTHON_KEYWORDS: [&str; 35] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+    "continue", "def", "del", "elif", "else", "except", "finally", "for", "from", "global",
+    "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return",
+    "try", "while", "with", "yield",
+];
+
+const PRELUDE: &str = r#"# Generated by canzero-config. Do not edit!
+from dataclasses import dataclass
+from enum import IntEnum
+
+
+def _unpack(raw: int, offset: int, size: int, signed: bool = False) -> int:
+    value = (raw >> offset) & ((1 << size) - 1)
+    if signed and value & (1 << (size - 1)):
+        value -= 1 << size
+    return value
+
+
+def _pack(value: int, offset: int, size: int) -> int:
+    return (int(value) & ((1 << size) - 1)) << offset
+
+
+def _to_fixed(value: float, offset: float, scale: float, size: int, wrap: bool = False) -> int:
+    raw = round((value - offset) / scale)
+    if wrap:
+        return raw & ((1 << size) - 1)
+    return min(max(raw, 0), (1 << size) - 1)
+
+
+def _clamp(value, lo, hi):
+    return min(max(value, lo), hi)
+
+
+def _crc8(data_id: int, data: bytes, skip: int) -> int:
+    crc = 0xFF
+    payload = bytes([data_id & 0xFF, data_id >> 8]) + bytes(b for i, b in enumerate(data) if i != skip)
+    for b in payload:
+        crc ^= b
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x1D) & 0xFF if crc & 0x80 else (crc << 1) & 0xFF
+    return crc ^ 0xFF
+
+
+def _check(value, lo, hi, name: str):
+    if not lo <= value <= hi:
+        raise ValueError(f"{name} = {value} is out of range [{lo}, {hi}]")
+    return value
+"#;
+
+fn field_name(name: &str) -> String {
+    if PYTHON_KEYWORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn write_enum(py: &mut impl Write, name: &str, entries: &[(String, u64)], entry_info: &[EnumEntryInfo]) {
+    writeln!(py, "\n\nclass {}(IntEnum):", camel_case(name)).unwrap();
+    if entries.is_empty() {
+        writeln!(py, "    pass").unwrap();
+    }
+    for ((entry, value), info) in entries.iter().zip(entry_info) {
+        let mut comment = info
+            .description()
+            .map(|description| description.replace('\n', " "))
+            .unwrap_or_default();
+        if info.is_deprecated() {
+            comment = if comment.is_empty() {
+                "deprecated".to_owned()
+            } else {
+                format!("{comment} (deprecated)")
+            };
+        }
+        if comment.is_empty() {
+            writeln!(py, "    {} = {value}", field_name(entry)).unwrap();
+        } else {
+            writeln!(py, "    {} = {value}  # {comment}", field_name(entry)).unwrap();
+        }
+    }
+}
+
+fn write_message(py: &mut impl Write, message: &Message) {
+    let class_name = camel_case(message.name());
+    writeln!(py, "\n\n@dataclass").unwrap();
+    writeln!(py, "class {class_name}:").unwrap();
+    if let Some(description) = message.description() {
+        writeln!(py, "    \"\"\"{}\"\"\"", description.replace("\"\"\"", "'''")).unwrap();
+    }
+    writeln!(py, "    ID = 0x{:X}", message.id().as_u32()).unwrap();
+    writeln!(py, "    IDE = {}", if message.id().ide() { "True" } else { "False" }).unwrap();
+    writeln!(py, "    DLC = {}", message.dlc()).unwrap();
+    writeln!(py, "    BUS = \"{}\"", message.bus().name()).unwrap();
+    if let Some(e2e) = message.e2e() {
+        writeln!(py, "    E2E_DATA_ID = 0x{:X}", e2e.profile().data_id()).unwrap();
+        writeln!(py, "    E2E_CRC_BYTE = {}", e2e.crc().byte_offset() / 8).unwrap();
+    }
+    let crc_signal = message.e2e().map(|e2e| e2e.crc().name());
+    let layout = message_layout(message);
+    for signal in &layout {
+        let name = field_name(signal.name);
+        let (annotation, default) = match &signal.kind {
+            SignalKind::Unsigned | SignalKind::Signed => ("int".to_owned(), "0".to_owned()),
+            SignalKind::Decimal { offset, scale: _ } => ("float".to_owned(), format!("{offset:?}")),
+            SignalKind::Enum { name, entries } => {
+                let enum_ty = camel_case(name);
+                let default = match entries.first() {
+                    Some((entry, _)) => format!("{enum_ty}.{}", field_name(entry)),
+                    None => format!("{enum_ty}(0)"),
+                };
+                (enum_ty, default)
+            }
+        };
+        writeln!(py, "    {name}: {annotation} = {default}").unwrap();
+    }
+
+    writeln!(py, "\n    def encode(self) -> bytes:").unwrap();
+    // bits, which aren't occupied by signals, contain the fill pattern.
+    match message.fill_bits() {
+        0 => writeln!(py, "        raw = 0").unwrap(),
+        fill => writeln!(py, "        raw = 0x{fill:X}").unwrap(),
+    }
+    for signal in layout.iter().filter(|s| Some(s.name) != crc_signal) {
+        let name = field_name(signal.name);
+        let offset = signal.offset;
+        let size = signal.size;
+        let (min, max) = signal.range;
+        let value = match (&signal.kind, signal.overflow) {
+            (SignalKind::Enum { .. }, _) => format!("self.{name}"),
+            (SignalKind::Decimal { .. }, OverflowPolicy::Error) => {
+                format!("_check(self.{name}, {min:?}, {max:?}, \"{name}\")")
+            }
+            (SignalKind::Decimal { .. }, _) => format!("self.{name}"),
+            (_, OverflowPolicy::Saturate) => format!("_clamp(self.{name}, {min}, {max})"),
+            (_, OverflowPolicy::Error) => format!("_check(self.{name}, {min}, {max}, \"{name}\")"),
+            (_, OverflowPolicy::Wrap) => format!("self.{name}"),
+        };
+        // only the variant selected by the tag of the union is encoded.
+        let indent = match signal.multiplexor {
+            Some((tag, tag_value)) => {
+                writeln!(py, "        if int(self.{}) == {tag_value}:", field_name(tag)).unwrap();
+                "    "
+            }
+            None => "",
+        };
+        match &signal.kind {
+            SignalKind::Decimal {
+                offset: value_offset,
+                scale,
+            } => {
+                let wrap = if signal.overflow == OverflowPolicy::Wrap { ", wrap=True" } else { "" };
+                writeln!(
+                    py,
+                    "        {indent}raw |= _pack(_to_fixed({value}, {value_offset:?}, {scale:?}, {size}{wrap}), {offset}, {size})"
+                )
+                .unwrap()
+            }
+            _ => writeln!(py, "        {indent}raw |= _pack({value}, {offset}, {size})").unwrap(),
+        }
+    }
+    if message.e2e().is_some() {
+        writeln!(py, "        data = bytearray(raw.to_bytes(self.DLC, \"little\"))").unwrap();
+        writeln!(py, "        data[self.E2E_CRC_BYTE] = _crc8(self.E2E_DATA_ID, data, self.E2E_CRC_BYTE)").unwrap();
+        writeln!(py, "        return bytes(data)").unwrap();
+        writeln!(py, "\n    @classmethod").unwrap();
+        writeln!(py, "    def check(cls, data: bytes) -> bool:").unwrap();
+        writeln!(py, "        data = bytes(data[:cls.DLC])").unwrap();
+        writeln!(
+            py,
+            "        return len(data) == cls.DLC and data[cls.E2E_CRC_BYTE] == _crc8(cls.E2E_DATA_ID, data, cls.E2E_CRC_BYTE)"
+        )
+        .unwrap();
+    } else {
+        writeln!(py, "        return raw.to_bytes(self.DLC, \"little\")").unwrap();
+    }
+
+    writeln!(py, "\n    @classmethod").unwrap();
+    writeln!(py, "    def decode(cls, data: bytes) -> \"{class_name}\":").unwrap();
+    writeln!(py, "        raw = int.from_bytes(bytes(data[:cls.DLC]), \"little\")").unwrap();
+    writeln!(py, "        return cls(").unwrap();
+    for signal in &layout {
+        let name = field_name(signal.name);
+        let offset = signal.offset;
+        let size = signal.size;
+        let value = match &signal.kind {
+            SignalKind::Unsigned => format!("_unpack(raw, {offset}, {size})"),
+            SignalKind::Signed => format!("_unpack(raw, {offset}, {size}, signed=True)"),
+            SignalKind::Decimal {
+                offset: value_offset,
+                scale,
+            } => format!("_unpack(raw, {offset}, {size}) * {scale:?} + {value_offset:?}"),
+            SignalKind::Enum { name: enum_name, .. } => {
+                format!("{}(_unpack(raw, {offset}, {size}))", camel_case(enum_name))
+            }
+        };
+        writeln!(py, "            {name}={value},").unwrap();
+    }
+    writeln!(py, "        )").unwrap();
+}
+
+/// Generates a python module with a IntEnum for every enum of the network
+/// and a dataclass with encode / decode methods for every message.
+/// `decode(bus, id, data)` dispatches on the bus name and can id.
+pub struct PythonBackend;
+
+impl CodegenBackend for PythonBackend {
+    fn begin(&mut self, ctx: &mut CodegenContext) {
+        ctx.write_str(PRELUDE).unwrap();
+        writeln!(ctx, "\n\nBUSES = {{").unwrap();
+        let buses = ctx.network().buses().clone();
+        for bus in &buses {
+            let optional = |value: Option<String>| value.unwrap_or_else(|| "None".to_owned());
+            writeln!(
+                ctx,
+                "    \"{}\": {{\"id\": {}, \"baudrate\": {}, \"sample_point\": {}, \"sjw\": {}}},",
+                bus.name(),
+                bus.id(),
+                bus.baudrate(),
+                optional(bus.sample_point().map(|p| format!("{:?}", p / 100.0))),
+                optional(bus.sjw().map(|sjw| sjw.to_string())),
+            )
+            .unwrap();
+        }
+        writeln!(ctx, "}}").unwrap();
+    }
+    fn visit_type(&mut self, ctx: &mut CodegenContext, ty: &TypeRef) {
+        if let Type::Enum {
+            name,
+            entries,
+            entry_info,
+            ..
+        } = ty as &Type
+        {
+            write_enum(ctx, name, entries, entry_info);
+        }
+    }
+    fn visit_message(&mut self, ctx: &mut CodegenContext, message: &MessageRef) {
+        write_message(ctx, message);
+    }
+    fn end(&mut self, ctx: &mut CodegenContext) {
+        writeln!(ctx, "\n\nMESSAGES = {{").unwrap();
+        for message in ctx.network().messages() {
+            writeln!(
+                ctx,
+                "    (\"{}\", 0x{:X}): {},",
+                message.bus().name(),
+                message.id().as_u32(),
+                camel_case(message.name())
+            )
+            .unwrap();
+        }
+        writeln!(ctx, "}}").unwrap();
+        writeln!(ctx, "\n\ndef decode(bus: str, can_id: int, data: bytes):").unwrap();
+        writeln!(ctx, "    message = MESSAGES.get((bus, can_id))").unwrap();
+        writeln!(ctx, "    if message is None:").unwrap();
+        writeln!(ctx, "        return None").unwrap();
+        writeln!(ctx, "    return message.decode(data)").unwrap();
+    }
+}
+
+pub fn network_to_python(network: &Network) -> String {
+    generate(network, &mut PythonBackend)
+}
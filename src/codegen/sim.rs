@@ -0,0 +1,459 @@
+use std::fmt::Write;
+
+use crate::{
+    builder::STANDARD_OD_ERROR_CODES,
+    config::{
+        encoding::TypeSignalEncoding, Message, Network, Node, ObjectEntry, ObjectEntryAccess,
+        SignalType, Type,
+    },
+};
+
+use super::camel_case;
+
+const PRELUDE: &str = r#"// Generated by canzero-config. Do not edit!
+#![allow(dead_code)]
+
+/// Transmits the frames of the simulated node, e.g. over a socketcan socket.
+pub trait CanBus {
+    fn send(&mut self, bus: &str, id: u32, ide: bool, data: &[u8]);
+}
+
+fn mask(size: u32) -> u64 {
+    if size >= 64 {
+        u64::MAX
+    } else {
+        (1 << size) - 1
+    }
+}
+
+fn pack(raw: &mut u64, value: u64, (offset, size): (u32, u32)) {
+    *raw |= (value & mask(size)) << offset;
+}
+
+fn unpack(raw: u64, (offset, size): (u32, u32)) -> u64 {
+    (raw >> offset) & mask(size)
+}
+
+fn to_payload(raw: u64, dlc: usize) -> Vec<u8> {
+    raw.to_le_bytes()[..dlc.min(8)].to_vec()
+}
+
+fn from_payload(data: &[u8]) -> u64 {
+    let mut bytes = [0u8; 8];
+    let len = data.len().min(8);
+    bytes[..len].copy_from_slice(&data[..len]);
+    u64::from_le_bytes(bytes)
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjectEntry {
+    pub index: u32,
+    pub name: &'static str,
+    /// clients are allowed to write the entry with a set request.
+    pub writable: bool,
+    pub size: u32,
+    /// raw bits of the value, attributes of structs are packed in order.
+    pub value: u64,
+}
+
+struct Frame {
+    bus: &'static str,
+    id: u32,
+    ide: bool,
+    dlc: usize,
+    /// raw value of the bits, which aren't occupied by signals.
+    fill: u64,
+}
+
+/// Linear conversion of the raw value of an object entry into the
+/// raw value of its signal: `signal = entry * factor + offset`.
+struct Scaling {
+    /// (signed, size) of the object entry.
+    entry: (bool, u32),
+    /// (signed, size) of the signal.
+    signal: (bool, u32),
+    factor: f64,
+    offset: f64,
+}
+
+fn scale(value: u64, scaling: &Scaling) -> u64 {
+    let (signed, size) = scaling.entry;
+    let value = if signed {
+        let shift = 64 - size.clamp(1, 64);
+        ((value << shift) as i64 >> shift) as f64
+    } else {
+        value as f64
+    };
+    let value = (value * scaling.factor + scaling.offset).round();
+    let (signed, size) = scaling.signal;
+    let raw = if signed { value as i64 as u64 } else { value as u64 };
+    raw & mask(size)
+}
+
+struct Stream {
+    frame: Frame,
+    interval_us: u64,
+    /// (position in the object dictionary, (offset, size) of the signals
+    /// of the entry, scaling if the signal has a different type).
+    entries: &'static [(usize, &'static [(u32, u32)], Option<Scaling>)],
+}
+
+/// (offset, size) of the header fields of a get/set message, (0, 0) if absent.
+struct Header {
+    frame: Frame,
+    sof: (u32, u32),
+    eof: (u32, u32),
+    toggle: (u32, u32),
+    od_index: (u32, u32),
+    client_id: (u32, u32),
+    server_id: (u32, u32),
+    data: (u32, u32),
+    erno: (u32, u32),
+}
+"#;
+
+type Field = (u32, u32);
+
+fn frame(message: &Message) -> String {
+    format!(
+        "Frame {{ bus: {:?}, id: 0x{:X}, ide: {}, dlc: {}, fill: 0x{:X} }}",
+        message.bus().name(),
+        message.id().as_u32(),
+        message.id().ide(),
+        message.dlc(),
+        message.fill_bits()
+    )
+}
+
+fn field(message: &Message, name: &str) -> Field {
+    message
+        .signals()
+        .iter()
+        .find(|signal| signal.name().ends_with(name))
+        .map(|signal| (signal.byte_offset() as u32, signal.size() as u32))
+        .unwrap_or((0, 0))
+}
+
+fn header(message: &Message) -> String {
+    let fields = ["sof", "eof", "toggle", "od_index", "client_id", "server_id", "data", "erno"]
+        .iter()
+        .map(|name| format!("{name}: {:?}", field(message, name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("Header {{ frame: {}, {fields} }}", frame(message))
+}
+
+fn collect_signals(encoding: &TypeSignalEncoding, signals: &mut Vec<Field>) {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => {
+            for attrib in composite.attributes() {
+                collect_signals(attrib, signals);
+            }
+        }
+        TypeSignalEncoding::Primitive(primitive) => {
+            let signal = primitive.signal();
+            signals.push((signal.byte_offset() as u32, signal.size() as u32));
+        }
+        TypeSignalEncoding::Multiplexed(_) => {
+            for signal in encoding.signals() {
+                signals.push((signal.byte_offset() as u32, signal.size() as u32));
+            }
+        }
+    }
+}
+
+/// Raw value of the default value of the entry or of the physical value zero,
+/// if it's representable.
+fn default_raw(object_entry: &ObjectEntry) -> u64 {
+    let default = object_entry.default_value();
+    match object_entry.ty() as &Type {
+        Type::Primitive(SignalType::UnsignedInt { .. }) => {
+            default.and_then(|d| d.parse::<u64>().ok()).unwrap_or(0)
+        }
+        Type::Primitive(SignalType::SignedInt { size }) => {
+            let value = default.and_then(|d| d.parse::<i64>().ok()).unwrap_or(0);
+            value as u64 & (u64::MAX >> (64 - *size as u32))
+        }
+        Type::Primitive(SignalType::Decimal {
+            size,
+            offset,
+            scale,
+        }) => {
+            let value = default.and_then(|d| d.parse::<f64>().ok()).unwrap_or(0.0);
+            let raw = ((value - offset) / scale).round();
+            let max = (u64::MAX >> (64 - *size as u32)) as f64;
+            if (0.0..=max).contains(&raw) {
+                raw as u64
+            } else {
+                0
+            }
+        }
+        Type::Enum { entries, .. } => default
+            .and_then(|d| entries.iter().find(|(entry, _)| entry == d))
+            .map(|(_, value)| *value)
+            .unwrap_or(0),
+        Type::Struct { .. } | Type::Array { .. } | Type::Union { .. } => 0,
+    }
+}
+
+/// Generates a Rust simulation stub of the node, which transmits its streams
+/// (at their max interval) and the heartbeat with the values of an in-memory
+/// object dictionary and answers get/set requests against it.
+///
+/// Frames are sent through the `CanBus` trait and received frames have to be
+/// passed to `on_frame`, so the stub can run against any CAN interface.
+/// Object entries wider than 64 bits are not simulated.
+pub fn node_to_sim(network: &Network, node: &Node) -> String {
+    let mut sim = String::new();
+    sim.write_str(PRELUDE).unwrap();
+    let name = format!("{}Sim", camel_case(node.name()));
+
+    let object_entries: Vec<_> = node
+        .object_entries()
+        .iter()
+        .filter(|oe| oe.ty().size() <= 64)
+        .collect();
+
+    let streams: Vec<String> = node
+        .tx_streams()
+        .iter()
+        .map(|stream| {
+            let message = stream.message();
+            let attributes = message
+                .encoding()
+                .map(|encoding| encoding.attributes().as_slice())
+                .unwrap_or(&[]);
+            let entries: Vec<String> = stream
+                .mapping()
+                .iter()
+                .zip(attributes)
+                .filter_map(|(mapping, attribute)| {
+                    let mapping = mapping.as_ref()?;
+                    let position = object_entries
+                        .iter()
+                        .position(|o| o.name() == mapping.name())?;
+                    let mut signals = vec![];
+                    collect_signals(attribute, &mut signals);
+                    let scaling = match mapping.conversion() {
+                        Some(conversion) => format!(
+                            "Some(Scaling {{ entry: ({}, {}), signal: ({}, {}), factor: {:?}, offset: {:?} }})",
+                            conversion.is_signed(),
+                            conversion.object_entry_type().size(),
+                            matches!(conversion.signal_type(), SignalType::SignedInt { .. }),
+                            conversion.signal_type().size(),
+                            conversion.factor(),
+                            conversion.offset()
+                        ),
+                        None => "None".to_owned(),
+                    };
+                    Some(format!("({position}, &{signals:?}, {scaling})"))
+                })
+                .collect();
+            format!(
+                "        Stream {{ frame: {}, interval_us: {}, entries: &[{}] }},",
+                frame(message),
+                stream.max_interval().as_micros(),
+                entries.join(", ")
+            )
+        })
+        .collect();
+
+    writeln!(sim, "\n/// Simulation of the node {}.", node.name()).unwrap();
+    writeln!(sim, "pub struct {name} {{").unwrap();
+    writeln!(sim, "    pub object_entries: Vec<ObjectEntry>,").unwrap();
+    writeln!(sim, "    /// next transmission of every stream and the heartbeat in us.").unwrap();
+    writeln!(sim, "    next_transmission: Vec<u64>,").unwrap();
+    writeln!(sim, "    /// (od_index, client_id, received fragments, value) of a pending set request.").unwrap();
+    writeln!(sim, "    set_request: Option<(u32, u64, u32, u64)>,").unwrap();
+    writeln!(sim, "}}").unwrap();
+
+    writeln!(sim, "\nimpl {name} {{").unwrap();
+    writeln!(sim, "    pub const NODE_ID: u8 = {};", node.id()).unwrap();
+    // nodes without the od protocol never answer get/set requests.
+    let server_ids = if node.has_od_protocol() {
+        network.server_ids(node)
+    } else {
+        vec![]
+    };
+    writeln!(
+        sim,
+        "    pub const SERVER_IDS: [u8; {}] = {server_ids:?};",
+        server_ids.len()
+    )
+    .unwrap();
+    writeln!(sim, "    const STREAMS: [Stream; {}] = [", streams.len()).unwrap();
+    for stream in &streams {
+        writeln!(sim, "{stream}").unwrap();
+    }
+    writeln!(sim, "    ];").unwrap();
+    let heartbeat = network.heartbeat_message();
+    writeln!(sim, "    const HEARTBEAT: Frame = {};", frame(heartbeat)).unwrap();
+    writeln!(sim, "    const HEARTBEAT_NODE_ID: (u32, u32) = {:?};", field(heartbeat, "node_id")).unwrap();
+    writeln!(sim, "    const HEARTBEAT_INTERVAL_US: u64 = 100000;").unwrap();
+    for (constant, code) in [("INVALID_INDEX", "InvalidIndex"), ("ACCESS_DENIED", "AccessDenied")] {
+        let value = STANDARD_OD_ERROR_CODES
+            .iter()
+            .position(|(name, _)| *name == code)
+            .unwrap();
+        writeln!(sim, "    const {constant}: u64 = {value};").unwrap();
+    }
+    for (constant, message) in [
+        ("GET_REQ", network.get_req_message()),
+        ("GET_RESP", network.get_resp_message()),
+        ("SET_REQ", network.set_req_message()),
+        ("SET_RESP", network.set_resp_message()),
+    ] {
+        writeln!(sim, "    const {constant}: Header = {};", header(message)).unwrap();
+    }
+
+    writeln!(sim, "\n    pub fn new() -> Self {{").unwrap();
+    writeln!(sim, "        Self {{").unwrap();
+    writeln!(sim, "            object_entries: vec![").unwrap();
+    for oe in &object_entries {
+        writeln!(
+            sim,
+            "                ObjectEntry {{ index: {}, name: {:?}, writable: {}, size: {}, value: {} }},",
+            oe.id(),
+            oe.name(),
+            matches!(oe.access(), ObjectEntryAccess::Global),
+            oe.ty().size(),
+            default_raw(oe)
+        )
+        .unwrap();
+    }
+    writeln!(sim, "            ],").unwrap();
+    writeln!(sim, "            next_transmission: vec![0; {}],", streams.len() + 1).unwrap();
+    writeln!(sim, "            set_request: None,").unwrap();
+    writeln!(sim, "        }}").unwrap();
+    writeln!(sim, "    }}").unwrap();
+
+    sim.write_str(
+        r#"
+    pub fn get(&self, name: &str) -> Option<u64> {
+        self.object_entries.iter().find(|oe| oe.name == name).map(|oe| oe.value)
+    }
+
+    pub fn set(&mut self, name: &str, value: u64) {
+        if let Some(oe) = self.object_entries.iter_mut().find(|oe| oe.name == name) {
+            oe.value = value & mask(oe.size);
+        }
+    }
+
+    /// Transmits all streams and the heartbeat, which are due at {now_us}.
+    pub fn poll(&mut self, now_us: u64, can: &mut impl CanBus) {
+        for (i, stream) in Self::STREAMS.iter().enumerate() {
+            if now_us < self.next_transmission[i] {
+                continue;
+            }
+            self.next_transmission[i] = now_us + stream.interval_us;
+            let mut raw = stream.frame.fill;
+            for (position, signals, scaling) in stream.entries {
+                let mut value = self.object_entries[*position].value;
+                if let Some(scaling) = scaling {
+                    value = scale(value, scaling);
+                }
+                for &(offset, size) in signals.iter() {
+                    pack(&mut raw, value, (offset, size));
+                    value = value.checked_shr(size).unwrap_or(0);
+                }
+            }
+            can.send(stream.frame.bus, stream.frame.id, stream.frame.ide, &to_payload(raw, stream.frame.dlc));
+        }
+        let heartbeat = Self::STREAMS.len();
+        if now_us >= self.next_transmission[heartbeat] {
+            self.next_transmission[heartbeat] = now_us + Self::HEARTBEAT_INTERVAL_US;
+            let frame = &Self::HEARTBEAT;
+            let mut raw = frame.fill;
+            pack(&mut raw, Self::NODE_ID as u64, Self::HEARTBEAT_NODE_ID);
+            can.send(frame.bus, frame.id, frame.ide, &to_payload(raw, frame.dlc));
+        }
+    }
+
+    /// Answers get and set requests, which are addressed to the node.
+    pub fn on_frame(&mut self, bus: &str, id: u32, ide: bool, data: &[u8], can: &mut impl CanBus) {
+        let is = |frame: &Frame| frame.bus == bus && frame.id == id && frame.ide == ide;
+        let raw = from_payload(data);
+        if is(&Self::GET_REQ.frame) {
+            let header = &Self::GET_REQ;
+            if !Self::SERVER_IDS.contains(&(unpack(raw, header.server_id) as u8)) {
+                return;
+            }
+            let od_index = unpack(raw, header.od_index) as u32;
+            let client_id = unpack(raw, header.client_id);
+            let resp = &Self::GET_RESP;
+            let Some(oe) = self.object_entries.iter().find(|oe| oe.index == od_index) else {
+                // a single fragment terminates the request, the error code is
+                // only transmitted if the get_resp header has an erno field.
+                let mut raw = resp.frame.fill;
+                pack(&mut raw, 1, resp.sof);
+                pack(&mut raw, 1, resp.eof);
+                pack(&mut raw, od_index as u64, resp.od_index);
+                pack(&mut raw, client_id, resp.client_id);
+                pack(&mut raw, Self::NODE_ID as u64, resp.server_id);
+                pack(&mut raw, Self::INVALID_INDEX, resp.erno);
+                can.send(resp.frame.bus, resp.frame.id, resp.frame.ide, &to_payload(raw, resp.frame.dlc));
+                return;
+            };
+            let fragments = oe.size.div_ceil(32).max(1);
+            for fragment in 0..fragments {
+                let mut raw = resp.frame.fill;
+                pack(&mut raw, (fragment == 0) as u64, resp.sof);
+                pack(&mut raw, (fragment + 1 == fragments) as u64, resp.eof);
+                pack(&mut raw, (fragment % 2) as u64, resp.toggle);
+                pack(&mut raw, od_index as u64, resp.od_index);
+                pack(&mut raw, client_id, resp.client_id);
+                pack(&mut raw, Self::NODE_ID as u64, resp.server_id);
+                pack(&mut raw, oe.value.checked_shr(32 * fragment).unwrap_or(0), resp.data);
+                can.send(resp.frame.bus, resp.frame.id, resp.frame.ide, &to_payload(raw, resp.frame.dlc));
+            }
+        } else if is(&Self::SET_REQ.frame) {
+            let header = &Self::SET_REQ;
+            if !Self::SERVER_IDS.contains(&(unpack(raw, header.server_id) as u8)) {
+                return;
+            }
+            let od_index = unpack(raw, header.od_index) as u32;
+            let client_id = unpack(raw, header.client_id);
+            if unpack(raw, header.sof) == 1 {
+                self.set_request = Some((od_index, client_id, 0, 0));
+            }
+            let Some((index, client, fragments, value)) = self.set_request.as_mut() else {
+                return;
+            };
+            if *index != od_index || *client != client_id {
+                return;
+            }
+            *value |= unpack(raw, header.data).checked_shl(32 * *fragments).unwrap_or(0);
+            *fragments += 1;
+            if unpack(raw, header.eof) == 0 {
+                return;
+            }
+            let value = *value;
+            self.set_request = None;
+            let erno = match self.object_entries.iter_mut().find(|oe| oe.index == od_index) {
+                Some(oe) if oe.writable => {
+                    oe.value = value & mask(oe.size);
+                    0
+                }
+                Some(_) => Self::ACCESS_DENIED,
+                None => Self::INVALID_INDEX,
+            };
+            let resp = &Self::SET_RESP;
+            let mut raw = resp.frame.fill;
+            pack(&mut raw, od_index as u64, resp.od_index);
+            pack(&mut raw, client_id, resp.client_id);
+            pack(&mut raw, Self::NODE_ID as u64, resp.server_id);
+            pack(&mut raw, erno, resp.erno);
+            can.send(resp.frame.bus, resp.frame.id, resp.frame.ide, &to_payload(raw, resp.frame.dlc));
+        }
+    }
+}
+"#,
+    )
+    .unwrap();
+    writeln!(sim, "\nimpl Default for {name} {{").unwrap();
+    writeln!(sim, "    fn default() -> Self {{").unwrap();
+    writeln!(sim, "        Self::new()").unwrap();
+    writeln!(sim, "    }}").unwrap();
+    writeln!(sim, "}}").unwrap();
+    sim
+}
@@ -0,0 +1,294 @@
+use std::collections::BTreeMap;
+
+use minijinja::{Environment, Value};
+
+use crate::{
+    config::{Message, Network, Node, ObjectEntryAccess, Type},
+    errors::{ConfigError, Result},
+};
+
+use super::layout::{message_layout, SignalKind};
+
+fn map<const N: usize>(entries: [(&str, Value); N]) -> Value {
+    Value::from(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect::<BTreeMap<String, Value>>(),
+    )
+}
+
+fn type_value(ty: &Type) -> Value {
+    match ty {
+        Type::Primitive(_) => map([
+            ("name", Value::from(ty.name())),
+            ("kind", Value::from("primitive")),
+            ("size", Value::from(ty.size())),
+        ]),
+        Type::Struct {
+            name,
+            description,
+            attribs,
+            attrib_info,
+            ..
+        } => map([
+            ("name", Value::from(name.as_str())),
+            ("kind", Value::from("struct")),
+            ("description", Value::from(description.clone())),
+            (
+                "attributes",
+                Value::from(
+                    attribs
+                        .iter()
+                        .zip(attrib_info)
+                        .map(|((name, ty), info)| {
+                            map([
+                                ("name", Value::from(name.as_str())),
+                                ("type", Value::from(ty.name())),
+                                ("description", Value::from(info.description())),
+                            ])
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+        ]),
+        Type::Enum {
+            name,
+            description,
+            size,
+            entries,
+            entry_info,
+            ..
+        } => map([
+            ("name", Value::from(name.as_str())),
+            ("kind", Value::from("enum")),
+            ("description", Value::from(description.clone())),
+            ("size", Value::from(*size)),
+            (
+                "entries",
+                Value::from(
+                    entries
+                        .iter()
+                        .zip(entry_info)
+                        .map(|((name, value), info)| {
+                            map([
+                                ("name", Value::from(name.as_str())),
+                                ("value", Value::from(*value)),
+                                ("description", Value::from(info.description())),
+                                ("deprecated", Value::from(info.is_deprecated())),
+                            ])
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+        ]),
+        Type::Union {
+            name,
+            description,
+            tag,
+            variants,
+            ..
+        } => map([
+            ("name", Value::from(name.as_str())),
+            ("kind", Value::from("union")),
+            ("description", Value::from(description.clone())),
+            ("tag", Value::from(tag.name())),
+            (
+                "variants",
+                Value::from(
+                    variants
+                        .iter()
+                        .map(|(entry, ty)| {
+                            map([
+                                ("entry", Value::from(entry.as_str())),
+                                ("type", Value::from(ty.name())),
+                            ])
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            ),
+        ]),
+        Type::Array { len, ty: element } => map([
+            ("name", Value::from(ty.name())),
+            ("kind", Value::from("array")),
+            ("len", Value::from(*len)),
+            ("type", Value::from(element.name())),
+        ]),
+    }
+}
+
+fn message_value(message: &Message) -> Value {
+    let signals: Vec<Value> = message_layout(message)
+        .iter()
+        .map(|signal| {
+            let (kind, scale, offset, enum_name) = match &signal.kind {
+                SignalKind::Unsigned => ("unsigned", 1.0, 0.0, None),
+                SignalKind::Signed => ("signed", 1.0, 0.0, None),
+                SignalKind::Decimal { offset, scale } => ("decimal", *scale, *offset, None),
+                SignalKind::Enum { name, .. } => ("enum", 1.0, 0.0, Some(*name)),
+            };
+            map([
+                ("name", Value::from(signal.name)),
+                ("bit_offset", Value::from(signal.offset)),
+                ("size", Value::from(signal.size)),
+                ("kind", Value::from(kind)),
+                ("scale", Value::from(scale)),
+                ("offset", Value::from(offset)),
+                ("enum", Value::from(enum_name)),
+            ])
+        })
+        .collect();
+    map([
+        ("name", Value::from(message.name())),
+        ("description", Value::from(message.description())),
+        ("id", Value::from(message.id().as_u32())),
+        ("ide", Value::from(message.id().ide())),
+        ("dlc", Value::from(message.dlc())),
+        ("fill", Value::from(message.fill())),
+        ("bus", Value::from(message.bus().name())),
+        ("signals", Value::from(signals)),
+    ])
+}
+
+fn node_value(node: &Node) -> Value {
+    let object_entries: Vec<Value> = node
+        .object_entries()
+        .iter()
+        .map(|oe| {
+            let access = match oe.access() {
+                ObjectEntryAccess::Const => "const".to_owned(),
+                ObjectEntryAccess::Local => "local".to_owned(),
+                ObjectEntryAccess::Global => "global".to_owned(),
+                ObjectEntryAccess::Protected { level } => format!("protected({level})"),
+            };
+            map([
+                ("name", Value::from(oe.name())),
+                ("id", Value::from(oe.id())),
+                ("description", Value::from(oe.description())),
+                ("unit", Value::from(oe.unit())),
+                ("type", Value::from(oe.ty().name())),
+                ("access", Value::from(access)),
+                ("default", Value::from(oe.default_value())),
+            ])
+        })
+        .collect();
+    let names = |messages: &Vec<crate::config::MessageRef>| {
+        Value::from(
+            messages
+                .iter()
+                .map(|m| Value::from(m.name()))
+                .collect::<Vec<_>>(),
+        )
+    };
+    map([
+        ("name", Value::from(node.name())),
+        ("id", Value::from(node.id())),
+        ("description", Value::from(node.description().cloned())),
+        ("extern", Value::from(node.is_extern())),
+        ("od_protocol", Value::from(node.has_od_protocol())),
+        ("object_entries", Value::from(object_entries)),
+        ("tx_messages", names(node.tx_messages())),
+        ("rx_messages", names(node.rx_messages())),
+        (
+            "tx_schedule",
+            Value::from(
+                node.tx_schedule()
+                    .slots()
+                    .iter()
+                    .map(|slot| {
+                        map([
+                            ("message", Value::from(slot.message().name())),
+                            ("period_us", Value::from(slot.period().as_micros() as u64)),
+                            ("offset_us", Value::from(slot.offset().as_micros() as u64)),
+                        ])
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "rx_timeouts",
+            Value::from(
+                node.rx_timeouts()
+                    .iter()
+                    .map(|(message, timeout)| {
+                        map([
+                            ("message", Value::from(message.name())),
+                            ("timeout_us", Value::from(timeout.as_micros() as u64)),
+                        ])
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "buses",
+            Value::from(
+                node.buses()
+                    .iter()
+                    .map(|b| Value::from(b.name()))
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+    ])
+}
+
+/// The network as a template context, the top level variables are
+/// `name`, `description`, `version`, `author`, `build_time`, `config_hash`,
+/// `buses`, `types`, `nodes` and `messages`.
+pub fn template_context(network: &Network) -> Value {
+    map([
+        ("name", Value::from(network.name())),
+        ("description", Value::from(network.description())),
+        ("version", Value::from(network.version().map(|version| version.to_string()))),
+        ("author", Value::from(network.author())),
+        ("build_time", Value::from(network.build_time().to_rfc3339())),
+        ("config_hash", Value::from(network.config_hash())),
+        (
+            "buses",
+            Value::from(
+                network
+                    .buses()
+                    .iter()
+                    .map(|bus| {
+                        map([
+                            ("name", Value::from(bus.name())),
+                            ("id", Value::from(bus.id())),
+                            ("description", Value::from(bus.description())),
+                            ("connector", Value::from(bus.connector())),
+                            ("baudrate", Value::from(bus.baudrate())),
+                            ("sample_point", Value::from(bus.sample_point())),
+                            ("sjw", Value::from(bus.sjw())),
+                        ])
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        (
+            "types",
+            Value::from(network.types().iter().map(|ty| type_value(ty)).collect::<Vec<_>>()),
+        ),
+        (
+            "nodes",
+            Value::from(network.nodes().iter().map(|n| node_value(n)).collect::<Vec<_>>()),
+        ),
+        (
+            "messages",
+            Value::from(
+                network
+                    .messages()
+                    .iter()
+                    .map(|m| message_value(m))
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+    ])
+}
+
+/// Renders a jinja2 template (minijinja dialect) with the network context.
+pub fn render(network: &Network, template: &str) -> Result<String> {
+    let mut env = Environment::new();
+    env.add_template("template", template)
+        .map_err(|e| ConfigError::Template(e.to_string()))?;
+    env.get_template("template")
+        .and_then(|t| t.render(template_context(network)))
+        .map_err(|e| ConfigError::Template(e.to_string()))
+}
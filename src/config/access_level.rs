@@ -0,0 +1,43 @@
+use std::hash::Hash;
+
+use super::ConfigRef;
+
+pub type AccessLevelRef = ConfigRef<AccessLevel>;
+
+/// Access level required to write protected object entries.
+/// Levels are ordered by their definition, a client with a higher
+/// rank may also write entries protected by a lower level.
+#[derive(Debug, PartialEq)]
+pub struct AccessLevel {
+    name: String,
+    description: Option<String>,
+    rank: u8,
+}
+
+impl AccessLevel {
+    pub fn new(name: &str, description: Option<&str>, rank: u8) -> Self {
+        Self {
+            name: name.to_owned(),
+            description: description.map(str::to_owned),
+            rank,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn rank(&self) -> u8 {
+        self.rank
+    }
+}
+
+impl Hash for AccessLevel {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.rank);
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::Range;
+
+use super::{ObjectEntry, ObjectEntryAccess};
+
+/// Bits of the value carried by a single get_resp or set_req frame.
+pub const OD_FRAGMENT_BITS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OdFrameKind {
+    GetReq,
+    GetResp,
+    SetReq,
+    SetResp,
+}
+
+impl OdFrameKind {
+    /// Requests are sent by the client, responses by the server.
+    pub fn is_request(&self) -> bool {
+        matches!(self, OdFrameKind::GetReq | OdFrameKind::SetReq)
+    }
+}
+
+/// A single frame of an object entry access with the expected header values.
+/// The client id is chosen by the client and echoed by the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OdFrame {
+    kind: OdFrameKind,
+    od_index: u32,
+    server_id: u8,
+    // (sof, eof, toggle) of fragmented frames.
+    fragment: Option<(bool, bool, bool)>,
+    data: Option<Range<usize>>,
+}
+
+impl OdFrame {
+    pub fn kind(&self) -> OdFrameKind {
+        self.kind
+    }
+    pub fn od_index(&self) -> u32 {
+        self.od_index
+    }
+    pub fn server_id(&self) -> u8 {
+        self.server_id
+    }
+    /// None for frames without a sof, eof and toggle bit (get_req and set_resp).
+    pub fn sof(&self) -> Option<bool> {
+        self.fragment.map(|(sof, _, _)| sof)
+    }
+    pub fn eof(&self) -> Option<bool> {
+        self.fragment.map(|(_, eof, _)| eof)
+    }
+    pub fn toggle(&self) -> Option<bool> {
+        self.fragment.map(|(_, _, toggle)| toggle)
+    }
+    /// Bits of the raw value carried in the data field of the frame,
+    /// starting at bit 0 of the data field.
+    pub fn data_bits(&self) -> Option<&Range<usize>> {
+        self.data.as_ref()
+    }
+}
+
+/// Sequence of get/set frames required to read or write an object entry.
+///
+/// Values wider than 32 bits are split into fragments, the first fragment
+/// carries the least significant bits and has sof set, the last one has eof
+/// set and the toggle bit alternates starting with 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessPlan {
+    read: Vec<OdFrame>,
+    write: Option<Vec<OdFrame>>,
+}
+
+impl AccessPlan {
+    /// Plan to access the object entry on the node with the `server_id`.
+    pub fn new(object_entry: &ObjectEntry, server_id: u8) -> Self {
+        let od_index = object_entry.id();
+        let size = object_entry.ty().size() as usize;
+        let fragment_count = size.div_ceil(OD_FRAGMENT_BITS).max(1);
+        let fragments = |kind: OdFrameKind| {
+            (0..fragment_count).map(move |i| OdFrame {
+                kind,
+                od_index,
+                server_id,
+                fragment: Some((i == 0, i + 1 == fragment_count, i % 2 == 1)),
+                data: Some(i * OD_FRAGMENT_BITS..((i + 1) * OD_FRAGMENT_BITS).min(size)),
+            })
+        };
+        let frame = |kind: OdFrameKind| OdFrame {
+            kind,
+            od_index,
+            server_id,
+            fragment: None,
+            data: None,
+        };
+        let read = std::iter::once(frame(OdFrameKind::GetReq))
+            .chain(fragments(OdFrameKind::GetResp))
+            .collect();
+        let write = match object_entry.access() {
+            ObjectEntryAccess::Const | ObjectEntryAccess::Local => None,
+            ObjectEntryAccess::Global | ObjectEntryAccess::Protected { .. } => Some(
+                fragments(OdFrameKind::SetReq)
+                    .chain(std::iter::once(frame(OdFrameKind::SetResp)))
+                    .collect(),
+            ),
+        };
+        Self { read, write }
+    }
+    /// get_req followed by the fragments of the get_resp.
+    pub fn read(&self) -> &Vec<OdFrame> {
+        &self.read
+    }
+    /// fragments of the set_req followed by the set_resp,
+    /// None if the object entry can't be written by clients.
+    pub fn write(&self) -> Option<&Vec<OdFrame>> {
+        self.write.as_ref()
+    }
+}
@@ -0,0 +1,139 @@
+use std::ops::RangeInclusive;
+
+/// Register settings of a CAN controller for a baudrate.
+/// A bit consists of the sync segment (1 tq), seg1 (propagation and
+/// phase segment 1) and seg2 (phase segment 2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitTiming {
+    pub prescaler: u32,
+    pub seg1: u8,
+    pub seg2: u8,
+    pub sjw: u8,
+}
+
+impl BitTiming {
+    /// number of time quanta per bit.
+    pub fn tq_per_bit(&self) -> u32 {
+        1 + self.seg1 as u32 + self.seg2 as u32
+    }
+    /// fraction of the bit at which the bus is sampled.
+    pub fn sample_point(&self) -> f64 {
+        (1 + self.seg1 as u32) as f64 / self.tq_per_bit() as f64
+    }
+    pub fn baudrate(&self, clock_hz: u32) -> f64 {
+        clock_hz as f64 / (self.prescaler * self.tq_per_bit()) as f64
+    }
+}
+
+/// Ranges of the bit timing registers supported by a CAN controller.
+#[derive(Debug, Clone)]
+pub struct BitTimingConstraints {
+    pub prescaler: RangeInclusive<u32>,
+    pub seg1: RangeInclusive<u8>,
+    pub seg2: RangeInclusive<u8>,
+    pub sjw_max: u8,
+}
+
+impl BitTimingConstraints {
+    /// STM32 bxCAN.
+    pub fn bxcan() -> Self {
+        Self {
+            prescaler: 1..=1024,
+            seg1: 1..=16,
+            seg2: 1..=8,
+            sjw_max: 4,
+        }
+    }
+    /// Bosch M_CAN / STM32 FDCAN (nominal bit timing).
+    pub fn fdcan() -> Self {
+        Self {
+            prescaler: 1..=512,
+            seg1: 2..=255,
+            seg2: 2..=128,
+            sjw_max: 128,
+        }
+    }
+    /// NXP FlexCAN (classic bit timing).
+    pub fn flexcan() -> Self {
+        Self {
+            prescaler: 1..=256,
+            seg1: 2..=16,
+            seg2: 2..=8,
+            sjw_max: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BitTimingOptions {
+    pub constraints: BitTimingConstraints,
+    /// targeted sample point (CiA recommends 87.5%).
+    pub sample_point: f64,
+    /// largest accepted deviation from the targeted sample point.
+    pub sample_point_tolerance: f64,
+    /// fixed synchronization jump width, otherwise the largest possible one.
+    pub sjw: Option<u8>,
+}
+
+impl Default for BitTimingOptions {
+    fn default() -> Self {
+        Self {
+            constraints: BitTimingConstraints::bxcan(),
+            sample_point: 0.875,
+            sample_point_tolerance: 0.05,
+            sjw: None,
+        }
+    }
+}
+
+/// Searches the settings which achieve the exact baudrate with the sample point
+/// closest to the targeted one, preferring more time quanta per bit.
+pub fn compute_bit_timing(
+    clock_hz: u32,
+    baudrate: u32,
+    options: &BitTimingOptions,
+) -> Option<BitTiming> {
+    let constraints = &options.constraints;
+    let mut best: Option<(f64, BitTiming)> = None;
+    for prescaler in constraints.prescaler.clone() {
+        let Some(divider) = prescaler.checked_mul(baudrate) else {
+            break;
+        };
+        if divider == 0 || !clock_hz.is_multiple_of(divider) {
+            continue;
+        }
+        let tq = clock_hz / divider;
+        let min_tq = 1 + *constraints.seg1.start() as u32 + *constraints.seg2.start() as u32;
+        let max_tq = 1 + *constraints.seg1.end() as u32 + *constraints.seg2.end() as u32;
+        if tq < min_tq || tq > max_tq {
+            continue;
+        }
+        let seg2 = ((tq as f64 * (1.0 - options.sample_point)).round() as u32).clamp(
+            (*constraints.seg2.start() as u32).max((tq - 1).saturating_sub(*constraints.seg1.end() as u32)),
+            (*constraints.seg2.end() as u32).min(tq - 1 - *constraints.seg1.start() as u32),
+        );
+        let seg1 = tq - 1 - seg2;
+        let max_sjw = (seg2 as u8).min(constraints.sjw_max);
+        let sjw = match options.sjw {
+            Some(sjw) if sjw > max_sjw => continue,
+            Some(sjw) => sjw,
+            None => max_sjw,
+        };
+        let timing = BitTiming {
+            prescaler,
+            seg1: seg1 as u8,
+            seg2: seg2 as u8,
+            sjw,
+        };
+        let error = (timing.sample_point() - options.sample_point).abs();
+        if error > options.sample_point_tolerance {
+            continue;
+        }
+        // prescalers are visited in increasing order, so on equal error
+        // the timing with more time quanta is kept.
+        if best.is_none_or(|(best_error, _)| error < best_error - 1e-9) {
+            best = Some((error, timing));
+        }
+    }
+    best.map(|(_, timing)| timing)
+}
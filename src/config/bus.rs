@@ -1,6 +1,9 @@
 use std::hash::Hash;
 
-use super::ConfigRef;
+use super::{
+    bit_timing::{compute_bit_timing, BitTiming, BitTimingOptions},
+    ConfigRef,
+};
 
 
 
@@ -12,14 +15,23 @@ pub struct Bus {
     id : u32,
     baudrate : u32,
     name : String,
+    description : Option<String>,
+    connector : Option<String>,
+    sample_point : Option<f64>,
+    sjw : Option<u8>,
 }
 
 impl Bus {
-    pub fn new(name : &str, id : u32, baudrate : u32) -> Self{
+    pub fn new(name : &str, id : u32, baudrate : u32, sample_point : Option<f64>, sjw : Option<u8>,
+               description : Option<String>, connector : Option<String>) -> Self{
         Self {
             id,
             baudrate,
             name : name.to_owned(),
+            description,
+            connector,
+            sample_point,
+            sjw,
         }
     }
     pub fn id(&self) -> u32 {
@@ -31,9 +43,55 @@ impl Bus {
     pub fn name(&self) -> &str {
         &self.name
     }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// label of the physical connector of the bus.
+    pub fn connector(&self) -> Option<&str> {
+        self.connector.as_deref()
+    }
+    /// sample point in percent of the bit time.
+    pub fn sample_point(&self) -> Option<f64> {
+        self.sample_point
+    }
+    /// synchronization jump width in time quanta.
+    pub fn sjw(&self) -> Option<u8> {
+        self.sjw
+    }
+    /// Default options with the sample point and sjw of the bus.
+    pub fn bit_timing_options(&self) -> BitTimingOptions {
+        let default = BitTimingOptions::default();
+        BitTimingOptions {
+            sample_point: self.sample_point.map_or(default.sample_point, |p| p / 100.0),
+            sjw: self.sjw,
+            ..default
+        }
+    }
+    /// Register settings for the baudrate and sample point (87.5% if not
+    /// configured) of the bus on a bxCAN controller clocked with clock_hz.
+    pub fn bit_timing(&self, clock_hz: u32) -> Option<BitTiming> {
+        self.bit_timing_with(clock_hz, &self.bit_timing_options())
+    }
+    pub fn bit_timing_with(&self, clock_hz: u32, options: &BitTimingOptions) -> Option<BitTiming> {
+        compute_bit_timing(clock_hz, self.baudrate, options)
+    }
 }
 
 
+/// The sample point is compared bitwise like it's hashed,
+/// so equal buses always have the same hash.
+impl PartialEq for Bus {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+            && self.baudrate == other.baudrate
+            && self.name == other.name
+            && self.description == other.description
+            && self.connector == other.connector
+            && self.sample_point.map(f64::to_bits) == other.sample_point.map(f64::to_bits)
+            && self.sjw == other.sjw
+    }
+}
+
 impl Hash for Bus {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         state.write_u32(self.id);
@@ -41,5 +99,19 @@ impl Hash for Bus {
         for b in self.name.bytes() {
             state.write_u8(b);
         }
+        match self.sample_point {
+            Some(sample_point) => {
+                state.write_u8(1);
+                state.write_u64(sample_point.to_bits());
+            }
+            None => state.write_u8(0),
+        }
+        match self.sjw {
+            Some(sjw) => {
+                state.write_u8(1);
+                state.write_u8(sjw);
+            }
+            None => state.write_u8(0),
+        }
     }
 }
@@ -1,11 +1,11 @@
 use std::{hash::Hash, time::Duration};
 
-use super::{ConfigRef, MessageRef, Visibility, Message};
+use super::{ConfigRef, MessageRef, SignalRef, Visibility, Message};
 
 
 pub type CommandRef = ConfigRef<Command>;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Command {
     name: String,
     description: Option<String>,
@@ -13,6 +13,7 @@ pub struct Command {
     rx_message: MessageRef,
     visibility: Visibility,
     expected_interval : Duration,
+    responders : Vec<String>,
 }
 
 impl Hash for Command {
@@ -23,6 +24,12 @@ impl Hash for Command {
         self.visibility.hash(state);
         let us =  self.expected_interval().as_micros();
         state.write_u128(us);
+        // the first responder is the node defining the command.
+        for responder in self.responders.iter().skip(1) {
+            for b in responder.bytes() {
+                state.write_u8(b);
+            }
+        }
     }
 }
 
@@ -39,9 +46,13 @@ impl Command {
             tx_message,
             rx_message,
             visibility,
-            expected_interval
+            expected_interval,
+            responders : vec![],
         }
     }
+    pub(crate) fn with_responders(self, responders : Vec<String>) -> Self {
+        Self { responders, ..self }
+    }
     pub fn visibility(&self) -> &Visibility {
         &self.visibility
     }
@@ -63,4 +74,17 @@ impl Command {
     pub fn rx_message(&self) -> &Message {
         &self.rx_message
     }
+    /// Names of the nodes executing the command, the node defining the
+    /// command comes first. A caller has to wait for a response of each.
+    pub fn responders(&self) -> &[String] {
+        &self.responders
+    }
+    pub fn expected_responses(&self) -> usize {
+        self.responders.len()
+    }
+    /// Signal of the response containing the node id of the responder,
+    /// None if only a single node executes the command.
+    pub fn responder_id(&self) -> Option<&SignalRef> {
+        self.rx_message.sender_id()
+    }
 }
@@ -0,0 +1,180 @@
+use std::fmt::Display;
+
+use super::{Message, Network, Type};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompatibilityChange {
+    /// old firmware can not coexist with the new configuration.
+    Breaking(String),
+    /// the new configuration only adds to the old one.
+    Additive(String),
+}
+
+#[derive(Debug, Default)]
+pub struct CompatibilityReport {
+    changes: Vec<CompatibilityChange>,
+}
+
+impl CompatibilityReport {
+    fn breaking(&mut self, description: String) {
+        self.changes.push(CompatibilityChange::Breaking(description));
+    }
+    fn additive(&mut self, description: String) {
+        self.changes.push(CompatibilityChange::Additive(description));
+    }
+    pub fn changes(&self) -> &Vec<CompatibilityChange> {
+        &self.changes
+    }
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &str> {
+        self.changes.iter().filter_map(|c| match c {
+            CompatibilityChange::Breaking(desc) => Some(desc.as_str()),
+            CompatibilityChange::Additive(_) => None,
+        })
+    }
+    pub fn additive_changes(&self) -> impl Iterator<Item = &str> {
+        self.changes.iter().filter_map(|c| match c {
+            CompatibilityChange::Breaking(_) => None,
+            CompatibilityChange::Additive(desc) => Some(desc.as_str()),
+        })
+    }
+    pub fn is_compatible(&self) -> bool {
+        self.breaking_changes().next().is_none()
+    }
+}
+
+impl Display for CompatibilityReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            match change {
+                CompatibilityChange::Breaking(desc) => writeln!(f, "BREAKING : {desc}")?,
+                CompatibilityChange::Additive(desc) => writeln!(f, "ADDITIVE : {desc}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_message(report: &mut CompatibilityReport, old: &Message, new: &Message) {
+    let name = old.name();
+    if old.id() != new.id() || old.bus().name() != new.bus().name() {
+        report.breaking(format!(
+            "message {name} moved from {} on {} to {} on {}",
+            old.id(),
+            old.bus().name(),
+            new.id(),
+            new.bus().name()
+        ));
+    }
+    if new.dlc() < old.dlc() {
+        report.breaking(format!(
+            "dlc of message {name} shrank from {} to {}",
+            old.dlc(),
+            new.dlc()
+        ));
+    } else if new.dlc() > old.dlc() {
+        report.additive(format!(
+            "dlc of message {name} grew from {} to {}",
+            old.dlc(),
+            new.dlc()
+        ));
+    }
+    for old_signal in old.signals() {
+        match new.signals().iter().find(|s| s.name() == old_signal.name()) {
+            Some(new_signal) => {
+                if new_signal.byte_offset() != old_signal.byte_offset() {
+                    report.breaking(format!(
+                        "signal {} of message {name} moved from bit {} to bit {}",
+                        old_signal.name(),
+                        old_signal.byte_offset(),
+                        new_signal.byte_offset()
+                    ));
+                }
+                if new_signal.ty() != old_signal.ty() {
+                    report.breaking(format!(
+                        "signal {} of message {name} changed its encoding",
+                        old_signal.name()
+                    ));
+                }
+            }
+            None => report.breaking(format!(
+                "signal {} was removed from message {name}",
+                old_signal.name()
+            )),
+        }
+    }
+    for new_signal in new.signals() {
+        if !old.signals().iter().any(|s| s.name() == new_signal.name()) {
+            report.additive(format!(
+                "signal {} was added to message {name}",
+                new_signal.name()
+            ));
+        }
+    }
+}
+
+fn check_enum(
+    report: &mut CompatibilityReport,
+    name: &str,
+    old_entries: &[(String, u64)],
+    new_entries: &[(String, u64)],
+) {
+    for (entry, old_value) in old_entries {
+        match new_entries.iter().find(|(e, _)| e == entry) {
+            Some((_, new_value)) if new_value != old_value => report.breaking(format!(
+                "enum entry {name}::{entry} was renumbered from {old_value} to {new_value}"
+            )),
+            Some(_) => (),
+            None => report.breaking(format!("enum entry {name}::{entry} was removed")),
+        }
+    }
+    for (entry, _) in new_entries {
+        if !old_entries.iter().any(|(e, _)| e == entry) {
+            report.additive(format!("enum entry {name}::{entry} was added"));
+        }
+    }
+}
+
+/// Compares the wire format of two networks.
+pub fn check_wire_compatibility(old: &Network, new: &Network) -> CompatibilityReport {
+    let mut report = CompatibilityReport::default();
+
+    for old_message in old.messages() {
+        match new.messages().iter().find(|m| m.name() == old_message.name()) {
+            Some(new_message) => check_message(&mut report, old_message, new_message),
+            None => report.breaking(format!("message {} was removed", old_message.name())),
+        }
+    }
+    for new_message in new.messages() {
+        if old.messages().iter().any(|m| m.name() == new_message.name()) {
+            continue;
+        }
+        let reused = old.messages().iter().find(|m| {
+            m.id() == new_message.id() && m.bus().name() == new_message.bus().name()
+        });
+        match reused {
+            Some(old_message) => report.breaking(format!(
+                "id {} of removed message {} is reused by message {}",
+                new_message.id(),
+                old_message.name(),
+                new_message.name()
+            )),
+            None => report.additive(format!("message {} was added", new_message.name())),
+        }
+    }
+
+    for old_type in old.types() {
+        let Type::Enum { name, entries: old_entries, .. } = old_type as &Type else {
+            continue;
+        };
+        let new_entries = new.types().iter().find_map(|t| match t as &Type {
+            Type::Enum { name: n, entries, .. } if n == name => Some(entries),
+            _ => None,
+        });
+        match new_entries {
+            Some(new_entries) => check_enum(&mut report, name, old_entries, new_entries),
+            None => report.breaking(format!("enum {name} was removed")),
+        }
+    }
+
+    report
+}
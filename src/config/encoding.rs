@@ -7,7 +7,7 @@ use super::{TypeRef, SignalRef};
 // describes how to map Type to signals.
 // vector of elements with name and type of the encoded Types
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct MessageEncoding {
     attributes : Vec<TypeSignalEncoding>,
 }
@@ -29,12 +29,20 @@ impl MessageEncoding {
     pub fn attributes(&self) -> &Vec<TypeSignalEncoding> {
         &self.attributes
     }
+    /// tag signal and tag value, which have to match for the signal
+    /// to be present, None if the signal isn't part of a union variant.
+    pub fn multiplexor_of(&self, signal_name: &str) -> Option<(&SignalRef, u64)> {
+        self.attributes
+            .iter()
+            .find_map(|attrib| attrib.multiplexor_of(signal_name))
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum TypeSignalEncoding {
     Composite(CompositeSignalEncoding),
     Primitive(PrimitiveSignalEncoding),
+    Multiplexed(MultiplexedSignalEncoding),
 }
 
 impl Hash for TypeSignalEncoding {
@@ -48,6 +56,10 @@ impl Hash for TypeSignalEncoding {
                 state.write_u8(1);
                 prim.hash(state);
             }
+            TypeSignalEncoding::Multiplexed(mux) => {
+                state.write_u8(2);
+                mux.hash(state);
+            }
         }
     }
 }
@@ -57,21 +69,73 @@ impl TypeSignalEncoding {
         match &self {
             TypeSignalEncoding::Composite(comp) => comp.name(),
             TypeSignalEncoding::Primitive(prim) => prim.name(),
+            TypeSignalEncoding::Multiplexed(mux) => mux.name(),
         }
     }
     pub fn ty(&self) -> &TypeRef {
         match &self {
             TypeSignalEncoding::Composite(comp) => comp.ty(),
             TypeSignalEncoding::Primitive(prim) => prim.ty(),
+            TypeSignalEncoding::Multiplexed(mux) => mux.ty(),
+        }
+    }
+    pub(crate) fn with_padding(self, padding : usize) -> Self {
+        match self {
+            TypeSignalEncoding::Composite(comp) => TypeSignalEncoding::Composite(comp.with_padding(padding)),
+            TypeSignalEncoding::Primitive(prim) => TypeSignalEncoding::Primitive(prim.with_padding(padding)),
+            TypeSignalEncoding::Multiplexed(mux) => TypeSignalEncoding::Multiplexed(mux.with_padding(padding)),
+        }
+    }
+    /// unused bits in front of the attribute (see AttributePadding).
+    pub fn padding(&self) -> usize {
+        match &self {
+            TypeSignalEncoding::Composite(comp) => comp.padding(),
+            TypeSignalEncoding::Primitive(prim) => prim.padding(),
+            TypeSignalEncoding::Multiplexed(mux) => mux.padding(),
+        }
+    }
+    /// all signals the attribute is encoded in.
+    pub fn signals(&self) -> Vec<&SignalRef> {
+        match &self {
+            TypeSignalEncoding::Composite(comp) => comp
+                .attributes()
+                .iter()
+                .flat_map(|attrib| attrib.signals())
+                .collect(),
+            TypeSignalEncoding::Primitive(prim) => vec![prim.signal()],
+            TypeSignalEncoding::Multiplexed(mux) => std::iter::once(mux.tag().signal())
+                .chain(mux.variants().iter().flat_map(|(_, _, variant)| variant.signals()))
+                .collect(),
+        }
+    }
+    /// tag signal and tag value, which have to match for the signal
+    /// to be present, None if the signal isn't part of a union variant.
+    pub fn multiplexor_of(&self, signal_name: &str) -> Option<(&SignalRef, u64)> {
+        match &self {
+            TypeSignalEncoding::Composite(comp) => comp
+                .attributes()
+                .iter()
+                .find_map(|attrib| attrib.multiplexor_of(signal_name)),
+            TypeSignalEncoding::Primitive(_) => None,
+            TypeSignalEncoding::Multiplexed(mux) => {
+                mux.variants().iter().find_map(|(_, value, variant)| {
+                    variant
+                        .signals()
+                        .iter()
+                        .any(|s| s.name() == signal_name)
+                        .then_some((mux.tag().signal(), *value))
+                })
+            }
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct CompositeSignalEncoding {
     composite_name : String,
     attributes : Vec<TypeSignalEncoding>,
     ty : TypeRef,
+    padding : usize,
 }
 
 impl Hash for CompositeSignalEncoding {
@@ -83,6 +147,7 @@ impl Hash for CompositeSignalEncoding {
             a.hash(state);
         }
         self.ty.hash(state);
+        state.write_usize(self.padding);
     }
 }
 
@@ -93,8 +158,15 @@ impl CompositeSignalEncoding {
             composite_name,
             attributes,
             ty,
+            padding : 0,
         }
     }
+    pub(crate) fn with_padding(self, padding : usize) -> Self {
+        Self { padding, ..self }
+    }
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
     pub fn name(&self) -> &str {
         &self.composite_name
     }
@@ -106,11 +178,76 @@ impl CompositeSignalEncoding {
     }
 }
 
-#[derive(Debug)]
+/// Encoding of a tagged union, the payloads of all variants
+/// start at the same bit after the tag.
+#[derive(Debug, PartialEq)]
+pub struct MultiplexedSignalEncoding {
+    name : String,
+    tag : PrimitiveSignalEncoding,
+    /// (tag entry, tag value, payload)
+    variants : Vec<(String, u64, TypeSignalEncoding)>,
+    ty : TypeRef,
+    padding : usize,
+}
+
+impl Hash for MultiplexedSignalEncoding {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        self.tag.hash(state);
+        for (entry, value, variant) in &self.variants {
+            for b in entry.bytes() {
+                state.write_u8(b);
+            }
+            state.write_u64(*value);
+            variant.hash(state);
+        }
+        self.ty.hash(state);
+        state.write_usize(self.padding);
+    }
+}
+
+impl MultiplexedSignalEncoding {
+    pub fn new(name : String,
+               tag : PrimitiveSignalEncoding,
+               variants : Vec<(String, u64, TypeSignalEncoding)>,
+               ty : TypeRef) -> Self {
+        Self {
+            name,
+            tag,
+            variants,
+            ty,
+            padding : 0,
+        }
+    }
+    pub(crate) fn with_padding(self, padding : usize) -> Self {
+        Self { padding, ..self }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// encoding of the tag enum.
+    pub fn tag(&self) -> &PrimitiveSignalEncoding {
+        &self.tag
+    }
+    pub fn variants(&self) -> &Vec<(String, u64, TypeSignalEncoding)> {
+        &self.variants
+    }
+    pub fn ty(&self) -> &TypeRef {
+        &self.ty
+    }
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub struct PrimitiveSignalEncoding {
     name : String,
     ty : TypeRef,
     signal : SignalRef,
+    padding : usize,
 }
 
 impl Hash for PrimitiveSignalEncoding {
@@ -120,6 +257,7 @@ impl Hash for PrimitiveSignalEncoding {
         }
         self.ty.hash(state);
         self.signal.hash(state);
+        state.write_usize(self.padding);
 
     }
 }
@@ -131,9 +269,16 @@ impl PrimitiveSignalEncoding {
         Self {
             name,
             ty,
-            signal
+            signal,
+            padding : 0,
         }
     }
+    pub(crate) fn with_padding(self, padding : usize) -> Self {
+        Self { padding, ..self }
+    }
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
     pub fn name(&self) -> &str {
         &self.name
     }
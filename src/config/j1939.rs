@@ -0,0 +1,78 @@
+use crate::errors::{self, ConfigError};
+
+/// 29 bit extended id composed of a SAE J1939 parameter group number,
+/// priority and source address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct J1939Id {
+    priority: u8,
+    pgn: u32,
+    source_address: u8,
+}
+
+/// destination address of PDU1 messages, which are always sent to all nodes.
+const GLOBAL_ADDRESS: u32 = 0xFF;
+
+impl J1939Id {
+    pub fn new(priority: u8, pgn: u32, source_address: u8) -> errors::Result<J1939Id> {
+        if priority > 7 {
+            return Err(ConfigError::InvalidJ1939Id(format!(
+                "priority {priority} doesn't fit into 3 bits"
+            )));
+        }
+        if pgn > 0x3FFFF {
+            return Err(ConfigError::InvalidJ1939Id(format!(
+                "pgn 0x{pgn:X} doesn't fit into 18 bits"
+            )));
+        }
+        let id = J1939Id {
+            priority,
+            pgn,
+            source_address,
+        };
+        if id.is_pdu1() && pgn & 0xFF != 0 {
+            return Err(ConfigError::InvalidJ1939Id(format!(
+                "pgn 0x{pgn:X} is a PDU1 format, the PDU specific byte must be zero"
+            )));
+        }
+        if source_address > 253 {
+            return Err(ConfigError::InvalidJ1939Id(format!(
+                "source address {source_address} is reserved"
+            )));
+        }
+        Ok(id)
+    }
+    /// Decomposes a 29 bit extended id.
+    pub fn from_ext_id(id: u32) -> J1939Id {
+        let pgn = (id >> 8) & 0x3FFFF;
+        let pdu_format = (pgn >> 8) & 0xFF;
+        J1939Id {
+            priority: ((id >> 26) & 0x7) as u8,
+            pgn: if pdu_format < 240 { pgn & !0xFF } else { pgn },
+            source_address: (id & 0xFF) as u8,
+        }
+    }
+    pub fn to_ext_id(&self) -> u32 {
+        let pgn = if self.is_pdu1() {
+            self.pgn | GLOBAL_ADDRESS
+        } else {
+            self.pgn
+        };
+        ((self.priority as u32) << 26) | (pgn << 8) | self.source_address as u32
+    }
+    pub fn priority(&self) -> u8 {
+        self.priority
+    }
+    pub fn pgn(&self) -> u32 {
+        self.pgn
+    }
+    pub fn source_address(&self) -> u8 {
+        self.source_address
+    }
+    pub fn pdu_format(&self) -> u8 {
+        ((self.pgn >> 8) & 0xFF) as u8
+    }
+    /// PDU1 messages (PDU format < 240) are addressed to a destination.
+    pub fn is_pdu1(&self) -> bool {
+        self.pdu_format() < 240
+    }
+}
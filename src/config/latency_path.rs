@@ -0,0 +1,95 @@
+use std::{hash::Hash, time::Duration};
+
+use super::{CommandRef, ConfigRef, NodeRef, ObjectEntryRef, stream::StreamRef};
+
+pub type LatencyPathRef = ConfigRef<LatencyPath>;
+
+/// Step of a data path, streams and commands are owned by the node,
+/// which transmits them.
+#[derive(Debug, PartialEq, Clone)]
+pub enum LatencyHop {
+    ObjectEntry {
+        node: NodeRef,
+        object_entry: ObjectEntryRef,
+    },
+    Stream {
+        node: NodeRef,
+        stream: StreamRef,
+    },
+    Command {
+        node: NodeRef,
+        command: CommandRef,
+    },
+}
+
+impl LatencyHop {
+    pub fn node(&self) -> &NodeRef {
+        match self {
+            LatencyHop::ObjectEntry { node, .. }
+            | LatencyHop::Stream { node, .. }
+            | LatencyHop::Command { node, .. } => node,
+        }
+    }
+    /// node::element, e.g. secu::state.
+    pub fn name(&self) -> String {
+        let element = match self {
+            LatencyHop::ObjectEntry { object_entry, .. } => object_entry.name(),
+            LatencyHop::Stream { stream, .. } => stream.name(),
+            LatencyHop::Command { command, .. } => command.name(),
+        };
+        format!("{}::{element}", self.node().name())
+    }
+}
+
+/// Data path (e.g. sensor object entry -> stream -> rx node -> command),
+/// whose end-to-end latency has to stay within the budget,
+/// see `analysis::latency`.
+#[derive(Debug, PartialEq)]
+pub struct LatencyPath {
+    name: String,
+    description: Option<String>,
+    budget: Duration,
+    hops: Vec<LatencyHop>,
+}
+
+impl Hash for LatencyPath {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        state.write_u128(self.budget.as_micros());
+        for hop in &self.hops {
+            state.write_u8(match hop {
+                LatencyHop::ObjectEntry { .. } => 0,
+                LatencyHop::Stream { .. } => 1,
+                LatencyHop::Command { .. } => 2,
+            });
+            for b in hop.name().bytes() {
+                state.write_u8(b);
+            }
+        }
+    }
+}
+
+impl LatencyPath {
+    pub fn new(name: String, description: Option<String>, budget: Duration, hops: Vec<LatencyHop>) -> Self {
+        Self {
+            name,
+            description,
+            budget,
+            hops,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+    pub fn hops(&self) -> &Vec<LatencyHop> {
+        &self.hops
+    }
+}
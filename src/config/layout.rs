@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use super::Message;
+
+/// Bit grid of a message, showing which signal occupies which bit.
+///
+/// Bits are numbered in little endian order, bit `i` is bit `i % 8`
+/// of byte `i / 8`. The grid covers the dlc of the message,
+/// so CAN FD messages get one row per byte as well.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LayoutTable {
+    signals: Vec<String>,
+    // index into signals for every bit of the message.
+    bits: Vec<Option<usize>>,
+}
+
+impl LayoutTable {
+    pub fn new(message: &Message) -> Self {
+        let signals: Vec<String> = message
+            .signals()
+            .iter()
+            .map(|signal| signal.name().to_owned())
+            .collect();
+        let end = message
+            .signals()
+            .iter()
+            .map(|signal| signal.byte_offset() + signal.size() as usize)
+            .max()
+            .unwrap_or(0);
+        let byte_count = (message.dlc() as usize).max(end.div_ceil(8));
+        let mut bits = vec![None; byte_count * 8];
+        for (i, signal) in message.signals().iter().enumerate() {
+            let offset = signal.byte_offset();
+            for bit in &mut bits[offset..offset + signal.size() as usize] {
+                *bit = Some(i);
+            }
+        }
+        Self { signals, bits }
+    }
+    pub fn byte_count(&self) -> usize {
+        self.bits.len() / 8
+    }
+    /// Signal names in the order of the message.
+    pub fn signals(&self) -> &Vec<String> {
+        &self.signals
+    }
+    pub fn signal_at(&self, bit: usize) -> Option<&str> {
+        self.bits
+            .get(bit)
+            .copied()
+            .flatten()
+            .map(|i| self.signals[i].as_str())
+    }
+    /// Bits that aren't occupied by any signal.
+    pub fn unused_bits(&self) -> Vec<usize> {
+        (0..self.bits.len()).filter(|bit| self.bits[*bit].is_none()).collect()
+    }
+    fn label(index: usize) -> char {
+        const LABELS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        LABELS.get(index).map(|c| *c as char).unwrap_or('#')
+    }
+}
+
+/// Renders one row per byte with bit 7 on the left, followed by a legend.
+/// Signals are labeled with letters, unused bits are shown as `.`.
+impl Display for LayoutTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "byte |")?;
+        for bit in (0..8).rev() {
+            write!(f, " {bit}")?;
+        }
+        writeln!(f)?;
+        for byte in 0..self.byte_count() {
+            write!(f, "{byte:>4} |")?;
+            for bit in (0..8).rev() {
+                match self.bits[byte * 8 + bit] {
+                    Some(i) => write!(f, " {}", Self::label(i))?,
+                    None => write!(f, " .")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        for (i, name) in self.signals.iter().enumerate() {
+            let mut occupied = (0..self.bits.len()).filter(|bit| self.bits[*bit] == Some(i));
+            match (occupied.next(), occupied.next_back()) {
+                (Some(first), last) => writeln!(
+                    f,
+                    "{}: {name} (bits {first}..={})",
+                    Self::label(i),
+                    last.unwrap_or(first)
+                )?,
+                (None, _) => writeln!(f, "{}: {name}", Self::label(i))?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,21 +1,71 @@
-use std::{fmt::Display, hash::Hash, sync::OnceLock, time::Duration};
+use std::{fmt::Display, hash::Hash, time::Duration};
 
-use super::{ConfigRef, MessageEncoding, SignalRef, Visibility, bus::BusRef, stream::StreamRef, CommandRef};
+use super::{ConfigRef, ConfigWeak, Lifecycle, LayoutTable, MessageEncoding, SignalRef, Visibility, bus::BusRef, stream::Stream, Command, TransportChannel};
 
 
-#[derive(Debug)]
+/// Interval in which every node transmits its heartbeat.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Purpose of a message, streams, commands and transport channels
+/// are referenced weakly, because they own the message. Upgrading
+/// them returns None once the network was dropped.
+#[derive(Debug, Clone)]
 pub enum MessageUsage {
-    Stream(StreamRef),
-    CommandReq(CommandRef),
-    CommandResp(CommandRef),
+    Stream(ConfigWeak<Stream>),
+    CommandReq(ConfigWeak<Command>),
+    CommandResp(ConfigWeak<Command>),
+    TransportData(ConfigWeak<TransportChannel>),
+    TransportFlowControl(ConfigWeak<TransportChannel>),
     GetResp,
     GetReq,
     SetResp,
     SetReq,
     Heartbeat,
+    /// sporadic messages, which signal faults and have the highest priority.
+    Emergency,
+    /// diagnostic output, which isn't required during operation.
+    Debug{interval : Option<Duration>},
+    /// messages of a firmware update protocol.
+    Bootloader,
     External{interval : Duration},
 }
 
+/// Usages referencing streams, commands or transport channels are equal
+/// if they reference elements with the same name. Comparing the elements
+/// themselves would compare the message again.
+impl PartialEq for MessageUsage {
+    fn eq(&self, other: &Self) -> bool {
+        fn same_name<T>(a: &ConfigWeak<T>, b: &ConfigWeak<T>, name: fn(&T) -> &str) -> bool {
+            match (a.upgrade(), b.upgrade()) {
+                (Some(a), Some(b)) => name(&a) == name(&b),
+                (None, None) => true,
+                _ => false,
+            }
+        }
+        match (self, other) {
+            (MessageUsage::Stream(a), MessageUsage::Stream(b)) => same_name(a, b, Stream::name),
+            (MessageUsage::CommandReq(a), MessageUsage::CommandReq(b))
+            | (MessageUsage::CommandResp(a), MessageUsage::CommandResp(b)) => {
+                same_name(a, b, Command::name)
+            }
+            (MessageUsage::TransportData(a), MessageUsage::TransportData(b))
+            | (MessageUsage::TransportFlowControl(a), MessageUsage::TransportFlowControl(b)) => {
+                same_name(a, b, TransportChannel::name)
+            }
+            (MessageUsage::Debug { interval: a }, MessageUsage::Debug { interval: b }) => a == b,
+            (MessageUsage::External { interval: a }, MessageUsage::External { interval: b }) => a == b,
+            (MessageUsage::GetResp, MessageUsage::GetResp)
+            | (MessageUsage::GetReq, MessageUsage::GetReq)
+            | (MessageUsage::SetResp, MessageUsage::SetResp)
+            | (MessageUsage::SetReq, MessageUsage::SetReq)
+            | (MessageUsage::Heartbeat, MessageUsage::Heartbeat)
+            | (MessageUsage::Emergency, MessageUsage::Emergency)
+            | (MessageUsage::Bootloader, MessageUsage::Bootloader) => true,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum MessageId {
     StandardId(u32),
@@ -53,9 +103,126 @@ impl MessageId {
     }
 }
 
+/// End-to-end protection profile of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum E2EProfile {
+    /// CRC-8 (SAE J1850) over the data id and the payload,
+    /// combined with a 4 bit alive counter.
+    Crc8Counter4 { data_id: u16 },
+}
+
+impl Hash for E2EProfile {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match &self {
+            E2EProfile::Crc8Counter4 { data_id } => {
+                state.write_u8(0);
+                state.write_u16(*data_id);
+            }
+        }
+    }
+}
+
+impl E2EProfile {
+    pub fn data_id(&self) -> u16 {
+        match &self {
+            E2EProfile::Crc8Counter4 { data_id } => *data_id,
+        }
+    }
+}
+
+/// Signals reserved for the end-to-end protection of a message.
+#[derive(Debug, PartialEq)]
+pub struct E2EProtection {
+    profile: E2EProfile,
+    crc: SignalRef,
+    counter: SignalRef,
+}
+
+impl Hash for E2EProtection {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.profile.hash(state);
+        self.crc.hash(state);
+        self.counter.hash(state);
+    }
+}
+
+impl E2EProtection {
+    pub fn new(profile: E2EProfile, crc: SignalRef, counter: SignalRef) -> Self {
+        Self {
+            profile,
+            crc,
+            counter,
+        }
+    }
+    pub fn profile(&self) -> &E2EProfile {
+        &self.profile
+    }
+    /// byte aligned signal containing the CRC.
+    pub fn crc(&self) -> &SignalRef {
+        &self.crc
+    }
+    pub fn counter(&self) -> &SignalRef {
+        &self.counter
+    }
+    /// Computes the CRC of a payload. The byte of the payload
+    /// containing the CRC is skipped.
+    pub fn compute_crc(&self, data: &[u8]) -> u8 {
+        let crc_byte = self.crc.byte_offset() / 8;
+        let data_id = self.profile.data_id().to_le_bytes();
+        let payload = data
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != crc_byte)
+            .map(|(_, b)| b);
+        let mut crc: u8 = 0xFF;
+        for b in data_id.iter().chain(payload) {
+            crc ^= b;
+            for _ in 0..8 {
+                crc = if crc & 0x80 != 0 {
+                    (crc << 1) ^ 0x1D
+                } else {
+                    crc << 1
+                };
+            }
+        }
+        crc ^ 0xFF
+    }
+}
+
+/// Named set of signals of a message, which are updated atomically
+/// (e.g. the setpoints of all four wheels), see DBC `SIG_GROUP_`.
+#[derive(Debug, PartialEq)]
+pub struct SignalGroup {
+    name: String,
+    signals: Vec<SignalRef>,
+}
+
+impl Hash for SignalGroup {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        for s in &self.signals {
+            s.hash(state);
+        }
+    }
+}
+
+impl SignalGroup {
+    pub fn new(name: String, signals: Vec<SignalRef>) -> Self {
+        Self { name, signals }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn signals(&self) -> &Vec<SignalRef> {
+        &self.signals
+    }
+}
+
 pub type MessageRef = ConfigRef<Message>;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Message {
     name: String,
     description: Option<String>,
@@ -65,7 +232,12 @@ pub struct Message {
     visibility: Visibility,
     dlc : u8,
     bus : BusRef,
-    usage : OnceLock<MessageUsage>,
+    e2e : Option<E2EProtection>,
+    usage : MessageUsage,
+    lifecycle : Lifecycle,
+    signal_groups : Vec<SignalGroup>,
+    fill : u8,
+    sender_id : Option<SignalRef>,
 }
 
 impl Hash for Message {
@@ -89,18 +261,60 @@ impl Hash for Message {
         self.visibility.hash(state);
         state.write_u8(self.dlc);
         state.write_u32(self.bus.id());
+        match &self.e2e {
+            Some(e2e) => {
+                state.write_u8(0);
+                e2e.hash(state);
+            }
+            None => state.write_u8(1),
+        }
+        for group in &self.signal_groups {
+            group.hash(state);
+        }
+        state.write_u8(self.fill);
+        if let Some(sender_id) = &self.sender_id {
+            sender_id.hash(state);
+        }
     }
 }
 
 
+/// Parts of a message, see `Message::new`.
+#[derive(Debug)]
+pub struct MessageParams {
+    pub name: String,
+    pub description: Option<String>,
+    pub id: MessageId,
+    pub encoding: Option<MessageEncoding>,
+    pub signals: Vec<SignalRef>,
+    pub visibility: Visibility,
+    pub dlc: u8,
+    pub bus: BusRef,
+    pub e2e: Option<E2EProtection>,
+    pub lifecycle: Lifecycle,
+    pub signal_groups: Vec<SignalGroup>,
+    pub fill: u8,
+    pub sender_id: Option<SignalRef>,
+}
+
 impl Message {
-    pub fn new(name : String,
-               description : Option<String>,
-               id : MessageId,
-               encoding : Option<MessageEncoding>,
-               signals : Vec<SignalRef>,
-               visibility : Visibility, dlc : u8,
-               bus : BusRef) -> Self {
+    /// Messages are external until their usage is set.
+    pub fn new(params: MessageParams) -> Self {
+        let MessageParams {
+            name,
+            description,
+            id,
+            encoding,
+            signals,
+            visibility,
+            dlc,
+            bus,
+            e2e,
+            lifecycle,
+            signal_groups,
+            fill,
+            sender_id,
+        } = params;
         Self {
             name,
             description,
@@ -110,18 +324,49 @@ impl Message {
             visibility,
             dlc,
             bus,
-            usage : OnceLock::new(),
+            e2e,
+            usage : MessageUsage::External { interval : Duration::from_secs(60) },
+            lifecycle,
+            signal_groups,
+            fill,
+            sender_id,
         }
     }
-    pub fn usage(&self) -> &MessageUsage {
-        self.usage.get().expect("Karl fucked up big time (message usage was not set property while building!)")
+    /// Sets the usage before the message is shared.
+    pub(crate) fn with_usage(self, usage : MessageUsage) -> Self {
+        Self { usage, ..self }
     }
-    pub fn __set_usage(&self, usage : MessageUsage) {
-        self.usage.set(usage).expect("__set_usage can only be called once (when calling NetworkBuilder::build(&self))");
+    /// Versions in which the message was introduced and deprecated.
+    pub fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
     }
-    pub fn __get_usage(&self) -> &OnceLock<MessageUsage> {
+    pub fn usage(&self) -> &MessageUsage {
         &self.usage
     }
+    /// Longest expected time between two transmissions of the message,
+    /// None if it's only transmitted on demand (get/set, transport channels).
+    /// Monitoring tools consider a message stale if it isn't received within this interval.
+    /// Streams are expected within their max interval, commands within
+    /// their expected interval.
+    pub fn expected_interval(&self) -> Option<Duration> {
+        match &self.usage {
+            MessageUsage::Stream(stream) => stream.upgrade().map(|stream| *stream.max_interval()),
+            MessageUsage::CommandReq(command) | MessageUsage::CommandResp(command) => {
+                command.upgrade().map(|command| *command.expected_interval())
+            }
+            MessageUsage::External { interval } => Some(*interval),
+            MessageUsage::Heartbeat => Some(HEARTBEAT_INTERVAL),
+            MessageUsage::Debug { interval } => *interval,
+            MessageUsage::Emergency
+            | MessageUsage::Bootloader
+            | MessageUsage::TransportData(_)
+            | MessageUsage::TransportFlowControl(_)
+            | MessageUsage::GetResp
+            | MessageUsage::GetReq
+            | MessageUsage::SetResp
+            | MessageUsage::SetReq => None,
+        }
+    }
 
     pub fn id(&self) -> &MessageId {
         &self.id
@@ -144,12 +389,41 @@ impl Message {
     pub fn dlc(&self) -> u8 { 
         self.dlc
     }
+    /// Byte pattern of the bits, which aren't occupied by signals.
+    pub fn fill(&self) -> u8 {
+        self.fill
+    }
+    /// Raw value of the frame without any signal, which contains the fill
+    /// pattern in every bit of the dlc, that isn't occupied by a signal.
+    pub fn fill_bits(&self) -> u64 {
+        let frame_mask = u64::MAX.checked_shr(64 - 8 * self.dlc as u32).unwrap_or(0);
+        let occupied = self.signals.iter().fold(0u64, |occupied, signal| {
+            let mask = u64::MAX.checked_shr(64 - signal.size() as u32).unwrap_or(0);
+            occupied | mask.checked_shl(signal.byte_offset() as u32).unwrap_or(0)
+        });
+        u64::from_le_bytes([self.fill; 8]) & frame_mask & !occupied
+    }
+    /// Signal containing the node id of the transmitter,
+    /// if the message is shared by several nodes.
+    pub fn sender_id(&self) -> Option<&SignalRef> {
+        self.sender_id.as_ref()
+    }
     pub fn visibility(&self) -> &Visibility {
         &self.visibility
     }
     pub fn bus(&self) -> &BusRef {
         &self.bus
     }
+    pub fn e2e(&self) -> Option<&E2EProtection> {
+        self.e2e.as_ref()
+    }
+    pub fn signal_groups(&self) -> &Vec<SignalGroup> {
+        &self.signal_groups
+    }
+    /// Bit grid of the signals, which can be printed for documentation.
+    pub fn layout_table(&self) -> LayoutTable {
+        LayoutTable::new(self)
+    }
 }
 
 
@@ -1,38 +1,101 @@
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
+pub use self::access_plan::AccessPlan;
+pub use self::access_plan::OdFrame;
+pub use self::access_plan::OdFrameKind;
+pub use self::access_level::AccessLevel;
+pub use self::access_level::AccessLevelRef;
+pub use self::bit_timing::BitTiming;
+pub use self::bit_timing::BitTimingConstraints;
+pub use self::bit_timing::BitTimingOptions;
 pub use self::command::Command;
 pub use self::command::CommandRef;
+pub use self::compatibility::CompatibilityReport;
 pub use self::encoding::MessageEncoding;
 pub use self::encoding::TypeSignalEncoding;
+pub use self::j1939::J1939Id;
+pub use self::latency_path::LatencyHop;
+pub use self::latency_path::LatencyPath;
+pub use self::latency_path::LatencyPathRef;
+pub use self::layout::LayoutTable;
+pub use self::transport_channel::TransportChannel;
+pub use self::transport_channel::TransportChannelRef;
+pub use self::uds::Uds;
+pub use self::uds::UdsService;
 pub use self::message::MessageId;
 pub use self::message::Message;
+pub use self::message::MessageParams;
 pub use self::message::MessageRef;
+pub use self::message::E2EProfile;
+pub use self::message::E2EProtection;
+pub use self::message::SignalGroup;
 pub use self::network::Network;
+pub use self::network::NetworkParams;
 pub use self::network::NetworkRef;
+pub use self::network::ProtocolMessages;
+pub use self::public_view::PublicView;
+pub use self::rx_filter::RxFilter;
+pub use self::schedule::TxSchedule;
+pub use self::schedule::TxSlot;
 pub use self::node::Node;
+pub use self::node::NodeParams;
 pub use self::node::NodeRef;
+pub use self::node_group::NodeGroup;
+pub use self::node_group::NodeGroupRef;
+pub use self::node_group::BROADCAST_SERVER_ID;
 pub use self::object_entry::ObjectEntryAccess;
 pub use self::object_entry::ObjectEntry;
 pub use self::object_entry::ObjectEntryRef;
+pub use self::object_entry::ObjectEntryGroup;
+pub use self::object_entry::ObjectEntryGroupRef;
 pub use self::signal::SignalSign;
 pub use self::signal::SignalType;
+pub use self::signal::OverflowPolicy;
 pub use self::signal::SignalRef;
 pub use self::signal::ValueTable;
 pub use self::signal::ValueTableRef;
+pub use self::supervision::Supervision;
+pub use self::workspace::Workspace;
+pub use self::workspace::WorkspaceRef;
+pub use self::types::AttributePadding;
+pub use self::types::EnumEntryInfo;
+pub use self::types::StructAttributeInfo;
 pub use self::types::Type;
 pub use self::types::TypeRef;
+pub use self::value::TypedValue;
+pub use self::version::Lifecycle;
+pub use self::version::Version;
 pub use self::visibility::Visibility;
+pub use self::visitor::NetworkVisitor;
 
+pub mod access_level;
+pub mod access_plan;
+pub mod bit_timing;
 pub mod command;
+pub mod compatibility;
 pub mod encoding;
+pub mod j1939;
+pub mod latency_path;
+pub mod layout;
 pub mod message;
 pub mod network;
 pub mod node;
+pub mod node_group;
+pub mod public_view;
+pub mod rx_filter;
+pub mod schedule;
 pub mod object_entry;
 pub mod signal;
 pub mod stream;
+pub mod supervision;
+pub mod workspace;
+pub mod transport_channel;
 pub mod types;
+pub mod uds;
+pub mod value;
+pub mod version;
 pub mod visibility;
+pub mod visitor;
 pub mod bus;
 
 pub type ConfigRef<T> = Arc<T>;
@@ -40,3 +103,41 @@ pub type ConfigRef<T> = Arc<T>;
 pub fn make_config_ref<T>(value: T) -> ConfigRef<T> {
     Arc::new(value)
 }
+
+/// Back-reference to a config object that owns the referencing object.
+pub type ConfigWeak<T> = Weak<T>;
+
+/// Creates n config refs at once, where {f} is given weak references
+/// to all of them, which allows building cyclic structures
+/// without mutating them after construction.
+pub(crate) fn make_cyclic_config_refs<T>(
+    n: usize,
+    f: impl FnOnce(&[ConfigWeak<T>]) -> Vec<T>,
+) -> Vec<ConfigRef<T>> {
+    fn rec<T, F: FnOnce(&[ConfigWeak<T>]) -> Vec<T>>(
+        n: usize,
+        weaks: &mut Vec<ConfigWeak<T>>,
+        f: &mut Option<F>,
+        values: &mut Vec<Option<T>>,
+        refs: &mut Vec<ConfigRef<T>>,
+    ) {
+        if weaks.len() == n {
+            let f = f.take().unwrap();
+            *values = f(weaks).into_iter().map(Some).collect();
+            assert_eq!(values.len(), n);
+            return;
+        }
+        let index = weaks.len();
+        let config_ref = Arc::new_cyclic(|weak| {
+            weaks.push(weak.clone());
+            rec(n, weaks, f, values, refs);
+            values[index].take().unwrap()
+        });
+        refs.push(config_ref);
+    }
+    let mut refs = Vec::with_capacity(n);
+    rec(n, &mut Vec::with_capacity(n), &mut Some(f), &mut vec![], &mut refs);
+    // the innermost ref is created first.
+    refs.reverse();
+    refs
+}
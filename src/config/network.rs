@@ -1,6 +1,12 @@
-use std::{fmt::Display, hash::{self, Hash, Hasher}};
+use std::{collections::HashMap, fmt::Display, hash::{self, Hash, Hasher}};
 
-use super::{ConfigRef, NodeRef, MessageRef, TypeRef, Type, SignalType, bus::BusRef};
+use crate::{
+    analysis::{latency, lint, od_throughput, response_time, stats, LintOptions, LintReport, MessageResponseTime, NetworkStats, OdSnapshotTime, OdThroughputOptions, PathLatency, ResponseTimeOptions},
+    codegen::{self, c, consts, cpp, python, sim, CodegenBackend, ConstsLanguage},
+    export::{arxml, canonical, dbc, docs, dot, kcd, mdf4, supervision, sym, DotOptions},
+};
+
+use super::{ConfigRef, Version, NodeRef, MessageId, MessageRef, TypeRef, Type, SignalType, bus::BusRef, AccessLevelRef, compatibility::{self, CompatibilityReport}, PublicView, Node, NodeGroupRef, BROADCAST_SERVER_ID, LatencyPathRef, Supervision};
 
 
 pub type NetworkRef = ConfigRef<Network>;
@@ -8,6 +14,10 @@ pub type NetworkRef = ConfigRef<Network>;
 #[derive(Debug)]
 pub struct Network {
     build_time: chrono::DateTime<chrono::Local>,
+    name: Option<String>,
+    description: Option<String>,
+    version: Option<Version>,
+    author: Option<String>,
     nodes: Vec<NodeRef>,
     messages: Vec<MessageRef>,
     types: Vec<TypeRef>,
@@ -17,6 +27,28 @@ pub struct Network {
     set_req_message : MessageRef,
     heartbeat_message : MessageRef,
     buses : Vec<BusRef>,
+    access_levels : Vec<AccessLevelRef>,
+    node_groups : Vec<NodeGroupRef>,
+    latency_paths : Vec<LatencyPathRef>,
+    supervisions : Vec<Supervision>,
+    node_id_width : u8,
+    od_index_width : u8,
+    // indices into nodes, messages and types for constant time lookups.
+    node_indices : HashMap<String, usize>,
+    message_indices : HashMap<String, usize>,
+    message_id_indices : HashMap<(MessageId, u32), usize>,
+    type_indices : HashMap<String, usize>,
+}
+
+/// Messages of the built-in protocols (object dictionary access and heartbeat),
+/// which are shared by all nodes.
+#[derive(Debug, Clone, Copy)]
+pub struct ProtocolMessages<'a> {
+    pub get_req: &'a MessageRef,
+    pub get_resp: &'a MessageRef,
+    pub set_req: &'a MessageRef,
+    pub set_resp: &'a MessageRef,
+    pub heartbeat: &'a MessageRef,
 }
 
 impl hash::Hash for Network {
@@ -24,41 +56,253 @@ impl hash::Hash for Network {
         self.nodes.hash(state);
         self.messages().hash(state);
         self.buses().hash(state);
+        self.types().hash(state);
+        self.access_levels().hash(state);
+        self.node_groups.hash(state);
+        self.supervisions.hash(state);
+        self.latency_paths.hash(state);
     }
 }
 
+/// Two networks are equal if they describe the same configuration.
+/// Like the config hash, the build time and the metadata aren't compared.
+impl PartialEq for Network {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+            && self.messages == other.messages
+            && self.buses == other.buses
+            && self.types == other.types
+            && self.access_levels == other.access_levels
+            && self.node_groups == other.node_groups
+            && self.supervisions == other.supervisions
+            && self.latency_paths == other.latency_paths
+    }
+}
+
+impl Eq for Network {}
+
 impl Network {
-    pub fn portable_hash(&self) -> u64 {
+    /// Deterministic hash over the semantic content of the network.
+    /// The build time and the metadata (name, description, version and author)
+    /// are not part of the hash, so rebuilding the same configuration always
+    /// yields the same value.
+    pub fn config_hash(&self) -> u64 {
         let mut hasher = seahash::SeaHasher::new();
-        for n in &self.nodes {
-            n.hash(&mut hasher);
-        }
-        for m in &self.messages {
-            m.hash(&mut hasher);
-        }
-        for b in &self.buses {
-            b.hash(&mut hasher);
-        }
+        self.hash(&mut hasher);
         hasher.finish()
     }
+    pub fn portable_hash(&self) -> u64 {
+        self.config_hash()
+    }
+
+    /// Checks if firmware built from the `old` network can coexist
+    /// with firmware built from this network.
+    pub fn is_wire_compatible(&self, old: &Network) -> CompatibilityReport {
+        compatibility::check_wire_compatibility(old, self)
+    }
+
+    /// Renders the topology of the network as a Graphviz graph.
+    pub fn to_dot(&self) -> String {
+        dot::network_to_dot(self, &DotOptions::default())
+    }
+
+    pub fn to_dot_with(&self, options: &DotOptions) -> String {
+        dot::network_to_dot(self, options)
+    }
+
+    /// Exports the network in the Kayak CAN definition (KCD) format.
+    pub fn to_kcd(&self) -> String {
+        kcd::network_to_kcd(self)
+    }
+
+    /// Exports the communication matrix as an AUTOSAR system description (ARXML).
+    pub fn to_arxml(&self) -> String {
+        arxml::network_to_arxml(self)
+    }
+
+    /// Exports the network as a Vector CAN database (.dbc).
+    pub fn to_dbc(&self) -> String {
+        dbc::network_to_dbc(self)
+    }
+
+    /// Exports the network as a PCAN symbol file (.sym).
+    pub fn to_sym(&self) -> String {
+        sym::network_to_sym(self)
+    }
+
+    /// Exports the channel descriptions required to convert raw CAN logs
+    /// into MDF4 measurements, see `export::mdf4`.
+    pub fn to_mdf4_channels(&self) -> String {
+        mdf4::network_to_mdf4_channels(self)
+    }
+
+    /// Renders the supervision relationships as a markdown table,
+    /// see `export::supervision`.
+    pub fn to_supervision_table(&self) -> String {
+        supervision::network_to_supervision_table(self)
+    }
+
+    /// Renders a markdown reference of the buses, nodes and messages,
+    /// see `export::docs`.
+    pub fn to_docs(&self) -> String {
+        docs::network_to_docs(self)
+    }
+
+    /// Serializes the network into a deterministic and diff friendly text,
+    /// see `export::canonical`.
+    pub fn to_canonical_string(&self) -> String {
+        canonical::network_to_canonical(self)
+    }
+
+    /// Runs a custom code generation backend over the network.
+    pub fn generate<B: CodegenBackend + ?Sized>(&self, backend: &mut B) -> String {
+        codegen::generate(self, backend)
+    }
+
+    /// Generates a python module to encode and decode all messages.
+    pub fn to_python(&self) -> String {
+        python::network_to_python(self)
+    }
+
+    /// Generates a header-only C++ library to encode and decode all messages.
+    pub fn to_cpp(&self) -> String {
+        cpp::network_to_cpp(self)
+    }
+
+    /// Generates a header-only C library to encode and decode all messages.
+    pub fn to_c(&self) -> String {
+        c::network_to_c(self)
+    }
+
+    /// Generates the constants (ids, od indices, enum values and the
+    /// config hash) of the network, see `codegen::consts`.
+    pub fn to_consts(&self, language: ConstsLanguage) -> String {
+        consts::network_to_consts(self, language)
+    }
+
+    /// Generates a Rust simulation stub of the node, see `codegen::sim`.
+    pub fn to_sim(&self, node: &Node) -> String {
+        sim::node_to_sim(self, node)
+    }
+
+    /// Runs the lint rules over the network, see `analysis::lint`.
+    pub fn lint(&self, options: &LintOptions) -> LintReport {
+        lint::lint(self, options)
+    }
+    /// Projection onto the globally visible elements, see `PublicView`.
+    pub fn public_view(&self) -> PublicView<'_> {
+        PublicView::new(self)
+    }
+    /// Copy of the network without the types, which are used by neither a message
+    /// nor an object entry, so that generated artifacts only contain the types they need.
+    /// Returns the pruned network and the removed types.
+    pub fn prune(&self) -> (NetworkRef, Vec<TypeRef>) {
+        let used_types = lint::used_types(self);
+        let (types, removed): (Vec<TypeRef>, Vec<TypeRef>) = self
+            .types
+            .iter()
+            .cloned()
+            .partition(|ty| used_types.contains(&ty.name()));
+        let network = Network::new(NetworkParams {
+            build_time: self.build_time,
+            nodes: self.nodes.clone(),
+            messages: self.messages.clone(),
+            types,
+            get_req_message: self.get_req_message.clone(),
+            get_resp_message: self.get_resp_message.clone(),
+            set_req_message: self.set_req_message.clone(),
+            set_resp_message: self.set_resp_message.clone(),
+            heartbeat_message: self.heartbeat_message.clone(),
+            buses: self.buses.clone(),
+            access_levels: self.access_levels.clone(),
+            node_groups: self.node_groups.clone(),
+            latency_paths: self.latency_paths.clone(),
+            node_id_width: self.node_id_width,
+            od_index_width: self.od_index_width,
+        }).with_metadata(
+            self.name.clone(),
+            self.description.clone(),
+            self.version,
+            self.author.clone(),
+        ).with_supervisions(self.supervisions.clone());
+        (ConfigRef::new(network), removed)
+    }
+    /// Worst case response times of all messages, see `analysis::response_time`.
+    pub fn response_times(&self, options: &ResponseTimeOptions) -> Vec<MessageResponseTime> {
+        response_time::response_times(self, options)
+    }
+    /// End-to-end latencies of the annotated data paths, see `analysis::latency`.
+    pub fn path_latencies(&self, options: &ResponseTimeOptions) -> Vec<PathLatency> {
+        latency::path_latencies(self, options)
+    }
+    /// Time to read the whole object dictionary of every node,
+    /// see `analysis::od_throughput`.
+    pub fn od_snapshot_times(&self, options: &OdThroughputOptions) -> Vec<OdSnapshotTime> {
+        od_throughput::od_snapshot_times(self, options)
+    }
+    /// Catalogue statistics (message, signal and object entry counts and
+    /// id space utilization), see `analysis::stats`.
+    pub fn stats(&self) -> NetworkStats {
+        stats::stats(self)
+    }
+}
+
+/// Parts of a network, see `Network::new`.
+#[derive(Debug)]
+pub struct NetworkParams {
+    pub build_time: chrono::DateTime<chrono::Local>,
+    pub nodes: Vec<NodeRef>,
+    pub messages: Vec<MessageRef>,
+    pub types: Vec<TypeRef>,
+    pub get_req_message: MessageRef,
+    pub get_resp_message: MessageRef,
+    pub set_req_message: MessageRef,
+    pub set_resp_message: MessageRef,
+    pub heartbeat_message: MessageRef,
+    pub buses: Vec<BusRef>,
+    pub access_levels: Vec<AccessLevelRef>,
+    pub node_groups: Vec<NodeGroupRef>,
+    pub latency_paths: Vec<LatencyPathRef>,
+    /// size of the client_id and server_id fields of the get/set headers in bits.
+    pub node_id_width: u8,
+    /// size of the od_index field of the get/set headers in bits.
+    pub od_index_width: u8,
 }
 
 impl Network {
-    pub fn new(
-        build_time: chrono::DateTime<chrono::Local>,
-        nodes: Vec<NodeRef>,
-        messages: Vec<MessageRef>,
-        types: Vec<TypeRef>,
-        get_req_message : MessageRef,
-        get_resp_message : MessageRef,
-        set_req_message : MessageRef,
-        set_resp_message : MessageRef,
-        heartbeat_message : MessageRef,
-        buses : Vec<BusRef>,
-    ) -> Network {
+    pub fn new(params: NetworkParams) -> Network {
+        let NetworkParams {
+            build_time,
+            nodes,
+            messages,
+            types,
+            get_req_message,
+            get_resp_message,
+            set_req_message,
+            set_resp_message,
+            heartbeat_message,
+            buses,
+            access_levels,
+            node_groups,
+            latency_paths,
+            node_id_width,
+            od_index_width,
+        } = params;
+        let node_indices = nodes.iter().enumerate().map(|(i, n)| (n.name().to_owned(), i)).collect();
+        let message_indices = messages.iter().enumerate().map(|(i, m)| (m.name().to_owned(), i)).collect();
+        let message_id_indices = messages.iter().enumerate().map(|(i, m)| ((*m.id(), m.bus().id()), i)).collect();
+        let type_indices = types.iter().enumerate().map(|(i, t)| (t.name(), i)).collect();
         Network {
+            node_indices,
+            message_indices,
+            message_id_indices,
+            type_indices,
             types,
             build_time,
+            name: None,
+            description: None,
+            version: None,
+            author: None,
             nodes,
             messages,
             get_req_message,
@@ -66,9 +310,45 @@ impl Network {
             set_req_message,
             set_resp_message,
             heartbeat_message,
-            buses
+            buses,
+            access_levels,
+            node_groups,
+            latency_paths,
+            node_id_width,
+            od_index_width,
+            supervisions: vec![],
         }
     }
+    pub(crate) fn with_metadata(
+        mut self,
+        name: Option<String>,
+        description: Option<String>,
+        version: Option<Version>,
+        author: Option<String>,
+    ) -> Self {
+        self.name = name;
+        self.description = description;
+        self.version = version;
+        self.author = author;
+        self
+    }
+    pub(crate) fn with_supervisions(self, supervisions: Vec<Supervision>) -> Self {
+        Self { supervisions, ..self }
+    }
+    /// Name of the network, None if it wasn't set with `NetworkBuilder::set_name`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// Version of the configuration.
+    pub fn version(&self) -> Option<&Version> {
+        self.version.as_ref()
+    }
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
     pub fn buses(&self) -> &Vec<BusRef> {
         &self.buses
     }
@@ -78,12 +358,79 @@ impl Network {
     pub fn messages(&self) -> &Vec<MessageRef> {
         &self.messages
     }
+    pub fn node_by_name(&self, name: &str) -> Option<&NodeRef> {
+        self.node_indices.get(name).map(|&i| &self.nodes[i])
+    }
+    pub fn message_by_name(&self, name: &str) -> Option<&MessageRef> {
+        self.message_indices.get(name).map(|&i| &self.messages[i])
+    }
+    /// Message with the given id on the bus, ids are only unique per bus.
+    pub fn message_by_id(&self, id: MessageId, bus: &BusRef) -> Option<&MessageRef> {
+        self.message_id_indices.get(&(id, bus.id())).map(|&i| &self.messages[i])
+    }
+    pub fn type_by_name(&self, name: &str) -> Option<&TypeRef> {
+        self.type_indices.get(name).map(|&i| &self.types[i])
+    }
+    pub fn node_groups(&self) -> &Vec<NodeGroupRef> {
+        &self.node_groups
+    }
+    /// Data paths annotated with a latency budget.
+    pub fn latency_paths(&self) -> &Vec<LatencyPathRef> {
+        &self.latency_paths
+    }
+    /// Supervision relationships between nodes, see `NodeBuilder::supervise`.
+    pub fn supervisions(&self) -> &Vec<Supervision> {
+        &self.supervisions
+    }
+    /// Supervisions, in which the node is supervised.
+    pub fn supervisors_of(&self, node: &Node) -> Vec<&Supervision> {
+        self.supervisions
+            .iter()
+            .filter(|supervision| supervision.supervised().name() == node.name())
+            .collect()
+    }
+    pub fn bus_by_name(&self, name: &str) -> Option<&BusRef> {
+        self.buses.iter().find(|bus| bus.name() == name)
+    }
+    pub fn group_by_name(&self, name: &str) -> Option<&NodeGroupRef> {
+        self.node_groups.iter().find(|group| group.name() == name)
+    }
+    /// Groups, which contain the node.
+    pub fn groups_of(&self, node: &Node) -> Vec<&NodeGroupRef> {
+        self.node_groups
+            .iter()
+            .filter(|group| group.contains(node))
+            .collect()
+    }
+    /// server_ids of get/set requests, which the node has to handle:
+    /// its own id, the ids of its groups and the broadcast id.
+    /// Requests to group or broadcast ids only write object entries,
+    /// which opted into broadcast writes.
+    pub fn server_ids(&self, node: &Node) -> Vec<u8> {
+        let mut server_ids = vec![node.id()];
+        server_ids.extend(self.groups_of(node).iter().map(|group| group.server_id()));
+        server_ids.push(self.broadcast_server_id());
+        server_ids
+    }
+    /// Size of the client_id and server_id fields in the get/set protocol headers in bits.
+    pub fn node_id_width(&self) -> u8 {
+        self.node_id_width
+    }
+    /// server_id of get/set requests, which are addressed to all nodes,
+    /// `BROADCAST_SERVER_ID` unless the node_id width is smaller than 8 bits.
+    pub fn broadcast_server_id(&self) -> u8 {
+        BROADCAST_SERVER_ID >> (8 - self.node_id_width().clamp(1, 8))
+    }
     pub fn build_time(&self) -> &chrono::DateTime<chrono::Local> {
         &self.build_time
     }
     pub fn types(&self) -> &Vec<TypeRef> {
         &self.types
     }
+    /// Size of the od_index field in the get/set protocol headers in bits.
+    pub fn od_index_width(&self) -> u8 {
+        self.od_index_width
+    }
     pub fn get_resp_message(&self) -> &MessageRef {
         &self.get_resp_message
     }
@@ -99,6 +446,58 @@ impl Network {
     pub fn heartbeat_message(&self) -> &MessageRef {
         &self.heartbeat_message
     }
+    pub fn protocol_messages(&self) -> ProtocolMessages<'_> {
+        ProtocolMessages {
+            get_req: &self.get_req_message,
+            get_resp: &self.get_resp_message,
+            set_req: &self.set_req_message,
+            set_resp: &self.set_resp_message,
+            heartbeat: &self.heartbeat_message,
+        }
+    }
+    pub fn access_levels(&self) -> &Vec<AccessLevelRef> {
+        &self.access_levels
+    }
+    /// Descriptions of wiring mistakes, which are printed as warnings while building:
+    /// messages that no node transmits or receives (except the protocol messages)
+    /// and commands whose call message isn't received by the node executing them.
+    pub fn wiring_warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+        let protocol = self.protocol_messages();
+        let protocol_messages = [
+            protocol.get_req,
+            protocol.get_resp,
+            protocol.set_req,
+            protocol.set_resp,
+            protocol.heartbeat,
+        ];
+        for message in &self.messages {
+            if protocol_messages.iter().any(|m| ConfigRef::ptr_eq(m, message)) {
+                continue;
+            }
+            let name = message.name();
+            if !self.nodes.iter().any(|n| n.tx_messages().iter().any(|m| m.name() == name)) {
+                warnings.push(format!("message {name} isn't transmitted by any node"));
+            }
+            if !self.nodes.iter().any(|n| n.rx_messages().iter().any(|m| m.name() == name)) {
+                warnings.push(format!("message {name} isn't received by any node"));
+            }
+        }
+        for node in &self.nodes {
+            for command in node.commands() {
+                let call_message = command.tx_message().name();
+                if !node.rx_messages().iter().any(|m| m.name() == call_message) {
+                    warnings.push(format!(
+                        "call message {call_message} of command {}::{} isn't received by {}",
+                        node.name(),
+                        command.name(),
+                        node.name()
+                    ));
+                }
+            }
+        }
+        warnings
+    }
     /// The control panel ids start at 0.
     /// returns the node_id associated with the control_panel.
     pub fn control_panel_node_id(&self, control_panel_id : u8) -> u8{
@@ -117,8 +516,17 @@ impl Display for Network {
         writeln!(f, "{s1}build_time : {}", self.build_time)?;
         writeln!(f, "{s1}busses:")?;
         for bus in &self.buses {
-            writeln!(f, "{s2}{}", bus.id())?;
+            writeln!(f, "{s2}{} (id {})", bus.name(), bus.id())?;
+            if let Some(connector) = bus.connector() {
+                writeln!(f, "{s3}connector : {connector}")?;
+            }
             writeln!(f, "{s3}baudrate : {}", bus.baudrate())?;
+            if let Some(sample_point) = bus.sample_point() {
+                writeln!(f, "{s3}sample_point : {sample_point}%")?;
+            }
+            if let Some(sjw) = bus.sjw() {
+                writeln!(f, "{s3}sjw : {sjw}")?;
+            }
         }
         writeln!(f, "{s1}types:")?;
         for ty in &self.types {
@@ -128,6 +536,7 @@ impl Display for Network {
                     name: _,
                     description: _,
                     attribs: _,
+                    attrib_info: _,
                     visibility,
                 } => format!("{visibility:?}"),
                 Type::Enum {
@@ -135,9 +544,11 @@ impl Display for Network {
                     description: _,
                     size: _,
                     entries: _,
+                    entry_info: _,
                     visibility,
                 } => format!("{visibility:?}"),
                 Type::Array { len: _, ty: _ } => "Static".to_owned(),
+                Type::Union { visibility, .. } => format!("{visibility:?}"),
             };
             write!(f, "{s2}{} ({})", ty.name(), vis)?;
             match ty as &Type {
@@ -148,6 +559,7 @@ impl Display for Network {
                     name: _,
                     description: _,
                     attribs,
+                    attrib_info: _,
                     visibility: _,
                 } => {
                     writeln!(f, ": (struct)")?;
@@ -160,6 +572,7 @@ impl Display for Network {
                     description: _,
                     size: _,
                     entries,
+                    entry_info: _,
                     visibility: _,
                 } => {
                     writeln!(f, ": (enum)")?;
@@ -170,6 +583,12 @@ impl Display for Network {
                 Type::Array { len: _, ty: _ } => {
                     write!(f, "\n")?;
                 }
+                Type::Union { tag, variants, .. } => {
+                    writeln!(f, ": (union tagged by {})", tag.name())?;
+                    for (entry, variant_type) in variants {
+                        writeln!(f, "{s3}{} : {}", entry, variant_type.name())?;
+                    }
+                }
             }
         }
         writeln!(f, "{s1}messages:")?;
@@ -202,6 +621,7 @@ impl Display for Network {
                             name,
                             description: _,
                             attribs: _,
+                            attrib_info: _,
                             visibility: _,
                         } => {
                             write!(f, "{name} (struct)")?;
@@ -211,6 +631,7 @@ impl Display for Network {
                             description: _,
                             size: _,
                             entries: _,
+                            entry_info: _,
                             visibility: _,
                         } => {
                             write!(f, "{name} (enum)")?;
@@ -218,6 +639,9 @@ impl Display for Network {
                         Type::Array { len, ty } => {
                             write!(f, "{}[{len}]", ty.name())?;
                         }
+                        Type::Union { name, .. } => {
+                            write!(f, "{name} (union)")?;
+                        }
                     }
                     writeln!(f)?;
                 }
@@ -265,13 +689,13 @@ impl Display for Network {
             writeln!(f, "{s3}tx_streams:")?;
             for stream in node.tx_streams() {
                 writeln!(f, "{s4}{} [{}]", stream.name(), stream.message().name())?;
-                for oe in stream.mapping() {
-                    let oe_name = match oe {
-                        Some(oe) => oe.name(),
+                for mapping in stream.mapping() {
+                    let oe_name = match mapping {
+                        Some(mapping) => mapping.name(),
                         None => "None",
                     };
-                    let oe_ty = match oe {
-                        Some(oe) => oe.ty().name(),
+                    let oe_ty = match mapping {
+                        Some(mapping) => mapping.object_entry().ty().name(),
                         None => "?".to_owned(),
                     };
                     writeln!(f, "{s5}<-{} : {}", oe_name, oe_ty)?;
@@ -280,13 +704,13 @@ impl Display for Network {
             writeln!(f, "{s3}rx_streams:")?;
             for stream in node.rx_streams() {
                 writeln!(f, "{s4}{} [{}]", stream.name(), stream.message().name())?;
-                for oe in stream.mapping() {
-                    let oe_name = match oe {
-                        Some(oe) => oe.name(),
+                for mapping in stream.mapping() {
+                    let oe_name = match mapping {
+                        Some(mapping) => mapping.name(),
                         None => "None",
                     };
-                    let oe_ty = match oe {
-                        Some(oe) => oe.ty().name(),
+                    let oe_ty = match mapping {
+                        Some(mapping) => mapping.object_entry().ty().name(),
                         None => "?".to_owned(),
                     };
                     writeln!(f, "{s5}->{} : {}", oe_name, oe_ty)?;
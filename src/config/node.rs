@@ -1,12 +1,14 @@
-use std::hash::Hash;
+use std::{collections::HashMap, hash::{Hash, Hasher}, time::Duration};
 
-use super::{ConfigRef, TypeRef, CommandRef, stream::StreamRef, MessageRef, ObjectEntryRef, bus::BusRef};
+use crate::{errors, export::eds};
+
+use super::{ConfigRef, TypeRef, CommandRef, stream::StreamRef, Message, MessageRef, ObjectEntryRef, ObjectEntryGroupRef, bus::BusRef, RxFilter, TransportChannelRef, Uds, TxSchedule, Version};
 
 
 pub type NodeRef = ConfigRef<Node>;
 
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Node {
     name: String,
     description: Option<String>,
@@ -24,7 +26,20 @@ pub struct Node {
     tx_messages: Vec<MessageRef>,
 
     object_entries: Vec<ObjectEntryRef>,
+    object_entry_groups: Vec<ObjectEntryGroupRef>,
+    transport_channels: Vec<TransportChannelRef>,
+    uds: Option<Uds>,
     buses : Vec<BusRef>,
+    tx_mailbox_count : Option<u8>,
+    extern_node : bool,
+    firmware_version : Option<Version>,
+    rx_timeouts : Vec<(MessageRef, Duration)>,
+    tx_schedule : TxSchedule,
+    rx_filters : Vec<(BusRef, Vec<RxFilter>)>,
+    od_protocol : bool,
+    listen_only : bool,
+    // object dictionary index to position in object_entries.
+    object_entry_indices : HashMap<u32, usize>,
 }
 
 impl Hash for Node {
@@ -33,6 +48,7 @@ impl Hash for Node {
             state.write_u8(b);
         }
         state.write_u8(self.id);
+        self.extern_node.hash(state);
         for c in &self.commands {
             c.hash(state);
         }
@@ -51,21 +67,84 @@ impl Hash for Node {
         for oe in &self.object_entries {
             oe.hash(state);
         }
+        for group in &self.object_entry_groups {
+            group.hash(state);
+        }
+        for channel in &self.transport_channels {
+            channel.hash(state);
+        }
+        if let Some(uds) = &self.uds {
+            uds.hash(state);
+        }
+        for (message, timeout) in &self.rx_timeouts {
+            for b in message.name().bytes() {
+                state.write_u8(b);
+            }
+            timeout.hash(state);
+        }
+        if self.listen_only {
+            state.write_u8(1);
+        }
     }
 }
 
+/// Parts of a node, see `Node::new`.
+#[derive(Debug)]
+pub struct NodeParams {
+    pub name: String,
+    pub description: Option<String>,
+    pub id: u8,
+    pub types: Vec<TypeRef>,
+    pub commands: Vec<CommandRef>,
+    pub extern_commands: Vec<(String, CommandRef)>,
+    pub tx_streams: Vec<StreamRef>,
+    pub rx_streams: Vec<StreamRef>,
+    pub rx_messages: Vec<MessageRef>,
+    pub tx_messages: Vec<MessageRef>,
+    pub object_entries: Vec<ObjectEntryRef>,
+    pub object_entry_groups: Vec<ObjectEntryGroupRef>,
+    pub transport_channels: Vec<TransportChannelRef>,
+    pub uds: Option<Uds>,
+    pub buses: Vec<BusRef>,
+    pub tx_mailbox_count: Option<u8>,
+    pub extern_node: bool,
+    pub firmware_version: Option<Version>,
+    pub rx_timeouts: Vec<(MessageRef, Duration)>,
+    pub rx_filters: Vec<(BusRef, Vec<RxFilter>)>,
+    pub od_protocol: bool,
+    pub listen_only: bool,
+}
+
 impl Node {
-    pub fn new(name : String, description : Option<String>, id : u8,
-               types : Vec<TypeRef>,
-               commands : Vec<CommandRef>,
-               extern_commands : Vec<(String, CommandRef)>,
-               tx_streams : Vec<StreamRef>,
-               rx_streams : Vec<StreamRef>,
-               rx_messages : Vec<MessageRef>,
-               tx_messages : Vec<MessageRef>,
-               object_entries : Vec<ObjectEntryRef>,
-               buses : Vec<BusRef>)-> Self{
+    pub fn new(params: NodeParams) -> Self {
+        let NodeParams {
+            name,
+            description,
+            id,
+            types,
+            commands,
+            extern_commands,
+            tx_streams,
+            rx_streams,
+            rx_messages,
+            tx_messages,
+            object_entries,
+            object_entry_groups,
+            transport_channels,
+            uds,
+            buses,
+            tx_mailbox_count,
+            extern_node,
+            firmware_version,
+            rx_timeouts,
+            rx_filters,
+            od_protocol,
+            listen_only,
+        } = params;
+        let object_entry_indices = object_entries.iter().enumerate().map(|(i, oe)| (oe.id(), i)).collect();
+        let tx_schedule = TxSchedule::new(&tx_messages);
         Self {
+            object_entry_indices,
             name,
             description,
             id,
@@ -77,7 +156,18 @@ impl Node {
             rx_messages,
             tx_messages,
             object_entries,
+            object_entry_groups,
+            transport_channels,
+            uds,
             buses,
+            tx_mailbox_count,
+            extern_node,
+            firmware_version,
+            rx_timeouts,
+            tx_schedule,
+            rx_filters,
+            od_protocol,
+            listen_only,
         }
     }
 
@@ -94,18 +184,12 @@ impl Node {
     pub fn extern_commands(&self) -> &Vec<(String, CommandRef)> {
         &self.extern_commands
     }
-    pub fn extern_commands_mut(&mut self) -> &mut Vec<(String, CommandRef)> {
-        &mut self.extern_commands
-    }
     pub fn tx_streams(&self) -> &Vec<StreamRef> {
         &self.tx_streams
     }
     pub fn rx_streams(&self) -> &Vec<StreamRef> {
         &self.rx_streams
     }
-    pub fn rx_streams_mut(&mut self) -> &mut Vec<StreamRef> {
-        &mut self.rx_streams
-    }
     pub fn tx_messages(&self) -> &Vec<MessageRef> {
         &self.tx_messages
     }
@@ -115,6 +199,21 @@ impl Node {
     pub fn object_entries(&self) -> &Vec<ObjectEntryRef> {
         &self.object_entries
     }
+    /// Object entry with the given index in the object dictionary.
+    pub fn object_entry_by_index(&self, index: u32) -> Option<&ObjectEntryRef> {
+        self.object_entry_indices.get(&index).map(|&i| &self.object_entries[i])
+    }
+    pub fn object_entry_groups(&self) -> &Vec<ObjectEntryGroupRef> {
+        &self.object_entry_groups
+    }
+    /// transport channels the node is sending or receiving on.
+    pub fn transport_channels(&self) -> &Vec<TransportChannelRef> {
+        &self.transport_channels
+    }
+    /// diagnostic services the node answers to.
+    pub fn uds(&self) -> Option<&Uds> {
+        self.uds.as_ref()
+    }
     pub fn description(&self) -> Option<&String> {
         match &self.description {
             Some(some) => Some(&some),
@@ -127,4 +226,80 @@ impl Node {
     pub fn buses(&self) -> &Vec<BusRef> {
         &self.buses
     }
+    /// Third-party device, which only transmits and receives messages with
+    /// fixed ids and has no object dictionary.
+    pub fn is_extern(&self) -> bool {
+        self.extern_node
+    }
+    /// Receives get/set requests and transmits their responses,
+    /// false for extern nodes and nodes, which disabled the protocol.
+    pub fn has_od_protocol(&self) -> bool {
+        self.od_protocol
+    }
+    /// Node with a listen-only transceiver (e.g. a logger), which never transmits.
+    pub fn is_listen_only(&self) -> bool {
+        self.listen_only
+    }
+    /// Exports the object dictionary as a CANopen electronic data sheet.
+    pub fn to_eds(&self) -> errors::Result<String> {
+        eds::node_to_eds(self, false)
+    }
+    /// Exports the object dictionary as a CANopen device configuration file,
+    /// which additionally contains the node id and baudrate.
+    pub fn to_dcf(&self) -> errors::Result<String> {
+        eds::node_to_eds(self, true)
+    }
+    /// rx messages, whose freshness is supervised, with their timeouts.
+    pub fn rx_timeouts(&self) -> &Vec<(MessageRef, Duration)> {
+        &self.rx_timeouts
+    }
+    pub fn rx_timeout(&self, message: &Message) -> Option<Duration> {
+        self.rx_timeouts
+            .iter()
+            .find(|(m, _)| m.name() == message.name())
+            .map(|(_, timeout)| *timeout)
+    }
+    /// Version of the configuration the firmware of the node targets.
+    pub fn firmware_version(&self) -> Option<&Version> {
+        self.firmware_version.as_ref()
+    }
+    /// Offsets of the periodic tx messages, which de-synchronize their transmissions.
+    pub fn tx_schedule(&self) -> &TxSchedule {
+        &self.tx_schedule
+    }
+    /// Acceptance filters of the node per bus, which accept every message
+    /// the node receives on the bus.
+    pub fn rx_filters(&self) -> &Vec<(BusRef, Vec<RxFilter>)> {
+        &self.rx_filters
+    }
+    pub fn tx_mailbox_count(&self) -> Option<u8> {
+        self.tx_mailbox_count
+    }
+    /// Upper bound of transmissions that can be pending at the same time
+    /// on a single bus of the node. Every stream, command response, extern
+    /// command request, od response and the heartbeat may be enqueued at the
+    /// same instant, so each tx message can occupy a mailbox.
+    pub fn required_tx_mailboxes(&self) -> usize {
+        self.tx_messages
+            .iter()
+            .map(|message| {
+                self.tx_messages
+                    .iter()
+                    .filter(|m| m.bus().id() == message.bus().id())
+                    .count()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+    /// Deterministic hash over everything the node depends on
+    /// (its messages, types and object entries), can be compared against
+    /// the config_hash object entry of a running node.
+    pub fn config_hash(&self) -> u64 {
+        let mut hasher = seahash::SeaHasher::new();
+        self.hash(&mut hasher);
+        self.tx_messages.hash(&mut hasher);
+        self.rx_messages.hash(&mut hasher);
+        self.types.hash(&mut hasher);
+        hasher.finish()
+    }
 }
@@ -0,0 +1,60 @@
+use std::hash::Hash;
+
+use super::{ConfigRef, Node, NodeRef};
+
+/// server_id of get/set requests, which are addressed to all nodes,
+/// with the default 8 bit node_id, see `Network::broadcast_server_id`.
+pub const BROADCAST_SERVER_ID: u8 = 0xFF;
+
+pub type NodeGroupRef = ConfigRef<NodeGroup>;
+
+/// Nodes, which can be addressed together by the get/set protocol
+/// with the reserved server_id of the group.
+///
+/// Group server_ids are assigned downwards from below the broadcast id,
+/// in the order the groups were defined.
+#[derive(Debug, PartialEq)]
+pub struct NodeGroup {
+    name: String,
+    description: Option<String>,
+    server_id: u8,
+    nodes: Vec<NodeRef>,
+}
+
+impl Hash for NodeGroup {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        state.write_u8(self.server_id);
+        for node in &self.nodes {
+            state.write_u8(node.id());
+        }
+    }
+}
+
+impl NodeGroup {
+    pub fn new(name: String, description: Option<String>, server_id: u8, nodes: Vec<NodeRef>) -> Self {
+        Self {
+            name,
+            description,
+            server_id,
+            nodes,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn server_id(&self) -> u8 {
+        self.server_id
+    }
+    pub fn nodes(&self) -> &Vec<NodeRef> {
+        &self.nodes
+    }
+    pub fn contains(&self, node: &Node) -> bool {
+        self.nodes.iter().any(|n| n.name() == node.name())
+    }
+}
@@ -1,15 +1,16 @@
-use std::{hash::Hash, sync::OnceLock};
+use std::hash::Hash;
 
-use super::{ConfigRef, TypeRef, Visibility, NodeRef};
+use super::{AccessPlan, ConfigRef, ConfigWeak, Lifecycle, TypeRef, Visibility, Node, NodeRef, OverflowPolicy};
 
 
 pub type ObjectEntryRef = ConfigRef<ObjectEntry>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ObjectEntryAccess {
     Const,  // no write
     Local,  // local write public read
     Global, // public write
+    Protected { level: String }, // public write for clients with the access level
 }
 
 impl Hash for ObjectEntryAccess {
@@ -18,6 +19,12 @@ impl Hash for ObjectEntryAccess {
             ObjectEntryAccess::Const => state.write_u8(0),
             ObjectEntryAccess::Local => state.write_u8(1),
             ObjectEntryAccess::Global => state.write_u8(2),
+            ObjectEntryAccess::Protected { level } => {
+                state.write_u8(3);
+                for b in level.bytes() {
+                    state.write_u8(b);
+                }
+            }
         }
     }
 }
@@ -31,7 +38,13 @@ pub struct ObjectEntry {
     ty: TypeRef,
     access: ObjectEntryAccess,
     visibility: Visibility,
-    node : OnceLock<NodeRef>,
+    default: Option<String>,
+    persistent: bool,
+    overflow: OverflowPolicy,
+    range: Option<(f64, f64)>,
+    broadcast: bool,
+    node : ConfigWeak<Node>,
+    lifecycle : Lifecycle,
 }
 
 impl Hash for ObjectEntry {
@@ -52,16 +65,64 @@ impl Hash for ObjectEntry {
         self.ty.hash(state);
         self.access.hash(state);
         self.visibility.hash(state);
+        match &self.default {
+            Some(default) => {
+                state.write_u8(1);
+                for b in default.bytes() {
+                    state.write_u8(b);
+                }
+            }
+            None => state.write_u8(0),
+        }
+        state.write_u8(self.persistent as u8);
+        self.overflow.hash(state);
+        match &self.range {
+            Some((min, max)) => {
+                state.write_u8(1);
+                state.write_u64(min.to_bits());
+                state.write_u64(max.to_bits());
+            }
+            None => state.write_u8(0),
+        }
+        state.write_u8(self.broadcast as u8);
+    }
+}
+
+/// The owning node isn't compared, it's a back reference of the node,
+/// which contains the object entry. The range is compared bitwise like
+/// it's hashed, so equal object entries always have the same hash.
+impl PartialEq for ObjectEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.description == other.description
+            && self.unit == other.unit
+            && self.id == other.id
+            && self.ty == other.ty
+            && self.access == other.access
+            && self.visibility == other.visibility
+            && self.default == other.default
+            && self.persistent == other.persistent
+            && self.overflow == other.overflow
+            && self.range.map(|(min, max)| (min.to_bits(), max.to_bits()))
+                == other.range.map(|(min, max)| (min.to_bits(), max.to_bits()))
+            && self.broadcast == other.broadcast
+            && self.lifecycle == other.lifecycle
     }
 }
 
 impl ObjectEntry {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(name : String, description : Option<String>,
                unit : Option<String>,
                id : u32,
                ty : TypeRef,
                access : ObjectEntryAccess,
-               visibility : Visibility) -> Self {
+               visibility : Visibility,
+               default : Option<String>,
+               persistent : bool,
+               overflow : OverflowPolicy,
+               range : Option<(f64, f64)>,
+               broadcast : bool) -> Self {
         Self {
             name,
             description,
@@ -70,7 +131,13 @@ impl ObjectEntry {
             ty,
             access,
             visibility,
-            node : OnceLock::new(),
+            default,
+            persistent,
+            overflow,
+            range,
+            broadcast,
+            node : ConfigWeak::new(),
+            lifecycle : Lifecycle::default(),
         }
     }
     pub fn id(&self) -> u32 {
@@ -91,16 +158,93 @@ impl ObjectEntry {
     pub fn access(&self) -> &ObjectEntryAccess {
         &self.access
     }
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+    /// Accepts set requests addressed to a node group or to all nodes.
+    pub fn broadcast(&self) -> bool {
+        self.broadcast
+    }
     pub fn unit(&self) -> Option<&str> {
         match &self.unit {
             Some(unit) => Some(&unit),
             None => None,
         }
     }
-    pub fn __set_node(&self, node : NodeRef){
-        self.node.set(node).expect("can't set the node of a object entry");
+    /// value the object entry is initialized with
+    /// (number literal or enum entry name).
+    pub fn default_value(&self) -> Option<&str> {
+        self.default.as_deref()
+    }
+    pub fn persistent(&self) -> bool {
+        self.persistent
+    }
+    pub fn overflow(&self) -> OverflowPolicy {
+        self.overflow
+    }
+    /// declared (min, max) range of the object entry.
+    pub fn range(&self) -> Option<(f64, f64)> {
+        self.range
+    }
+    /// Sets the owning node before the object entry is shared.
+    pub(crate) fn with_node(self, node : ConfigWeak<Node>) -> Self {
+        Self { node, ..self }
+    }
+    pub(crate) fn with_lifecycle(self, lifecycle : Lifecycle) -> Self {
+        Self { lifecycle, ..self }
+    }
+    /// Versions in which the object entry was introduced and deprecated.
+    pub fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
+    }
+    /// Node owning the object entry, which is referenced weakly,
+    /// so it's None once the network was dropped.
+    pub fn node(&self) -> Option<NodeRef> {
+        self.node.upgrade()
+    }
+    /// Frames a client has to exchange with the owning node
+    /// to read or write the object entry, None once the network was dropped.
+    pub fn access_plan(&self) -> Option<AccessPlan> {
+        self.node().map(|node| AccessPlan::new(self, node.id()))
+    }
+}
+
+pub type ObjectEntryGroupRef = ConfigRef<ObjectEntryGroup>;
+
+/// Named set of related object entries of a node.
+#[derive(Debug, PartialEq)]
+pub struct ObjectEntryGroup {
+    name: String,
+    description: Option<String>,
+    object_entries: Vec<ObjectEntryRef>,
+}
+
+impl Hash for ObjectEntryGroup {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        for oe in &self.object_entries {
+            state.write_u32(oe.id());
+        }
+    }
+}
+
+impl ObjectEntryGroup {
+    pub fn new(name: String, description: Option<String>, object_entries: Vec<ObjectEntryRef>) -> Self {
+        Self {
+            name,
+            description,
+            object_entries,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
     }
-    pub fn node(&self) -> &NodeRef {
-        self.node.get().unwrap()
+    pub fn object_entries(&self) -> &Vec<ObjectEntryRef> {
+        &self.object_entries
     }
 }
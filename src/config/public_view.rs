@@ -0,0 +1,68 @@
+use super::{
+    stream::StreamRef, CommandRef, MessageRef, Network, Node, NodeRef, ObjectEntryRef,
+    TransportChannelRef, TypeRef, Visibility,
+};
+
+/// Projection of a network onto its globally visible elements,
+/// for external documentation and third party exports.
+///
+/// Static elements are internal to the firmware of their node and are hidden,
+/// the underlying network stays complete and is available through `network()`.
+#[derive(Debug, Clone, Copy)]
+pub struct PublicView<'a> {
+    network: &'a Network,
+}
+
+fn is_public(visibility: &Visibility) -> bool {
+    *visibility == Visibility::Global
+}
+
+impl<'a> PublicView<'a> {
+    pub fn new(network: &'a Network) -> Self {
+        Self { network }
+    }
+    pub fn network(&self) -> &'a Network {
+        self.network
+    }
+    pub fn nodes(&self) -> &'a Vec<NodeRef> {
+        self.network.nodes()
+    }
+    pub fn types(&self) -> Vec<&'a TypeRef> {
+        self.network
+            .types()
+            .iter()
+            .filter(|ty| is_public(&ty.visibility()))
+            .collect()
+    }
+    pub fn messages(&self) -> Vec<&'a MessageRef> {
+        self.network
+            .messages()
+            .iter()
+            .filter(|message| is_public(message.visibility()))
+            .collect()
+    }
+    pub fn object_entries(&self, node: &'a Node) -> Vec<&'a ObjectEntryRef> {
+        node.object_entries()
+            .iter()
+            .filter(|object_entry| is_public(object_entry.visibility()))
+            .collect()
+    }
+    pub fn tx_streams(&self, node: &'a Node) -> Vec<&'a StreamRef> {
+        node.tx_streams()
+            .iter()
+            .filter(|stream| is_public(stream.visibility()))
+            .collect()
+    }
+    pub fn commands(&self, node: &'a Node) -> Vec<&'a CommandRef> {
+        node.commands()
+            .iter()
+            .filter(|command| is_public(command.visibility()))
+            .collect()
+    }
+    pub fn transport_channels(&self, node: &'a Node) -> Vec<&'a TransportChannelRef> {
+        node.transport_channels()
+            .iter()
+            .filter(|channel| is_public(channel.visibility()))
+            .collect()
+    }
+}
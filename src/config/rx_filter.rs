@@ -0,0 +1,46 @@
+use super::MessageId;
+
+/// Acceptance filter of a CAN controller as resolved by the id assignment.
+///
+/// A frame passes the filter if it has the same id type and the bits of its
+/// id selected by the mask are equal to the bits of the filter id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RxFilter {
+    id: u32,
+    mask: u32,
+    ide: bool,
+}
+
+impl RxFilter {
+    /// bits of the id outside of the mask are ignored.
+    pub fn new(id: u32, mask: u32, ide: bool) -> Self {
+        Self {
+            id: id & mask,
+            mask,
+            ide,
+        }
+    }
+    /// filter, which only accepts the id.
+    pub fn exact(id: &MessageId) -> Self {
+        match id {
+            MessageId::StandardId(id) => Self::new(*id, 0x7FF, false),
+            MessageId::ExtendedId(id) => Self::new(*id, 0x1FFF_FFFF, true),
+        }
+    }
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+    /// true for filters of extended ids.
+    pub fn ide(&self) -> bool {
+        self.ide
+    }
+    pub fn accepts(&self, id: &MessageId) -> bool {
+        match id {
+            MessageId::StandardId(id) => !self.ide && id & self.mask == self.id,
+            MessageId::ExtendedId(id) => self.ide && id & self.mask == self.id,
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use super::{message::{MessageUsage, HEARTBEAT_INTERVAL}, Message, MessageId, MessageRef};
+
+/// Resolution of the offsets of a tx schedule.
+pub const TX_SCHEDULE_SLOT: Duration = Duration::from_millis(1);
+/// Upper bound of the cycle (in slots), within which collisions are minimized.
+const MAX_CYCLE_SLOTS: u64 = 10_000;
+
+/// Transmission of a periodic message, first at {offset} and then every {period}.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TxSlot {
+    message: MessageRef,
+    period: Duration,
+    offset: Duration,
+}
+
+impl TxSlot {
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+    pub fn offset(&self) -> Duration {
+        self.offset
+    }
+}
+
+/// Cyclic transmission schedule of the periodic messages of a node.
+/// Offsets are assigned greedily (shortest period and highest priority first),
+/// such that as few messages as possible are released in the same slot.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TxSchedule {
+    cycle: Duration,
+    slots: Vec<TxSlot>,
+    max_burst: usize,
+}
+
+/// Interval of messages, which are transmitted periodically.
+/// Streams are scheduled at their minimum interval.
+fn cyclic_period(message: &Message) -> Option<Duration> {
+    match message.usage() {
+        MessageUsage::Stream(stream) => stream.upgrade().map(|stream| *stream.min_interval()),
+        MessageUsage::External { interval } => Some(*interval),
+        MessageUsage::Heartbeat => Some(HEARTBEAT_INTERVAL),
+        MessageUsage::Debug { interval } => *interval,
+        _ => None,
+    }
+}
+
+/// Lower values win the arbitration.
+fn priority(id: &MessageId) -> (u32, bool) {
+    match id {
+        MessageId::StandardId(id) => (id << 18, false),
+        MessageId::ExtendedId(id) => (*id, true),
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl TxSchedule {
+    pub fn new(tx_messages: &[MessageRef]) -> Self {
+        let slot = TX_SCHEDULE_SLOT.as_micros() as u64;
+        let mut periodic: Vec<(MessageRef, u64)> = tx_messages
+            .iter()
+            .filter_map(|message| {
+                let period = cyclic_period(message)?;
+                let slots = (period.as_micros() as u64 / slot).max(1);
+                Some((message.clone(), slots))
+            })
+            .collect();
+        if periodic.is_empty() {
+            return Self::default();
+        }
+        periodic.sort_by_key(|(message, period)| (*period, priority(message.id())));
+
+        // hyperperiod of all messages, bounded by MAX_CYCLE_SLOTS.
+        let cycle = periodic.iter().fold(1, |lcm, (_, period)| {
+            (lcm / gcd(lcm, *period))
+                .saturating_mul(*period)
+                .min(MAX_CYCLE_SLOTS)
+        });
+
+        let mut load = vec![0usize; cycle as usize];
+        let mut slots = vec![];
+        for (message, period) in periodic {
+            let releases = |offset: u64| (offset..cycle).step_by(period as usize);
+            let mut best = (0, (usize::MAX, usize::MAX));
+            for offset in 0..period.min(cycle) {
+                let cost = releases(offset).fold((0, 0), |(max, sum), t| {
+                    (max.max(load[t as usize]), sum + load[t as usize])
+                });
+                if cost < best.1 {
+                    best = (offset, cost);
+                }
+                if cost == (0, 0) {
+                    // no collision at all.
+                    break;
+                }
+            }
+            let offset = best.0;
+            for t in releases(offset) {
+                load[t as usize] += 1;
+            }
+            slots.push(TxSlot {
+                message,
+                period: TX_SCHEDULE_SLOT * period as u32,
+                offset: TX_SCHEDULE_SLOT * offset as u32,
+            });
+        }
+        Self {
+            cycle: TX_SCHEDULE_SLOT * cycle as u32,
+            slots,
+            max_burst: load.into_iter().max().unwrap_or(0),
+        }
+    }
+    /// Length of the cycle after which the schedule repeats
+    /// (at most 10s, longer hyperperiods are truncated).
+    pub fn cycle(&self) -> Duration {
+        self.cycle
+    }
+    pub fn slots(&self) -> &Vec<TxSlot> {
+        &self.slots
+    }
+    pub fn slot_of(&self, message: &Message) -> Option<&TxSlot> {
+        self.slots.iter().find(|slot| slot.message.name() == message.name())
+    }
+    /// Maximum number of messages released in the same slot.
+    pub fn max_burst(&self) -> usize {
+        self.max_burst
+    }
+}
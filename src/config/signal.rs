@@ -1,10 +1,10 @@
-use std::{fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash, ops::Range};
 
-use super::ConfigRef;
+use super::{ConfigRef, Lifecycle};
 
 
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum SignalSign {
     Signed,
     Unsigned,
@@ -28,13 +28,39 @@ impl Display for SignalSign {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum SignalType {
     UnsignedInt { size: u8 },
     SignedInt { size: u8 },
     Decimal { size: u8, offset: f64, scale: f64 },
 }
 
+/// offset and scale are compared bitwise like they are hashed,
+/// so equal signal types always have the same hash.
+impl PartialEq for SignalType {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SignalType::UnsignedInt { size: a }, SignalType::UnsignedInt { size: b })
+            | (SignalType::SignedInt { size: a }, SignalType::SignedInt { size: b }) => a == b,
+            (
+                SignalType::Decimal { size, offset, scale },
+                SignalType::Decimal {
+                    size: other_size,
+                    offset: other_offset,
+                    scale: other_scale,
+                },
+            ) => {
+                size == other_size
+                    && offset.to_bits() == other_offset.to_bits()
+                    && scale.to_bits() == other_scale.to_bits()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SignalType {}
+
 impl Hash for SignalType {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         match &self {
@@ -49,8 +75,8 @@ impl Hash for SignalType {
             SignalType::Decimal { size, offset, scale } => {
                 state.write_u8(2);
                 state.write_u128(*size as u128);
-                ((*offset * 1e4) as u128).hash(state);
-                ((*scale * 1e4) as u128).hash(state);
+                state.write_u64(offset.to_bits());
+                state.write_u64(scale.to_bits());
             }
         }
     }
@@ -101,11 +127,126 @@ impl SignalType {
             } => SignalSign::Unsigned,
         }
     }
+    /// mask of the raw bits of the signal.
+    pub fn raw_mask(&self) -> u64 {
+        u64::MAX >> (64 - self.size().clamp(1, 64) as u32)
+    }
+    /// smallest and largest representable physical value.
+    pub fn physical_range(&self) -> (f64, f64) {
+        let max_raw = self.raw_mask() as f64;
+        match &self {
+            SignalType::UnsignedInt { size: _ } => (0.0, max_raw),
+            SignalType::SignedInt { size } => {
+                let half = (1u64 << (*size as u32 - 1)) as f64;
+                (-half, half - 1.0)
+            }
+            SignalType::Decimal {
+                size: _,
+                offset,
+                scale,
+            } => (*offset, *offset + *scale * max_raw),
+        }
+    }
+    /// Converts the raw bits of the signal into its physical value.
+    /// Bits above the size of the signal are ignored, signed values
+    /// are sign extended.
+    pub fn raw_to_physical(&self, raw: u64) -> f64 {
+        let raw = raw & self.raw_mask();
+        match &self {
+            SignalType::UnsignedInt { size: _ } => raw as f64,
+            SignalType::SignedInt { size } => {
+                let shift = 64 - *size as u32;
+                (((raw << shift) as i64) >> shift) as f64
+            }
+            SignalType::Decimal {
+                size: _,
+                offset,
+                scale,
+            } => raw as f64 * *scale + *offset,
+        }
+    }
+    /// Converts a physical value into the raw bits of the signal.
+    /// The value is rounded to the nearest representable value and saturated
+    /// to the range of the signal, NaN is mapped to the smallest value.
+    pub fn physical_to_raw(&self, value: f64) -> u64 {
+        let (min, max) = self.physical_range();
+        let value = if value.is_nan() { min } else { value.clamp(min, max) };
+        match &self {
+            SignalType::UnsignedInt { size: _ } => value.round() as u64,
+            SignalType::SignedInt { size: _ } => (value.round() as i64) as u64 & self.raw_mask(),
+            SignalType::Decimal {
+                size: _,
+                offset,
+                scale,
+            } => (((value - *offset) / *scale).round() as u64).min(self.raw_mask()),
+        }
+    }
+    /// converts a physical value to its raw representation, handling values
+    /// outside of the representable range according to the given policy.
+    /// Returns None if the policy is Error and the value is not representable.
+    pub fn physical_to_raw_with(&self, value: f64, policy: OverflowPolicy) -> Option<u64> {
+        let (min, max) = self.physical_range();
+        match policy {
+            OverflowPolicy::Saturate => Some(self.physical_to_raw(value)),
+            OverflowPolicy::Error => {
+                if value.is_nan() || value < min || value > max {
+                    None
+                } else {
+                    Some(self.physical_to_raw(value))
+                }
+            }
+            OverflowPolicy::Wrap => {
+                if !value.is_finite() {
+                    return Some(self.physical_to_raw(value));
+                }
+                let raw = match &self {
+                    SignalType::UnsignedInt { size: _ } | SignalType::SignedInt { size: _ } => {
+                        value.round() as i128
+                    }
+                    SignalType::Decimal {
+                        size: _,
+                        offset,
+                        scale,
+                    } => ((value - *offset) / *scale).round() as i128,
+                };
+                Some(raw as u64 & self.raw_mask())
+            }
+        }
+    }
+}
+
+/// Defines how values outside of the representable range of a signal are encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    #[default]
+    Saturate,
+    Error,
+    Wrap,
+}
+
+impl Hash for OverflowPolicy {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match &self {
+            OverflowPolicy::Saturate => state.write_u8(0),
+            OverflowPolicy::Error => state.write_u8(1),
+            OverflowPolicy::Wrap => state.write_u8(2),
+        }
+    }
+}
+
+impl Display for OverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            OverflowPolicy::Saturate => write!(f, "saturate"),
+            OverflowPolicy::Error => write!(f, "error"),
+            OverflowPolicy::Wrap => write!(f, "wrap"),
+        }
+    }
 }
 
 pub type SignalRef = ConfigRef<Signal>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Signal {
     pub name: String,
     pub description: Option<String>,
@@ -113,6 +254,12 @@ pub struct Signal {
     pub value_table: Option<ValueTableRef>,
     // refers to the byte offset!
     pub offset: usize,
+    pub overflow: OverflowPolicy,
+    pub lifecycle: Lifecycle,
+    /// nodes, which use the signal, empty if every receiver of the message does.
+    pub receivers: Vec<String>,
+    /// reserved region, which occupies bits of the frame without carrying a value.
+    pub reserved: bool,
 }
 
 impl Hash for Signal {
@@ -138,6 +285,11 @@ impl Hash for Signal {
             None => state.write_u8(1),
         }
         state.write_u128(self.offset as u128);
+        self.overflow.hash(state);
+        self.receivers.hash(state);
+        if self.reserved {
+            state.write_u8(1);
+        }
     }
 }
 
@@ -149,6 +301,10 @@ impl Signal {
             ty,
             offset,
             value_table : None,
+            overflow : OverflowPolicy::Saturate,
+            lifecycle : Lifecycle::default(),
+            receivers : vec![],
+            reserved : false,
         }
     }
     pub fn create(name : &str, description : Option<&str>, ty : SignalType) -> Signal {
@@ -158,6 +314,10 @@ impl Signal {
             ty,
             offset : 0,
             value_table : None,
+            overflow : OverflowPolicy::Saturate,
+            lifecycle : Lifecycle::default(),
+            receivers : vec![],
+            reserved : false,
         }
     }
     pub fn name(&self) -> &str {
@@ -175,9 +335,46 @@ impl Signal {
     pub fn scale(&self) -> f64 {
         self.ty.scale()
     }
+    /// Position of the least significant bit in the frame. Despite its name
+    /// the offset is given in bits, bit i of byte n is the bit 8n+i.
     pub fn byte_offset(&self) -> usize {
         self.offset
     }
+    /// Bits of the frame, which are occupied by the signal (little endian).
+    pub fn bit_range(&self) -> Range<usize> {
+        self.offset..self.offset + self.size() as usize
+    }
+    /// Byte of the frame, which contains the least significant bit.
+    pub fn start_byte(&self) -> usize {
+        self.offset / 8
+    }
+    /// Position of the least significant bit within its byte.
+    pub fn bit_in_byte(&self) -> u8 {
+        (self.offset % 8) as u8
+    }
+    /// true if the raw value has to be sign extended (two's complement).
+    pub fn is_signed(&self) -> bool {
+        matches!(self.ty, SignalType::SignedInt { .. })
+    }
+    /// Sign extends the raw value of a signed signal to 64 bit, the raw values
+    /// of unsigned signals are returned unchanged.
+    pub fn sign_extend(&self, raw: u64) -> i64 {
+        let shift = 64 - self.size() as u32;
+        if self.is_signed() {
+            ((raw << shift) as i64) >> shift
+        } else {
+            raw as i64
+        }
+    }
+    /// Start bit of the signal as written into DBC files (`@1`, little endian),
+    /// which is the position of the least significant bit.
+    pub fn dbc_start_bit(&self) -> usize {
+        self.offset
+    }
+    /// Start bit of the signal in the sawtooth numbering (see `dbc_to_sawtooth`).
+    pub fn sawtooth_start_bit(&self) -> usize {
+        dbc_to_sawtooth(self.offset)
+    }
     pub fn offset(&self) -> f64 {
         self.ty.offset()
     }
@@ -187,10 +384,60 @@ impl Signal {
     pub fn size(&self) -> u8 {
         self.ty.size()
     }
+    pub fn overflow(&self) -> OverflowPolicy {
+        self.overflow
+    }
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Signal {
+        self.overflow = overflow;
+        self
+    }
+    pub fn lifecycle(&self) -> &Lifecycle {
+        &self.lifecycle
+    }
+    /// Annotates the versions in which the signal was introduced and deprecated.
+    pub fn with_lifecycle(mut self, lifecycle: Lifecycle) -> Signal {
+        self.lifecycle = lifecycle;
+        self
+    }
+    /// Nodes, which use the signal. Empty if the signal isn't annotated,
+    /// then every receiver of the message uses it.
+    pub fn receivers(&self) -> &[String] {
+        &self.receivers
+    }
+    /// true for reserved regions (see `MessageSignalFormatBuilder::add_reserved`),
+    /// which are transmitted as zero and skipped by the generated accessors.
+    pub fn is_reserved(&self) -> bool {
+        self.reserved
+    }
+    pub fn with_receivers(mut self, receivers: Vec<String>) -> Signal {
+        self.receivers = receivers;
+        self
+    }
+    /// true if the node uses the signal, assuming it receives the message.
+    pub fn is_received_by(&self, node_name: &str) -> bool {
+        self.receivers.is_empty() || self.receivers.iter().any(|rx| rx == node_name)
+    }
+    /// converts a physical value to its raw representation respecting the
+    /// overflow policy of the signal.
+    pub fn physical_to_raw(&self, value: f64) -> Option<u64> {
+        self.ty.physical_to_raw_with(value, self.overflow)
+    }
+}
+
+/// Converts a bit position of the DBC numbering (bit i of byte n is 8n+i) into
+/// the sawtooth numbering of datasheets, which count the bits of every byte
+/// starting at the most significant one (bit i of byte n is 8n+7-i).
+pub fn dbc_to_sawtooth(bit: usize) -> usize {
+    bit - bit % 8 + 7 - bit % 8
+}
+
+/// Inverse of `dbc_to_sawtooth` (the conversion is its own inverse).
+pub fn sawtooth_to_dbc(bit: usize) -> usize {
+    dbc_to_sawtooth(bit)
 }
 
 pub type ValueTableRef = ConfigRef<ValueTable>;
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct ValueTable(pub Vec<(String, u64)>);
 
 impl Hash for ValueTable {
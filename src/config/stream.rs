@@ -1,18 +1,43 @@
 use std::{hash::Hash, time::Duration};
 
-use super::{ConfigRef, ObjectEntryRef, MessageRef, Visibility};
+use crate::errors::{ConfigError, Result};
+
+use super::{ConfigRef, ObjectEntryRef, MessageRef, SignalRef, SignalSign, SignalType, Visibility};
 
 
 pub type StreamRef = ConfigRef<Stream>;
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Stream {
     name: String,
     description: Option<String>,
-    mappings: Vec<Option<ObjectEntryRef>>,
+    mappings: Vec<Option<StreamMapping>>,
     message: MessageRef,
     visibility: Visibility,
     interval : (Duration, Duration),
+    encoding : StreamEncoding,
+    keyframe : Option<SignalRef>,
+}
+
+/// How the values of a stream are encoded in its message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StreamEncoding {
+    /// Every message contains the raw values of all entries.
+    #[default]
+    Absolute,
+    /// Every `keyframe_interval`-th message is a keyframe, which contains the
+    /// raw values of all entries, all other messages contain the difference
+    /// to the previous message. Differences are computed per signal modulo
+    /// 2^size of the signal, the receiver adds them (again modulo 2^size)
+    /// to the last known values. The 1 bit signal `<message>_keyframe`, which
+    /// follows the values (see `Stream::keyframe_signal`), is set for keyframes,
+    /// after a lost message the receiver has to wait for the next keyframe.
+    Delta { keyframe_interval: u32 },
+}
+
+/// Name of the signal, which marks the keyframes of a delta encoded stream.
+pub(crate) fn keyframe_signal_name(message_name: &str) -> String {
+    format!("{message_name}_keyframe")
 }
 
 impl Hash for Stream {
@@ -22,9 +47,12 @@ impl Hash for Stream {
         }
         for m in &self.mappings {
             match m {
-                Some(oe) => {
+                Some(mapping) => {
                     state.write_u8(1);
-                    oe.hash(state);
+                    mapping.object_entry.hash(state);
+                    if let Some(conversion) = &mapping.conversion {
+                        conversion.signal_type.hash(state);
+                    }
                 }
                 None => {
                     state.write_u8(0);
@@ -36,12 +64,15 @@ impl Hash for Stream {
         let us2 = self.interval.1.as_micros();
         state.write_u128(us1);
         state.write_u128(us2);
+        if self.encoding != StreamEncoding::Absolute {
+            self.encoding.hash(state);
+        }
     }
 }
 
 impl Stream {
     pub fn new(name : String, description : Option<String>,
-               mappings : Vec<Option<ObjectEntryRef>>,
+               mappings : Vec<Option<StreamMapping>>,
                message : MessageRef,
                visibility : Visibility,
                interval : (Duration,Duration)) -> Self {
@@ -52,8 +83,21 @@ impl Stream {
             message,
             visibility,
             interval,
+            encoding : StreamEncoding::Absolute,
+            keyframe : None,
         }
     }
+    pub(crate) fn with_encoding(self, encoding : StreamEncoding) -> Self {
+        // the keyframe signal is appended by the message assembly.
+        let keyframe = match encoding {
+            StreamEncoding::Absolute => None,
+            StreamEncoding::Delta { .. } => {
+                let name = keyframe_signal_name(self.message.name());
+                self.message.signals().iter().find(|s| s.name() == name).cloned()
+            }
+        };
+        Self { encoding, keyframe, ..self }
+    }
     pub fn min_interval(&self) -> &Duration {
         &self.interval.0
     }
@@ -75,10 +119,111 @@ impl Stream {
             None => None,
         }
     }
-    pub fn mapping(&self) -> &Vec<Option<ObjectEntryRef>> {
+    pub fn mapping(&self) -> &Vec<Option<StreamMapping>> {
         &self.mappings
     }
     pub fn message(&self) -> &MessageRef {
         &self.message
     }
+    pub fn encoding(&self) -> &StreamEncoding {
+        &self.encoding
+    }
+    /// Signal, which marks keyframes of delta encoded streams.
+    pub fn keyframe_signal(&self) -> Option<&SignalRef> {
+        self.keyframe.as_ref()
+    }
+}
+
+/// Object entry, which is mapped into the message of a stream.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StreamMapping {
+    object_entry: ObjectEntryRef,
+    conversion: Option<SignalConversion>,
+}
+
+impl StreamMapping {
+    pub fn new(object_entry: ObjectEntryRef, conversion: Option<SignalConversion>) -> Self {
+        Self {
+            object_entry,
+            conversion,
+        }
+    }
+    pub fn object_entry(&self) -> &ObjectEntryRef {
+        &self.object_entry
+    }
+    pub fn name(&self) -> &str {
+        self.object_entry.name()
+    }
+    /// Conversion of the object entry into the signal of the message,
+    /// None if the signal has the type of the object entry.
+    pub fn conversion(&self) -> Option<&SignalConversion> {
+        self.conversion.as_ref()
+    }
+}
+
+/// Linear conversion between the raw value of an object entry and the raw
+/// value of the signal, which encodes it in a stream:
+/// `signal = entry * factor + offset`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalConversion {
+    object_entry_type: SignalType,
+    signal_type: SignalType,
+}
+
+impl SignalConversion {
+    /// Fails if the signal can't represent every value of the object entry
+    /// (range and signedness) or has a coarser resolution.
+    pub fn new(object_entry_type: SignalType, signal_type: SignalType) -> Result<Self> {
+        // tolerate rounding errors of decimal bounds.
+        let eps = object_entry_type.scale().min(signal_type.scale()) * 1e-6;
+        let (oe_min, oe_max) = object_entry_type.physical_range();
+        let (signal_min, signal_max) = signal_type.physical_range();
+        if oe_min < 0.0 && signal_min >= 0.0 {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "signed values of {object_entry_type:?} can't be represented by {signal_type:?}"
+            )));
+        }
+        if oe_min < signal_min - eps || oe_max > signal_max + eps {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "range [{oe_min}, {oe_max}] of {object_entry_type:?} exceeds the range [{signal_min}, {signal_max}] of {signal_type:?}"
+            )));
+        }
+        if signal_type.scale() > object_entry_type.scale() + eps {
+            return Err(ConfigError::InvalidStreamMapping(format!(
+                "resolution {} of {signal_type:?} is coarser than the resolution {} of {object_entry_type:?}",
+                signal_type.scale(),
+                object_entry_type.scale()
+            )));
+        }
+        Ok(Self {
+            object_entry_type,
+            signal_type,
+        })
+    }
+    pub fn object_entry_type(&self) -> &SignalType {
+        &self.object_entry_type
+    }
+    pub fn signal_type(&self) -> &SignalType {
+        &self.signal_type
+    }
+    pub fn factor(&self) -> f64 {
+        self.object_entry_type.scale() / self.signal_type.scale()
+    }
+    pub fn offset(&self) -> f64 {
+        (self.object_entry_type.offset() - self.signal_type.offset()) / self.signal_type.scale()
+    }
+    /// raw value of the object entry is sign extended before the conversion.
+    pub fn is_signed(&self) -> bool {
+        matches!(self.object_entry_type.sign(), SignalSign::Signed)
+    }
+    /// Converts the raw value of the object entry into the raw value of the signal.
+    pub fn to_signal(&self, raw: u64) -> u64 {
+        self.signal_type
+            .physical_to_raw(self.object_entry_type.raw_to_physical(raw))
+    }
+    /// Converts the raw value of the signal into the raw value of the object entry.
+    pub fn to_object_entry(&self, raw: u64) -> u64 {
+        self.object_entry_type
+            .physical_to_raw(self.signal_type.raw_to_physical(raw))
+    }
 }
@@ -0,0 +1,53 @@
+use std::{hash::Hash, time::Duration};
+
+use super::{MessageRef, NodeRef};
+
+/// The supervisor detects a failure of the supervised node, if the
+/// message of the supervised node isn't received within the timeout.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Supervision {
+    supervisor: NodeRef,
+    supervised: NodeRef,
+    message: MessageRef,
+    timeout: Duration,
+}
+
+impl Hash for Supervision {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.supervisor.name().bytes() {
+            state.write_u8(b);
+        }
+        for b in self.supervised.name().bytes() {
+            state.write_u8(b);
+        }
+        for b in self.message.name().bytes() {
+            state.write_u8(b);
+        }
+        state.write_u128(self.timeout.as_micros());
+    }
+}
+
+impl Supervision {
+    pub fn new(supervisor: NodeRef, supervised: NodeRef, message: MessageRef, timeout: Duration) -> Self {
+        Self {
+            supervisor,
+            supervised,
+            message,
+            timeout,
+        }
+    }
+    /// Node, which receives the message.
+    pub fn supervisor(&self) -> &NodeRef {
+        &self.supervisor
+    }
+    /// Node, which transmits the message.
+    pub fn supervised(&self) -> &NodeRef {
+        &self.supervised
+    }
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    pub fn timeout(&self) -> &Duration {
+        &self.timeout
+    }
+}
@@ -0,0 +1,106 @@
+use std::{hash::Hash, time::Duration};
+
+use super::{ConfigRef, MessageRef, Visibility};
+
+pub type TransportChannelRef = ConfigRef<TransportChannel>;
+
+/// Pair of messages reserved for ISO 15765-2 segmented transfers
+/// between two nodes.
+#[derive(Debug, PartialEq)]
+pub struct TransportChannel {
+    name: String,
+    description: Option<String>,
+    tx_node: String,
+    rx_node: String,
+    data_message: MessageRef,
+    flow_control_message: MessageRef,
+    block_size: u8,
+    st_min: Duration,
+    visibility: Visibility,
+}
+
+impl Hash for TransportChannel {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        for b in self.tx_node.bytes() {
+            state.write_u8(b);
+        }
+        for b in self.rx_node.bytes() {
+            state.write_u8(b);
+        }
+        state.write_u32(self.data_message.id().as_u32());
+        state.write_u32(self.flow_control_message.id().as_u32());
+        state.write_u8(self.block_size);
+        state.write_u128(self.st_min.as_micros());
+        self.visibility.hash(state);
+    }
+}
+
+impl TransportChannel {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        name: String,
+        description: Option<String>,
+        tx_node: String,
+        rx_node: String,
+        data_message: MessageRef,
+        flow_control_message: MessageRef,
+        block_size: u8,
+        st_min: Duration,
+        visibility: Visibility,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            tx_node,
+            rx_node,
+            data_message,
+            flow_control_message,
+            block_size,
+            st_min,
+            visibility,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// name of the node sending the segmented data.
+    pub fn tx_node(&self) -> &str {
+        &self.tx_node
+    }
+    /// name of the node receiving the segmented data.
+    pub fn rx_node(&self) -> &str {
+        &self.rx_node
+    }
+    /// message carrying single, first and consecutive frames.
+    pub fn data_message(&self) -> &MessageRef {
+        &self.data_message
+    }
+    /// message carrying the flow control frames of the receiver.
+    pub fn flow_control_message(&self) -> &MessageRef {
+        &self.flow_control_message
+    }
+    pub fn block_size(&self) -> u8 {
+        self.block_size
+    }
+    pub fn st_min(&self) -> &Duration {
+        &self.st_min
+    }
+    /// STmin as encoded in the flow control frame.
+    pub fn st_min_byte(&self) -> u8 {
+        let us = self.st_min.as_micros();
+        if us > 0 && us < 1000 {
+            0xF0 + (us / 100) as u8
+        } else {
+            (us / 1000) as u8
+        }
+    }
+    pub fn visibility(&self) -> &Visibility {
+        &self.visibility
+    }
+}
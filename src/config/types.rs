@@ -4,6 +4,79 @@ use super::{ConfigRef, SignalType, Visibility};
 
 pub type TypeRef = ConfigRef<Type>;
 
+/// Unused bits in front of a struct attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AttributePadding {
+    #[default]
+    None,
+    /// fixed number of unused bits.
+    Bits(u8),
+    /// aligns the attribute to a multiple of the given number of bits
+    /// relative to the start of the struct, Align(8) aligns it to a byte.
+    Align(u8),
+}
+
+impl AttributePadding {
+    /// padding bits in front of an attribute starting at `offset`
+    /// (relative to the start of the struct).
+    pub fn bits(&self, offset: usize) -> usize {
+        match &self {
+            AttributePadding::None => 0,
+            AttributePadding::Bits(bits) => *bits as usize,
+            AttributePadding::Align(alignment) => {
+                let alignment = (*alignment).max(1) as usize;
+                offset.next_multiple_of(alignment) - offset
+            }
+        }
+    }
+}
+
+/// Documentation and padding of a single struct attribute.
+#[derive(Debug, Clone, Default, PartialEq, Hash)]
+pub struct StructAttributeInfo {
+    description: Option<String>,
+    padding: AttributePadding,
+}
+
+impl StructAttributeInfo {
+    pub fn new(description: Option<String>, padding: AttributePadding) -> Self {
+        Self {
+            description,
+            padding,
+        }
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    pub fn padding(&self) -> &AttributePadding {
+        &self.padding
+    }
+}
+
+/// Documentation of a single enum entry.
+#[derive(Debug, Clone, Default, PartialEq, Hash)]
+pub struct EnumEntryInfo {
+    description: Option<String>,
+    deprecated: bool,
+}
+
+impl EnumEntryInfo {
+    pub fn new(description: Option<String>, deprecated: bool) -> Self {
+        Self {
+            description,
+            deprecated,
+        }
+    }
+    /// what the state means.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// the entry is kept for compatibility, but shouldn't be used anymore.
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Type {
     Primitive(SignalType),
@@ -11,6 +84,8 @@ pub enum Type {
         name: String,
         description: Option<String>,
         attribs: Vec<(String, TypeRef)>,
+        /// documentation and padding of the attributes, in the same order as `attribs`.
+        attrib_info: Vec<StructAttributeInfo>,
         visibility: Visibility,
     },
     Enum {
@@ -18,12 +93,24 @@ pub enum Type {
         description: Option<String>,
         size: u8,
         entries: Vec<(String, u64)>,
+        /// documentation of the entries, in the same order as `entries`.
+        entry_info: Vec<EnumEntryInfo>,
         visibility: Visibility,
     },
     Array {
         len: usize,
         ty: TypeRef,
     },
+    /// Tagged union, the value of the tag enum selects which of the
+    /// variants occupies the bits following the tag.
+    Union {
+        name: String,
+        description: Option<String>,
+        tag: TypeRef,
+        /// (tag entry, payload type)
+        variants: Vec<(String, TypeRef)>,
+        visibility: Visibility,
+    },
 }
 
 impl Hash for Type  {
@@ -33,7 +120,7 @@ impl Hash for Type  {
                 state.write_u8(0);
                 signal.hash(state);
             }
-            Type::Struct { name, description, attribs, visibility } => {
+            Type::Struct { name, description, attribs, attrib_info, visibility } => {
                 state.write_u8(1);
                 for b in name.bytes() {
                     state.write_u8(b);
@@ -53,9 +140,10 @@ impl Hash for Type  {
                     }
                     y.hash(state);
                 }
+                attrib_info.hash(state);
                 visibility.hash(state);
             },
-            Type::Enum { name, description, size, entries, visibility } => {
+            Type::Enum { name, description, size, entries, entry_info, visibility } => {
                 state.write_u8(2);
                 for b in name.bytes() {
                     state.write_u8(b);
@@ -76,6 +164,7 @@ impl Hash for Type  {
                     }
                     state.write_u64(*y);
                 }
+                entry_info.hash(state);
                 visibility.hash(state);
             },
             Type::Array { len, ty } => {
@@ -83,6 +172,21 @@ impl Hash for Type  {
                 state.write_u128(*len as u128);
                 ty.hash(state);
             }
+            Type::Union { name, description, tag, variants, visibility } => {
+                state.write_u8(4);
+                for b in name.bytes() {
+                    state.write_u8(b);
+                }
+                description.hash(state);
+                tag.hash(state);
+                for (entry, ty) in variants {
+                    for b in entry.bytes() {
+                        state.write_u8(b);
+                    }
+                    ty.hash(state);
+                }
+                visibility.hash(state);
+            }
         }
     }
 }
@@ -109,6 +213,7 @@ impl Type {
                 name,
                 description: _,
                 attribs: _,
+                attrib_info: _,
                 visibility: _,
             } => name.to_owned(),
             Type::Enum {
@@ -116,9 +221,36 @@ impl Type {
                 description: _,
                 size: _,
                 entries: _,
+                entry_info: _,
                 visibility: _,
             } => name.to_owned(),
             Type::Array { len, ty } => format!("{}[{len}]", ty.name()),
+            Type::Union { name, .. } => name.to_owned(),
+        }
+    }
+    /// Documentation of an entry of an enum, None if the type isn't an enum
+    /// or has no entry with the given name.
+    pub fn enum_entry_info(&self, entry: &str) -> Option<&EnumEntryInfo> {
+        match &self {
+            Type::Enum {
+                entries,
+                entry_info,
+                ..
+            } => entries
+                .iter()
+                .position(|(name, _)| name == entry)
+                .and_then(|i| entry_info.get(i)),
+            _ => None,
+        }
+    }
+    /// Primitives are always global, arrays have the visibility of their elements.
+    pub fn visibility(&self) -> Visibility {
+        match &self {
+            Type::Primitive(_) => Visibility::Global,
+            Type::Struct { visibility, .. }
+            | Type::Enum { visibility, .. }
+            | Type::Union { visibility, .. } => visibility.clone(),
+            Type::Array { ty, .. } => ty.visibility(),
         }
     }
     pub fn size(&self) -> u32 {
@@ -128,16 +260,28 @@ impl Type {
                 name: _,
                 description: _,
                 attribs,
+                attrib_info,
                 visibility: _,
-            } => attribs.iter().map(|(_, attrib_ty)| attrib_ty.size()).sum(),
+            } => {
+                let mut offset = 0;
+                for ((_, attrib_ty), info) in attribs.iter().zip(attrib_info) {
+                    offset += info.padding().bits(offset as usize) as u32;
+                    offset += attrib_ty.size();
+                }
+                offset
+            }
             Type::Enum {
                 name: _,
                 description: _,
                 size,
                 entries: _,
+                entry_info: _,
                 visibility: _,
             } => *size as u32,
             Type::Array { len, ty } => ty.size() * *len as u32,
+            Type::Union { tag, variants, .. } => {
+                tag.size() + variants.iter().map(|(_, ty)| ty.size()).max().unwrap_or(0)
+            }
         }
     }
 }
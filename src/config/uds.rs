@@ -0,0 +1,109 @@
+use std::{fmt::Display, hash::Hash};
+
+use super::TransportChannelRef;
+
+/// Diagnostic services of ISO 14229-1 (UDS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UdsService {
+    DiagnosticSessionControl,
+    EcuReset,
+    ClearDiagnosticInformation,
+    ReadDtcInformation,
+    ReadDataByIdentifier,
+    ReadMemoryByAddress,
+    SecurityAccess,
+    CommunicationControl,
+    WriteDataByIdentifier,
+    RoutineControl,
+    RequestDownload,
+    RequestUpload,
+    TransferData,
+    RequestTransferExit,
+    WriteMemoryByAddress,
+    TesterPresent,
+}
+
+impl UdsService {
+    /// service identifier of the request.
+    pub fn sid(&self) -> u8 {
+        match &self {
+            UdsService::DiagnosticSessionControl => 0x10,
+            UdsService::EcuReset => 0x11,
+            UdsService::ClearDiagnosticInformation => 0x14,
+            UdsService::ReadDtcInformation => 0x19,
+            UdsService::ReadDataByIdentifier => 0x22,
+            UdsService::ReadMemoryByAddress => 0x23,
+            UdsService::SecurityAccess => 0x27,
+            UdsService::CommunicationControl => 0x28,
+            UdsService::WriteDataByIdentifier => 0x2E,
+            UdsService::RoutineControl => 0x31,
+            UdsService::RequestDownload => 0x34,
+            UdsService::RequestUpload => 0x35,
+            UdsService::TransferData => 0x36,
+            UdsService::RequestTransferExit => 0x37,
+            UdsService::WriteMemoryByAddress => 0x3D,
+            UdsService::TesterPresent => 0x3E,
+        }
+    }
+    /// service identifier of a positive response.
+    pub fn response_sid(&self) -> u8 {
+        self.sid() + 0x40
+    }
+}
+
+impl Hash for UdsService {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u8(self.sid());
+    }
+}
+
+impl Display for UdsService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?} (0x{:02X})", self.sid())
+    }
+}
+
+/// Diagnostic services a node answers to and the transport channels
+/// used for requests (tester -> node) and responses (node -> tester).
+#[derive(Debug, PartialEq)]
+pub struct Uds {
+    services: Vec<UdsService>,
+    request_channel: TransportChannelRef,
+    response_channel: TransportChannelRef,
+}
+
+impl Hash for Uds {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for service in &self.services {
+            service.hash(state);
+        }
+        self.request_channel.hash(state);
+        self.response_channel.hash(state);
+    }
+}
+
+impl Uds {
+    pub fn new(
+        services: Vec<UdsService>,
+        request_channel: TransportChannelRef,
+        response_channel: TransportChannelRef,
+    ) -> Self {
+        Self {
+            services,
+            request_channel,
+            response_channel,
+        }
+    }
+    pub fn services(&self) -> &Vec<UdsService> {
+        &self.services
+    }
+    pub fn supports(&self, service: UdsService) -> bool {
+        self.services.contains(&service)
+    }
+    pub fn request_channel(&self) -> &TransportChannelRef {
+        &self.request_channel
+    }
+    pub fn response_channel(&self) -> &TransportChannelRef {
+        &self.response_channel
+    }
+}
@@ -0,0 +1,301 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::errors::{ConfigError, Result};
+
+use super::{OverflowPolicy, SignalType, Type};
+
+/// Value of a type of the network, e.g. of an object entry.
+///
+/// Enums are referenced by the name of their entry and structs by the names
+/// of their attributes. A value is only meaningful together with the type it
+/// was validated against.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedValue {
+    Unsigned(u64),
+    Signed(i64),
+    Decimal(f64),
+    Enum(String),
+    Struct(BTreeMap<String, TypedValue>),
+    Array(Vec<TypedValue>),
+    /// the selected variant (an entry of the tag enum) and its payload.
+    Union {
+        variant: String,
+        value: Box<TypedValue>,
+    },
+}
+
+impl TypedValue {
+    /// Checks that the value is representable by the type.
+    pub fn validate(&self, ty: &Type) -> Result<()> {
+        self.validate_at(ty, "value")
+    }
+
+    fn validate_at(&self, ty: &Type, path: &str) -> Result<()> {
+        let invalid = |reason: String| {
+            Err(ConfigError::InvalidValue(format!(
+                "{path} = {self} is not a valid {}: {reason}",
+                ty.name()
+            )))
+        };
+        match (ty, self) {
+            (Type::Primitive(signal_type), value) => match Self::primitive_raw(signal_type, value) {
+                Some(_) => Ok(()),
+                None => invalid(format!("out of range {:?}", signal_type.physical_range())),
+            },
+            (Type::Enum { entries, .. }, TypedValue::Enum(entry)) => {
+                if entries.iter().any(|(name, _)| name == entry) {
+                    Ok(())
+                } else {
+                    invalid("not an entry of the enum".to_owned())
+                }
+            }
+            (Type::Struct { attribs, .. }, TypedValue::Struct(values)) => {
+                for (name, attrib_type) in attribs {
+                    let Some(value) = values.get(name) else {
+                        return invalid(format!("missing attribute {name}"));
+                    };
+                    value.validate_at(attrib_type, &format!("{path}.{name}"))?;
+                }
+                if let Some(name) = values
+                    .keys()
+                    .find(|name| !attribs.iter().any(|(attrib, _)| attrib == *name))
+                {
+                    return invalid(format!("unknown attribute {name}"));
+                }
+                Ok(())
+            }
+            (Type::Array { len, ty: element_type }, TypedValue::Array(values)) => {
+                if values.len() != *len {
+                    return invalid(format!("expected {len} elements, got {}", values.len()));
+                }
+                for (i, value) in values.iter().enumerate() {
+                    value.validate_at(element_type, &format!("{path}[{i}]"))?;
+                }
+                Ok(())
+            }
+            (Type::Union { variants, .. }, TypedValue::Union { variant, value }) => {
+                match variants.iter().find(|(entry, _)| entry == variant) {
+                    Some((_, variant_type)) => {
+                        value.validate_at(variant_type, &format!("{path}.{variant}"))
+                    }
+                    None => invalid(format!("no variant {variant}")),
+                }
+            }
+            _ => invalid("value has the wrong shape".to_owned()),
+        }
+    }
+
+    /// raw bits of a primitive value, None if the value isn't representable.
+    fn primitive_raw(signal_type: &SignalType, value: &TypedValue) -> Option<u64> {
+        let (min, max) = signal_type.physical_range();
+        match (signal_type, value) {
+            (SignalType::UnsignedInt { .. }, TypedValue::Unsigned(value)) => {
+                (*value <= signal_type.raw_mask()).then_some(*value)
+            }
+            (SignalType::UnsignedInt { .. }, TypedValue::Signed(value)) => {
+                u64::try_from(*value).ok().filter(|value| *value <= signal_type.raw_mask())
+            }
+            (SignalType::SignedInt { .. }, TypedValue::Signed(value)) => ((*value as f64) >= min
+                && (*value as f64) <= max)
+                .then_some(*value as u64 & signal_type.raw_mask()),
+            (SignalType::SignedInt { .. }, TypedValue::Unsigned(value)) => ((*value as f64) <= max)
+                .then_some(*value & signal_type.raw_mask()),
+            (SignalType::Decimal { .. }, TypedValue::Decimal(value)) => {
+                signal_type.physical_to_raw_with(*value, OverflowPolicy::Error)
+            }
+            (SignalType::Decimal { .. }, TypedValue::Unsigned(value)) => {
+                signal_type.physical_to_raw_with(*value as f64, OverflowPolicy::Error)
+            }
+            (SignalType::Decimal { .. }, TypedValue::Signed(value)) => {
+                signal_type.physical_to_raw_with(*value as f64, OverflowPolicy::Error)
+            }
+            _ => None,
+        }
+    }
+
+    /// Encodes the value into the ceil(size / 8) bytes of the type, using the
+    /// same little endian bit order as the messages (bit `i` is bit `i % 8`
+    /// of byte `i / 8`). Unused bits (padding, smaller union variants) are zero.
+    pub fn to_raw(&self, ty: &Type) -> Result<Vec<u8>> {
+        self.validate(ty)?;
+        let mut raw = vec![0u8; (ty.size() as usize).div_ceil(8)];
+        self.write(ty, &mut raw, 0);
+        Ok(raw)
+    }
+
+    // assumes that the value was validated against the type.
+    fn write(&self, ty: &Type, raw: &mut [u8], offset: usize) {
+        match (ty, self) {
+            (Type::Primitive(signal_type), value) => {
+                let bits = Self::primitive_raw(signal_type, value).unwrap();
+                write_bits(raw, offset, signal_type.size() as usize, bits);
+            }
+            (Type::Enum { size, entries, .. }, TypedValue::Enum(entry)) => {
+                let (_, bits) = entries.iter().find(|(name, _)| name == entry).unwrap();
+                write_bits(raw, offset, *size as usize, *bits);
+            }
+            (
+                Type::Struct {
+                    attribs,
+                    attrib_info,
+                    ..
+                },
+                TypedValue::Struct(values),
+            ) => {
+                let mut attrib_offset = 0;
+                for ((name, attrib_type), info) in attribs.iter().zip(attrib_info) {
+                    attrib_offset += info.padding().bits(attrib_offset);
+                    values[name].write(attrib_type, raw, offset + attrib_offset);
+                    attrib_offset += attrib_type.size() as usize;
+                }
+            }
+            (Type::Array { ty: element_type, .. }, TypedValue::Array(values)) => {
+                let element_size = element_type.size() as usize;
+                for (i, value) in values.iter().enumerate() {
+                    value.write(element_type, raw, offset + i * element_size);
+                }
+            }
+            (Type::Union { tag, variants, .. }, TypedValue::Union { variant, value }) => {
+                TypedValue::Enum(variant.clone()).write(tag, raw, offset);
+                let (_, variant_type) = variants.iter().find(|(entry, _)| entry == variant).unwrap();
+                value.write(variant_type, raw, offset + tag.size() as usize);
+            }
+            _ => unreachable!("value was validated against the type"),
+        }
+    }
+
+    /// Decodes a value of the type from its raw bytes, see `to_raw`.
+    /// Fails if there are fewer bytes than the type requires or the raw
+    /// value of an enum isn't one of its entries.
+    pub fn from_raw(ty: &Type, raw: &[u8]) -> Result<TypedValue> {
+        let required = (ty.size() as usize).div_ceil(8);
+        if raw.len() < required {
+            return Err(ConfigError::InvalidValue(format!(
+                "{} requires {required} bytes, got {}",
+                ty.name(),
+                raw.len()
+            )));
+        }
+        Self::read(ty, raw, 0)
+    }
+
+    fn read(ty: &Type, raw: &[u8], offset: usize) -> Result<TypedValue> {
+        match ty {
+            Type::Primitive(signal_type) => {
+                let bits = read_bits(raw, offset, signal_type.size() as usize);
+                Ok(match signal_type {
+                    SignalType::UnsignedInt { .. } => TypedValue::Unsigned(bits),
+                    SignalType::SignedInt { .. } => {
+                        TypedValue::Signed(signal_type.raw_to_physical(bits) as i64)
+                    }
+                    SignalType::Decimal { .. } => {
+                        TypedValue::Decimal(signal_type.raw_to_physical(bits))
+                    }
+                })
+            }
+            Type::Enum { size, entries, .. } => {
+                let bits = read_bits(raw, offset, *size as usize);
+                match entries.iter().find(|(_, value)| *value == bits) {
+                    Some((entry, _)) => Ok(TypedValue::Enum(entry.clone())),
+                    None => Err(ConfigError::InvalidValue(format!(
+                        "{bits} is not an entry of enum {}",
+                        ty.name()
+                    ))),
+                }
+            }
+            Type::Struct {
+                attribs,
+                attrib_info,
+                ..
+            } => {
+                let mut values = BTreeMap::new();
+                let mut attrib_offset = 0;
+                for ((name, attrib_type), info) in attribs.iter().zip(attrib_info) {
+                    attrib_offset += info.padding().bits(attrib_offset);
+                    values.insert(
+                        name.clone(),
+                        Self::read(attrib_type, raw, offset + attrib_offset)?,
+                    );
+                    attrib_offset += attrib_type.size() as usize;
+                }
+                Ok(TypedValue::Struct(values))
+            }
+            Type::Array { len, ty: element_type } => {
+                let element_size = element_type.size() as usize;
+                let values = (0..*len)
+                    .map(|i| Self::read(element_type, raw, offset + i * element_size))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(TypedValue::Array(values))
+            }
+            Type::Union { tag, variants, .. } => {
+                let TypedValue::Enum(variant) = Self::read(tag, raw, offset)? else {
+                    unreachable!("union tags are enums");
+                };
+                let Some((_, variant_type)) = variants.iter().find(|(entry, _)| *entry == variant)
+                else {
+                    return Err(ConfigError::InvalidValue(format!(
+                        "union {} has no variant {variant}",
+                        ty.name()
+                    )));
+                };
+                let value = Self::read(variant_type, raw, offset + tag.size() as usize)?;
+                Ok(TypedValue::Union {
+                    variant,
+                    value: Box::new(value),
+                })
+            }
+        }
+    }
+}
+
+fn write_bits(raw: &mut [u8], offset: usize, size: usize, value: u64) {
+    for i in 0..size.min(64) {
+        if value >> i & 1 != 0 {
+            let bit = offset + i;
+            raw[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+}
+
+fn read_bits(raw: &[u8], offset: usize, size: usize) -> u64 {
+    let mut value = 0;
+    for i in 0..size.min(64) {
+        let bit = offset + i;
+        if raw[bit / 8] >> (bit % 8) & 1 != 0 {
+            value |= 1 << i;
+        }
+    }
+    value
+}
+
+impl Display for TypedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            TypedValue::Unsigned(value) => write!(f, "{value}"),
+            TypedValue::Signed(value) => write!(f, "{value}"),
+            TypedValue::Decimal(value) => write!(f, "{value}"),
+            TypedValue::Enum(entry) => write!(f, "{entry}"),
+            TypedValue::Struct(values) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                write!(f, "}}")
+            }
+            TypedValue::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            TypedValue::Union { variant, value } => write!(f, "{variant}({value})"),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+use crate::errors::ConfigError;
+
+/// Firmware / hardware revision in the form `v{major}.{minor}.{patch}`,
+/// the leading `v` and trailing components are optional ("2", "v2.3").
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl Version {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+}
+
+impl FromStr for Version {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ConfigError::InvalidVersion(format!("{s:?} is not a version (e.g. v2.3)"));
+        let digits = s.strip_prefix('v').unwrap_or(s);
+        let parts = digits
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<u32>, ConfigError>>()?;
+        match parts[..] {
+            [major] => Ok(Version::new(major, 0, 0)),
+            [major, minor] => Ok(Version::new(major, minor, 0)),
+            [major, minor, patch] => Ok(Version::new(major, minor, patch)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.patch == 0 {
+            write!(f, "v{}.{}", self.major, self.minor)
+        } else {
+            write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+        }
+    }
+}
+
+/// Versions in which an element (message, signal or object entry)
+/// was added to and deprecated in the configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Lifecycle {
+    introduced_in: Option<Version>,
+    deprecated_in: Option<Version>,
+}
+
+impl Lifecycle {
+    pub fn new(introduced_in: Option<Version>, deprecated_in: Option<Version>) -> Self {
+        Self {
+            introduced_in,
+            deprecated_in,
+        }
+    }
+    pub fn introduced_in(&self) -> Option<&Version> {
+        self.introduced_in.as_ref()
+    }
+    pub fn deprecated_in(&self) -> Option<&Version> {
+        self.deprecated_in.as_ref()
+    }
+    /// false if the element was introduced after `version`.
+    pub fn is_available_in(&self, version: &Version) -> bool {
+        self.introduced_in.is_none_or(|introduced| introduced <= *version)
+    }
+    pub fn is_deprecated_in(&self, version: &Version) -> bool {
+        self.deprecated_in.is_some_and(|deprecated| deprecated <= *version)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.introduced_in.is_none() && self.deprecated_in.is_none()
+    }
+}
@@ -0,0 +1,115 @@
+use super::{
+    encoding::TypeSignalEncoding, signal::Signal, stream::Stream, Command, Message, Network,
+    Node, ObjectEntry, Type,
+};
+
+/// Traversal over a built network.
+///
+/// Every visit function defaults to the matching walk function, which
+/// visits the children of the element. Overriding a visit function and
+/// calling the walk function from it continues the traversal,
+/// not calling it skips the children.
+///
+/// The network is traversed as
+/// - types, recursing into struct attributes and array elements
+/// - nodes with their object entries (and their types), tx streams and commands
+/// - messages with their encodings, recursing into composite encodings,
+///   and all their signals
+pub trait NetworkVisitor {
+    fn visit_network(&mut self, network: &Network) {
+        walk_network(self, network);
+    }
+    fn visit_node(&mut self, node: &Node) {
+        walk_node(self, node);
+    }
+    fn visit_object_entry(&mut self, object_entry: &ObjectEntry) {
+        walk_object_entry(self, object_entry);
+    }
+    fn visit_stream(&mut self, _stream: &Stream) {}
+    fn visit_command(&mut self, _command: &Command) {}
+    fn visit_message(&mut self, message: &Message) {
+        walk_message(self, message);
+    }
+    fn visit_encoding(&mut self, encoding: &TypeSignalEncoding) {
+        walk_encoding(self, encoding);
+    }
+    fn visit_signal(&mut self, _signal: &Signal) {}
+    fn visit_type(&mut self, ty: &Type) {
+        walk_type(self, ty);
+    }
+}
+
+pub fn walk_network<V: NetworkVisitor + ?Sized>(visitor: &mut V, network: &Network) {
+    for ty in network.types() {
+        visitor.visit_type(ty);
+    }
+    for node in network.nodes() {
+        visitor.visit_node(node);
+    }
+    for message in network.messages() {
+        visitor.visit_message(message);
+    }
+}
+
+pub fn walk_node<V: NetworkVisitor + ?Sized>(visitor: &mut V, node: &Node) {
+    for object_entry in node.object_entries() {
+        visitor.visit_object_entry(object_entry);
+    }
+    for stream in node.tx_streams() {
+        visitor.visit_stream(stream);
+    }
+    for command in node.commands() {
+        visitor.visit_command(command);
+    }
+}
+
+pub fn walk_object_entry<V: NetworkVisitor + ?Sized>(visitor: &mut V, object_entry: &ObjectEntry) {
+    visitor.visit_type(object_entry.ty());
+}
+
+pub fn walk_message<V: NetworkVisitor + ?Sized>(visitor: &mut V, message: &Message) {
+    if let Some(encoding) = message.encoding() {
+        for attribute in encoding.attributes() {
+            visitor.visit_encoding(attribute);
+        }
+    }
+    for signal in message.signals() {
+        visitor.visit_signal(signal);
+    }
+}
+
+/// Recurses into the attributes of composite encodings and the variants of
+/// multiplexed encodings, the signals of primitive encodings are visited by walk_message.
+pub fn walk_encoding<V: NetworkVisitor + ?Sized>(visitor: &mut V, encoding: &TypeSignalEncoding) {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => {
+            for attribute in composite.attributes() {
+                visitor.visit_encoding(attribute);
+            }
+        }
+        TypeSignalEncoding::Multiplexed(mux) => {
+            for (_, _, variant) in mux.variants() {
+                visitor.visit_encoding(variant);
+            }
+        }
+        TypeSignalEncoding::Primitive(_) => (),
+    }
+}
+
+pub fn walk_type<V: NetworkVisitor + ?Sized>(visitor: &mut V, ty: &Type) {
+    match ty {
+        Type::Struct { attribs, .. } => {
+            for (_, attrib_ty) in attribs {
+                visitor.visit_type(attrib_ty);
+            }
+        }
+        Type::Array { ty, .. } => visitor.visit_type(ty),
+        Type::Union { tag, variants, .. } => {
+            visitor.visit_type(tag);
+            for (_, variant_ty) in variants {
+                visitor.visit_type(variant_ty);
+            }
+        }
+        Type::Primitive(_) | Type::Enum { .. } => (),
+    }
+}
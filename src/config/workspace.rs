@@ -0,0 +1,35 @@
+use super::{ConfigRef, NetworkRef, NodeRef};
+
+pub type WorkspaceRef = ConfigRef<Workspace>;
+
+/// Named networks, which were built together, see `WorkspaceBuilder`.
+#[derive(Debug)]
+pub struct Workspace {
+    networks: Vec<(String, NetworkRef)>,
+}
+
+impl Workspace {
+    pub fn new(networks: Vec<(String, NetworkRef)>) -> Self {
+        Self { networks }
+    }
+    /// (name, network) in the order of their creation.
+    pub fn networks(&self) -> &Vec<(String, NetworkRef)> {
+        &self.networks
+    }
+    pub fn network(&self, name: &str) -> Option<&NetworkRef> {
+        self.networks
+            .iter()
+            .find(|(network_name, _)| network_name == name)
+            .map(|(_, network)| network)
+    }
+    /// (network name, node) of every network, which contains the node.
+    pub fn node_instances(&self, node_name: &str) -> Vec<(&str, &NodeRef)> {
+        self.networks
+            .iter()
+            .filter_map(|(name, network)| {
+                let node = network.nodes().iter().find(|node| node.name() == node_name)?;
+                Some((name.as_str(), node))
+            })
+            .collect()
+    }
+}
@@ -6,14 +6,72 @@ pub enum ConfigError {
     InvalidRange(String),
     InvalidType(String),
     DuplicatedSignal(String),
+    OverlappingSignals(String),
+    InvalidSignalGroup(String),
+    InvalidSignalReceiver(String),
+    MessageTooLong(String),
     DuplicatedEnumEntry(String),
+    UndefinedEnumEntry(String),
     DuplicatedStructAttribute(String),
+    DuplicatedType(String),
+    DuplicatedMessage(String),
+    DuplicatedNode(String),
+    DuplicatedObjectEntry(String),
+    DuplicatedStream(String),
+    DuplicatedCommand(String),
+    DuplicatedBus(String),
+    /// a name isn't a valid C identifier or is a C/C++ keyword.
+    InvalidName(String),
+    /// two definitions of the same category share a name, names both definitions.
+    DuplicateName(String),
+    DuplicatedObjectEntryIndex(String),
+    DuplicatedNodeId(String),
+    InvalidObjectEntryIndex(String),
+    InvalidNodeId(String),
+    InvalidDefaultValue(String),
+    InvalidValue(String),
+    DuplicatedAccessLevel(String),
+    UndefinedAccessLevel(String),
     UndefinedType(String),
+    CyclicType(String),
+    UndefinedMessage(String),
+    UndefinedSignal(String),
+    UndefinedNode(String),
+    UndefinedObjectEntry(String),
+    UndefinedGroup(String),
+    UndefinedVariant(String),
     InvalidDecimalDefinition(String),
+    InvalidJ1939Id(String),
+    InvalidUdsConfiguration(String),
+    InvalidBroadcast(String),
+    InvalidExternNode(String),
+    InvalidListenOnlyNode(String),
+    InvalidOdProtocol(String),
+    InvalidLatencyPath(String),
+    InvalidSupervision(String),
+    InvalidVersion(String),
+    InvalidWorkspace(String),
+    InvalidBaudrate(String),
     FailedToResolveId,
+    UnresolvedId(String),
+    MessageNotRegistered(String),
+    BusNotFound(String),
+    StreamEntryMissing(String),
+    InvalidStreamMapping(String),
     NoBusAvaiable,
+    BuildCancelled(String),
     Io(std::io::Error),
     CanDbc(String),
+    Kcd(String),
+    Eds(String),
+    #[cfg(feature = "tools")]
+    Candump(String),
+    #[cfg(feature = "template")]
+    Template(String),
+    #[cfg(feature = "cli")]
+    Json(String),
+    #[cfg(feature = "cli")]
+    Yaml(String),
 }
 
 impl From<std::io::Error> for ConfigError {
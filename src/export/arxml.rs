@@ -0,0 +1,359 @@
+use std::{collections::HashSet, fmt::Write};
+
+use crate::config::{Message, MessageId, Network, Node, SignalType};
+
+use super::xml_escape;
+
+pub const AUTOSAR_NAMESPACE: &str = "http://autosar.org/schema/r4.0";
+
+const ROOT_PACKAGE: &str = "canzero";
+
+/// Short names of the signals of every message, which have to be unique
+/// within the signal package. Signal names, which don't start with the
+/// message name, are prefixed with it, remaining collisions get a suffix.
+fn signal_short_names(network: &Network) -> Vec<Vec<String>> {
+    let mut used = HashSet::new();
+    network
+        .messages()
+        .iter()
+        .map(|message| {
+            message
+                .signals()
+                .iter()
+                .map(|signal| {
+                    let base = if signal.name().starts_with(&format!("{}_", message.name())) {
+                        signal.name().to_owned()
+                    } else {
+                        format!("{}_{}", message.name(), signal.name())
+                    };
+                    let mut short_name = base.clone();
+                    let mut n = 1;
+                    while !used.insert(short_name.clone()) {
+                        short_name = format!("{base}_{n}");
+                        n += 1;
+                    }
+                    short_name
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// AUTOSAR base type of the raw value of a signal,
+/// decimals are transmitted as unsigned integers.
+fn base_type(ty: &SignalType) -> (String, &'static str) {
+    match ty {
+        SignalType::SignedInt { size } => (format!("sint{size}"), "2C"),
+        SignalType::UnsignedInt { size } | SignalType::Decimal { size, .. } => {
+            (format!("uint{size}"), "NONE")
+        }
+    }
+}
+
+fn transmits(node: &Node, message: &Message) -> bool {
+    node.tx_messages().iter().any(|m| m.name() == message.name())
+}
+
+fn receives(node: &Node, message: &Message) -> bool {
+    node.rx_messages().iter().any(|m| m.name() == message.name())
+}
+
+struct Writer {
+    arxml: String,
+    depth: usize,
+}
+
+impl Writer {
+    fn open(&mut self, tag: &str) {
+        writeln!(self.arxml, "{:indent$}<{tag}>", "", indent = self.depth * 2).unwrap();
+        self.depth += 1;
+    }
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        writeln!(self.arxml, "{:indent$}</{tag}>", "", indent = self.depth * 2).unwrap();
+    }
+    fn leaf(&mut self, tag: &str, value: impl std::fmt::Display) {
+        let value = xml_escape(&value.to_string());
+        writeln!(self.arxml, "{:indent$}<{tag}>{value}</{tag}>", "", indent = self.depth * 2)
+            .unwrap();
+    }
+    fn reference(&mut self, tag: &str, dest: &str, path: &str) {
+        writeln!(
+            self.arxml,
+            "{:indent$}<{tag} DEST=\"{dest}\">/{ROOT_PACKAGE}/{}</{tag}>",
+            "",
+            xml_escape(path),
+            indent = self.depth * 2
+        )
+        .unwrap();
+    }
+    fn open_package(&mut self, name: &str) {
+        self.open("AR-PACKAGE");
+        self.leaf("SHORT-NAME", name);
+        self.open("ELEMENTS");
+    }
+    fn close_package(&mut self) {
+        self.close("ELEMENTS");
+        self.close("AR-PACKAGE");
+    }
+}
+
+fn write_clusters(w: &mut Writer, network: &Network) {
+    w.open_package("Clusters");
+    for bus in network.buses() {
+        w.open("CAN-CLUSTER");
+        w.leaf("SHORT-NAME", bus.name());
+        w.open("CAN-CLUSTER-VARIANTS");
+        w.open("CAN-CLUSTER-CONDITIONAL");
+        w.leaf("BAUDRATE", bus.baudrate());
+        w.open("PHYSICAL-CHANNELS");
+        w.open("CAN-PHYSICAL-CHANNEL");
+        w.leaf("SHORT-NAME", format!("{}_channel", bus.name()));
+        w.open("FRAME-TRIGGERINGS");
+        for message in network.messages().iter().filter(|m| m.bus().id() == bus.id()) {
+            w.open("CAN-FRAME-TRIGGERING");
+            w.leaf("SHORT-NAME", format!("{}_triggering", message.name()));
+            w.open("FRAME-PORT-REFS");
+            for node in network.nodes() {
+                let direction = match (transmits(node, message), receives(node, message)) {
+                    (true, _) => "out",
+                    (false, true) => "in",
+                    (false, false) => continue,
+                };
+                w.reference(
+                    "FRAME-PORT-REF",
+                    "FRAME-PORT",
+                    &format!(
+                        "ECUs/{0}/{0}_connector/{1}_{direction}",
+                        node.name(),
+                        message.name()
+                    ),
+                );
+            }
+            w.close("FRAME-PORT-REFS");
+            w.reference("FRAME-REF", "CAN-FRAME", &format!("Frames/{}", message.name()));
+            let (mode, id) = match message.id() {
+                MessageId::StandardId(id) => ("STANDARD", id),
+                MessageId::ExtendedId(id) => ("EXTENDED", id),
+            };
+            w.leaf("CAN-ADDRESSING-MODE", mode);
+            w.leaf("IDENTIFIER", id);
+            w.close("CAN-FRAME-TRIGGERING");
+        }
+        w.close("FRAME-TRIGGERINGS");
+        w.close("CAN-PHYSICAL-CHANNEL");
+        w.close("PHYSICAL-CHANNELS");
+        w.close("CAN-CLUSTER-CONDITIONAL");
+        w.close("CAN-CLUSTER-VARIANTS");
+        w.close("CAN-CLUSTER");
+    }
+    w.close_package();
+}
+
+/// Ecu instances with a frame port for every message they transmit or receive.
+fn write_ecus(w: &mut Writer, network: &Network) {
+    w.open_package("ECUs");
+    for node in network.nodes() {
+        w.open("ECU-INSTANCE");
+        w.leaf("SHORT-NAME", node.name());
+        if let Some(description) = node.description() {
+            w.open("DESC");
+            w.leaf("L-2", description);
+            w.close("DESC");
+        }
+        w.open("CONNECTORS");
+        w.open("CAN-COMMUNICATION-CONNECTOR");
+        w.leaf("SHORT-NAME", format!("{}_connector", node.name()));
+        w.open("ECU-COMM-PORT-INSTANCES");
+        for message in network.messages() {
+            let direction = match (transmits(node, message), receives(node, message)) {
+                (true, _) => "OUT",
+                (false, true) => "IN",
+                (false, false) => continue,
+            };
+            w.open("FRAME-PORT");
+            w.leaf(
+                "SHORT-NAME",
+                format!("{}_{}", message.name(), direction.to_lowercase()),
+            );
+            w.leaf("COMMUNICATION-DIRECTION", direction);
+            w.close("FRAME-PORT");
+        }
+        w.close("ECU-COMM-PORT-INSTANCES");
+        w.close("CAN-COMMUNICATION-CONNECTOR");
+        w.close("CONNECTORS");
+        w.close("ECU-INSTANCE");
+    }
+    w.close_package();
+}
+
+fn write_frames(w: &mut Writer, network: &Network) {
+    w.open_package("Frames");
+    for message in network.messages() {
+        w.open("CAN-FRAME");
+        w.leaf("SHORT-NAME", message.name());
+        if let Some(description) = message.description() {
+            w.open("DESC");
+            w.leaf("L-2", description);
+            w.close("DESC");
+        }
+        w.leaf("FRAME-LENGTH", message.dlc());
+        w.open("PDU-TO-FRAME-MAPPINGS");
+        w.open("PDU-TO-FRAME-MAPPING");
+        w.leaf("SHORT-NAME", format!("{}_pdu_mapping", message.name()));
+        w.leaf("PACKING-BYTE-ORDER", "MOST-SIGNIFICANT-BYTE-LAST");
+        w.reference("PDU-REF", "I-SIGNAL-I-PDU", &format!("PDUs/{}_pdu", message.name()));
+        w.leaf("START-POSITION", 0);
+        w.close("PDU-TO-FRAME-MAPPING");
+        w.close("PDU-TO-FRAME-MAPPINGS");
+        w.close("CAN-FRAME");
+    }
+    w.close_package();
+}
+
+fn write_pdus(w: &mut Writer, network: &Network, short_names: &[Vec<String>]) {
+    w.open_package("PDUs");
+    for (message, short_names) in std::iter::zip(network.messages(), short_names) {
+        w.open("I-SIGNAL-I-PDU");
+        w.leaf("SHORT-NAME", format!("{}_pdu", message.name()));
+        w.leaf("LENGTH", message.dlc());
+        w.open("I-SIGNAL-TO-PDU-MAPPINGS");
+        for (signal, short_name) in std::iter::zip(message.signals(), short_names) {
+            w.open("I-SIGNAL-TO-I-PDU-MAPPING");
+            w.leaf("SHORT-NAME", format!("{short_name}_mapping"));
+            w.reference("I-SIGNAL-REF", "I-SIGNAL", &format!("Signals/{short_name}"));
+            w.leaf("PACKING-BYTE-ORDER", "MOST-SIGNIFICANT-BYTE-LAST");
+            w.leaf("START-POSITION", signal.byte_offset());
+            w.close("I-SIGNAL-TO-I-PDU-MAPPING");
+        }
+        w.close("I-SIGNAL-TO-PDU-MAPPINGS");
+        w.close("I-SIGNAL-I-PDU");
+    }
+    w.close_package();
+}
+
+fn write_signals(w: &mut Writer, network: &Network, short_names: &[Vec<String>]) {
+    w.open_package("Signals");
+    for (message, short_names) in std::iter::zip(network.messages(), short_names) {
+        for (signal, short_name) in std::iter::zip(message.signals(), short_names) {
+            w.open("I-SIGNAL");
+            w.leaf("SHORT-NAME", short_name);
+            if let Some(description) = signal.description() {
+                w.open("DESC");
+                w.leaf("L-2", description);
+                w.close("DESC");
+            }
+            w.leaf("LENGTH", signal.size());
+            w.open("NETWORK-REPRESENTATION-PROPS");
+            w.open("SW-DATA-DEF-PROPS-VARIANTS");
+            w.open("SW-DATA-DEF-PROPS-CONDITIONAL");
+            w.reference(
+                "BASE-TYPE-REF",
+                "SW-BASE-TYPE",
+                &format!("BaseTypes/{}", base_type(signal.ty()).0),
+            );
+            w.reference(
+                "COMPU-METHOD-REF",
+                "COMPU-METHOD",
+                &format!("CompuMethods/{short_name}_compu"),
+            );
+            w.close("SW-DATA-DEF-PROPS-CONDITIONAL");
+            w.close("SW-DATA-DEF-PROPS-VARIANTS");
+            w.close("NETWORK-REPRESENTATION-PROPS");
+            w.close("I-SIGNAL");
+        }
+    }
+    w.close_package();
+}
+
+/// Base types of all signals, which define the size and the signedness.
+fn write_base_types(w: &mut Writer, network: &Network) {
+    let mut base_types: Vec<(String, &'static str, u8)> = vec![];
+    for message in network.messages() {
+        for signal in message.signals() {
+            let (name, encoding) = base_type(signal.ty());
+            if !base_types.iter().any(|(n, _, _)| *n == name) {
+                base_types.push((name, encoding, signal.size()));
+            }
+        }
+    }
+    base_types.sort();
+    w.open_package("BaseTypes");
+    for (name, encoding, size) in base_types {
+        w.open("SW-BASE-TYPE");
+        w.leaf("SHORT-NAME", name);
+        w.leaf("CATEGORY", "FIXED_LENGTH");
+        w.leaf("BASE-TYPE-SIZE", size);
+        w.leaf("BASE-TYPE-ENCODING", encoding);
+        w.close("SW-BASE-TYPE");
+    }
+    w.close_package();
+}
+
+fn write_compu_methods(w: &mut Writer, network: &Network, short_names: &[Vec<String>]) {
+    w.open_package("CompuMethods");
+    for (message, short_names) in std::iter::zip(network.messages(), short_names) {
+        for (signal, short_name) in std::iter::zip(message.signals(), short_names) {
+            w.open("COMPU-METHOD");
+            w.leaf("SHORT-NAME", format!("{short_name}_compu"));
+            match signal.ty() {
+                SignalType::UnsignedInt { size: _ } | SignalType::SignedInt { size: _ } => {
+                    w.leaf("CATEGORY", "IDENTICAL");
+                }
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                } => {
+                    w.leaf("CATEGORY", "LINEAR");
+                    w.open("COMPU-INTERNAL-TO-PHYS");
+                    w.open("COMPU-SCALES");
+                    w.open("COMPU-SCALE");
+                    w.open("COMPU-RATIONAL-COEFFS");
+                    w.open("COMPU-NUMERATOR");
+                    w.leaf("V", offset);
+                    w.leaf("V", scale);
+                    w.close("COMPU-NUMERATOR");
+                    w.open("COMPU-DENOMINATOR");
+                    w.leaf("V", 1);
+                    w.close("COMPU-DENOMINATOR");
+                    w.close("COMPU-RATIONAL-COEFFS");
+                    w.close("COMPU-SCALE");
+                    w.close("COMPU-SCALES");
+                    w.close("COMPU-INTERNAL-TO-PHYS");
+                }
+            }
+            w.close("COMPU-METHOD");
+        }
+    }
+    w.close_package();
+}
+
+/// Exports the communication matrix (clusters, ecus, frames, pdus and signals)
+/// as an AUTOSAR 4 system description.
+pub fn network_to_arxml(network: &Network) -> String {
+    let mut w = Writer {
+        arxml: String::new(),
+        depth: 0,
+    };
+    writeln!(w.arxml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(w.arxml, "<AUTOSAR xmlns=\"{AUTOSAR_NAMESPACE}\">").unwrap();
+    w.depth = 1;
+    w.open("AR-PACKAGES");
+    w.open("AR-PACKAGE");
+    w.leaf("SHORT-NAME", ROOT_PACKAGE);
+    w.open("AR-PACKAGES");
+    let short_names = signal_short_names(network);
+    write_clusters(&mut w, network);
+    write_ecus(&mut w, network);
+    write_frames(&mut w, network);
+    write_pdus(&mut w, network, &short_names);
+    write_signals(&mut w, network, &short_names);
+    write_base_types(&mut w, network);
+    write_compu_methods(&mut w, network, &short_names);
+    w.close("AR-PACKAGES");
+    w.close("AR-PACKAGE");
+    w.close("AR-PACKAGES");
+    writeln!(w.arxml, "</AUTOSAR>").unwrap();
+    w.arxml
+}
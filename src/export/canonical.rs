@@ -0,0 +1,460 @@
+use std::fmt::Write;
+
+use crate::config::{
+    message::MessageUsage, stream::StreamEncoding, AttributePadding, ConfigWeak, Lifecycle, MessageId, Network, ObjectEntryAccess, SignalType, Type, Visibility,
+};
+
+/// Version of the structure of the canonical text, written into its first line.
+/// Bumped whenever lines or keys are renamed, removed or change their meaning.
+pub const CANONICAL_SCHEMA_VERSION: u32 = 1;
+
+fn visibility(visibility: &Visibility) -> &'static str {
+    match visibility {
+        Visibility::Global => "global",
+        Visibility::Static => "static",
+    }
+}
+
+fn signal_type(ty: &SignalType) -> String {
+    match ty {
+        SignalType::UnsignedInt { size } => format!("u{size}"),
+        SignalType::SignedInt { size } => format!("i{size}"),
+        SignalType::Decimal {
+            size,
+            offset,
+            scale,
+        } => format!("d{size} offset={offset} scale={scale}"),
+    }
+}
+
+fn message_id(id: &MessageId) -> String {
+    match id {
+        MessageId::StandardId(id) => format!("std:0x{id:03X}"),
+        MessageId::ExtendedId(id) => format!("ext:0x{id:08X}"),
+    }
+}
+
+fn usage(usage: &MessageUsage) -> String {
+    fn weak_name<T>(weak: &ConfigWeak<T>, name: fn(&T) -> &str) -> String {
+        weak.upgrade()
+            .map(|t| name(&t).to_owned())
+            .unwrap_or_else(|| "?".to_owned())
+    }
+    match usage {
+        MessageUsage::Stream(stream) => format!("stream:{}", weak_name(stream, |s| s.name())),
+        MessageUsage::CommandReq(command) => {
+            format!("command_req:{}", weak_name(command, |c| c.name()))
+        }
+        MessageUsage::CommandResp(command) => {
+            format!("command_resp:{}", weak_name(command, |c| c.name()))
+        }
+        MessageUsage::TransportData(channel) => {
+            format!("transport_data:{}", weak_name(channel, |c| c.name()))
+        }
+        MessageUsage::TransportFlowControl(channel) => {
+            format!("transport_flow_control:{}", weak_name(channel, |c| c.name()))
+        }
+        MessageUsage::GetResp => "get_resp".to_owned(),
+        MessageUsage::GetReq => "get_req".to_owned(),
+        MessageUsage::SetResp => "set_resp".to_owned(),
+        MessageUsage::SetReq => "set_req".to_owned(),
+        MessageUsage::Heartbeat => "heartbeat".to_owned(),
+        MessageUsage::Emergency => "emergency".to_owned(),
+        MessageUsage::Debug { interval: Some(interval) } => format!("debug:{interval:?}"),
+        MessageUsage::Debug { interval: None } => "debug".to_owned(),
+        MessageUsage::Bootloader => "bootloader".to_owned(),
+        MessageUsage::External { interval } => format!("external:{interval:?}"),
+    }
+}
+
+fn lifecycle(lifecycle: &Lifecycle) -> String {
+    let mut out = String::new();
+    if let Some(introduced_in) = lifecycle.introduced_in() {
+        write!(out, " introduced_in={introduced_in}").unwrap();
+    }
+    if let Some(deprecated_in) = lifecycle.deprecated_in() {
+        write!(out, " deprecated_in={deprecated_in}").unwrap();
+    }
+    out
+}
+
+fn sorted_names<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let mut names: Vec<&str> = names.collect();
+    names.sort_unstable();
+    names.join(" ")
+}
+
+/// Serializes the network into a line based text format with a stable ordering,
+/// such that two builds of the same configuration always produce the same text
+/// and changes of the configuration result in minimal diffs.
+///
+/// Buses, types, nodes, messages, groups and paths are sorted by name, object
+/// entries by name and signals by their offset. The order of struct attributes,
+/// enum entries, stream mappings and path hops is preserved, because it is part
+/// of the configuration. The build time is not serialized.
+pub fn network_to_canonical(network: &Network) -> String {
+    let mut out = String::new();
+    writeln!(out, "canonical {CANONICAL_SCHEMA_VERSION}").unwrap();
+    if let Some(name) = network.name() {
+        writeln!(out, "name {name:?}").unwrap();
+    }
+    if let Some(version) = network.version() {
+        writeln!(out, "version {version}").unwrap();
+    }
+    if let Some(author) = network.author() {
+        writeln!(out, "author {author:?}").unwrap();
+    }
+    if let Some(description) = network.description() {
+        writeln!(out, "description {description:?}").unwrap();
+    }
+    writeln!(out, "od_index_width {}", network.od_index_width()).unwrap();
+
+    let mut buses: Vec<_> = network.buses().iter().collect();
+    buses.sort_by(|a, b| a.name().cmp(b.name()));
+    for bus in buses {
+        write!(out, "bus {} baudrate={}", bus.name(), bus.baudrate()).unwrap();
+        if let Some(sample_point) = bus.sample_point() {
+            write!(out, " sample_point={sample_point}").unwrap();
+        }
+        if let Some(sjw) = bus.sjw() {
+            write!(out, " sjw={sjw}").unwrap();
+        }
+        if let Some(connector) = bus.connector() {
+            write!(out, " connector={connector:?}").unwrap();
+        }
+        writeln!(out).unwrap();
+        if let Some(description) = bus.description() {
+            writeln!(out, "  description {description:?}").unwrap();
+        }
+    }
+
+    let mut access_levels: Vec<_> = network.access_levels().iter().collect();
+    access_levels.sort_by(|a, b| a.name().cmp(b.name()));
+    for level in access_levels {
+        writeln!(out, "access_level {} rank={}", level.name(), level.rank()).unwrap();
+    }
+
+    let mut types: Vec<_> = network
+        .types()
+        .iter()
+        .filter(|ty| matches!(ty as &Type, Type::Struct { .. } | Type::Enum { .. } | Type::Union { .. }))
+        .collect();
+    types.sort_by_key(|ty| ty.name());
+    for ty in types {
+        match ty as &Type {
+            Type::Enum {
+                name,
+                description,
+                size,
+                entries,
+                entry_info,
+                visibility: vis,
+            } => {
+                writeln!(out, "enum {name} size={size} visibility={}", visibility(vis)).unwrap();
+                if let Some(description) = description {
+                    writeln!(out, "  description {description:?}").unwrap();
+                }
+                for ((entry, value), info) in entries.iter().zip(entry_info) {
+                    write!(out, "  entry {entry}={value}").unwrap();
+                    if info.is_deprecated() {
+                        write!(out, " deprecated").unwrap();
+                    }
+                    if let Some(description) = info.description() {
+                        write!(out, " description={description:?}").unwrap();
+                    }
+                    writeln!(out).unwrap();
+                }
+            }
+            Type::Struct {
+                name,
+                description,
+                attribs,
+                attrib_info,
+                visibility: vis,
+            } => {
+                writeln!(out, "struct {name} visibility={}", visibility(vis)).unwrap();
+                if let Some(description) = description {
+                    writeln!(out, "  description {description:?}").unwrap();
+                }
+                for ((attrib, attrib_type), info) in attribs.iter().zip(attrib_info) {
+                    write!(out, "  attribute {attrib}: {}", attrib_type.name()).unwrap();
+                    match info.padding() {
+                        AttributePadding::None => (),
+                        AttributePadding::Bits(bits) => write!(out, " padding={bits}").unwrap(),
+                        AttributePadding::Align(bits) => write!(out, " align={bits}").unwrap(),
+                    }
+                    if let Some(description) = info.description() {
+                        write!(out, " description={description:?}").unwrap();
+                    }
+                    writeln!(out).unwrap();
+                }
+            }
+            Type::Union {
+                name,
+                description,
+                tag,
+                variants,
+                visibility: vis,
+            } => {
+                writeln!(
+                    out,
+                    "union {name} tag={} visibility={}",
+                    tag.name(),
+                    visibility(vis)
+                )
+                .unwrap();
+                if let Some(description) = description {
+                    writeln!(out, "  description {description:?}").unwrap();
+                }
+                for (entry, variant_type) in variants {
+                    writeln!(out, "  variant {entry}: {}", variant_type.name()).unwrap();
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let mut nodes: Vec<_> = network.nodes().iter().collect();
+    nodes.sort_by(|a, b| a.name().cmp(b.name()));
+    for node in nodes {
+        write!(out, "node {} id={}", node.name(), node.id()).unwrap();
+        if node.is_extern() {
+            write!(out, " extern").unwrap();
+        } else if !node.has_od_protocol() {
+            write!(out, " no_od_protocol").unwrap();
+        }
+        if node.is_listen_only() {
+            write!(out, " listen_only").unwrap();
+        }
+        if let Some(version) = node.firmware_version() {
+            write!(out, " firmware_version={version}").unwrap();
+        }
+        writeln!(out).unwrap();
+        if let Some(description) = node.description() {
+            writeln!(out, "  description {description:?}").unwrap();
+        }
+        writeln!(
+            out,
+            "  buses {}",
+            sorted_names(node.buses().iter().map(|bus| bus.name()))
+        )
+        .unwrap();
+
+        let mut object_entries: Vec<_> = node.object_entries().iter().collect();
+        object_entries.sort_by(|a, b| a.name().cmp(b.name()));
+        for object_entry in object_entries {
+            let access = match object_entry.access() {
+                ObjectEntryAccess::Const => "const".to_owned(),
+                ObjectEntryAccess::Local => "local".to_owned(),
+                ObjectEntryAccess::Global => "global".to_owned(),
+                ObjectEntryAccess::Protected { level } => format!("protected:{level}"),
+            };
+            write!(
+                out,
+                "  object_entry {} index={} type={} access={access} visibility={}",
+                object_entry.name(),
+                object_entry.id(),
+                object_entry.ty().name(),
+                visibility(object_entry.visibility()),
+            )
+            .unwrap();
+            if let Some(unit) = object_entry.unit() {
+                write!(out, " unit={unit:?}").unwrap();
+            }
+            if let Some(default) = object_entry.default_value() {
+                write!(out, " default={default:?}").unwrap();
+            }
+            if object_entry.persistent() {
+                write!(out, " persistent").unwrap();
+            }
+            if object_entry.broadcast() {
+                write!(out, " broadcast").unwrap();
+            }
+            writeln!(out, "{}", lifecycle(object_entry.lifecycle())).unwrap();
+        }
+
+        let mut streams: Vec<_> = node.tx_streams().iter().collect();
+        streams.sort_by(|a, b| a.name().cmp(b.name()));
+        for stream in streams {
+            writeln!(
+                out,
+                "  tx_stream {} message={} interval={:?}..{:?}",
+                stream.name(),
+                stream.message().name(),
+                stream.min_interval(),
+                stream.max_interval()
+            )
+            .unwrap();
+            if let StreamEncoding::Delta { keyframe_interval } = stream.encoding() {
+                writeln!(out, "    delta keyframe_interval={keyframe_interval}").unwrap();
+            }
+            let mapping: Vec<&str> = stream
+                .mapping()
+                .iter()
+                .map(|mapping| mapping.as_ref().map_or("_", |mapping| mapping.name()))
+                .collect();
+            writeln!(out, "    mapping {}", mapping.join(" ")).unwrap();
+        }
+        let rx_streams = sorted_names(node.rx_streams().iter().map(|s| s.message().name()));
+        if !rx_streams.is_empty() {
+            writeln!(out, "  rx_streams {rx_streams}").unwrap();
+        }
+
+        let mut commands: Vec<_> = node.commands().iter().collect();
+        commands.sort_by(|a, b| a.name().cmp(b.name()));
+        for command in commands {
+            writeln!(
+                out,
+                "  command {} req={} resp={} interval={:?}",
+                command.name(),
+                command.tx_message().name(),
+                command.rx_message().name(),
+                command.expected_interval()
+            )
+            .unwrap();
+        }
+        let mut extern_commands: Vec<_> = node
+            .extern_commands()
+            .iter()
+            .map(|(tx_node, command)| format!("{tx_node}::{}", command.name()))
+            .collect();
+        extern_commands.sort_unstable();
+        if !extern_commands.is_empty() {
+            writeln!(out, "  extern_commands {}", extern_commands.join(" ")).unwrap();
+        }
+
+        let mut rx_timeouts: Vec<String> = node
+            .rx_timeouts()
+            .iter()
+            .map(|(message, timeout)| format!("{}={timeout:?}", message.name()))
+            .collect();
+        rx_timeouts.sort_unstable();
+        if !rx_timeouts.is_empty() {
+            writeln!(out, "  rx_timeouts {}", rx_timeouts.join(" ")).unwrap();
+        }
+        let mut supervisions: Vec<String> = network
+            .supervisions()
+            .iter()
+            .filter(|supervision| supervision.supervisor().name() == node.name())
+            .map(|supervision| {
+                format!(
+                    "{}:{}={:?}",
+                    supervision.supervised().name(),
+                    supervision.message().name(),
+                    supervision.timeout()
+                )
+            })
+            .collect();
+        supervisions.sort_unstable();
+        if !supervisions.is_empty() {
+            writeln!(out, "  supervises {}", supervisions.join(" ")).unwrap();
+        }
+        writeln!(
+            out,
+            "  tx_messages {}",
+            sorted_names(node.tx_messages().iter().map(|m| m.name()))
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "  rx_messages {}",
+            sorted_names(node.rx_messages().iter().map(|m| m.name()))
+        )
+        .unwrap();
+    }
+
+    let mut messages: Vec<_> = network.messages().iter().collect();
+    messages.sort_by(|a, b| a.name().cmp(b.name()));
+    for message in messages {
+        writeln!(
+            out,
+            "message {} id={} bus={} dlc={} usage={} visibility={}{}",
+            message.name(),
+            message_id(message.id()),
+            message.bus().name(),
+            message.dlc(),
+            usage(message.usage()),
+            visibility(message.visibility()),
+            lifecycle(message.lifecycle()),
+        )
+        .unwrap();
+        if let Some(description) = message.description() {
+            writeln!(out, "  description {description:?}").unwrap();
+        }
+        if message.fill() != 0 {
+            writeln!(out, "  fill 0x{:02X}", message.fill()).unwrap();
+        }
+        if let Some(e2e) = message.e2e() {
+            writeln!(
+                out,
+                "  e2e data_id={} crc={} counter={}",
+                e2e.profile().data_id(),
+                e2e.crc().name(),
+                e2e.counter().name()
+            )
+            .unwrap();
+        }
+        let mut signals: Vec<_> = message.signals().iter().collect();
+        signals.sort_by(|a, b| {
+            a.byte_offset()
+                .cmp(&b.byte_offset())
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        for signal in signals {
+            write!(
+                out,
+                "  signal {} offset={} type={} overflow={}{}",
+                signal.name(),
+                signal.byte_offset(),
+                signal_type(signal.ty()),
+                signal.overflow(),
+                lifecycle(signal.lifecycle())
+            )
+            .unwrap();
+            if !signal.receivers().is_empty() {
+                let mut receivers: Vec<&str> = signal.receivers().iter().map(String::as_str).collect();
+                receivers.sort_unstable();
+                write!(out, " receivers={}", receivers.join(",")).unwrap();
+            }
+            if signal.is_reserved() {
+                write!(out, " reserved").unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+        let mut signal_groups: Vec<_> = message.signal_groups().iter().collect();
+        signal_groups.sort_by(|a, b| a.name().cmp(b.name()));
+        for group in signal_groups {
+            let signals: Vec<&str> = group.signals().iter().map(|s| s.name()).collect();
+            writeln!(
+                out,
+                "  signal_group {} signals={}",
+                group.name(),
+                signals.join(",")
+            )
+            .unwrap();
+        }
+    }
+
+    let mut groups: Vec<_> = network.node_groups().iter().collect();
+    groups.sort_by(|a, b| a.name().cmp(b.name()));
+    for group in groups {
+        writeln!(
+            out,
+            "group {} server_id={} nodes={}",
+            group.name(),
+            group.server_id(),
+            sorted_names(group.nodes().iter().map(|node| node.name()))
+        )
+        .unwrap();
+    }
+
+    let mut paths: Vec<_> = network.latency_paths().iter().collect();
+    paths.sort_by(|a, b| a.name().cmp(b.name()));
+    for path in paths {
+        writeln!(out, "latency_path {} budget={:?}", path.name(), path.budget()).unwrap();
+        for hop in path.hops() {
+            writeln!(out, "  hop {}", hop.name()).unwrap();
+        }
+    }
+    out
+}
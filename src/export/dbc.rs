@@ -0,0 +1,195 @@
+use std::fmt::Write;
+
+use crate::config::{Message, MessageId, Network, SignalType};
+
+use super::enum_labels;
+
+/// placeholder for a missing transmitter or receiver.
+const NO_NODE: &str = "Vector__XXX";
+
+fn dbc_id(message: &Message) -> u32 {
+    match message.id() {
+        MessageId::StandardId(id) => *id,
+        // extended ids are marked by the most significant bit.
+        MessageId::ExtendedId(id) => *id | 0x8000_0000,
+    }
+}
+
+fn dbc_string(value: &str) -> String {
+    value.replace('"', "'").replace('\n', " ")
+}
+
+/// Exports the network as a Vector CAN database (.dbc). Enums are exported
+/// as value tables of the signals that encode them and signal groups
+/// as `SIG_GROUP_` entries. Unions are exported as multiplexed signals.
+pub fn network_to_dbc(network: &Network) -> String {
+    let mut dbc = String::new();
+    let version = network.version().map(|version| version.to_string()).unwrap_or_default();
+    writeln!(dbc, "VERSION \"{version}\"").unwrap();
+    writeln!(dbc).unwrap();
+    writeln!(dbc, "NS_ :").unwrap();
+    writeln!(dbc).unwrap();
+    writeln!(dbc, "BS_:").unwrap();
+    writeln!(dbc).unwrap();
+    let nodes: Vec<&str> = network.nodes().iter().map(|n| n.name()).collect();
+    writeln!(dbc, "BU_: {}", nodes.join(" ")).unwrap();
+
+    for message in network.messages() {
+        let id = dbc_id(message);
+        let transmitters: Vec<&str> = network
+            .nodes()
+            .iter()
+            .filter(|n| n.tx_messages().iter().any(|m| m.name() == message.name()))
+            .map(|n| n.name())
+            .collect();
+        let receivers: Vec<&str> = network
+            .nodes()
+            .iter()
+            .filter(|n| n.rx_messages().iter().any(|m| m.name() == message.name()))
+            .map(|n| n.name())
+            .collect();
+        writeln!(dbc).unwrap();
+        writeln!(
+            dbc,
+            "BO_ {id} {}: {} {}",
+            message.name(),
+            message.dlc(),
+            transmitters.first().copied().unwrap_or(NO_NODE)
+        )
+        .unwrap();
+        let multiplexed: Vec<(&str, &str, u64)> = message
+            .signals()
+            .iter()
+            .filter_map(|signal| {
+                message
+                    .encoding()
+                    .and_then(|encoding| encoding.multiplexor_of(signal.name()))
+                    .map(|(tag, value)| (signal.name(), tag.name(), value))
+            })
+            .collect();
+        // dbc only supports a single multiplexor per message.
+        let multiplexor = multiplexed
+            .first()
+            .map(|(_, tag, _)| *tag)
+            .filter(|tag| multiplexed.iter().all(|(_, t, _)| t == tag));
+        for signal in message.signals() {
+            let (sign, scale, offset) = match signal.ty() {
+                SignalType::UnsignedInt { size: _ } => ('+', 1.0, 0.0),
+                SignalType::SignedInt { size: _ } => ('-', 1.0, 0.0),
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                } => ('+', *scale, *offset),
+            };
+            let (min, max) = signal.ty().physical_range();
+            let indicator = match multiplexor {
+                Some(tag) if tag == signal.name() => " M".to_owned(),
+                Some(_) => multiplexed
+                    .iter()
+                    .find(|(name, _, _)| *name == signal.name())
+                    .map(|(_, _, value)| format!(" m{value}"))
+                    .unwrap_or_default(),
+                None => String::new(),
+            };
+            let receivers: Vec<&str> = receivers
+                .iter()
+                .copied()
+                .filter(|rx| signal.is_received_by(rx))
+                .collect();
+            let receivers = if receivers.is_empty() {
+                NO_NODE.to_owned()
+            } else {
+                receivers.join(",")
+            };
+            writeln!(
+                dbc,
+                " SG_ {}{indicator} : {}|{}@1{sign} ({scale},{offset}) [{min}|{max}] \"\" {receivers}",
+                signal.name(),
+                signal.byte_offset(),
+                signal.size()
+            )
+            .unwrap();
+        }
+    }
+    writeln!(dbc).unwrap();
+
+    for message in network.messages() {
+        let transmitters: Vec<&str> = network
+            .nodes()
+            .iter()
+            .filter(|n| n.tx_messages().iter().any(|m| m.name() == message.name()))
+            .map(|n| n.name())
+            .collect();
+        if transmitters.len() > 1 {
+            writeln!(dbc, "BO_TX_BU_ {} : {};", dbc_id(message), transmitters.join(",")).unwrap();
+        }
+    }
+
+    if let Some(description) = network.description() {
+        writeln!(dbc, "CM_ \"{}\";", dbc_string(description)).unwrap();
+    }
+    for node in network.nodes() {
+        if let Some(description) = node.description() {
+            writeln!(dbc, "CM_ BU_ {} \"{}\";", node.name(), dbc_string(description)).unwrap();
+        }
+    }
+    for message in network.messages() {
+        let id = dbc_id(message);
+        if let Some(description) = message.description() {
+            writeln!(dbc, "CM_ BO_ {id} \"{}\";", dbc_string(description)).unwrap();
+        }
+        for signal in message.signals() {
+            if let Some(description) = signal.description() {
+                writeln!(
+                    dbc,
+                    "CM_ SG_ {id} {} \"{}\";",
+                    signal.name(),
+                    dbc_string(description)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    // listen-only nodes are marked with a node attribute.
+    let listen_only: Vec<&str> = network
+        .nodes()
+        .iter()
+        .filter(|node| node.is_listen_only())
+        .map(|node| node.name())
+        .collect();
+    if !listen_only.is_empty() {
+        writeln!(dbc, "BA_DEF_ BU_ \"ListenOnly\" INT 0 1;").unwrap();
+        writeln!(dbc, "BA_DEF_DEF_ \"ListenOnly\" 0;").unwrap();
+        for node in listen_only {
+            writeln!(dbc, "BA_ \"ListenOnly\" BU_ {node} 1;").unwrap();
+        }
+    }
+
+    for message in network.messages() {
+        let id = dbc_id(message);
+        for (signal_name, _, entries) in enum_labels(message) {
+            let values: Vec<String> = entries
+                .iter()
+                .map(|(entry, value)| format!("{value} \"{}\"", dbc_string(entry)))
+                .collect();
+            writeln!(dbc, "VAL_ {id} {signal_name} {} ;", values.join(" ")).unwrap();
+        }
+    }
+
+    for message in network.messages() {
+        let id = dbc_id(message);
+        for group in message.signal_groups() {
+            let signals: Vec<&str> = group.signals().iter().map(|s| s.name()).collect();
+            writeln!(
+                dbc,
+                "SIG_GROUP_ {id} {} 1 : {};",
+                group.name(),
+                signals.join(" ")
+            )
+            .unwrap();
+        }
+    }
+    dbc
+}
@@ -0,0 +1,127 @@
+use std::fmt::Write;
+
+use crate::config::{Message, Network, Node};
+
+/// markdown table cells must not contain line breaks or pipes.
+fn cell(value: &str) -> String {
+    value.replace('\n', " ").replace('|', "\\|")
+}
+
+fn names<'a>(nodes: impl Iterator<Item = &'a Node>) -> String {
+    let names: Vec<&str> = nodes.map(|node| node.name()).collect();
+    match names.is_empty() {
+        true => "-".to_owned(),
+        false => names.join(", "),
+    }
+}
+
+fn write_message(docs: &mut String, network: &Network, message: &Message) {
+    writeln!(docs, "\n### {}\n", message.name()).unwrap();
+    if let Some(description) = message.description() {
+        writeln!(docs, "{description}\n").unwrap();
+    }
+    let transmitters = names(network.nodes().iter().map(|node| node as &Node).filter(|node| {
+        node.tx_messages().iter().any(|tx| tx.name() == message.name())
+    }));
+    let receivers = names(network.nodes().iter().map(|node| node as &Node).filter(|node| {
+        node.rx_messages().iter().any(|rx| rx.name() == message.name())
+    }));
+    writeln!(docs, "| Id | Bus | Dlc | Transmitters | Receivers |").unwrap();
+    writeln!(docs, "|---|---|---|---|---|").unwrap();
+    writeln!(
+        docs,
+        "| {} | {} | {} | {transmitters} | {receivers} |",
+        format!("{}", message.id()).trim(),
+        message.bus().name(),
+        message.dlc()
+    )
+    .unwrap();
+    if message.signals().is_empty() {
+        return;
+    }
+    writeln!(docs, "\n| Signal | Bits | Scale | Offset | Description |").unwrap();
+    writeln!(docs, "|---|---|---|---|---|").unwrap();
+    for signal in message.signals() {
+        let bits = signal.bit_range();
+        writeln!(
+            docs,
+            "| {} | {}..{} | {} | {} | {} |",
+            signal.name(),
+            bits.start,
+            bits.end,
+            signal.scale(),
+            signal.offset(),
+            cell(signal.description().unwrap_or("")),
+        )
+        .unwrap();
+    }
+    writeln!(docs, "\n```text\n{}```", message.layout_table()).unwrap();
+}
+
+fn write_node(docs: &mut String, node: &Node) {
+    writeln!(docs, "\n### {}\n", node.name()).unwrap();
+    if let Some(description) = node.description() {
+        writeln!(docs, "{description}\n").unwrap();
+    }
+    let buses: Vec<&str> = node.buses().iter().map(|bus| bus.name()).collect();
+    writeln!(docs, "node id {} on {}", node.id(), buses.join(", ")).unwrap();
+    if node.object_entries().is_empty() {
+        return;
+    }
+    writeln!(docs, "\n| Object entry | Index | Type | Unit | Description |").unwrap();
+    writeln!(docs, "|---|---|---|---|---|").unwrap();
+    for oe in node.object_entries() {
+        writeln!(
+            docs,
+            "| {} | {} | {} | {} | {} |",
+            oe.name(),
+            oe.id(),
+            oe.ty().name(),
+            oe.unit().unwrap_or(""),
+            cell(oe.description().unwrap_or("")),
+        )
+        .unwrap();
+    }
+}
+
+/// Renders a markdown reference of the network with its buses, nodes
+/// (including their object dictionaries) and messages (including their
+/// signals and bit layout), e.g. for the project wiki.
+pub fn network_to_docs(network: &Network) -> String {
+    let mut docs = String::new();
+    writeln!(docs, "# {}", network.name().unwrap_or("Network")).unwrap();
+    if let Some(description) = network.description() {
+        writeln!(docs, "\n{description}").unwrap();
+    }
+    match network.version() {
+        Some(version) => writeln!(docs, "\nversion {version}, config hash `{:016x}`", network.config_hash()),
+        None => writeln!(docs, "\nconfig hash `{:016x}`", network.config_hash()),
+    }
+    .unwrap();
+
+    writeln!(docs, "\n## Buses\n").unwrap();
+    writeln!(docs, "| Bus | Id | Baudrate | Description |").unwrap();
+    writeln!(docs, "|---|---|---|---|").unwrap();
+    for bus in network.buses() {
+        writeln!(
+            docs,
+            "| {} | {} | {} | {} |",
+            bus.name(),
+            bus.id(),
+            bus.baudrate(),
+            cell(bus.description().unwrap_or("")),
+        )
+        .unwrap();
+    }
+
+    writeln!(docs, "\n## Nodes").unwrap();
+    for node in network.nodes() {
+        write_node(&mut docs, node);
+    }
+
+    writeln!(docs, "\n## Messages").unwrap();
+    for message in network.messages() {
+        write_message(&mut docs, network, message);
+    }
+    docs
+}
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+
+use crate::config::{message::MessageUsage, Message, Network, Node};
+
+/// Restricts which parts of the network are rendered.
+#[derive(Debug, Clone, Default)]
+pub struct DotOptions {
+    /// only render messages on the bus with this name.
+    pub bus: Option<String>,
+    /// only render messages that are transmitted or received by this node.
+    pub node: Option<String>,
+    /// also render the get/set, heartbeat messages, which every node uses.
+    pub protocol_messages: bool,
+}
+
+fn is_protocol_message(network: &Network, message: &Message) -> bool {
+    [
+        network.get_req_message(),
+        network.get_resp_message(),
+        network.set_req_message(),
+        network.set_resp_message(),
+        network.heartbeat_message(),
+    ]
+    .iter()
+    .any(|m| m.name() == message.name())
+}
+
+fn transmits(node: &Node, message: &Message) -> bool {
+    node.tx_messages().iter().any(|m| m.name() == message.name())
+}
+
+fn receives(node: &Node, message: &Message) -> bool {
+    node.rx_messages().iter().any(|m| m.name() == message.name())
+}
+
+pub fn network_to_dot(network: &Network, options: &DotOptions) -> String {
+    let messages: Vec<&Message> = network
+        .messages()
+        .iter()
+        .map(|m| m as &Message)
+        .filter(|m| options.protocol_messages || !is_protocol_message(network, m))
+        .filter(|m| match &options.bus {
+            Some(bus) => m.bus().name() == bus,
+            None => true,
+        })
+        .filter(|m| match &options.node {
+            Some(node_name) => network
+                .nodes()
+                .iter()
+                .filter(|n| n.name() == node_name)
+                .any(|n| transmits(n, m) || receives(n, m)),
+            None => true,
+        })
+        .collect();
+    let nodes: Vec<&Node> = network
+        .nodes()
+        .iter()
+        .map(|n| n as &Node)
+        .filter(|n| match &options.bus {
+            Some(bus) => n.buses().iter().any(|b| b.name() == bus)
+                || messages.iter().any(|m| transmits(n, m) || receives(n, m)),
+            None => true,
+        })
+        .filter(|n| match &options.node {
+            Some(node_name) => {
+                n.name() == node_name
+                    || messages.iter().any(|m| transmits(n, m) || receives(n, m))
+            }
+            None => true,
+        })
+        .collect();
+
+    let mut dot = String::new();
+    writeln!(dot, "digraph network {{").unwrap();
+    writeln!(dot, "  rankdir=LR;").unwrap();
+    writeln!(dot, "  node [shape=box];").unwrap();
+    for node in &nodes {
+        if node.is_listen_only() {
+            writeln!(dot, "  \"{}\" [style=dashed, xlabel=\"listen-only\"];", node.name()).unwrap();
+        } else {
+            writeln!(dot, "  \"{}\";", node.name()).unwrap();
+        }
+    }
+    for bus in network.buses() {
+        if let Some(bus_name) = &options.bus {
+            if bus.name() != bus_name {
+                continue;
+            }
+        }
+        let sample_point = match bus.sample_point() {
+            Some(sample_point) => format!(", {sample_point}%"),
+            None => String::new(),
+        };
+        writeln!(
+            dot,
+            "  \"bus_{}\" [shape=ellipse, label=\"{} ({} baud{sample_point})\"];",
+            bus.name(),
+            bus.name(),
+            bus.baudrate()
+        )
+        .unwrap();
+        for node in nodes.iter().filter(|n| n.buses().iter().any(|b| b.id() == bus.id())) {
+            writeln!(
+                dot,
+                "  \"{}\" -> \"bus_{}\" [dir=none, style=dashed];",
+                node.name(),
+                bus.name()
+            )
+            .unwrap();
+        }
+    }
+    for message in &messages {
+        let id = message.id().to_string();
+        let (label, style) = match message.usage() {
+            MessageUsage::Stream(stream) => {
+                // the network is borrowed, so the stream is still alive.
+                let name = stream.upgrade().map_or_else(|| message.name().to_owned(), |s| s.name().to_owned());
+                (format!("{name} [{}]", id.trim()), "bold")
+            }
+            MessageUsage::Debug { .. } => (format!("{} [{}]", message.name(), id.trim()), "dotted"),
+            _ => (format!("{} [{}]", message.name(), id.trim()), "solid"),
+        };
+        for tx in nodes.iter().filter(|n| transmits(n, message)) {
+            for rx in nodes.iter().filter(|n| receives(n, message)) {
+                if tx.name() == rx.name() {
+                    continue;
+                }
+                writeln!(
+                    dot,
+                    "  \"{}\" -> \"{}\" [label=\"{label}\", style={style}];",
+                    tx.name(),
+                    rx.name()
+                )
+                .unwrap();
+            }
+        }
+    }
+    for supervision in network.supervisions() {
+        let rendered = |name: &str| nodes.iter().any(|n| n.name() == name);
+        if !rendered(supervision.supervisor().name()) || !rendered(supervision.supervised().name()) {
+            continue;
+        }
+        writeln!(
+            dot,
+            "  \"{}\" -> \"{}\" [label=\"supervises via {} ({:?})\", style=dotted, color=red];",
+            supervision.supervisor().name(),
+            supervision.supervised().name(),
+            supervision.message().name(),
+            supervision.timeout()
+        )
+        .unwrap();
+    }
+    writeln!(dot, "}}").unwrap();
+    dot
+}
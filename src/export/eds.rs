@@ -0,0 +1,253 @@
+use std::fmt::Write;
+
+use crate::{
+    config::{Node, ObjectEntry, ObjectEntryAccess, SignalType, Type},
+    errors::{ConfigError, Result},
+};
+
+/// first index of the manufacturer specific profile area.
+const MANUFACTURER_AREA: u32 = 0x2000;
+/// last index of the manufacturer specific profile area.
+const MANUFACTURER_AREA_END: u32 = 0x5FFF;
+
+const OBJECT_TYPE_VAR: u8 = 0x7;
+const OBJECT_TYPE_ARRAY: u8 = 0x8;
+const OBJECT_TYPE_RECORD: u8 = 0x9;
+
+/// highest sub-index of a record or array, 0xFF is reserved.
+const MAX_SUB_INDEX: usize = 0xFE;
+
+/// CANopen data type of a primitive value, odd sizes are widened to the
+/// next standard type. Decimals are exposed as their physical value.
+/// Composite types don't have a data type, they are mapped to sub-indices.
+fn data_type(ty: &Type) -> Result<u16> {
+    let (size, signed) = match ty {
+        Type::Primitive(SignalType::UnsignedInt { size }) => (*size, false),
+        Type::Primitive(SignalType::SignedInt { size }) => (*size, true),
+        Type::Primitive(SignalType::Decimal { size, .. }) => {
+            return Ok(if *size <= 32 { 0x0008 } else { 0x0011 });
+        }
+        Type::Enum { size, .. } => (*size, false),
+        Type::Struct { .. } | Type::Array { .. } | Type::Union { .. } => {
+            return Err(ConfigError::Eds(format!(
+                "{} is not a primitive type and has no data type",
+                ty.name()
+            )))
+        }
+    };
+    Ok(match (size, signed) {
+        (1, false) => 0x0001,
+        (0..=8, true) => 0x0002,
+        (9..=16, true) => 0x0003,
+        (17..=32, true) => 0x0004,
+        (_, true) => 0x0015,
+        (0..=8, false) => 0x0005,
+        (9..=16, false) => 0x0006,
+        (17..=32, false) => 0x0007,
+        (_, false) => 0x001B,
+    })
+}
+
+fn access_type(access: &ObjectEntryAccess) -> &'static str {
+    match access {
+        ObjectEntryAccess::Const => "const",
+        ObjectEntryAccess::Local => "ro",
+        ObjectEntryAccess::Global | ObjectEntryAccess::Protected { .. } => "rw",
+    }
+}
+
+/// default value in the CANopen representation (enum entries by value).
+fn default_value(ty: &Type, default: Option<&str>) -> String {
+    match (ty, default) {
+        (Type::Enum { entries, .. }, Some(default)) => entries
+            .iter()
+            .find(|(name, _)| name == default)
+            .map(|(_, value)| value.to_string())
+            .unwrap_or_else(|| "0".to_owned()),
+        (_, Some(default)) => default.to_owned(),
+        (_, None) => "0".to_owned(),
+    }
+}
+
+/// flattens a composite type into its primitive leaves.
+fn leaves<'a>(prefix: &str, ty: &'a Type, out: &mut Vec<(String, &'a Type)>) {
+    match ty {
+        Type::Struct { attribs, .. } => {
+            for (name, attrib_ty) in attribs {
+                leaves(&format!("{prefix}.{name}"), attrib_ty, out);
+            }
+        }
+        Type::Array { len, ty } => {
+            for i in 0..*len {
+                leaves(&format!("{prefix}[{i}]"), ty, out);
+            }
+        }
+        Type::Union { tag, variants, .. } => {
+            leaves(&format!("{prefix}.tag"), tag, out);
+            for (entry, variant_ty) in variants {
+                leaves(&format!("{prefix}.{entry}"), variant_ty, out);
+            }
+        }
+        _ => out.push((prefix.to_owned(), ty)),
+    }
+}
+
+fn write_var(
+    eds: &mut String,
+    section: &str,
+    name: &str,
+    ty: &Type,
+    access: &str,
+    default: &str,
+) -> Result<()> {
+    writeln!(eds, "[{section}]").unwrap();
+    writeln!(eds, "ParameterName={name}").unwrap();
+    writeln!(eds, "ObjectType=0x{OBJECT_TYPE_VAR:X}").unwrap();
+    writeln!(eds, "DataType=0x{:04X}", data_type(ty)?).unwrap();
+    writeln!(eds, "AccessType={access}").unwrap();
+    writeln!(eds, "DefaultValue={default}").unwrap();
+    writeln!(eds, "PDOMapping=0").unwrap();
+    writeln!(eds).unwrap();
+    Ok(())
+}
+
+fn write_object_entry(eds: &mut String, index: u32, object_entry: &ObjectEntry) -> Result<()> {
+    let access = access_type(object_entry.access());
+    let ty = object_entry.ty() as &Type;
+    match ty {
+        Type::Primitive(_) | Type::Enum { .. } => write_var(
+            eds,
+            &format!("{index:04X}"),
+            object_entry.name(),
+            ty,
+            access,
+            &default_value(ty, object_entry.default_value()),
+        ),
+        Type::Struct { .. } | Type::Array { .. } | Type::Union { .. } => {
+            let mut sub_entries = vec![];
+            leaves("", ty, &mut sub_entries);
+            if sub_entries.len() > MAX_SUB_INDEX {
+                return Err(ConfigError::Eds(format!(
+                    "object entry {} has {} primitive members, but a record or array can only have {MAX_SUB_INDEX} sub-indices",
+                    object_entry.name(),
+                    sub_entries.len()
+                )));
+            }
+            let object_type = match ty {
+                Type::Array { .. } => OBJECT_TYPE_ARRAY,
+                _ => OBJECT_TYPE_RECORD,
+            };
+            writeln!(eds, "[{index:04X}]").unwrap();
+            writeln!(eds, "ParameterName={}", object_entry.name()).unwrap();
+            writeln!(eds, "ObjectType=0x{object_type:X}").unwrap();
+            writeln!(eds, "SubNumber={}", sub_entries.len() + 1).unwrap();
+            writeln!(eds).unwrap();
+            write_var(
+                eds,
+                &format!("{index:04X}sub0"),
+                "Highest sub-index supported",
+                &Type::Primitive(SignalType::UnsignedInt { size: 8 }),
+                "const",
+                &sub_entries.len().to_string(),
+            )?;
+            for (i, (name, sub_ty)) in sub_entries.iter().enumerate() {
+                write_var(
+                    eds,
+                    &format!("{index:04X}sub{:X}", i + 1),
+                    &format!("{}{name}", object_entry.name()),
+                    sub_ty,
+                    access,
+                    "0",
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Exports the object dictionary of a node as a CANopen electronic data
+/// sheet. Object entries are mapped into the manufacturer specific area
+/// at 0x2000 + od index, composite types are mapped to sub-indices.
+/// A device configuration file (dcf) additionally contains the node id and
+/// baudrate of the node.
+///
+/// Fails if an od index doesn't fit into the manufacturer specific area.
+pub fn node_to_eds(node: &Node, dcf: bool) -> Result<String> {
+    if let Some(object_entry) = node
+        .object_entries()
+        .iter()
+        .find(|object_entry| MANUFACTURER_AREA + object_entry.id() > MANUFACTURER_AREA_END)
+    {
+        return Err(ConfigError::Eds(format!(
+            "index {} of object entry {}::{} is outside of the manufacturer specific area (0x{MANUFACTURER_AREA:04X}-0x{MANUFACTURER_AREA_END:04X})",
+            object_entry.id(),
+            node.name(),
+            object_entry.name()
+        )));
+    }
+    let mut eds = String::new();
+    let file_name = format!("{}.{}", node.name(), if dcf { "dcf" } else { "eds" });
+    writeln!(eds, "[FileInfo]").unwrap();
+    writeln!(eds, "FileName={file_name}").unwrap();
+    writeln!(eds, "FileVersion=1").unwrap();
+    writeln!(eds, "FileRevision=0").unwrap();
+    writeln!(eds, "EDSVersion=4.0").unwrap();
+    if let Some(description) = node.description() {
+        writeln!(eds, "Description={description}").unwrap();
+    }
+    writeln!(eds, "CreatedBy=canzero-config").unwrap();
+    writeln!(eds).unwrap();
+
+    writeln!(eds, "[DeviceInfo]").unwrap();
+    writeln!(eds, "VendorName=mu-zero").unwrap();
+    writeln!(eds, "ProductName={}", node.name()).unwrap();
+    for baudrate in [10, 20, 50, 125, 250, 500, 800, 1000] {
+        let supported = node.buses().iter().any(|b| b.baudrate() == baudrate * 1000);
+        writeln!(eds, "BaudRate_{baudrate}={}", supported as u8).unwrap();
+    }
+    writeln!(eds, "SimpleBootUpMaster=0").unwrap();
+    writeln!(eds, "SimpleBootUpSlave=0").unwrap();
+    writeln!(eds, "Granularity=0").unwrap();
+    writeln!(eds, "NrOfRXPDO=0").unwrap();
+    writeln!(eds, "NrOfTXPDO=0").unwrap();
+    writeln!(eds, "LSS_Supported=0").unwrap();
+    writeln!(eds).unwrap();
+
+    if dcf {
+        writeln!(eds, "[DeviceComissioning]").unwrap();
+        writeln!(eds, "NodeID={}", node.id()).unwrap();
+        writeln!(eds, "NodeName={}", node.name()).unwrap();
+        if let Some(bus) = node.buses().first() {
+            writeln!(eds, "Baudrate={}", bus.baudrate() / 1000).unwrap();
+        }
+        writeln!(eds).unwrap();
+    }
+
+    writeln!(eds, "[MandatoryObjects]").unwrap();
+    writeln!(eds, "SupportedObjects=1").unwrap();
+    writeln!(eds, "1=0x1000").unwrap();
+    writeln!(eds).unwrap();
+    write_var(
+        &mut eds,
+        "1000",
+        "Device type",
+        &Type::Primitive(SignalType::UnsignedInt { size: 32 }),
+        "ro",
+        "0",
+    )?;
+
+    writeln!(eds, "[OptionalObjects]").unwrap();
+    writeln!(eds, "SupportedObjects=0").unwrap();
+    writeln!(eds).unwrap();
+
+    writeln!(eds, "[ManufacturerObjects]").unwrap();
+    writeln!(eds, "SupportedObjects={}", node.object_entries().len()).unwrap();
+    for (i, object_entry) in node.object_entries().iter().enumerate() {
+        writeln!(eds, "{}=0x{:04X}", i + 1, MANUFACTURER_AREA + object_entry.id()).unwrap();
+    }
+    writeln!(eds).unwrap();
+    for object_entry in node.object_entries() {
+        write_object_entry(&mut eds, MANUFACTURER_AREA + object_entry.id(), object_entry)?;
+    }
+    Ok(eds)
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use crate::config::{Message, MessageId, Network, SignalType};
+
+use super::{enum_labels, xml_escape};
+
+pub const KCD_NAMESPACE: &str = "http://kayak.2codeornot2code.org/1.0";
+
+fn write_message(kcd: &mut String, network: &Network, message: &Message) {
+    let (id, format) = match message.id() {
+        MessageId::StandardId(id) => (*id, "standard"),
+        MessageId::ExtendedId(id) => (*id, "extended"),
+    };
+    writeln!(
+        kcd,
+        "    <Message id=\"0x{id:03X}\" name=\"{}\" length=\"{}\" format=\"{format}\">",
+        xml_escape(message.name()),
+        message.dlc()
+    )
+    .unwrap();
+    if let Some(description) = message.description() {
+        writeln!(kcd, "      <Notes>{}</Notes>", xml_escape(description)).unwrap();
+    }
+    let producers: Vec<_> = network
+        .nodes()
+        .iter()
+        .filter(|n| n.tx_messages().iter().any(|m| m.name() == message.name()))
+        .collect();
+    if !producers.is_empty() {
+        writeln!(kcd, "      <Producer>").unwrap();
+        for node in producers {
+            writeln!(kcd, "        <NodeRef id=\"{}\"/>", node.id()).unwrap();
+        }
+        writeln!(kcd, "      </Producer>").unwrap();
+    }
+    let consumers: Vec<_> = network
+        .nodes()
+        .iter()
+        .filter(|n| n.rx_messages().iter().any(|m| m.name() == message.name()))
+        .collect();
+    let labels = enum_labels(message);
+
+    for signal in message.signals() {
+        writeln!(
+            kcd,
+            "      <Signal name=\"{}\" offset=\"{}\" length=\"{}\" endianess=\"little\">",
+            xml_escape(signal.name()),
+            signal.byte_offset(),
+            signal.size()
+        )
+        .unwrap();
+        if let Some(description) = signal.description() {
+            writeln!(kcd, "        <Notes>{}</Notes>", xml_escape(description)).unwrap();
+        }
+        let consumers: Vec<_> = consumers
+            .iter()
+            .filter(|node| signal.is_received_by(node.name()))
+            .collect();
+        if !consumers.is_empty() {
+            writeln!(kcd, "        <Consumer>").unwrap();
+            for node in &consumers {
+                writeln!(kcd, "          <NodeRef id=\"{}\"/>", node.id()).unwrap();
+            }
+            writeln!(kcd, "        </Consumer>").unwrap();
+        }
+        match signal.ty() {
+            SignalType::UnsignedInt { size: _ } => {
+                writeln!(kcd, "        <Value type=\"unsigned\"/>").unwrap()
+            }
+            SignalType::SignedInt { size: _ } => {
+                writeln!(kcd, "        <Value type=\"signed\"/>").unwrap()
+            }
+            SignalType::Decimal {
+                size: _,
+                offset,
+                scale,
+            } => writeln!(
+                kcd,
+                "        <Value type=\"unsigned\" slope=\"{scale}\" intercept=\"{offset}\"/>"
+            )
+            .unwrap(),
+        }
+        if let Some((_, _, entries)) = labels.iter().find(|(name, _, _)| *name == signal.name()) {
+            writeln!(kcd, "        <LabelSet>").unwrap();
+            for (entry, value) in entries.iter() {
+                writeln!(
+                    kcd,
+                    "          <Label name=\"{}\" value=\"{value}\"/>",
+                    xml_escape(entry)
+                )
+                .unwrap();
+            }
+            writeln!(kcd, "        </LabelSet>").unwrap();
+        }
+        writeln!(kcd, "      </Signal>").unwrap();
+    }
+    writeln!(kcd, "    </Message>").unwrap();
+}
+
+/// Exports the network in the Kayak CAN definition (KCD) format.
+pub fn network_to_kcd(network: &Network) -> String {
+    let mut kcd = String::new();
+    writeln!(kcd, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(kcd, "<NetworkDefinition xmlns=\"{KCD_NAMESPACE}\">").unwrap();
+    write!(
+        kcd,
+        "  <Document name=\"{}\"",
+        xml_escape(network.name().unwrap_or("canzero"))
+    )
+    .unwrap();
+    if let Some(version) = network.version() {
+        write!(kcd, " version=\"{version}\"").unwrap();
+    }
+    if let Some(author) = network.author() {
+        write!(kcd, " author=\"{}\"", xml_escape(author)).unwrap();
+    }
+    write!(kcd, " date=\"{}\"", network.build_time().format("%Y-%m-%d")).unwrap();
+    match network.description() {
+        Some(description) => writeln!(kcd, ">{}</Document>", xml_escape(description)).unwrap(),
+        None => writeln!(kcd, "/>").unwrap(),
+    }
+    for node in network.nodes() {
+        writeln!(
+            kcd,
+            "  <Node id=\"{}\" name=\"{}\"/>",
+            node.id(),
+            xml_escape(node.name())
+        )
+        .unwrap();
+    }
+    for bus in network.buses() {
+        writeln!(
+            kcd,
+            "  <Bus name=\"{}\" baudrate=\"{}\">",
+            xml_escape(bus.name()),
+            bus.baudrate()
+        )
+        .unwrap();
+        for message in network.messages().iter().filter(|m| m.bus().id() == bus.id()) {
+            write_message(&mut kcd, network, message);
+        }
+        writeln!(kcd, "  </Bus>").unwrap();
+    }
+    writeln!(kcd, "</NetworkDefinition>").unwrap();
+    kcd
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+use crate::config::{Message, MessageId, Network, SignalType};
+
+use super::{enum_labels, xml_escape};
+
+/// cn_data_type of unsigned little endian integers.
+const CN_DATA_TYPE_UNSIGNED_LE: u8 = 0;
+/// cn_data_type of signed little endian integers.
+const CN_DATA_TYPE_SIGNED_LE: u8 = 2;
+/// cc_type of linear conversions (phys = p1 + p2 * raw).
+const CC_TYPE_LINEAR: u8 = 1;
+/// cc_type of value to text conversions.
+const CC_TYPE_VALUE_TO_TEXT: u8 = 7;
+
+fn write_message(mdf: &mut String, message: &Message) {
+    let (id, ide) = match message.id() {
+        MessageId::StandardId(id) => (format!("0x{id:03X}"), false),
+        MessageId::ExtendedId(id) => (format!("0x{id:08X}"), true),
+    };
+    writeln!(
+        mdf,
+        "  <channel_group name=\"{}\" acq_source=\"{}\" bus_type=\"CAN\" id=\"{id}\" ide=\"{ide}\" dlc=\"{}\">",
+        xml_escape(message.name()),
+        xml_escape(message.bus().name()),
+        message.dlc()
+    )
+    .unwrap();
+    if let Some(description) = message.description() {
+        writeln!(mdf, "    <comment>{}</comment>", xml_escape(description)).unwrap();
+    }
+    let labels = enum_labels(message);
+    for signal in message.signals() {
+        let data_type = match signal.ty() {
+            SignalType::SignedInt { .. } => CN_DATA_TYPE_SIGNED_LE,
+            SignalType::UnsignedInt { .. } | SignalType::Decimal { .. } => {
+                CN_DATA_TYPE_UNSIGNED_LE
+            }
+        };
+        writeln!(
+            mdf,
+            "    <channel name=\"{}\" byte_offset=\"{}\" bit_offset=\"{}\" bit_count=\"{}\" data_type=\"{data_type}\">",
+            xml_escape(signal.name()),
+            signal.byte_offset() / 8,
+            signal.byte_offset() % 8,
+            signal.size()
+        )
+        .unwrap();
+        if let Some(description) = signal.description() {
+            writeln!(mdf, "      <comment>{}</comment>", xml_escape(description)).unwrap();
+        }
+        if let Some((_, enum_name, entries)) =
+            labels.iter().find(|(name, _, _)| *name == signal.name())
+        {
+            writeln!(
+                mdf,
+                "      <conversion type=\"{CC_TYPE_VALUE_TO_TEXT}\" name=\"{}\">",
+                xml_escape(enum_name)
+            )
+            .unwrap();
+            for (entry, value) in entries.iter() {
+                writeln!(
+                    mdf,
+                    "        <value raw=\"{value}\" text=\"{}\"/>",
+                    xml_escape(entry)
+                )
+                .unwrap();
+            }
+            writeln!(mdf, "      </conversion>").unwrap();
+        } else if let SignalType::Decimal { offset, scale, .. } = signal.ty() {
+            writeln!(
+                mdf,
+                "      <conversion type=\"{CC_TYPE_LINEAR}\" p1=\"{offset}\" p2=\"{scale}\"/>"
+            )
+            .unwrap();
+        }
+        writeln!(mdf, "    </channel>").unwrap();
+    }
+    writeln!(mdf, "  </channel_group>").unwrap();
+}
+
+/// Exports the channel groups (one per message), channels (one per signal) and
+/// conversions, which are required to convert raw CAN logs (e.g. BLF or MDF4
+/// bus logging files) into MDF4 measurements with named and scaled channels.
+///
+/// Offsets are relative to the first data byte of the frame, data types and
+/// conversion types are the cn_data_type and cc_type values of ASAM MDF 4.1.
+pub fn network_to_mdf4_channels(network: &Network) -> String {
+    let mut mdf = String::new();
+    writeln!(mdf, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+    writeln!(mdf, "<mdf4_channels version=\"4.10\">").unwrap();
+    for message in network.messages() {
+        write_message(&mut mdf, message);
+    }
+    writeln!(mdf, "</mdf4_channels>").unwrap();
+    mdf
+}
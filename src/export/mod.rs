@@ -0,0 +1,73 @@
+use crate::config::{
+    encoding::{PrimitiveSignalEncoding, TypeSignalEncoding},
+    Message, Type,
+};
+
+pub use self::dot::DotOptions;
+
+pub mod arxml;
+pub mod canonical;
+pub mod dbc;
+pub mod docs;
+pub mod dot;
+pub mod eds;
+pub mod kcd;
+pub mod mdf4;
+pub mod supervision;
+pub mod sym;
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// (signal name, enum name, enum entries)
+type EnumLabels<'a> = (&'a str, &'a str, &'a Vec<(String, u64)>);
+
+fn push_label<'a>(primitive: &'a PrimitiveSignalEncoding, labels: &mut Vec<EnumLabels<'a>>) {
+    if let Type::Enum { name, entries, .. } = primitive.ty() as &Type {
+        labels.push((primitive.signal().name(), name, entries));
+    }
+}
+
+/// collects the enum entries of all signals which encode an enum.
+fn collect_labels<'a>(
+    encoding: &'a TypeSignalEncoding,
+    labels: &mut Vec<EnumLabels<'a>>,
+) {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => {
+            for attrib in composite.attributes() {
+                collect_labels(attrib, labels);
+            }
+        }
+        TypeSignalEncoding::Primitive(primitive) => push_label(primitive, labels),
+        TypeSignalEncoding::Multiplexed(mux) => {
+            push_label(mux.tag(), labels);
+            for (_, _, variant) in mux.variants() {
+                collect_labels(variant, labels);
+            }
+        }
+    }
+}
+
+/// enum entries of all signals of a message, which encode an enum.
+fn enum_labels(message: &Message) -> Vec<EnumLabels<'_>> {
+    let mut labels = vec![];
+    if let Some(encoding) = message.encoding() {
+        for attrib in encoding.attributes() {
+            collect_labels(attrib, &mut labels);
+        }
+    }
+    labels
+}
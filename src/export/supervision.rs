@@ -0,0 +1,32 @@
+use std::fmt::Write;
+
+use crate::config::Network;
+
+/// Renders the supervision relationships as a markdown table, which can be
+/// included into the safety case. Rows are sorted by supervisor and
+/// supervised node, so the table only changes with the configuration.
+pub fn network_to_supervision_table(network: &Network) -> String {
+    let mut rows: Vec<[String; 5]> = network
+        .supervisions()
+        .iter()
+        .map(|supervision| {
+            let message = supervision.message();
+            [
+                supervision.supervisor().name().to_owned(),
+                supervision.supervised().name().to_owned(),
+                message.name().to_owned(),
+                format!("{}", message.id()).trim().to_owned(),
+                format!("{:?}", supervision.timeout()),
+            ]
+        })
+        .collect();
+    rows.sort();
+
+    let mut table = String::new();
+    writeln!(table, "| Supervisor | Supervised | Message | Id | Timeout |").unwrap();
+    writeln!(table, "|---|---|---|---|---|").unwrap();
+    for row in rows {
+        writeln!(table, "| {} |", row.join(" | ")).unwrap();
+    }
+    table
+}
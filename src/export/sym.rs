@@ -0,0 +1,136 @@
+use std::fmt::Write;
+
+use crate::config::{MessageId, Network, SignalRef, SignalType};
+
+use super::enum_labels;
+
+/// Exports the network as a PCAN symbol file (.sym, format version 6.0).
+/// All messages are listed in the {SENDRECEIVE} section, enums are exported
+/// as symbol enums and referenced by the signals that encode them.
+pub fn network_to_sym(network: &Network) -> String {
+    let mut sym = String::new();
+    writeln!(sym, "FormatVersion=6.0 // Do not edit this line!").unwrap();
+    writeln!(sym, "Title=\"{}\"", sym_string(network.name().unwrap_or("canzero"))).unwrap();
+    writeln!(sym).unwrap();
+
+    let mut enums: Vec<(&str, &Vec<(String, u64)>)> = vec![];
+    for message in network.messages() {
+        for (_, enum_name, entries) in enum_labels(message) {
+            if !enums.iter().any(|(name, _)| *name == enum_name) {
+                enums.push((enum_name, entries));
+            }
+        }
+    }
+    if !enums.is_empty() {
+        writeln!(sym, "{{ENUMS}}").unwrap();
+        for (name, entries) in &enums {
+            let entries: Vec<String> = entries
+                .iter()
+                .map(|(entry, value)| format!("{value}=\"{}\"", sym_string(entry)))
+                .collect();
+            writeln!(sym, "enum {name}({})", entries.join(", ")).unwrap();
+        }
+        writeln!(sym).unwrap();
+    }
+
+    writeln!(sym, "{{SENDRECEIVE}}").unwrap();
+    for message in network.messages() {
+        let labels = enum_labels(message);
+        let var = |signal: &SignalRef| {
+            let position = format!("{},{}", signal.byte_offset(), signal.size());
+            let var = match signal.ty() {
+                SignalType::UnsignedInt { size: 1 } => {
+                    format!("Var={} bit {position}", signal.name())
+                }
+                SignalType::UnsignedInt { size: _ } => {
+                    format!("Var={} unsigned {position}", signal.name())
+                }
+                SignalType::SignedInt { size: _ } => {
+                    format!("Var={} signed {position}", signal.name())
+                }
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                } => format!(
+                    "Var={} unsigned {position} /f:{scale} /o:{offset}",
+                    signal.name()
+                ),
+            };
+            match labels.iter().find(|(name, _, _)| *name == signal.name()) {
+                Some((_, enum_name, _)) => format!("{var} /e:{enum_name}"),
+                None => var,
+            }
+        };
+        let write_header = |sym: &mut String| {
+            writeln!(sym).unwrap();
+            writeln!(sym, "[{}]", message.name()).unwrap();
+            match message.id() {
+                MessageId::StandardId(id) => writeln!(sym, "ID={id:03X}h").unwrap(),
+                MessageId::ExtendedId(id) => {
+                    writeln!(sym, "ID={id:08X}h").unwrap();
+                    writeln!(sym, "Type=Extended").unwrap();
+                }
+            }
+            writeln!(sym, "Len={}", message.dlc()).unwrap();
+            if let Some(description) = message.description() {
+                writeln!(sym, "// {}", sym_string(description)).unwrap();
+            }
+        };
+
+        // (signal, multiplexor, value) like in dbc exports,
+        // only a single multiplexor per message is supported.
+        let multiplexed: Vec<(&SignalRef, &SignalRef, u64)> = message
+            .signals()
+            .iter()
+            .filter_map(|signal| {
+                message
+                    .encoding()
+                    .and_then(|encoding| encoding.multiplexor_of(signal.name()))
+                    .map(|(tag, value)| (signal, tag, value))
+            })
+            .collect();
+        let multiplexor = multiplexed
+            .first()
+            .map(|(_, tag, _)| *tag)
+            .filter(|tag| multiplexed.iter().all(|(_, t, _)| t.name() == tag.name()));
+        let Some(multiplexor) = multiplexor else {
+            write_header(&mut sym);
+            for signal in message.signals() {
+                writeln!(sym, "{}", var(signal)).unwrap();
+            }
+            continue;
+        };
+        // every value of the multiplexor is a section of its own,
+        // which repeats the signals, which aren't multiplexed.
+        let mut values: Vec<u64> = multiplexed.iter().map(|(_, _, value)| *value).collect();
+        values.sort();
+        values.dedup();
+        for value in values {
+            write_header(&mut sym);
+            writeln!(
+                sym,
+                "Mux={} {},{} {value:X}h",
+                multiplexor.name(),
+                multiplexor.byte_offset(),
+                multiplexor.size()
+            )
+            .unwrap();
+            for signal in message.signals() {
+                if signal.name() == multiplexor.name() {
+                    continue;
+                }
+                match multiplexed.iter().find(|(s, _, _)| s.name() == signal.name()) {
+                    Some((_, _, v)) if *v != value => continue,
+                    _ => writeln!(sym, "{}", var(signal)).unwrap(),
+                }
+            }
+        }
+    }
+    sym
+}
+
+/// Quotes are replaced, because the format doesn't define escape sequences.
+fn sym_string(text: &str) -> String {
+    text.replace('"', "'").replace(['\n', '\r'], " ")
+}
@@ -0,0 +1,64 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+/// Orders the nodes of a dependency graph, such that every node comes after
+/// all nodes it depends on. `dependencies[i]` contains the indices of the nodes,
+/// which node `i` depends on.
+///
+/// Implemented iteratively with Kahn's algorithm, so deep dependency chains
+/// can't overflow the stack. Among independent nodes the lower index comes first,
+/// which keeps the order deterministic.
+///
+/// Returns the nodes of a cycle, if the graph isn't acyclic. Every node of the
+/// returned cycle depends on the next one and the last depends on the first.
+pub fn topological_sort(dependencies: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let n = dependencies.len();
+    let mut remaining = vec![0usize; n];
+    let mut dependents = vec![vec![]; n];
+    for (node, deps) in dependencies.iter().enumerate() {
+        let mut deps = deps.clone();
+        deps.sort();
+        deps.dedup();
+        remaining[node] = deps.len();
+        for dep in deps {
+            dependents[dep].push(node);
+        }
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..n)
+        .filter(|node| remaining[*node] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse(node)) = ready.pop() {
+        order.push(node);
+        for dependent in &dependents[node] {
+            remaining[*dependent] -= 1;
+            if remaining[*dependent] == 0 {
+                ready.push(Reverse(*dependent));
+            }
+        }
+    }
+    if order.len() == n {
+        return Ok(order);
+    }
+
+    // every node that wasn't ordered depends on another node that wasn't ordered,
+    // so following those dependencies has to end up in a cycle.
+    let start = (0..n).find(|node| remaining[*node] != 0).unwrap();
+    let mut path = vec![start];
+    let mut position = vec![None; n];
+    position[start] = Some(0);
+    let mut current = start;
+    loop {
+        let next = *dependencies[current]
+            .iter()
+            .find(|dep| remaining[**dep] != 0)
+            .unwrap();
+        if let Some(cycle_start) = position[next] {
+            return Err(path.split_off(cycle_start));
+        }
+        position[next] = Some(path.len());
+        path.push(next);
+        current = next;
+    }
+}
@@ -2,4 +2,13 @@
 pub mod errors;
 pub mod config;
 pub mod builder;
-
+pub mod export;
+pub mod analysis;
+pub mod codegen;
+#[cfg(feature = "tools")]
+pub mod tools;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "cli")]
+pub mod cli;
+mod graph;
@@ -0,0 +1,341 @@
+//! Strategies, which generate random networks for property based tests,
+//! and assertions over the invariants of built networks and the round trips
+//! through the importers and exporters.
+use std::collections::HashSet;
+
+use proptest::{collection, prelude::*, sample};
+
+use crate::{
+    builder::NetworkBuilder,
+    config::{Network, NetworkRef},
+    errors::Result,
+};
+
+/// Messages of the built-in protocols, which are not exported.
+const PROTOCOL_MESSAGES: [&str; 5] = ["get_req", "get_resp", "set_req", "set_resp", "heartbeat"];
+
+#[derive(Debug, Clone)]
+pub enum TypeSpec {
+    Enum {
+        name: String,
+        entries: Vec<String>,
+    },
+    Struct {
+        name: String,
+        /// (attribute name, type name)
+        attributes: Vec<(String, String)>,
+    },
+}
+
+impl TypeSpec {
+    pub fn name(&self) -> &str {
+        match self {
+            TypeSpec::Enum { name, .. } | TypeSpec::Struct { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeSpec {
+    pub name: String,
+    /// (object entry name, type name)
+    pub object_entries: Vec<(String, String)>,
+    /// indices into object_entries of every stream.
+    pub streams: Vec<Vec<usize>>,
+}
+
+/// Message with a fixed standard id, which is transmitted by an index into nodes.
+#[derive(Debug, Clone)]
+pub struct MessageSpec {
+    pub name: String,
+    pub id: u32,
+    /// (signal name, primitive type name)
+    pub signals: Vec<(String, String)>,
+    pub transmitter: usize,
+    pub receivers: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NetworkSpec {
+    pub types: Vec<TypeSpec>,
+    pub nodes: Vec<NodeSpec>,
+    pub messages: Vec<MessageSpec>,
+}
+
+impl NetworkSpec {
+    /// Network builder with a single bus, which declares everything of the spec.
+    pub fn to_builder(&self) -> Result<NetworkBuilder> {
+        let network_builder = NetworkBuilder::new();
+        network_builder.create_bus("can0", Some(1000000));
+        for ty in &self.types {
+            match ty {
+                TypeSpec::Enum { name, entries } => {
+                    let enum_builder = network_builder.define_enum(name);
+                    for entry in entries {
+                        enum_builder.add_entry(entry, None)?;
+                    }
+                }
+                TypeSpec::Struct { name, attributes } => {
+                    let struct_builder = network_builder.define_struct(name);
+                    for (attribute, ty) in attributes {
+                        struct_builder.add_attribute(attribute, ty)?;
+                    }
+                }
+            }
+        }
+        for (i, node) in self.nodes.iter().enumerate() {
+            let node_builder = network_builder.create_node(&node.name);
+            for (name, ty) in &node.object_entries {
+                node_builder.create_object_entry(name, ty);
+            }
+            for (j, entries) in node.streams.iter().enumerate() {
+                let stream_name = format!("stream_{j}");
+                let stream = node_builder.create_stream(&stream_name);
+                for &entry in entries {
+                    stream.add_entry(&node.object_entries[entry].0);
+                }
+                // the stream is received by the next node.
+                if self.nodes.len() > 1 {
+                    let rx_node = &self.nodes[(i + 1) % self.nodes.len()];
+                    network_builder
+                        .create_node(&rx_node.name)
+                        .receive_stream(&node.name, &stream_name);
+                }
+            }
+        }
+        for message in &self.messages {
+            let message_builder = network_builder.create_message(&message.name, None);
+            message_builder.set_std_id(message.id);
+            let format = message_builder.make_type_format();
+            for (name, ty) in &message.signals {
+                format.add_type(ty, name);
+            }
+            message_builder.add_transmitter(&self.nodes[message.transmitter].name);
+            for &rx in &message.receivers {
+                message_builder.add_receiver(&self.nodes[rx].name);
+            }
+        }
+        Ok(network_builder)
+    }
+
+    pub fn build(&self) -> Result<NetworkRef> {
+        self.to_builder()?.build()
+    }
+}
+
+/// Primitive types of at most 16 bits, e.g. u4, i12 or d8<-10..100>.
+pub fn arb_primitive_type() -> impl Strategy<Value = String> {
+    prop_oneof![
+        (1u8..=16).prop_map(|size| format!("u{size}")),
+        (2u8..=16).prop_map(|size| format!("i{size}")),
+        (1u8..=16, -100i32..=0, 1i32..=200)
+            .prop_map(|(size, min, max)| format!("d{size}<{min}..{max}>")),
+    ]
+}
+
+/// Up to two enums followed by up to two structs, whose attributes are
+/// primitives or the enums.
+pub fn arb_types() -> impl Strategy<Value = Vec<TypeSpec>> {
+    let enums = collection::vec(1usize..=6, 0..=2).prop_map(|entry_counts| {
+        entry_counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| TypeSpec::Enum {
+                name: format!("enum_{i}"),
+                entries: (0..count).map(|j| format!("entry_{j}")).collect(),
+            })
+            .collect::<Vec<_>>()
+    });
+    enums.prop_flat_map(|enums| {
+        let enum_names: Vec<String> = enums.iter().map(|e| e.name().to_owned()).collect();
+        let attribute = if enum_names.is_empty() {
+            arb_primitive_type().boxed()
+        } else {
+            prop_oneof![arb_primitive_type(), sample::select(enum_names)].boxed()
+        };
+        // signals of enum attributes are named after the enum, which has to be
+        // unique within the struct.
+        let attributes = collection::vec(attribute, 1..=3).prop_filter(
+            "enum is used by more than one attribute",
+            |attributes| {
+                let enums: Vec<_> = attributes.iter().filter(|ty| ty.starts_with("enum_")).collect();
+                enums.iter().collect::<HashSet<_>>().len() == enums.len()
+            },
+        );
+        let structs = collection::vec(attributes, 0..=2);
+        (Just(enums), structs).prop_map(|(mut types, structs)| {
+            for (i, attributes) in structs.into_iter().enumerate() {
+                types.push(TypeSpec::Struct {
+                    name: format!("struct_{i}"),
+                    attributes: attributes
+                        .into_iter()
+                        .enumerate()
+                        .map(|(j, ty)| (format!("attribute_{j}"), ty))
+                        .collect(),
+                });
+            }
+            types
+        })
+    })
+}
+
+/// Node with up to four object entries of the given types and up to two
+/// streams of at most two entries.
+pub fn arb_node(name: String, types: Vec<String>) -> impl Strategy<Value = NodeSpec> {
+    let ty = if types.is_empty() {
+        arb_primitive_type().boxed()
+    } else {
+        prop_oneof![arb_primitive_type(), sample::select(types)].boxed()
+    };
+    collection::vec(ty, 0..=4).prop_flat_map(move |entry_types| {
+        let object_entries: Vec<(String, String)> = entry_types
+            .into_iter()
+            .enumerate()
+            .map(|(i, ty)| (format!("oe_{i}"), ty))
+            .collect();
+        let streams = if object_entries.is_empty() {
+            Just(vec![]).boxed()
+        } else {
+            // signals of composite types are named after the type, which has to be
+            // unique within the stream.
+            let types: Vec<String> = object_entries.iter().map(|(_, ty)| ty.clone()).collect();
+            let stream = sample::subsequence(
+                (0..object_entries.len()).collect::<Vec<_>>(),
+                1..=2.min(object_entries.len()),
+            )
+            .prop_filter("composite type is streamed twice", move |entries| {
+                entries.len() < 2
+                    || types[entries[0]] != types[entries[1]]
+                    || !(types[entries[0]].starts_with("enum_")
+                        || types[entries[0]].starts_with("struct_"))
+            });
+            collection::vec(stream, 0..=2).boxed()
+        };
+        let name = name.clone();
+        (Just(object_entries), streams).prop_map(move |(object_entries, streams)| NodeSpec {
+            name: name.clone(),
+            object_entries,
+            streams,
+        })
+    })
+}
+
+/// Message with fixed id of up to four primitive signals between the nodes.
+pub fn arb_message(name: String, id: u32, node_count: usize) -> impl Strategy<Value = MessageSpec> {
+    (
+        collection::vec(arb_primitive_type(), 1..=4),
+        0..node_count,
+        collection::vec(0..node_count, 0..=2),
+    )
+        .prop_map(move |(types, transmitter, mut receivers)| {
+            receivers.sort();
+            receivers.dedup();
+            receivers.retain(|rx| *rx != transmitter);
+            MessageSpec {
+                name: name.clone(),
+                id,
+                signals: types
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, ty)| (format!("signal_{i}"), ty))
+                    .collect(),
+                transmitter,
+                receivers,
+            }
+        })
+}
+
+/// Network of up to four nodes, random types, streams and messages with fixed ids.
+pub fn arb_network() -> impl Strategy<Value = NetworkSpec> {
+    (arb_types(), 1usize..=4, collection::btree_set(0x100u32..0x400, 0..=4)).prop_flat_map(
+        |(types, node_count, ids)| {
+            let type_names: Vec<String> = types.iter().map(|ty| ty.name().to_owned()).collect();
+            let nodes: Vec<_> = (0..node_count)
+                .map(|i| arb_node(format!("node_{i}"), type_names.clone()))
+                .collect();
+            let messages: Vec<_> = ids
+                .into_iter()
+                .enumerate()
+                .map(|(i, id)| arb_message(format!("message_{i}"), id, node_count))
+                .collect();
+            (Just(types), nodes, messages).prop_map(|(types, nodes, messages)| NetworkSpec {
+                types,
+                nodes,
+                messages,
+            })
+        },
+    )
+}
+
+/// Ids are unique per bus, signals fit into the dlc of their message
+/// without overlapping and object entry indices are unique per node.
+pub fn assert_invariants(network: &Network) {
+    let mut ids = HashSet::new();
+    for message in network.messages() {
+        assert!(
+            ids.insert((*message.id(), message.bus().id())),
+            "id of message {} is assigned twice",
+            message.name()
+        );
+        let mut bits = vec![false; message.dlc() as usize * 8];
+        for signal in message.signals() {
+            for bit in signal.byte_offset()..signal.byte_offset() + signal.size() as usize {
+                assert!(bit < bits.len(), "signal {} exceeds the dlc", signal.name());
+                assert!(!bits[bit], "signal {} overlaps another signal", signal.name());
+                bits[bit] = true;
+            }
+        }
+    }
+    for node in network.nodes() {
+        let mut indices = HashSet::new();
+        for object_entry in node.object_entries() {
+            assert!(
+                indices.insert(object_entry.id()),
+                "index of object entry {}::{} is assigned twice",
+                node.name(),
+                object_entry.name()
+            );
+        }
+        for stream in node.tx_streams() {
+            assert!(
+                node.tx_messages().iter().any(|m| m.name() == stream.message().name()),
+                "stream {}::{} isn't transmitted",
+                node.name(),
+                stream.name()
+            );
+        }
+    }
+}
+
+/// Building the same spec twice results in the same configuration.
+pub fn assert_deterministic_build(spec: &NetworkSpec) {
+    let a = spec.build().expect("spec should build");
+    let b = spec.build().expect("spec should build");
+    assert_eq!(a.config_hash(), b.config_hash());
+}
+
+/// Exporting to KCD and importing it again preserves the ids, layouts
+/// and types of all messages except the built-in protocol messages.
+pub fn assert_kcd_round_trip(network: &Network) {
+    let kcd = network.to_kcd();
+    let imported = NetworkBuilder::from_kcd(&kcd)
+        .and_then(NetworkBuilder::build)
+        .expect("exported kcd should be importable");
+    for message in network.messages() {
+        if PROTOCOL_MESSAGES.contains(&message.name()) {
+            continue;
+        }
+        let other = imported
+            .message_by_name(message.name())
+            .unwrap_or_else(|| panic!("message {} was not imported", message.name()));
+        assert_eq!(other.id(), message.id(), "{}", message.name());
+        assert_eq!(other.bus().name(), message.bus().name());
+        assert_eq!(other.signals().len(), message.signals().len(), "{}", message.name());
+        for (a, b) in message.signals().iter().zip(other.signals()) {
+            // signals of signal formats are prefixed with the message name.
+            assert!(b.name().ends_with(a.name()), "{}", b.name());
+            assert_eq!(a.byte_offset(), b.byte_offset(), "{}", a.name());
+            assert_eq!(a.ty(), b.ty(), "{}", a.name());
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    fmt::Display,
+    io::{BufRead, Write},
+};
+
+use crate::{
+    codegen::layout::{message_layout, SignalKind},
+    config::{message::MessageUsage, MessageId, MessageRef, Network},
+    errors::{ConfigError, Result},
+};
+
+/// A single frame of a candump log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandumpFrame {
+    /// seconds since the epoch, only present in the log format (candump -l).
+    pub timestamp: Option<f64>,
+    pub bus: String,
+    pub id: MessageId,
+    pub data: Vec<u8>,
+}
+
+fn parse_id(id: &str) -> Option<MessageId> {
+    let value = u32::from_str_radix(id, 16).ok()?;
+    // candump prints extended ids with 8 digits.
+    if id.len() == 8 {
+        Some(MessageId::ExtendedId(value & 0x1FFFFFFF))
+    } else {
+        Some(MessageId::StandardId(value))
+    }
+}
+
+fn parse_bytes<'a>(bytes: impl Iterator<Item = &'a str>) -> Option<Vec<u8>> {
+    bytes.map(|b| u8::from_str_radix(b, 16).ok()).collect()
+}
+
+impl CandumpFrame {
+    /// Parses the default candump output (`can0  123   [2]  11 22`)
+    /// and the log format (`(1700000000.000000) can0 123#1122`).
+    pub fn parse(line: &str) -> Result<CandumpFrame> {
+        let invalid = || ConfigError::Candump(format!("invalid candump line: {line}"));
+        let mut tokens = line.split_whitespace().peekable();
+        let timestamp = match tokens.peek() {
+            Some(token) if token.starts_with('(') => {
+                let token = tokens.next().unwrap();
+                let seconds = token.trim_start_matches('(').trim_end_matches(')');
+                Some(seconds.parse::<f64>().map_err(|_| invalid())?)
+            }
+            _ => None,
+        };
+        let bus = tokens.next().ok_or_else(invalid)?.to_owned();
+        let frame = tokens.next().ok_or_else(invalid)?;
+        let (id, data) = match frame.split_once('#') {
+            Some((id, data)) => {
+                if data.starts_with('R') {
+                    return Err(ConfigError::Candump(format!(
+                        "remote frames can't be decoded: {line}"
+                    )));
+                }
+                let data = parse_bytes(
+                    (0..data.len() / 2).map(|i| data.get(2 * i..2 * i + 2).unwrap_or("")),
+                )
+                .ok_or_else(invalid)?;
+                (id, data)
+            }
+            None => {
+                let dlc = tokens.next().ok_or_else(invalid)?;
+                let dlc: usize = dlc
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .parse()
+                    .map_err(|_| invalid())?;
+                let data = parse_bytes(tokens.by_ref().take(dlc)).ok_or_else(invalid)?;
+                if data.len() != dlc {
+                    return Err(invalid());
+                }
+                (frame, data)
+            }
+        };
+        Ok(CandumpFrame {
+            timestamp,
+            bus,
+            id: parse_id(id).ok_or_else(invalid)?,
+            data,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignalValue {
+    Unsigned(u64),
+    Signed(i64),
+    Decimal(f64),
+    /// label is None if the raw value is not an entry of the enum.
+    Enum { label: Option<String>, raw: u64 },
+}
+
+impl Display for SignalValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SignalValue::Unsigned(value) => write!(f, "{value}"),
+            SignalValue::Signed(value) => write!(f, "{value}"),
+            SignalValue::Decimal(value) => write!(f, "{value}"),
+            SignalValue::Enum { label: Some(label), .. } => write!(f, "{label}"),
+            SignalValue::Enum { label: None, raw } => write!(f, "{raw}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSignal {
+    pub name: String,
+    pub value: SignalValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodedFrame {
+    pub message: MessageRef,
+    pub signals: Vec<DecodedSignal>,
+    /// node::object_entry addressed by a get/set request or response.
+    pub object_entry: Option<String>,
+}
+
+impl DecodedFrame {
+    pub fn signal(&self, name: &str) -> Option<&SignalValue> {
+        self.signals
+            .iter()
+            .find(|signal| signal.name == name)
+            .map(|signal| &signal.value)
+    }
+}
+
+impl Display for DecodedFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message.name())?;
+        if let Some(object_entry) = &self.object_entry {
+            write!(f, " [{object_entry}]")?;
+        }
+        for signal in &self.signals {
+            write!(f, " {}={}", signal.name, signal.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bits [offset, offset + size) of the little endian payload.
+fn extract_bits(data: &[u8], offset: usize, size: u8) -> u64 {
+    let mut raw = 0;
+    for i in 0..size as usize {
+        let bit = offset + i;
+        let byte = data.get(bit / 8).copied().unwrap_or(0);
+        raw |= (((byte >> (bit % 8)) & 1) as u64) << i;
+    }
+    raw
+}
+
+/// Decodes frames into named signals and enum labels with the knowledge of
+/// the network, including the object entries addressed by the get/set protocol,
+/// which are lost when going through a DBC export.
+/// Frames can come from a candump log or any other source (e.g. a live socketcan socket).
+pub struct CandumpDecoder<'a> {
+    network: &'a Network,
+}
+
+impl<'a> CandumpDecoder<'a> {
+    pub fn new(network: &'a Network) -> Self {
+        Self { network }
+    }
+
+    /// Decodes a frame received on the bus with the given name. If the network
+    /// has no bus with that name (e.g. vcan0 while testing), all buses are searched.
+    pub fn decode(&self, bus: &str, id: MessageId, data: &[u8]) -> Option<DecodedFrame> {
+        let message = match self.network.buses().iter().find(|b| b.name() == bus) {
+            Some(bus) => self.network.message_by_id(id, bus),
+            None => self
+                .network
+                .buses()
+                .iter()
+                .find_map(|bus| self.network.message_by_id(id, bus)),
+        }?;
+        let signals: Vec<DecodedSignal> = message_layout(message)
+            .iter()
+            .map(|layout| {
+                let raw = extract_bits(data, layout.offset, layout.size);
+                let value = match &layout.kind {
+                    SignalKind::Unsigned => SignalValue::Unsigned(raw),
+                    SignalKind::Signed => {
+                        let shift = 64 - layout.size as u32;
+                        SignalValue::Signed(((raw << shift) as i64) >> shift)
+                    }
+                    SignalKind::Decimal { offset, scale } => {
+                        SignalValue::Decimal(raw as f64 * scale + offset)
+                    }
+                    SignalKind::Enum { entries, .. } => SignalValue::Enum {
+                        label: entries
+                            .iter()
+                            .find(|(_, value)| *value == raw)
+                            .map(|(label, _)| label.clone()),
+                        raw,
+                    },
+                };
+                DecodedSignal {
+                    name: layout.name.to_owned(),
+                    value,
+                }
+            })
+            .collect();
+        let object_entry = match message.usage() {
+            MessageUsage::GetReq
+            | MessageUsage::GetResp
+            | MessageUsage::SetReq
+            | MessageUsage::SetResp => self.addressed_object_entry(&signals),
+            _ => None,
+        };
+        Some(DecodedFrame {
+            message: message.clone(),
+            signals,
+            object_entry,
+        })
+    }
+
+    fn addressed_object_entry(&self, signals: &[DecodedSignal]) -> Option<String> {
+        let unsigned = |suffix: &str| {
+            signals.iter().find_map(|signal| match signal.value {
+                SignalValue::Unsigned(value) if signal.name.ends_with(suffix) => Some(value),
+                _ => None,
+            })
+        };
+        let server_id = unsigned("server_id")?;
+        let od_index = unsigned("od_index")?;
+        let node = self
+            .network
+            .nodes()
+            .iter()
+            .find(|node| node.id() as u64 == server_id)?;
+        let object_entry = node.object_entry_by_index(od_index as u32)?;
+        Some(format!("{}::{}", node.name(), object_entry.name()))
+    }
+
+    /// Decodes a single line of a candump log, None if the frame is not part
+    /// of the network.
+    pub fn decode_line(&self, line: &str) -> Result<Option<DecodedFrame>> {
+        let frame = CandumpFrame::parse(line)?;
+        Ok(self.decode(&frame.bus, frame.id, &frame.data))
+    }
+
+    /// Decodes a candump log line by line, unknown frames and lines, which
+    /// can't be parsed, are passed through unchanged.
+    pub fn decode_log(&self, reader: impl BufRead, mut writer: impl Write) -> Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            match CandumpFrame::parse(&line) {
+                Ok(frame) => match self.decode(&frame.bus, frame.id, &frame.data) {
+                    Some(decoded) => match frame.timestamp {
+                        Some(timestamp) => writeln!(writer, "({timestamp:.6}) {} {decoded}", frame.bus)?,
+                        None => writeln!(writer, "{} {decoded}", frame.bus)?,
+                    },
+                    None => writeln!(writer, "{line}")?,
+                },
+                Err(_) => writeln!(writer, "{line}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub use self::candump::CandumpDecoder;
+pub use self::candump::CandumpFrame;
+pub use self::candump::DecodedFrame;
+pub use self::candump::DecodedSignal;
+pub use self::candump::SignalValue;
+
+pub mod candump;
@@ -0,0 +1,131 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{BitTimingConstraints, BitTimingOptions},
+    errors::ConfigError,
+};
+
+#[test]
+fn bxcan_1mbit() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_bus("can1", Some(500_000));
+    let network = network_builder.build().unwrap();
+    let can0 = network.buses().iter().find(|b| b.name() == "can0").unwrap();
+    let timing = can0.bit_timing(36_000_000).unwrap();
+    assert_eq!(timing.baudrate(36_000_000), 1_000_000.0);
+    assert!((timing.sample_point() - 0.875).abs() < 0.03);
+    assert!(timing.seg1 <= 16 && timing.seg2 <= 8 && timing.sjw <= 4);
+
+    let can1 = network.buses().iter().find(|b| b.name() == "can1").unwrap();
+    let timing = can1.bit_timing(80_000_000).unwrap();
+    assert_eq!(timing.baudrate(80_000_000), 500_000.0);
+    assert_eq!(timing.sample_point(), 0.875);
+}
+
+#[test]
+fn constrained_sample_point() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let network = network_builder.build().unwrap();
+    let can0 = &network.buses()[0];
+    let options = BitTimingOptions {
+        constraints: BitTimingConstraints::fdcan(),
+        sample_point: 0.75,
+        sample_point_tolerance: 0.0,
+        sjw: None,
+    };
+    let timing = can0.bit_timing_with(80_000_000, &options).unwrap();
+    assert_eq!(timing.sample_point(), 0.75);
+    assert_eq!(timing.prescaler, 1);
+    assert_eq!(timing.tq_per_bit(), 80);
+
+    // 1 Mbit/s can't be derived from 1.5 MHz.
+    assert!(can0.bit_timing(1_500_000).is_none());
+}
+
+#[test]
+fn configured_sample_point_and_sjw() {
+    let network_builder = NetworkBuilder::new();
+    let bus = network_builder.create_bus("can0", Some(500_000));
+    bus.set_sample_point(80.0);
+    bus.set_sjw(2);
+    let network = network_builder.build().unwrap();
+    let can0 = &network.buses()[0];
+    assert_eq!(can0.sample_point(), Some(80.0));
+    assert_eq!(can0.sjw(), Some(2));
+    let timing = can0.bit_timing(80_000_000).unwrap();
+    assert_eq!(timing.sample_point(), 0.8);
+    assert_eq!(timing.sjw, 2);
+
+    assert!(network.to_cpp().contains("static constexpr float sample_point = 0.8f;"));
+    assert!(network.to_python().contains("\"sample_point\": 0.8, \"sjw\": 2"));
+}
+
+#[test]
+fn invalid_sample_point() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None).set_sample_point(40.0);
+    assert!(network_builder.build().is_err());
+}
+
+#[test]
+fn baudrate_validation() {
+    // defaults to 1 Mbit/s.
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let network = network_builder.build().unwrap();
+    assert_eq!(network.buses()[0].baudrate(), 1_000_000);
+
+    // not a standard baudrate.
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(300_000));
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidBaudrate(_))
+    ));
+
+    // allowed by the hardware of the bus.
+    let network_builder = NetworkBuilder::new();
+    network_builder
+        .create_bus("can0", Some(300_000))
+        .set_allowed_baudrates(&[300_000, 600_000]);
+    assert!(network_builder.build().is_ok());
+
+    let network_builder = NetworkBuilder::new();
+    let bus = network_builder.create_bus("can0", None);
+    bus.set_allowed_baudrates(&[250_000]);
+    bus.set_baudrate(500_000);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidBaudrate(_))
+    ));
+}
+
+#[test]
+fn baudrate_controller_clock() {
+    let network_builder = NetworkBuilder::new();
+    let bus = network_builder.create_bus("can0", Some(1_000_000));
+    bus.set_controller_clock(36_000_000);
+    assert!(network_builder.build().is_ok());
+
+    let network_builder = NetworkBuilder::new();
+    let bus = network_builder.create_bus("can0", Some(1_000_000));
+    bus.set_controller_clock(1_500_000);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidBaudrate(_))
+    ));
+
+    // conflicting clocks of the buses of a node only warn.
+    let network_builder = NetworkBuilder::new();
+    network_builder
+        .create_bus("can0", Some(1_000_000))
+        .set_controller_clock(36_000_000);
+    network_builder
+        .create_bus("can1", Some(500_000))
+        .set_controller_clock(80_000_000);
+    let secu = network_builder.create_node("secu");
+    secu.assign_bus("can0");
+    secu.assign_bus("can1");
+    assert!(network_builder.build().is_ok());
+}
@@ -0,0 +1,76 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{ObjectEntryAccess, BROADCAST_SERVER_ID},
+    errors::ConfigError,
+};
+
+#[test]
+fn node_groups_and_broadcast_entries() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let motor = network_builder.create_node("motor");
+    network_builder.create_node("master");
+    secu.create_object_entry("safe_state", "u1").set_broadcast(true);
+    secu.create_object_entry("temperature", "u8");
+    motor.create_object_entry("safe_state", "u1").set_broadcast(true);
+
+    let drive = network_builder.create_group("drive");
+    drive.add_node("secu");
+    drive.add_node("motor");
+    drive.add_node("secu");
+    let sensors = network_builder.create_group("sensors");
+    sensors.add_node("secu");
+    // defining a group again returns the existing group.
+    network_builder.create_group("drive").add_node("motor");
+
+    let network = network_builder.build().unwrap();
+    let groups = network.node_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name(), "drive");
+    assert_eq!(groups[0].server_id(), BROADCAST_SERVER_ID - 1);
+    assert_eq!(groups[0].nodes().len(), 2);
+    assert_eq!(groups[1].server_id(), BROADCAST_SERVER_ID - 2);
+
+    let secu = network.node_by_name("secu").unwrap();
+    assert_eq!(
+        network.server_ids(secu),
+        vec![secu.id(), BROADCAST_SERVER_ID - 1, BROADCAST_SERVER_ID - 2, BROADCAST_SERVER_ID]
+    );
+    let master = network.node_by_name("master").unwrap();
+    assert_eq!(network.server_ids(master), vec![master.id(), BROADCAST_SERVER_ID]);
+
+    let broadcast: Vec<&str> = secu
+        .object_entries()
+        .iter()
+        .filter(|oe| oe.broadcast())
+        .map(|oe| oe.name())
+        .collect();
+    assert_eq!(broadcast, vec!["safe_state"]);
+}
+
+#[test]
+fn broadcast_requires_global_write_access() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let safe_state = secu.create_object_entry("safe_state", "u1");
+    safe_state.set_broadcast(true);
+    safe_state.set_access(ObjectEntryAccess::Local);
+    match network_builder.build() {
+        Err(ConfigError::InvalidBroadcast(msg)) => assert!(msg.contains("secu::safe_state"), "{msg}"),
+        other => panic!("expected InvalidBroadcast, got {other:?}"),
+    }
+}
+
+#[test]
+fn groups_change_the_config_hash() {
+    let build = |with_group: bool| {
+        let network_builder = NetworkBuilder::new();
+        network_builder.create_node("secu");
+        network_builder.set_build_time(chrono::DateTime::default());
+        if with_group {
+            network_builder.create_group("all").add_node("secu");
+        }
+        network_builder.build().unwrap().config_hash()
+    };
+    assert_ne!(build(false), build(true));
+}
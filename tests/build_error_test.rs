@@ -0,0 +1,150 @@
+use canzero_config::{
+    builder::{bus::BusBuilder, MessageBuilder, NetworkBuilder},
+    errors::ConfigError,
+};
+
+#[test]
+fn unregistered_message() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    // created, but never added to the network.
+    let ghost = MessageBuilder::new("ghost", &network_builder, None);
+    secu.add_tx_message(&ghost);
+    match network_builder.build() {
+        Err(ConfigError::MessageNotRegistered(msg)) => {
+            assert!(msg.contains("ghost") && msg.contains("secu"), "{msg}")
+        }
+        other => panic!("expected MessageNotRegistered, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_stream_entry() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "u8");
+    secu.create_stream("state").add_entry("temperature");
+    secu.0.borrow_mut().object_entries.clear();
+    match network_builder.build() {
+        Err(ConfigError::StreamEntryMissing(msg)) => {
+            assert!(msg.contains("temperature") && msg.contains("secu::state"), "{msg}")
+        }
+        other => panic!("expected StreamEntryMissing, got {other:?}"),
+    }
+}
+
+#[test]
+fn missing_bus() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.0
+        .borrow_mut()
+        .buses
+        .push(BusBuilder::new("ghost_bus", 42, None));
+    match network_builder.build() {
+        Err(ConfigError::BusNotFound(msg)) => assert!(msg.contains("ghost_bus"), "{msg}"),
+        other => panic!("expected BusNotFound, got {other:?}"),
+    }
+}
+
+fn assert_invalid_name(network_builder: NetworkBuilder, name: &str) {
+    match network_builder.build() {
+        Err(ConfigError::InvalidName(msg)) => assert!(msg.contains(name), "{msg}"),
+        other => panic!("expected InvalidName, got {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_node_name() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("my-node");
+    assert_invalid_name(network_builder, "my-node");
+}
+
+#[test]
+fn invalid_node_element_names() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("static", "u8");
+    assert_invalid_name(network_builder, "static");
+
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "u8");
+    secu.create_stream("2nd_state").add_entry("temperature");
+    assert_invalid_name(network_builder, "2nd_state");
+
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_command("do it", None);
+    assert_invalid_name(network_builder, "do it");
+}
+
+#[test]
+fn invalid_bus_and_message_names() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can.0", None);
+    assert_invalid_name(network_builder, "can.0");
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let message = network_builder.create_message("new", None);
+    message.add_transmitter("secu");
+    assert_invalid_name(network_builder, "new");
+}
+
+#[test]
+fn invalid_type_names() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_struct("union").add_attribute("a", "u8").unwrap();
+    network_builder.create_node("secu").create_object_entry("x", "union");
+    assert_invalid_name(network_builder, "union");
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_struct("point").add_attribute("x-y", "u8").unwrap();
+    network_builder.create_node("secu").create_object_entry("x", "point");
+    assert_invalid_name(network_builder, "x-y");
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_enum("my enum").add_entry("a", None).unwrap();
+    network_builder.create_node("secu").create_object_entry("x", "my enum");
+    assert_invalid_name(network_builder, "my enum");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn invalid_name_from_json() {
+    let network_builder = NetworkBuilder::from_json(
+        r#"{ "schema_version": 1, "nodes": [{ "name": "sensor-ecu" }] }"#,
+    )
+    .unwrap();
+    assert_invalid_name(network_builder, "sensor-ecu");
+}
+
+fn assert_invalid_type(network_builder: NetworkBuilder, name: &str) {
+    match network_builder.build() {
+        Err(ConfigError::InvalidType(msg)) => assert!(msg.contains(name), "{msg}"),
+        other => panic!("expected InvalidType for {name}, got {other:?}"),
+    }
+}
+
+#[test]
+fn array_types() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let message = network_builder.create_message("raw", None);
+    message.make_type_format().add_type("u8[4]", "x");
+    secu.add_tx_message(&message);
+    assert_invalid_type(network_builder, "u8[4]");
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu").create_object_entry("x", "u8[4]");
+    assert_invalid_type(network_builder, "u8[4]");
+
+    // arrays nested in structs are rejected as well.
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_struct("inner").add_attribute("a", "u8[2]").unwrap();
+    network_builder.define_struct("outer").add_attribute("b", "inner").unwrap();
+    network_builder.create_node("secu").create_object_entry("x", "outer");
+    assert_invalid_type(network_builder, "outer");
+}
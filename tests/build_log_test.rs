@@ -0,0 +1,59 @@
+use std::sync::Mutex;
+
+use canzero_config::builder::{NetworkBuilder, BUILD_LOG_TARGET};
+
+struct CaptureLogger(Mutex<Vec<(log::Level, String, String)>>);
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        self.0.lock().unwrap().push((
+            record.level(),
+            record.target().to_owned(),
+            record.args().to_string(),
+        ));
+    }
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(vec![]));
+
+#[test]
+fn build_logs_phases_and_warnings() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let network_builder = NetworkBuilder::new();
+    // no baudrate is declared.
+    network_builder.create_bus("can0", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    network_builder.build().unwrap();
+
+    let records = LOGGER.0.lock().unwrap();
+    let phases: Vec<&str> = records
+        .iter()
+        .filter(|(_, target, message)| target == BUILD_LOG_TARGET && message.starts_with("begin "))
+        .map(|(_, _, message)| &message["begin ".len()..])
+        .collect();
+    assert_eq!(
+        phases,
+        [
+            "buses",
+            "type resolution",
+            "id assignment",
+            "message assembly",
+            "node assembly",
+            "validation"
+        ]
+    );
+    for phase in phases {
+        assert!(records
+            .iter()
+            .any(|(_, _, message)| message.starts_with(&format!("end {phase} after "))));
+    }
+    assert!(records.iter().any(|(level, _, message)| *level == log::Level::Warn
+        && message.contains("bus can0 doesn't declare a baudrate")));
+}
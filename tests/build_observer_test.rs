@@ -0,0 +1,84 @@
+use canzero_config::{
+    builder::{BuildObserver, BuildPhase, NetworkBuilder},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_stream("state").add_entry("position");
+    network_builder.create_node("master").receive_stream("secu", "state");
+    network_builder
+}
+
+#[derive(Default)]
+struct Recorder {
+    phases: Vec<BuildPhase>,
+    progress: Vec<u8>,
+    cancel_at: Option<BuildPhase>,
+}
+
+impl BuildObserver for Recorder {
+    fn on_phase(&mut self, phase: BuildPhase) {
+        self.phases.push(phase);
+    }
+    fn on_progress(&mut self, percent: u8) {
+        self.progress.push(percent);
+    }
+    fn is_cancelled(&self) -> bool {
+        self.cancel_at.is_some() && self.phases.last() == self.cancel_at.as_ref()
+    }
+}
+
+#[test]
+fn observer_reports_phases_and_progress() {
+    let mut recorder = Recorder::default();
+    network_builder().build_with_observer(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.phases,
+        [
+            BuildPhase::Buses,
+            BuildPhase::TypeResolution,
+            BuildPhase::IdAssignment,
+            BuildPhase::MessageAssembly,
+            BuildPhase::NodeAssembly,
+            BuildPhase::Validation
+        ]
+    );
+    assert!(recorder.progress.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(recorder.progress.first(), Some(&0));
+    assert_eq!(recorder.progress.last(), Some(&100));
+}
+
+#[test]
+fn observer_cancels_build() {
+    let mut recorder = Recorder {
+        cancel_at: Some(BuildPhase::MessageAssembly),
+        ..Default::default()
+    };
+    match network_builder().build_with_observer(&mut recorder) {
+        Err(ConfigError::BuildCancelled(reason)) => assert!(reason.contains("message assembly")),
+        other => panic!("expected BuildCancelled, got {other:?}"),
+    }
+    assert_eq!(recorder.phases.last(), Some(&BuildPhase::MessageAssembly));
+    assert!(!recorder.progress.contains(&100));
+}
+
+#[test]
+fn id_assignment_reports_progress_and_can_be_cancelled() {
+    let mut recorder = Recorder::default();
+    network_builder().build_with_observer(&mut recorder).unwrap();
+    assert!(recorder.progress.iter().any(|percent| (11..60).contains(percent)));
+
+    let mut recorder = Recorder {
+        cancel_at: Some(BuildPhase::IdAssignment),
+        ..Default::default()
+    };
+    match network_builder().build_with_observer(&mut recorder) {
+        Err(ConfigError::BuildCancelled(reason)) => assert!(reason.contains("id assignment"), "{reason}"),
+        other => panic!("expected BuildCancelled, got {other:?}"),
+    }
+    assert_eq!(recorder.phases.last(), Some(&BuildPhase::IdAssignment));
+}
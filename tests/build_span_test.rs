@@ -0,0 +1,71 @@
+#![cfg(feature = "logging-info")]
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use canzero_config::builder::{NetworkBuilder, BUILD_LOG_TARGET};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+/// Records the name and the field of all spans of the build.
+#[derive(Default)]
+struct SpanRecorder(Mutex<Vec<String>>);
+
+struct FieldValue(String);
+
+impl Visit for FieldValue {
+    fn record_str(&mut self, _: &Field, value: &str) {
+        self.0 = value.to_owned();
+    }
+    fn record_debug(&mut self, _: &Field, value: &dyn Debug) {
+        self.0 = format!("{value:?}");
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == BUILD_LOG_TARGET
+    }
+    fn new_span(&self, span: &Attributes) -> Id {
+        let mut value = FieldValue(String::new());
+        span.record(&mut value);
+        let mut spans = self.0.lock().unwrap();
+        spans.push(format!("{} {}", span.metadata().name(), value.0));
+        Id::from_u64(spans.len() as u64)
+    }
+    fn record(&self, _: &Id, _: &Record) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, _: &Event) {}
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn build_spans_of_phases_and_steps() {
+    let recorder = Arc::new(SpanRecorder::default());
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let network_builder = NetworkBuilder::new();
+        let secu = network_builder.create_node("secu");
+        secu.create_object_entry("position", "u16");
+        network_builder.build().unwrap();
+    });
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        [
+            "build_phase buses",
+            "build_phase type resolution",
+            "build_phase id assignment",
+            "build_step j1939 ids",
+            "build_step resolve_ids_filters_and_buses",
+            "build_phase message assembly",
+            "build_step assemble_messages",
+            "build_phase node assembly",
+            "build_phase validation",
+        ]
+    );
+}
@@ -0,0 +1,75 @@
+use canzero_config::builder::NetworkBuilder;
+
+fn network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let mode = network_builder.define_enum("mode");
+    mode.add_entry("off", None).unwrap();
+    mode.add_entry("on", None).unwrap();
+    let status = network_builder.define_struct("status");
+    status.add_attribute("state", "state").unwrap();
+    status.add_attribute("temperature", "d8<0..100>").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    secu.create_object_entry("status", "status");
+    let stream = secu.create_stream("state");
+    stream.add_entry("state");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+    let dyno = network_builder.create_message("dyno_torque", None);
+    dyno.set_std_id(0x50);
+    dyno.make_type_format().add_type("u16", "torque");
+    dyno.add_transmitter("master");
+    dyno.add_receiver("secu");
+    network_builder
+}
+
+#[test]
+fn remove_message() {
+    let network_builder = network();
+    network_builder.remove_message("dyno_torque").unwrap();
+    network_builder.remove_message("secu_stream_state").unwrap();
+    assert!(network_builder.remove_message("dyno_torque").is_err());
+    let network = network_builder.build().unwrap();
+    assert!(!network.messages().iter().any(|m| m.name() == "dyno_torque"));
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert!(secu.tx_streams().is_empty());
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert!(master.rx_streams().is_empty());
+}
+
+#[test]
+fn rename_node_and_message() {
+    let network_builder = network();
+    assert!(network_builder.rename_node("secu", "master").is_err());
+    assert!(network_builder.rename_node("pdu", "pdu12").is_err());
+    network_builder.rename_node("secu", "sensor").unwrap();
+    network_builder.rename_message("dyno_torque", "torque").unwrap();
+    assert!(network_builder.rename_message("torque", "sensor_stream_state").is_err());
+    let network = network_builder.build().unwrap();
+    assert!(network.nodes().iter().any(|n| n.name() == "sensor"));
+    assert!(!network.nodes().iter().any(|n| n.name() == "secu"));
+    assert!(network.messages().iter().any(|m| m.name() == "sensor_stream_state"));
+    assert!(network.messages().iter().any(|m| m.name() == "torque"));
+}
+
+#[test]
+fn rename_and_replace_type() {
+    let network_builder = network();
+    assert!(network_builder.rename_type("state", "mode").is_err());
+    network_builder.rename_type("state", "secu_state").unwrap();
+    let built = network_builder.build().unwrap();
+    assert!(built.types().iter().any(|t| t.name() == "secu_state"));
+    assert!(!built.types().iter().any(|t| t.name() == "state"));
+
+    let network_builder = network();
+    network_builder.replace_type("state", "mode").unwrap();
+    assert!(network_builder.replace_type("state", "mode").is_err());
+    let built = network_builder.build().unwrap();
+    assert!(!built.types().iter().any(|t| t.name() == "state"));
+    let secu = built.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let state = secu.object_entries().iter().find(|oe| oe.name() == "state").unwrap();
+    assert_eq!(state.ty().name(), "mode");
+}
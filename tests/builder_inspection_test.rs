@@ -0,0 +1,37 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn inspect_builder() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    network_builder.create_bus("can0", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    let stream = secu.create_stream("state");
+    stream.add_entry("state");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+
+    assert!(network_builder.find_node("secu").is_some());
+    assert!(network_builder.find_node("pdu").is_none());
+    assert!(network_builder.find_type("state").is_some());
+    assert!(network_builder.find_bus("can0").is_some());
+
+    let secu = network_builder.find_node("secu").unwrap();
+    let state = secu.find_object_entry("state").unwrap();
+    assert_eq!(state.ty(), "state");
+    assert!(secu.object_entries().iter().any(|oe| oe.name() == "config_hash"));
+
+    let stream = secu.find_stream("state").unwrap();
+    let message = network_builder
+        .find_message(&stream.message().name())
+        .unwrap();
+    assert_eq!(message.name(), "secu_stream_state");
+    assert!(message.transmitters().iter().any(|n| n.name() == "secu"));
+    assert!(message.receivers().iter().any(|n| n.name() == "master"));
+    assert_eq!(master.rx_streams().len(), 1);
+
+    // inspecting doesn't prevent building.
+    network_builder.build().unwrap();
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "tools")]
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{Message, MessageId, NetworkRef},
+    tools::{CandumpDecoder, CandumpFrame, SignalValue},
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let state = network_builder.define_enum("secu_state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "secu_state");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    let stream = secu.create_stream("status");
+    stream.add_entry("state");
+    stream.add_entry("temperature");
+    network_builder.create_node("master");
+    network_builder.build().unwrap()
+}
+
+/// Payload with the given raw signal values.
+fn encode(message: &Message, values: &[(&str, u64)]) -> Vec<u8> {
+    let mut raw: u64 = 0;
+    for (name, value) in values {
+        let signal = message
+            .signals()
+            .iter()
+            .find(|s| s.name().ends_with(name))
+            .unwrap();
+        raw |= value << signal.byte_offset();
+    }
+    raw.to_le_bytes()[..message.dlc() as usize].to_vec()
+}
+
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+#[test]
+fn parse_lines() {
+    let frame = CandumpFrame::parse("  can0  123   [2]  11 22").unwrap();
+    assert_eq!(frame.bus, "can0");
+    assert_eq!(frame.id, MessageId::StandardId(0x123));
+    assert_eq!(frame.data, vec![0x11, 0x22]);
+    assert_eq!(frame.timestamp, None);
+
+    let frame = CandumpFrame::parse("(1700000000.250000) vcan0 18FF0001#AABB").unwrap();
+    assert_eq!(frame.timestamp, Some(1700000000.25));
+    assert_eq!(frame.id, MessageId::ExtendedId(0x18FF0001));
+    assert_eq!(frame.data, vec![0xAA, 0xBB]);
+
+    assert!(CandumpFrame::parse("can0 123#R").is_err());
+    assert!(CandumpFrame::parse("can0 123 [3] 11 22").is_err());
+}
+
+#[test]
+fn decode_stream() {
+    let network = network();
+    let message = network.message_by_name("secu_stream_status").unwrap();
+    let data = encode(message, &[("state", 1), ("temperature", 255)]);
+    let line = format!("can0 {:03X}#{}", message.id().as_u32(), hex(&data));
+
+    let decoder = CandumpDecoder::new(&network);
+    let decoded = decoder.decode_line(&line).unwrap().unwrap();
+    assert_eq!(decoded.message.name(), "secu_stream_status");
+    let state = decoded
+        .signals
+        .iter()
+        .find(|s| s.name.ends_with("state"))
+        .unwrap();
+    assert_eq!(
+        state.value,
+        SignalValue::Enum { label: Some("running".to_owned()), raw: 1 }
+    );
+    let temperature = decoded
+        .signals
+        .iter()
+        .find(|s| s.name.ends_with("temperature"))
+        .unwrap();
+    match temperature.value {
+        SignalValue::Decimal(value) => assert!((value - 100.0).abs() < 1e-9),
+        ref other => panic!("{other:?}"),
+    }
+    assert!(decoded.to_string().contains("=running"));
+}
+
+#[test]
+fn decode_object_dictionary_access() {
+    let network = network();
+    let secu = network.node_by_name("secu").unwrap();
+    let temperature = secu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "temperature")
+        .unwrap();
+    let get_req = network.get_req_message();
+    let data = encode(
+        get_req,
+        &[("od_index", temperature.id() as u64), ("server_id", secu.id() as u64)],
+    );
+    let decoder = CandumpDecoder::new(&network);
+    let decoded = decoder
+        .decode(get_req.bus().name(), *get_req.id(), &data)
+        .unwrap();
+    assert_eq!(decoded.object_entry.as_deref(), Some("secu::temperature"));
+}
+
+#[test]
+fn unknown_frames_pass_through() {
+    let network = network();
+    let decoder = CandumpDecoder::new(&network);
+    let log = "can0 7FF#00\nnot a frame\n";
+    let mut output = vec![];
+    decoder.decode_log(log.as_bytes(), &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), log);
+}
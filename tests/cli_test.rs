@@ -0,0 +1,63 @@
+#![cfg(feature = "cli")]
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    cli::{run, Artifact, CliOptions},
+};
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn cli_arguments() {
+    let options = CliOptions::parse(args(&[
+        "network.json",
+        "--emit",
+        "dbc=out.dbc",
+        "--emit",
+        "hash",
+        "--deny-warnings",
+        "--verbose",
+    ]))
+    .unwrap();
+    assert_eq!(options.input(), Some("network.json"));
+    assert!(options.deny_warnings());
+    assert!(options.verbose());
+    assert!(!CliOptions::parse(args(&["network.json"])).unwrap().verbose());
+    assert_eq!(
+        options.emit(),
+        &vec![
+            (Artifact::Dbc, Some("out.dbc".to_owned())),
+            (Artifact::Hash, None)
+        ]
+    );
+    assert_eq!(
+        CliOptions::parse(args(&["--emit", "c", "--emit", "docs"])).unwrap().emit(),
+        &vec![(Artifact::C, None), (Artifact::Docs, None)]
+    );
+    assert!(CliOptions::parse(args(&["--emit", "pdf"])).is_err());
+    assert!(CliOptions::parse(args(&["--emit"])).is_err());
+    assert!(CliOptions::parse(args(&["a.json", "b.json"])).is_err());
+}
+
+#[test]
+fn cli_emits_artifacts() {
+    let dir = std::env::temp_dir().join(format!("canzero_cli_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dbc = dir.join("network.dbc");
+    let hash = dir.join("network.hash");
+    let options = CliOptions::parse(args(&[
+        "--emit",
+        &format!("dbc={}", dbc.display()),
+        "--emit",
+        &format!("hash={}", hash.display()),
+    ]))
+    .unwrap();
+    let network_builder =
+        NetworkBuilder::from_json(r#"{ "nodes": [{ "name": "secu" }] }"#).unwrap();
+    assert!(run(&options, network_builder).unwrap());
+    assert!(std::fs::read_to_string(&dbc).unwrap().contains("BU_: secu"));
+    assert_eq!(std::fs::read_to_string(&hash).unwrap().trim().len(), 16);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,224 @@
+use std::fmt::Write;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    codegen::{CodegenBackend, CodegenContext},
+    config::{MessageRef, NetworkRef, OverflowPolicy, SignalRef, TypeRef},
+    errors::ConfigError,
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let state = network_builder.define_enum("secu_state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Running", None).unwrap();
+    let info = network_builder.define_struct("secu_info");
+    info.add_attribute("state", "secu_state").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("info", "secu_info");
+    secu.create_object_entry("temperature", "d8<-10..100>");
+    secu.create_object_entry("state", "secu_state");
+    secu.create_object_entry("offset", "i16");
+    let stream = secu.create_stream("status");
+    stream.add_entry("temperature");
+    stream.add_entry("state");
+    stream.add_entry("offset");
+    stream.set_priority(MessagePriority::Low);
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "status");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn python_module() {
+    let network = network();
+    let py = network.to_python();
+    assert!(py.contains("class SecuState(IntEnum):\n    Idle = 0\n    Running = 1\n"));
+    assert!(py.contains("class SecuStreamStatus:"));
+    assert!(py.contains("def encode(self) -> bytes:"));
+    assert!(py.contains("value_name_secu_state: SecuState = SecuState.Idle"));
+    assert!(py.contains("_unpack(raw, 9, 16, signed=True)"));
+    for message in network.messages() {
+        assert!(py.contains(&format!("0x{:X}): ", message.id().as_u32())));
+    }
+}
+
+#[test]
+fn cpp_header() {
+    let network = network();
+    let cpp = network.to_cpp();
+    assert!(cpp.contains("enum class SecuState : uint8_t {\n  Idle = 0,\n  Running = 1,\n};"));
+    assert!(cpp.contains("struct SecuStreamStatus {"));
+    assert!(cpp.contains("SecuState value_name_secu_state = SecuState::Idle;"));
+    assert!(cpp.contains("int16_t value_name_offset = {};"));
+    assert!(cpp.contains("std::array<uint8_t, dlc> encode() const {"));
+    assert!(cpp.contains("detail::sign_extend(detail::unpack(raw, 9, 16), 16)"));
+}
+
+#[test]
+fn c_header() {
+    let network = network();
+    let c = network.to_c();
+    assert!(c.contains("typedef enum {\n  CANZERO_SECU_STATE_IDLE = 0,\n  CANZERO_SECU_STATE_RUNNING = 1,\n} canzero_secu_state;"));
+    assert!(c.contains("} canzero_secu_stream_status;"));
+    assert!(c.contains("  canzero_secu_state value_name_secu_state;"));
+    assert!(c.contains("  int16_t value_name_offset;"));
+    assert!(c.contains("static inline void canzero_secu_stream_status_encode(const canzero_secu_stream_status* message, uint8_t* data) {"));
+    assert!(c.contains("(int16_t)canzero_sign_extend(canzero_unpack(raw, 9, 16), 16)"));
+    let message = network.message_by_name("secu_stream_status").unwrap();
+    assert!(c.contains(&format!("#define CANZERO_SECU_STREAM_STATUS_ID 0x{:X}U", message.id().as_u32())));
+}
+
+#[test]
+fn overflow_policy() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("current", "d8<0..10>")
+        .set_overflow_policy(OverflowPolicy::Error);
+    secu.create_object_entry("counter", "u4")
+        .set_overflow_policy(OverflowPolicy::Wrap);
+    let level = secu.create_object_entry("level", "i5");
+    // not representable by i5, only emits a warning.
+    level.set_range(-20.0, 10.0);
+    let stream = secu.create_stream("status");
+    stream.add_entry("current");
+    stream.add_entry("counter");
+    stream.add_entry("level");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "status");
+    let network = network_builder.build().unwrap();
+
+    let message = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "secu_stream_status")
+        .unwrap();
+    let level = network.nodes()[0].object_entries().iter().find(|oe| oe.name() == "level").unwrap();
+    assert_eq!(level.range(), Some((-20.0, 10.0)));
+    let policies: Vec<OverflowPolicy> = message.signals().iter().map(|s| s.overflow()).collect();
+    assert_eq!(
+        policies,
+        vec![OverflowPolicy::Error, OverflowPolicy::Wrap, OverflowPolicy::Saturate]
+    );
+    assert_eq!(message.signals()[0].physical_to_raw(11.0), None);
+
+    let py = network.to_python();
+    assert!(py.contains("_check(self.value_name_current, 0.0, 10.0, \"value_name_current\")"));
+    assert!(py.contains("raw |= _pack(self.value_name_counter, 8, 4)"));
+    assert!(py.contains("_clamp(self.value_name_level, -16, 15)"));
+
+    let cpp = network.to_cpp();
+    assert!(cpp.contains("valid = valid && value_name_current >= 0.0 && value_name_current <= 10.0;"));
+    assert!(cpp.contains("detail::saturate<int8_t>(value_name_level, -16, 15)"));
+}
+
+#[derive(Default)]
+struct ListingBackend {
+    types: Vec<String>,
+    messages: usize,
+    signals: usize,
+}
+
+impl CodegenBackend for ListingBackend {
+    fn visit_type(&mut self, _ctx: &mut CodegenContext, ty: &TypeRef) {
+        self.types.push(ty.name());
+    }
+    fn visit_message(&mut self, ctx: &mut CodegenContext, message: &MessageRef) {
+        self.messages += 1;
+        writeln!(ctx, "{}", message.name()).unwrap();
+    }
+    fn visit_signal(&mut self, _ctx: &mut CodegenContext, _message: &MessageRef, _signal: &SignalRef) {
+        self.signals += 1;
+    }
+}
+
+#[test]
+fn custom_backend() {
+    let network = network();
+    let mut backend = ListingBackend::default();
+    let output = network.generate(&mut backend);
+    assert_eq!(backend.messages, network.messages().len());
+    assert_eq!(
+        backend.signals,
+        network.messages().iter().map(|m| m.signals().len()).sum::<usize>()
+    );
+    assert_eq!(output.lines().count(), network.messages().len());
+    // types are visited after the types they depend on.
+    let position = |name: &str| backend.types.iter().position(|t| t == name).unwrap();
+    assert!(position("secu_state") < position("secu_info"));
+}
+
+#[test]
+fn simulation_stub() {
+    let network = network();
+    let secu = network.node_by_name("secu").unwrap();
+    let sim = network.to_sim(secu);
+    assert!(sim.contains("pub trait CanBus {"));
+    assert!(sim.contains("pub struct SecuSim {"));
+    assert!(sim.contains(&format!("pub const NODE_ID: u8 = {};", secu.id())));
+    assert!(sim.contains("pub fn on_frame(&mut self"));
+    for oe in secu.object_entries() {
+        assert!(sim.contains(&format!("index: {}, name: {:?}", oe.id(), oe.name())));
+    }
+    // one stream with the temperature, state and offset entries.
+    assert!(sim.contains("const STREAMS: [Stream; 1] = ["));
+    // the physical temperature 0 is encoded as round(10 / (110 / 255)).
+    assert!(sim.contains("name: \"temperature\", writable: true, size: 8, value: 23 }"));
+    let heartbeat = network.heartbeat_message();
+    assert!(sim.contains(&format!("const HEARTBEAT: Frame = Frame {{ bus: \"can0\", id: 0x{:X}", heartbeat.id().as_u32())));
+}
+
+#[test]
+fn simulation_error_codes() {
+    let network = network();
+    let secu = network.node_by_name("secu").unwrap();
+    let sim = network.to_sim(secu);
+    assert!(sim.contains("const INVALID_INDEX: u64 = 2;"));
+    assert!(sim.contains("const ACCESS_DENIED: u64 = 3;"));
+    // the config hash is const and can't be written by set requests.
+    assert!(sim.contains("name: \"config_hash\", writable: false"));
+    assert!(sim.contains("Some(_) => Self::ACCESS_DENIED"));
+    assert!(sim.contains("None => Self::INVALID_INDEX"));
+}
+
+#[test]
+fn enum_entry_documentation() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("pdu_state");
+    state.add_description("state of the power distribution");
+    state.add_entry("Init", None).unwrap();
+    state.add_entry("Charging", None).unwrap();
+    state.add_entry("Ready", None).unwrap();
+    state.add_entry_description("Init", "waiting for the precharge").unwrap();
+    state.deprecate_entry("Charging").unwrap();
+    state.add_entry_description("Charging", "replaced by Init").unwrap();
+    assert!(matches!(
+        state.deprecate_entry("Error"),
+        Err(ConfigError::UndefinedEnumEntry(_))
+    ));
+    let pdu = network_builder.create_node("pdu");
+    pdu.create_object_entry("state", "pdu_state");
+    let network = network_builder.build().unwrap();
+
+    let ty = network.types().iter().find(|ty| ty.name() == "pdu_state").unwrap();
+    let init = ty.enum_entry_info("Init").unwrap();
+    assert_eq!(init.description(), Some("waiting for the precharge"));
+    assert!(!init.is_deprecated());
+    assert!(ty.enum_entry_info("Charging").unwrap().is_deprecated());
+    assert_eq!(ty.enum_entry_info("Ready").unwrap().description(), None);
+    assert!(ty.enum_entry_info("Error").is_none());
+
+    let cpp = network.to_cpp();
+    assert!(cpp.contains(
+        "/// state of the power distribution\nenum class PduState : uint8_t {\n  /// waiting for the precharge\n  Init = 0,\n  /// replaced by Init\n  /// @deprecated\n  Charging = 1,\n  Ready = 2,\n};"
+    ));
+    let py = network.to_python();
+    assert!(py.contains(
+        "    Init = 0  # waiting for the precharge\n    Charging = 1  # replaced by Init (deprecated)\n    Ready = 2\n"
+    ));
+    assert!(network
+        .to_canonical_string()
+        .contains("  entry Charging=1 deprecated description=\"replaced by Init\"\n"));
+}
@@ -0,0 +1,59 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+#[test]
+fn command_with_multiple_responders() {
+    let network_builder = NetworkBuilder::new();
+    let brake_front = network_builder.create_node("brake_front");
+    network_builder.create_node("brake_rear");
+    network_builder.create_node("brake_spare");
+    let brakes = network_builder.create_group("brakes");
+    brakes.add_node("brake_rear");
+    brakes.add_node("brake_spare");
+    let arm = brake_front.create_command("arm", None);
+    arm.add_argument("force", "u8");
+    arm.add_callee("master");
+    arm.add_responder("brake_rear");
+    arm.add_responder("brake_front");
+    arm.add_responder_group("brakes");
+
+    let network = network_builder.build().unwrap();
+    let brake_front = network.node_by_name("brake_front").unwrap();
+    let arm = &brake_front.commands()[0];
+    assert_eq!(arm.responders(), ["brake_front", "brake_rear", "brake_spare"]);
+    assert_eq!(arm.expected_responses(), 3);
+    let responder_id = arm.responder_id().unwrap();
+    assert!(arm
+        .rx_message()
+        .signals()
+        .iter()
+        .any(|signal| signal.name() == responder_id.name()));
+
+    // every responder receives the call and transmits a response with the same id.
+    for name in ["brake_front", "brake_rear", "brake_spare"] {
+        let node = network.node_by_name(name).unwrap();
+        assert!(node.rx_messages().iter().any(|m| m.id() == arm.tx_message().id()));
+        assert!(node.tx_messages().iter().any(|m| m.id() == arm.rx_message().id()));
+    }
+    let master = network.node_by_name("master").unwrap();
+    assert!(master.rx_messages().iter().any(|m| m.id() == arm.rx_message().id()));
+}
+
+#[test]
+fn point_to_point_command() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_command("reset", None).add_callee("master");
+    let network = network_builder.build().unwrap();
+    let reset = &network.node_by_name("secu").unwrap().commands()[0];
+    assert_eq!(reset.responders(), ["secu"]);
+    assert_eq!(reset.expected_responses(), 1);
+    assert!(reset.responder_id().is_none());
+}
+
+#[test]
+fn undefined_responder_group() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_command("reset", None).add_responder_group("brakes");
+    assert!(matches!(network_builder.build(), Err(ConfigError::UndefinedGroup(_))));
+}
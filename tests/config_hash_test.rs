@@ -0,0 +1,91 @@
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::SignalType,
+};
+
+fn network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<-10..100>");
+    let stream = secu.create_stream("temperatures");
+    stream.add_entry("temperature");
+    stream.set_priority(MessagePriority::Low);
+    network_builder.create_node("master");
+    network_builder
+}
+
+#[test]
+fn config_hash_is_deterministic() {
+    let a = network().build().unwrap();
+    let b = network().build().unwrap();
+    assert_eq!(a.config_hash(), b.config_hash());
+    for (na, nb) in std::iter::zip(a.nodes(), b.nodes()) {
+        assert_eq!(na.config_hash(), nb.config_hash());
+    }
+}
+
+#[test]
+fn config_hash_detects_decimal_range_changes() {
+    let a = network().build().unwrap();
+    let other = NetworkBuilder::new();
+    let secu = other.create_node("secu");
+    secu.create_object_entry("temperature", "d8<-20..100>");
+    let stream = secu.create_stream("temperatures");
+    stream.add_entry("temperature");
+    stream.set_priority(MessagePriority::Low);
+    other.create_node("master");
+    let b = other.build().unwrap();
+    assert_ne!(a.config_hash(), b.config_hash());
+}
+
+#[test]
+fn injected_build_time_makes_builds_reproducible() {
+    let build_time = chrono::DateTime::parse_from_rfc3339("2024-01-01T12:00:00+00:00")
+        .unwrap()
+        .with_timezone(&chrono::Local);
+    let a = network();
+    a.set_build_time(build_time);
+    let b = network();
+    b.set_build_time(build_time);
+    let a = a.build().unwrap();
+    let b = b.build().unwrap();
+    assert_eq!(a.build_time(), &build_time);
+    assert_eq!(format!("{a}"), format!("{b}"));
+}
+
+#[test]
+fn equality_compares_the_configuration() {
+    let a = network().build().unwrap();
+    assert_eq!(*a, *network().build().unwrap());
+
+    // descriptions aren't part of the config hash, but of the configuration.
+    let other = network();
+    other
+        .find_node("secu")
+        .unwrap()
+        .create_object_entry("temperature", "d8<-10..100>")
+        .add_description("temperature of the cooling cycle");
+    let b = other.build().unwrap();
+    assert_eq!(a.config_hash(), b.config_hash());
+    assert_ne!(*a, *b);
+}
+
+#[test]
+fn equal_signal_types_have_equal_hashes() {
+    fn hash(ty: &SignalType) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        ty.hash(&mut hasher);
+        hasher.finish()
+    }
+    let decimal = |offset: f64, scale: f64| SignalType::Decimal { size: 8, offset, scale };
+    let nan = decimal(f64::NAN, 1.0);
+    assert_eq!(nan, nan.clone());
+    assert_eq!(hash(&nan), hash(&nan.clone()));
+    // 0.0 == -0.0, but they are hashed differently.
+    assert_ne!(decimal(0.0, 1.0), decimal(-0.0, 1.0));
+    assert_ne!(hash(&decimal(0.0, 1.0)), hash(&decimal(-0.0, 1.0)));
+    assert_eq!(decimal(-10.0, 0.5), decimal(-10.0, 0.5));
+    assert_ne!(decimal(-10.0, 0.5), SignalType::UnsignedInt { size: 8 });
+}
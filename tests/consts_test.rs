@@ -0,0 +1,55 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    codegen::ConstsLanguage,
+    config::NetworkRef,
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let state = network_builder.define_enum("state");
+    state.add_entry("Idle", Some(0)).unwrap();
+    state.add_entry("Running", Some(4)).unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    let torque = network_builder.create_message("torque", None);
+    torque.set_std_id(0x50);
+    torque.add_transmitter("secu");
+    torque.make_type_format().add_type("u16", "value");
+    let status = network_builder.create_message("status", None);
+    status.set_any_ext_id(MessagePriority::Low);
+    status.add_transmitter("secu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn rust_consts() {
+    let network = network();
+    let consts = network.to_consts(ConstsLanguage::Rust);
+    let secu = network.node_by_name("secu").unwrap();
+    assert!(consts.contains(&format!("pub const NETWORK_HASH: u64 = 0x{:016X};", network.config_hash())));
+    assert!(consts.contains(&format!("pub mod node_id {{\n    pub const SECU: u8 = {};", secu.id())));
+    assert!(consts.contains("pub mod bus_id {\n    pub const CAN0: u32 = 0;"));
+    assert!(consts.contains("    pub const TORQUE: u32 = 0x50;"));
+    assert!(consts.contains("    pub const STATUS: bool = true;"));
+    let state = secu.object_entries().iter().find(|oe| oe.name() == "state").unwrap();
+    assert!(consts.contains("pub mod od_index {\n    pub mod secu {\n"));
+    assert!(consts.contains(&format!("        pub const STATE: u32 = {};", state.id())));
+    assert!(consts.contains("    pub mod state {\n        pub const IDLE: u64 = 0;\n        pub const RUNNING: u64 = 4;\n    }"));
+    assert!(consts.contains("pub const INVALID_INDEX: u64 = 2;"));
+    // every opened module is closed.
+    assert_eq!(consts.matches('{').count(), consts.matches('}').count());
+}
+
+#[test]
+fn c_consts() {
+    let network = network();
+    let consts = network.to_consts(ConstsLanguage::C);
+    assert!(consts.contains("#pragma once"));
+    assert!(consts.contains(&format!("#define CANZERO_NETWORK_HASH 0x{:016X}ULL", network.config_hash())));
+    assert!(consts.contains("#define CANZERO_MESSAGE_ID_TORQUE 0x50U"));
+    assert!(consts.contains("#define CANZERO_MESSAGE_IDE_TORQUE 0"));
+    assert!(consts.contains("#define CANZERO_MESSAGE_IDE_STATUS 1"));
+    assert!(consts.contains("#define CANZERO_ENUMS_STATE_RUNNING 4ULL"));
+    assert!(consts.contains("#define CANZERO_OD_INDEX_SECU_STATE "));
+}
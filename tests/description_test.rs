@@ -0,0 +1,93 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, NetworkRef, SignalType},
+    errors::ConfigError,
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_description("configuration of the pod");
+    network_builder.create_bus("can0", Some(1_000_000)).add_description("main bus");
+    network_builder.create_node("master");
+    network_builder.create_node("secu").add_description("safety controller");
+    let torque = network_builder.create_message("torque", None);
+    torque.set_std_id(0x42);
+    torque.add_description("torque setpoints of the motors");
+    torque.add_transmitter("master");
+    torque.add_receiver("secu");
+    let format = torque.make_signal_format();
+    for wheel in ["left", "right"] {
+        format
+            .add_signal(Signal::create(wheel, None, SignalType::UnsignedInt { size: 16 }))
+            .unwrap();
+    }
+    format.add_signal_description("left", "torque of the left motor").unwrap();
+    network_builder.build().unwrap()
+}
+
+fn signal_description<'a>(network: &'a NetworkRef, message: &str, suffix: &str) -> Option<&'a str> {
+    network
+        .message_by_name(message)
+        .unwrap()
+        .signals()
+        .iter()
+        .find(|signal| signal.name().ends_with(suffix))
+        .unwrap()
+        .description()
+}
+
+#[test]
+fn descriptions_are_exposed() {
+    let network = network();
+    assert_eq!(network.buses()[0].description(), Some("main bus"));
+    assert_eq!(
+        network.node_by_name("secu").unwrap().description().map(String::as_str),
+        Some("safety controller")
+    );
+    assert_eq!(signal_description(&network, "torque", "left"), Some("torque of the left motor"));
+    assert_eq!(signal_description(&network, "torque", "right"), None);
+}
+
+#[test]
+fn undefined_signal_description() {
+    let network_builder = NetworkBuilder::new();
+    let format = network_builder.create_message("torque", None).make_signal_format();
+    match format.add_signal_description("left", "torque of the left motor") {
+        Err(ConfigError::UndefinedSignal(reason)) => assert!(reason.contains("left")),
+        other => panic!("expected UndefinedSignal, got {other:?}"),
+    }
+}
+
+#[test]
+fn dbc_round_trip() {
+    let network = network();
+    let path = std::env::temp_dir().join("canzero_description_test.dbc");
+    std::fs::write(&path, network.to_dbc()).unwrap();
+    let network_builder = NetworkBuilder::new();
+    network_builder.include_dbc("can0", path.to_str().unwrap()).unwrap();
+    let imported = network_builder.build().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(imported.description(), Some("configuration of the pod"));
+    assert_eq!(
+        imported.message_by_name("torque").unwrap().description(),
+        Some("torque setpoints of the motors")
+    );
+    assert_eq!(signal_description(&imported, "torque", "left"), Some("torque of the left motor"));
+    assert_eq!(
+        imported.node_by_name("secu").unwrap().description().map(String::as_str),
+        Some("safety controller")
+    );
+}
+
+#[test]
+fn kcd_round_trip() {
+    let network = network();
+    let imported = NetworkBuilder::from_kcd(&network.to_kcd()).unwrap().build().unwrap();
+    assert_eq!(imported.description(), Some("configuration of the pod"));
+    assert_eq!(
+        imported.message_by_name("torque").unwrap().description(),
+        Some("torque setpoints of the motors")
+    );
+    assert_eq!(signal_description(&imported, "torque", "left"), Some("torque of the left motor"));
+}
@@ -0,0 +1,60 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn reason(network_builder: NetworkBuilder) -> String {
+    match network_builder.build() {
+        Err(ConfigError::DuplicateName(reason)) => reason,
+        other => panic!("duplicate wasn't detected: {other:?}"),
+    }
+}
+
+#[test]
+fn duplicate_messages() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let first = network_builder.create_message("torque", None);
+    first.add_transmitter("secu");
+    network_builder.create_message("torque", None);
+    let reason = reason(network_builder);
+    assert!(reason.contains("message torque is defined twice"), "{reason}");
+    assert!(reason.contains("transmitted by secu"), "{reason}");
+}
+
+#[test]
+fn message_clashing_with_stream() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u8");
+    secu.create_stream("state").add_entry("position");
+    network_builder.create_message("secu_stream_state", None);
+    let reason = reason(network_builder);
+    assert!(reason.contains("message of stream secu::state"), "{reason}");
+}
+
+#[test]
+fn duplicate_types() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_enum("state");
+    network_builder.define_struct("state");
+    let reason = reason(network_builder);
+    assert!(reason.contains("type state is defined twice, as enum"), "{reason}");
+    assert!(reason.contains("and as struct"), "{reason}");
+}
+
+#[test]
+fn duplicate_buses() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can0", None);
+    let reason = reason(network_builder);
+    assert!(reason.contains("bus with id 0 and as bus with id 1"), "{reason}");
+}
+
+#[test]
+fn duplicate_commands() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_command("reset", None);
+    secu.create_command("reset", None);
+    let reason = reason(network_builder);
+    assert!(reason.contains("command secu::reset is defined twice"), "{reason}");
+}
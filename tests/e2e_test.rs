@@ -0,0 +1,66 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{E2EProfile, NetworkRef},
+};
+
+fn network(payload_bytes: usize) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    let command = network_builder.create_message("brake_command", None);
+    command.set_std_id(0x20);
+    let format = command.make_type_format();
+    for i in 0..payload_bytes {
+        format.add_type("u8", &format!("b{i}"));
+    }
+    command.add_transmitter("master");
+    command.enable_e2e_protection(E2EProfile::Crc8Counter4 { data_id: 0x3231 });
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn e2e_signals_are_appended() {
+    let network = network(6);
+    let message = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "brake_command")
+        .unwrap();
+    let e2e = message.e2e().unwrap();
+    assert_eq!(e2e.counter().byte_offset(), 48);
+    assert_eq!(e2e.counter().size(), 4);
+    assert_eq!(e2e.crc().byte_offset(), 56);
+    assert_eq!(e2e.crc().size(), 8);
+    assert_eq!(message.dlc(), 8);
+    assert_eq!(message.signals().len(), 8);
+
+    // CRC-8 SAE J1850 check value of "123456789".
+    assert_eq!(e2e.compute_crc(b"3456789\x00"), 0x4B);
+
+    let py = network.to_python();
+    assert!(py.contains("E2E_CRC_BYTE = 7"));
+    assert!(py.contains("def check(cls, data: bytes) -> bool:"));
+    let cpp = network.to_cpp();
+    assert!(cpp.contains("data[e2e_crc_byte] = detail::crc8(e2e_data_id, data.data(), dlc, e2e_crc_byte);"));
+}
+
+#[test]
+fn e2e_protection_extends_the_dlc() {
+    let network = network(1);
+    let message = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "brake_command")
+        .unwrap();
+    assert_eq!(message.e2e().unwrap().crc().byte_offset(), 16);
+    assert_eq!(message.dlc(), 3);
+}
+
+#[test]
+fn e2e_protection_requires_space() {
+    let network_builder = NetworkBuilder::new();
+    let command = network_builder.create_message("brake_command", None);
+    command.set_std_id(0x20);
+    command.make_type_format().add_type("u64", "payload");
+    command.add_transmitter("master");
+    command.enable_e2e_protection(E2EProfile::Crc8Counter4 { data_id: 1 });
+    assert!(network_builder.build().is_err());
+}
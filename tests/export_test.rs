@@ -0,0 +1,430 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{signal::Signal, NetworkRef, SignalType},
+    errors::ConfigError,
+    export::{canonical::CANONICAL_SCHEMA_VERSION, DotOptions},
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    network_builder.create_bus("can1", Some(500000));
+    let secu = network_builder.create_node("secu");
+    secu.assign_bus("can0");
+    secu.create_object_entry("temperature", "d8<-10..100>");
+    let stream = secu.create_stream("temperatures");
+    stream.add_entry("temperature");
+    stream.set_priority(MessagePriority::Low);
+    let master = network_builder.create_node("master");
+    master.assign_bus("can0");
+    master.assign_bus("can1");
+    let rx = master.receive_stream("secu", "temperatures");
+    rx.map("temperature", "secu_temperature");
+    let emergency = master.create_command("emergency", None);
+    emergency.add_callee("pdu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn dot_contains_nodes_and_streams() {
+    let network = network();
+    let dot = network.to_dot();
+    assert!(dot.starts_with("digraph network {"));
+    assert!(dot.contains("\"secu\" -> \"master\" [label=\"temperatures"));
+    assert!(dot.contains("\"master\" -> \"bus_can1\""));
+    assert!(!dot.contains("get_req"));
+}
+
+#[test]
+fn dot_filter_by_node() {
+    let network = network();
+    let dot = network.to_dot_with(&DotOptions {
+        node: Some("pdu".to_owned()),
+        ..Default::default()
+    });
+    assert!(dot.contains("\"pdu\";"));
+    assert!(!dot.contains("\"secu\";"));
+}
+
+#[test]
+fn kcd_round_trip() {
+    let network = network();
+    let kcd = network.to_kcd();
+    assert!(kcd.contains("<NetworkDefinition xmlns=\"http://kayak.2codeornot2code.org/1.0\">"));
+    assert!(kcd.contains("<Bus name=\"can1\" baudrate=\"500000\">"));
+
+    let imported = NetworkBuilder::from_kcd(&kcd).unwrap().build().unwrap();
+    for message in network.messages() {
+        if ["get_req", "get_resp", "set_req", "set_resp", "heartbeat"].contains(&message.name()) {
+            continue;
+        }
+        let other = imported
+            .messages()
+            .iter()
+            .find(|m| m.name() == message.name())
+            .unwrap();
+        assert_eq!(other.id(), message.id(), "{}", message.name());
+        assert_eq!(other.bus().name(), message.bus().name());
+        assert_eq!(other.signals().len(), message.signals().len());
+        for (a, b) in message.signals().iter().zip(other.signals()) {
+            // signals of signal formats are prefixed with the message name.
+            assert!(b.name().ends_with(a.name()), "{}", b.name());
+            assert_eq!(a.byte_offset(), b.byte_offset());
+            assert_eq!(a.ty(), b.ty());
+        }
+    }
+}
+
+#[test]
+fn kcd_keeps_signedness() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let message = network_builder.create_message("motor", None);
+    message.set_std_id(0x20);
+    message.add_transmitter("secu");
+    let format = message.make_signal_format();
+    format
+        .add_signal(Signal::new("torque", None, SignalType::SignedInt { size: 12 }, 0))
+        .unwrap();
+    format
+        .add_signal(Signal::new(
+            "current",
+            None,
+            SignalType::Decimal { size: 8, offset: -10.0, scale: 0.5 },
+            12,
+        ))
+        .unwrap();
+    let network = network_builder.build().unwrap();
+    let imported = NetworkBuilder::from_kcd(&network.to_kcd()).unwrap().build().unwrap();
+    let signals = imported.message_by_name("motor").unwrap().signals();
+    assert_eq!(signals[0].ty(), &SignalType::SignedInt { size: 12 });
+    assert_eq!(
+        signals[1].ty(),
+        &SignalType::Decimal { size: 8, offset: -10.0, scale: 0.5 }
+    );
+
+    // signed raw values with a slope can't be represented without changing the encoding.
+    let kcd = network
+        .to_kcd()
+        .replace("<Value type=\"signed\"/>", "<Value type=\"signed\" slope=\"0.5\"/>");
+    assert!(kcd.contains("slope=\"0.5\""));
+    match NetworkBuilder::from_kcd(&kcd) {
+        Err(ConfigError::Kcd(reason)) => assert!(reason.contains("torque"), "{reason}"),
+        other => panic!("expected a kcd error, got {other:?}"),
+    }
+}
+
+type Frames = Vec<(String, u32)>;
+type Signals = Vec<(String, u8)>;
+
+/// collects (frame, identifier) pairs and signal lengths from an arxml document.
+fn parse_arxml(arxml: &str) -> (Frames, Signals) {
+    use quick_xml::{events::Event, Reader};
+    let mut reader = Reader::from_str(arxml);
+    reader.config_mut().trim_text(true);
+    let mut path: Vec<String> = vec![];
+    let mut frames = vec![];
+    let mut signals = vec![];
+    let mut short_name = String::new();
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Start(e) => path.push(String::from_utf8_lossy(e.name().as_ref()).into_owned()),
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Text(text) => {
+                let text = text.unescape().unwrap().into_owned();
+                let parent = path.get(path.len().wrapping_sub(2)).map(|s| s.as_str());
+                match (path.last().map(|s| s.as_str()), parent) {
+                    (Some("SHORT-NAME"), _) => short_name = text,
+                    (Some("FRAME-REF"), _) => {
+                        frames.push((text.rsplit('/').next().unwrap().to_owned(), 0))
+                    }
+                    (Some("IDENTIFIER"), _) => frames.last_mut().unwrap().1 = text.parse().unwrap(),
+                    (Some("LENGTH"), Some("I-SIGNAL")) => {
+                        signals.push((short_name.clone(), text.parse().unwrap()))
+                    }
+                    _ => (),
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    (frames, signals)
+}
+
+#[test]
+fn arxml_contains_frames_and_signals() {
+    let network = network();
+    let arxml = network.to_arxml();
+    assert!(arxml.contains("<AUTOSAR xmlns=\"http://autosar.org/schema/r4.0\">"));
+    let (frames, signals) = parse_arxml(&arxml);
+    assert_eq!(frames.len(), network.messages().len());
+    for message in network.messages() {
+        let (_, id) = frames.iter().find(|(name, _)| name == message.name()).unwrap();
+        assert_eq!(*id, message.id().as_u32());
+        for signal in message.signals() {
+            assert!(signals
+                .iter()
+                .any(|(name, size)| name.ends_with(signal.name()) && *size == signal.size()));
+        }
+    }
+}
+
+#[test]
+fn arxml_signal_types_ecus_and_unique_names() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    network_builder.create_node("master");
+    let a_b = network_builder.create_message("a_b", None);
+    a_b.set_std_id(0x10);
+    a_b.add_transmitter("secu");
+    a_b.add_receiver("master");
+    let format = a_b.make_signal_format();
+    format
+        .add_signal(Signal::new("c", None, SignalType::SignedInt { size: 12 }, 0))
+        .unwrap();
+    let a = network_builder.create_message("a", None);
+    a.set_std_id(0x11);
+    a.add_transmitter("secu");
+    a.make_signal_format()
+        .add_signal(Signal::new("b_c", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    let network = network_builder.build().unwrap();
+    let arxml = network.to_arxml();
+
+    let (_, signals) = parse_arxml(&arxml);
+    let mut names: Vec<&String> = signals.iter().map(|(name, _)| name).collect();
+    let count = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count, "{names:?}");
+
+    assert!(arxml.contains("<SHORT-NAME>sint12</SHORT-NAME>\n"));
+    assert!(arxml.contains("<BASE-TYPE-ENCODING>2C</BASE-TYPE-ENCODING>"));
+    assert!(arxml.contains("<BASE-TYPE-ENCODING>NONE</BASE-TYPE-ENCODING>"));
+    assert!(arxml.contains("<BASE-TYPE-REF DEST=\"SW-BASE-TYPE\">/canzero/BaseTypes/sint12</BASE-TYPE-REF>"));
+
+    assert!(arxml.contains("<FRAME-PORT-REF DEST=\"FRAME-PORT\">/canzero/ECUs/secu/secu_connector/a_b_out</FRAME-PORT-REF>"));
+    assert!(arxml.contains("<FRAME-PORT-REF DEST=\"FRAME-PORT\">/canzero/ECUs/master/master_connector/a_b_in</FRAME-PORT-REF>"));
+    assert!(arxml.contains("<COMMUNICATION-DIRECTION>OUT</COMMUNICATION-DIRECTION>"));
+}
+
+#[test]
+fn eds_of_node() {
+    let network = network();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let eds = secu.to_eds().unwrap();
+    assert!(eds.starts_with("[FileInfo]"));
+    assert!(eds.contains(&format!("SupportedObjects={}", secu.object_entries().len())));
+    let temperature = secu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "temperature")
+        .unwrap();
+    let section = format!("[{:04X}]\nParameterName=temperature\n", 0x2000 + temperature.id());
+    assert!(eds.contains(&section), "{eds}");
+    // build_time is a struct and mapped to sub indices.
+    assert!(eds.contains("ParameterName=build_time.year"));
+    assert!(!eds.contains("[DeviceComissioning]"));
+    assert!(secu.to_dcf().unwrap().contains(&format!("NodeID={}", secu.id())));
+}
+
+#[test]
+fn eds_rejects_records_with_too_many_members() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let flags = network_builder.define_struct("flags");
+    for i in 0..255 {
+        flags.add_attribute(&format!("flag{i}"), "u1").unwrap();
+    }
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("flags", "flags");
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert!(matches!(secu.to_eds(), Err(ConfigError::Eds(_))));
+}
+
+#[test]
+fn sym_contains_messages() {
+    let network = network();
+    let sym = network.to_sym();
+    assert!(sym.starts_with("FormatVersion=6.0"));
+    for message in network.messages() {
+        assert!(sym.contains(&format!("[{}]", message.name())));
+    }
+    // enum of the heartbeat message
+    assert!(sym.contains("enum node_id("));
+    assert!(sym.contains("/e:node_id"));
+    assert!(sym.contains("/f:"));
+    for message in network.messages() {
+        assert!(sym.contains(&format!("Len={}\n", message.dlc())));
+    }
+    assert!(!sym.contains("DLC="));
+}
+
+#[test]
+fn sym_multiplexes_unions() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let command = network_builder.define_enum("command");
+    command.add_entry("set_speed", None).unwrap();
+    command.add_entry("set_\"mode\"", None).unwrap();
+    let payload = network_builder.define_union("command_payload");
+    payload.set_tag("command");
+    payload.add_variant("set_speed", "u16").unwrap();
+    payload.add_variant("set_\"mode\"", "u8").unwrap();
+    let message = network_builder.create_message("command_frame", None);
+    message.set_std_id(0x52);
+    message.make_type_format().add_type("command_payload", "payload");
+    message.add_transmitter("secu");
+    let network = network_builder.build().unwrap();
+
+    let sym = network.to_sym();
+    let section = |value: u64| {
+        let mux = format!("Mux=value_name_command_payload_command 0,1 {value}h\n");
+        let begin = sym.find(&mux).unwrap_or_else(|| panic!("{sym}"));
+        let end = sym[begin..].find("\n\n").map_or(sym.len(), |end| begin + end);
+        sym[begin..end].to_owned()
+    };
+    assert!(sym.contains("[command_frame]\nID=052h\nLen=3\nMux="));
+    assert!(section(0).contains("Var=value_name_command_payload_set_speed unsigned 1,16"));
+    assert!(!section(0).contains("set_'mode'"));
+    assert!(section(1).contains("unsigned 1,8"));
+    assert!(!section(1).contains("set_speed unsigned"));
+    assert!(sym.contains("1=\"set_'mode'\""));
+}
+
+#[test]
+fn mdf4_channels_of_all_signals() {
+    let network = network();
+    let mdf = network.to_mdf4_channels();
+    assert!(mdf.starts_with("<?xml"));
+    for message in network.messages() {
+        assert!(mdf.contains(&format!("<channel_group name=\"{}\"", message.name())));
+        for signal in message.signals() {
+            assert!(mdf.contains(&format!(
+                "<channel name=\"{}\" byte_offset=\"{}\" bit_offset=\"{}\" bit_count=\"{}\"",
+                signal.name(),
+                signal.byte_offset() / 8,
+                signal.byte_offset() % 8,
+                signal.size()
+            )));
+        }
+    }
+    // the decimal temperature is scaled linearly.
+    assert!(mdf.contains("<conversion type=\"1\" p1=\"-10\""));
+    // node ids of the heartbeat are labeled.
+    assert!(mdf.contains("<conversion type=\"7\" name=\"node_id\">"));
+    assert!(mdf.contains("<value raw=\"0\" text=\"secu\"/>"));
+}
+
+#[test]
+fn docs_of_buses_nodes_and_messages() {
+    let network = network();
+    let docs = network.to_docs();
+    assert!(docs.starts_with("# Network\n"));
+    assert!(docs.contains(&format!("config hash `{:016x}`", network.config_hash())));
+    assert!(docs.contains("| can1 | 1 | 500000 |  |"));
+    assert!(docs.contains("\n### secu\n"));
+    let secu = network.node_by_name("secu").unwrap();
+    let temperature = secu.object_entries().iter().find(|oe| oe.name() == "temperature").unwrap();
+    assert!(docs.contains(&format!(
+        "| temperature | {} | {} |  |  |",
+        temperature.id(),
+        temperature.ty().name()
+    )));
+    let message = network.message_by_name("secu_stream_temperatures").unwrap();
+    assert!(docs.contains(&format!(
+        "| {} | can0 | {} | secu | master |",
+        format!("{}", message.id()).trim(),
+        message.dlc()
+    )));
+    assert!(docs.contains(&format!("```text\n{}```", message.layout_table())));
+}
+
+#[test]
+fn canonical_string_is_stable_and_sorted() {
+    let canonical = network().to_canonical_string();
+    assert_eq!(canonical, network().to_canonical_string());
+    assert!(canonical.starts_with(&format!("canonical {CANONICAL_SCHEMA_VERSION}\n")));
+
+    let node_position = |name: &str| canonical.find(&format!("\nnode {name} ")).unwrap();
+    assert!(node_position("master") < node_position("pdu"));
+    assert!(node_position("pdu") < node_position("secu"));
+
+    let messages: Vec<&str> = canonical
+        .lines()
+        .filter_map(|line| line.strip_prefix("message "))
+        .map(|line| line.split(' ').next().unwrap())
+        .collect();
+    let mut sorted = messages.clone();
+    sorted.sort_unstable();
+    assert_eq!(messages, sorted);
+
+    assert!(canonical.contains("bus can1 baudrate=500000\n"));
+    assert!(canonical.contains("  tx_stream temperatures message=secu_stream_temperatures"));
+    assert!(canonical.contains("    mapping temperature\n"));
+}
+
+#[test]
+fn dbc_signal_groups() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    network_builder.create_node("master");
+    network_builder.create_node("secu");
+    let torque = network_builder.create_message("torque_vectoring", Some(Duration::from_millis(10)));
+    torque.set_std_id(0x42);
+    torque.add_transmitter("master");
+    torque.add_receiver("secu");
+    let format = torque.make_signal_format();
+    for wheel in ["fl", "fr", "rl", "rr"] {
+        format
+            .add_signal(Signal::create(
+                wheel,
+                None,
+                SignalType::Decimal { size: 16, offset: -100.0, scale: 0.01 },
+            ))
+            .unwrap();
+    }
+    torque.add_signal_group("setpoints", &["fl", "fr", "rl", "torque_vectoring_rr"]);
+    let network = network_builder.build().unwrap();
+
+    let message = network.message_by_name("torque_vectoring").unwrap();
+    let group = &message.signal_groups()[0];
+    assert_eq!(group.name(), "setpoints");
+    assert_eq!(group.signals().len(), 4);
+    assert_eq!(group.signals()[3].name(), "torque_vectoring_rr");
+    assert!(network
+        .to_canonical_string()
+        .contains("signal_group setpoints signals=torque_vectoring_fl,"));
+
+    let dbc = network.to_dbc();
+    assert!(dbc.contains("BO_ 66 torque_vectoring: 8 master"));
+    assert!(dbc.contains(" SG_ torque_vectoring_fl : 0|16@1+ (0.01,-100) [-100|555.35] \"\" secu"));
+    assert!(dbc.contains(
+        "SIG_GROUP_ 66 setpoints 1 : torque_vectoring_fl torque_vectoring_fr torque_vectoring_rl torque_vectoring_rr;"
+    ));
+
+    let parsed = can_dbc::DBC::from_slice(dbc.as_bytes()).unwrap();
+    assert_eq!(parsed.messages().len(), network.messages().len());
+    assert_eq!(parsed.signal_groups().len(), 1);
+    assert_eq!(parsed.signal_groups()[0].signal_names().len(), 4);
+
+    // undefined signals are rejected.
+    let network_builder = NetworkBuilder::new();
+    let message = network_builder.create_message("status", None);
+    message.set_std_id(0x43);
+    message
+        .make_signal_format()
+        .add_signal(Signal::create("state", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    message.add_signal_group("group", &["state", "error"]);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidSignalGroup(_))
+    ));
+}
@@ -0,0 +1,69 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::MessageId,
+    errors::ConfigError,
+};
+
+#[test]
+fn extern_node() {
+    let network_builder = NetworkBuilder::new();
+    let bms = network_builder.create_extern_node("bms");
+    bms.add_description("purchased battery management system");
+    let master = network_builder.create_node("master");
+
+    let bms_status = network_builder.create_message("bms_status", None);
+    bms_status.set_std_id(0x123);
+    bms_status.make_type_format().add_type("u16", "voltage");
+    bms_status.add_transmitter("bms");
+    bms_status.add_receiver("master");
+
+    let master_cmd = network_builder.create_message("master_cmd", None);
+    master_cmd.set_any_std_id(MessagePriority::Normal);
+    master_cmd.make_type_format().add_type("u8", "mode");
+    master_cmd.add_transmitter("master");
+    master_cmd.add_receiver("bms");
+
+    assert!(bms.is_extern());
+    assert!(!master.is_extern());
+    assert!(bms.object_entries().is_empty());
+
+    let network = network_builder.build().unwrap();
+    let bms = network.node_by_name("bms").unwrap();
+    assert!(bms.is_extern());
+    assert!(bms.object_entries().is_empty());
+    let tx: Vec<&str> = bms.tx_messages().iter().map(|m| m.name()).collect();
+    assert_eq!(tx, vec!["bms_status"]);
+    let rx: Vec<&str> = bms.rx_messages().iter().map(|m| m.name()).collect();
+    assert_eq!(rx, vec!["master_cmd"]);
+
+    let status = network.messages().iter().find(|m| m.name() == "bms_status").unwrap();
+    assert_eq!(status.id(), &MessageId::StandardId(0x123));
+
+    let master = network.node_by_name("master").unwrap();
+    assert!(!master.is_extern());
+    assert!(master.rx_messages().iter().any(|m| m.name() == "bms_status"));
+    assert!(master.tx_messages().iter().any(|m| m.name() == "heartbeat"));
+}
+
+#[test]
+fn extern_node_requires_fixed_ids() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_extern_node("bms");
+    let bms_status = network_builder.create_message("bms_status", None);
+    bms_status.add_transmitter("bms");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidExternNode(_))
+    ));
+}
+
+#[test]
+fn extern_node_without_object_dictionary() {
+    let network_builder = NetworkBuilder::new();
+    let bms = network_builder.create_extern_node("bms");
+    bms.create_object_entry("soc", "u8");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidExternNode(_))
+    ));
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use canzero_config::{
+    builder::{
+        CompactAllocator, HashStableAllocator, IdAllocation, IdAllocator, IdRequests,
+        MessagePriority, NetworkBuilder,
+    },
+    config::{MessageId, Network},
+    errors::{ConfigError, Result},
+};
+
+fn network_builder(extra_messages: &[&str]) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("secu");
+    network_builder.create_node("master");
+    let fixed = network_builder.create_message("fixed", None);
+    fixed.set_std_id(0);
+    fixed.add_transmitter("master");
+    fixed.add_receiver("secu");
+    for (name, priority) in [
+        ("torque", MessagePriority::Realtime),
+        ("state", MessagePriority::Normal),
+        ("temperature", MessagePriority::SuperLow),
+    ]
+    .into_iter()
+    .chain(extra_messages.iter().map(|name| (*name, MessagePriority::Normal)))
+    {
+        let message = network_builder.create_message(name, None);
+        message.set_any_std_id(priority);
+        message.add_transmitter("master");
+        message.add_receiver("secu");
+    }
+    network_builder
+}
+
+fn id_of(network: &Network, name: &str) -> MessageId {
+    *network
+        .messages()
+        .iter()
+        .find(|message| message.name() == name)
+        .unwrap()
+        .id()
+}
+
+fn assert_filters_accept_rx_messages(network: &Network) {
+    for node in network.nodes() {
+        for message in node.rx_messages() {
+            assert!(node
+                .rx_filters()
+                .iter()
+                .filter(|(bus, _)| bus.id() == message.bus().id())
+                .flat_map(|(_, filters)| filters.iter())
+                .any(|filter| filter.accepts(message.id())));
+        }
+    }
+}
+
+#[test]
+fn compact_allocator_orders_by_priority() {
+    let network_builder = network_builder(&[]);
+    network_builder.set_id_allocator(CompactAllocator);
+    let network = network_builder.build().unwrap();
+    assert_eq!(id_of(&network, "fixed"), MessageId::StandardId(0));
+    assert_eq!(id_of(&network, "torque"), MessageId::StandardId(1));
+    let MessageId::StandardId(state) = id_of(&network, "state") else {
+        panic!("expected a standard id");
+    };
+    let MessageId::StandardId(temperature) = id_of(&network, "temperature") else {
+        panic!("expected a standard id");
+    };
+    assert!(1 < state && state < temperature);
+    assert_filters_accept_rx_messages(&network);
+}
+
+#[test]
+fn hash_stable_allocator_keeps_ids() {
+    let network_builder = network_builder(&[]);
+    network_builder.set_id_allocator(HashStableAllocator);
+    let network = network_builder.build().unwrap();
+    let extended = crate::network_builder(&["pressure", "velocity"]);
+    extended.set_id_allocator(HashStableAllocator);
+    let extended = extended.build().unwrap();
+    for name in ["fixed", "torque", "state", "temperature"] {
+        assert_eq!(id_of(&network, name), id_of(&extended, name));
+    }
+    assert_filters_accept_rx_messages(&extended);
+}
+
+#[derive(Debug)]
+struct LegacyIdMap(HashMap<&'static str, u32>);
+
+impl IdAllocator for LegacyIdMap {
+    fn allocate(&self, requests: &IdRequests) -> Result<IdAllocation> {
+        let mut next = 0x700;
+        let ids = requests
+            .requests()
+            .iter()
+            .map(|request| {
+                let id = self.0.get(request.name()).copied().unwrap_or_else(|| {
+                    next += 1;
+                    next
+                });
+                MessageId::StandardId(id)
+            })
+            .collect();
+        Ok(IdAllocation::new(ids))
+    }
+}
+
+#[test]
+fn custom_allocator_assigns_ids() {
+    let network_builder = network_builder(&[]);
+    network_builder.set_id_allocator(LegacyIdMap(HashMap::from([
+        ("torque", 0x10),
+        ("state", 0x20),
+        ("temperature", 0x30),
+    ])));
+    let network = network_builder.build().unwrap();
+    assert_eq!(id_of(&network, "torque"), MessageId::StandardId(0x10));
+    assert_eq!(id_of(&network, "state"), MessageId::StandardId(0x20));
+    assert_eq!(id_of(&network, "temperature"), MessageId::StandardId(0x30));
+    assert_filters_accept_rx_messages(&network);
+}
+
+#[test]
+fn duplicated_ids_are_rejected() {
+    let network_builder = network_builder(&[]);
+    network_builder.set_id_allocator(LegacyIdMap(HashMap::from([
+        ("torque", 0x10),
+        ("state", 0x10),
+    ])));
+    match network_builder.build() {
+        Err(ConfigError::UnresolvedId(reason)) => assert!(reason.contains("twice")),
+        other => panic!("expected UnresolvedId, got {other:?}"),
+    }
+}
+
+#[test]
+fn fixed_ids_are_not_reassigned() {
+    let network_builder = network_builder(&[]);
+    network_builder.set_id_allocator(LegacyIdMap(HashMap::from([("torque", 0x0)])));
+    match network_builder.build() {
+        Err(ConfigError::UnresolvedId(reason)) => assert!(reason.contains("fixed")),
+        other => panic!("expected UnresolvedId, got {other:?}"),
+    }
+}
+
+#[test]
+fn priority_buckets_skip_fixed_ids_outside_of_sets() {
+    let network_builder = network_builder(&[]);
+    // received by other nodes than the allocated messages, so the fixed
+    // ids don't belong to any set.
+    for id in 1..64 {
+        let fixed = network_builder.create_message(&format!("fixed_{id}"), None);
+        fixed.set_std_id(id);
+        fixed.add_transmitter("secu");
+        fixed.add_receiver("master");
+    }
+    let network = network_builder.build().unwrap();
+    let mut ids: Vec<MessageId> = network.messages().iter().map(|m| *m.id()).collect();
+    ids.sort_by_key(|id| format!("{id:?}"));
+    ids.dedup();
+    assert_eq!(ids.len(), network.messages().len());
+    assert_filters_accept_rx_messages(&network);
+}
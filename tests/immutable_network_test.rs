@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use canzero_config::{builder::NetworkBuilder, config::message::MessageUsage};
+
+fn network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    let stream = secu.create_stream("state");
+    stream.add_entry("temperature");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+    let reset = master.create_command("reset", None);
+    reset.add_callee("secu");
+    network_builder
+}
+
+#[test]
+fn back_references() {
+    let network = network().build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let temperature = secu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "temperature")
+        .unwrap();
+    assert!(Arc::ptr_eq(&temperature.node().unwrap(), secu));
+
+    let stream = &secu.tx_streams()[0];
+    let MessageUsage::Stream(owner) = stream.message().usage() else {
+        panic!("stream message isn't used by the stream");
+    };
+    assert!(Arc::ptr_eq(&owner.upgrade().unwrap(), stream));
+
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let reset = &master.commands()[0];
+    let MessageUsage::CommandReq(owner) = reset.tx_message().usage() else {
+        panic!("command request isn't used by the command");
+    };
+    assert!(Arc::ptr_eq(&owner.upgrade().unwrap(), reset));
+    assert_eq!(secu.extern_commands().len(), 1);
+}
+
+#[test]
+fn networks_are_comparable_and_shareable() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<canzero_config::config::Network>();
+
+    let a = network().build().unwrap();
+    let b = network().build().unwrap();
+    assert_eq!(a, b);
+    // debug formatting terminates, because back-references are weak.
+    assert!(!format!("{a:?}").is_empty());
+
+    let other = network();
+    other.create_node("pdu");
+    assert_ne!(a, other.build().unwrap());
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    secu.create_stream("state").add_entry("state");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+    let message = network_builder.create_message("torque", None);
+    message.set_any_std_id(MessagePriority::High);
+    message.make_type_format().add_type("u16", "torque");
+    message.add_transmitter("master");
+    message.add_receiver("secu");
+    network_builder
+}
+
+#[test]
+fn rebuild_without_changes_reuses_network() {
+    let network_builder = network_builder();
+    assert!(network_builder.is_dirty());
+    let network = network_builder.rebuild().unwrap();
+    assert!(!network_builder.is_dirty());
+    assert!(network_builder.nodes().iter().all(|node| !node.is_dirty()));
+    let again = network_builder.rebuild().unwrap();
+    assert!(Arc::ptr_eq(&network, &again));
+}
+
+#[test]
+fn rebuild_after_edit() {
+    let network_builder = network_builder();
+    let network = network_builder.rebuild().unwrap();
+    let torque_id = *network.message_by_name("torque").unwrap().id();
+
+    let message = network_builder.find_message("torque").unwrap();
+    message.add_description("requested torque");
+    assert!(message.is_dirty());
+    assert!(!network_builder.find_node("secu").unwrap().is_dirty());
+    assert!(!network_builder.find_type("state").unwrap().is_dirty());
+    assert!(network_builder.is_dirty());
+
+    let rebuilt = network_builder.rebuild().unwrap();
+    assert!(!Arc::ptr_eq(&network, &rebuilt));
+    let torque = rebuilt.message_by_name("torque").unwrap();
+    assert_eq!(torque.description(), Some("requested torque"));
+    assert_eq!(torque.id(), &torque_id);
+    assert_eq!(rebuilt.messages().len(), network.messages().len());
+    assert_eq!(
+        rebuilt.messages().iter().filter(|m| m.name() == "heartbeat").count(),
+        1
+    );
+
+    let state = |network: &canzero_config::config::Network| {
+        network.types().iter().find(|ty| ty.name() == "state").unwrap().clone()
+    };
+    assert!(Arc::ptr_eq(&state(&network), &state(&rebuilt)));
+}
+
+#[test]
+fn rebuild_with_new_node() {
+    let network_builder = network_builder();
+    let network = network_builder.rebuild().unwrap();
+    network_builder.create_node("bms");
+    assert!(network_builder.is_dirty());
+    let rebuilt = network_builder.rebuild().unwrap();
+    assert_eq!(rebuilt.nodes().len(), network.nodes().len() + 1);
+    let node_id = rebuilt.types().iter().find(|ty| ty.name() == "node_id").unwrap();
+    assert_eq!(node_id.size(), 2);
+}
@@ -0,0 +1,53 @@
+use canzero_config::{builder::NetworkBuilder, config::J1939Id};
+
+#[test]
+fn j1939_id_layout() {
+    // cruise control / vehicle speed (PDU2 format).
+    let id = J1939Id::new(6, 0xFEF1, 0x11).unwrap();
+    assert_eq!(id.to_ext_id(), 0x18FEF111);
+    assert!(!id.is_pdu1());
+    assert_eq!(J1939Id::from_ext_id(0x18FEF111), id);
+
+    // torque speed control (PDU1 format) is sent to the global address.
+    let id = J1939Id::new(3, 0x0000, 0x03).unwrap();
+    assert_eq!(id.to_ext_id(), 0x0C00FF03);
+    assert_eq!(J1939Id::from_ext_id(0x0C00FF03), id);
+
+    assert!(J1939Id::new(8, 0xFEF1, 0).is_err());
+    assert!(J1939Id::new(6, 0x40000, 0).is_err());
+    assert!(J1939Id::new(6, 0xEF12, 0).is_err());
+    assert!(J1939Id::new(6, 0xFEF1, 254).is_err());
+}
+
+#[test]
+fn j1939_message_ids() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("master");
+    network_builder.create_node("secu");
+    let speed = network_builder.create_message("vehicle_speed", None);
+    speed.make_type_format().add_type("u16", "speed");
+    speed.add_transmitter("secu");
+    speed.set_j1939_id(0xFEF1, 6);
+    let network = network_builder.build().unwrap();
+
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let message = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "vehicle_speed")
+        .unwrap();
+    assert!(message.id().ide());
+    let id = J1939Id::from_ext_id(message.id().as_u32());
+    assert_eq!(id.pgn(), 0xFEF1);
+    assert_eq!(id.priority(), 6);
+    assert_eq!(id.source_address(), secu.id());
+}
+
+#[test]
+fn j1939_requires_transmitter() {
+    let network_builder = NetworkBuilder::new();
+    let speed = network_builder.create_message("vehicle_speed", None);
+    speed.make_type_format().add_type("u16", "speed");
+    speed.set_j1939_id(0xFEF1, 6);
+    assert!(network_builder.build().is_err());
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "cli")]
+
+use canzero_config::{builder::NetworkBuilder, config::MessageId, errors::ConfigError};
+
+const NETWORK: &str = r#"{
+  "schema_version": 1,
+  "buses": [{ "name": "can0", "baudrate": 1000000 }],
+  "enums": [{ "name": "state", "entries": ["idle", { "name": "running", "value": 4 }] }],
+  "structs": [{ "name": "status", "attributes": [
+    { "name": "state", "type": "state" },
+    { "name": "temperature", "type": "d8<0..100>" }
+  ] }],
+  "nodes": [
+    {
+      "name": "secu",
+      "description": "sensor \"ecu\"",
+      "object_entries": [{ "name": "status", "type": "status", "unit": "-" }],
+      "streams": [{ "name": "status", "entries": ["status"] }],
+      "commands": [{ "name": "reset", "arguments": [{ "name": "delay", "type": "u16" }], "callees": ["master"] }]
+    },
+    { "name": "master", "receive": [{ "node": "secu", "stream": "status" }] }
+  ],
+  "messages": [{ "name": "dyno_torque", "id": 80,
+    "signals": [{ "name": "torque", "type": "u16" }],
+    "transmitters": ["master"], "receivers": ["secu"] }]
+}"#;
+
+#[test]
+fn json_network() {
+    let network = NetworkBuilder::from_json(NETWORK).unwrap().build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.description().map(|d| d.as_str()), Some("sensor \"ecu\""));
+    assert!(secu.object_entries().iter().any(|oe| oe.name() == "status"));
+    assert_eq!(secu.tx_streams().len(), 1);
+    assert!(secu.commands().iter().any(|c| c.name() == "reset"));
+    let message = network.message_by_name("dyno_torque").unwrap();
+    assert_eq!(message.id(), &MessageId::StandardId(80));
+    let state = network.types().iter().find(|ty| ty.name() == "state").unwrap();
+    assert_eq!(state.size(), 3);
+}
+
+#[test]
+fn invalid_json() {
+    for json in [
+        "{",
+        "[]",
+        r#"{ "nodes": [{ "name": 1 }] }"#,
+        r#"{ "messages": [{ "name": "m", "id": -1 }] }"#,
+        r#"{ "buses": [] } x"#,
+        r#"{ "schema_version": 2 }"#,
+        r#"{ "schema_version": "1" }"#,
+    ] {
+        assert!(
+            matches!(NetworkBuilder::from_json(json), Err(ConfigError::Json(_))),
+            "{json}"
+        );
+    }
+}
+
+#[test]
+fn json_surrogate_pairs() {
+    let network_builder = NetworkBuilder::from_json(
+        r#"{ "nodes": [{ "name": "secu", "description": "sensor \uD83D\uDE00" }] }"#,
+    )
+    .unwrap();
+    let network = network_builder.build().unwrap();
+    let secu = network.node_by_name("secu").unwrap();
+    assert_eq!(secu.description().map(|d| d.as_str()), Some("sensor \u{1F600}"));
+}
+
+#[test]
+fn yaml_network() {
+    let yaml = r#"
+schema_version: 1
+buses:
+  - name: can0
+    baudrate: 1000000
+enums:
+  - name: state
+    entries: [idle, { name: running, value: 4 }]
+nodes:
+  - name: secu
+    description: sensor ecu
+    object_entries:
+      - { name: state, type: state }
+    streams:
+      - { name: status, entries: [state] }
+  - name: master
+    receive:
+      - { node: secu, stream: status }
+messages:
+  - name: dyno_torque
+    id: 80
+    signals:
+      - { name: torque, type: u16 }
+    transmitters: [master]
+    receivers: [secu]
+"#;
+    let from_yaml = NetworkBuilder::from_yaml(yaml).unwrap().build().unwrap();
+    let secu = from_yaml.node_by_name("secu").unwrap();
+    assert_eq!(secu.description().map(|d| d.as_str()), Some("sensor ecu"));
+    assert_eq!(secu.tx_streams().len(), 1);
+    let message = from_yaml.message_by_name("dyno_torque").unwrap();
+    assert_eq!(message.id(), &MessageId::StandardId(80));
+}
+
+#[test]
+fn invalid_yaml() {
+    for yaml in ["nodes: [", "- secu", "nodes: [{ name: 1 }]", "schema_version: 2"] {
+        assert!(
+            matches!(NetworkBuilder::from_yaml(yaml), Err(ConfigError::Yaml(_))),
+            "{yaml}"
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::Duration;
+
+use canzero_config::{
+    analysis::ResponseTimeOptions,
+    builder::NetworkBuilder,
+    config::LatencyHop,
+    errors::ConfigError,
+};
+
+fn brake_network(budget: Duration) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("brake_pressure", "d16<0..200>");
+    let stream = secu.create_stream("brake_state");
+    stream.add_entry("brake_pressure");
+    stream.set_interval(Duration::from_millis(1), Duration::from_millis(10));
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "brake_state");
+    let brakes = network_builder.create_node("brakes");
+    brakes.create_command("engage", None).add_callee("master");
+
+    let path = network_builder.create_latency_path("brake_trigger");
+    path.add_description("pressure drop to engaged brakes");
+    path.set_budget(budget);
+    path.add_object_entry("secu", "brake_pressure");
+    path.add_stream("secu", "brake_state");
+    path.add_command("brakes", "engage");
+    network_builder
+}
+
+#[test]
+fn brake_trigger_chain() {
+    let network = brake_network(Duration::from_millis(20)).build().unwrap();
+    let path = &network.latency_paths()[0];
+    assert_eq!(path.name(), "brake_trigger");
+    assert_eq!(path.budget(), Duration::from_millis(20));
+    assert!(matches!(path.hops()[1], LatencyHop::Stream { .. }));
+
+    let latencies = network.path_latencies(&ResponseTimeOptions::default());
+    assert_eq!(latencies.len(), 1);
+    let latency = &latencies[0];
+    let names: Vec<&str> = latency.hops().iter().map(|hop| hop.name()).collect();
+    assert_eq!(names, vec!["secu::brake_pressure", "secu::brake_state", "brakes::engage"]);
+    assert_eq!(latency.hops()[0].worst_case(), Some(Duration::ZERO));
+    assert_eq!(latency.hops()[1].interval(), Duration::from_millis(10));
+
+    let worst_case = latency.worst_case().unwrap();
+    let sum: Duration = latency.hops().iter().map(|hop| hop.worst_case().unwrap()).sum();
+    assert_eq!(worst_case, sum);
+    assert!(worst_case > Duration::from_millis(10));
+    assert!(!latency.is_violated());
+}
+
+#[test]
+fn violated_budget() {
+    let network = brake_network(Duration::from_millis(5)).build().unwrap();
+    let latencies = network.path_latencies(&ResponseTimeOptions::default());
+    assert!(latencies[0].is_violated());
+}
+
+#[test]
+fn undefined_hop() {
+    let network_builder = brake_network(Duration::from_millis(20));
+    network_builder
+        .create_latency_path("brake_trigger")
+        .add_command("brakes", "release");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidLatencyPath(_))
+    ));
+}
+
+#[test]
+fn latency_paths_change_config_hash() {
+    let a = brake_network(Duration::from_millis(20)).build().unwrap();
+    let b = brake_network(Duration::from_millis(5)).build().unwrap();
+    assert_ne!(a.config_hash(), b.config_hash());
+    assert_ne!(*a, *b);
+
+    let without_path = brake_network(Duration::from_millis(20));
+    without_path.0.borrow_mut().latency_paths.clear();
+    assert_ne!(a.config_hash(), without_path.build().unwrap().config_hash());
+}
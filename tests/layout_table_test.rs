@@ -0,0 +1,163 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, AttributePadding, SignalType, TypeSignalEncoding},
+    errors::ConfigError,
+};
+
+#[test]
+fn layout_table() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let message = network_builder.create_message("frame", None);
+    message.set_std_id(0x50);
+    let format = message.make_signal_format();
+    for (name, size) in [("a", 8), ("b", 4), ("c", 16)] {
+        format
+            .add_signal(Signal::create(name, None, SignalType::UnsignedInt { size }))
+            .unwrap();
+    }
+    message.add_transmitter("secu");
+    let network = network_builder.build().unwrap();
+    let message = network.message_by_name("frame").unwrap();
+
+    let table = message.layout_table();
+    assert_eq!(table.byte_count(), message.dlc() as usize);
+    assert_eq!(table.byte_count(), 4);
+    assert_eq!(table.signals(), &vec!["frame_a".to_owned(), "frame_b".to_owned(), "frame_c".to_owned()]);
+    assert_eq!(table.signal_at(0), Some("frame_a"));
+    assert_eq!(table.signal_at(7), Some("frame_a"));
+    assert_eq!(table.signal_at(8), Some("frame_b"));
+    assert_eq!(table.signal_at(12), Some("frame_c"));
+    assert_eq!(table.signal_at(27), Some("frame_c"));
+    assert_eq!(table.signal_at(28), None);
+    assert_eq!(table.signal_at(64), None);
+    assert_eq!(table.unused_bits(), vec![28, 29, 30, 31]);
+
+    let rendered = table.to_string();
+    let expected = "\
+byte | 7 6 5 4 3 2 1 0
+   0 | a a a a a a a a
+   1 | c c c c b b b b
+   2 | c c c c c c c c
+   3 | . . . . c c c c
+a: frame_a (bits 0..=7)
+b: frame_b (bits 8..=11)
+c: frame_c (bits 12..=27)
+";
+    assert_eq!(rendered, expected);
+}
+
+#[test]
+fn struct_attribute_padding() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("bms");
+    let status = network_builder.define_struct("bms_status");
+    status.add_attribute("state", "u4").unwrap();
+    status
+        .add_attribute_with("voltage", "u12", Some("pack voltage"), AttributePadding::Align(8))
+        .unwrap();
+    status
+        .add_attribute_with("fault", "u1", None, AttributePadding::Bits(3))
+        .unwrap();
+    assert!(status
+        .add_attribute_with("other", "u1", None, AttributePadding::Align(0))
+        .is_err());
+    let message = network_builder.create_message("bms_frame", None);
+    message.set_std_id(0x51);
+    message.make_type_format().add_type("bms_status", "status");
+    message.add_transmitter("bms");
+    let network = network_builder.build().unwrap();
+
+    let ty = network.types().iter().find(|ty| ty.name() == "bms_status").unwrap();
+    assert_eq!(ty.size(), 24);
+    let message = network.message_by_name("bms_frame").unwrap();
+    assert_eq!(message.dlc(), 3);
+    let offsets: Vec<(&str, usize)> = message
+        .signals()
+        .iter()
+        .map(|s| (s.name(), s.byte_offset()))
+        .collect();
+    assert_eq!(
+        offsets,
+        vec![
+            ("value_name_bms_status_state", 0),
+            ("value_name_bms_status_voltage", 8),
+            ("value_name_bms_status_fault", 23),
+        ]
+    );
+    assert_eq!(message.signals()[1].description(), Some("pack voltage"));
+
+    let TypeSignalEncoding::Composite(status) = &message.encoding().unwrap().attributes()[0] else {
+        panic!("structs are encoded as composites");
+    };
+    let paddings: Vec<usize> = status.attributes().iter().map(|a| a.padding()).collect();
+    assert_eq!(paddings, vec![0, 4, 3]);
+    assert_eq!(message.layout_table().signal_at(5), None);
+}
+
+#[test]
+fn union_variants_share_offsets() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let command = network_builder.define_enum("command");
+    command.add_entry("set_speed", None).unwrap();
+    command.add_entry("set_mode", None).unwrap();
+    let mode = network_builder.define_struct("mode");
+    mode.add_attribute("id", "u4").unwrap();
+    mode.add_attribute("flags", "u8").unwrap();
+    let payload = network_builder.define_union("command_payload");
+    payload.set_tag("command");
+    payload.add_variant("set_speed", "u16").unwrap();
+    payload.add_variant("set_mode", "mode").unwrap();
+    assert!(payload.add_variant("set_mode", "u8").is_err());
+    let message = network_builder.create_message("command_frame", None);
+    message.set_std_id(0x52);
+    message.make_type_format().add_type("command_payload", "payload");
+    message.add_transmitter("secu");
+    let network = network_builder.build().unwrap();
+
+    let message = network.message_by_name("command_frame").unwrap();
+    let offsets: Vec<(&str, usize)> = message
+        .signals()
+        .iter()
+        .map(|s| (s.name(), s.byte_offset()))
+        .collect();
+    assert_eq!(
+        offsets,
+        vec![
+            ("value_name_command_payload_command", 0),
+            ("value_name_command_payload_set_speed", 1),
+            ("value_name_command_payload_set_mode_mode_id", 1),
+            ("value_name_command_payload_set_mode_mode_flags", 5),
+        ]
+    );
+    assert_eq!(message.dlc(), 3);
+    let encoding = message.encoding().unwrap();
+    let (tag, value) = encoding
+        .multiplexor_of("value_name_command_payload_set_mode_mode_flags")
+        .unwrap();
+    assert_eq!(tag.name(), "value_name_command_payload_command");
+    assert_eq!(value, 1);
+    assert!(encoding
+        .multiplexor_of("value_name_command_payload_command")
+        .is_none());
+
+    let dbc = network.to_dbc();
+    assert!(dbc.contains(" SG_ value_name_command_payload_command M : 0|1@1+"));
+    assert!(dbc.contains(" SG_ value_name_command_payload_set_speed m0 : 1|16@1+"));
+    assert!(dbc.contains(" SG_ value_name_command_payload_set_mode_mode_id m1 : 1|4@1+"));
+}
+
+#[test]
+fn union_variant_must_be_tag_entry() {
+    let network_builder = NetworkBuilder::new();
+    let command = network_builder.define_enum("command");
+    command.add_entry("set_speed", None).unwrap();
+    let payload = network_builder.define_union("command_payload");
+    payload.set_tag("command");
+    payload.add_variant("set_torque", "u16").unwrap();
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::UndefinedEnumEntry(_))
+    ));
+}
@@ -0,0 +1,191 @@
+use canzero_config::{
+    analysis::{LintOptions, LintRule, Severity},
+    builder::NetworkBuilder,
+    config::{signal::Signal, Lifecycle, SignalType, Version},
+    errors::ConfigError,
+};
+
+fn network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", Some(0)).unwrap();
+    state.add_entry("running", Some(4)).unwrap();
+    let unused = network_builder.define_enum("unused");
+    unused.add_entry("a", None).unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    let stream = secu.create_stream("state");
+    stream.add_entry("state");
+    network_builder.create_node("master").receive_stream("secu", "state");
+    let debug = network_builder.create_message("debug", None);
+    debug.set_std_id(0x50);
+    let format = debug.make_signal_format();
+    format
+        .add_signal(Signal::new("flag", None, SignalType::UnsignedInt { size: 4 }, 0))
+        .unwrap();
+    format
+        .add_signal(Signal::new("value", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    debug.add_transmitter("secu");
+    network_builder
+}
+
+#[test]
+fn lint_rules() {
+    let network = network().build().unwrap();
+    let report = network.lint(&LintOptions::default());
+    let has = |rule: LintRule, subject: &str| {
+        report
+            .diagnostics()
+            .iter()
+            .any(|d| d.rule() == rule && d.subject() == subject)
+    };
+    assert!(has(LintRule::EnumHoles, "state"));
+    assert!(has(LintRule::UnusedType, "unused"));
+    assert!(!has(LintRule::UnusedType, "state"));
+    assert!(has(LintRule::UnreceivedMessage, "debug"));
+    assert!(!has(LintRule::UnreceivedMessage, "secu_stream_state"));
+    assert!(has(LintRule::UnmappedObjectEntry, "secu::temperature"));
+    assert!(!has(LintRule::UnmappedObjectEntry, "secu::state"));
+    assert!(has(LintRule::UnalignedSignal, "debug::debug_value"));
+    assert!(!has(LintRule::UnalignedSignal, "debug::debug_flag"));
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn lint_severities() {
+    let network = network().build().unwrap();
+    let mut options = LintOptions::default();
+    options
+        .set_severity(LintRule::EnumHoles, Severity::Error)
+        .set_severity(LintRule::UnmappedObjectEntry, Severity::Allow);
+    let report = network.lint(&options);
+    assert!(report.has_errors());
+    assert!(!report
+        .diagnostics()
+        .iter()
+        .any(|d| d.rule() == LintRule::UnmappedObjectEntry));
+    let json = report.to_json();
+    assert!(json.starts_with('['));
+    assert!(json.contains("{\"rule\":\"enum_holes\",\"severity\":\"error\",\"subject\":\"state\""));
+}
+
+fn versioned_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.set_firmware_version("v2.1");
+    secu.create_object_entry("pressure", "d8<0..10>").introduced_in("v2.3");
+    secu.create_object_entry("legacy", "u8").deprecated_in("v2");
+    let master = network_builder.create_node("master");
+    master.set_firmware_version("v2.3.1");
+    let status = network_builder.create_message("status", None);
+    status.set_std_id(0x60);
+    status.introduced_in("v2.2");
+    let format = status.make_signal_format();
+    format
+        .add_signal(
+            Signal::new("mode", None, SignalType::UnsignedInt { size: 8 }, 0)
+                .with_lifecycle(Lifecycle::new(None, Some("v2.3".parse().unwrap()))),
+        )
+        .unwrap();
+    status.add_transmitter("master");
+    status.add_receiver("secu");
+    network_builder
+}
+
+#[test]
+fn lint_versions() {
+    let network = versioned_network().build().unwrap();
+    assert_eq!(
+        network.node_by_name("master").unwrap().firmware_version(),
+        Some(&Version::new(2, 3, 1))
+    );
+    let report = network.lint(&LintOptions::default());
+    let has = |rule: LintRule, subject: &str| {
+        report
+            .diagnostics()
+            .iter()
+            .any(|d| d.rule() == rule && d.subject() == subject)
+    };
+    assert!(has(LintRule::UnavailableElement, "secu::pressure"));
+    assert!(has(LintRule::DeprecatedElement, "secu::legacy"));
+    assert!(has(LintRule::UnavailableElement, "secu::status"));
+    assert!(!has(LintRule::UnavailableElement, "master::status"));
+    assert!(has(LintRule::DeprecatedElement, "master::status::status_mode"));
+    assert!(!has(LintRule::DeprecatedElement, "secu::status::status_mode"));
+
+    // nodes without firmware version are checked against the target version.
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("pressure", "d8<0..10>").introduced_in("v2");
+    let network = network_builder.build().unwrap();
+    let mut options = LintOptions::default();
+    options.set_target_version("v1.9".parse().unwrap());
+    let report = network.lint(&options);
+    assert!(report
+        .diagnostics()
+        .iter()
+        .any(|d| d.rule() == LintRule::UnavailableElement && d.subject() == "secu::pressure"));
+}
+
+#[test]
+fn invalid_versions() {
+    let network_builder = versioned_network();
+    network_builder
+        .create_node("secu")
+        .create_object_entry("pressure", "d8<0..10>")
+        .introduced_in("2.x");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidVersion(_))
+    ));
+
+    let network_builder = versioned_network();
+    let status = network_builder.create_message("late", None);
+    status.introduced_in("v3");
+    status.deprecated_in("v2.5");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidVersion(_))
+    ));
+}
+
+#[test]
+fn lint_rx_timeouts() {
+    use std::time::Duration;
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let master = network_builder.create_node("master");
+    let torque = network_builder.create_message("torque", Some(Duration::from_millis(10)));
+    torque.set_std_id(0x42);
+    torque.add_transmitter("master");
+    let status = network_builder.create_message("status", Some(Duration::from_millis(100)));
+    status.set_std_id(0x43);
+    status.add_transmitter("master");
+    secu.add_rx_message_with_timeout(&torque, Duration::from_millis(12));
+    secu.add_rx_message_with_timeout(&status, Duration::from_millis(250));
+    master.add_rx_message_with_timeout(&network_builder.get_req_message(), Duration::from_secs(1));
+    let network = network_builder.build().unwrap();
+
+    let secu = network.node_by_name("secu").unwrap();
+    let torque = network.message_by_name("torque").unwrap();
+    assert_eq!(secu.rx_timeout(torque), Some(Duration::from_millis(12)));
+    assert!(secu.rx_messages().iter().any(|m| m.name() == "torque"));
+
+    let report = network.lint(&LintOptions::default());
+    let has = |report: &canzero_config::analysis::LintReport, subject: &str| {
+        report
+            .diagnostics()
+            .iter()
+            .any(|d| d.rule() == LintRule::ShortRxTimeout && d.subject() == subject)
+    };
+    assert!(has(&report, "secu::torque"));
+    assert!(!has(&report, "secu::status"));
+    assert!(has(&report, "master::get_req"));
+
+    let mut options = LintOptions::default();
+    options.set_rx_timeout_margin(1.1);
+    let report = network.lint(&options);
+    assert!(!has(&report, "secu::torque"));
+}
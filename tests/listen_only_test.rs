@@ -0,0 +1,60 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_stream("state").add_entry("position");
+    let logger = network_builder.create_node("logger");
+    logger.set_listen_only(true);
+    logger.receive_stream("secu", "state").map("position", "position");
+    network_builder
+}
+
+#[test]
+fn listen_only_node() {
+    let network = network_builder().build().unwrap();
+    let logger = network.node_by_name("logger").unwrap();
+    assert!(logger.is_listen_only());
+    assert!(!logger.has_od_protocol());
+    assert!(logger.tx_messages().is_empty());
+    assert!(logger.rx_messages().iter().any(|m| m.name() == "heartbeat"));
+    assert!(logger.rx_messages().iter().any(|m| m.name() == "secu_stream_state"));
+    assert!(!network.node_by_name("secu").unwrap().is_listen_only());
+
+    assert!(network.to_canonical_string().contains(" listen_only"));
+    let dbc = network.to_dbc();
+    assert!(dbc.contains("BA_ \"ListenOnly\" BU_ logger 1;"));
+    assert!(network.to_dot().contains("\"logger\" [style=dashed"));
+}
+
+#[test]
+fn exported_dbc_can_be_imported() {
+    let network = network_builder().build().unwrap();
+    let path = std::env::temp_dir().join("canzero_listen_only_test.dbc");
+    std::fs::write(&path, network.to_dbc()).unwrap();
+    let network_builder = NetworkBuilder::new();
+    let imported = network_builder.include_dbc("can0", path.to_str().unwrap());
+    std::fs::remove_file(&path).unwrap();
+    imported.unwrap();
+}
+
+#[test]
+fn listen_only_node_transmits() {
+    let network_builder = network_builder();
+    let logger = network_builder.find_node("logger").unwrap();
+    logger.create_object_entry("level", "u8");
+    logger.create_stream("status").add_entry("level");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidListenOnlyNode(reason)) if reason.contains("stream status")
+    ));
+
+    let network_builder = self::network_builder();
+    let message = network_builder.create_message("log_ack", None);
+    message.add_transmitter("logger");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidListenOnlyNode(reason)) if reason.contains("message log_ack")
+    ));
+}
@@ -0,0 +1,60 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, SignalType},
+    errors::ConfigError,
+};
+
+fn network_builder(dlc: Option<u8>) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("master");
+    network_builder.create_node("bms");
+    let limits = network_builder.create_message("limits", None);
+    limits.set_std_id(0x42);
+    limits.add_transmitter("master");
+    limits.add_receiver("bms");
+    limits
+        .make_signal_format()
+        .add_signal(Signal::create("current", None, SignalType::UnsignedInt { size: 12 }))
+        .unwrap();
+    if let Some(dlc) = dlc {
+        limits.set_dlc(dlc);
+    }
+    limits.set_fill(0xAA);
+    network_builder
+}
+
+#[test]
+fn fixed_dlc_and_fill() {
+    let network = network_builder(Some(8)).build().unwrap();
+    let limits = network.message_by_name("limits").unwrap();
+    assert_eq!(limits.dlc(), 8);
+    assert_eq!(limits.fill(), 0xAA);
+    // the lowest 12 bits are occupied by the signal.
+    assert_eq!(limits.fill_bits(), 0xAAAA_AAAA_AAAA_A000);
+    assert!(network.to_cpp().contains("uint64_t raw = 0xAAAAAAAAAAAAA000ull;"));
+    assert!(network.to_python().contains("raw = 0xAAAAAAAAAAAAA000"));
+    assert!(network.to_dbc().contains("BO_ 66 limits: 8 master"));
+    assert!(network.to_canonical_string().contains("message limits id=std:0x042 bus=can0 dlc=8 "));
+    assert!(network.to_canonical_string().contains("  fill 0xAA\n"));
+}
+
+#[test]
+fn fill_without_fixed_dlc() {
+    let network = network_builder(None).build().unwrap();
+    let limits = network.message_by_name("limits").unwrap();
+    assert_eq!(limits.dlc(), 2);
+    assert_eq!(limits.fill_bits(), 0xA000);
+}
+
+#[test]
+fn dlc_smaller_than_signals() {
+    match network_builder(Some(1)).build() {
+        Err(ConfigError::MessageTooLong(reason)) => assert!(reason.contains("limits"), "{reason}"),
+        other => panic!("expected MessageTooLong, got {other:?}"),
+    }
+    assert!(matches!(
+        network_builder(Some(9)).build(),
+        Err(ConfigError::InvalidRange(_))
+    ));
+}
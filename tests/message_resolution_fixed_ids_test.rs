@@ -3,7 +3,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use can_config_rs::{
+use canzero_config::{
     builder::{MessagePriority, NetworkBuilder},
     config::MessageId,
 };
@@ -66,7 +66,7 @@ fn check_builder(det: impl Fn() -> NetworkBuilder) {
 #[test]
 fn message_resolution_empty_config() {
     check_builder(|| {
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
 
         network_builder
     });
@@ -77,7 +77,7 @@ fn perf1(node_count: u32, non_message_count: u32, fixed_message_count: u32, ide:
         let mut node_gen = MessageNameGen::new();
         let mut name_gen = MessageNameGen::new();
 
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
         let mut message_ids_used: HashSet<u32> = HashSet::new();
         for _ in 0..node_count {
             let node_name = node_gen.next();
@@ -3,7 +3,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use can_config_rs::{
+use canzero_config::{
     builder::{MessagePriority, NetworkBuilder},
     config::MessageId,
 };
@@ -65,18 +65,18 @@ fn check_builder(det: impl Fn() -> NetworkBuilder) {
 #[test]
 fn message_resolution_empty_config() {
     check_builder(|| {
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
 
         network_builder
     });
 }
 
-fn perf1(node_count: u32, message_count: u32, priorities: Vec<MessagePriority>) {
+fn perf1(node_count: u32, message_count: u32) {
     check_builder(|| {
         let mut node_gen = MessageNameGen::new();
         let mut name_gen = MessageNameGen::new();
 
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
         for _ in 0..node_count {
             let node_name = node_gen.next();
             network_builder.create_node(&node_name);
@@ -98,44 +98,44 @@ fn perf1(node_count: u32, message_count: u32, priorities: Vec<MessagePriority>)
 
 #[test]
 fn message_resolution_1x1000_uniform() {
-    perf1(1, 1000, vec![MessagePriority::Normal]);
+    perf1(1, 1000);
 }
 
 #[test]
 fn message_resolution_1x2000_uniform() {
-    perf1(1, 2000, vec![MessagePriority::Normal]);
+    perf1(1, 2000);
 }
 
 
 #[test]
 fn message_resolution_2x500_normal() {
-    perf1(2, 500, vec![MessagePriority::Normal]);
+    perf1(2, 500);
 }
 
 
 #[test]
 fn message_resolution_2x1000_uniform() {
-    perf1(2, 1000, vec![MessagePriority::Normal]);
+    perf1(2, 1000);
 }
 
 #[test]
 fn message_resolution_4x500_uniform() {
-    perf1(4, 500, vec![MessagePriority::Normal]);
+    perf1(4, 500);
 }
 
 #[test]
 fn message_resolution_8x250_uniform() {
-    perf1(8, 250, vec![MessagePriority::Normal]);
+    perf1(8, 250);
 }
 
 #[test]
 fn message_resolution_16x125_uniform() {
-    perf1(16, 125, vec![MessagePriority::Normal]);
+    perf1(16, 125);
 }
 
 #[test]
 fn message_resolution_32x75_uniform() {
-    perf1(32, 75, vec![MessagePriority::Normal]);
+    perf1(32, 75);
 }
 
 
@@ -144,11 +144,7 @@ fn message_resolution_000_fixed_1to16x0to200() {
     for node_count in 1..16 {
         let max_msg_count = (200f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -159,11 +155,7 @@ fn message_resolution_000_fixed_1to16x0to400() {
     for node_count in 1..16 {
         let max_msg_count = (400f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -173,11 +165,7 @@ fn message_resolution_000_fixed_1to16x0to600() {
     for node_count in 1..16 {
         let max_msg_count = (600f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -187,11 +175,7 @@ fn message_resolution_000_fixed_1to16x0to800() {
     for node_count in 1..16 {
         let max_msg_count = (800f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -201,11 +185,7 @@ fn message_resolution_000_fixed_1to16x0to1000() {
     for node_count in 1..16 {
         let max_msg_count = (1000f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -215,11 +195,7 @@ fn message_resolution_000_fixed_1to16x0to1200() {
     for node_count in 1..16 {
         let max_msg_count = (1200f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -229,11 +205,7 @@ fn message_resolution_000_fixed_1to16x0to1400() {
     for node_count in 1..16 {
         let max_msg_count = (1400f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -243,11 +215,7 @@ fn message_resolution_000_fixed_1to16x0to1600() {
     for node_count in 1..16 {
         let max_msg_count = (1600f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -257,11 +225,7 @@ fn message_resolution_000_fixed_1to16x0to1800() {
     for node_count in 1..16 {
         let max_msg_count = (1800f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -271,11 +235,7 @@ fn message_resolution_000_fixed_1to16x0to2000() {
     for node_count in 1..16 {
         let max_msg_count = (2000f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
-                node_count,
-                msg_count,
-                vec![],
-            );
+            perf1(node_count, msg_count);
         }
     }
 }
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use canzero_config::{builder::{MessagePriority, NetworkBuilder}, config::message::MessageUsage};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    network_builder.create_node("master");
+    for name in ["status", "fault", "trace", "flash_block"] {
+        let message = network_builder.create_message(name, Some(Duration::from_millis(20)));
+        message.add_transmitter("secu");
+        message.add_receiver("master");
+        message.set_any_std_id(MessagePriority::Normal);
+    }
+    network_builder
+}
+
+#[test]
+fn message_categories() {
+    let network_builder = network_builder();
+    network_builder.find_message("fault").unwrap().set_emergency();
+    network_builder.find_message("trace").unwrap().set_debug();
+    network_builder.find_message("flash_block").unwrap().set_bootloader();
+    let network = network_builder.build().unwrap();
+    let usage = |name: &str| {
+        network
+            .messages()
+            .iter()
+            .find(|message| message.name() == name)
+            .unwrap()
+            .usage()
+            .clone()
+    };
+    assert!(matches!(usage("status"), MessageUsage::External { .. }));
+    assert!(matches!(usage("fault"), MessageUsage::Emergency));
+    assert!(matches!(
+        usage("trace"),
+        MessageUsage::Debug { interval: Some(interval) } if interval == Duration::from_millis(20)
+    ));
+    assert!(matches!(usage("flash_block"), MessageUsage::Bootloader));
+
+    let canonical = network.to_canonical_string();
+    assert!(canonical.contains("emergency"));
+    assert!(canonical.contains("debug:20ms"));
+    assert!(canonical.contains("bootloader"));
+}
+
+#[test]
+fn emergency_wins_arbitration() {
+    let network_builder = network_builder();
+    network_builder.find_message("fault").unwrap().set_emergency();
+    let network = network_builder.build().unwrap();
+    let id = |name: &str| {
+        let message = network.messages().iter().find(|message| message.name() == name).unwrap();
+        message.id().as_u32()
+    };
+    assert!(id("fault") < id("status"));
+    assert!(id("fault") < id("trace"));
+}
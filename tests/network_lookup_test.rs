@@ -0,0 +1,56 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn lookups() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    let torque = network_builder.create_message("torque", None);
+    torque.set_std_id(0x42);
+    torque.make_type_format().add_type("u16", "value");
+    torque.add_transmitter("secu");
+    let network = network_builder.build().unwrap();
+
+    let secu = network.node_by_name("secu").unwrap();
+    assert_eq!(secu.name(), "secu");
+    assert!(network.node_by_name("pdu").is_none());
+
+    let torque = network.message_by_name("torque").unwrap();
+    let by_id = network.message_by_id(*torque.id(), torque.bus()).unwrap();
+    assert_eq!(by_id.name(), "torque");
+    for message in network.messages() {
+        let found = network.message_by_id(*message.id(), message.bus()).unwrap();
+        assert_eq!(found.name(), message.name());
+    }
+
+    assert_eq!(network.type_by_name("state").unwrap().name(), "state");
+    assert!(network.type_by_name("status").is_none());
+
+    for object_entry in secu.object_entries() {
+        let found = secu.object_entry_by_index(object_entry.id()).unwrap();
+        assert_eq!(found.name(), object_entry.name());
+    }
+    assert!(secu.object_entry_by_index(0xFFFF).is_none());
+}
+
+#[test]
+fn bus_lookup() {
+    let network_builder = NetworkBuilder::new();
+    let can0 = network_builder.create_bus("can0", Some(1000000));
+    can0.add_description("Powertrain");
+    can0.set_connector("X12");
+    network_builder.create_bus("can1", Some(500000));
+    let network = network_builder.build().unwrap();
+
+    let can0 = network.bus_by_name("can0").unwrap();
+    assert_eq!(can0.name(), "can0");
+    assert_eq!(can0.description(), Some("Powertrain"));
+    assert_eq!(can0.connector(), Some("X12"));
+    let can1 = network.bus_by_name("can1").unwrap();
+    assert_eq!(can1.baudrate(), 500000);
+    assert_eq!(can1.connector(), None);
+    assert!(network.bus_by_name("can2").is_none());
+    assert!(network.to_string().contains("can0 (id 0)\n      connector : X12"));
+}
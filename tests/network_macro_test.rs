@@ -0,0 +1,44 @@
+use canzero_config::network;
+
+#[test]
+fn network_macro() {
+    let network_builder = network! {
+        enum state { idle, running = 4 }
+        struct status { state: state, temperature: "d8<0..100>" }
+        node secu {
+            object_entry state: state;
+            object_entry status: status;
+            stream state { state, status }
+            command reset(delay: u16) -> master;
+        }
+        node master {
+            receive secu::state;
+        }
+        message dyno_torque: 0x50 { torque: u16, load: "d8<0..1>" } master -> secu;
+    }
+    .unwrap();
+    let network = network_builder.build().unwrap();
+
+    assert!(network.types().iter().any(|t| t.name() == "state"));
+    assert!(network.types().iter().any(|t| t.name() == "status"));
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert!(secu.object_entries().iter().any(|oe| oe.name() == "status"));
+    assert_eq!(secu.tx_streams().len(), 1);
+    assert_eq!(secu.commands().len(), 1);
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert_eq!(master.rx_streams().len(), 1);
+    let dyno = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "dyno_torque")
+        .unwrap();
+    assert_eq!(dyno.signals().len(), 2);
+}
+
+#[test]
+fn network_macro_errors() {
+    let result = network! {
+        enum state { idle, idle }
+    };
+    assert!(result.is_err());
+}
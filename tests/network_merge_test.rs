@@ -0,0 +1,169 @@
+use canzero_config::{
+    builder::{bus::BusBuilder, MessagePriority, NetworkBuilder, ObjectEntryBuilder},
+    config::ObjectEntryAccess,
+    errors::ConfigError,
+};
+
+fn propulsion() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let motor = network_builder.create_node("motor");
+    let state = network_builder.define_enum("motor_state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Running", None).unwrap();
+    motor.create_object_entry("state", "motor_state");
+    let stream = motor.create_stream("state");
+    stream.add_entry("state");
+    stream.set_priority(MessagePriority::High);
+    let cmd = network_builder.create_message("motor_cmd", None);
+    cmd.set_any_std_id(MessagePriority::Normal);
+    cmd.add_receiver("motor");
+    cmd.add_transmitter("master");
+    network_builder
+}
+
+#[test]
+fn merge_disjoint_networks() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let master = network_builder.create_node("master");
+    master.create_object_entry("velocity", "d16<0..10>");
+
+    network_builder.merge(propulsion()).unwrap();
+    let network = network_builder.build().unwrap();
+
+    assert_eq!(network.nodes().len(), 2);
+    assert_eq!(network.buses().len(), 1);
+    let master = network
+        .nodes()
+        .iter()
+        .find(|n| n.name() == "master")
+        .unwrap();
+    assert!(master.tx_messages().iter().any(|m| m.name() == "motor_cmd"));
+    assert!(master
+        .object_entries()
+        .iter()
+        .any(|oe| oe.name() == "velocity"));
+    let motor = network.nodes().iter().find(|n| n.name() == "motor").unwrap();
+    assert!(motor.tx_streams().iter().any(|s| s.name() == "state"));
+    assert!(motor.rx_messages().iter().any(|m| m.name() == "get_req"));
+    assert_eq!(
+        network
+            .types()
+            .iter()
+            .filter(|t| t.name() == "get_resp_header")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn merge_detects_message_collision() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_message("motor_cmd", None);
+    match network_builder.merge(propulsion()) {
+        Err(ConfigError::DuplicatedMessage(_)) => (),
+        other => panic!("expected a message collision, got {other:?}"),
+    }
+}
+
+#[test]
+fn merge_detects_type_collision() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("motor_state");
+    state.add_entry("Off", None).unwrap();
+    match network_builder.merge(propulsion()) {
+        Err(ConfigError::DuplicatedType(_)) => (),
+        other => panic!("expected a type collision, got {other:?}"),
+    }
+}
+
+#[test]
+fn merge_detects_access_level_conflict() {
+    let base = || {
+        let network_builder = NetworkBuilder::new();
+        network_builder.define_access_level("operator", None);
+        network_builder.define_access_level("engineer", Some("calibration"));
+        network_builder
+    };
+
+    let other = propulsion();
+    other.define_access_level("operator", Some("driving"));
+    base().merge(other).unwrap();
+
+    let other = propulsion();
+    other.define_access_level("engineer", None);
+    match base().merge(other) {
+        Err(ConfigError::DuplicatedAccessLevel(_)) => (),
+        other => panic!("expected an access level conflict, got {other:?}"),
+    }
+
+    let other = propulsion();
+    other.define_access_level("operator", None);
+    other.define_access_level("engineer", Some("service"));
+    match base().merge(other) {
+        Err(ConfigError::DuplicatedAccessLevel(_)) => (),
+        other => panic!("expected an access level conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn merge_detects_bus_conflict() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None).set_sjw(2);
+    network_builder.merge(propulsion()).unwrap();
+    let network = network_builder.build().unwrap();
+    assert_eq!(network.buses().len(), 1);
+    assert_eq!(network.buses()[0].baudrate(), 1000000);
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500000));
+    match network_builder.merge(propulsion()) {
+        Err(ConfigError::DuplicatedBus(_)) => (),
+        other => panic!("expected a bus conflict, got {other:?}"),
+    }
+}
+
+#[test]
+fn merge_detects_object_entry_conflict() {
+    let with_motor_state = || {
+        let network_builder = NetworkBuilder::new();
+        let state = network_builder.define_enum("motor_state");
+        state.add_entry("Idle", None).unwrap();
+        state.add_entry("Running", None).unwrap();
+        network_builder.create_node("motor").create_object_entry("state", "motor_state");
+        network_builder
+    };
+    with_motor_state().merge(propulsion()).unwrap();
+
+    let conflicts: [(_, fn(&ObjectEntryBuilder)); 4] = [
+        ("access", |oe| oe.set_access(ObjectEntryAccess::Local)),
+        ("default value", |oe| oe.set_default("Running")),
+        ("persistence", |oe| oe.set_persistent(true)),
+        ("index", |oe| oe.set_index(7)),
+    ];
+    for (property, modify) in conflicts {
+        let network_builder = with_motor_state();
+        let motor = network_builder.create_node("motor");
+        modify(&motor.0.borrow().object_entries[0]);
+        match network_builder.merge(propulsion()) {
+            Err(ConfigError::DuplicatedObjectEntry(msg)) => assert!(msg.contains(property), "{msg}"),
+            other => panic!("expected an object entry conflict on the {property}, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn merge_detects_unregistered_bus() {
+    let other = propulsion();
+    other
+        .create_node("motor")
+        .0
+        .borrow_mut()
+        .buses
+        .push(BusBuilder::new("ghost_bus", 42, None));
+    match NetworkBuilder::new().merge(other) {
+        Err(ConfigError::BusNotFound(msg)) => assert!(msg.contains("ghost_bus"), "{msg}"),
+        other => panic!("expected BusNotFound, got {other:?}"),
+    }
+}
@@ -0,0 +1,60 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, Version},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("secu");
+    network_builder
+}
+
+fn network() -> NetworkRef {
+    let network_builder = network_builder();
+    network_builder.set_name("pod");
+    network_builder.set_description("configuration of the pod");
+    network_builder.set_version("v2.3.1");
+    network_builder.set_author("mu-zero");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn metadata_is_carried_onto_network() {
+    let network = network();
+    assert_eq!(network.name(), Some("pod"));
+    assert_eq!(network.description(), Some("configuration of the pod"));
+    assert_eq!(network.version(), Some(&Version::new(2, 3, 1)));
+    assert_eq!(network.author(), Some("mu-zero"));
+
+    let unnamed = network_builder().build().unwrap();
+    assert_eq!(unnamed.name(), None);
+    assert_eq!(unnamed.version(), None);
+    assert_eq!(unnamed.config_hash(), network.config_hash());
+    assert_eq!(network.prune().0.name(), Some("pod"));
+}
+
+#[test]
+fn invalid_version_is_rejected() {
+    let network_builder = network_builder();
+    network_builder.set_version("2.x");
+    match network_builder.build() {
+        Err(ConfigError::InvalidVersion(reason)) => assert!(reason.contains("network"), "{reason}"),
+        other => panic!("expected InvalidVersion, got {other:?}"),
+    }
+}
+
+#[test]
+fn exporters_emit_metadata() {
+    let network = network();
+    let canonical = network.to_canonical_string();
+    assert!(canonical.contains("\nname \"pod\"\nversion v2.3.1\nauthor \"mu-zero\"\n"));
+    let kcd = network.to_kcd();
+    assert!(kcd.contains("<Document name=\"pod\" version=\"v2.3.1\" author=\"mu-zero\""));
+    assert!(kcd.contains(">configuration of the pod</Document>"));
+    let dbc = network.to_dbc();
+    assert!(dbc.starts_with("VERSION \"v2.3.1\"\n"));
+    assert!(dbc.contains("CM_ \"configuration of the pod\";"));
+    assert!(network.to_sym().contains("Title=\"pod\""));
+}
@@ -0,0 +1,55 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{signal::Signal, SignalType},
+};
+
+#[test]
+fn network_stats() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("master");
+    network_builder.create_node("bms");
+    let limits = network_builder.create_message("limits", None);
+    limits.set_std_id(0x42);
+    limits.add_transmitter("master");
+    limits.add_receiver("bms");
+    let format = limits.make_signal_format();
+    for name in ["current", "voltage", "temperature"] {
+        format
+            .add_signal(Signal::create(name, None, SignalType::UnsignedInt { size: 16 }))
+            .unwrap();
+    }
+    let state = network_builder.create_message("state", None);
+    state.set_std_id(0x700);
+    state.add_transmitter("bms");
+    state.add_receiver("master");
+    state
+        .make_signal_format()
+        .add_signal(Signal::create("state", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    let network = network_builder.build().unwrap();
+    let stats = network.stats();
+
+    assert_eq!(stats.message_count(), network.messages().len());
+    assert_eq!(stats.dlc_histogram().iter().sum::<usize>(), network.messages().len());
+    assert!(stats.dlc_histogram()[6] >= 1);
+    assert_eq!(stats.buses().len(), 1);
+    assert_eq!(stats.buses()[0].bus(), "can0");
+    assert_eq!(stats.buses()[0].messages(), network.messages().len());
+    assert_eq!(stats.buses()[0].signals(), stats.signal_count());
+    assert!(stats.signals_per_message().max() >= 3);
+    assert_eq!(
+        stats.object_entries_per_node().iter().map(|(node, _)| node.as_str()).collect::<Vec<_>>(),
+        network.nodes().iter().map(|node| node.name()).collect::<Vec<_>>()
+    );
+
+    let std_ranges: Vec<_> = stats.id_utilization().iter().filter(|range| !range.ide()).collect();
+    assert_eq!(std_ranges.len(), MessagePriority::count());
+    assert_eq!(std_ranges[0].priority(), MessagePriority::Realtime);
+    assert_eq!(std_ranges[0].first(), 0);
+    assert_eq!(std_ranges.last().unwrap().last(), 0x7FF);
+    let used: usize = stats.id_utilization().iter().map(|range| range.used()).sum();
+    assert!(used >= 2);
+    assert!(std_ranges.last().unwrap().used() >= 1);
+    assert!(std_ranges[0].utilization() > 0.0);
+}
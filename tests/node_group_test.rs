@@ -0,0 +1,61 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+#[test]
+fn subsystems() {
+    let network_builder = NetworkBuilder::new();
+    let propulsion = network_builder.create_group("propulsion");
+    propulsion.add_description("everything that moves the pod");
+    propulsion.add_node("motor_driver");
+    propulsion.add_node("pdu");
+    let telemetry = network_builder.create_group("telemetry");
+    telemetry.add_node("telemetry_board");
+    telemetry.add_node("pdu");
+    assert!(network_builder.find_group("propulsion").is_some());
+    assert_eq!(network_builder.node_groups().len(), 2);
+
+    let network = network_builder.build().unwrap();
+    let propulsion = network.group_by_name("propulsion").unwrap();
+    assert_eq!(propulsion.description(), Some("everything that moves the pod"));
+    let members: Vec<&str> = propulsion.nodes().iter().map(|n| n.name()).collect();
+    assert_eq!(members, vec!["motor_driver", "pdu"]);
+    assert!(network.group_by_name("levitation").is_none());
+
+    let pdu = network.node_by_name("pdu").unwrap();
+    let groups: Vec<&str> = network.groups_of(pdu).iter().map(|g| g.name()).collect();
+    assert_eq!(groups, vec!["propulsion", "telemetry"]);
+}
+
+#[test]
+fn group_addressed_commands() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    let emergency = master.create_command("emergency", None);
+    emergency.add_callee_group("propulsion");
+    // nodes can join the group after the command was created.
+    let propulsion = network_builder.create_group("propulsion");
+    propulsion.add_node("motor_driver");
+    propulsion.add_node("pdu");
+    propulsion.add_node("master");
+    emergency.add_callee("pdu");
+
+    let network = network_builder.build().unwrap();
+    for name in ["motor_driver", "pdu"] {
+        let node = network.node_by_name(name).unwrap();
+        let extern_commands: Vec<&str> =
+            node.extern_commands().iter().map(|(_, c)| c.name()).collect();
+        assert_eq!(extern_commands, vec!["emergency"], "{name}");
+    }
+    let master = network.node_by_name("master").unwrap();
+    assert!(master.extern_commands().is_empty());
+}
+
+#[test]
+fn undefined_callee_group() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    master.create_command("emergency", None).add_callee_group("levitation");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::UndefinedGroup(_))
+    ));
+}
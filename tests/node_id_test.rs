@@ -0,0 +1,86 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{Network, Node, Type},
+    errors::ConfigError,
+};
+
+fn node<'a>(network: &'a Network, name: &str) -> &'a Node {
+    network.nodes().iter().find(|node| node.name() == name).unwrap()
+}
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder
+}
+
+#[test]
+fn pinned_ids_survive_reordering() {
+    let network_builder = network_builder();
+    network_builder.create_node("master");
+    network_builder.create_node("secu").set_node_id(7);
+    network_builder.create_node("motor");
+    let network = network_builder.build().unwrap();
+    assert_eq!(node(&network, "master").id(), 0);
+    assert_eq!(node(&network, "secu").id(), 7);
+    assert_eq!(node(&network, "motor").id(), 1);
+
+    let reordered = crate::network_builder();
+    reordered.create_node("secu").set_node_id(7);
+    reordered.create_node("master").set_node_id(0);
+    reordered.create_node("motor").set_node_id(1);
+    let reordered = reordered.build().unwrap();
+    for name in ["master", "secu", "motor"] {
+        assert_eq!(node(&network, name).id(), node(&reordered, name).id());
+    }
+    match reordered.type_by_name("node_id").map(|ty| ty as &Type) {
+        Some(Type::Enum { entries, .. }) => {
+            assert!(entries.contains(&("secu".to_owned(), 7)));
+            assert!(entries.contains(&("motor".to_owned(), 1)));
+        }
+        other => panic!("expected enum node_id, got {other:?}"),
+    }
+}
+
+#[test]
+fn colliding_ids_are_rejected() {
+    let network_builder = network_builder();
+    network_builder.create_node("master").set_node_id(3);
+    network_builder.create_node("secu").set_node_id(3);
+    match network_builder.build() {
+        Err(ConfigError::DuplicatedNodeId(reason)) => {
+            assert!(reason.contains("master") && reason.contains("secu"), "{reason}")
+        }
+        other => panic!("expected DuplicatedNodeId, got {other:?}"),
+    }
+}
+
+#[test]
+fn node_id_width_limits_ids() {
+    let network_builder = network_builder();
+    network_builder.set_node_id_width(4);
+    network_builder.create_node("master");
+    network_builder.create_node("secu");
+    network_builder.create_group("all").add_node("secu");
+    let network = network_builder.build().unwrap();
+    assert_eq!(network.node_id_width(), 4);
+    assert_eq!(network.broadcast_server_id(), 15);
+    assert_eq!(network.node_groups()[0].server_id(), 14);
+    assert_eq!(network.server_ids(node(&network, "secu")), vec![1, 14, 15]);
+
+    let network_builder = crate::network_builder();
+    network_builder.set_node_id_width(4);
+    network_builder.create_node("master").set_node_id(15);
+    match network_builder.build() {
+        Err(ConfigError::InvalidNodeId(reason)) => assert!(reason.contains("master"), "{reason}"),
+        other => panic!("expected InvalidNodeId, got {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_node_id_width() {
+    let network_builder = network_builder();
+    network_builder.set_node_id_width(9);
+    network_builder.create_node("master");
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidRange(_))));
+}
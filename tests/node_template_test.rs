@@ -0,0 +1,40 @@
+use canzero_config::builder::{NetworkBuilder, NodeTemplate};
+
+#[test]
+fn wheel_instances() {
+    let network_builder = NetworkBuilder::new();
+    let wheel = NodeTemplate::new(|node| {
+        node.create_object_entry("speed", "d16<0..100>");
+        node.create_object_entry("gain", "d16<0..10>");
+        let stream = node.create_stream("state");
+        stream.add_entry("speed");
+    });
+    let wheels = wheel.instantiate_with(&network_builder, "wheel", 4, |index, node| {
+        let gain = node.create_object_entry("gain", "d16<0..10>");
+        gain.set_default(&format!("{}", index + 1));
+    });
+    assert_eq!(wheels.len(), 4);
+    let master = network_builder.create_node("master");
+    for index in 0..4 {
+        master.receive_stream(&format!("wheel_{index}"), "state");
+    }
+    let network = network_builder.build().unwrap();
+
+    let mut ids = vec![];
+    for index in 0..4 {
+        let node = network
+            .nodes()
+            .iter()
+            .find(|n| n.name() == format!("wheel_{index}"))
+            .unwrap();
+        ids.push(node.id());
+        assert_eq!(node.tx_streams().len(), 1);
+        let gain = node.object_entries().iter().find(|oe| oe.name() == "gain").unwrap();
+        assert_eq!(gain.default_value(), Some(format!("{}", index + 1).as_str()));
+    }
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert_eq!(master.rx_streams().len(), 4);
+}
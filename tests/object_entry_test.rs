@@ -0,0 +1,213 @@
+use canzero_config::{
+    builder::{NetworkBuilder, ObjectEntryIndexAssignment},
+    config::{ObjectEntryAccess, OdFrameKind},
+    errors::ConfigError,
+};
+
+fn od_index(network: &canzero_config::config::Network, node: &str, oe: &str) -> u32 {
+    network
+        .nodes()
+        .iter()
+        .find(|n| n.name() == node)
+        .unwrap()
+        .object_entries()
+        .iter()
+        .find(|e| e.name() == oe)
+        .unwrap()
+        .id()
+}
+
+#[test]
+fn pinned_index_is_kept() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("a", "u8");
+    secu.create_object_entry("b", "u8").set_index(2);
+    secu.create_object_entry("c", "u8");
+    let network = network_builder.build().unwrap();
+    assert_eq!(od_index(&network, "secu", "config_hash"), 0);
+    assert_eq!(od_index(&network, "secu", "build_time"), 1);
+    assert_eq!(od_index(&network, "secu", "b"), 2);
+    assert_eq!(od_index(&network, "secu", "a"), 3);
+    assert_eq!(od_index(&network, "secu", "c"), 4);
+}
+
+#[test]
+fn pinned_index_collision() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("a", "u8").set_index(7);
+    secu.create_object_entry("b", "u8").set_index(7);
+    match network_builder.build() {
+        Err(ConfigError::DuplicatedObjectEntryIndex(_)) => (),
+        other => panic!("expected index collision, got {other:?}"),
+    }
+}
+
+#[test]
+fn hash_based_indices_are_stable() {
+    let build = |with_extra: bool| {
+        let network_builder = NetworkBuilder::new();
+        network_builder.set_object_entry_index_assignment(ObjectEntryIndexAssignment::HashBased);
+        let secu = network_builder.create_node("secu");
+        if with_extra {
+            secu.create_object_entry("inserted", "u8");
+        }
+        secu.create_object_entry("velocity", "u8");
+        network_builder.build().unwrap()
+    };
+    let a = build(false);
+    let b = build(true);
+    assert_eq!(
+        od_index(&a, "secu", "velocity"),
+        od_index(&b, "secu", "velocity")
+    );
+}
+
+#[test]
+fn default_values_are_validated() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let state = network_builder.define_enum("secu_state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Running", None).unwrap();
+    let threshold = secu.create_object_entry("threshold", "d8<0..10>");
+    threshold.set_default("2.5");
+    threshold.set_persistent(true);
+    secu.create_object_entry("state", "secu_state").set_default("Idle");
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let threshold = secu
+        .object_entries()
+        .iter()
+        .find(|e| e.name() == "threshold")
+        .unwrap();
+    assert_eq!(threshold.default_value(), Some("2.5"));
+    assert!(threshold.persistent());
+
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("count", "u8").set_default("256");
+    match network_builder.build() {
+        Err(ConfigError::InvalidDefaultValue(_)) => (),
+        other => panic!("expected an invalid default value, got {other:?}"),
+    }
+}
+
+#[test]
+fn protected_entries_require_defined_access_level() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_access_level("operator", None);
+    network_builder.define_access_level("engineer", Some("calibration"));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("gain", "u8")
+        .set_access(ObjectEntryAccess::Protected {
+            level: "engineer".to_owned(),
+        });
+    let network = network_builder.build().unwrap();
+    let engineer = network
+        .access_levels()
+        .iter()
+        .find(|l| l.name() == "engineer")
+        .unwrap();
+    assert_eq!(engineer.rank(), 1);
+
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("gain", "u8")
+        .set_access(ObjectEntryAccess::Protected {
+            level: "root".to_owned(),
+        });
+    match network_builder.build() {
+        Err(ConfigError::UndefinedAccessLevel(_)) => (),
+        other => panic!("expected an undefined access level, got {other:?}"),
+    }
+}
+
+#[test]
+fn access_level_ranks_fit_into_a_byte() {
+    let network_builder = NetworkBuilder::new();
+    for i in 0..=256 {
+        network_builder.define_access_level(&format!("level{i}"), None);
+    }
+    match network_builder.build() {
+        Err(ConfigError::InvalidRange(_)) => (),
+        other => panic!("expected too many access levels, got {other:?}"),
+    }
+}
+
+#[test]
+fn object_entry_groups() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("setpoint", "d16<0..10>");
+    let gains = secu.create_object_entry_group("pid_gains");
+    gains.add_description("gains of the airgap controller");
+    gains.create_object_entry("kp", "d16<0..10>");
+    gains.create_object_entry("ki", "d16<0..10>");
+    gains.add_entry("kp").unwrap();
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.object_entry_groups().len(), 1);
+    let gains = &secu.object_entry_groups()[0];
+    assert_eq!(gains.name(), "pid_gains");
+    let names: Vec<&str> = gains.object_entries().iter().map(|oe| oe.name()).collect();
+    assert_eq!(names, vec!["kp", "ki"]);
+
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("kp", "u8");
+    secu.create_object_entry_group("a").add_entry("kp").unwrap();
+    secu.create_object_entry_group("b").add_entry("kp").unwrap();
+    match secu.create_object_entry_group("a").add_entry("ki") {
+        Err(ConfigError::UndefinedObjectEntry(msg)) => assert!(msg.contains("secu::ki"), "{msg}"),
+        other => panic!("expected an undefined object entry, got {other:?}"),
+    }
+    match network_builder.build() {
+        Err(ConfigError::DuplicatedObjectEntry(_)) => (),
+        other => panic!("expected a duplicated object entry, got {other:?}"),
+    }
+}
+
+#[test]
+fn access_plan_fragments() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("small", "u8");
+    let wide = network_builder.define_struct("wide");
+    wide.add_attribute("a", "u32").unwrap();
+    wide.add_attribute("b", "u32").unwrap();
+    wide.add_attribute("c", "u8").unwrap();
+    secu.create_object_entry("wide", "wide");
+    secu.create_object_entry("fixed", "u8").set_access(ObjectEntryAccess::Const);
+    let network = network_builder.build().unwrap();
+    let node = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let entry = |name: &str| node.object_entries().iter().find(|e| e.name() == name).unwrap();
+
+    let plan = entry("small").access_plan().unwrap();
+    let kinds: Vec<OdFrameKind> = plan.read().iter().map(|f| f.kind()).collect();
+    assert_eq!(kinds, vec![OdFrameKind::GetReq, OdFrameKind::GetResp]);
+    assert_eq!(plan.read()[0].sof(), None);
+    assert_eq!(plan.read()[1].data_bits(), Some(&(0..8)));
+    assert!(plan.read().iter().all(|f| f.server_id() == node.id()));
+
+    let plan = entry("wide").access_plan().unwrap();
+    let write = plan.write().unwrap();
+    let frames: Vec<_> = write
+        .iter()
+        .map(|f| (f.kind(), f.sof(), f.eof(), f.toggle(), f.data_bits().cloned()))
+        .collect();
+    assert_eq!(
+        frames,
+        vec![
+            (OdFrameKind::SetReq, Some(true), Some(false), Some(false), Some(0..32)),
+            (OdFrameKind::SetReq, Some(false), Some(false), Some(true), Some(32..64)),
+            (OdFrameKind::SetReq, Some(false), Some(true), Some(false), Some(64..72)),
+            (OdFrameKind::SetResp, None, None, None, None),
+        ]
+    );
+    assert_eq!(plan.read().len(), 4);
+    assert!(write.iter().all(|f| f.od_index() == entry("wide").id()));
+
+    assert!(entry("fixed").access_plan().unwrap().write().is_none());
+}
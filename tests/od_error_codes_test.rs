@@ -0,0 +1,80 @@
+use canzero_config::{
+    builder::{NetworkBuilder, TypeBuilder, STANDARD_OD_ERROR_CODES},
+    config::{Network, Type},
+    errors::ConfigError,
+};
+
+fn erno_entries(network: &Network, name: &str) -> (u8, Vec<(String, u64)>) {
+    match network.type_by_name(name).map(|ty| ty as &Type) {
+        Some(Type::Enum { size, entries, .. }) => (*size, entries.clone()),
+        other => panic!("expected enum {name}, got {other:?}"),
+    }
+}
+
+#[test]
+fn standard_error_codes() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let network = network_builder.build().unwrap();
+    let (size, entries) = erno_entries(&network, "set_resp_erno");
+    assert_eq!(size, 3);
+    let expected: Vec<(String, u64)> = STANDARD_OD_ERROR_CODES
+        .iter()
+        .enumerate()
+        .map(|(value, (name, _))| (name.to_string(), value as u64))
+        .collect();
+    assert_eq!(entries, expected);
+    assert_eq!(entries[0], ("Success".to_owned(), 0));
+    assert_eq!(entries[1], ("Error".to_owned(), 1));
+}
+
+#[test]
+fn custom_error_codes_extend_both_enums() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let od_error_codes = network_builder.od_error_codes().unwrap();
+    od_error_codes.add_code("SensorFault", None).unwrap();
+    od_error_codes
+        .add_code_description("SensorFault", "the sensor doesn't respond")
+        .unwrap();
+    od_error_codes.add_code("Busy", Some(12)).unwrap();
+    assert_eq!(od_error_codes.codes().len(), STANDARD_OD_ERROR_CODES.len() + 2);
+    match od_error_codes.add_code("Timeout", None) {
+        Err(ConfigError::DuplicatedEnumEntry(name)) => assert_eq!(name, "Timeout"),
+        other => panic!("expected DuplicatedEnumEntry, got {other:?}"),
+    }
+
+    let network = network_builder.build().unwrap();
+    for name in ["get_resp_erno", "set_resp_erno"] {
+        let (size, entries) = erno_entries(&network, name);
+        assert_eq!(size, 4);
+        assert!(entries.contains(&("SensorFault".to_owned(), 6)));
+        assert!(entries.contains(&("Busy".to_owned(), 12)));
+    }
+}
+
+#[test]
+fn error_codes_stay_consistent() {
+    let network_builder = NetworkBuilder::new();
+    let od_error_codes = network_builder.od_error_codes().unwrap();
+    // a code, which only exists in one of the enums, isn't added to the other one.
+    match network_builder.find_type("set_resp_erno") {
+        Some(TypeBuilder::Enum(set_resp_erno)) => set_resp_erno.add_entry("Busy", None).unwrap(),
+        other => panic!("expected enum set_resp_erno, got {other:?}"),
+    }
+    assert!(matches!(
+        od_error_codes.add_code("Busy", None),
+        Err(ConfigError::DuplicatedEnumEntry(_))
+    ));
+    assert_eq!(od_error_codes.codes().len(), STANDARD_OD_ERROR_CODES.len());
+    assert!(matches!(
+        od_error_codes.add_code_description("Busy", "the server is busy"),
+        Err(ConfigError::UndefinedEnumEntry(_))
+    ));
+
+    network_builder.replace_type("get_resp_erno", "u8").unwrap();
+    assert!(matches!(
+        network_builder.od_error_codes(),
+        Err(ConfigError::UndefinedType(_))
+    ));
+}
@@ -0,0 +1,86 @@
+use canzero_config::{builder::NetworkBuilder, config::Type, errors::ConfigError};
+
+fn od_index_size(network: &canzero_config::config::Network, header: &str) -> u32 {
+    match network.type_by_name(header).map(|ty| ty as &Type) {
+        Some(Type::Struct { attribs, .. }) => attribs
+            .iter()
+            .find(|(name, _)| name == "od_index")
+            .unwrap()
+            .1
+            .size(),
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn default_width() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let network = network_builder.build().unwrap();
+    assert_eq!(network.od_index_width(), 13);
+}
+
+#[test]
+fn narrow_width() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_od_index_width(4);
+    let secu = network_builder.create_node("secu");
+    // every node defines two object entries of its own.
+    for i in 0..14 {
+        secu.create_object_entry(&format!("entry{i}"), "u8");
+    }
+    let network = network_builder.build().unwrap();
+    assert_eq!(network.od_index_width(), 4);
+    for header in ["get_req_header", "get_resp_header", "set_req_header", "set_resp_header"] {
+        assert_eq!(od_index_size(&network, header), 4, "{header}");
+    }
+    let get_req = network.message_by_name("get_req").unwrap();
+    assert_eq!(get_req.dlc(), 3);
+}
+
+#[test]
+fn too_many_object_entries() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_od_index_width(4);
+    let secu = network_builder.create_node("secu");
+    for i in 0..15 {
+        secu.create_object_entry(&format!("entry{i}"), "u8");
+    }
+    match network_builder.build() {
+        Err(ConfigError::InvalidObjectEntryIndex(msg)) => {
+            assert!(msg.contains("secu") && msg.contains("4 bit"), "{msg}")
+        }
+        other => panic!("expected InvalidObjectEntryIndex, got {other:?}"),
+    }
+}
+
+#[test]
+fn invalid_width() {
+    for width in [0, 14, 32] {
+        let network_builder = NetworkBuilder::new();
+        network_builder.set_od_index_width(width);
+        assert!(
+            matches!(network_builder.build(), Err(ConfigError::InvalidRange(_))),
+            "{width}"
+        );
+    }
+}
+
+#[test]
+fn width_fits_into_the_eds_manufacturer_area() {
+    // 4 bit node_ids would leave 21 bits for the od_index, but wider
+    // indices than 14 bits exceed 0x5FFF in EDS exports.
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_node_id_width(4);
+    network_builder.set_od_index_width(15);
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidRange(_))));
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_node_id_width(4);
+    network_builder.set_od_index_width(14);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u8").set_index(0x3FFF);
+    let network = network_builder.build().unwrap();
+    let eds = network.node_by_name("secu").unwrap().to_eds().unwrap();
+    assert!(eds.contains("[5FFF]\nParameterName=position\n"), "{eds}");
+}
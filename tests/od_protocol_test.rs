@@ -0,0 +1,85 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{Network, Node, ObjectEntryAccess},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("pressure", "u16");
+    sensor.create_stream("state").add_entry("pressure");
+    sensor.disable_od_protocol();
+    network_builder.create_node("master").receive_stream("sensor", "state");
+    network_builder
+}
+
+fn node<'a>(network: &'a Network, name: &str) -> &'a Node {
+    network.nodes().iter().find(|node| node.name() == name).unwrap()
+}
+
+#[test]
+fn disabled_od_protocol_omits_protocol_messages() {
+    let network = network_builder().build().unwrap();
+    let sensor = node(&network, "sensor");
+    assert!(!sensor.has_od_protocol());
+    let protocol = ["get_req", "get_resp", "set_req", "set_resp"];
+    for message in sensor.rx_messages().iter().chain(sensor.tx_messages()) {
+        assert!(!protocol.contains(&message.name()), "{}", message.name());
+    }
+    let get_req = network.get_req_message().id();
+    assert!(!sensor
+        .rx_filters()
+        .iter()
+        .flat_map(|(_, filters)| filters.iter())
+        .any(|filter| filter.accepts(get_req)));
+    assert!(sensor.object_entries().iter().all(|oe| oe.name() == "pressure"));
+    assert!(sensor.types().iter().all(|ty| ty.name() != "get_req_header"));
+    assert!(sensor.types().iter().all(|ty| ty.name() != "date_time"));
+
+    let master = node(&network, "master");
+    assert!(master.has_od_protocol());
+    assert!(master.rx_messages().iter().any(|m| m.name() == "get_req"));
+    assert!(master.object_entries().iter().any(|oe| oe.name() == "config_hash"));
+}
+
+#[test]
+fn streamed_build_time_is_kept() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_stream("info").add_entry("build_time");
+    sensor.disable_od_protocol();
+    network_builder.create_node("master").receive_stream("sensor", "info");
+    let network = network_builder.build().unwrap();
+    let sensor = node(&network, "sensor");
+    assert!(sensor.object_entries().iter().any(|oe| oe.name() == "build_time"));
+    assert!(sensor.object_entries().iter().all(|oe| oe.name() != "config_hash"));
+}
+
+#[test]
+fn protected_object_entries_require_od_protocol() {
+    let network_builder = network_builder();
+    network_builder.define_access_level("service", None);
+    let sensor = network_builder.create_node("sensor");
+    sensor
+        .create_object_entry("offset", "u16")
+        .set_access(ObjectEntryAccess::Protected {
+            level: "service".to_owned(),
+        });
+    match network_builder.build() {
+        Err(ConfigError::InvalidOdProtocol(reason)) => assert!(reason.contains("sensor::offset")),
+        other => panic!("expected InvalidOdProtocol, got {other:?}"),
+    }
+}
+
+#[test]
+fn groups_require_od_protocol() {
+    let network_builder = network_builder();
+    network_builder.create_group("sensors").add_node("sensor");
+    match network_builder.build() {
+        Err(ConfigError::InvalidOdProtocol(reason)) => assert!(reason.contains("sensors")),
+        other => panic!("expected InvalidOdProtocol, got {other:?}"),
+    }
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use canzero_config::{
+    analysis::{LintOptions, LintRule, OdThroughputOptions},
+    builder::NetworkBuilder,
+    config::NetworkRef,
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u8");
+    secu.create_object_entry("timestamp", "u64");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn snapshot_time() {
+    let network = network();
+    let options = OdThroughputOptions::default();
+    let snapshots = network.od_snapshot_times(&options);
+    let secu = snapshots.iter().find(|s| s.node().name() == "secu").unwrap();
+    let entry = |name: &str| {
+        secu.entries()
+            .iter()
+            .find(|entry| entry.object_entry().name() == name)
+            .unwrap()
+    };
+    let (position, timestamp) = (entry("position"), entry("timestamp"));
+    assert_eq!(position.frames(), 1);
+    assert_eq!(timestamp.frames(), 2);
+    // at 1 MBit/s the get_resp interval dominates the transmission time.
+    assert_eq!(timestamp.duration() - position.duration(), options.get_resp_interval);
+    assert!(position.duration() > options.get_resp_interval);
+
+    let total: Duration = secu.entries().iter().map(|entry| entry.duration()).sum();
+    assert_eq!(secu.duration(), total);
+    assert!(secu.throughput() > 0.0);
+    assert!(secu.to_string().starts_with("secu: "));
+
+    // slower servers take longer.
+    let slow = network.od_snapshot_times(&OdThroughputOptions {
+        get_resp_interval: Duration::from_millis(100),
+        ..Default::default()
+    });
+    assert!(slow[0].duration() > snapshots[0].duration());
+}
+
+#[test]
+fn slow_snapshot_lint() {
+    let network = network();
+    let slow = |report: &canzero_config::analysis::LintReport| {
+        report
+            .diagnostics()
+            .iter()
+            .any(|d| d.rule() == LintRule::SlowOdSnapshot && d.subject() == "secu")
+    };
+    assert!(!slow(&network.lint(&LintOptions::default())));
+    let mut options = LintOptions::default();
+    options.set_max_od_snapshot_time(Duration::from_millis(1));
+    assert!(slow(&network.lint(&options)));
+    options.set_max_od_snapshot_time(Duration::from_secs(60));
+    assert!(!slow(&network.lint(&options)));
+}
@@ -0,0 +1,48 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    errors::ConfigError,
+};
+
+// messages named in oversized require more than 64 bits.
+fn network_builder(message_count: usize, oversized: &[&str]) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("secu");
+    network_builder.create_node("master");
+    for i in 0..message_count {
+        let message = network_builder.create_message(&format!("m{i}"), None);
+        message.set_any_std_id(MessagePriority::Normal);
+        let format = message.make_type_format();
+        format.add_type("u16", "a");
+        format.add_type("d8<0..10>", "b");
+        if oversized.contains(&format!("m{i}").as_str()) {
+            format.add_type("u64", "overflow");
+        }
+        message.add_transmitter("master");
+        message.add_receiver("secu");
+    }
+    network_builder
+}
+
+#[test]
+fn messages_keep_creation_order() {
+    let network = network_builder(64, &[]).build().unwrap();
+    let names: Vec<&str> = network
+        .messages()
+        .iter()
+        .map(|message| message.name())
+        .filter(|name| name.starts_with('m'))
+        .collect();
+    let expected: Vec<String> = (0..64).map(|i| format!("m{i}")).collect();
+    assert_eq!(names, expected);
+    let again = network_builder(64, &[]).build().unwrap();
+    assert_eq!(network.to_canonical_string(), again.to_canonical_string());
+}
+
+#[test]
+fn first_invalid_message_determines_error() {
+    match network_builder(64, &["m17", "m40"]).build() {
+        Err(ConfigError::MessageTooLong(reason)) => assert!(reason.contains("message m17 ")),
+        other => panic!("expected MessageTooLong, got {other:?}"),
+    }
+}
@@ -0,0 +1,42 @@
+use canzero_config::{builder::NetworkBuilder, config::message::MessageUsage};
+
+#[test]
+fn protocol_messages() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let get_req = network_builder.get_req_message().name();
+    let network = network_builder.build().unwrap();
+    let protocol = network.protocol_messages();
+    assert_eq!(protocol.get_req.name(), get_req);
+    assert!(matches!(protocol.get_req.usage(), MessageUsage::GetReq));
+    assert!(matches!(protocol.get_resp.usage(), MessageUsage::GetResp));
+    assert!(matches!(protocol.set_req.usage(), MessageUsage::SetReq));
+    assert!(matches!(protocol.set_resp.usage(), MessageUsage::SetResp));
+    assert_eq!(protocol.heartbeat.name(), network.heartbeat_message().name());
+}
+
+#[test]
+fn expected_intervals() {
+    use std::time::Duration;
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "u8");
+    let stream = secu.create_stream("state");
+    stream.add_entry("state");
+    stream.set_interval(Duration::from_millis(10), Duration::from_millis(200));
+    network_builder.create_node("master").receive_stream("secu", "state");
+    secu.create_command("reset", Some(Duration::from_millis(250)));
+    let torque = network_builder.create_message("torque", Some(Duration::from_millis(20)));
+    torque.set_std_id(0x42);
+    torque.add_transmitter("master");
+    let network = network_builder.build().unwrap();
+
+    let interval = |name: &str| network.message_by_name(name).unwrap().expected_interval();
+    assert_eq!(interval("secu_stream_state"), Some(Duration::from_millis(200)));
+    assert_eq!(interval("secu_reset_command_req"), Some(Duration::from_millis(250)));
+    assert_eq!(interval("secu_reset_command_resp"), Some(Duration::from_millis(250)));
+    assert_eq!(interval("torque"), Some(Duration::from_millis(20)));
+    assert!(matches!(network.heartbeat_message().usage(), MessageUsage::Heartbeat));
+    assert_eq!(network.heartbeat_message().expected_interval(), Some(Duration::from_millis(100)));
+    assert_eq!(network.get_req_message().expected_interval(), None);
+}
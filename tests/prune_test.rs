@@ -0,0 +1,42 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn prune_unused_types() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let unused = network_builder.define_enum("unused");
+    unused.add_entry("a", None).unwrap();
+    let inner = network_builder.define_struct("inner");
+    inner.add_attribute("state", "state").unwrap();
+    let status = network_builder.define_struct("status");
+    status.add_attribute("inner", "inner").unwrap();
+    let orphan = network_builder.define_struct("orphan");
+    orphan.add_attribute("value", "u8").unwrap();
+
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("status", "status");
+
+    let network = network_builder.build().unwrap();
+    let (pruned, removed) = network.prune();
+
+    let mut removed: Vec<String> = removed.iter().map(|ty| ty.name()).collect();
+    removed.sort();
+    // without commands and get requests, their error enums are unused as well.
+    assert_eq!(
+        removed,
+        vec!["command_resp_erno", "get_resp_erno", "orphan", "unused"]
+    );
+    for name in ["state", "inner", "status", "node_id"] {
+        assert!(pruned.type_by_name(name).is_some(), "{name} was pruned");
+    }
+    assert!(pruned.type_by_name("unused").is_none());
+    assert_eq!(pruned.types().len(), network.types().len() - 4);
+    assert_eq!(pruned.messages().len(), network.messages().len());
+    assert_eq!(pruned.nodes().len(), network.nodes().len());
+
+    let (pruned_again, removed_again) = pruned.prune();
+    assert!(removed_again.is_empty());
+    assert_eq!(pruned_again.config_hash(), pruned.config_hash());
+}
@@ -0,0 +1,53 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn public_view_hides_static_elements() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    let internal = network_builder.define_enum("internal_state");
+    internal.add_entry("idle", None).unwrap();
+    internal.hide();
+
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("state", "state");
+    secu.create_object_entry("internal_state", "internal_state").hide();
+    secu.create_stream("state").add_entry("state");
+    let debug_stream = secu.create_stream("debug");
+    debug_stream.add_entry("internal_state");
+    debug_stream.hide();
+    secu.create_command("reset", None);
+    secu.create_command("calibrate", None).hide();
+
+    let debug = network_builder.create_message("debug_frame", None);
+    debug.set_std_id(0x50);
+    debug.make_type_format().add_type("u8", "value");
+    debug.add_transmitter("secu");
+    debug.hide();
+
+    let network = network_builder.build().unwrap();
+    let view = network.public_view();
+    let secu = network.node_by_name("secu").unwrap();
+
+    let types: Vec<String> = view.types().iter().map(|ty| ty.name()).collect();
+    assert!(types.contains(&"state".to_owned()));
+    assert!(!types.contains(&"internal_state".to_owned()));
+
+    let object_entries: Vec<&str> = view.object_entries(secu).iter().map(|oe| oe.name()).collect();
+    assert!(object_entries.contains(&"state"));
+    assert!(!object_entries.contains(&"internal_state"));
+
+    let streams: Vec<&str> = view.tx_streams(secu).iter().map(|s| s.name()).collect();
+    assert_eq!(streams, vec!["state"]);
+
+    let commands: Vec<&str> = view.commands(secu).iter().map(|c| c.name()).collect();
+    assert_eq!(commands, vec!["reset"]);
+
+    assert!(view.messages().iter().all(|m| m.name() != "debug_frame"));
+    assert!(view.messages().len() < network.messages().len());
+
+    // the full network is untouched.
+    assert!(network.message_by_name("debug_frame").is_some());
+    assert!(network.type_by_name("internal_state").is_some());
+    assert_eq!(view.nodes().len(), network.nodes().len());
+}
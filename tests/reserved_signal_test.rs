@@ -0,0 +1,59 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, SignalType},
+    errors::ConfigError,
+};
+
+fn network() -> canzero_config::config::NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    network_builder.create_node("master");
+    let message = network_builder.create_message("debug", None);
+    message.set_std_id(0x50);
+    let format = message.make_signal_format();
+    format
+        .add_signal(Signal::new("mode", None, SignalType::UnsignedInt { size: 5 }, 0))
+        .unwrap();
+    assert_eq!(format.add_reserved(3).unwrap(), "reserved_0");
+    format
+        .add_signal(Signal::new("value", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    assert_eq!(format.add_reserved(4).unwrap(), "reserved_1");
+    message.add_transmitter("secu");
+    message.add_receiver("master");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn reserved_regions_occupy_space() {
+    let network = network();
+    let debug = network.message_by_name("debug").unwrap();
+    let reserved: Vec<(&str, usize)> = debug
+        .signals()
+        .iter()
+        .filter(|signal| signal.is_reserved())
+        .map(|signal| (signal.name(), signal.byte_offset()))
+        .collect();
+    assert_eq!(reserved, vec![("debug_reserved_0", 5), ("debug_reserved_1", 16)]);
+    assert_eq!(debug.signals()[2].byte_offset(), 8);
+    assert_eq!(debug.dlc(), 3);
+    assert!(network.to_dbc().contains("SG_ debug_reserved_0 : 5|3@1+"));
+}
+
+#[test]
+fn codegen_skips_reserved_regions() {
+    let network = network();
+    let cpp = network.to_cpp();
+    assert!(cpp.contains("debug_value"));
+    assert!(!cpp.contains("reserved"));
+}
+
+#[test]
+fn invalid_reserved_regions() {
+    let network_builder = NetworkBuilder::new();
+    let format = network_builder.create_message("debug", None).make_signal_format();
+    assert!(matches!(format.add_reserved(0), Err(ConfigError::InvalidRange(_))));
+    assert!(matches!(format.add_reserved(65), Err(ConfigError::InvalidRange(_))));
+    // rejected regions don't take a name.
+    assert_eq!(format.add_reserved(64).unwrap(), "reserved_0");
+}
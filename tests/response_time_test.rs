@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use canzero_config::{analysis::ResponseTimeOptions, builder::NetworkBuilder};
+
+#[test]
+fn response_times_of_lightly_loaded_bus() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    let stream = secu.create_stream("state");
+    stream.add_entry("temperature");
+    stream.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+    network_builder.create_node("master");
+    let network = network_builder.build().unwrap();
+
+    let results = network.response_times(&ResponseTimeOptions::default());
+    assert_eq!(results.len(), network.messages().len());
+    for result in &results {
+        let worst_case = result.worst_case().unwrap();
+        assert!(worst_case >= result.transmission_time());
+        assert!(result.is_schedulable(), "{}", result.message().name());
+    }
+    // a single 8 byte standard frame takes at most 135 bits at 1MBit/s.
+    let max_transmission = results
+        .iter()
+        .map(|r| r.transmission_time())
+        .max()
+        .unwrap();
+    assert!(max_transmission <= Duration::from_micros(135));
+}
+
+#[test]
+fn overloaded_bus_has_no_bound() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "u64");
+    let stream = secu.create_stream("state");
+    stream.add_entry("temperature");
+    stream.set_interval(Duration::from_micros(100), Duration::from_millis(1));
+    let network = network_builder.build().unwrap();
+
+    let results = network.response_times(&ResponseTimeOptions::default());
+    let state = results
+        .iter()
+        .find(|r| r.message().name() == "secu_stream_state")
+        .unwrap();
+    assert!(state.worst_case().is_none());
+    assert!(!state.is_schedulable());
+}
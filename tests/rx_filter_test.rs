@@ -0,0 +1,70 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::MessageId,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_stream("state").add_entry("position");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+    network_builder.create_node("pdu");
+    let fixed = network_builder.create_message("fixed", None);
+    fixed.set_std_id(0x42);
+    fixed.add_transmitter("pdu");
+    fixed.add_receiver("master");
+    for i in 0..8 {
+        let message = network_builder.create_message(&format!("m{i}"), None);
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_transmitter("master");
+        message.add_receiver("pdu");
+    }
+    network_builder
+}
+
+#[test]
+fn rx_filters_accept_all_rx_messages() {
+    let network = network_builder().build().unwrap();
+    for node in network.nodes() {
+        for message in node.rx_messages() {
+            let bus = message.bus();
+            let (_, filters) = node
+                .rx_filters()
+                .iter()
+                .find(|(filter_bus, _)| filter_bus.id() == bus.id())
+                .unwrap_or_else(|| panic!("node {} has no filters on {}", node.name(), bus.name()));
+            assert!(
+                filters.iter().any(|filter| filter.accepts(message.id())),
+                "message {} is not accepted by node {}",
+                message.name(),
+                node.name()
+            );
+        }
+    }
+}
+
+#[test]
+fn fixed_ids_are_accepted() {
+    let network = network_builder().build().unwrap();
+    let master = network
+        .nodes()
+        .iter()
+        .find(|n| n.name() == "master")
+        .unwrap();
+    assert!(master
+        .rx_filters()
+        .iter()
+        .flat_map(|(_, filters)| filters.iter())
+        .any(|filter| filter.accepts(&MessageId::StandardId(0x42))));
+    assert!(!master
+        .rx_filters()
+        .iter()
+        .flat_map(|(_, filters)| filters.iter())
+        .any(|filter| filter.accepts(&MessageId::ExtendedId(0x42))));
+    for filter in master.rx_filters().iter().flat_map(|(_, f)| f.iter()) {
+        assert_eq!(filter.id() & filter.mask(), filter.id());
+    }
+}
@@ -0,0 +1,92 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{signal::Signal, SignalType},
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("master");
+    let status = network_builder.create_message("node_status", None);
+    status.set_any_std_id(MessagePriority::Low);
+    status.shared_by_nodes(&["bms", "pdu", "bms"]);
+    status.add_receiver("master");
+    status
+        .make_signal_format()
+        .add_signal(Signal::create("state", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    let temperature = network_builder.create_message("temperature", None);
+    temperature.set_any_std_id(MessagePriority::Normal);
+    temperature.shared_by_nodes(&["bms", "pdu"]);
+    temperature.add_receiver("master");
+    temperature.make_type_format().add_type("u16", "value");
+    network_builder
+}
+
+#[test]
+fn shared_signal_format() {
+    let network = network_builder().build().unwrap();
+    let status = network.message_by_name("node_status").unwrap();
+    let sender_id = status.sender_id().unwrap();
+    // the sender id follows the signals of the message.
+    assert_eq!(status.signals().len(), 2);
+    assert_eq!(status.signals()[0].byte_offset(), 0);
+    assert_eq!(sender_id.byte_offset(), 8);
+    assert_eq!(sender_id.name(), status.signals()[1].name());
+
+    // one message with a single id, transmitted by every node.
+    assert_eq!(
+        network.messages().iter().filter(|message| message.name() == "node_status").count(),
+        1
+    );
+    for node_name in ["bms", "pdu"] {
+        let node = network.nodes().iter().find(|node| node.name() == node_name).unwrap();
+        assert_eq!(
+            node.tx_messages().iter().filter(|message| message.name() == "node_status").count(),
+            1
+        );
+    }
+}
+
+#[test]
+fn shared_type_format() {
+    let network = network_builder().build().unwrap();
+    let temperature = network.message_by_name("temperature").unwrap();
+    let sender_id = temperature.sender_id().unwrap();
+    assert_eq!(sender_id.byte_offset(), 16);
+    assert_eq!(temperature.encoding().unwrap().attributes().len(), 1);
+
+    // both formats encode the sender id the same way.
+    let status = network.message_by_name("node_status").unwrap();
+    assert_eq!(sender_id.ty(), status.sender_id().unwrap().ty());
+    assert_eq!(sender_id.size(), 8);
+}
+
+#[test]
+fn sender_id_fills_the_gap_before_pinned_signals() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("master");
+    let status = network_builder.create_message("pinned_status", None);
+    status.set_any_std_id(MessagePriority::Low);
+    status.shared_by_nodes(&["bms", "pdu"]);
+    status.add_receiver("master");
+    let format = status.make_signal_format();
+    format
+        .add_signal_at(Signal::create("state", None, SignalType::UnsignedInt { size: 8 }), 0)
+        .unwrap();
+    format
+        .add_signal_at(Signal::create("error", None, SignalType::UnsignedInt { size: 8 }), 24)
+        .unwrap();
+    let network = network_builder.build().unwrap();
+    let status = network.message_by_name("pinned_status").unwrap();
+    let sender_id = status.sender_id().unwrap();
+    assert_eq!(sender_id.byte_offset(), 8);
+    assert_eq!(status.dlc(), 4);
+}
+
+#[test]
+fn unshared_message_has_no_sender_id() {
+    let network = network_builder().build().unwrap();
+    let heartbeat = network.message_by_name("heartbeat").unwrap();
+    assert!(heartbeat.sender_id().is_none());
+}
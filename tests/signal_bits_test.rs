@@ -0,0 +1,33 @@
+use canzero_config::config::{
+    signal::{dbc_to_sawtooth, sawtooth_to_dbc, Signal},
+    SignalType,
+};
+
+#[test]
+fn bit_accessors() {
+    let signal = Signal::new("velocity", None, SignalType::SignedInt { size: 12 }, 13);
+    assert_eq!(signal.bit_range(), 13..25);
+    assert_eq!(signal.bit_range().len(), 12);
+    assert_eq!(signal.start_byte(), 1);
+    assert_eq!(signal.bit_in_byte(), 5);
+    assert!(signal.is_signed());
+    assert_eq!(signal.sign_extend(0xFFF), -1);
+    assert_eq!(signal.sign_extend(0x7FF), 0x7FF);
+    assert_eq!(signal.sign_extend(0x800), -2048);
+
+    let signal = Signal::new("position", None, SignalType::UnsignedInt { size: 8 }, 0);
+    assert!(!signal.is_signed());
+    assert_eq!(signal.sign_extend(0xFF), 0xFF);
+}
+
+#[test]
+fn start_bits() {
+    let signal = Signal::new("velocity", None, SignalType::UnsignedInt { size: 4 }, 10);
+    assert_eq!(signal.dbc_start_bit(), 10);
+    assert_eq!(signal.sawtooth_start_bit(), 13);
+    assert_eq!(dbc_to_sawtooth(0), 7);
+    assert_eq!(dbc_to_sawtooth(63), 56);
+    for bit in 0..64 {
+        assert_eq!(sawtooth_to_dbc(dbc_to_sawtooth(bit)), bit);
+    }
+}
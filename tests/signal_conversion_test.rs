@@ -0,0 +1,65 @@
+use canzero_config::config::{OverflowPolicy, SignalType};
+
+#[test]
+fn unsigned_conversion() {
+    let ty = SignalType::UnsignedInt { size: 8 };
+    assert_eq!(ty.raw_to_physical(0x1FF), 255.0);
+    assert_eq!(ty.physical_to_raw(12.4), 12);
+    assert_eq!(ty.physical_to_raw(300.0), 255);
+    assert_eq!(ty.physical_to_raw(-1.0), 0);
+}
+
+#[test]
+fn signed_conversion() {
+    let ty = SignalType::SignedInt { size: 4 };
+    assert_eq!(ty.raw_to_physical(0b1111), -1.0);
+    assert_eq!(ty.raw_to_physical(0b1000), -8.0);
+    assert_eq!(ty.raw_to_physical(0b0111), 7.0);
+    assert_eq!(ty.physical_to_raw(-1.0), 0b1111);
+    assert_eq!(ty.physical_to_raw(-100.0), 0b1000);
+    assert_eq!(ty.physical_to_raw(100.0), 0b0111);
+}
+
+#[test]
+fn decimal_conversion() {
+    let ty = SignalType::Decimal {
+        size: 8,
+        offset: -10.0,
+        scale: 0.5,
+    };
+    assert_eq!(ty.physical_range(), (-10.0, 117.5));
+    assert_eq!(ty.raw_to_physical(0), -10.0);
+    assert_eq!(ty.raw_to_physical(21), 0.5);
+    assert_eq!(ty.physical_to_raw(0.6), 21);
+    assert_eq!(ty.physical_to_raw(1000.0), 255);
+    assert_eq!(ty.physical_to_raw(f64::NAN), 0);
+    for raw in 0..=255 {
+        assert_eq!(ty.physical_to_raw(ty.raw_to_physical(raw)), raw);
+    }
+}
+
+#[test]
+fn full_width_conversion() {
+    let ty = SignalType::SignedInt { size: 64 };
+    assert_eq!(ty.raw_to_physical(u64::MAX), -1.0);
+    assert_eq!(ty.physical_to_raw(-1.0), u64::MAX);
+}
+
+#[test]
+fn overflow_policies() {
+    let ty = SignalType::Decimal {
+        size: 8,
+        offset: -10.0,
+        scale: 0.5,
+    };
+    assert_eq!(ty.physical_to_raw_with(118.0, OverflowPolicy::Saturate), Some(255));
+    assert_eq!(ty.physical_to_raw_with(118.0, OverflowPolicy::Error), None);
+    assert_eq!(ty.physical_to_raw_with(f64::NAN, OverflowPolicy::Error), None);
+    assert_eq!(ty.physical_to_raw_with(0.5, OverflowPolicy::Error), Some(21));
+    assert_eq!(ty.physical_to_raw_with(118.0, OverflowPolicy::Wrap), Some(0));
+    assert_eq!(ty.physical_to_raw_with(-10.5, OverflowPolicy::Wrap), Some(255));
+
+    let ty = SignalType::UnsignedInt { size: 4 };
+    assert_eq!(ty.physical_to_raw_with(17.0, OverflowPolicy::Wrap), Some(1));
+    assert_eq!(ty.physical_to_raw_with(17.0, OverflowPolicy::Saturate), Some(15));
+}
@@ -0,0 +1,110 @@
+use canzero_config::{
+    analysis::{LintOptions, LintRule},
+    builder::NetworkBuilder,
+    config::{signal::Signal, NetworkRef, SignalType},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("master");
+    let torque = network_builder.create_message("torque", None);
+    torque.set_std_id(0x42);
+    torque.add_transmitter("master");
+    torque.add_receiver("motor_left");
+    torque.add_receiver("motor_right");
+    torque.add_receiver("logger");
+    let format = torque.make_signal_format();
+    for wheel in ["left", "right"] {
+        format
+            .add_signal(Signal::create(wheel, None, SignalType::UnsignedInt { size: 16 }))
+            .unwrap();
+    }
+    format.add_signal_receiver("left", "motor_left").unwrap();
+    format.add_signal_receiver("left", "logger").unwrap();
+    format.add_signal_receiver("right", "motor_right").unwrap();
+    format.add_signal_receiver("right", "logger").unwrap();
+    network_builder
+}
+
+fn receivers<'a>(network: &'a NetworkRef, suffix: &str) -> &'a [String] {
+    network
+        .message_by_name("torque")
+        .unwrap()
+        .signals()
+        .iter()
+        .find(|signal| signal.name().ends_with(suffix))
+        .unwrap()
+        .receivers()
+}
+
+#[test]
+fn signal_receivers_are_exposed() {
+    let network = network_builder().build().unwrap();
+    assert_eq!(receivers(&network, "left"), ["motor_left", "logger"]);
+    let right = network.message_by_name("torque").unwrap().signals()[1].clone();
+    assert!(right.is_received_by("motor_right"));
+    assert!(!right.is_received_by("motor_left"));
+    assert!(network
+        .to_canonical_string()
+        .contains("  signal torque_left offset=0 type=u16 overflow=saturate receivers=logger,motor_left"));
+}
+
+#[test]
+fn signal_receivers_have_to_receive_the_message() {
+    let network_builder = network_builder();
+    network_builder.create_node("secu");
+    let torque = network_builder.create_message("brake", None);
+    torque.add_transmitter("master");
+    torque.add_receiver("logger");
+    let format = torque.make_signal_format();
+    format
+        .add_signal(Signal::create("pressure", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    format.add_signal_receiver("pressure", "secu").unwrap();
+    match network_builder.build() {
+        Err(ConfigError::InvalidSignalReceiver(reason)) => {
+            assert!(reason.contains("secu") && reason.contains("brake"), "{reason}")
+        }
+        other => panic!("expected InvalidSignalReceiver, got {other:?}"),
+    }
+}
+
+#[test]
+fn exporters_use_signal_receivers() {
+    let network = network_builder().build().unwrap();
+    let dbc = network.to_dbc();
+    assert!(dbc.contains(" SG_ torque_left : 0|16@1+ (1,0) [0|65535] \"\" motor_left,logger"));
+    assert!(dbc.contains(" SG_ torque_right : 16|16@1+ (1,0) [0|65535] \"\" motor_right,logger"));
+
+    let path = std::env::temp_dir().join("canzero_signal_receiver_test.dbc");
+    std::fs::write(&path, &dbc).unwrap();
+    let network_builder = NetworkBuilder::new();
+    network_builder.include_dbc("can0", path.to_str().unwrap()).unwrap();
+    let imported = network_builder.build().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut left = receivers(&imported, "left").to_vec();
+    left.sort();
+    assert_eq!(left, ["logger", "motor_left"]);
+
+    let imported = NetworkBuilder::from_kcd(&network.to_kcd()).unwrap().build().unwrap();
+    let mut right = receivers(&imported, "right").to_vec();
+    right.sort();
+    assert_eq!(right, ["logger", "motor_right"]);
+}
+
+#[test]
+fn unused_rx_message_lint() {
+    let network_builder = network_builder();
+    network_builder.find_message("torque").unwrap().add_receiver("secu");
+    let network = network_builder.build().unwrap();
+    let report = network.lint(&LintOptions::default());
+    let unused: Vec<&str> = report
+        .diagnostics()
+        .iter()
+        .filter(|d| d.rule() == LintRule::UnusedRxMessage)
+        .map(|d| d.subject())
+        .collect();
+    assert_eq!(unused, ["secu::torque"]);
+}
@@ -0,0 +1,104 @@
+use canzero_config::{
+    builder::{parse_signal_type, NetworkBuilder},
+    config::{SignalType, Type},
+    errors::ConfigError,
+};
+
+#[test]
+fn integers() {
+    assert_eq!(parse_signal_type("u8").unwrap(), SignalType::UnsignedInt { size: 8 });
+    assert_eq!(parse_signal_type("i64").unwrap(), SignalType::SignedInt { size: 64 });
+    assert_eq!(parse_signal_type("u1").unwrap(), SignalType::UnsignedInt { size: 1 });
+    for name in ["u0", "i65", "u128", "u256"] {
+        assert!(
+            matches!(parse_signal_type(name), Err(ConfigError::InvalidType(_))),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn decimals() {
+    match parse_signal_type("d8<0..255>").unwrap() {
+        SignalType::Decimal { size, offset, scale } => {
+            assert_eq!(size, 8);
+            assert_eq!(offset, 0.0);
+            assert_eq!(scale, 1.0);
+        }
+        other => panic!("{other:?}"),
+    }
+    match parse_signal_type("d64<-1.5..1.5>").unwrap() {
+        SignalType::Decimal { size, offset, .. } => {
+            assert_eq!(size, 64);
+            assert_eq!(offset, -1.5);
+        }
+        other => panic!("{other:?}"),
+    }
+}
+
+#[test]
+fn scientific_notation() {
+    match parse_signal_type("d16<-1e3..2.5E+3>").unwrap() {
+        SignalType::Decimal { offset, scale, .. } => {
+            assert_eq!(offset, -1000.0);
+            assert_eq!(scale, 3500.0 / 65535.0);
+        }
+        other => panic!("{other:?}"),
+    }
+    assert!(parse_signal_type("d8<.5..1e-1>").is_err());
+    assert!(parse_signal_type("d8<1e-3..5e-1>").is_ok());
+}
+
+#[test]
+fn invalid_decimals() {
+    assert!(matches!(
+        parse_signal_type("d0<0..1>"),
+        Err(ConfigError::InvalidDecimalDefinition(_))
+    ));
+    assert!(matches!(
+        parse_signal_type("d65<0..1>"),
+        Err(ConfigError::InvalidDecimalDefinition(_))
+    ));
+    assert!(matches!(
+        parse_signal_type("d8<1..1>"),
+        Err(ConfigError::InvalidRange(_))
+    ));
+    assert!(matches!(
+        parse_signal_type("d8<1e400..1e401>"),
+        Err(ConfigError::InvalidDecimalDefinition(_))
+    ));
+    assert!(matches!(
+        parse_signal_type("d8<-1e308..1e308>"),
+        Err(ConfigError::InvalidDecimalDefinition(_))
+    ));
+    assert!(matches!(
+        parse_signal_type("state"),
+        Err(ConfigError::InvalidType(_))
+    ));
+}
+
+#[test]
+fn invalid_object_entry_type_fails_build() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d0<0..1>");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidDecimalDefinition(_))
+    ));
+}
+
+#[test]
+fn scientific_array_elements() {
+    let ty = NetworkBuilder::resolve_type(&vec![], "d8<-1e1..1e1>[4]").unwrap();
+    match &ty as &Type {
+        Type::Array { len, ty } => {
+            assert_eq!(*len, 4);
+            assert!(matches!(
+                ty as &Type,
+                Type::Primitive(SignalType::Decimal { size: 8, offset, .. }) if *offset == -10.0
+            ));
+        }
+        other => panic!("{other:?}"),
+    }
+}
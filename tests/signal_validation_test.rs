@@ -0,0 +1,90 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, SignalType},
+    errors::ConfigError,
+};
+
+fn network(signals: &[(&str, u8, usize)], manual_offsets: bool) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let message = network_builder.create_message("debug", None);
+    message.set_std_id(0x50);
+    let format = message.make_signal_format();
+    if manual_offsets {
+        format.use_manual_offsets();
+    }
+    for (name, size, offset) in signals {
+        format
+            .add_signal(Signal::new(name, None, SignalType::UnsignedInt { size: *size }, *offset))
+            .unwrap();
+    }
+    message.add_transmitter("secu");
+    network_builder
+}
+
+#[test]
+fn manual_offsets() {
+    let network = network(&[("a", 8, 8), ("b", 4, 0)], true).build().unwrap();
+    let debug = network.message_by_name("debug").unwrap();
+    assert_eq!(debug.signals()[0].byte_offset(), 8);
+    assert_eq!(debug.signals()[1].byte_offset(), 0);
+    assert_eq!(debug.dlc(), 2);
+}
+
+#[test]
+fn overlapping_signals() {
+    let Err(ConfigError::OverlappingSignals(reason)) =
+        network(&[("a", 8, 0), ("b", 8, 4)], true).build()
+    else {
+        panic!("overlapping signals weren't detected");
+    };
+    assert!(reason.contains("debug_a (bits 0..8)"), "{reason}");
+    assert!(reason.contains("debug_b (bits 4..12)"), "{reason}");
+}
+
+#[test]
+fn message_too_long() {
+    let Err(ConfigError::MessageTooLong(reason)) =
+        network(&[("a", 32, 0), ("b", 32, 0), ("c", 8, 0)], false).build()
+    else {
+        panic!("messages longer than 64 bits weren't detected");
+    };
+    assert!(reason.contains("debug_c occupies bits 64..72"), "{reason}");
+
+    assert!(matches!(
+        network(&[("a", 16, 56)], true).build(),
+        Err(ConfigError::MessageTooLong(_))
+    ));
+}
+
+fn pinned_network(pinned_at: usize) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let message = network_builder.create_message("debug", None);
+    message.set_std_id(0x50);
+    let format = message.make_signal_format();
+    let signal = |name: &str, size: u8| Signal::new(name, None, SignalType::UnsignedInt { size }, 0);
+    format.add_signal(signal("a", 12)).unwrap();
+    format.add_signal_at(signal("b", 8), pinned_at).unwrap();
+    format.add_signal(signal("c", 4)).unwrap();
+    message.add_transmitter("secu");
+    network_builder
+}
+
+#[test]
+fn pinned_signals() {
+    let network = pinned_network(16).build().unwrap();
+    let debug = network.message_by_name("debug").unwrap();
+    let offsets: Vec<usize> = debug.signals().iter().map(|s| s.byte_offset()).collect();
+    // a is packed, b pinned and c packed behind b.
+    assert_eq!(offsets, vec![0, 16, 24]);
+    assert_eq!(debug.dlc(), 4);
+}
+
+#[test]
+fn overlapping_pinned_signals() {
+    assert!(matches!(
+        pinned_network(8).build(),
+        Err(ConfigError::OverlappingSignals(_))
+    ));
+}
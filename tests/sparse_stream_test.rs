@@ -0,0 +1,97 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_object_entry("velocity", "i16");
+    secu.create_object_entry("state", "u8");
+    let stream = secu.create_stream("state");
+    stream.add_entry("position");
+    assert_eq!(stream.skip_slot("u8"), 1);
+    assert_eq!(stream.skip_slot("i16"), 2);
+    stream.add_entry("state");
+    network_builder
+}
+
+#[test]
+fn skipped_slots() {
+    let network = network_builder().build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let stream = &secu.tx_streams()[0];
+    let mapping: Vec<Option<&str>> = stream
+        .mapping()
+        .iter()
+        .map(|m| m.as_ref().map(|m| m.name()))
+        .collect();
+    assert_eq!(mapping, vec![Some("position"), None, None, Some("state")]);
+
+    // reserved slots occupy the message.
+    let signals = stream.message().signals();
+    assert_eq!(signals.len(), 4);
+    assert_eq!(signals[3].byte_offset(), 16 + 8 + 16);
+    assert_eq!(stream.message().dlc(), 6);
+}
+
+#[test]
+fn map_into_skipped_slot() {
+    let network_builder = network_builder();
+    let secu = network_builder.create_node("secu");
+    let stream = secu.find_stream("state").unwrap();
+    stream.map_at(2, "velocity").unwrap();
+    let master = network_builder.create_node("master");
+    let rx_stream = master.receive_stream("secu", "state");
+    rx_stream.map("state", "state");
+    let network = network_builder.build().unwrap();
+
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let mapping: Vec<Option<&str>> = secu.tx_streams()[0]
+        .mapping()
+        .iter()
+        .map(|m| m.as_ref().map(|m| m.name()))
+        .collect();
+    assert_eq!(mapping, vec![Some("position"), None, Some("velocity"), Some("state")]);
+
+
+    // the mapped slot keeps the layout of the reserved slot.
+    let signals = secu.tx_streams()[0].message().signals();
+    let offsets: Vec<usize> = signals.iter().map(|s| s.byte_offset()).collect();
+    assert_eq!(offsets, vec![0, 16, 16 + 8, 16 + 8 + 16]);
+    // the receiver only maps the last slot.
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let mapping: Vec<Option<&str>> = master.rx_streams()[0]
+        .mapping()
+        .iter()
+        .map(|m| m.as_ref().map(|m| m.name()))
+        .collect();
+    assert_eq!(mapping, vec![None, None, None, Some("state")]);
+}
+
+#[test]
+fn map_into_mapped_slot() {
+    let network_builder = network_builder();
+    let secu = network_builder.create_node("secu");
+    let result = secu.find_stream("state").unwrap().map_at(0, "velocity");
+    assert!(matches!(result, Err(ConfigError::InvalidStreamMapping(_))));
+}
+
+#[test]
+fn map_behind_the_last_slot() {
+    let network_builder = network_builder();
+    let secu = network_builder.create_node("secu");
+    let result = secu.find_stream("state").unwrap().map_at(5, "velocity");
+    assert!(matches!(result, Err(ConfigError::InvalidStreamMapping(_))));
+}
+
+#[test]
+fn map_with_other_type_than_the_slot() {
+    let network_builder = network_builder();
+    let secu = network_builder.create_node("secu");
+    let stream = secu.find_stream("state").unwrap();
+    // slot 1 is reserved for an u8.
+    let result = stream.map_at(1, "velocity");
+    assert!(matches!(result, Err(ConfigError::InvalidStreamMapping(_))));
+    // an already mapped entry and a missing entry can't be mapped either.
+    assert!(matches!(stream.map_at(2, "position"), Err(ConfigError::InvalidStreamMapping(_))));
+    assert!(matches!(stream.map_at(2, "missing"), Err(ConfigError::StreamEntryMissing(_))));
+}
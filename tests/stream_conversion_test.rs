@@ -0,0 +1,110 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, SignalType, Type},
+    errors::ConfigError,
+};
+
+fn build(oe_type: &str, signal_type: &str) -> Result<NetworkRef, ConfigError> {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", oe_type);
+    let stream = secu.create_stream("state");
+    stream.add_entry_as("position", signal_type);
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state").map("position", "position");
+    network_builder.build()
+}
+
+#[test]
+fn scaled_stream_entry() {
+    let network = build("d8<0..100>", "d12<-10..200>").unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let stream = &secu.tx_streams()[0];
+    let mapping = stream.mapping()[0].as_ref().unwrap();
+    assert_eq!(mapping.name(), "position");
+    let conversion = mapping.conversion().unwrap();
+    assert_eq!(conversion.object_entry_type().size(), 8);
+    assert_eq!(conversion.signal_type().size(), 12);
+
+    // the message encodes the entry with the signal type.
+    let encoding = stream.message().encoding().unwrap();
+    match &**encoding.attributes()[0].ty() {
+        Type::Primitive(signal_type) => assert_eq!(signal_type, conversion.signal_type()),
+        ty => panic!("unexpected type {ty:?}"),
+    }
+
+    let raw = conversion.object_entry_type().physical_to_raw(50.0);
+    let value = conversion.object_entry_type().raw_to_physical(raw);
+    let signal = conversion.to_signal(raw);
+    assert!((conversion.signal_type().raw_to_physical(signal) - value).abs() < 0.05);
+    let linear = raw as f64 * conversion.factor() + conversion.offset();
+    assert_eq!(linear.round() as u64, signal);
+    assert_eq!(conversion.to_object_entry(signal), raw);
+
+    // the receiver converts the signal back.
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let rx_mapping = master.rx_streams()[0].mapping()[0].as_ref().unwrap();
+    assert_eq!(rx_mapping.conversion(), Some(conversion));
+}
+
+#[test]
+fn unscaled_stream_entry() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u8");
+    secu.create_stream("state").add_entry("position");
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let mapping = secu.tx_streams()[0].mapping()[0].as_ref().unwrap();
+    assert!(mapping.conversion().is_none());
+}
+
+#[test]
+fn integer_widening() {
+    let network = build("i8", "i16").unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let mapping = secu.tx_streams()[0].mapping()[0].as_ref().unwrap();
+    let conversion = mapping.conversion().unwrap();
+    assert_eq!(conversion.factor(), 1.0);
+    assert_eq!(conversion.offset(), 0.0);
+    assert!(conversion.is_signed());
+    assert_eq!(conversion.to_signal(0xFF), 0xFFFF);
+    assert_eq!(conversion.signal_type(), &SignalType::SignedInt { size: 16 });
+}
+
+#[test]
+fn invalid_conversions() {
+    // signedness
+    assert!(matches!(build("i8", "u16"), Err(ConfigError::InvalidStreamMapping(_))));
+    // range
+    assert!(matches!(build("u16", "u8"), Err(ConfigError::InvalidStreamMapping(_))));
+    assert!(matches!(
+        build("d8<0..100>", "d16<0..50>"),
+        Err(ConfigError::InvalidStreamMapping(_))
+    ));
+    // resolution
+    assert!(matches!(
+        build("d16<0..100>", "d8<0..100>"),
+        Err(ConfigError::InvalidStreamMapping(_))
+    ));
+    // non primitive object entry
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_struct("point").add_attribute("x", "u8").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "point");
+    secu.create_stream("state").add_entry_as("position", "u16");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidStreamMapping(_))
+    ));
+    // arrays are rejected before the streams are mapped.
+    assert!(matches!(build("u8[2]", "u16"), Err(ConfigError::InvalidType(_))));
+}
+
+#[test]
+fn simulation_scales_entries() {
+    let network = build("d8<0..100>", "d12<-10..200>").unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let sim = network.to_sim(secu);
+    assert!(sim.contains("Some(Scaling { entry: (false, 8), signal: (false, 12)"));
+}
@@ -0,0 +1,91 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{stream::StreamEncoding, E2EProfile},
+};
+
+#[test]
+fn delta_encoded_stream() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_object_entry("velocity", "i16");
+    let stream = secu.create_stream("state");
+    stream.add_entry("position");
+    stream.add_entry("velocity");
+    stream.set_delta_encoding(10);
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state").map("position", "position");
+    let network = network_builder.build().unwrap();
+
+    let secu = network.node_by_name("secu").unwrap();
+    let stream = &secu.tx_streams()[0];
+    assert_eq!(stream.encoding(), &StreamEncoding::Delta { keyframe_interval: 10 });
+    let keyframe = stream.keyframe_signal().unwrap();
+    assert_eq!(keyframe.size(), 1);
+    assert_eq!(keyframe.byte_offset(), 32);
+    assert_eq!(stream.message().signals().len(), 3);
+
+    // the receiver agrees on the encoding.
+    let master = network.node_by_name("master").unwrap();
+    assert_eq!(master.rx_streams()[0].encoding(), stream.encoding());
+    assert!(network.to_canonical_string().contains("    delta keyframe_interval=10\n"));
+}
+
+#[test]
+fn absolute_stream() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u16");
+    secu.create_stream("state").add_entry("position");
+    let network = network_builder.build().unwrap();
+    let stream = &network.node_by_name("secu").unwrap().tx_streams()[0];
+    assert_eq!(stream.encoding(), &StreamEncoding::Absolute);
+    assert!(stream.keyframe_signal().is_none());
+    assert_eq!(stream.message().signals().len(), 1);
+}
+
+#[test]
+fn encoding_changes_hash() {
+    let build = |delta: bool| {
+        let network_builder = NetworkBuilder::new();
+        let secu = network_builder.create_node("secu");
+        secu.create_object_entry("position", "u16");
+        let stream = secu.create_stream("state");
+        stream.add_entry("position");
+        if delta {
+            stream.set_delta_encoding(4);
+        }
+        network_builder.build().unwrap().config_hash()
+    };
+    assert_ne!(build(false), build(true));
+}
+
+#[test]
+fn keyframe_with_e2e_and_sender_id() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    network_builder.create_node("pdu");
+    secu.create_object_entry("position", "u16");
+    let stream = secu.create_stream("state");
+    stream.add_entry("position");
+    stream.set_delta_encoding(8);
+    stream
+        .message()
+        .enable_e2e_protection(E2EProfile::Crc8Counter4 { data_id: 7 });
+    stream.message().shared_by_nodes(&["secu", "pdu"]);
+    let network = network_builder.build().unwrap();
+
+    let stream = &network.node_by_name("secu").unwrap().tx_streams()[0];
+    let message = stream.message();
+    let keyframe = stream.keyframe_signal().unwrap();
+    assert_eq!(keyframe.name(), format!("{}_keyframe", message.name()));
+    assert_eq!(keyframe.size(), 1);
+    assert_eq!(keyframe.byte_offset(), 16);
+    // sender id and e2e signals follow the keyframe.
+    let sender_id = message.sender_id().unwrap();
+    assert!(sender_id.byte_offset() > keyframe.byte_offset());
+    let e2e = message.e2e().unwrap();
+    assert_ne!(e2e.crc().name(), keyframe.name());
+    assert_ne!(e2e.counter().name(), keyframe.name());
+    assert!(e2e.counter().byte_offset() > keyframe.byte_offset());
+}
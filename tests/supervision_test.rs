@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let alive = network_builder.create_message("brake_alive", Some(Duration::from_millis(10)));
+    alive.set_any_std_id(MessagePriority::High);
+    alive.add_transmitter("brake");
+    alive.add_receiver("master");
+    alive.make_type_format().add_type("u8", "counter");
+    network_builder
+}
+
+#[test]
+fn supervision_is_stored_and_exported() {
+    let network_builder = network_builder();
+    let master = network_builder.create_node("master");
+    master.supervise("brake", "brake_alive", Duration::from_millis(50));
+    // declaring the relationship again replaces the timeout.
+    master.supervise("brake", "brake_alive", Duration::from_millis(30));
+    let network = network_builder.build().unwrap();
+
+    assert_eq!(network.supervisions().len(), 1);
+    let supervision = &network.supervisions()[0];
+    assert_eq!(supervision.supervisor().name(), "master");
+    assert_eq!(supervision.supervised().name(), "brake");
+    assert_eq!(supervision.message().name(), "brake_alive");
+    assert_eq!(supervision.timeout(), &Duration::from_millis(30));
+    let brake = network.node_by_name("brake").unwrap();
+    assert_eq!(network.supervisors_of(brake).len(), 1);
+
+    let table = network.to_supervision_table();
+    assert!(table.starts_with("| Supervisor | Supervised | Message | Id | Timeout |"));
+    assert!(table.contains("| master | brake | brake_alive |"));
+    assert!(table.contains("| 30ms |"));
+    assert!(network
+        .to_dot()
+        .contains("\"master\" -> \"brake\" [label=\"supervises via brake_alive (30ms)\""));
+    assert!(network
+        .to_canonical_string()
+        .contains("  supervises brake:brake_alive=30ms"));
+}
+
+#[test]
+fn supervisor_has_to_receive_the_message() {
+    let network_builder = network_builder();
+    network_builder
+        .create_node("secu")
+        .supervise("brake", "brake_alive", Duration::from_millis(50));
+    match network_builder.build() {
+        Err(ConfigError::InvalidSupervision(msg)) => assert!(msg.contains("secu doesn't receive"), "{msg}"),
+        other => panic!("expected InvalidSupervision, got {other:?}"),
+    }
+}
+
+#[test]
+fn supervised_node_has_to_transmit_the_message() {
+    let network_builder = network_builder();
+    network_builder.create_node("pdu");
+    network_builder
+        .create_node("master")
+        .supervise("pdu", "brake_alive", Duration::from_millis(50));
+    match network_builder.build() {
+        Err(ConfigError::InvalidSupervision(msg)) => assert!(msg.contains("pdu doesn't transmit"), "{msg}"),
+        other => panic!("expected InvalidSupervision, got {other:?}"),
+    }
+}
+
+#[test]
+fn undefined_supervision_targets() {
+    let network_builder = network_builder();
+    let master = network_builder.create_node("master");
+    master.supervise("brake", "missing", Duration::from_millis(50));
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidSupervision(_))));
+
+    let network_builder = self::network_builder();
+    let master = network_builder.create_node("master");
+    master.supervise("missing", "brake_alive", Duration::from_millis(50));
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidSupervision(_))));
+}
+
+#[test]
+fn supervision_changes_config_hash() {
+    let build = |timeout: Option<u64>| {
+        let network_builder = network_builder();
+        let master = network_builder.create_node("master");
+        if let Some(timeout) = timeout {
+            master.supervise("brake", "brake_alive", Duration::from_millis(timeout));
+        }
+        network_builder.build().unwrap()
+    };
+    let fast = build(Some(30));
+    let slow = build(Some(5000));
+    let unsupervised = build(None);
+    assert_ne!(fast.config_hash(), slow.config_hash());
+    assert_ne!(fast.config_hash(), unsupervised.config_hash());
+    assert_ne!(*fast, *slow);
+    assert_eq!(fast.config_hash(), build(Some(30)).config_hash());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "template")]
+
+use canzero_config::{builder::NetworkBuilder, codegen::template, errors::ConfigError};
+
+#[test]
+fn render_message_table() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<-10..100>")
+        .add_unit("C");
+    secu.create_stream("state").add_entry("temperature");
+    let network = network_builder.build().unwrap();
+
+    let output = template::render(
+        &network,
+        "{% for m in messages %}{{ m.name }}={{ m.id }}\n{% endfor %}\
+         {% for n in nodes %}{% for oe in n.object_entries %}{% if oe.unit %}{{ oe.name }}[{{ oe.unit }}]{% endif %}{% endfor %}{% endfor %}",
+    )
+    .unwrap();
+    for message in network.messages() {
+        assert!(output.contains(&format!("{}={}\n", message.name(), message.id().as_u32())));
+    }
+    assert!(output.ends_with("temperature[C]"));
+
+    match template::render(&network, "{% for %}") {
+        Err(ConfigError::Template(_)) => (),
+        other => panic!("expected a template error, got {other:?}"),
+    }
+}
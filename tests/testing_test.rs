@@ -0,0 +1,25 @@
+#![cfg(feature = "testing")]
+
+use canzero_config::testing::{
+    arb_network, assert_deterministic_build, assert_invariants, assert_kcd_round_trip,
+};
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn generated_networks_build(spec in arb_network()) {
+        let network = spec.build().unwrap();
+        assert_invariants(&network);
+        for message in &spec.messages {
+            prop_assert!(network.message_by_name(&message.name).is_some());
+        }
+    }
+
+    #[test]
+    fn generated_networks_round_trip(spec in arb_network()) {
+        assert_deterministic_build(&spec);
+        assert_kcd_round_trip(&spec.build().unwrap());
+    }
+}
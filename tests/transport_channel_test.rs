@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use canzero_config::{builder::NetworkBuilder, config::message::MessageUsage, errors::ConfigError};
+
+#[test]
+fn transport_channel_on_both_nodes() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1000000));
+    let secu = network_builder.create_node("secu");
+    let channel = secu.create_transport_channel("log", "master");
+    channel.add_description("log file transfer");
+    channel.set_block_size(8);
+    channel.set_st_min(Duration::from_micros(300));
+    let network = network_builder.build().unwrap();
+
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert_eq!(secu.transport_channels().len(), 1);
+    assert_eq!(master.transport_channels().len(), 1);
+    let channel = &secu.transport_channels()[0];
+    assert_eq!(channel.name(), "log");
+    assert_eq!(channel.tx_node(), "secu");
+    assert_eq!(channel.rx_node(), "master");
+    assert_eq!(channel.block_size(), 8);
+    assert_eq!(channel.st_min_byte(), 0xF3);
+    assert_eq!(channel.data_message().dlc(), 8);
+    assert_ne!(
+        channel.data_message().id(),
+        channel.flow_control_message().id()
+    );
+    assert!(matches!(
+        channel.data_message().usage(),
+        MessageUsage::TransportData(_)
+    ));
+    assert!(secu
+        .tx_messages()
+        .iter()
+        .any(|m| m.name() == channel.data_message().name()));
+    assert!(master
+        .rx_messages()
+        .iter()
+        .any(|m| m.name() == channel.data_message().name()));
+    assert!(master
+        .tx_messages()
+        .iter()
+        .any(|m| m.name() == channel.flow_control_message().name()));
+}
+
+#[test]
+fn invalid_st_min() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let channel = secu.create_transport_channel("log", "master");
+    channel.set_st_min(Duration::from_millis(200));
+    assert!(network_builder.build().is_err());
+
+    // only whole milliseconds and steps of 100us below 1ms can be encoded.
+    for st_min in [
+        Duration::from_micros(1500),
+        Duration::from_micros(150),
+        Duration::from_nanos(100_500),
+    ] {
+        let network_builder = NetworkBuilder::new();
+        let secu = network_builder.create_node("secu");
+        secu.create_transport_channel("log", "master").set_st_min(st_min);
+        assert!(
+            matches!(network_builder.build(), Err(ConfigError::InvalidRange(_))),
+            "{st_min:?}"
+        );
+    }
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_transport_channel("log", "master").set_st_min(Duration::from_millis(127));
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.transport_channels()[0].st_min_byte(), 127);
+}
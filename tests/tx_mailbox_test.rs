@@ -0,0 +1,17 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn required_tx_mailboxes() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.set_tx_mailbox_count(3);
+    secu.create_object_entry("temperature", "d8<0..100>");
+    secu.create_stream("state").add_entry("temperature");
+    secu.create_command("reset", None);
+    network_builder.create_node("master");
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.tx_mailbox_count(), Some(3));
+    // get_resp, set_resp, heartbeat, stream and command response.
+    assert_eq!(secu.required_tx_mailboxes(), 5);
+}
@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn offsets_desynchronize_streams() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    for name in ["a", "b", "c"] {
+        secu.create_object_entry(name, "u8");
+        let stream = secu.create_stream(&format!("stream_{name}"));
+        stream.add_entry(name);
+        stream.set_interval(Duration::from_millis(10), Duration::from_millis(50));
+    }
+    let network = network_builder.build().unwrap();
+    let secu = network.node_by_name("secu").unwrap();
+    let schedule = secu.tx_schedule();
+
+    // three streams and the heartbeat, get/set responses are sporadic.
+    assert_eq!(schedule.slots().len(), 4);
+    assert_eq!(schedule.cycle(), Duration::from_millis(100));
+    assert_eq!(schedule.max_burst(), 1);
+
+    let mut offsets: Vec<Duration> = schedule
+        .slots()
+        .iter()
+        .filter(|slot| slot.period() == Duration::from_millis(10))
+        .map(|slot| slot.offset())
+        .collect();
+    offsets.sort();
+    offsets.dedup();
+    assert_eq!(offsets.len(), 3);
+
+    let heartbeat = network.heartbeat_message();
+    let slot = schedule.slot_of(heartbeat).unwrap();
+    assert_eq!(slot.period(), Duration::from_millis(100));
+    assert!(slot.offset() < Duration::from_millis(100));
+}
+
+#[test]
+fn sporadic_messages_are_not_scheduled() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let network = network_builder.build().unwrap();
+    let secu = network.node_by_name("secu").unwrap();
+    let scheduled: Vec<&str> = secu
+        .tx_schedule()
+        .slots()
+        .iter()
+        .map(|slot| slot.message().name())
+        .collect();
+    assert_eq!(scheduled, vec!["heartbeat"]);
+}
@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use canzero_config::{builder::NetworkBuilder, config::Type, errors::ConfigError};
+
+#[test]
+fn cyclic_types() {
+    let network_builder = NetworkBuilder::new();
+    let a = network_builder.define_struct("a");
+    a.add_attribute("b", "b").unwrap();
+    let b = network_builder.define_struct("b");
+    b.add_attribute("c", "c").unwrap();
+    let c = network_builder.define_struct("c");
+    c.add_attribute("a", "a").unwrap();
+    c.add_attribute("value", "u8").unwrap();
+    match network_builder.build() {
+        Err(ConfigError::CyclicType(msg)) => assert!(msg.contains("a -> b -> c -> a"), "{msg}"),
+        other => panic!("expected CyclicType, got {other:?}"),
+    }
+}
+
+#[test]
+fn self_referencing_type() {
+    let network_builder = NetworkBuilder::new();
+    let list = network_builder.define_struct("list");
+    list.add_attribute("next", "list").unwrap();
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::CyclicType(_))
+    ));
+}
+
+#[test]
+fn types_declared_before_their_dependencies() {
+    let network_builder = NetworkBuilder::new();
+    // declared in reverse order, every struct depends on the next one.
+    let depth = 1000;
+    for i in 0..depth {
+        let s = network_builder.define_struct(&format!("s{i}"));
+        if i + 1 < depth {
+            s.add_attribute("inner", &format!("s{}", i + 1)).unwrap();
+        } else {
+            s.add_attribute("value", "u8").unwrap();
+        }
+    }
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("root", "s0");
+    let network = network_builder.build().unwrap();
+    let secu = network.node_by_name("secu").unwrap();
+    let names: Vec<String> = secu.types().iter().map(|ty| ty.name()).collect();
+    let position = |name: &str| names.iter().position(|n| n == name).unwrap();
+    for i in 0..depth - 1 {
+        assert!(position(&format!("s{}", i + 1)) < position(&format!("s{i}")));
+    }
+}
+
+#[test]
+fn equal_types_share_their_ref() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let mode = network_builder.define_enum("mode");
+    mode.add_entry("a", None).unwrap();
+    mode.add_entry("b", None).unwrap();
+    let first = network_builder.define_struct("first");
+    first.add_attribute("value", "u16").unwrap();
+    first.add_attribute("mode", "mode").unwrap();
+    let second = network_builder.define_struct("second");
+    second.add_attribute("value", "u16").unwrap();
+    second.add_attribute("mode", "mode").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("first", "first");
+    secu.create_object_entry("second", "second");
+    let network = network_builder.build().unwrap();
+
+    let attribs = |name: &str| match network.type_by_name(name).unwrap() as &Type {
+        Type::Struct { attribs, .. } => attribs.clone(),
+        _ => panic!("{name} is not a struct"),
+    };
+    let (first, second) = (attribs("first"), attribs("second"));
+    assert!(Arc::ptr_eq(&first[0].1, &second[0].1));
+    assert!(Arc::ptr_eq(&first[1].1, &second[1].1));
+    // nodes reference the types of the network.
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    for ty in secu.types() {
+        assert!(network.types().iter().any(|t| Arc::ptr_eq(t, ty)));
+    }
+}
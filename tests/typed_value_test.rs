@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{AttributePadding, Type, TypedValue},
+    errors::ConfigError,
+};
+
+fn type_by_name<'a>(network: &'a canzero_config::config::Network, name: &str) -> &'a Type {
+    network.types().iter().find(|ty| ty.name() == name).unwrap()
+}
+
+#[test]
+fn typed_value_round_trip() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    let status = network_builder.define_struct("status");
+    status.add_attribute("state", "state").unwrap();
+    status
+        .add_attribute_with("temperature", "d8<-10..117.5>", None, AttributePadding::Align(8))
+        .unwrap();
+    status.add_attribute("offset", "i4").unwrap();
+    status.add_attribute("cells", "u4[2]").unwrap();
+    let network = network_builder.build().unwrap();
+    let status = type_by_name(&network, "status");
+
+    let value = TypedValue::Struct(BTreeMap::from([
+        ("state".to_owned(), TypedValue::Enum("running".to_owned())),
+        ("temperature".to_owned(), TypedValue::Decimal(2.5)),
+        ("offset".to_owned(), TypedValue::Signed(-2)),
+        (
+            "cells".to_owned(),
+            TypedValue::Array(vec![TypedValue::Unsigned(3), TypedValue::Unsigned(15)]),
+        ),
+    ]));
+    value.validate(status).unwrap();
+    let raw = value.to_raw(status).unwrap();
+    // state | padding, temperature, offset | cells[0], cells[1]
+    assert_eq!(raw, vec![0b1, 25, 0b0011_1110, 0b1111]);
+    assert_eq!(TypedValue::from_raw(status, &raw).unwrap(), value);
+    assert_eq!(
+        value.to_string(),
+        "{cells: [3, 15], offset: -2, state: running, temperature: 2.5}"
+    );
+    assert!(TypedValue::from_raw(status, &raw[..3]).is_err());
+}
+
+#[test]
+fn typed_value_validation() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("idle", None).unwrap();
+    let status = network_builder.define_struct("status");
+    status.add_attribute("state", "state").unwrap();
+    status.add_attribute("count", "u4").unwrap();
+    let network = network_builder.build().unwrap();
+    let status = type_by_name(&network, "status");
+
+    let value = |state: TypedValue, count: TypedValue| {
+        TypedValue::Struct(BTreeMap::from([
+            ("state".to_owned(), state),
+            ("count".to_owned(), count),
+        ]))
+    };
+    assert!(value(TypedValue::Enum("idle".to_owned()), TypedValue::Unsigned(15))
+        .validate(status)
+        .is_ok());
+    for invalid in [
+        value(TypedValue::Enum("idle".to_owned()), TypedValue::Unsigned(16)),
+        value(TypedValue::Enum("running".to_owned()), TypedValue::Unsigned(1)),
+        value(TypedValue::Enum("idle".to_owned()), TypedValue::Signed(-1)),
+        value(TypedValue::Enum("idle".to_owned()), TypedValue::Decimal(1.0)),
+        TypedValue::Struct(BTreeMap::from([(
+            "state".to_owned(),
+            TypedValue::Enum("idle".to_owned()),
+        )])),
+        TypedValue::Unsigned(0),
+    ] {
+        assert!(matches!(
+            invalid.validate(status),
+            Err(ConfigError::InvalidValue(_))
+        ));
+    }
+}
+
+#[test]
+fn typed_value_union() {
+    let network_builder = NetworkBuilder::new();
+    let command = network_builder.define_enum("command");
+    command.add_entry("set_speed", None).unwrap();
+    command.add_entry("stop", None).unwrap();
+    let payload = network_builder.define_union("command_payload");
+    payload.set_tag("command");
+    payload.add_variant("set_speed", "u8").unwrap();
+    payload.add_variant("stop", "u1").unwrap();
+    let network = network_builder.build().unwrap();
+    let payload = type_by_name(&network, "command_payload");
+
+    let value = TypedValue::Union {
+        variant: "set_speed".to_owned(),
+        value: Box::new(TypedValue::Unsigned(0x80)),
+    };
+    let raw = value.to_raw(payload).unwrap();
+    assert_eq!(raw, vec![0x00, 0x01]);
+    assert_eq!(TypedValue::from_raw(payload, &raw).unwrap(), value);
+    assert_eq!(value.to_string(), "set_speed(128)");
+}
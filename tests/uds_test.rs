@@ -0,0 +1,44 @@
+use canzero_config::{builder::NetworkBuilder, config::UdsService};
+
+#[test]
+fn uds_channels() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_uds_tester("master");
+    let secu = network_builder.create_node("secu");
+    secu.enable_uds(&[
+        UdsService::ReadDataByIdentifier,
+        UdsService::TesterPresent,
+        UdsService::ReadDataByIdentifier,
+    ]);
+    network_builder.create_node("motor");
+    let network = network_builder.build().unwrap();
+
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    let uds = secu.uds().unwrap();
+    assert_eq!(
+        uds.services(),
+        &vec![UdsService::ReadDataByIdentifier, UdsService::TesterPresent]
+    );
+    assert!(uds.supports(UdsService::TesterPresent));
+    assert!(!uds.supports(UdsService::EcuReset));
+    assert_eq!(uds.request_channel().tx_node(), "master");
+    assert_eq!(uds.request_channel().rx_node(), "secu");
+    assert_eq!(uds.response_channel().tx_node(), "secu");
+    assert_eq!(uds.response_channel().rx_node(), "master");
+    assert_eq!(UdsService::ReadDataByIdentifier.response_sid(), 0x62);
+
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert!(master.uds().is_none());
+    assert_eq!(master.transport_channels().len(), 2);
+    let motor = network.nodes().iter().find(|n| n.name() == "motor").unwrap();
+    assert!(motor.uds().is_none());
+    assert!(motor.transport_channels().is_empty());
+}
+
+#[test]
+fn uds_requires_tester() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.enable_uds(&[UdsService::ReadDataByIdentifier]);
+    assert!(network_builder.build().is_err());
+}
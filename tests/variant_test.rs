@@ -0,0 +1,52 @@
+use canzero_config::builder::NetworkBuilder;
+
+fn network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.define_variant("vehicle");
+    network_builder.define_variant("test_bench");
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    secu.create_object_entry("load", "d8<0..100>");
+    let status = secu.create_stream("status");
+    status.add_entry("temperature");
+    let load = secu.create_stream("load");
+    load.add_entry("load");
+    load.0.borrow().message.only_in(&["test_bench"]);
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "status");
+    master.receive_stream("secu", "load");
+    let dyno = network_builder.create_message("dyno_torque", None);
+    dyno.set_std_id(0x50);
+    dyno.make_type_format().add_type("u16", "torque");
+    dyno.add_transmitter("master");
+    dyno.only_in(&["test_bench"]);
+    network_builder
+}
+
+#[test]
+fn variants_filter_messages() {
+    let vehicle = network().build_variant("vehicle").unwrap();
+    assert!(!vehicle.messages().iter().any(|m| m.name() == "dyno_torque"));
+    let secu = vehicle.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.tx_streams().len(), 1);
+    let master = vehicle.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert_eq!(master.rx_streams().len(), 1);
+
+    let test_bench = network().build_variant("test_bench").unwrap();
+    assert!(test_bench.messages().iter().any(|m| m.name() == "dyno_torque"));
+    let secu = test_bench.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert_eq!(secu.tx_streams().len(), 2);
+
+    let all = network().build().unwrap();
+    assert!(all.messages().iter().any(|m| m.name() == "dyno_torque"));
+}
+
+#[test]
+fn undefined_variants() {
+    assert!(network().build_variant("simulation").is_err());
+    let network_builder = network();
+    network_builder
+        .create_message("debug", None)
+        .only_in(&["simulation"]);
+    assert!(network_builder.build().is_err());
+}
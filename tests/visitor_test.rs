@@ -0,0 +1,64 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{
+        encoding::TypeSignalEncoding,
+        signal::Signal,
+        visitor::{walk_encoding, walk_message, walk_node},
+        Message,
+        NetworkRef, NetworkVisitor, Node,
+    },
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    let position = network_builder.define_struct("position");
+    position.add_attribute("x", "d16<0..10>").unwrap();
+    position.add_attribute("y", "d16<0..10>").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "position");
+    let stream = secu.create_stream("position");
+    stream.add_entry("position");
+    network_builder.create_node("master");
+    network_builder.build().unwrap()
+}
+
+#[derive(Default)]
+struct Counter {
+    signals: usize,
+    composites: usize,
+    stream_messages: usize,
+    nodes: Vec<String>,
+}
+
+impl NetworkVisitor for Counter {
+    fn visit_message(&mut self, message: &Message) {
+        if message.name() == "secu_stream_position" {
+            self.stream_messages += 1;
+            walk_message(self, message);
+        }
+    }
+    fn visit_encoding(&mut self, encoding: &TypeSignalEncoding) {
+        if let TypeSignalEncoding::Composite(_) = encoding {
+            self.composites += 1;
+        }
+        walk_encoding(self, encoding);
+    }
+    fn visit_signal(&mut self, _signal: &Signal) {
+        self.signals += 1;
+    }
+    fn visit_node(&mut self, node: &Node) {
+        self.nodes.push(node.name().to_owned());
+        walk_node(self, node);
+    }
+}
+
+#[test]
+fn visit_network() {
+    let network = network();
+    let mut counter = Counter::default();
+    counter.visit_network(&network);
+    assert_eq!(counter.nodes, vec!["secu", "master"]);
+    assert_eq!(counter.stream_messages, 1);
+    assert_eq!(counter.composites, 1);
+    assert_eq!(counter.signals, 2);
+}
@@ -0,0 +1,54 @@
+use canzero_config::builder::NetworkBuilder;
+
+fn base(extra_signal: bool, state_entries: &[&str]) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("pod_state");
+    for entry in state_entries {
+        state.add_entry(entry, None).unwrap();
+    }
+    let status = network_builder.create_message("status", None);
+    status.set_std_id(0x10);
+    let format = status.make_type_format();
+    format.add_type("pod_state", "state");
+    if extra_signal {
+        format.add_type("u8", "error_code");
+    }
+    status.add_transmitter("master");
+    network_builder
+}
+
+#[test]
+fn identical_networks_are_compatible() {
+    let old = base(false, &["Idle", "Levitating"]).build().unwrap();
+    let new = base(false, &["Idle", "Levitating"]).build().unwrap();
+    let report = new.is_wire_compatible(&old);
+    assert!(report.is_compatible(), "{report}");
+    assert!(report.changes().is_empty(), "{report}");
+}
+
+#[test]
+fn appending_is_additive() {
+    let old = base(false, &["Idle", "Levitating", "Braking"]).build().unwrap();
+    let new = base(true, &["Idle", "Levitating", "Braking", "Stopped"])
+        .build()
+        .unwrap();
+    let report = new.is_wire_compatible(&old);
+    assert!(report.is_compatible(), "{report}");
+    assert!(report.additive_changes().count() >= 2, "{report}");
+}
+
+#[test]
+fn renumbering_enum_is_breaking() {
+    let old = base(false, &["Idle", "Levitating"]).build().unwrap();
+    let new = base(false, &["Levitating", "Idle"]).build().unwrap();
+    let report = new.is_wire_compatible(&old);
+    assert!(!report.is_compatible(), "{report}");
+}
+
+#[test]
+fn removing_signal_is_breaking() {
+    let old = base(true, &["Idle"]).build().unwrap();
+    let new = base(false, &["Idle"]).build().unwrap();
+    let report = new.is_wire_compatible(&old);
+    assert!(!report.is_compatible(), "{report}");
+}
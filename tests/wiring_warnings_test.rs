@@ -0,0 +1,48 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn wiring_warnings() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d8<0..100>");
+    secu.create_stream("state").add_entry("temperature");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "state");
+    let reset = secu.create_command("reset", None);
+    reset.add_callee("master");
+    let orphan = network_builder.create_message("orphan", None);
+    orphan.set_std_id(0x50);
+    orphan.make_type_format().add_type("u8", "value");
+    orphan.add_transmitter("secu");
+    let unsent = network_builder.create_message("unsent", None);
+    unsent.set_std_id(0x51);
+    unsent.make_type_format().add_type("u8", "value");
+    unsent.add_receiver("master");
+
+    let network = network_builder.build().unwrap();
+    let warnings = network.wiring_warnings();
+    assert!(warnings.contains(&"message orphan isn't received by any node".to_owned()));
+    assert!(warnings.contains(&"message unsent isn't transmitted by any node".to_owned()));
+    assert!(!warnings.iter().any(|w| w.contains("secu_stream_state")));
+    assert!(!warnings.iter().any(|w| w.contains("command")));
+    assert!(!warnings.iter().any(|w| w.contains("get_req") || w.contains("heartbeat")));
+    assert_eq!(warnings.len(), 2, "{warnings:?}");
+}
+
+#[test]
+fn unreceived_command_call() {
+    let network_builder = NetworkBuilder::new();
+    let secu = network_builder.create_node("secu");
+    let reset = secu.create_command("reset", None);
+    reset.add_callee("master");
+    // drop the call message from the rx messages of the command's node.
+    secu.0
+        .borrow_mut()
+        .rx_messages
+        .retain(|m| m.name() != "secu_reset_command_req");
+    let network = network_builder.build().unwrap();
+    assert!(network.wiring_warnings().contains(
+        &"call message secu_reset_command_req of command secu::reset isn't received by secu"
+            .to_owned()
+    ));
+}
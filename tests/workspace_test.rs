@@ -0,0 +1,64 @@
+use canzero_config::{
+    builder::{TypeLibrary, WorkspaceBuilder},
+    errors::ConfigError,
+};
+
+fn workspace(charger_position: &str) -> WorkspaceBuilder {
+    let workspace = WorkspaceBuilder::new();
+    let vehicle = workspace.create_network("vehicle");
+    workspace.add_type_library(TypeLibrary::new(|network_builder| {
+        let state = network_builder.define_enum("state");
+        state.add_entry("idle", None).unwrap();
+        state.add_entry("charging", None).unwrap();
+    }));
+    let charger = workspace.create_network("charger");
+
+    let bms = vehicle.create_node("bms");
+    bms.create_object_entry("state", "state");
+    bms.create_object_entry("position", "u16");
+    vehicle.create_node("secu").create_object_entry("state", "state");
+
+    let bms = charger.create_node("bms");
+    bms.create_object_entry("state", "state");
+    bms.create_object_entry("position", charger_position);
+    workspace
+}
+
+#[test]
+fn shared_type_library() {
+    let workspace = workspace("u16").build().unwrap();
+    assert_eq!(workspace.networks().len(), 2);
+    let vehicle = workspace.network("vehicle").unwrap();
+    let charger = workspace.network("charger").unwrap();
+    assert!(vehicle.types().iter().any(|ty| ty.name() == "state"));
+    assert!(charger.types().iter().any(|ty| ty.name() == "state"));
+
+    let instances = workspace.node_instances("bms");
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[0].0, "vehicle");
+    assert_eq!(instances[1].0, "charger");
+    assert_eq!(workspace.node_instances("secu").len(), 1);
+}
+
+#[test]
+fn inconsistent_shared_node() {
+    match workspace("u8").build() {
+        Err(ConfigError::InvalidWorkspace(reason)) => {
+            assert!(reason.contains("bms"));
+            assert!(reason.contains("position"));
+        }
+        other => panic!("expected InvalidWorkspace, got {other:?}"),
+    }
+}
+
+#[test]
+fn create_network_is_idempotent() {
+    let workspace = WorkspaceBuilder::new();
+    workspace.add_type_library(TypeLibrary::new(|network_builder| {
+        network_builder.define_enum("state").add_entry("idle", None).unwrap();
+    }));
+    workspace.create_network("vehicle");
+    workspace.create_network("vehicle");
+    let workspace = workspace.build().unwrap();
+    assert_eq!(workspace.networks().len(), 1);
+}